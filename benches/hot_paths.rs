@@ -0,0 +1,156 @@
+//! Mesures des chemins chauds, pour comparer avant et après un refactor motivé par les
+//! performances (moteur de template, stockage des métriques) :
+//!
+//! ```bash
+//! cargo bench --bench hot_paths
+//! cargo bench --bench hot_paths -- status_page   # un seul groupe
+//! ```
+
+use axum::response::IntoResponse;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use template_axum_sqlx_api::{
+    config::Config,
+    handlers::{
+        response::ApiResponse,
+        status::{generate_history_bars, generate_network_history_bars, render_status_page, StatusPageContent},
+    },
+    models::{
+        status::{
+            calculate_cpu_score, calculate_error_penalty, calculate_memory_score, calculate_network_score,
+            calculate_performance_score, HistoryEntry, NetworkUsage, PerformanceMetrics, RequestCounts,
+        },
+        user::User,
+    },
+};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+}
+
+fn users(count: i64) -> Vec<User> {
+    (1..=count)
+        .map(|id| User {
+            id: id.into(),
+            email: format!("user{}@example.com", id),
+            name: format!("User {}", id),
+            created_at: now(),
+            updated_at: now(),
+        })
+        .collect()
+}
+
+fn metrics() -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: now(),
+        health_score: 88,
+        cpu_score: 22,
+        memory_score: 24,
+        perf_score: 20,
+        network_score: 22,
+        network: Some(NetworkUsage { rx_bytes_per_sec: 250_000, tx_bytes_per_sec: 125_000, errors: 0, load_percent: 12.5 }),
+        requests: Some(RequestCounts { success: 980, redirection: 5, client_errors: 10, server_errors: 5 }),
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 42.0,
+        system_load: 0.4,
+        cpu_usage: 35.0,
+        cpu_count: 8,
+        memory_usage_percent: 48.0,
+        memory_used_mb: 7680,
+        memory_total_mb: 16000,
+        disk_usage_percent: 61.0,
+        uptime: 86400,
+        response_time_ms: 42,
+        db_connected: true,
+        db_response_time_ms: Some(4),
+        status: "healthy".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+/// Historique d'une mesure par minute, avec quelques pannes de base de données
+fn history(entries: i64) -> Vec<HistoryEntry> {
+    (0..entries)
+        .map(|minute| {
+            let db_connected = minute % 17 != 0;
+            HistoryEntry {
+                timestamp: now() - Duration::minutes(minute),
+                response_time_ms: 20 + (minute as u64 * 37) % 900,
+                db_connected,
+                db_response_time_ms: db_connected.then_some(3),
+                status: if db_connected { "healthy" } else { "unhealthy" }.to_string(),
+                issues: if db_connected { Vec::new() } else { vec!["Database unreachable".to_string()] },
+                network: Some(NetworkUsage {
+                    rx_bytes_per_sec: 10_000 * minute as u64,
+                    tx_bytes_per_sec: 50_000,
+                    errors: 0,
+                    load_percent: (minute % 100) as f32,
+                }),
+            }
+        })
+        .collect()
+}
+
+fn api_response(c: &mut Criterion) {
+    let mut group = c.benchmark_group("api_response");
+    for count in [1, 50, 500] {
+        let data = users(count);
+        // Réponse complète : métadonnées horodatées puis corps JSON
+        group.bench_with_input(BenchmarkId::new("into_response", count), &data, |b, data| {
+            b.iter(|| ApiResponse::ok(data.clone()).into_response())
+        });
+        group.bench_with_input(BenchmarkId::new("to_vec", count), &data, |b, data| {
+            let response = ApiResponse::ok(data.clone());
+            b.iter(|| serde_json::to_vec(black_box(&response)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn status_page(c: &mut Criterion) {
+    let config = Config::default();
+    let mut group = c.benchmark_group("status_page");
+    let starting = StatusPageContent { now: now(), ..StatusPageContent::default() };
+    group.bench_function("starting", |b| b.iter(|| render_status_page(black_box(&starting), &config)));
+    let running = StatusPageContent { now: now(), metrics: Some(metrics()), history: history(50), ..StatusPageContent::default() };
+    group.bench_function("running", |b| b.iter(|| render_status_page(black_box(&running), &config)));
+    group.finish();
+}
+
+fn history_bars(c: &mut Criterion) {
+    let mut group = c.benchmark_group("history_bars");
+    for entries in [50, 500] {
+        let history = history(entries);
+        group.bench_with_input(BenchmarkId::new("api", entries), &history, |b, history| {
+            b.iter(|| generate_history_bars(black_box(history), "api"))
+        });
+        group.bench_with_input(BenchmarkId::new("database", entries), &history, |b, history| {
+            b.iter(|| generate_history_bars(black_box(history), "database"))
+        });
+        group.bench_with_input(BenchmarkId::new("network", entries), &history, |b, history| {
+            b.iter(|| generate_network_history_bars(black_box(history)))
+        });
+    }
+    group.finish();
+}
+
+fn scoring(c: &mut Criterion) {
+    let metrics = metrics();
+    let network = metrics.network.unwrap();
+    let requests = metrics.requests.unwrap();
+    c.bench_function("scoring/health_score", |b| {
+        b.iter(|| {
+            let metrics = black_box(&metrics);
+            (calculate_cpu_score(metrics.cpu_usage)
+                + calculate_memory_score(metrics.memory_usage_percent)
+                + calculate_performance_score(metrics.response_time_ms)
+                + calculate_network_score(&network))
+            .saturating_sub(calculate_error_penalty(&requests))
+        })
+    });
+}
+
+criterion_group!(benches, api_response, status_page, history_bars, scoring);
+criterion_main!(benches);
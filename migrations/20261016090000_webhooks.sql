@@ -0,0 +1,39 @@
+-- Outbound webhooks: subscriptions, pending deliveries and attempt log
+
+create table if not exists webhook_subscriptions (
+    id serial primary key,
+    url text not null,
+    secret text not null,
+    -- empty array = every event type
+    event_types text[] not null default '{}',
+    active boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create table if not exists webhook_deliveries (
+    id bigserial primary key,
+    subscription_id integer not null references webhook_subscriptions(id) on delete cascade,
+    event_type varchar(255) not null,
+    payload jsonb not null,
+    -- pending | delivered | dead
+    status varchar(32) not null default 'pending',
+    attempts integer not null default 0,
+    next_attempt_at timestamptz not null default now(),
+    last_error text,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index if not exists webhook_deliveries_due_idx
+    on webhook_deliveries (next_attempt_at)
+    where status = 'pending';
+
+create table if not exists webhook_delivery_attempts (
+    id bigserial primary key,
+    delivery_id bigint not null references webhook_deliveries(id) on delete cascade,
+    status_code integer,
+    error text,
+    duration_ms bigint not null,
+    attempted_at timestamptz not null default now()
+);
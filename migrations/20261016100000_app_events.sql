@@ -0,0 +1,12 @@
+-- Application event timeline (deploys, maintenance, incidents, config reloads, fixture runs)
+
+create table if not exists app_events (
+    id bigserial primary key,
+    kind varchar(64) not null,
+    message text not null,
+    details jsonb not null default '{}',
+    occurred_at timestamptz not null default now()
+);
+
+create index if not exists app_events_occurred_at_idx on app_events (occurred_at desc);
+create index if not exists app_events_kind_idx on app_events (kind, occurred_at desc);
@@ -0,0 +1,8 @@
+-- CORS origins registered at runtime (e.g. customer domains in a multi-tenant setup)
+
+create table if not exists cors_origins (
+    id bigserial primary key,
+    origin varchar(255) not null unique,
+    tenant varchar(128),
+    created_at timestamptz not null default now()
+);
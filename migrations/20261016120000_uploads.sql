@@ -0,0 +1,11 @@
+-- Metadata of uploaded files (content lives in the configured storage)
+
+create table if not exists uploads (
+    id uuid primary key,
+    filename varchar(255) not null,
+    content_type varchar(255) not null,
+    size_bytes bigint not null,
+    sha256 char(64) not null,
+    storage_key varchar(255) not null,
+    created_at timestamptz not null default now()
+);
@@ -0,0 +1,9 @@
+-- Reference resource: application users
+
+create table if not exists users (
+    id bigserial primary key,
+    email varchar(255) not null unique,
+    name varchar(255) not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
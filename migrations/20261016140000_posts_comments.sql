@@ -0,0 +1,25 @@
+-- Reference relations: users write posts, posts receive comments.
+-- Deleting a user deletes their posts (and, through them, the comments on those posts);
+-- comments written by a deleted user are kept without author.
+
+create table if not exists posts (
+    id bigserial primary key,
+    user_id bigint not null references users (id) on delete cascade,
+    title varchar(255) not null,
+    body text not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index if not exists posts_user_id_idx on posts (user_id);
+
+create table if not exists comments (
+    id bigserial primary key,
+    post_id bigint not null references posts (id) on delete cascade,
+    user_id bigint references users (id) on delete set null,
+    body text not null,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists comments_post_id_idx on comments (post_id);
+create index if not exists comments_user_id_idx on comments (user_id);
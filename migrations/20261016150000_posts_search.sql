@@ -0,0 +1,11 @@
+-- Full-text search on posts: a generated tsvector (title weighted above body)
+-- kept up to date by Postgres, and the GIN index used by `search @@ query`.
+
+alter table posts
+    add column if not exists search tsvector
+    generated always as (
+        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(body, '')), 'B')
+    ) stored;
+
+create index if not exists posts_search_idx on posts using gin (search);
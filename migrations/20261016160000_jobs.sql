@@ -0,0 +1,21 @@
+-- Background jobs submitted by handlers (202 Accepted + status polling on /api/jobs/{id})
+
+create table if not exists jobs (
+    id uuid primary key,
+    kind varchar(255) not null,
+    payload jsonb not null default '{}',
+    -- queued | running | succeeded | failed
+    status varchar(32) not null default 'queued',
+    result jsonb,
+    error text,
+    attempts integer not null default 0,
+    -- next run for queued jobs, lease expiry for running ones
+    run_at timestamptz not null default now(),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    finished_at timestamptz
+);
+
+create index if not exists jobs_due_idx
+    on jobs (run_at)
+    where status in ('queued', 'running');
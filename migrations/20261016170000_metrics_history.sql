@@ -0,0 +1,13 @@
+-- Metrics history written by the background metrics task (status page and /api/status/history)
+
+create table if not exists metrics_history (
+    id bigserial primary key,
+    recorded_at timestamptz not null default now(),
+    response_time_ms bigint not null,
+    db_connected boolean not null,
+    db_response_time_ms bigint,
+    status varchar(64) not null,
+    issues text[] not null default '{}'
+);
+
+create index if not exists metrics_history_recorded_at_idx on metrics_history (recorded_at desc);
@@ -0,0 +1,7 @@
+-- Network traffic measured by the background metrics task (null on older entries)
+
+alter table metrics_history
+    add column if not exists network_rx_bytes_per_sec bigint,
+    add column if not exists network_tx_bytes_per_sec bigint,
+    add column if not exists network_errors bigint,
+    add column if not exists network_load_percent real;
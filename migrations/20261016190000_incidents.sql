@@ -0,0 +1,25 @@
+-- Incidents communicated on the status page, with their timeline of updates.
+-- Deleting an incident deletes its updates.
+
+create table if not exists incidents (
+    id bigserial primary key,
+    title varchar(255) not null,
+    severity varchar(16) not null check (severity in ('minor', 'major', 'critical')),
+    status varchar(16) not null default 'investigating'
+        check (status in ('investigating', 'identified', 'monitoring', 'resolved')),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    resolved_at timestamptz
+);
+
+create index if not exists incidents_open_idx on incidents (created_at desc) where resolved_at is null;
+
+create table if not exists incident_updates (
+    id bigserial primary key,
+    incident_id bigint not null references incidents (id) on delete cascade,
+    status varchar(16) not null,
+    message text not null,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists incident_updates_incident_id_idx on incident_updates (incident_id, created_at);
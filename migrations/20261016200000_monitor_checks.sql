@@ -0,0 +1,13 @@
+-- Probe results of the external dependencies listed in [[monitoring.targets]]
+
+create table if not exists monitor_checks (
+    id bigserial primary key,
+    target varchar(255) not null,
+    checked_at timestamptz not null default now(),
+    up boolean not null,
+    response_time_ms bigint,
+    error text
+);
+
+create index if not exists idx_monitor_checks_target_checked_at on monitor_checks (target, checked_at desc);
+create index if not exists idx_monitor_checks_checked_at on monitor_checks (checked_at);
@@ -0,0 +1,14 @@
+-- Scheduled maintenance windows: alerts are muted and the status page shows a
+-- "planned maintenance" banner while a window is active.
+
+create table if not exists maintenance_windows (
+    id bigserial primary key,
+    title varchar(255) not null,
+    description text not null default '',
+    starts_at timestamptz not null,
+    ends_at timestamptz not null,
+    created_at timestamptz not null default now(),
+    check (ends_at > starts_at)
+);
+
+create index if not exists maintenance_windows_ends_at_idx on maintenance_windows (ends_at);
@@ -0,0 +1,8 @@
+-- Last metrics computed by the background task, restored at startup so the status
+-- page shows real data before the first pass
+
+create table if not exists metrics_snapshot (
+    id smallint primary key default 1 check (id = 1),
+    metrics jsonb not null,
+    saved_at timestamptz not null default now()
+);
@@ -0,0 +1,21 @@
+-- Authentication principals: users with a hashed password, a role and API keys.
+-- Passwords are argon2 PHC strings; API keys are stored as their SHA-256 hex digest,
+-- the plaintext key being shown only once, when it is issued.
+
+create table if not exists user_credentials (
+    user_id bigint primary key references users (id) on delete cascade,
+    password_hash text not null,
+    role varchar(32) not null default 'user',
+    updated_at timestamptz not null default now()
+);
+
+create table if not exists api_keys (
+    id bigserial primary key,
+    user_id bigint not null references users (id) on delete cascade,
+    name varchar(255) not null,
+    key_hash char(64) not null unique,
+    created_at timestamptz not null default now(),
+    expires_at timestamptz
+);
+
+create index if not exists api_keys_user_id_idx on api_keys (user_id);
@@ -0,0 +1,7 @@
+-- Dead-letter state for jobs: retryable failures that exhausted max_attempts end up
+-- with status = 'dead' until an administrator replays them (POST /api/admin/jobs/{id}/retry).
+-- Statuses: queued | running | succeeded | failed (permanent error) | dead
+
+create index if not exists jobs_dead_idx
+    on jobs (updated_at desc)
+    where status = 'dead';
@@ -0,0 +1,137 @@
+//! # Allocator Module
+//!
+//! Ce module installe l'allocateur global choisi par feature et lit ses statistiques,
+//! pour diagnostiquer une croissance mémoire sur un déploiement de longue durée :
+//! - `jemalloc` : mémoire résidente, active et allouée (`stats.*`), profils de tas
+//!   (`prof.dump`) si le profilage est activé au démarrage (`_RJEM_MALLOC_CONF=prof:true`)
+//! - `mimalloc` : mémoire résidente et engagée du processus (`mi_process_info`)
+//!
+//! Sans feature, l'allocateur système est utilisé et aucune statistique n'est disponible.
+//! Si les deux features sont activées, jemalloc l'emporte.
+//! Les statistiques sont exposées par `GET /api/admin/allocator`.
+
+use thiserror::Error;
+
+use crate::models::help::AllocatorStats;
+
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+#[cfg(all(feature = "mimalloc", not(feature = "jemalloc")))]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+/// Erreurs de la génération d'un profil de tas
+#[derive(Debug, Error)]
+pub enum HeapProfileError {
+    /// L'allocateur ne sait pas produire de profil, ou le profilage n'a pas été activé au démarrage
+    #[error("{0}")]
+    Unavailable(&'static str),
+    /// Échec de l'écriture ou de la lecture du profil
+    #[error("heap profile failed: {0}")]
+    Failed(String),
+}
+
+/// Statistiques de l'allocateur global
+#[cfg(feature = "jemalloc")]
+pub fn stats() -> AllocatorStats {
+    use tikv_jemalloc_ctl::{epoch, stats};
+
+    // Les statistiques sont mises en cache par jemalloc jusqu'au prochain epoch
+    let _ = epoch::advance();
+    let resident = stats::resident::read().ok().map(|bytes| bytes as u64);
+    let allocated = stats::allocated::read().ok().map(|bytes| bytes as u64);
+
+    AllocatorStats {
+        allocator: "jemalloc".to_string(),
+        resident_bytes: resident,
+        peak_resident_bytes: None,
+        active_bytes: stats::active::read().ok().map(|bytes| bytes as u64),
+        allocated_bytes: allocated,
+        fragmentation_percent: resident.zip(allocated).and_then(|(resident, allocated)| fragmentation_percent(resident, allocated)),
+        heap_profiling: heap_profiling_enabled(),
+    }
+}
+
+/// Statistiques de l'allocateur global
+#[cfg(all(feature = "mimalloc", not(feature = "jemalloc")))]
+pub fn stats() -> AllocatorStats {
+    let (mut elapsed, mut user, mut system) = (0, 0, 0);
+    let (mut rss, mut peak_rss, mut commit, mut peak_commit, mut page_faults) = (0, 0, 0, 0, 0);
+    // SAFETY: chaque pointeur désigne une variable locale valide pendant l'appel
+    unsafe {
+        libmimalloc_sys::mi_process_info(
+            &mut elapsed,
+            &mut user,
+            &mut system,
+            &mut rss,
+            &mut peak_rss,
+            &mut commit,
+            &mut peak_commit,
+            &mut page_faults,
+        );
+    }
+
+    AllocatorStats {
+        allocator: "mimalloc".to_string(),
+        resident_bytes: Some(rss as u64),
+        peak_resident_bytes: Some(peak_rss as u64),
+        active_bytes: Some(commit as u64),
+        allocated_bytes: None,
+        fragmentation_percent: None,
+        heap_profiling: false,
+    }
+}
+
+/// Statistiques de l'allocateur global
+#[cfg(not(any(feature = "jemalloc", feature = "mimalloc")))]
+pub fn stats() -> AllocatorStats {
+    AllocatorStats {
+        allocator: "system".to_string(),
+        resident_bytes: None,
+        peak_resident_bytes: None,
+        active_bytes: None,
+        allocated_bytes: None,
+        fragmentation_percent: None,
+        heap_profiling: false,
+    }
+}
+
+/// Part de la mémoire résidente qui n'est pas allouée par l'application
+pub fn fragmentation_percent(resident: u64, allocated: u64) -> Option<f64> {
+    (resident > 0).then(|| resident.saturating_sub(allocated) as f64 / resident as f64 * 100.0)
+}
+
+/// Le profilage de tas a été activé au démarrage (`opt.prof`)
+#[cfg(feature = "jemalloc")]
+fn heap_profiling_enabled() -> bool {
+    tikv_jemalloc_ctl::profiling::prof::read().unwrap_or(false)
+}
+
+/// Écrit un profil de tas (format jeprof) et renvoie son contenu
+#[cfg(feature = "jemalloc")]
+pub fn heap_profile() -> Result<Vec<u8>, HeapProfileError> {
+    use std::ffi::CString;
+
+    if !heap_profiling_enabled() {
+        return Err(HeapProfileError::Unavailable("heap profiling is disabled; start the server with _RJEM_MALLOC_CONF=prof:true"));
+    }
+
+    let path = std::env::temp_dir().join(format!("heap-{}-{}.prof", std::process::id(), uuid::Uuid::new_v4()));
+    let c_path = CString::new(path.to_string_lossy().into_owned()).map_err(|e| HeapProfileError::Failed(e.to_string()))?;
+    // SAFETY: `prof.dump` attend un pointeur vers un chemin terminé par un octet nul,
+    // valide pendant l'appel
+    unsafe { tikv_jemalloc_ctl::raw::write(b"prof.dump\0", c_path.as_ptr()) }
+        .map_err(|e| HeapProfileError::Failed(e.to_string()))?;
+
+    let profile = std::fs::read(&path).map_err(|e| HeapProfileError::Failed(e.to_string()));
+    let _ = std::fs::remove_file(&path);
+    profile
+}
+
+/// Écrit un profil de tas (format jeprof) et renvoie son contenu
+#[cfg(not(feature = "jemalloc"))]
+pub fn heap_profile() -> Result<Vec<u8>, HeapProfileError> {
+    Err(HeapProfileError::Unavailable("heap profiles require the `jemalloc` feature"))
+}
@@ -0,0 +1,247 @@
+//! # Cache Module
+//!
+//! Ce module fournit des caches en mémoire typés (moka), pour que les handlers et les
+//! services gardent leurs lectures coûteuses sans variable statique ad hoc :
+//! - chaque cache appartient à un espace de noms, configuré sous `[cache.namespaces.<nom>]`
+//!   (capacité, durée de vie depuis l'écriture et depuis le dernier accès)
+//! - `get_or_compute` lit une valeur ou la calcule ; les calculs concurrents d'une même
+//!   clé sont regroupés, et `try_get_or_compute` ne conserve que les succès
+//! - `invalidate` / `invalidate_all` retirent les entrées après une écriture
+//! - les lectures réussies et manquées de chaque espace sont relevées par la tâche des
+//!   métriques (`PerformanceMetrics::caches`, dans `/api/status`)
+//!
+//! Le registre est propre à chaque instance (`AppState::caches`) ; pour un cache partagé
+//! entre instances, voir le module `redis`.
+//!
+//! ```ignore
+//! let users = state.caches.namespace::<i64, User>("users");
+//! let user = users.try_get_or_compute(id, user::get_user(pool, id)).await?;
+//! // Après une modification :
+//! users.invalidate(&id).await;
+//! ```
+
+use async_trait::async_trait;
+use moka::future::Cache;
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use crate::{
+    config::{CacheConfig, CacheNamespaceConfig},
+    models::status::CacheStats,
+};
+
+/// Lectures réussies et manquées d'un espace de noms
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Counters {
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Cache typé d'un espace de noms.
+///
+/// Le clone est peu coûteux : tous les clones partagent les mêmes entrées et compteurs.
+pub struct TypedCache<K, V> {
+    name: String,
+    entries: Cache<K, V>,
+    counters: Arc<Counters>,
+}
+
+impl<K, V> Clone for TypedCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            entries: self.entries.clone(),
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+impl<K, V> TypedCache<K, V>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn new(name: &str, config: &CacheNamespaceConfig) -> Self {
+        let mut builder = Cache::builder().name(name).max_capacity(config.max_entries);
+        if config.ttl_seconds > 0 {
+            builder = builder.time_to_live(Duration::from_secs(config.ttl_seconds));
+        }
+        if config.tti_seconds > 0 {
+            builder = builder.time_to_idle(Duration::from_secs(config.tti_seconds));
+        }
+
+        Self {
+            name: name.to_string(),
+            entries: builder.build(),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// Espace de noms du cache
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Lit une valeur ; `None` si elle est absente ou a expiré.
+    pub async fn get(&self, key: &K) -> Option<V> {
+        let value = self.entries.get(key).await;
+        self.counters.record(value.is_some());
+        value
+    }
+
+    /// Écrit une valeur, qui remplace la précédente.
+    pub async fn insert(&self, key: K, value: V) {
+        self.entries.insert(key, value).await;
+    }
+
+    /// Lit une valeur ou la calcule avec `compute`, qui n'est attendu qu'en son absence.
+    ///
+    /// Les appels concurrents sur une même clé absente attendent un seul calcul.
+    pub async fn get_or_compute(&self, key: K, compute: impl Future<Output = V>) -> V {
+        let entry = self.entries.entry(key).or_insert_with(compute).await;
+        // Une entrée « fraîche » vient d'être calculée par cet appel
+        self.counters.record(!entry.is_fresh());
+        entry.into_value()
+    }
+
+    /// Comme `get_or_compute`, pour un calcul faillible : seul un succès est conservé et
+    /// l'erreur est rendue telle quelle. Les calculs concurrents ne sont pas regroupés.
+    pub async fn try_get_or_compute<E>(&self, key: K, compute: impl Future<Output = Result<V, E>>) -> Result<V, E> {
+        if let Some(value) = self.get(&key).await {
+            return Ok(value);
+        }
+        let value = compute.await?;
+        self.entries.insert(key, value.clone()).await;
+        Ok(value)
+    }
+
+    /// Retire une entrée.
+    pub async fn invalidate(&self, key: &K) {
+        self.entries.invalidate(key).await;
+    }
+
+    /// Retire toutes les entrées de l'espace de noms.
+    pub fn invalidate_all(&self) {
+        self.entries.invalidate_all();
+    }
+
+    /// Entrées présentes et lectures depuis la création du cache
+    pub async fn stats(&self) -> CacheStats {
+        // Les évictions et expirations sont appliquées en différé
+        self.entries.run_pending_tasks().await;
+        CacheStats {
+            namespace: self.name.clone(),
+            entries: self.entries.entry_count(),
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Opérations d'un cache indépendantes des types de clé et de valeur
+#[async_trait]
+trait Namespace: Send + Sync {
+    async fn stats(&self) -> CacheStats;
+    fn invalidate_all(&self);
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[async_trait]
+impl<K, V> Namespace for TypedCache<K, V>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    async fn stats(&self) -> CacheStats {
+        TypedCache::stats(self).await
+    }
+
+    fn invalidate_all(&self) {
+        TypedCache::invalidate_all(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Registre des caches typés, par espace de noms.
+pub struct CacheRegistry {
+    config: HashMap<String, CacheNamespaceConfig>,
+    namespaces: Mutex<BTreeMap<String, Arc<dyn Namespace>>>,
+}
+
+impl CacheRegistry {
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            config: config.namespaces.clone(),
+            namespaces: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Cache de l'espace de noms `name`, créé au premier appel selon `[cache.namespaces.<name>]`.
+    ///
+    /// # Panics
+    ///
+    /// Si l'espace de noms existe déjà avec d'autres types de clé ou de valeur.
+    pub fn namespace<K, V>(&self, name: &str) -> TypedCache<K, V>
+    where
+        K: Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        let mut namespaces = self.namespaces.lock().unwrap();
+        if let Some(existing) = namespaces.get(name) {
+            return existing
+                .as_any()
+                .downcast_ref::<TypedCache<K, V>>()
+                .unwrap_or_else(|| panic!("cache namespace {} already exists with other key or value types", name))
+                .clone();
+        }
+
+        let cache = TypedCache::new(name, &self.config.get(name).cloned().unwrap_or_default());
+        namespaces.insert(name.to_string(), Arc::new(cache.clone()));
+        cache
+    }
+
+    /// Espaces de noms créés, triés
+    pub fn names(&self) -> Vec<String> {
+        self.namespaces.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Retire toutes les entrées d'un espace de noms ; retourne `false` s'il n'existe pas.
+    pub fn invalidate_all(&self, name: &str) -> bool {
+        match self.namespaces.lock().unwrap().get(name) {
+            Some(namespace) => {
+                namespace.invalidate_all();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Utilisation de chaque espace de noms depuis sa création, triée par nom
+    pub async fn stats(&self) -> Vec<CacheStats> {
+        let namespaces: Vec<Arc<dyn Namespace>> = self.namespaces.lock().unwrap().values().cloned().collect();
+        let mut stats = Vec::with_capacity(namespaces.len());
+        for namespace in namespaces {
+            stats.push(namespace.stats().await);
+        }
+        stats
+    }
+}
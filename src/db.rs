@@ -6,7 +6,12 @@
 
 use crate::config::Config;
 use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::str::FromStr;
+
+/// Migrations de `migrations/`, embarquées à la compilation
+pub static MIGRATOR: Migrator = sqlx::migrate!();
 
 /// Gestionnaire de base de données.
 ///
@@ -34,12 +39,18 @@ impl DatabaseManager {
         Self { pool: None }
     }
 
+    /// Crée une instance à partir d'un pool déjà connecté (tests, outils).
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self { pool: Some(pool) }
+    }
+
     /// Établit la connexion à la base de données.
     ///
     /// Cette méthode :
     /// 1. Utilise la configuration de l'application
-    /// 2. Crée un pool de connexions
-    /// 3. Stocke le pool dans l'instance
+    /// 2. Valide et applique les options TLS (sslmode, certificats)
+    /// 3. Crée un pool de connexions
+    /// 4. Stocke le pool dans l'instance
     ///
     /// # Arguments
     ///
@@ -49,10 +60,12 @@ impl DatabaseManager {
     ///
     /// * `Result<(), sqlx::Error>` - Succès ou erreur de connexion
     pub async fn connect(&mut self, config: &Config) -> Result<(), sqlx::Error> {
+        let options = Self::connect_options(config)?;
+
         let pool = PgPoolOptions::new()
             .max_connections(config.database.max_connections)
             .min_connections(config.database.min_connections)
-            .connect(&config.database.url)
+            .connect_with(options)
             .await?;
 
         self.pool = Some(pool);
@@ -60,6 +73,39 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Construit les options de connexion à partir de l'URL et des options TLS.
+    pub fn connect_options(config: &Config) -> Result<PgConnectOptions, sqlx::Error> {
+        let database = &config.database;
+        database
+            .validate()
+            .map_err(|e| sqlx::Error::Configuration(format!("invalid database TLS configuration: {}", e).into()))?;
+
+        let mut options = PgConnectOptions::from_str(&database.url)?;
+        if let Some(mode) = database.sslmode {
+            options = options.ssl_mode(mode.into());
+        }
+        if let Some(root_cert) = &database.ssl_root_cert {
+            options = options.ssl_root_cert(root_cert);
+        }
+        if let (Some(cert), Some(key)) = (&database.ssl_client_cert, &database.ssl_client_key) {
+            options = options.ssl_client_cert(cert).ssl_client_key(key);
+        }
+
+        if config.is_production() && !database.sslmode.is_some_and(|mode| mode.is_encrypted()) {
+            tracing::warn!("Production profile without an enforced encrypted database connection (sslmode should be require, verify-ca or verify-full)");
+        }
+
+        Ok(options)
+    }
+
+    /// Indique si la connexion courante à la base de données est chiffrée (TLS).
+    pub async fn is_encrypted(&self) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, Option<bool>>("SELECT ssl FROM pg_stat_ssl WHERE pid = pg_backend_pid()")
+            .fetch_optional(self.get_pool())
+            .await
+            .map(|ssl| ssl.flatten().unwrap_or(false))
+    }
+
     /// Récupère le pool de connexions.
     ///
     /// # Returns
@@ -0,0 +1,121 @@
+//! # Fields Extractor Module
+//!
+//! Ce module fournit `Fields<S>`, la sélection d'attributs (« sparse fieldsets ») :
+//!
+//! ```text
+//! ?fields=id,kind,occurred_at
+//! ```
+//!
+//! Les champs demandés sont validés contre la liste du modèle (`FieldSpec`) ;
+//! un champ inconnu est refusé avec un 400. Sans paramètre, la ressource est complète.
+//! Le filtrage s'applique à la représentation JSON, après sérialisation :
+//!
+//! ```ignore
+//! pub async fn get_user(fields: Fields<UserFields>, ...) -> Result<Json<Value>, AppError> {
+//!     Ok(Json(fields.shape(&user)?))
+//! }
+//! ```
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use serde::Serialize;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+use crate::{handlers::error::AppError, models::error::ErrorCode};
+
+/// Attributs sélectionnables d'un modèle, tels qu'ils apparaissent en JSON.
+pub trait FieldSpec: Send + Sync + 'static {
+    const FIELDS: &'static [&'static str];
+}
+
+/// Attributs demandés par le client, validés contre `S`.
+#[derive(Debug, Clone)]
+pub struct Fields<S> {
+    /// `None` : tous les attributs
+    selected: Option<Vec<String>>,
+    spec: PhantomData<S>,
+}
+
+impl<S> Default for Fields<S> {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            spec: PhantomData,
+        }
+    }
+}
+
+impl<S: FieldSpec> Fields<S> {
+    /// Analyse une query string ; seul le paramètre `fields` est pris en compte.
+    pub fn parse(query: &str) -> Result<Self, AppError> {
+        let pairs: Vec<(String, String)> =
+            serde_urlencoded::from_str(query).map_err(|e| AppError::coded(ErrorCode::InvalidFields, e.to_string()))?;
+
+        let mut selected = Vec::new();
+        for (_, value) in pairs.into_iter().filter(|(key, _)| key == "fields") {
+            for field in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                if !S::FIELDS.contains(&field) {
+                    return Err(AppError::coded(ErrorCode::InvalidFields, format!("unknown field: {}", field)));
+                }
+                if !selected.iter().any(|f| f == field) {
+                    selected.push(field.to_string());
+                }
+            }
+        }
+
+        Ok(Self {
+            selected: (!selected.is_empty()).then_some(selected),
+            spec: PhantomData,
+        })
+    }
+
+    /// Champs demandés, ou `None` si la ressource complète est attendue
+    pub fn selected(&self) -> Option<&[String]> {
+        self.selected.as_deref()
+    }
+
+    /// Indique si l'attribut fait partie de la réponse
+    pub fn contains(&self, field: &str) -> bool {
+        self.selected.as_ref().is_none_or(|selected| selected.iter().any(|f| f == field))
+    }
+
+    /// Filtre une valeur JSON (objet, ou tableau d'objets)
+    pub fn filter(&self, value: Value) -> Value {
+        match &self.selected {
+            Some(selected) => select_fields(value, selected),
+            None => value,
+        }
+    }
+
+    /// Sérialise une ressource puis ne garde que les attributs demandés
+    pub fn shape<T: Serialize>(&self, item: &T) -> Result<Value, AppError> {
+        let value = serde_json::to_value(item)
+            .map_err(|e| AppError::Internal(format!("failed to serialize response: {}", e)))?;
+        Ok(self.filter(value))
+    }
+
+    /// `shape` appliqué à chaque élément d'une liste
+    pub fn shape_all<T: Serialize>(&self, items: &[T]) -> Result<Vec<Value>, AppError> {
+        items.iter().map(|item| self.shape(item)).collect()
+    }
+}
+
+/// Ne garde que les clés `fields` d'un objet JSON (ou de chaque objet d'un tableau).
+pub fn select_fields<F: AsRef<str>>(value: Value, fields: &[F]) -> Value {
+    match value {
+        Value::Object(mut object) => {
+            object.retain(|key, _| fields.iter().any(|f| f.as_ref() == key));
+            Value::Object(object)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| select_fields(item, fields)).collect()),
+        other => other,
+    }
+}
+
+impl<S: FieldSpec, St: Send + Sync> FromRequestParts<St> for Fields<S> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &St) -> Result<Self, Self::Rejection> {
+        Self::parse(parts.uri.query().unwrap_or_default())
+    }
+}
@@ -0,0 +1,160 @@
+//! # JSON Extractor Module
+//!
+//! Ce module fournit `ApiJson<T>`, un extracteur JSON qui applique la politique
+//! de strictesse configurée (`[api] request_strictness`) :
+//! - `lenient` : les champs inconnus sont ignorés, comme avec `axum::Json`
+//! - `strict` : les champs inconnus sont refusés avec un 400 qui les liste
+//!
+//! Une route peut surcharger la politique globale avec une extension :
+//!
+//! ```ignore
+//! .route("/users", post(create_user))
+//! .route_layer(Extension(SchemaStrictness::Strict))
+//! ```
+
+use axum::{
+    extract::{rejection::JsonRejection, FromRef, FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::{
+    config::{Config, SchemaStrictness},
+    handlers::{
+        error::{problem_response, status_slug},
+        response::ResponseMeta,
+    },
+    models::error::{ErrorCode, FieldError, ProblemDetails},
+};
+
+/// Corps JSON désérialisé selon la politique de strictesse en vigueur.
+///
+/// `T` doit aussi implémenter `Serialize` : en mode strict, le corps reçu est comparé
+/// à la forme canonique de `T` pour détecter les champs qui auraient été ignorés.
+#[derive(Debug, Clone)]
+pub struct ApiJson<T>(pub T);
+
+/// Rejet de l'extracteur `ApiJson`
+#[derive(Debug)]
+pub enum ApiJsonRejection {
+    /// Corps absent, mal formé ou mauvais `Content-Type`
+    Json(JsonRejection),
+    /// Le JSON est valide mais ne correspond pas au type attendu
+    InvalidData(String),
+    /// Mode strict : des champs inconnus ont été envoyés
+    UnknownFields(Vec<String>),
+}
+
+impl IntoResponse for ApiJsonRejection {
+    fn into_response(self) -> Response {
+        match self {
+            ApiJsonRejection::Json(rejection) => {
+                let code = match rejection {
+                    JsonRejection::MissingJsonContentType(_) => ErrorCode::UnsupportedMediaType,
+                    _ => ErrorCode::InvalidJson,
+                };
+                rejection_problem(rejection.status(), code, rejection.body_text(), Vec::new())
+            }
+            ApiJsonRejection::InvalidData(message) => {
+                rejection_problem(StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidJson, message, Vec::new())
+            }
+            ApiJsonRejection::UnknownFields(fields) => rejection_problem(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::UnknownFields,
+                "Unknown fields in request body".to_string(),
+                fields,
+            ),
+        }
+    }
+}
+
+/// Document `application/problem+json` d'un rejet, au statut du rejet (400, 413, 415 ou 422).
+/// Les champs inconnus sont listés dans `errors` et dans le membre d'extension `unknown_fields`.
+fn rejection_problem(status: StatusCode, code: ErrorCode, detail: String, unknown_fields: Vec<String>) -> Response {
+    let problem = ProblemDetails {
+        problem_type: format!("urn:problem:{}", status_slug(status.as_u16())),
+        title: status.canonical_reason().unwrap_or("Error").to_string(),
+        status: status.as_u16(),
+        code,
+        detail: Some(detail),
+        errors: unknown_fields
+            .iter()
+            .map(|field| FieldError { field: field.clone(), message: "unknown field".to_string() })
+            .collect(),
+        meta: Some(ResponseMeta::current()),
+    };
+    if unknown_fields.is_empty() {
+        return problem_response(status, problem);
+    }
+    let mut body = serde_json::to_value(problem).unwrap_or_default();
+    body["unknown_fields"] = serde_json::json!(unknown_fields);
+    problem_response(status, body)
+}
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned + Serialize,
+    S: Send + Sync,
+    Arc<Config>: FromRef<S>,
+{
+    type Rejection = ApiJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // La surcharge par route est prioritaire sur la configuration globale
+        let strictness = req
+            .extensions()
+            .get::<SchemaStrictness>()
+            .copied()
+            .unwrap_or_else(|| Arc::<Config>::from_ref(state).api.request_strictness);
+
+        let Json(raw) = Json::<Value>::from_request(req, state)
+            .await
+            .map_err(ApiJsonRejection::Json)?;
+
+        let data = T::deserialize(&raw).map_err(|e| ApiJsonRejection::InvalidData(e.to_string()))?;
+
+        if strictness == SchemaStrictness::Strict {
+            let canonical =
+                serde_json::to_value(&data).map_err(|e| ApiJsonRejection::InvalidData(e.to_string()))?;
+            let mut unknown = Vec::new();
+            collect_unknown_fields(&raw, &canonical, "", &mut unknown);
+            if !unknown.is_empty() {
+                return Err(ApiJsonRejection::UnknownFields(unknown));
+            }
+        }
+
+        Ok(ApiJson(data))
+    }
+}
+
+/// Liste les chemins présents dans `input` mais absents de la forme canonique.
+///
+/// Les valeurs `null` sont tolérées : elles correspondent souvent à des champs
+/// optionnels omis à la sérialisation (`skip_serializing_if`).
+pub fn collect_unknown_fields(input: &Value, canonical: &Value, prefix: &str, unknown: &mut Vec<String>) {
+    match (input, canonical) {
+        (Value::Object(input), Value::Object(canonical)) => {
+            for (key, value) in input {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                match canonical.get(key) {
+                    Some(expected) => collect_unknown_fields(value, expected, &path, unknown),
+                    None if value.is_null() => {}
+                    None => unknown.push(path),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(canonical)) => {
+            for (index, (value, expected)) in input.iter().zip(canonical).enumerate() {
+                collect_unknown_fields(value, expected, &format!("{}[{}]", prefix, index), unknown);
+            }
+        }
+        _ => {}
+    }
+}
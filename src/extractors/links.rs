@@ -0,0 +1,58 @@
+//! # Links Extractor Module
+//!
+//! Ce module fournit `LinkBuilder`, qui construit les URL des liens hypermédia à partir
+//! de `[server] public_url`. Sans URL publique configurée, les liens restent relatifs.
+//!
+//! ```ignore
+//! pub async fn get_user(links: LinkBuilder, ApiPath(id): ApiPath<UserId>) -> Result<ApiResponse<User>, AppError> {
+//!     let user = users::get(db.get_pool(), id).await?;
+//!     Ok(ApiResponse::ok(user).links(
+//!         links.to(&format!("/api/users/{}", id)).related("posts", links.url(&format!("/api/users/{}/posts", id))),
+//!     ))
+//! }
+//! ```
+
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use std::{convert::Infallible, sync::Arc};
+
+use crate::{config::Config, handlers::response::Links};
+
+/// Constructeur d'URL publiques
+#[derive(Debug, Clone, Default)]
+pub struct LinkBuilder {
+    base_url: String,
+}
+
+impl LinkBuilder {
+    /// `base_url` sans barre oblique finale ; `None` produit des liens relatifs
+    pub fn new(base_url: Option<&str>) -> Self {
+        Self {
+            base_url: base_url.unwrap_or_default().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// URL publique d'un chemin de l'API (`/api/...`)
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Liens d'une ressource située à `path`
+    pub fn to(&self, path: &str) -> Links {
+        Links::to(self.url(path))
+    }
+}
+
+impl<S> FromRequestParts<S> for LinkBuilder
+where
+    S: Send + Sync,
+    Arc<Config>: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::new(Arc::<Config>::from_ref(state).server.public_url.as_deref()))
+    }
+}
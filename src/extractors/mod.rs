@@ -0,0 +1,12 @@
+//! # Extractors Module
+//!
+//! Ce module regroupe les extracteurs Axum partagés par les handlers.
+
+pub mod fields;
+pub mod json;
+pub mod links;
+pub mod multipart;
+pub mod pagination;
+pub mod path;
+pub mod query;
+pub mod validated;
@@ -0,0 +1,231 @@
+//! # Multipart Extractor Module
+//!
+//! Ce module fournit `MultipartStream`, un lecteur `multipart/form-data` en flux :
+//! le corps n'est jamais chargé entièrement en mémoire, chaque partie est lue
+//! morceau par morceau et peut être écrite directement vers le stockage.
+//!
+//! ```ignore
+//! pub async fn upload(mut multipart: MultipartStream) -> Result<..., AppError> {
+//!     while let Some(part) = multipart.next_part().await? {
+//!         while let Some(chunk) = multipart.chunk().await? {
+//!             // ...
+//!         }
+//!     }
+//! }
+//! ```
+
+use axum::{
+    body::BodyDataStream,
+    extract::{FromRequest, Request},
+    http::header,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+
+use crate::{handlers::error::AppError, models::error::ErrorCode};
+
+/// Taille maximale du bloc d'en-têtes d'une partie
+const MAX_PART_HEADERS_BYTES: usize = 8 * 1024;
+
+/// En-têtes d'une partie du formulaire
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartHeaders {
+    /// Nom du champ (`Content-Disposition: form-data; name="..."`)
+    pub name: Option<String>,
+    /// Nom du fichier envoyé, pour les champs de type fichier
+    pub filename: Option<String>,
+    /// Type MIME déclaré par le client
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Avant un délimiteur (préambule ou fin d'une partie non lue)
+    Delimiter,
+    /// Dans le contenu d'une partie
+    Body,
+    /// Délimiteur final atteint
+    Done,
+}
+
+/// Lecteur en flux d'un corps `multipart/form-data`.
+pub struct MultipartStream {
+    stream: BodyDataStream,
+    buffer: BytesMut,
+    /// `\r\n--boundary`
+    delimiter: Vec<u8>,
+    state: State,
+}
+
+impl MultipartStream {
+    /// Crée le lecteur à partir du `boundary` et du flux du corps.
+    pub fn new(boundary: &str, stream: BodyDataStream) -> Self {
+        // Le premier délimiteur n'est pas précédé de CRLF : on l'ajoute pour n'avoir qu'un cas
+        let mut buffer = BytesMut::from(&b"\r\n"[..]);
+        buffer.reserve(8 * 1024);
+
+        Self {
+            stream,
+            buffer,
+            delimiter: format!("\r\n--{}", boundary).into_bytes(),
+            state: State::Delimiter,
+        }
+    }
+
+    /// Passe à la partie suivante et retourne ses en-têtes, ou `None` à la fin du formulaire.
+    ///
+    /// Le contenu non lu de la partie courante est ignoré.
+    pub async fn next_part(&mut self) -> Result<Option<PartHeaders>, AppError> {
+        while self.state == State::Body {
+            self.chunk().await?;
+        }
+        if self.state == State::Done {
+            return Ok(None);
+        }
+
+        // Délimiteur, puis `--` (fin) ou CRLF (nouvelle partie)
+        let position = self.find_or_fill(&self.delimiter.clone(), usize::MAX).await?;
+        let _ = self.buffer.split_to(position + self.delimiter.len());
+        self.fill_at_least(2).await?;
+        if self.buffer.starts_with(b"--") {
+            self.state = State::Done;
+            return Ok(None);
+        }
+
+        let end = self.find_or_fill(b"\r\n\r\n", MAX_PART_HEADERS_BYTES).await?;
+        let raw_headers = self.buffer.split_to(end + 4);
+        let headers = std::str::from_utf8(&raw_headers)
+            .map_err(|_| AppError::coded(ErrorCode::InvalidMultipart, "invalid multipart part headers"))?;
+
+        self.state = State::Body;
+        Ok(Some(parse_part_headers(headers)))
+    }
+
+    /// Retourne le morceau suivant du contenu de la partie courante, ou `None` à sa fin.
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, AppError> {
+        if self.state != State::Body {
+            return Ok(None);
+        }
+
+        loop {
+            if let Some(position) = find(&self.buffer, &self.delimiter) {
+                if position == 0 {
+                    self.state = State::Delimiter;
+                    return Ok(None);
+                }
+                return Ok(Some(self.buffer.split_to(position).freeze()));
+            }
+
+            // Tout ce qui ne peut pas être le début d'un délimiteur peut être rendu
+            let safe = self.buffer.len().saturating_sub(self.delimiter.len() - 1);
+            if safe > 0 {
+                return Ok(Some(self.buffer.split_to(safe).freeze()));
+            }
+
+            if !self.fill().await? {
+                return Err(AppError::coded(ErrorCode::InvalidMultipart, "unexpected end of multipart body"));
+            }
+        }
+    }
+
+    /// Lit le flux jusqu'à trouver `needle`, sans dépasser `limit` octets de recherche
+    async fn find_or_fill(&mut self, needle: &[u8], limit: usize) -> Result<usize, AppError> {
+        loop {
+            if let Some(position) = find(&self.buffer, needle) {
+                return Ok(position);
+            }
+            if self.buffer.len() > limit {
+                return Err(AppError::coded(ErrorCode::InvalidMultipart, "multipart part headers too large"));
+            }
+            if !self.fill().await? {
+                return Err(AppError::coded(ErrorCode::InvalidMultipart, "unexpected end of multipart body"));
+            }
+        }
+    }
+
+    async fn fill_at_least(&mut self, len: usize) -> Result<(), AppError> {
+        while self.buffer.len() < len {
+            if !self.fill().await? {
+                return Err(AppError::coded(ErrorCode::InvalidMultipart, "unexpected end of multipart body"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Ajoute le morceau suivant du flux au tampon ; `false` en fin de flux
+    async fn fill(&mut self) -> Result<bool, AppError> {
+        match self.stream.next().await {
+            Some(Ok(data)) => {
+                self.buffer.extend_from_slice(&data);
+                Ok(true)
+            }
+            Some(Err(e)) => Err(AppError::coded(ErrorCode::InvalidMultipart, format!("failed to read request body: {}", e))),
+            None => Ok(false),
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequest<S> for MultipartStream {
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        let boundary = parse_boundary(content_type).ok_or_else(|| {
+            AppError::UnsupportedMediaType("expected multipart/form-data with a boundary".to_string())
+        })?;
+
+        Ok(Self::new(&boundary, req.into_body().into_data_stream()))
+    }
+}
+
+/// Extrait le `boundary` d'un en-tête `Content-Type: multipart/form-data`
+pub fn parse_boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';').map(str::trim);
+    if !params.next()?.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .filter(|boundary| !boundary.is_empty() && boundary.len() <= 70)
+}
+
+fn parse_part_headers(raw: &str) -> PartHeaders {
+    let mut headers = PartHeaders::default();
+
+    for line in raw.split("\r\n") {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        if name.trim().eq_ignore_ascii_case("content-disposition") {
+            for param in value.split(';').skip(1) {
+                let Some((key, value)) = param.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"').to_string();
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "name" => headers.name = Some(value),
+                    "filename" => headers.filename = Some(value),
+                    _ => {}
+                }
+            }
+        } else if name.trim().eq_ignore_ascii_case("content-type") {
+            headers.content_type = Some(value.to_ascii_lowercase());
+        }
+    }
+
+    headers
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
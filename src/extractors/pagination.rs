@@ -0,0 +1,203 @@
+//! # Pagination Extractor Module
+//!
+//! Ce module fournit l'extracteur `Pagination`, commun à toutes les routes de liste.
+//! Deux styles de paramètres sont acceptés :
+//! - `?page=2&per_page=20` (pages numérotées à partir de 1)
+//! - `?limit=20&offset=40`
+//!
+//! La taille de page est bornée par `[api] max_per_page` ; sans paramètre,
+//! `[api] default_per_page` s'applique. Les deux styles ne peuvent pas être mélangés.
+//! Les liens de la réponse sont absolus lorsque `[server] public_url` est configurée.
+//!
+//! ```ignore
+//! pub async fn list_users(State(db): State<DatabaseManager>, pagination: Pagination)
+//!     -> Result<PaginatedResponse<User>, AppError>
+//! {
+//!     let (users, total) = users::list(db.get_pool(), pagination.limit(), pagination.offset()).await?;
+//!     Ok(pagination.response(users, total))
+//! }
+//! ```
+
+use axum::{
+    extract::{FromRef, FromRequestParts, Query},
+    http::{request::Parts, Uri},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::IntoParams;
+
+use crate::{
+    config::Config,
+    extractors::links::LinkBuilder,
+    handlers::{
+        error::AppError,
+        response::{Links, PaginatedResponse, ResponseMeta},
+    },
+    models::error::ErrorCode,
+};
+
+/// Paramètres de pagination bruts, tels que reçus dans la query string
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PaginationParams {
+    /// Numéro de page (à partir de 1)
+    pub page: Option<u64>,
+    /// Nombre d'éléments par page
+    pub per_page: Option<u64>,
+    /// Nombre maximal d'éléments (alternative à `per_page`)
+    pub limit: Option<u64>,
+    /// Nombre d'éléments à sauter (alternative à `page`)
+    pub offset: Option<u64>,
+}
+
+/// Pagination demandée par le client, validée et bornée.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    per_page: u64,
+    offset: u64,
+    offset_style: bool,
+    uri: Uri,
+    links: LinkBuilder,
+}
+
+impl Pagination {
+    /// Construit une pagination à partir des paramètres reçus.
+    pub fn from_params(params: PaginationParams, uri: Uri, default_per_page: u64, max_per_page: u64) -> Result<Self, AppError> {
+        let offset_style = params.limit.is_some() || params.offset.is_some();
+        if offset_style && (params.page.is_some() || params.per_page.is_some()) {
+            return Err(AppError::coded(
+                ErrorCode::InvalidPagination,
+                "use either page/per_page or limit/offset, not both",
+            ));
+        }
+
+        let per_page = params
+            .per_page
+            .or(params.limit)
+            .unwrap_or(default_per_page)
+            .clamp(1, max_per_page.max(1));
+
+        let offset = if offset_style {
+            params.offset.unwrap_or(0)
+        } else {
+            match params.page.unwrap_or(1) {
+                0 => return Err(AppError::coded(ErrorCode::InvalidPagination, "page starts at 1")),
+                page => (page - 1).saturating_mul(per_page),
+            }
+        };
+
+        Ok(Self {
+            per_page,
+            offset,
+            offset_style,
+            uri,
+            links: LinkBuilder::default(),
+        })
+    }
+
+    /// Construit des liens absolus à partir de `links` plutôt que relatifs
+    pub fn with_links(mut self, links: LinkBuilder) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Page courante (à partir de 1)
+    pub fn page(&self) -> u64 {
+        (self.offset / self.per_page).saturating_add(1)
+    }
+
+    /// Nombre d'éléments par page
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+    }
+
+    /// Valeur à passer en `LIMIT`
+    pub fn limit(&self) -> i64 {
+        self.per_page as i64
+    }
+
+    /// Valeur à passer en `OFFSET`
+    pub fn offset(&self) -> i64 {
+        self.offset.min(i64::MAX as u64) as i64
+    }
+
+    /// Construit la réponse paginée, avec les liens vers les pages voisines.
+    pub fn response<T>(&self, items: Vec<T>, total: i64) -> PaginatedResponse<T> {
+        let total = total.max(0) as u64;
+
+        // Arithmétique saturée : `offset` vient du client et peut valoir `u64::MAX`
+        let next_offset = self.offset.saturating_add(self.per_page);
+        let next = (next_offset < total).then(|| self.link(next_offset));
+        let prev = (self.offset > 0).then(|| self.link(self.offset.saturating_sub(self.per_page)));
+        let last_offset = total.saturating_sub(1) / self.per_page * self.per_page;
+        let links = Links {
+            self_link: Some(self.link(self.offset)),
+            first: Some(self.link(0)),
+            last: Some(self.link(last_offset)),
+            next: next.clone(),
+            prev: prev.clone(),
+            ..Links::default()
+        };
+
+        PaginatedResponse {
+            items,
+            total,
+            page: self.page(),
+            per_page: self.per_page,
+            total_pages: total.div_ceil(self.per_page),
+            next,
+            prev,
+            links,
+            meta: ResponseMeta::default(),
+        }
+    }
+
+    /// Lien vers la page commençant à `offset`, dans le style de paramètres reçu
+    fn link(&self, offset: u64) -> String {
+        let mut query: Vec<(String, String)> = self
+            .uri
+            .query()
+            .and_then(|query| serde_urlencoded::from_str(query).ok())
+            .unwrap_or_default();
+        query.retain(|(key, _)| !matches!(key.as_str(), "page" | "per_page" | "limit" | "offset"));
+
+        if self.offset_style {
+            query.push(("limit".to_string(), self.per_page.to_string()));
+            query.push(("offset".to_string(), offset.to_string()));
+        } else {
+            query.push(("page".to_string(), (offset / self.per_page).saturating_add(1).to_string()));
+            query.push(("per_page".to_string(), self.per_page.to_string()));
+        }
+
+        self.links.url(&format!(
+            "{}?{}",
+            self.uri.path(),
+            serde_urlencoded::to_string(&query).unwrap_or_default()
+        ))
+    }
+}
+
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+    Arc<Config>: FromRef<S>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Arc::<Config>::from_ref(state);
+        let Query(params) = Query::<PaginationParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| AppError::coded(ErrorCode::InvalidPagination, e.body_text()))?;
+
+        // Derrière un `nest`, l'URI d'origine conserve le préfixe complet
+        let uri = parts
+            .extensions
+            .get::<axum::extract::OriginalUri>()
+            .map(|original| original.0.clone())
+            .unwrap_or_else(|| parts.uri.clone());
+
+        let pagination = Pagination::from_params(params, uri, config.api.default_per_page, config.api.max_per_page)?;
+        Ok(pagination.with_links(LinkBuilder::new(config.server.public_url.as_deref())))
+    }
+}
@@ -0,0 +1,34 @@
+//! # Path Extractor Module
+//!
+//! Ce module fournit `ApiPath<T>`, équivalent de `Path<T>` dont les rejets sont rendus
+//! au format RFC 7807 (`INVALID_PATH_PARAMETER`) : un identifiant malformé produit un 400
+//! homogène avec les autres erreurs de l'API.
+
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{handlers::error::AppError, models::error::ErrorCode};
+
+/// Paramètres de chemin désérialisés
+#[derive(Debug, Clone, Copy)]
+pub struct ApiPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(ApiPath(value)),
+            // Paramètres absents de la route : erreur de câblage, pas du client
+            Err(rejection) if rejection.status().is_server_error() => Err(AppError::Internal(rejection.body_text())),
+            Err(rejection) => Err(AppError::coded(ErrorCode::InvalidPathParameter, rejection.body_text())),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! # Query Options Extractor Module
+//!
+//! Ce module fournit `QueryOptions<S>`, le langage de requête commun des routes de liste :
+//!
+//! ```text
+//! ?filter[status]=active,pending&sort=-created_at,name&search=foo
+//! ```
+//!
+//! - `filter[champ]=v1,v2` : égalité (ou appartenance à la liste des valeurs)
+//! - `sort=champ,-champ` : tri croissant, ou décroissant avec `-`
+//! - `search=texte` : recherche insensible à la casse dans les colonnes prévues
+//!
+//! Les champs autorisés sont déclarés par une liste blanche (`QuerySpec`) ;
+//! tout autre champ est refusé avec un 400. Les noms de colonnes ne proviennent
+//! donc jamais du client, et les valeurs sont toujours passées en paramètres liés.
+
+use axum::{
+    extract::FromRequestParts,
+    http::request::Parts,
+};
+use sqlx::{Postgres, QueryBuilder};
+use std::marker::PhantomData;
+
+use crate::{handlers::error::AppError, models::error::ErrorCode};
+
+/// Liste blanche des champs utilisables dans la query string d'une route.
+pub trait QuerySpec: Send + Sync + 'static {
+    /// Champs filtrables : (nom dans la query string, colonne SQL)
+    const FILTERS: &'static [(&'static str, &'static str)];
+    /// Champs triables : (nom dans la query string, colonne SQL)
+    const SORTS: &'static [(&'static str, &'static str)];
+    /// Colonnes texte parcourues par `search`
+    const SEARCH: &'static [&'static str];
+    /// Tri appliqué quand `sort` est absent, par exemple `"-created_at"`
+    const DEFAULT_SORT: &'static str;
+}
+
+/// Critère de tri validé
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortField {
+    /// Colonne SQL
+    pub column: &'static str,
+    pub descending: bool,
+}
+
+/// Filtres, tri et recherche demandés par le client, validés contre `S`.
+#[derive(Debug, Clone)]
+pub struct QueryOptions<S> {
+    /// (colonne SQL, valeurs acceptées)
+    pub filters: Vec<(&'static str, Vec<String>)>,
+    pub sort: Vec<SortField>,
+    pub search: Option<String>,
+    spec: PhantomData<S>,
+}
+
+impl<S> Default for QueryOptions<S> {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+            sort: Vec::new(),
+            search: None,
+            spec: PhantomData,
+        }
+    }
+}
+
+impl<S: QuerySpec> QueryOptions<S> {
+    /// Analyse une query string ; les paramètres étrangers au langage (pagination...) sont ignorés.
+    pub fn parse(query: &str) -> Result<Self, AppError> {
+        let pairs: Vec<(String, String)> =
+            serde_urlencoded::from_str(query).map_err(|e| AppError::coded(ErrorCode::InvalidQuery, e.to_string()))?;
+        let mut options = Self::default();
+
+        for (key, value) in pairs {
+            if let Some(field) = key.strip_prefix("filter[").and_then(|rest| rest.strip_suffix(']')) {
+                let column = lookup(S::FILTERS, field)
+                    .ok_or_else(|| AppError::coded(ErrorCode::InvalidQuery, format!("unknown filter field: {}", field)))?;
+                let values = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                if !values.is_empty() {
+                    options.filters.push((column, values));
+                }
+            } else if key == "sort" {
+                options.sort = parse_sort::<S>(&value)?;
+            } else if key == "search" {
+                let search = value.trim();
+                if !search.is_empty() {
+                    if S::SEARCH.is_empty() {
+                        return Err(AppError::coded(ErrorCode::InvalidQuery, "search is not supported here"));
+                    }
+                    options.search = Some(search.to_string());
+                }
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Ajoute les conditions à une requête dont la clause `WHERE` est déjà ouverte
+    /// (par exemple `SELECT ... WHERE true`) : chaque condition est préfixée par `AND`.
+    pub fn push_conditions(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        for (column, values) in &self.filters {
+            builder.push(format!(" AND {}::text = ANY(", column));
+            builder.push_bind(values.clone());
+            builder.push(")");
+        }
+
+        if let Some(search) = &self.search {
+            let pattern = format!("%{}%", escape_like(search));
+            builder.push(" AND (");
+            for (i, column) in S::SEARCH.iter().enumerate() {
+                if i > 0 {
+                    builder.push(" OR ");
+                }
+                builder.push(format!("{} ILIKE ", column));
+                builder.push_bind(pattern.clone());
+            }
+            builder.push(")");
+        }
+    }
+
+    /// Ajoute la clause `ORDER BY`, suivie de `tiebreaker` pour une pagination stable.
+    pub fn push_order_by(&self, builder: &mut QueryBuilder<'_, Postgres>, tiebreaker: &str) {
+        let sort = if self.sort.is_empty() {
+            parse_sort::<S>(S::DEFAULT_SORT).unwrap_or_default()
+        } else {
+            self.sort.clone()
+        };
+
+        builder.push(" ORDER BY ");
+        for field in &sort {
+            builder.push(format!("{} {}, ", field.column, if field.descending { "DESC" } else { "ASC" }));
+        }
+        builder.push(tiebreaker);
+    }
+}
+
+impl<S: QuerySpec, St: Send + Sync> FromRequestParts<St> for QueryOptions<S> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &St) -> Result<Self, Self::Rejection> {
+        Self::parse(parts.uri.query().unwrap_or_default())
+    }
+}
+
+fn lookup(fields: &[(&'static str, &'static str)], name: &str) -> Option<&'static str> {
+    fields.iter().find(|(field, _)| *field == name).map(|(_, column)| *column)
+}
+
+fn parse_sort<S: QuerySpec>(value: &str) -> Result<Vec<SortField>, AppError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (name, descending) = match field.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (field.strip_prefix('+').unwrap_or(field), false),
+            };
+            lookup(S::SORTS, name)
+                .map(|column| SortField { column, descending })
+                .ok_or_else(|| AppError::coded(ErrorCode::InvalidQuery, format!("unknown sort field: {}", name)))
+        })
+        .collect()
+}
+
+/// Échappe les jokers `%` et `_` d'un motif `LIKE`
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
@@ -0,0 +1,77 @@
+//! # Validated JSON Extractor Module
+//!
+//! Ce module fournit `ValidatedJson<T>` : le corps est d'abord extrait comme avec
+//! `ApiJson` (même politique de strictesse), nettoyé (`Sanitize`, voir `crate::sanitize`),
+//! puis validé avec le crate `validator`.
+//! Une validation en échec renvoie un 422 `application/problem+json` listant les
+//! erreurs par champ :
+//!
+//! ```ignore
+//! #[derive(Deserialize, Serialize, Validate)]
+//! pub struct NewUser {
+//!     #[validate(email)]
+//!     pub email: String,
+//! }
+//!
+//! impl Sanitize for NewUser {}
+//!
+//! pub async fn create_user(ValidatedJson(user): ValidatedJson<NewUser>) -> ... { ... }
+//! ```
+
+use axum::{
+    extract::{FromRef, FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::{
+    config::Config,
+    extractors::json::{ApiJson, ApiJsonRejection},
+    handlers::error::AppError,
+    sanitize::Sanitize,
+};
+
+/// Corps JSON désérialisé puis validé.
+#[derive(Debug, Clone)]
+pub struct ValidatedJson<T>(pub T);
+
+/// Rejet de l'extracteur `ValidatedJson`
+#[derive(Debug)]
+pub enum ValidatedJsonRejection {
+    /// Le corps n'a pas pu être extrait (voir `ApiJsonRejection`)
+    Json(ApiJsonRejection),
+    /// Le corps a été extrait mais n'est pas valide
+    Invalid(AppError),
+}
+
+impl IntoResponse for ValidatedJsonRejection {
+    fn into_response(self) -> Response {
+        match self {
+            ValidatedJsonRejection::Json(rejection) => rejection.into_response(),
+            ValidatedJsonRejection::Invalid(error) => error.into_response(),
+        }
+    }
+}
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Serialize + Sanitize + Validate,
+    S: Send + Sync,
+    Arc<Config>: FromRef<S>,
+{
+    type Rejection = ValidatedJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let ApiJson(mut data) = ApiJson::<T>::from_request(req, state)
+            .await
+            .map_err(ValidatedJsonRejection::Json)?;
+
+        data.sanitize();
+        data.validate()
+            .map_err(|e| ValidatedJsonRejection::Invalid(AppError::from(e)))?;
+
+        Ok(ValidatedJson(data))
+    }
+}
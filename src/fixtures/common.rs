@@ -1,18 +1,213 @@
-use sqlx::{Pool, Postgres};
+use crate::{
+    config::{ConflictStrategy, FakerLocale},
+    db::MIGRATOR,
+};
+use serde_json::{Map, Value};
+use sqlx::{PgConnection, Pool, Postgres};
+use std::{collections::HashMap, time::Instant};
 use tracing::{info, warn};
 
+/// Table de suivi des migrations, conservée par `FixtureManager::reset`
+const MIGRATIONS_TABLE: &str = "_sqlx_migrations";
+
+/// Encadre un nom de table pour l'insérer dans une requête
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Type SQL de chaque colonne d'une table (`timestamp without time zone`, `jsonb`...)
+pub(crate) async fn column_types(conn: &mut PgConnection, table: &str) -> Result<HashMap<String, String>, sqlx::Error> {
+    let types: HashMap<String, String> = sqlx::query_as(
+        "SELECT attname::text, format_type(atttypid, atttypmod) FROM pg_attribute
+         WHERE attrelid = to_regclass($1) AND attnum > 0 AND NOT attisdropped",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+    if types.is_empty() {
+        return Err(sqlx::Error::Protocol(format!("Unknown table: {}", table)));
+    }
+    Ok(types)
+}
+
+/// Insère des lignes JSON avec une seule instruction `INSERT ... SELECT ... FROM UNNEST(...)`.
+///
+/// Chaque colonne est envoyée comme un tableau de texte puis convertie dans le type de la
+/// colonne de la table : le nombre de paramètres ne dépend pas du nombre de lignes. Les
+/// colonnes sont celles de toutes les lignes ; une colonne absente d'une ligne vaut `NULL`.
+///
+/// Avec `ConflictStrategy::Update`, la cible du conflit est la première contrainte d'unicité
+/// dont toutes les colonnes sont insérées. Retourne le nombre de lignes insérées ou mises à jour.
+pub(crate) async fn bulk_insert(
+    conn: &mut PgConnection,
+    table: &str,
+    rows: &[Map<String, Value>],
+    conflict: ConflictStrategy,
+) -> Result<u64, sqlx::Error> {
+    let mut columns: Vec<&String> = Vec::new();
+    for column in rows.iter().flat_map(|row| row.keys()) {
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    if columns.is_empty() {
+        return Ok(0);
+    }
+
+    let types = column_types(conn, table).await?;
+    let mut selected = Vec::with_capacity(columns.len());
+    for column in &columns {
+        let column_type = types
+            .get(column.as_str())
+            .ok_or_else(|| sqlx::Error::Protocol(format!("Unknown column {} in table {}", column, table)))?;
+        selected.push(format!("{}::{}", quote_identifier(column), column_type));
+    }
+    let names: Vec<String> = columns.iter().map(|column| quote_identifier(column)).collect();
+    let arrays: Vec<String> = (1..=columns.len()).map(|i| format!("${}::text[]", i)).collect();
+    let mut statement = format!(
+        "INSERT INTO {table} ({names}) SELECT {selected} FROM UNNEST({arrays}) AS batch({names})",
+        table = quote_identifier(table),
+        names = names.join(", "),
+        selected = selected.join(", "),
+        arrays = arrays.join(", "),
+    );
+    match conflict {
+        ConflictStrategy::Error => {}
+        ConflictStrategy::Skip => statement.push_str(" ON CONFLICT DO NOTHING"),
+        ConflictStrategy::Update => {
+            let key = conflict_key(conn, table, &columns).await?;
+            let updated: Vec<String> = columns
+                .iter()
+                .filter(|column| !key.contains(*column))
+                .map(|column| format!("{name} = EXCLUDED.{name}", name = quote_identifier(column)))
+                .collect();
+            let key: Vec<String> = key.iter().map(|column| quote_identifier(column)).collect();
+            if updated.is_empty() {
+                statement.push_str(&format!(" ON CONFLICT ({}) DO NOTHING", key.join(", ")));
+            } else {
+                statement.push_str(&format!(" ON CONFLICT ({}) DO UPDATE SET {}", key.join(", "), updated.join(", ")));
+            }
+        }
+    }
+
+    let mut query = sqlx::query(&statement);
+    for column in &columns {
+        let values: Vec<Option<String>> = rows.iter().map(|row| row.get(column.as_str()).and_then(json_text)).collect();
+        query = query.bind(values);
+    }
+    Ok(query.execute(&mut *conn).await?.rows_affected())
+}
+
+/// Colonnes de la première contrainte d'unicité (clé primaire comprise) couverte par les
+/// colonnes insérées : la cible de `ON CONFLICT ... DO UPDATE`
+async fn conflict_key(conn: &mut PgConnection, table: &str, columns: &[&String]) -> Result<Vec<String>, sqlx::Error> {
+    let keys: Vec<Vec<String>> = sqlx::query_scalar(
+        "SELECT array_agg(a.attname::text ORDER BY k.ord) FROM pg_index i
+         CROSS JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS k(attnum, ord)
+         JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+         WHERE i.indrelid = to_regclass($1) AND i.indisunique AND i.indpred IS NULL AND i.indexprs IS NULL
+         GROUP BY i.indexrelid, i.indisprimary
+         ORDER BY i.indisprimary, i.indexrelid",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?;
+    keys.into_iter()
+        .find(|key| key.iter().all(|column| columns.contains(&column)))
+        .ok_or_else(|| {
+            sqlx::Error::Protocol(format!("No unique constraint of table {} covers the fixture columns to update on", table))
+        })
+}
+
+/// Représentation texte d'une valeur JSON, convertie par PostgreSQL dans le type de la colonne
+pub(crate) fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        // Tableaux et objets : colonnes JSON
+        other => Some(other.to_string()),
+    }
+}
+
+/// Lignes par instruction d'insertion sans `[fixtures] batch_size`
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
 pub struct FixtureManager {
     pool: Pool<Postgres>,
+    batch_size: usize,
+    dry_run: bool,
+    conflicts: HashMap<String, ConflictStrategy>,
+    locale: FakerLocale,
 }
 
 impl FixtureManager {
     /// Crée une nouvelle instance de FixtureManager
     pub fn new(pool: Pool<Postgres>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            batch_size: DEFAULT_BATCH_SIZE,
+            dry_run: false,
+            conflicts: HashMap::new(),
+            locale: FakerLocale::default(),
+        }
+    }
+
+    /// Stratégie appliquée aux conflits d'unicité d'une table (`ConflictStrategy::Error` par défaut)
+    pub fn with_conflict(mut self, table: impl Into<String>, strategy: ConflictStrategy) -> Self {
+        self.conflicts.insert(table.into(), strategy);
+        self
+    }
+
+    /// Stratégies par table, par exemple celles de `[fixtures.on_conflict]`
+    pub fn with_conflicts(mut self, conflicts: HashMap<String, ConflictStrategy>) -> Self {
+        self.conflicts.extend(conflicts);
+        self
+    }
+
+    /// Stratégie appliquée aux conflits d'unicité de `table`
+    pub fn conflict_strategy(&self, table: &str) -> ConflictStrategy {
+        self.conflicts.get(table).copied().unwrap_or_default()
+    }
+
+    /// Nombre de lignes par instruction d'insertion (et entre deux messages de progression)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Mode simulation : les lignes sont affichées sans être insérées
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Langue des données générées par les fabriques (`[fixtures] locale`)
+    pub fn with_locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Langue transmise aux fabriques
+    pub fn locale(&self) -> FakerLocale {
+        self.locale
+    }
+
+    /// Pool de connexions utilisé pour les insertions
+    pub fn pool(&self) -> &Pool<Postgres> {
+        &self.pool
+    }
+
+    /// Indique si le mode simulation est actif
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
     }
 
     /// Vérifie si les migrations sont à jour
-    async fn check_migrations(&self) -> Result<(), sqlx::Error> {
+    pub(crate) async fn check_migrations(&self) -> Result<(), sqlx::Error> {
         info!("Checking if migrations are up to date...");
         
         // Vérifie si la table _sqlx_migrations existe
@@ -34,90 +229,144 @@ impl FixtureManager {
         Ok(())
     }
 
+    /// Insère des lignes dans une table, par blocs de `batch_size` lignes (voir `bulk_insert`),
+    /// dans une seule transaction. La progression est journalisée après chaque bloc.
+    ///
+    /// Une ligne qui viole une contrainte d'unicité suit la stratégie de la table
+    /// (`with_conflict`) : échec du chargement, ligne ignorée ou ligne existante mise à jour.
+    ///
+    /// En mode simulation, les lignes sont affichées (`table<TAB>json`) au lieu d'être insérées.
     pub async fn submit_fixtures<T: serde::Serialize>(
         &self,
         fixture_data: Vec<T>,
         table_name: &str,
     ) -> Result<(), sqlx::Error> {
+        let rows = fixture_data
+            .into_iter()
+            .map(|data| match serde_json::to_value(data) {
+                Ok(Value::Object(row)) => Ok(row),
+                Ok(_) => Err(sqlx::Error::Protocol("Invalid JSON object".into())),
+                Err(e) => Err(sqlx::Error::Protocol(format!("JSON serialization error: {}", e))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if self.dry_run {
+            for row in &rows {
+                println!("{}\t{}", table_name, Value::Object(row.clone()));
+            }
+            info!("Dry run: {} fixtures would be submitted to table {}", rows.len(), table_name);
+            return Ok(());
+        }
+
         // Vérifie d'abord si les migrations sont à jour
         self.check_migrations().await?;
+        let conflict = self.conflict_strategy(table_name);
+        info!("Submitting {} fixtures to table {} (on conflict: {:?})", rows.len(), table_name, conflict);
 
-        let fixture_len = fixture_data.len();
-        info!("Submitting {} fixtures to table {}", fixture_len.clone(), table_name);
-
-        // Démarre une transaction
+        let started = Instant::now();
         let mut tx = self.pool.begin().await?;
-
-        for data in fixture_data {
-            // Convertit les données en JSON
-            let json_data = match serde_json::to_value(data) {
-                Ok(value) => value,
-                Err(e) => return Err(sqlx::Error::Protocol(format!("JSON serialization error: {}", e))),
-            };
-            
-            // Construit la requête d'insertion dynamiquement
-            let columns: Vec<String> = json_data.as_object()
-                .ok_or_else(|| sqlx::Error::Protocol("Invalid JSON object".into()))?
-                .keys()
-                .cloned()
-                .collect();
-
-            let placeholders: Vec<String> = (1..=columns.len())
-                .map(|i| format!("${}", i))
-                .collect();
-
-            let query = format!(
-                "INSERT INTO {} ({}) VALUES ({})",
+        let (mut done, mut written) = (0, 0);
+        for chunk in rows.chunks(self.batch_size.max(1)) {
+            written += bulk_insert(&mut tx, table_name, chunk, conflict).await?;
+            done += chunk.len();
+            let elapsed = started.elapsed().as_secs_f64();
+            info!(
+                "{}: {}/{} rows ({:.0}%, {:.0} rows/s)",
                 table_name,
-                columns.join(", "),
-                placeholders.join(", ")
+                done,
+                rows.len(),
+                done as f64 * 100.0 / rows.len() as f64,
+                done as f64 / elapsed.max(f64::EPSILON)
             );
+        }
+        tx.commit().await?;
 
-            // Prépare les valeurs pour la requête
-            let mut query_builder = sqlx::query(&query);
-            
-            for col in &columns {
-                let value = &json_data[col];
-                
-                // Ajoute chaque valeur au query builder selon son type
-                match value {
-                    serde_json::Value::Null => {
-                        query_builder = query_builder.bind::<Option<String>>(None);
-                    },
-                    serde_json::Value::Bool(b) => {
-                        query_builder = query_builder.bind(b);
-                    },
-                    serde_json::Value::Number(n) => {
-                        if let Some(i) = n.as_i64() {
-                            query_builder = query_builder.bind(i);
-                        } else if let Some(f) = n.as_f64() {
-                            query_builder = query_builder.bind(f);
-                        } else {
-                            return Err(sqlx::Error::Protocol(format!("Unsupported number type for column {}", col)));
-                        }
-                    },
-                    serde_json::Value::String(s) => {
-                        query_builder = query_builder.bind(s.clone());
-                    },
-                    _ => {
-                        // Pour les tableaux et objets, on les sérialise en JSON
-                        let json_string = match serde_json::to_string(value) {
-                            Ok(s) => s,
-                            Err(e) => return Err(sqlx::Error::Protocol(format!("JSON serialization error: {}", e))),
-                        };
-                        query_builder = query_builder.bind(json_string);
-                    }
-                }
-            }
+        if written < done as u64 {
+            info!("Skipped {} fixtures already present in table {}", done as u64 - written, table_name);
+        }
+        info!("Successfully submitted {} fixtures to table {}", written, table_name);
+        Ok(())
+    }
 
-            // Exécute la requête
-            query_builder.execute(&mut *tx).await?;
+    /// Vide des tables et remet leurs séquences à zéro.
+    ///
+    /// Les tables qui référencent celles demandées (clés étrangères, récursivement)
+    /// sont vidées avec elles, dans une seule instruction `TRUNCATE`. Retourne la liste
+    /// des tables vidées ; une table inconnue du schéma courant est une erreur.
+    pub async fn truncate(&self, tables: &[&str]) -> Result<Vec<String>, sqlx::Error> {
+        let names: Vec<String> = tables.iter().map(|table| table.to_string()).collect();
+        let found: Vec<String> = sqlx::query_scalar(
+            "SELECT relname::text FROM pg_class
+             WHERE relnamespace = current_schema()::regnamespace AND relkind IN ('r', 'p') AND relname = ANY($1)",
+        )
+        .bind(&names)
+        .fetch_all(&self.pool)
+        .await?;
+        if let Some(missing) = names.iter().find(|name| !found.contains(name)) {
+            return Err(sqlx::Error::Protocol(format!("Unknown table: {}", missing)));
         }
 
-        // Commit la transaction
-        tx.commit().await?;
+        let affected: Vec<String> = sqlx::query_scalar(
+            "WITH RECURSIVE affected(oid) AS (
+                 SELECT oid FROM pg_class
+                 WHERE relnamespace = current_schema()::regnamespace AND relkind IN ('r', 'p') AND relname = ANY($1)
+                 UNION
+                 SELECT c.conrelid FROM pg_constraint c JOIN affected a ON c.confrelid = a.oid WHERE c.contype = 'f'
+             )
+             SELECT relname::text FROM pg_class WHERE oid IN (SELECT oid FROM affected) ORDER BY relname",
+        )
+        .bind(&names)
+        .fetch_all(&self.pool)
+        .await?;
+        self.truncate_all(&affected).await?;
+        Ok(affected)
+    }
+
+    /// Vide toutes les tables du schéma courant, sauf le suivi des migrations
+    pub async fn reset(&self) -> Result<Vec<String>, sqlx::Error> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT relname::text FROM pg_class
+             WHERE relnamespace = current_schema()::regnamespace AND relkind IN ('r', 'p') AND relname <> $1
+             ORDER BY relname",
+        )
+        .bind(MIGRATIONS_TABLE)
+        .fetch_all(&self.pool)
+        .await?;
+        self.truncate_all(&tables).await?;
+        Ok(tables)
+    }
 
-        info!("Successfully submitted {} fixtures to table {}", fixture_len, table_name);
+    /// Supprime toutes les tables du schéma courant puis rejoue les migrations embarquées,
+    /// pour repartir d'un schéma identique à celui d'une installation neuve
+    pub async fn recreate(&self) -> Result<(), sqlx::Error> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT relname::text FROM pg_class
+             WHERE relnamespace = current_schema()::regnamespace AND relkind IN ('r', 'p') AND NOT relispartition",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        if !tables.is_empty() {
+            warn!("Dropping {} tables before re-running migrations", tables.len());
+            let list: Vec<String> = tables.iter().map(|table| quote_identifier(table)).collect();
+            sqlx::query(&format!("DROP TABLE {} CASCADE", list.join(", ")))
+                .execute(&self.pool)
+                .await?;
+        }
+        MIGRATOR.run(&self.pool).await?;
+        info!("Database recreated from {} migrations", MIGRATOR.iter().count());
+        Ok(())
+    }
+
+    /// Une seule instruction : PostgreSQL accepte les clés étrangères entre tables vidées ensemble
+    async fn truncate_all(&self, tables: &[String]) -> Result<(), sqlx::Error> {
+        if tables.is_empty() {
+            return Ok(());
+        }
+        info!("Truncating tables: {}", tables.join(", "));
+        let list: Vec<String> = tables.iter().map(|table| quote_identifier(table)).collect();
+        sqlx::query(&format!("TRUNCATE {} RESTART IDENTITY", list.join(", ")))
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
 
@@ -1,8 +1,13 @@
 
-use crate::fixtures::common::FixtureManager;
+use crate::{
+    config::FakerLocale,
+    fixtures::{common::FixtureManager, factory::Factory, locale, FixtureRng},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use sqlx::{Pool, Postgres};
-use fake::{Dummy as FakeDummy, Fake, Faker};
+use sqlx::{FromRow, PgPool, Pool, Postgres};
+use fake::{rand::Rng, Dummy as FakeDummy};
 use tracing::info;
 
 
@@ -18,19 +23,61 @@ pub struct Dummy {
     pub name: String
 }
 
-pub fn create_dummy_from_fake(number: u32) -> Vec<Dummy> {
-    let mut dummies = Vec::new();
-    for _ in 0..number {
-        let dummy : Dummy = Faker.fake();
-        dummies.push(dummy);
+/// Ligne enregistrée dans la table `dummy`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DummyRecord {
+    pub id: i32,
+    pub name: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Fabrique de lignes `dummy`
+#[derive(Debug, Clone, Default)]
+pub struct DummyFactory {
+    name: Option<String>,
+    locale: FakerLocale,
+}
+
+impl DummyFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Langue des noms générés (`en_US` par défaut)
+    pub fn locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
     }
-    dummies
 }
 
-pub async fn create_dummy(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+#[async_trait]
+impl Factory for DummyFactory {
+    type Row = Dummy;
+    type Model = DummyRecord;
+
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Dummy {
+        let name = locale::name(self.locale, rng);
+        Dummy { name: self.name.clone().unwrap_or(name) }
+    }
+
+    async fn insert(pool: &PgPool, row: Dummy) -> Result<DummyRecord, sqlx::Error> {
+        sqlx::query_as::<_, DummyRecord>("INSERT INTO dummy (name) VALUES ($1) RETURNING *")
+            .bind(row.name)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+pub async fn create_dummy(fixture_manager: &FixtureManager, rng: &mut FixtureRng, rows: u32) -> Result<(), sqlx::Error> {
     info!("Creating dummy...");
-    let dummies = create_dummy_from_fake(100);
-    let fixture_manager = FixtureManager::new(pool.clone());
+    let factory = DummyFactory::new().locale(fixture_manager.locale());
+    let dummies: Vec<Dummy> = (0..rows).map(|_| factory.build_with(rng)).collect();
     fixture_manager.submit_fixtures(dummies, "dummy").await?;
     Ok(())
 }
@@ -0,0 +1,243 @@
+//! # Fixture Export Module
+//!
+//! Ce module enregistre le contenu de tables au format des fixtures déclaratives (voir
+//! `fixtures::files`), pour conserver une base de développement préparée à la main :
+//!
+//! ```bash
+//! cargo run -- fixtures export users posts comments --output fixtures/
+//! ```
+//!
+//! Chaque table devient un fichier `<table>.yaml`. Les identifiants générés (`serial`,
+//! `identity`) et les colonnes calculées ne sont pas exportés : une clé étrangère vers
+//! une table exportée devient `{ $ref: table.<id> }` et la ligne visée reçoit `_ref: '<id>'`,
+//! si bien que les fichiers se rechargent dans une base qui a ses propres séquences.
+//! Une clé étrangère vers une table non exportée garde sa valeur.
+
+use crate::{
+    config::Config,
+    fixtures::{
+        dependency_order,
+        files::{check_identifier, FixtureFile, REF_KEY, REF_VALUE_KEY},
+    },
+};
+use serde_json::{Map, Value};
+use sqlx::{PgConnection, PgPool};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+use tracing::{info, warn};
+
+/// Commande parente des sous-commandes de fixtures
+pub const FIXTURES_COMMAND: &str = "fixtures";
+/// Sous-commande `fixtures export` enregistrant des tables au lieu de démarrer le serveur
+pub const EXPORT_COMMAND: &str = "export";
+
+/// Tables à exporter et dossier de destination
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSpec {
+    pub tables: Vec<String>,
+    /// `[fixtures] directory` par défaut, pour un rechargement avec `--fixtures`
+    pub output: PathBuf,
+}
+
+/// Clé étrangère d'une seule colonne
+struct ForeignKey {
+    table: String,
+    column: String,
+    referenced_table: String,
+    referenced_column: String,
+}
+
+/// Lit `fixtures export <tables...> [--output <dossier>]`. Retourne `None` sans la
+/// sous-commande. L'export ne modifie pas la base : il est permis en production.
+pub fn export_command<I, S>(config: &Config, args: I) -> Result<Option<ExportSpec>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<String> = args.into_iter().map(|arg| arg.as_ref().to_string()).collect();
+    let Some(start) = args
+        .windows(2)
+        .position(|pair| pair[0] == FIXTURES_COMMAND && pair[1] == EXPORT_COMMAND)
+    else {
+        return Ok(None);
+    };
+
+    let mut spec = ExportSpec { tables: Vec::new(), output: PathBuf::from(&config.fixtures.directory) };
+    let mut args = args[start + 2..].iter();
+    while let Some(arg) = args.next() {
+        match arg.split_once('=') {
+            Some(("--output", value)) => spec.output = PathBuf::from(value),
+            _ if arg == "--output" => {
+                spec.output = PathBuf::from(args.next().ok_or("--output expects a directory")?);
+            }
+            _ if arg.starts_with("--") => {}
+            _ if !spec.tables.contains(arg) => spec.tables.push(arg.clone()),
+            _ => {}
+        }
+    }
+
+    if spec.tables.is_empty() {
+        return Err(format!("{} {} expects at least one table", FIXTURES_COMMAND, EXPORT_COMMAND));
+    }
+    Ok(Some(spec))
+}
+
+/// Exporte les tables demandées dans `<output>/<table>.yaml`, en remplaçant les fichiers
+/// existants. Retourne le nombre de lignes exportées par table, dans l'ordre de chargement.
+pub async fn export_fixtures(pool: &PgPool, spec: &ExportSpec) -> Result<Vec<(String, usize)>, sqlx::Error> {
+    spec.tables.iter().try_for_each(|table| check_identifier(table))?;
+    let mut conn = pool.acquire().await?;
+
+    let mut generated = HashMap::new();
+    for table in &spec.tables {
+        generated.insert(table.as_str(), generated_columns(&mut conn, table).await?);
+    }
+    // Seuls les identifiants générés sont remplacés par des références : ce sont eux que
+    // le chargement des fichiers récupère avec `RETURNING id`
+    let foreign_keys: Vec<ForeignKey> = foreign_keys(&mut conn)
+        .await?
+        .into_iter()
+        .filter(|key| spec.tables.contains(&key.table))
+        .collect();
+    let resolvable = |key: &ForeignKey| {
+        key.referenced_column == "id"
+            && generated.get(key.referenced_table.as_str()).is_some_and(|columns| columns.contains("id"))
+    };
+    for key in foreign_keys.iter().filter(|key| !resolvable(key)) {
+        if !spec.tables.contains(&key.referenced_table) {
+            warn!(
+                "{}.{} references {}, which is not exported: values are kept as is",
+                key.table, key.column, key.referenced_table
+            );
+        }
+    }
+    let references: Vec<&ForeignKey> = foreign_keys.iter().filter(|key| resolvable(key)).collect();
+
+    let mut files = Vec::new();
+    for table in &spec.tables {
+        let referenced = references.iter().any(|key| key.referenced_table == *table);
+        let keys: Vec<&ForeignKey> = references.iter().copied().filter(|key| key.table == *table).collect();
+        let mut depends_on: Vec<String> = Vec::new();
+        for key in &keys {
+            if key.referenced_table != *table && !depends_on.contains(&key.referenced_table) {
+                depends_on.push(key.referenced_table.clone());
+            }
+        }
+
+        let order = if has_column(&mut conn, table, "id").await? { "ORDER BY t.id" } else { "" };
+        let records: Vec<Value> = sqlx::query_scalar(&format!("SELECT to_jsonb(t) FROM {} t {}", table, order))
+            .fetch_all(&mut *conn)
+            .await?;
+        let rows = records
+            .into_iter()
+            .filter_map(|record| match record {
+                Value::Object(record) => Some(export_row(record, &generated[table.as_str()], referenced, &keys)),
+                _ => None,
+            })
+            .collect();
+        files.push(FixtureFile { table: table.clone(), depends_on, rows });
+    }
+
+    let items: Vec<(&str, Vec<&str>)> = files
+        .iter()
+        .map(|file| (file.table.as_str(), file.depends_on.iter().map(String::as_str).collect()))
+        .collect();
+    let order = dependency_order(&items).map_err(sqlx::Error::Protocol)?;
+
+    std::fs::create_dir_all(&spec.output)
+        .map_err(|e| sqlx::Error::Protocol(format!("Cannot create {}: {}", spec.output.display(), e)))?;
+    let mut exported = Vec::new();
+    for index in order {
+        let file = &files[index];
+        let path = spec.output.join(format!("{}.yaml", file.table));
+        let content = serde_yaml::to_string(file)
+            .map_err(|e| sqlx::Error::Protocol(format!("Cannot serialize table {}: {}", file.table, e)))?;
+        std::fs::write(&path, content)
+            .map_err(|e| sqlx::Error::Protocol(format!("Cannot write {}: {}", path.display(), e)))?;
+        info!("Exported {} rows of {} to {}", file.rows.len(), file.table, path.display());
+        exported.push((file.table.clone(), file.rows.len()));
+    }
+    Ok(exported)
+}
+
+/// Retire les colonnes générées d'une ligne et remplace ses clés étrangères par des références
+fn export_row(
+    mut record: Map<String, Value>,
+    generated: &HashSet<String>,
+    referenced: bool,
+    keys: &[&ForeignKey],
+) -> Map<String, Value> {
+    let mut row = Map::new();
+    if referenced && let Some(id) = record.get("id").filter(|id| !id.is_null()) {
+        row.insert(REF_KEY.to_string(), Value::String(plain(id)));
+    }
+    for key in keys {
+        if let Some(value) = record.get_mut(&key.column).filter(|value| !value.is_null()) {
+            let reference = format!("{}.{}", key.referenced_table, plain(value));
+            *value = Value::Object(Map::from_iter([(REF_VALUE_KEY.to_string(), Value::String(reference))]));
+        }
+    }
+    row.extend(record.into_iter().filter(|(column, _)| !generated.contains(column)));
+    row
+}
+
+/// Valeur sans guillemets, pour composer un nom de référence
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Colonnes remplies par la base : `serial`, `identity` et colonnes calculées
+async fn generated_columns(conn: &mut PgConnection, table: &str) -> Result<HashSet<String>, sqlx::Error> {
+    let columns: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT a.attname::text,
+                a.attidentity <> '' OR a.attgenerated <> ''
+                    OR coalesce(pg_get_expr(d.adbin, d.adrelid) LIKE 'nextval(%', false)
+         FROM pg_attribute a
+         LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+         WHERE a.attrelid = to_regclass($1) AND a.attnum > 0 AND NOT a.attisdropped",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?;
+    if columns.is_empty() {
+        return Err(sqlx::Error::Protocol(format!("Unknown table: {}", table)));
+    }
+    Ok(columns.into_iter().filter(|(_, generated)| *generated).map(|(column, _)| column).collect())
+}
+
+async fn has_column(conn: &mut PgConnection, table: &str, column: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS (SELECT FROM pg_attribute WHERE attrelid = to_regclass($1) AND attname = $2 AND NOT attisdropped)",
+    )
+    .bind(table)
+    .bind(column)
+    .fetch_one(&mut *conn)
+    .await
+}
+
+/// Clés étrangères d'une seule colonne de toute la base
+async fn foreign_keys(conn: &mut PgConnection) -> Result<Vec<ForeignKey>, sqlx::Error> {
+    let keys: Vec<(String, String, String, String)> = sqlx::query_as(
+        "SELECT c.conrelid::regclass::text, a.attname::text, c.confrelid::regclass::text, fa.attname::text
+         FROM pg_constraint c
+         JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = c.conkey[1]
+         JOIN pg_attribute fa ON fa.attrelid = c.confrelid AND fa.attnum = c.confkey[1]
+         WHERE c.contype = 'f' AND cardinality(c.conkey) = 1",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(keys
+        .into_iter()
+        .map(|(table, column, referenced_table, referenced_column)| ForeignKey {
+            table,
+            column,
+            referenced_table,
+            referenced_column,
+        })
+        .collect())
+}
@@ -0,0 +1,53 @@
+//! # Factory Module
+//!
+//! Ce module définit `Factory`, le constructeur commun des lignes de test : chaque modèle
+//! a sa fabrique (`UserFactory`, `PostFactory`...), dont les valeurs générées peuvent être
+//! remplacées champ par champ avant la construction ou l'insertion.
+//!
+//! ```ignore
+//! let user = UserFactory::new().name("Ada").create(&pool).await?;
+//! let post = PostFactory::new(user.id).title("Bienvenue").create(&pool).await?;
+//! let comments = CommentFactory::new(post.id).author(user.id).create_many(&pool, 3).await?;
+//! ```
+//!
+//! Les fixtures Rust utilisent les mêmes fabriques avec le générateur seedé de leur jeu
+//! (`build_with`), puis insèrent les lignes par lots.
+
+use async_trait::async_trait;
+use fake::rand::Rng;
+use serde::Serialize;
+use sqlx::PgPool;
+
+#[async_trait]
+pub trait Factory: Send + Sync {
+    /// Ligne générée, prête à être insérée
+    type Row: Serialize + Send;
+    /// Modèle relu après l'insertion
+    type Model: Send;
+
+    /// Génère une ligne avec `rng`, en appliquant les valeurs imposées
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Row;
+
+    /// Insère une ligne et retourne le modèle enregistré
+    async fn insert(pool: &PgPool, row: Self::Row) -> Result<Self::Model, sqlx::Error>;
+
+    /// Génère une ligne avec un générateur non seedé
+    fn build(&self) -> Self::Row {
+        self.build_with(&mut fake::rand::rng())
+    }
+
+    /// Génère et insère une ligne
+    async fn create(&self, pool: &PgPool) -> Result<Self::Model, sqlx::Error> {
+        let row = self.build();
+        Self::insert(pool, row).await
+    }
+
+    /// Génère et insère `count` lignes, une par une
+    async fn create_many(&self, pool: &PgPool, count: u32) -> Result<Vec<Self::Model>, sqlx::Error> {
+        let mut models = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            models.push(self.create(pool).await?);
+        }
+        Ok(models)
+    }
+}
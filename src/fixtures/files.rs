@@ -0,0 +1,241 @@
+//! # Fixture Files Module
+//!
+//! Ce module charge des fixtures déclaratives depuis des fichiers YAML ou JSON,
+//! pour définir des données de test sans écrire de code d'insertion.
+//!
+//! Chaque fichier décrit une table et ses lignes. Une ligne peut être nommée avec
+//! `_ref`, puis référencée ailleurs par `{ $ref: table.nom }` : la référence est
+//! remplacée par l'`id` de la ligne insérée.
+//!
+//! ```yaml
+//! table: users
+//! rows:
+//!   - _ref: alice
+//!     email: alice@example.com
+//!     name: Alice
+//! ```
+//!
+//! ```yaml
+//! table: posts
+//! depends_on: [users]
+//! rows:
+//!   - user_id: { $ref: users.alice }
+//!     title: Bienvenue
+//!     body: Premier post
+//! ```
+
+use crate::fixtures::{
+    common::{column_types, json_text, FixtureManager},
+    dependency_order,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Clé nommant une ligne pour qu'elle puisse être référencée
+pub const REF_KEY: &str = "_ref";
+/// Clé d'une valeur remplacée par l'`id` d'une ligne nommée
+pub const REF_VALUE_KEY: &str = "$ref";
+
+/// Contenu d'un fichier de fixtures
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FixtureFile {
+    /// Table dans laquelle les lignes sont insérées
+    pub table: String,
+    /// Tables chargées avant celle-ci par d'autres fichiers, quel que soit leur nom
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Lignes à insérer, colonne par colonne
+    #[serde(default)]
+    pub rows: Vec<Map<String, Value>>,
+}
+
+impl FixtureFile {
+    /// Lit un fichier `.yaml`, `.yml` ou `.json`
+    pub fn read(path: &Path) -> Result<Self, sqlx::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| sqlx::Error::Protocol(format!("Cannot read fixture file {}: {}", path.display(), e)))?;
+        let file: Self = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content).map_err(|e| e.to_string())
+        } else {
+            serde_yaml::from_str(&content).map_err(|e| e.to_string())
+        }
+        .map_err(|e| sqlx::Error::Protocol(format!("Invalid fixture file {}: {}", path.display(), e)))?;
+
+        check_identifier(&file.table)?;
+        for row in &file.rows {
+            row.keys().filter(|column| *column != REF_KEY).try_for_each(|column| check_identifier(column))?;
+        }
+        Ok(file)
+    }
+}
+
+/// Liste les fichiers de fixtures d'un dossier, triés par nom
+pub fn fixture_files(dir: &Path) -> Result<Vec<PathBuf>, sqlx::Error> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| sqlx::Error::Protocol(format!("Cannot read fixtures directory {}: {}", dir.display(), e)))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| matches!(ext, "yaml" | "yml" | "json"))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Lit les fichiers de fixtures d'un dossier dans l'ordre de chargement.
+///
+/// Les fichiers sont triés par nom, puis chacun est placé après les fichiers des
+/// tables listées dans `depends_on`. Les dépendances vers des tables sans fichier
+/// (remplies par les fixtures Rust, chargées avant) sont ignorées.
+pub fn read_fixture_files(dir: &Path) -> Result<Vec<FixtureFile>, sqlx::Error> {
+    let files = fixture_files(dir)?
+        .iter()
+        .map(|path| FixtureFile::read(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let items: Vec<(&str, Vec<&str>)> = files
+        .iter()
+        .map(|file| {
+            let depends_on = file
+                .depends_on
+                .iter()
+                .map(String::as_str)
+                .filter(|dep| *dep != file.table && files.iter().any(|other| other.table == *dep))
+                .collect();
+            (file.table.as_str(), depends_on)
+        })
+        .collect();
+    let order = dependency_order(&items).map_err(sqlx::Error::Protocol)?;
+    Ok(order.into_iter().map(|index| files[index].clone()).collect())
+}
+
+/// Les noms de table et de colonne sont insérés tels quels dans les requêtes
+pub(crate) fn check_identifier(name: &str) -> Result<(), sqlx::Error> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(sqlx::Error::Protocol(format!("Invalid identifier in fixture file: {:?}", name)))
+    }
+}
+
+/// Remplace une valeur `{ $ref: table.nom }` par l'`id` correspondant
+fn resolve(value: &Value, ids: &HashMap<String, i64>) -> Result<Value, sqlx::Error> {
+    match value.as_object().and_then(|object| object.get(REF_VALUE_KEY)) {
+        Some(reference) if value.as_object().is_some_and(|object| object.len() == 1) => {
+            let name = reference
+                .as_str()
+                .ok_or_else(|| sqlx::Error::Protocol(format!("Invalid fixture reference: {}", reference)))?;
+            ids.get(name)
+                .map(|id| Value::from(*id))
+                .ok_or_else(|| sqlx::Error::Protocol(format!("Unknown fixture reference: {}", name)))
+        }
+        _ => Ok(value.clone()),
+    }
+}
+
+impl FixtureManager {
+    /// Charge tous les fichiers de fixtures d'un dossier dans une seule transaction.
+    ///
+    /// Les fichiers sont chargés dans l'ordre de `read_fixture_files` : une référence doit
+    /// désigner une ligne d'un fichier précédent ou plus haut dans le même fichier.
+    /// Retourne le nombre de lignes insérées.
+    ///
+    /// En mode simulation, les lignes sont affichées telles quelles, références non résolues.
+    pub async fn load_files(&self, dir: &Path) -> Result<usize, sqlx::Error> {
+        let files = read_fixture_files(dir)?;
+        if files.is_empty() {
+            return Ok(0);
+        }
+        if self.is_dry_run() {
+            let rows = files.iter().map(|file| file.rows.len()).sum();
+            for file in &files {
+                for row in &file.rows {
+                    println!("{}\t{}", file.table, Value::Object(row.clone()));
+                }
+            }
+            info!("Dry run: {} rows from {} fixture files would be loaded", rows, files.len());
+            return Ok(rows);
+        }
+        self.check_migrations().await?;
+        info!("Loading {} fixture files from {}", files.len(), dir.display());
+
+        let mut tx = self.pool().begin().await?;
+        let mut ids: HashMap<String, i64> = HashMap::new();
+        let mut inserted = 0;
+
+        for file in &files {
+            let types = column_types(&mut tx, &file.table).await?;
+            for row in &file.rows {
+                let name = match row.get(REF_KEY) {
+                    Some(Value::String(name)) => Some(format!("{}.{}", file.table, name)),
+                    Some(other) => {
+                        return Err(sqlx::Error::Protocol(format!("Invalid {} in table {}: {}", REF_KEY, file.table, other)));
+                    }
+                    None => None,
+                };
+                let columns: Vec<&String> = row.keys().filter(|column| *column != REF_KEY).collect();
+                // Valeurs envoyées en texte puis converties dans le type de la colonne
+                let mut placeholders = Vec::with_capacity(columns.len());
+                for (i, column) in columns.iter().enumerate() {
+                    let column_type = types.get(column.as_str()).ok_or_else(|| {
+                        sqlx::Error::Protocol(format!("Unknown column {} in table {}", column, file.table))
+                    })?;
+                    placeholders.push(format!("${}::{}", i + 1, column_type));
+                }
+                let mut query = format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    file.table,
+                    columns.iter().map(|column| column.as_str()).collect::<Vec<_>>().join(", "),
+                    placeholders.join(", ")
+                );
+                // Seules les lignes nommées ont besoin de leur identifiant
+                if name.is_some() {
+                    query.push_str(" RETURNING id");
+                }
+
+                let mut query_builder = sqlx::query(&query);
+                for column in &columns {
+                    query_builder = query_builder.bind(json_text(&resolve(&row[*column], &ids)?));
+                }
+
+                match name {
+                    Some(name) => {
+                        let id: i64 = sqlx::Row::try_get(&query_builder.fetch_one(&mut *tx).await?, 0)?;
+                        ids.insert(name, id);
+                    }
+                    None => {
+                        query_builder.execute(&mut *tx).await?;
+                    }
+                }
+                inserted += 1;
+            }
+        }
+
+        tx.commit().await?;
+        info!("Successfully loaded {} rows from fixture files", inserted);
+        Ok(inserted)
+    }
+
+    /// Vide les tables décrites par les fichiers de fixtures, dans l'ordre inverse du chargement
+    pub async fn cleanup_files(&self, dir: &Path) -> Result<(), sqlx::Error> {
+        let files = read_fixture_files(dir)?;
+        for file in files.iter().rev() {
+            self.cleanup_fixtures(&file.table).await?;
+        }
+        Ok(())
+    }
+}
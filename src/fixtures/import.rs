@@ -0,0 +1,341 @@
+//! # Anonymized Import Module
+//!
+//! Ce module importe un export de production dans une base de staging en remplaçant
+//! les données personnelles, colonne par colonne, selon `[import.rules]` :
+//!
+//! ```bash
+//! pg_dump --data-only --table users --table posts --table comments prod_db > dump.sql
+//! cargo run -- --reset-database import dump.sql
+//! ```
+//!
+//! Deux formats sont lus : les blocs `COPY ... FROM stdin` d'un fichier `.sql` (export
+//! `pg_dump` en texte, les autres instructions sont ignorées) et les fichiers `.csv`
+//! nommés d'après leur table, avec une ligne d'en-tête (un champ vide vaut `NULL`).
+//!
+//! Les lignes anonymisées sont chargées par `COPY` dans une seule transaction, les
+//! tables référencées avant celles qui les référencent, puis les séquences sont
+//! recalées sur les identifiants importés.
+
+use crate::{
+    config::{AnonymizeRule, Config, FakerLocale, ImportConfig},
+    fixtures::{
+        dependency_order,
+        files::check_identifier,
+        load::{copy_rows, push_row},
+        locale, FORCE_FIXTURES_FLAG,
+    },
+};
+use fake::rand::{rngs::StdRng, SeedableRng};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Sous-commande important des exports anonymisés au lieu de démarrer le serveur
+pub const IMPORT_COMMAND: &str = "import";
+
+/// Lignes d'une table lues dans un export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpTable {
+    pub table: String,
+    pub columns: Vec<String>,
+    /// Valeurs en texte, `None` pour `NULL`
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+/// Lit la sous-commande `import` et les fichiers qui la suivent. Retourne `None` sans
+/// la sous-commande.
+///
+/// Comme les fixtures, l'import est refusé en production sauf avec `--force-fixtures`
+/// ou `[fixtures] force = true`.
+pub fn import_command<I, S>(config: &Config, args: I) -> Result<Option<Vec<PathBuf>>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (mut requested, mut forced) = (false, config.fixtures.force);
+    let mut paths = Vec::new();
+    for arg in args {
+        match arg.as_ref() {
+            IMPORT_COMMAND if !requested => requested = true,
+            FORCE_FIXTURES_FLAG => forced = true,
+            arg if requested && !arg.starts_with("--") => paths.push(PathBuf::from(arg)),
+            _ => {}
+        }
+    }
+
+    if !requested {
+        return Ok(None);
+    }
+    if paths.is_empty() {
+        return Err(format!("{} expects at least one .sql or .csv file", IMPORT_COMMAND));
+    }
+    if config.is_production() && !forced {
+        return Err(format!("import is disabled in production, use {} to run it anyway", FORCE_FIXTURES_FLAG));
+    }
+    Ok(Some(paths))
+}
+
+/// Lit un export `.sql` (blocs `COPY`) ou `.csv` (une table)
+pub fn read_dump(path: &Path) -> Result<Vec<DumpTable>, sqlx::Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| sqlx::Error::Protocol(format!("Cannot read dump {}: {}", path.display(), e)))?;
+    let tables = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("sql") => parse_copy_blocks(&content),
+        Some("csv") => {
+            let table = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+            parse_csv(table, &content).map(|table| vec![table])
+        }
+        _ => Err("expected a .sql or .csv file".to_string()),
+    }
+    .map_err(|e| sqlx::Error::Protocol(format!("Invalid dump {}: {}", path.display(), e)))?;
+
+    for table in &tables {
+        check_identifier(&table.table)?;
+        table.columns.iter().try_for_each(|column| check_identifier(column))?;
+    }
+    Ok(tables)
+}
+
+/// Extrait les blocs `COPY table (colonnes) FROM stdin;` d'un export `pg_dump`
+pub fn parse_copy_blocks(content: &str) -> Result<Vec<DumpTable>, String> {
+    let mut tables = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let Some(header) = line.strip_prefix("COPY ") else {
+            continue;
+        };
+        let (target, rest) = header.split_once(" (").ok_or_else(|| format!("unsupported COPY statement: {}", line))?;
+        let (columns, _) = rest.split_once(')').ok_or_else(|| format!("unsupported COPY statement: {}", line))?;
+        // `public.users` ou `"public"."users"` : la table est recherchée dans le schéma courant
+        let table = unquote(target.rsplit('.').next().unwrap_or(target));
+        let columns: Vec<String> = columns.split(',').map(|column| unquote(column.trim())).collect();
+
+        let mut rows = Vec::new();
+        for line in lines.by_ref() {
+            if line == "\\." {
+                break;
+            }
+            let row: Vec<Option<String>> = line.split('\t').map(unescape_copy).collect();
+            if row.len() != columns.len() {
+                return Err(format!("{}: expected {} columns, got {}", table, columns.len(), row.len()));
+            }
+            rows.push(row);
+        }
+        tables.push(DumpTable { table, columns, rows });
+    }
+    Ok(tables)
+}
+
+/// Lit un fichier CSV avec une ligne d'en-tête
+pub fn parse_csv(table: &str, content: &str) -> Result<DumpTable, String> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let columns = reader
+        .headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| e.to_string())?;
+            Ok(record.iter().map(|value| (!value.is_empty()).then(|| value.to_string())).collect())
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(DumpTable { table: table.to_string(), columns, rows })
+}
+
+fn unquote(identifier: &str) -> String {
+    identifier.trim_matches('"').to_string()
+}
+
+/// Décode un champ du format texte de `COPY` (`\N` pour `NULL`)
+fn unescape_copy(field: &str) -> Option<String> {
+    if field == "\\N" {
+        return None;
+    }
+    let mut value = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('b') => value.push('\u{8}'),
+            Some('f') => value.push('\u{c}'),
+            Some('v') => value.push('\u{b}'),
+            Some(other) => value.push(other),
+            None => value.push('\\'),
+        }
+    }
+    Some(value)
+}
+
+/// Applique les règles d'anonymisation de `[import]`
+pub struct Anonymizer {
+    salt: String,
+    rules: HashMap<String, AnonymizeRule>,
+    locale: FakerLocale,
+}
+
+impl Anonymizer {
+    /// Utilise le sel configuré, ou un sel aléatoire
+    pub fn new(config: &ImportConfig) -> Self {
+        let salt = config
+            .salt
+            .clone()
+            .unwrap_or_else(|| format!("{:016x}", fake::rand::random::<u64>()));
+        Self { salt, rules: config.rules.clone(), locale: FakerLocale::default() }
+    }
+
+    /// Langue des valeurs de remplacement (`[fixtures] locale`)
+    pub fn with_locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Règle d'une colonne, `Keep` si aucune n'est configurée
+    pub fn rule(&self, table: &str, column: &str) -> AnonymizeRule {
+        self.rules
+            .get(&format!("{}.{}", table, column))
+            .copied()
+            .unwrap_or(AnonymizeRule::Keep)
+    }
+
+    /// Remplace une valeur ; une même valeur donne toujours le même résultat
+    pub fn apply(&self, rule: AnonymizeRule, value: &str) -> Option<String> {
+        let digest = Sha256::new()
+            .chain_update(self.salt.as_bytes())
+            .chain_update([0])
+            .chain_update(value.as_bytes())
+            .finalize();
+        let hex = hex::encode(digest);
+        // Générateur propre à la valeur et à la règle : les remplacements sont reproductibles
+        let seed = u64::from_be_bytes(digest[..8].try_into().expect("digest is 32 bytes")) ^ rule as u64;
+        let rng = &mut StdRng::seed_from_u64(seed);
+
+        let replaced = match rule {
+            AnonymizeRule::Keep => value.to_string(),
+            AnonymizeRule::Null => return None,
+            AnonymizeRule::Hash => match value.parse::<i64>() {
+                // 53 bits : pas de collision en pratique, et des identifiants exacts en JSON
+                Ok(_) => ((u64::from_be_bytes(digest[..8].try_into().expect("digest is 32 bytes")) >> 11) + 1).to_string(),
+                Err(_) => hex[..16].to_string(),
+            },
+            AnonymizeRule::Email => {
+                let username = locale::username(self.locale, rng);
+                format!("{}.{}@example.com", locale::email_local_part(&username), &hex[..12])
+            }
+            AnonymizeRule::Name => locale::name(self.locale, rng),
+            AnonymizeRule::FirstName => locale::first_name(self.locale, rng),
+            AnonymizeRule::LastName => locale::last_name(self.locale, rng),
+            AnonymizeRule::Username => format!("{}_{}", locale::username(self.locale, rng), &hex[..6]),
+            AnonymizeRule::Phone => locale::phone_number(self.locale, rng),
+            AnonymizeRule::Sentence => locale::sentence(self.locale, rng, 4..12),
+            AnonymizeRule::Paragraph => locale::paragraph(self.locale, rng, 2..5),
+        };
+        Some(replaced)
+    }
+
+    /// Anonymise toutes les lignes d'une table
+    pub fn anonymize(&self, dump: &mut DumpTable) {
+        let rules: Vec<AnonymizeRule> = dump.columns.iter().map(|column| self.rule(&dump.table, column)).collect();
+        for row in &mut dump.rows {
+            for (value, rule) in row.iter_mut().zip(&rules) {
+                if *rule != AnonymizeRule::Keep
+                    && let Some(original) = value.take()
+                {
+                    *value = self.apply(*rule, &original);
+                }
+            }
+        }
+    }
+}
+
+/// Lit, anonymise et charge des exports dans une seule transaction, les remplacements
+/// étant générés dans la langue `locale`.
+/// Retourne le nombre de lignes importées par table, dans l'ordre de chargement.
+pub async fn import_dumps(
+    pool: &PgPool,
+    paths: &[PathBuf],
+    config: &ImportConfig,
+    locale: FakerLocale,
+) -> Result<Vec<(String, u64)>, sqlx::Error> {
+    let mut dumps = Vec::new();
+    for path in paths {
+        dumps.extend(read_dump(path)?);
+    }
+    let anonymizer = Anonymizer::new(config).with_locale(locale);
+    dumps.iter_mut().for_each(|dump| anonymizer.anonymize(dump));
+
+    let mut tx = pool.begin().await?;
+    let order = load_order(&mut tx, &dumps).await?;
+    let mut imported = Vec::new();
+    for index in order {
+        let dump = &dumps[index];
+        let statement = format!("COPY {} ({}) FROM STDIN", dump.table, dump.columns.join(", "));
+        let rows = copy_rows(&mut tx, &dump.table, &statement, dump.rows.len() as u64, |i, buf| {
+            push_row(buf, dump.rows[i as usize].iter().map(Option::as_deref));
+        })
+        .await?;
+        for column in &dump.columns {
+            reset_sequence(&mut tx, &dump.table, column).await?;
+        }
+        imported.push((dump.table.clone(), rows));
+    }
+    tx.commit().await?;
+
+    info!(
+        "Imported {} anonymized rows into {} tables",
+        imported.iter().map(|(_, rows)| rows).sum::<u64>(),
+        imported.len()
+    );
+    Ok(imported)
+}
+
+/// Ordre de chargement : les tables référencées par une clé étrangère d'abord
+async fn load_order(conn: &mut PgConnection, dumps: &[DumpTable]) -> Result<Vec<usize>, sqlx::Error> {
+    let references: Vec<(String, String)> = sqlx::query_as(
+        "SELECT conrelid::regclass::text, confrelid::regclass::text FROM pg_constraint WHERE contype = 'f'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let items: Vec<(&str, Vec<&str>)> = dumps
+        .iter()
+        .map(|dump| {
+            let depends_on = references
+                .iter()
+                .filter(|(table, referenced)| {
+                    *table == dump.table && *referenced != dump.table && dumps.iter().any(|other| other.table == *referenced)
+                })
+                .map(|(_, referenced)| referenced.as_str())
+                .collect();
+            (dump.table.as_str(), depends_on)
+        })
+        .collect();
+    dependency_order(&items).map_err(sqlx::Error::Protocol)
+}
+
+/// Place la séquence d'une colonne `serial` après la plus grande valeur importée
+async fn reset_sequence(conn: &mut PgConnection, table: &str, column: &str) -> Result<(), sqlx::Error> {
+    let sequence: Option<String> = sqlx::query_scalar("SELECT pg_get_serial_sequence($1, $2)")
+        .bind(table)
+        .bind(column)
+        .fetch_one(&mut *conn)
+        .await?;
+    if let Some(sequence) = sequence {
+        sqlx::query(&format!("SELECT setval($1, (SELECT COALESCE(MAX({}), 0) + 1 FROM {}), false)", column, table))
+            .bind(sequence)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
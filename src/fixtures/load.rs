@@ -0,0 +1,258 @@
+//! # Load Data Module
+//!
+//! Ce module génère des millions de lignes réalistes (utilisateurs, posts, commentaires)
+//! pour tester les performances sur un volume significatif. Les lignes sont produites par
+//! les fabriques des fixtures puis envoyées par `COPY ... FROM STDIN`, par blocs, avec
+//! une progression journalisée.
+//!
+//! ```bash
+//! cargo run --release -- generate-load --users 100000 --posts 1000000 --comments 3000000
+//! ```
+//!
+//! Les lignes s'ajoutent aux données existantes ; les emails portent un préfixe propre
+//! à chaque exécution pour rester uniques.
+
+use crate::{
+    config::{Config, FakerLocale},
+    fixtures::{
+        factory::Factory,
+        fixture_rng, fixture_seed,
+        post::{CommentFactory, PostFactory},
+        user::UserFactory,
+        FORCE_FIXTURES_FLAG,
+    },
+    models::{post::PostId, user::UserId},
+};
+use fake::rand::{seq::IndexedRandom, Rng};
+use sqlx::{PgConnection, PgPool};
+use std::time::Instant;
+use tracing::info;
+
+/// Sous-commande générant les données de charge au lieu de démarrer le serveur
+pub const GENERATE_LOAD_COMMAND: &str = "generate-load";
+
+/// Lignes envoyées par bloc `COPY` ; la progression est journalisée après chaque bloc
+const CHUNK_ROWS: u64 = 50_000;
+
+/// Volumes demandés à `generate-load`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadSpec {
+    pub users: u64,
+    pub posts: u64,
+    pub comments: u64,
+}
+
+impl LoadSpec {
+    /// Les posts ont besoin d'auteurs, les commentaires de posts
+    pub fn validate(&self) -> Result<(), String> {
+        if self.posts > 0 && self.users == 0 {
+            return Err("posts need at least one user".to_string());
+        }
+        if self.comments > 0 && self.posts == 0 {
+            return Err("comments need at least one post".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for LoadSpec {
+    fn default() -> Self {
+        Self {
+            users: 100_000,
+            posts: 1_000_000,
+            comments: 3_000_000,
+        }
+    }
+}
+
+/// Lit la sous-commande `generate-load` et ses options (`--users`, `--posts`, `--comments`,
+/// suivies d'un nombre ou avec `=`). Retourne `None` sans la sous-commande.
+///
+/// Comme les fixtures, la génération est refusée en production sauf avec
+/// `--force-fixtures` ou `[fixtures] force = true`.
+pub fn load_command<I, S>(config: &Config, args: I) -> Result<Option<LoadSpec>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (mut requested, mut forced) = (false, config.fixtures.force);
+    let mut spec = LoadSpec::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let arg = arg.as_ref();
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg, None),
+        };
+        let target = match name {
+            GENERATE_LOAD_COMMAND => {
+                requested = true;
+                continue;
+            }
+            FORCE_FIXTURES_FLAG => {
+                forced = true;
+                continue;
+            }
+            "--users" => &mut spec.users,
+            "--posts" => &mut spec.posts,
+            "--comments" => &mut spec.comments,
+            _ => continue,
+        };
+        let value = match inline {
+            Some(value) => value,
+            None => args.next().ok_or_else(|| format!("{} expects a number of rows", name))?.as_ref().to_string(),
+        };
+        *target = value.parse().map_err(|_| format!("{} expects a number of rows, got {:?}", name, value))?;
+    }
+
+    if !requested {
+        return Ok(None);
+    }
+    spec.validate()?;
+    if config.is_production() && !forced {
+        return Err(format!(
+            "load data generation is disabled in production, use {} to run it anyway",
+            FORCE_FIXTURES_FLAG
+        ));
+    }
+    Ok(Some(spec))
+}
+
+/// Génère les lignes demandées : utilisateurs, puis posts répartis au hasard entre ces
+/// utilisateurs, puis commentaires répartis entre ces posts, dans la langue `locale`.
+/// Avec `seed`, les données générées sont reproductibles. Retourne le nombre de lignes
+/// insérées par table.
+pub async fn generate_load(
+    pool: &PgPool,
+    spec: LoadSpec,
+    seed: Option<u64>,
+    locale: FakerLocale,
+) -> Result<LoadSpec, sqlx::Error> {
+    spec.validate().map_err(sqlx::Error::Protocol)?;
+    let seed = fixture_seed(seed);
+    let started = Instant::now();
+    info!(
+        "Generating load data: {} users, {} posts, {} comments (seed {})",
+        spec.users, spec.posts, spec.comments, seed
+    );
+
+    let run = format!("load{:08x}", fake::rand::random::<u32>());
+    let mut rng = fixture_rng(seed, "load-users");
+    let factory = UserFactory::new().locale(locale);
+    let mut conn = pool.acquire().await?;
+    let users = copy_rows(&mut conn, "users", "COPY users (email, name) FROM STDIN", spec.users, |i, buf| {
+        let user = factory.build_with(&mut rng);
+        // Index dans l'email : unicité garantie quel que soit le volume
+        push_row(buf, [Some(format!("{}.{}.{}", run, i, user.email).as_str()), Some(&user.name)]);
+    })
+    .await?;
+    let user_ids: Vec<UserId> = sqlx::query_scalar("SELECT id FROM users WHERE email LIKE $1 || '.%' ORDER BY id")
+        .bind(&run)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut rng = fixture_rng(seed, "load-posts");
+    let posts = copy_rows(&mut conn, "posts", "COPY posts (user_id, title, body) FROM STDIN", spec.posts, |_, buf| {
+        let user_id = pick(&user_ids, &mut rng);
+        let post = PostFactory::new(user_id).locale(locale).build_with(&mut rng);
+        push_row(buf, [Some(post.user_id.to_string().as_str()), Some(&post.title), Some(&post.body)]);
+    })
+    .await?;
+    let post_ids: Vec<PostId> = sqlx::query_scalar(
+        "SELECT posts.id FROM posts JOIN users ON users.id = posts.user_id WHERE users.email LIKE $1 || '.%' ORDER BY posts.id",
+    )
+    .bind(&run)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut rng = fixture_rng(seed, "load-comments");
+    let comments = copy_rows(
+        &mut conn,
+        "comments",
+        "COPY comments (post_id, user_id, body) FROM STDIN",
+        spec.comments,
+        |_, buf| {
+            let post_id = pick(&post_ids, &mut rng);
+            let user_id = pick(&user_ids, &mut rng);
+            let comment = CommentFactory::new(post_id).author(user_id).locale(locale).build_with(&mut rng);
+            push_row(
+                buf,
+                [Some(comment.post_id.to_string().as_str()), Some(user_id.to_string().as_str()), Some(&comment.body)],
+            );
+        },
+    )
+    .await?;
+
+    info!("Load data generated in {:.1}s", started.elapsed().as_secs_f64());
+    Ok(LoadSpec { users, posts, comments })
+}
+
+/// Envoie `total` lignes produites par `row` avec une instruction `COPY`, bloc par bloc,
+/// en journalisant la progression. Retourne le nombre de lignes insérées.
+pub(crate) async fn copy_rows(
+    conn: &mut PgConnection,
+    table: &str,
+    statement: &str,
+    total: u64,
+    mut row: impl FnMut(u64, &mut String),
+) -> Result<u64, sqlx::Error> {
+    if total == 0 {
+        return Ok(0);
+    }
+    let started = Instant::now();
+    let mut copy = conn.copy_in_raw(statement).await?;
+    let mut buf = String::new();
+    let mut done = 0;
+    while done < total {
+        let end = (done + CHUNK_ROWS).min(total);
+        buf.clear();
+        for i in done..end {
+            row(i, &mut buf);
+        }
+        if let Err(e) = copy.send(buf.as_bytes()).await {
+            copy.abort(e.to_string()).await.ok();
+            return Err(e);
+        }
+        done = end;
+        let elapsed = started.elapsed().as_secs_f64();
+        info!(
+            "{}: {}/{} rows ({:.0}%, {:.0} rows/s)",
+            table,
+            done,
+            total,
+            done as f64 * 100.0 / total as f64,
+            done as f64 / elapsed.max(f64::EPSILON)
+        );
+    }
+    copy.finish().await
+}
+
+/// Ajoute une ligne au format texte de `COPY` : colonnes séparées par des tabulations,
+/// `None` pour `NULL`
+pub(crate) fn push_row<'a>(buf: &mut String, columns: impl IntoIterator<Item = Option<&'a str>>) {
+    for (index, column) in columns.into_iter().enumerate() {
+        if index > 0 {
+            buf.push('\t');
+        }
+        let Some(column) = column else {
+            buf.push_str("\\N");
+            continue;
+        };
+        for c in column.chars() {
+            match c {
+                '\\' => buf.push_str("\\\\"),
+                '\t' => buf.push_str("\\t"),
+                '\n' => buf.push_str("\\n"),
+                '\r' => buf.push_str("\\r"),
+                c => buf.push(c),
+            }
+        }
+    }
+    buf.push('\n');
+}
+
+/// Identifiant pris au hasard ; les listes sont non vides (voir `LoadSpec::validate`)
+fn pick<T: Copy, R: Rng + ?Sized>(ids: &[T], rng: &mut R) -> T {
+    *ids.choose(rng).expect("no rows to reference")
+}
+
@@ -0,0 +1,93 @@
+//! # Locale Module
+//!
+//! Ce module génère les valeurs fictives dans la langue de `[fixtures] locale` : les
+//! fabriques, `generate-load` et les remplacements de l'`import` passent par ces fonctions
+//! plutôt que par les fakers anglais (`fake::faker::name::en::Name`...).
+//!
+//! ```ignore
+//! let name = locale::name(FakerLocale::FrFr, rng);
+//! let post = PostFactory::new(user.id).locale(FakerLocale::FrFr).build_with(rng);
+//! ```
+
+use fake::{rand::Rng, Fake};
+use std::ops::Range;
+
+use crate::config::FakerLocale;
+
+// `fake` n'a qu'un lorem ipsum, commun à toutes les langues : `sentence` et `paragraph`
+// passent tout de même par la langue pour suivre les versions qui en ajouteraient.
+
+/// Appelle le faker `module::Faker(args)` de la langue demandée
+macro_rules! localized {
+    ($locale:expr, $rng:expr, $module:ident::$faker:ident($($arg:expr),*)) => {
+        match $locale {
+            FakerLocale::EnUs => fake::faker::$module::en::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::FrFr => fake::faker::$module::fr_fr::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::DeDe => fake::faker::$module::de_de::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::ItIt => fake::faker::$module::it_it::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::PtBr => fake::faker::$module::pt_br::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::PtPt => fake::faker::$module::pt_pt::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::JaJp => fake::faker::$module::ja_jp::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::ZhCn => fake::faker::$module::zh_cn::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::ZhTw => fake::faker::$module::zh_tw::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+            FakerLocale::ArSa => fake::faker::$module::ar_sa::$faker($($arg),*).fake_with_rng::<String, _>($rng),
+        }
+    };
+}
+
+pub fn name<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R) -> String {
+    localized!(locale, rng, name::Name())
+}
+
+pub fn first_name<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R) -> String {
+    localized!(locale, rng, name::FirstName())
+}
+
+pub fn last_name<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R) -> String {
+    localized!(locale, rng, name::LastName())
+}
+
+/// Identifiant tiré des prénoms de la langue, éventuellement hors ASCII (voir `email_local_part`)
+pub fn username<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R) -> String {
+    localized!(locale, rng, internet::Username())
+}
+
+pub fn phone_number<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R) -> String {
+    localized!(locale, rng, phone_number::PhoneNumber())
+}
+
+pub fn sentence<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R, words: Range<usize>) -> String {
+    localized!(locale, rng, lorem::Sentence(words))
+}
+
+pub fn paragraph<R: Rng + ?Sized>(locale: FakerLocale, rng: &mut R, sentences: Range<usize>) -> String {
+    localized!(locale, rng, lorem::Paragraph(sentences))
+}
+
+/// Partie locale d'email tirée d'un identifiant : minuscules ASCII, chiffres, `.` et `_`.
+/// Les caractères accentués perdent leur accent ; un identifiant sans caractère ASCII
+/// (japonais, chinois, arabe) devient `user`.
+pub fn email_local_part(username: &str) -> String {
+    let local: String = username
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => Some('a'),
+            'ç' => Some('c'),
+            'è' | 'é' | 'ê' | 'ë' => Some('e'),
+            'ì' | 'í' | 'î' | 'ï' => Some('i'),
+            'ñ' => Some('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => Some('o'),
+            'ù' | 'ú' | 'û' | 'ü' => Some('u'),
+            'ß' => Some('s'),
+            c if c.is_ascii_alphanumeric() || c == '.' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect();
+    let local = local.trim_matches('.');
+    if local.is_empty() {
+        "user".to_string()
+    } else {
+        local.to_string()
+    }
+}
@@ -1,35 +1,309 @@
-mod dummy;
+pub mod dummy;
 mod common;
+pub mod export;
+pub mod factory;
+pub mod files;
+pub mod import;
+pub mod load;
+pub mod locale;
+pub mod post;
+pub mod principal;
+pub mod user;
+use fake::rand::{rngs::StdRng, SeedableRng};
+use futures::{future::BoxFuture, FutureExt};
 use sqlx::{Pool, Postgres};
+use std::{collections::HashSet, path::Path};
 use tracing::{info, warn};
 use dummy::{create_dummy, clean_dummy};
+use post::{clean_posts, create_posts};
+use principal::{clean_principals, create_principals};
+use user::{clean_users, create_users};
+pub use common::FixtureManager;
+pub use factory::Factory;
+use crate::{config::{Config, FixtureProfile, FixturesConfig}, models::events::EventKind, services::events::try_record_event};
 
-async fn clean_fixtures(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+/// Option de ligne de commande demandant le chargement des fixtures
+pub const FIXTURES_FLAG: &str = "--fixtures";
+/// Option de ligne de commande autorisant le chargement en production
+pub const FORCE_FIXTURES_FLAG: &str = "--force-fixtures";
+/// Option de ligne de commande choisissant le profil de volume (`--fixtures-profile demo`)
+pub const FIXTURES_PROFILE_FLAG: &str = "--fixtures-profile";
+/// Option de ligne de commande vidant toutes les tables avant le démarrage
+pub const RESET_DATABASE_FLAG: &str = "--reset-database";
+/// Option de ligne de commande affichant les fixtures au lieu de les insérer
+pub const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// Choix de démarrage concernant les fixtures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixturesDecision {
+    /// Aucune demande : la base n'est pas modifiée
+    Skip,
+    /// Chargement demandé et autorisé ; avec `dry_run`, les lignes sont seulement affichées
+    Run { clean: bool, profile: FixtureProfile, dry_run: bool },
+}
+
+/// Décide du chargement des fixtures d'après `[fixtures]` et les arguments de la ligne de commande.
+///
+/// Le chargement n'a lieu que sur demande explicite (`--fixtures` ou `enabled = true`).
+/// En production, il est refusé sauf avec `--force-fixtures` ou `force = true`.
+/// `--fixtures-profile <nom>` (ou `--fixtures-profile=<nom>`) remplace `[fixtures] profile`
+/// et demande le chargement. Avec `--dry-run`, les lignes sont affichées sans modifier la
+/// base, ce qui est aussi permis en production.
+pub fn fixtures_decision<I, S>(config: &Config, args: I) -> Result<FixturesDecision, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (mut requested, mut forced) = (config.fixtures.enabled, config.fixtures.force);
+    let mut profile = config.fixtures.profile;
+    let mut dry_run = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            FIXTURES_FLAG => requested = true,
+            FORCE_FIXTURES_FLAG => (requested, forced) = (true, true),
+            DRY_RUN_FLAG => dry_run = true,
+            FIXTURES_PROFILE_FLAG => {
+                let name = args.next().ok_or_else(|| format!("{} expects a profile name", FIXTURES_PROFILE_FLAG))?;
+                profile = name.as_ref().parse()?;
+                requested = true;
+            }
+            arg => {
+                if let Some(name) = arg.strip_prefix(FIXTURES_PROFILE_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+                    profile = name.parse()?;
+                    requested = true;
+                }
+            }
+        }
+    }
+
+    if !requested {
+        return Ok(FixturesDecision::Skip);
+    }
+    if config.is_production() && !forced && !dry_run {
+        return Err(format!(
+            "fixtures are disabled in production, use {} to load them anyway",
+            FORCE_FIXTURES_FLAG
+        ));
+    }
+    Ok(FixturesDecision::Run { clean: config.fixtures.clean, profile, dry_run })
+}
+
+/// Indique si `--reset-database` demande de vider la base (voir `FixtureManager::reset`).
+///
+/// Comme les fixtures, la remise à zéro est refusée en production sauf avec
+/// `--force-fixtures` ou `[fixtures] force = true`.
+pub fn reset_requested<I, S>(config: &Config, args: I) -> Result<bool, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (mut requested, mut forced) = (false, config.fixtures.force);
+    for arg in args {
+        match arg.as_ref() {
+            RESET_DATABASE_FLAG => requested = true,
+            FORCE_FIXTURES_FLAG => forced = true,
+            _ => {}
+        }
+    }
+
+    if requested && config.is_production() && !forced {
+        return Err(format!(
+            "database reset is disabled in production, add {} to reset anyway",
+            FORCE_FIXTURES_FLAG
+        ));
+    }
+    Ok(requested)
+}
+
+/// Générateur aléatoire des fixtures, reproductible à partir d'une graine
+pub type FixtureRng = StdRng;
+
+/// Graine des fixtures : celle de la configuration, sinon une nouvelle à chaque exécution
+pub fn fixture_seed(configured: Option<u64>) -> u64 {
+    configured.unwrap_or_else(fake::rand::random)
+}
+
+/// Générateur d'un jeu de fixtures.
+///
+/// Chaque jeu a son propre générateur dérivé de la graine et de son nom : ajouter
+/// un jeu ou modifier le nombre de lignes d'un autre ne change pas ses données.
+pub fn fixture_rng(seed: u64, name: &str) -> FixtureRng {
+    // FNV-1a : stable d'une version de Rust et d'une machine à l'autre
+    let hash = name
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    FixtureRng::seed_from_u64(seed ^ hash)
+}
+
+/// Étape de chargement d'un jeu de fixtures, avec le nombre de lignes du profil choisi
+pub type FixtureFn = for<'a> fn(&'a FixtureManager, &'a mut FixtureRng, u32) -> BoxFuture<'a, Result<(), sqlx::Error>>;
+/// Étape de nettoyage d'un jeu de fixtures
+pub type CleanFn = for<'a> fn(&'a Pool<Postgres>) -> BoxFuture<'a, Result<(), sqlx::Error>>;
+
+/// Jeu de fixtures et jeux dont il dépend (clés étrangères)
+#[derive(Clone, Copy)]
+pub struct FixtureSet {
+    pub name: &'static str,
+    /// Jeux chargés avant celui-ci et nettoyés après
+    pub depends_on: &'static [&'static str],
+    pub create: FixtureFn,
+    pub clean: CleanFn,
+}
+
+/// Jeux de fixtures de l'application, dans un ordre quelconque
+pub fn fixture_sets() -> Vec<FixtureSet> {
+    vec![
+        FixtureSet {
+            name: "dummy",
+            depends_on: &[],
+            create: |manager, rng, rows| create_dummy(manager, rng, rows).boxed(),
+            clean: |pool| clean_dummy(pool).boxed(),
+        },
+        FixtureSet {
+            name: "users",
+            depends_on: &[],
+            create: |manager, rng, rows| create_users(manager, rng, rows).boxed(),
+            clean: |pool| clean_users(pool).boxed(),
+        },
+        FixtureSet {
+            name: "principals",
+            depends_on: &["users"],
+            create: |manager, rng, rows| create_principals(manager, rng, rows).boxed(),
+            clean: |pool| clean_principals(pool).boxed(),
+        },
+        FixtureSet {
+            name: "posts",
+            depends_on: &["users"],
+            create: |manager, rng, rows| create_posts(manager, rng, rows).boxed(),
+            clean: |pool| clean_posts(pool).boxed(),
+        },
+    ]
+}
+
+/// Ordonne des éléments `(nom, dépendances)` pour que chacun suive ses dépendances.
+///
+/// L'ordre de déclaration est conservé entre éléments indépendants. Retourne les
+/// indices dans l'ordre de chargement, ou une erreur pour une dépendance inconnue
+/// ou un cycle.
+pub fn dependency_order<N, D>(items: &[(N, D)]) -> Result<Vec<usize>, String>
+where
+    N: AsRef<str>,
+    D: AsRef<[N]>,
+{
+    let names: Vec<&str> = items.iter().map(|(name, _)| name.as_ref()).collect();
+    for (name, depends_on) in items {
+        if let Some(unknown) = depends_on.as_ref().iter().find(|dep| !names.contains(&dep.as_ref())) {
+            return Err(format!("fixture set {} depends on unknown set {}", name.as_ref(), unknown.as_ref()));
+        }
+    }
+
+    // Une dépendance est satisfaite quand tous les éléments de ce nom sont chargés
+    let mut done = vec![false; items.len()];
+    let loaded = |done: &[bool], dep: &str| names.iter().zip(done).all(|(name, done)| *name != dep || *done);
+    let mut order = Vec::with_capacity(items.len());
+    while order.len() < items.len() {
+        let ready = items
+            .iter()
+            .enumerate()
+            .position(|(index, (_, depends_on))| !done[index] && depends_on.as_ref().iter().all(|dep| loaded(&done, dep.as_ref())));
+        let Some(index) = ready else {
+            let blocked: HashSet<&str> = names.iter().zip(&done).filter(|(_, done)| !**done).map(|(name, _)| *name).collect();
+            let mut blocked: Vec<&str> = blocked.into_iter().collect();
+            blocked.sort_unstable();
+            return Err(format!("circular fixture dependencies between {}", blocked.join(", ")));
+        };
+        done[index] = true;
+        order.push(index);
+    }
+    Ok(order)
+}
+
+/// Jeux de fixtures dans l'ordre de chargement
+fn ordered_sets() -> Result<Vec<FixtureSet>, sqlx::Error> {
+    let sets = fixture_sets();
+    let items: Vec<(&str, &[&str])> = sets.iter().map(|set| (set.name, set.depends_on)).collect();
+    let order = dependency_order(&items).map_err(sqlx::Error::Protocol)?;
+    Ok(order.into_iter().map(|index| sets[index]).collect())
+}
+
+async fn clean_fixtures(pool: &Pool<Postgres>, directory: &Path) -> Result<(), sqlx::Error> {
     info!("Cleaning fixtures...");
 
-    clean_dummy(pool).await.map_err(|e| {
-        warn!("Error cleaning fixtures: {}", e);
-        e
-    })
+    FixtureManager::new(pool.clone()).cleanup_files(directory).await?;
+
+    // Ordre inverse du chargement : les dépendants d'abord
+    for set in ordered_sets()?.iter().rev() {
+        (set.clean)(pool).await.map_err(|e| {
+            warn!("Error cleaning fixtures {}: {}", set.name, e);
+            e
+        })?;
+    }
+    Ok(())
 }
 
-async fn load_fixtures(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
-    info!("Loading fixtures...");
+async fn load_fixtures(manager: &FixtureManager, directory: &Path, seed: u64, profile: FixtureProfile) -> Result<(), sqlx::Error> {
+    info!("Loading fixtures ({} profile, {} rows per set)...", profile.as_str(), profile.rows());
 
-    create_dummy(pool).await.map_err(|e| {
+    for set in ordered_sets()? {
+        (set.create)(manager, &mut fixture_rng(seed, set.name), profile.rows()).await.map_err(|e| {
+            warn!("Error loading fixtures {}: {}", set.name, e);
+            e
+        })?;
+    }
+    manager.load_files(directory).await.map_err(|e| {
         warn!("Error loading fixtures: {}", e);
         e
-    })
+    })?;
+    Ok(())
 }
+
 /// Structure pour gérer les fixtures de test
-pub async fn run_fixtures(pool: &Pool<Postgres>, clean : bool) -> Result<(), sqlx::Error> {
-    info!("Running fixtures...");
+///
+/// Les fichiers YAML/JSON de `[fixtures] directory` (voir `files`) sont chargés après
+/// les fixtures Rust. La graine utilisée est journalisée pour pouvoir rejouer les mêmes données.
+///
+/// Avec `dry_run`, rien n'est nettoyé ni inséré : les lignes générées sont affichées. Les
+/// jeux qui répartissent leurs lignes entre des lignes existantes (posts entre utilisateurs)
+/// se basent alors sur le contenu actuel de la base.
+pub async fn run_fixtures(
+    pool: &Pool<Postgres>,
+    clean: bool,
+    profile: FixtureProfile,
+    dry_run: bool,
+    config: &FixturesConfig,
+) -> Result<(), sqlx::Error> {
+    let directory = Path::new(&config.directory);
+    let seed = fixture_seed(config.seed);
+    info!("Running fixtures with seed {} (set [fixtures] seed to reproduce)", seed);
+    let manager = FixtureManager::new(pool.clone())
+        .with_batch_size(config.batch_size)
+        .with_conflicts(config.on_conflict.clone())
+        .with_locale(config.locale)
+        .with_dry_run(dry_run);
+
+    if dry_run {
+        if clean {
+            info!("Dry run: fixture tables would be cleaned first");
+        }
+        load_fixtures(&manager, directory, seed, profile).await?;
+        info!("Dry run finished, the database was not modified");
+        return Ok(());
+    }
 
     // delete this, it's just an example of use
     if clean {
-        clean_fixtures(pool).await?;
+        clean_fixtures(pool, directory).await?;
     }
-    load_fixtures(pool).await?;
+    load_fixtures(&manager, directory, seed, profile).await?;
+
+    try_record_event(
+        pool,
+        EventKind::Fixtures,
+        &format!("Fixtures loaded (clean: {}, profile: {}, seed: {})", clean, profile.as_str(), seed),
+        serde_json::json!({ "clean": clean, "profile": profile.as_str(), "seed": seed }),
+    )
+    .await;
     
     info!("Fixtures run successfully");
     Ok(())
@@ -0,0 +1,179 @@
+use crate::{
+    config::FakerLocale,
+    fixtures::{common::FixtureManager, factory::Factory, locale, FixtureRng},
+    models::{
+        post::{Comment, Post, PostId},
+        user::UserId,
+    },
+};
+use async_trait::async_trait;
+use fake::rand::{seq::IndexedRandom, Rng};
+use serde::Serialize;
+use sqlx::{PgPool, Pool, Postgres};
+use tracing::info;
+
+/// Ligne insérée dans la table `posts`
+#[derive(Debug, Serialize)]
+pub struct PostFixture {
+    pub user_id: UserId,
+    pub title: String,
+    pub body: String,
+}
+
+/// Ligne insérée dans la table `comments`
+#[derive(Debug, Serialize)]
+pub struct CommentFixture {
+    pub post_id: PostId,
+    pub user_id: Option<UserId>,
+    pub body: String,
+}
+
+/// Fabrique de posts d'un auteur existant
+#[derive(Debug, Clone)]
+pub struct PostFactory {
+    user_id: UserId,
+    title: Option<String>,
+    body: Option<String>,
+    locale: FakerLocale,
+}
+
+impl PostFactory {
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id, title: None, body: None, locale: FakerLocale::default() }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Langue des textes générés (`en_US` par défaut)
+    pub fn locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+#[async_trait]
+impl Factory for PostFactory {
+    type Row = PostFixture;
+    type Model = Post;
+
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> PostFixture {
+        let title = locale::sentence(self.locale, rng, 3..8);
+        let body = locale::paragraph(self.locale, rng, 2..5);
+        PostFixture {
+            user_id: self.user_id,
+            title: self.title.clone().unwrap_or(title),
+            body: self.body.clone().unwrap_or(body),
+        }
+    }
+
+    async fn insert(pool: &PgPool, row: PostFixture) -> Result<Post, sqlx::Error> {
+        sqlx::query_as::<_, Post>("INSERT INTO posts (user_id, title, body) VALUES ($1, $2, $3) RETURNING *")
+            .bind(row.user_id)
+            .bind(row.title)
+            .bind(row.body)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+/// Fabrique de commentaires d'un post existant, sans auteur par défaut
+#[derive(Debug, Clone)]
+pub struct CommentFactory {
+    post_id: PostId,
+    user_id: Option<UserId>,
+    body: Option<String>,
+    locale: FakerLocale,
+}
+
+impl CommentFactory {
+    pub fn new(post_id: PostId) -> Self {
+        Self { post_id, user_id: None, body: None, locale: FakerLocale::default() }
+    }
+
+    pub fn author(mut self, user_id: UserId) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Langue des textes générés (`en_US` par défaut)
+    pub fn locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+#[async_trait]
+impl Factory for CommentFactory {
+    type Row = CommentFixture;
+    type Model = Comment;
+
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> CommentFixture {
+        let body = locale::sentence(self.locale, rng, 4..16);
+        CommentFixture {
+            post_id: self.post_id,
+            user_id: self.user_id,
+            body: self.body.clone().unwrap_or(body),
+        }
+    }
+
+    async fn insert(pool: &PgPool, row: CommentFixture) -> Result<Comment, sqlx::Error> {
+        sqlx::query_as::<_, Comment>("INSERT INTO comments (post_id, user_id, body) VALUES ($1, $2, $3) RETURNING *")
+            .bind(row.post_id)
+            .bind(row.user_id)
+            .bind(row.body)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+/// Crée `rows` posts répartis entre les utilisateurs existants, puis `rows` commentaires
+/// répartis entre les posts, d'auteurs pris au hasard : à charger après les utilisateurs.
+pub async fn create_posts(fixture_manager: &FixtureManager, rng: &mut FixtureRng, rows: u32) -> Result<(), sqlx::Error> {
+    info!("Creating posts...");
+    let pool = fixture_manager.pool();
+    let locale = fixture_manager.locale();
+
+    // Tri par id : avec une graine fixe, les mêmes lignes reçoivent les mêmes données
+    let user_ids: Vec<UserId> = sqlx::query_scalar("SELECT id FROM users ORDER BY id").fetch_all(pool).await?;
+    let posts = user_ids
+        .iter()
+        .cycle()
+        .take(rows as usize)
+        .map(|user_id| PostFactory::new(*user_id).locale(locale).build_with(rng))
+        .collect();
+    fixture_manager.submit_fixtures(posts, "posts").await?;
+
+    let post_ids: Vec<PostId> = sqlx::query_scalar("SELECT id FROM posts ORDER BY id").fetch_all(pool).await?;
+    let comments = post_ids
+        .iter()
+        .cycle()
+        .take(rows as usize)
+        .filter_map(|post_id| {
+            let user_id = *user_ids.choose(rng)?;
+            Some(CommentFactory::new(*post_id).author(user_id).locale(locale).build_with(rng))
+        })
+        .collect();
+    fixture_manager.submit_fixtures(comments, "comments").await?;
+    Ok(())
+}
+
+pub async fn clean_posts(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    info!("Cleaning posts...");
+    let fixture_manager = FixtureManager::new(pool.clone());
+    fixture_manager.cleanup_fixtures("comments").await?;
+    fixture_manager.cleanup_fixtures("posts").await?;
+    Ok(())
+}
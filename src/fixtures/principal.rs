@@ -0,0 +1,169 @@
+use crate::{
+    config::FakerLocale,
+    fixtures::{common::FixtureManager, factory::Factory, locale, FixtureRng},
+    models::{auth::Role, user::User},
+    services::auth::{hash_password, issue_api_key, set_credentials, API_KEY_PREFIX},
+};
+use async_trait::async_trait;
+use fake::rand::{distr::Alphanumeric, Rng};
+use serde::Serialize;
+use sqlx::{PgPool, Pool, Postgres};
+use tracing::info;
+
+/// Nom des clés d'API émises par les fixtures, remplacées à chaque chargement
+pub const FIXTURE_KEY_NAME: &str = "fixtures";
+
+/// Utilisateur authentifiable : ligne de `users`, mot de passe et clé d'API en clair
+#[derive(Debug, Clone, Serialize)]
+pub struct PrincipalFixture {
+    pub email: String,
+    pub name: String,
+    pub role: Role,
+    pub password: String,
+    pub api_key: String,
+}
+
+/// Principal enregistré, avec les secrets en clair pour s'authentifier dans les tests
+#[derive(Debug, Clone)]
+pub struct SeededPrincipal {
+    pub user: User,
+    pub role: Role,
+    pub password: String,
+    pub api_key: String,
+}
+
+/// Fabrique d'utilisateurs authentifiables d'un rôle donné.
+///
+/// Le mot de passe est haché avec argon2 et seule l'empreinte de la clé d'API est
+/// enregistrée. Un email déjà présent est repris : l'utilisateur garde son id et reçoit
+/// le nouveau mot de passe, le rôle et une nouvelle clé.
+#[derive(Debug, Clone)]
+pub struct PrincipalFactory {
+    role: Role,
+    email: Option<String>,
+    name: Option<String>,
+    password: Option<String>,
+    locale: FakerLocale,
+}
+
+impl PrincipalFactory {
+    pub fn new(role: Role) -> Self {
+        Self { role, email: None, name: None, password: None, locale: FakerLocale::default() }
+    }
+
+    pub fn admin() -> Self {
+        Self::new(Role::Admin)
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Langue des noms générés (`en_US` par défaut)
+    pub fn locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+#[async_trait]
+impl Factory for PrincipalFactory {
+    type Row = PrincipalFixture;
+    type Model = SeededPrincipal;
+
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> PrincipalFixture {
+        let username = locale::username(self.locale, rng);
+        let suffix: u32 = rng.random();
+        let name = locale::name(self.locale, rng);
+        let password: String = (0..20).map(|_| char::from(rng.sample(Alphanumeric))).collect();
+        PrincipalFixture {
+            email: self
+                .email
+                .clone()
+                .unwrap_or_else(|| format!("{}.{:08x}@example.com", locale::email_local_part(&username), suffix)),
+            name: self.name.clone().unwrap_or(name),
+            role: self.role,
+            password: self.password.clone().unwrap_or(password),
+            api_key: format!("{}{:032x}", API_KEY_PREFIX, rng.random::<u128>()),
+        }
+    }
+
+    async fn insert(pool: &PgPool, row: PrincipalFixture) -> Result<SeededPrincipal, sqlx::Error> {
+        let password_hash =
+            hash_password(&row.password).map_err(|e| sqlx::Error::Protocol(format!("Password hashing error: {}", e)))?;
+
+        let mut tx = pool.begin().await?;
+        let user = sqlx::query_as::<_, User>(
+            "INSERT INTO users (email, name) VALUES ($1, $2)
+             ON CONFLICT (email) DO UPDATE SET name = EXCLUDED.name, updated_at = now() RETURNING *",
+        )
+        .bind(&row.email)
+        .bind(&row.name)
+        .fetch_one(&mut *tx)
+        .await?;
+        set_credentials(&mut *tx, user.id, &password_hash, row.role).await?;
+        sqlx::query("DELETE FROM api_keys WHERE user_id = $1 AND name = $2")
+            .bind(user.id)
+            .bind(FIXTURE_KEY_NAME)
+            .execute(&mut *tx)
+            .await?;
+        issue_api_key(&mut *tx, user.id, FIXTURE_KEY_NAME, &row.api_key).await?;
+        tx.commit().await?;
+
+        Ok(SeededPrincipal { user, role: row.role, password: row.password, api_key: row.api_key })
+    }
+}
+
+/// Crée un administrateur (`admin@example.com`) et un utilisateur (`member@example.com`).
+///
+/// Leurs mots de passe et clés d'API dépendent de la graine des fixtures et sont
+/// journalisés, pour les tests manuels des routes protégées. Le nombre de lignes du
+/// profil est ignoré.
+pub async fn create_principals(fixture_manager: &FixtureManager, rng: &mut FixtureRng, _rows: u32) -> Result<(), sqlx::Error> {
+    info!("Creating principals...");
+    let locale = fixture_manager.locale();
+    let principals = [
+        PrincipalFactory::admin().email("admin@example.com").name("Admin").locale(locale).build_with(rng),
+        PrincipalFactory::new(Role::User).email("member@example.com").name("Member").locale(locale).build_with(rng),
+    ];
+
+    if fixture_manager.is_dry_run() {
+        for principal in &principals {
+            println!("principals\t{}", serde_json::to_string(principal).unwrap_or_default());
+        }
+        info!("Dry run: {} principals would be created", principals.len());
+        return Ok(());
+    }
+
+    fixture_manager.check_migrations().await?;
+    for principal in principals {
+        let seeded = PrincipalFactory::insert(fixture_manager.pool(), principal).await?;
+        info!(
+            "Principal {} ({}): password {}, API key {}",
+            seeded.user.email,
+            seeded.role.as_str(),
+            seeded.password,
+            seeded.api_key
+        );
+    }
+    Ok(())
+}
+
+pub async fn clean_principals(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    info!("Cleaning principals...");
+    let fixture_manager = FixtureManager::new(pool.clone());
+    fixture_manager.cleanup_fixtures("api_keys").await?;
+    fixture_manager.cleanup_fixtures("user_credentials").await?;
+    Ok(())
+}
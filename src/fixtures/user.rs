@@ -0,0 +1,90 @@
+use crate::{
+    config::FakerLocale,
+    fixtures::{common::FixtureManager, factory::Factory, locale, FixtureRng},
+    models::user::User,
+};
+use async_trait::async_trait;
+use fake::rand::Rng;
+use serde::Serialize;
+use sqlx::{PgPool, Pool, Postgres};
+use tracing::info;
+
+/// Ligne insérée dans la table `users`
+#[derive(Debug, Serialize)]
+pub struct UserFixture {
+    pub email: String,
+    pub name: String,
+}
+
+/// Fabrique d'utilisateurs, à l'email unique par défaut
+#[derive(Debug, Clone, Default)]
+pub struct UserFactory {
+    email: Option<String>,
+    name: Option<String>,
+    locale: FakerLocale,
+}
+
+impl UserFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Langue des noms générés (`en_US` par défaut)
+    pub fn locale(mut self, locale: FakerLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+#[async_trait]
+impl Factory for UserFactory {
+    type Row = UserFixture;
+    type Model = User;
+
+    fn build_with<R: Rng + ?Sized>(&self, rng: &mut R) -> UserFixture {
+        let username = locale::username(self.locale, rng);
+        // Suffixe aléatoire : sans graine fixe, les fixtures peuvent être rechargées sans nettoyage
+        let suffix: u32 = rng.random();
+        let name = locale::name(self.locale, rng);
+        UserFixture {
+            email: self
+                .email
+                .clone()
+                .unwrap_or_else(|| format!("{}.{:08x}@example.com", locale::email_local_part(&username), suffix)),
+            name: self.name.clone().unwrap_or(name),
+        }
+    }
+
+    async fn insert(pool: &PgPool, row: UserFixture) -> Result<User, sqlx::Error> {
+        sqlx::query_as::<_, User>("INSERT INTO users (email, name) VALUES ($1, $2) RETURNING *")
+            .bind(row.email)
+            .bind(row.name)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+pub async fn create_users(fixture_manager: &FixtureManager, rng: &mut FixtureRng, rows: u32) -> Result<(), sqlx::Error> {
+    info!("Creating users...");
+    let factory = UserFactory::new().locale(fixture_manager.locale());
+    let users: Vec<UserFixture> = (0..rows).map(|_| factory.build_with(rng)).collect();
+    fixture_manager.submit_fixtures(users, "users").await?;
+    Ok(())
+}
+
+pub async fn clean_users(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    info!("Cleaning users...");
+    let fixture_manager = FixtureManager::new(pool.clone());
+    fixture_manager.cleanup_fixtures("users").await?;
+    Ok(())
+}
@@ -0,0 +1,268 @@
+//! # Admin Handlers Module
+//!
+//! Ce module contient les handlers des routes d'administration.
+//! L'authentification est assurée par le middleware `require_admin`.
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::{
+    allocator::{self, HeapProfileError},
+    db::DatabaseManager,
+    extractors::{
+        json::ApiJson,
+        pagination::{Pagination, PaginationParams},
+        path::ApiPath,
+        query::QueryOptions,
+        validated::ValidatedJson,
+    },
+    handlers::{error::AppError, response::PaginatedResponse},
+    models::{
+        cors::{CorsOrigin, CorsOriginFields, CorsOriginId, NewCorsOrigin},
+        error::{ErrorCode, ProblemDetails},
+        help::{AllocatorStats, ReadinessStatus},
+        routes::RouteInfo,
+        status::QueryInsightsReport,
+    },
+    routes::route_registry,
+    services::{
+        cors::{self, normalize_origin, CorsOrigins},
+        queries::QueryInsights,
+    },
+    state::Readiness,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/routes.json",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Route registry", body = [RouteInfo]),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Export the route registry",
+    description = "Lists every route exposed by the service with its method, authentication requirement, rate-limit policy and deprecation status, for ingestion by gateways and API catalogs."
+)]
+pub async fn routes_sitemap() -> Json<Vec<RouteInfo>> {
+    Json(route_registry())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/readiness",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Current readiness flag", body = ReadinessStatus),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Get the readiness flag"
+)]
+pub async fn get_readiness(State(readiness): State<Arc<Readiness>>) -> Json<ReadinessStatus> {
+    Json(ReadinessStatus { ready: readiness.is_ready() })
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/readiness",
+    tag = "Admin",
+    request_body = ReadinessStatus,
+    responses(
+        (status = 200, description = "Readiness flag updated", body = ReadinessStatus),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Drain or restore the instance",
+    description = "Set `ready` to false to make the readiness probe fail so the load balancer drains this node before a deployment; in-flight requests and liveness are unaffected."
+)]
+pub async fn set_readiness(
+    State(readiness): State<Arc<Readiness>>,
+    ApiJson(update): ApiJson<ReadinessStatus>,
+) -> Json<ReadinessStatus> {
+    readiness.set_ready(update.ready);
+    if update.ready {
+        warn!("Readiness restored by admin, instance accepts traffic again");
+    } else {
+        warn!("Instance set to draining by admin, readiness probe now failing");
+    }
+    Json(ReadinessStatus { ready: readiness.is_ready() })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/cors-origins",
+    tag = "Admin",
+    params(
+        PaginationParams,
+        ("filter[tenant]" = Option<String>, Query, description = "Only origins of these tenants (comma-separated)"),
+        ("sort" = Option<String>, Query, description = "Sort fields: origin, created_at (prefix with - for descending)"),
+        ("search" = Option<String>, Query, description = "Case-insensitive search in origin and tenant")
+    ),
+    responses(
+        (status = 200, description = "Registered CORS origins", body = PaginatedResponse<CorsOrigin>),
+        (status = 400, description = "Invalid pagination, filter or sort parameters", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "List dynamic CORS origins"
+)]
+pub async fn list_cors_origins(
+    State(db): State<DatabaseManager>,
+    pagination: Pagination,
+    options: QueryOptions<CorsOriginFields>,
+) -> Result<PaginatedResponse<CorsOrigin>, AppError> {
+    let pool = db.get_pool();
+    let origins = cors::list_origins(pool, &options, pagination.limit(), pagination.offset()).await?;
+    let total = cors::count_origins(pool, &options).await?;
+
+    Ok(pagination.response(origins, total))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/cors-origins",
+    tag = "Admin",
+    request_body = NewCorsOrigin,
+    responses(
+        (status = 201, description = "Origin registered", body = CorsOrigin),
+        (status = 400, description = "Invalid origin", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Register a dynamic CORS origin",
+    description = "Allows browsers on the given origin (`https://host[:port]`) to call the API. Takes effect immediately on this instance and within `dynamic_origins_ttl_seconds` on the others; requires `[cors] dynamic_origins = true`."
+)]
+pub async fn create_cors_origin(
+    State(db): State<DatabaseManager>,
+    State(origins): State<Arc<CorsOrigins>>,
+    ValidatedJson(new_origin): ValidatedJson<NewCorsOrigin>,
+) -> Result<(StatusCode, Json<CorsOrigin>), AppError> {
+    let origin = normalize_origin(&new_origin.origin).map_err(|e| AppError::coded(ErrorCode::InvalidCorsOrigin, e))?;
+    let new_origin = NewCorsOrigin { origin, ..new_origin };
+
+    let created = cors::add_origin(db.get_pool(), &new_origin).await?;
+    origins.invalidate();
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/cors-origins/{id}",
+    tag = "Admin",
+    params(("id" = i64, Path, description = "Origin identifier")),
+    responses(
+        (status = 204, description = "Origin removed"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown origin", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Remove a dynamic CORS origin"
+)]
+pub async fn delete_cors_origin(
+    State(db): State<DatabaseManager>,
+    State(origins): State<Arc<CorsOrigins>>,
+    ApiPath(id): ApiPath<CorsOriginId>,
+) -> Result<StatusCode, AppError> {
+    if !cors::remove_origin(db.get_pool(), id).await? {
+        return Err(AppError::coded(ErrorCode::CorsOriginNotFound, "unknown CORS origin"));
+    }
+    origins.invalidate();
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/allocator",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Allocator statistics", body = AllocatorStats),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Get memory allocator statistics",
+    description = "Reports resident, active and allocated memory and fragmentation as seen by the global allocator, to diagnose memory growth. Statistics require building with the `jemalloc` or `mimalloc` feature; with the system allocator every value is null."
+)]
+pub async fn allocator_stats() -> Json<AllocatorStats> {
+    Json(allocator::stats())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/allocator/heap-profile",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Heap profile in jeprof format", content_type = "application/octet-stream"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 409, description = "Heap profiling is not available", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Dump a heap profile",
+    description = "Dumps the sampled heap allocations of this instance, to be analyzed with `jeprof`. Requires the `jemalloc` feature and the server started with `_RJEM_MALLOC_CONF=prof:true`."
+)]
+pub async fn heap_profile() -> Result<Response, AppError> {
+    // Le profil parcourt tout le tas et écrit un fichier : hors des workers du runtime
+    let profile = tokio::task::spawn_blocking(allocator::heap_profile)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    match profile {
+        Ok(profile) => Ok((
+            [
+                (header::CONTENT_TYPE, "application/octet-stream"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"heap.prof\""),
+            ],
+            profile,
+        )
+            .into_response()),
+        Err(e @ HeapProfileError::Unavailable(_)) => Err(AppError::coded(ErrorCode::HeapProfilingUnavailable, e.to_string())),
+        Err(e) => Err(AppError::Internal(e.to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/queries",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Most expensive SQL queries", body = QueryInsightsReport),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 409, description = "Query insights are disabled or pg_stat_statements is missing", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Get the most expensive SQL queries",
+    description = "Reads `pg_stat_statements` for the queries of this database with the highest total and mean execution time, to link API slowness to specific SQL. Requires `[status] query_insights = true` and the `pg_stat_statements` extension."
+)]
+pub async fn query_insights(
+    State(db): State<DatabaseManager>,
+    State(queries): State<Arc<QueryInsights>>,
+) -> Result<Json<QueryInsightsReport>, AppError> {
+    if !queries.is_enabled() {
+        return Err(AppError::coded(ErrorCode::QueryInsightsUnavailable, "query insights are disabled"));
+    }
+
+    match queries.refresh(db.get_pool()).await? {
+        Some(report) => Ok(Json(report)),
+        None => Err(AppError::coded(
+            ErrorCode::QueryInsightsUnavailable,
+            "the pg_stat_statements extension is not installed",
+        )),
+    }
+}
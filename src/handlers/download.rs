@@ -0,0 +1,191 @@
+//! # Download Module
+//!
+//! Ce module construit les réponses de téléchargement des fichiers stockés :
+//! - validateurs `ETag` et `Last-Modified`, et réponse `304 Not Modified` aux
+//!   requêtes conditionnelles (`If-None-Match`, `If-Modified-Since`)
+//! - plages d'octets (`Range: bytes=...`) servies en `206 Partial Content`, pour
+//!   reprendre un téléchargement interrompu ; `If-Range` protège la reprise si le
+//!   fichier a changé entre-temps
+//!
+//! Une seule plage est prise en charge : une demande de plusieurs plages, ou une
+//! valeur mal formée, reçoit le fichier entier, comme le permet la RFC 9110.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    handlers::error::AppError,
+    models::error::ErrorCode,
+    services::storage::{Storage, StorageReader},
+};
+
+/// Format des dates HTTP (IMF-fixdate)
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Fichier à servir, décrit par ses métadonnées
+#[derive(Debug, Clone)]
+pub struct StoredFile<'a> {
+    /// Clé du contenu dans le stockage
+    pub key: &'a str,
+    pub size: u64,
+    pub content_type: &'a str,
+    /// Nom proposé au client (`Content-Disposition`)
+    pub filename: &'a str,
+    /// Validateur fort du contenu, sans guillemets (par exemple son empreinte)
+    pub etag: &'a str,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Plage d'octets, bornes incluses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Nombre d'octets de la plage (au moins 1)
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Interprétation de l'en-tête `Range` pour un fichier donné
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// Pas de plage exploitable : le fichier entier est servi
+    Full,
+    Partial(ByteRange),
+    /// La plage commence au-delà de la fin du fichier (416)
+    Unsatisfiable,
+}
+
+impl RangeRequest {
+    /// Interprète la valeur d'un en-tête `Range` pour un fichier de `size` octets.
+    pub fn parse(value: &str, size: u64) -> Self {
+        let Some(spec) = value.trim().strip_prefix("bytes=") else {
+            return RangeRequest::Full;
+        };
+        let Some((start, end)) = spec.split_once('-').filter(|_| !spec.contains(',')) else {
+            return RangeRequest::Full;
+        };
+
+        match (start.trim(), end.trim()) {
+            // `bytes=-500` : les 500 derniers octets
+            ("", suffix) => match suffix.parse::<u64>() {
+                Ok(0) => RangeRequest::Unsatisfiable,
+                Ok(_) if size == 0 => RangeRequest::Unsatisfiable,
+                Ok(suffix) => RangeRequest::Partial(ByteRange { start: size.saturating_sub(suffix), end: size - 1 }),
+                Err(_) => RangeRequest::Full,
+            },
+            (start, end) => {
+                let Ok(start) = start.parse::<u64>() else {
+                    return RangeRequest::Full;
+                };
+                let end = match end {
+                    "" => u64::MAX,
+                    end => match end.parse::<u64>() {
+                        Ok(end) if end >= start => end,
+                        _ => return RangeRequest::Full,
+                    },
+                };
+                if start >= size {
+                    return RangeRequest::Unsatisfiable;
+                }
+                RangeRequest::Partial(ByteRange { start, end: end.min(size - 1) })
+            }
+        }
+    }
+}
+
+/// Sert un fichier du stockage en tenant compte des en-têtes conditionnels et de plage.
+pub async fn serve_file(storage: &dyn Storage, headers: &HeaderMap, file: StoredFile<'_>) -> Result<Response, AppError> {
+    let etag = format!("\"{}\"", file.etag);
+    let last_modified = file.last_modified.format(HTTP_DATE).to_string();
+    let validators = [(header::ETAG, etag.clone()), (header::LAST_MODIFIED, last_modified)];
+
+    if not_modified(headers, &etag, file.last_modified) {
+        return Ok((StatusCode::NOT_MODIFIED, validators).into_response());
+    }
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .filter(|_| range_applies(headers, &etag, file.last_modified))
+        .map_or(RangeRequest::Full, |value| RangeRequest::parse(value, file.size));
+
+    let (status, reader, content_range, length) = match range {
+        RangeRequest::Full => (StatusCode::OK, open(file.key, storage.reader(file.key).await)?, None, file.size),
+        RangeRequest::Partial(range) => {
+            let reader = storage.range_reader(file.key, range.start, range.length()).await;
+            let content_range = format!("bytes {}-{}/{}", range.start, range.end, file.size);
+            (StatusCode::PARTIAL_CONTENT, open(file.key, reader)?, Some(content_range), range.length())
+        }
+        RangeRequest::Unsatisfiable => {
+            let mut response = AppError::coded(ErrorCode::RangeNotSatisfiable, "range not satisfiable").into_response();
+            let content_range = HeaderValue::from_str(&format!("bytes */{}", file.size)).expect("valid header value");
+            response.headers_mut().insert(header::CONTENT_RANGE, content_range);
+            return Ok(response);
+        }
+    };
+
+    let mut response = (
+        status,
+        validators,
+        [
+            (header::CONTENT_TYPE, file.content_type.to_string()),
+            (header::CONTENT_LENGTH, length.to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file.filename)),
+            (header::ACCEPT_RANGES, "bytes".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        Body::from_stream(ReaderStream::new(reader)),
+    )
+        .into_response();
+    if let Some(content_range) = content_range {
+        let value = HeaderValue::from_str(&content_range).expect("valid header value");
+        response.headers_mut().insert(header::CONTENT_RANGE, value);
+    }
+
+    Ok(response)
+}
+
+fn open(key: &str, reader: std::io::Result<StorageReader>) -> Result<StorageReader, AppError> {
+    reader.map_err(|e| AppError::Internal(format!("failed to open stored file {}: {}", key, e)))
+}
+
+/// Le client a déjà la version courante ; `If-None-Match` prime sur `If-Modified-Since`
+fn not_modified(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if let Some(candidates) = header_str(headers, header::IF_NONE_MATCH) {
+        return candidates
+            .split(',')
+            .map(|candidate| candidate.trim().trim_start_matches("W/"))
+            .any(|candidate| candidate == "*" || candidate == etag);
+    }
+
+    header_str(headers, header::IF_MODIFIED_SINCE)
+        .and_then(parse_http_date)
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+/// `If-Range` : la plage n'est servie que si le fichier n'a pas changé depuis le début du téléchargement
+fn range_applies(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    match header_str(headers, header::IF_RANGE) {
+        None => true,
+        Some(value) if value.starts_with('"') => value == etag,
+        Some(value) => parse_http_date(value).is_some_and(|date| date.timestamp() == last_modified.timestamp()),
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim)
+}
+
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value).ok().map(|date| date.with_timezone(&Utc))
+}
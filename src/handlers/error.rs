@@ -0,0 +1,247 @@
+//! # Error Handling Module
+//!
+//! Ce module définit `AppError`, l'erreur commune des handlers.
+//! Elle est rendue en `application/problem+json` (RFC 7807), ce qui permet
+//! d'écrire les handlers avec `?` :
+//!
+//! ```ignore
+//! pub async fn get_user(...) -> Result<Json<User>, AppError> {
+//!     let user = find_user(pool, id).await?.ok_or_else(|| AppError::NotFound("user not found".into()))?;
+//!     Ok(Json(user))
+//! }
+//! ```
+
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use thiserror::Error;
+use tracing::{debug, error};
+use validator::ValidationErrors;
+
+use crate::{
+    handlers::response::ResponseMeta,
+    models::error::{ErrorCode, FieldError, ProblemDetails},
+};
+
+/// Type de contenu des réponses d'erreur
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Erreur commune des handlers de l'API
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{message}")]
+    Validation { message: String, errors: Vec<FieldError> },
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("{0}")]
+    PayloadTooLarge(String),
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    #[error("{0}")]
+    ServiceUnavailable(String),
+    /// Erreur de base de données inattendue ; le détail n'est jamais renvoyé au client
+    #[error("database error: {0}")]
+    Database(sqlx::Error),
+    /// Erreur interne ; le détail n'est jamais renvoyé au client
+    #[error("internal error: {0}")]
+    Internal(String),
+    /// Erreur métier portant un code précis du catalogue ; le statut découle du code
+    #[error("{message}")]
+    Coded { code: ErrorCode, message: String },
+}
+
+impl AppError {
+    /// Erreur de validation sans détail par champ
+    pub fn validation(message: impl Into<String>) -> Self {
+        AppError::Validation {
+            message: message.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Erreur portant un code précis du catalogue
+    pub fn coded(code: ErrorCode, message: impl Into<String>) -> Self {
+        AppError::Coded {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Code d'erreur stable transmis au client
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::BadRequest(_) => ErrorCode::BadRequest,
+            AppError::Validation { .. } => ErrorCode::ValidationFailed,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::PayloadTooLarge(_) => ErrorCode::PayloadTooLarge,
+            AppError::UnsupportedMediaType(_) => ErrorCode::UnsupportedMediaType,
+            AppError::ServiceUnavailable(_) => ErrorCode::ServiceUnavailable,
+            AppError::Database(_) => ErrorCode::DatabaseError,
+            AppError::Internal(_) => ErrorCode::InternalError,
+            AppError::Coded { code, .. } => *code,
+        }
+    }
+
+    /// Code HTTP associé à l'erreur
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Validation { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Coded { code, .. } => {
+                StatusCode::from_u16(code.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+
+    /// Identifiant stable du type d'erreur, utilisé dans le champ `type`
+    fn slug(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad-request",
+            AppError::Validation { .. } => "validation-error",
+            AppError::NotFound(_) => "not-found",
+            AppError::Conflict(_) => "conflict",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::PayloadTooLarge(_) => "payload-too-large",
+            AppError::UnsupportedMediaType(_) => "unsupported-media-type",
+            AppError::ServiceUnavailable(_) => "service-unavailable",
+            AppError::Database(_) => "database-error",
+            AppError::Internal(_) => "internal-error",
+            AppError::Coded { code, .. } => status_slug(code.status()),
+        }
+    }
+
+    /// Convertit l'erreur en corps RFC 7807
+    pub fn to_problem(&self) -> ProblemDetails {
+        let status = self.status();
+        let detail = match self {
+            AppError::Database(_) | AppError::Internal(_) => None,
+            other => Some(other.to_string()),
+        };
+        let errors = match self {
+            AppError::Validation { errors, .. } => errors.clone(),
+            _ => Vec::new(),
+        };
+
+        ProblemDetails {
+            problem_type: format!("urn:problem:{}", self.slug()),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            code: self.code(),
+            detail,
+            errors,
+            meta: None,
+        }
+    }
+}
+
+/// Identifiant du type d'erreur (`urn:problem:<slug>`) correspondant à un statut HTTP
+pub(crate) fn status_slug(status: u16) -> &'static str {
+    match status {
+        400 => "bad-request",
+        401 => "unauthorized",
+        403 => "forbidden",
+        404 => "not-found",
+        409 => "conflict",
+        413 => "payload-too-large",
+        415 => "unsupported-media-type",
+        422 => "validation-error",
+        503 => "service-unavailable",
+        _ => "internal-error",
+    }
+}
+
+/// Réponse `application/problem+json` d'un corps d'erreur déjà construit
+pub(crate) fn problem_response(status: StatusCode, problem: impl Serialize) -> Response {
+    let mut response = (status, Json(problem)).into_response();
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    response
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            error!("Request failed: {}", self);
+            crate::reporting::capture_server_error(&self, self.code().as_str());
+        } else {
+            debug!("Request rejected ({}): {}", status.as_u16(), self);
+        }
+
+        let problem = ProblemDetails {
+            meta: Some(ResponseMeta::current()),
+            ..self.to_problem()
+        };
+        problem_response(status, problem)
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        match &e {
+            sqlx::Error::RowNotFound => AppError::NotFound("resource not found".to_string()),
+            sqlx::Error::PoolTimedOut => AppError::ServiceUnavailable("database is unavailable".to_string()),
+            sqlx::Error::Database(db_error) => match db_error.code().as_deref() {
+                // unique_violation
+                Some("23505") => AppError::coded(ErrorCode::ResourceAlreadyExists, "resource already exists"),
+                // foreign_key_violation
+                Some("23503") => AppError::coded(
+                    ErrorCode::ResourceReferenced,
+                    "resource is referenced by or references another resource",
+                ),
+                // not_null_violation, check_violation, string_data_right_truncation
+                Some("23502") | Some("23514") | Some("22001") => AppError::validation("invalid data"),
+                _ => AppError::Database(e),
+            },
+            _ => AppError::Database(e),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(e: ValidationErrors) -> Self {
+        let mut errors: Vec<FieldError> = e
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, field_errors)| {
+                field_errors.iter().map(move |error| FieldError {
+                    field: field.to_string(),
+                    message: error
+                        .message
+                        .as_ref()
+                        .map(|message| message.to_string())
+                        .unwrap_or_else(|| error.code.to_string()),
+                })
+            })
+            .collect();
+        errors.sort_by(|a, b| a.field.cmp(&b.field));
+
+        AppError::Validation {
+            message: "request validation failed".to_string(),
+            errors,
+        }
+    }
+}
@@ -5,20 +5,32 @@
 //! des informations utiles pour le debugging et le monitoring.
 
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::StatusCode,
     response::Json,
 };
 use chrono::Utc;
-use sysinfo::{Disks, System};
-use std::time::Instant;
+use sysinfo::System;
+use std::{sync::Arc, time::Instant};
 
 use crate::{
-    db::DatabaseManager,
+    handlers::response::ApiResponse,
+    logs::LogBuffer,
+    middleware::latency::LatencyStats,
+    models::error::{ErrorCode, ErrorCodeInfo},
     models::help::{
-        HealthResponse, DatabaseStatus, SystemMetrics,
-        PerformanceMetrics, InfoResponse, EndpointInfo,
+        CheckResult, CpuUsage, HealthResponse, HealthStatus, SystemMetrics,
+        PerformanceMetrics, InfoResponse, EndpointInfo, ProbeResponse,
+        LogLevel, LogRecord, LogsQuery, SelfTestReport,
     },
+    models::routes::AuthRequirement,
+    models::status::{MetricsStore, SlowRequest},
+    routes::route_registry,
+    services::{
+        health::{self, HealthRegistry},
+        selftest,
+    },
+    state::{AppState, Readiness},
 };
 
 #[utoipa::path(
@@ -26,41 +38,23 @@ use crate::{
     path = "/api/help/health",
     tag = "System",
     responses(
-        (status = 200, description = "System is healthy", body = HealthResponse),
-        (status = 503, description = "System is unhealthy")
+        (status = 200, description = "System is healthy or degraded", body = HealthResponse),
+        (status = 503, description = "A critical check failed", body = HealthResponse)
     ),
     summary = "Get system health status",
-    description = "Performs a comprehensive health check of the system including database connection, system metrics, and performance metrics."
+    description = "Runs every registered health check (database, disk...) in parallel and reports their individual status, latency and criticality, along with system and performance metrics. A failing critical check makes the instance `unhealthy` (503); any other problem only makes it `degraded`."
 )]
-pub async fn health_check(State(db): State<DatabaseManager>) -> Result<Json<HealthResponse>, StatusCode> {
+pub async fn health_check(
+    State(health): State<Arc<HealthRegistry>>,
+    State(metrics): State<Arc<MetricsStore>>,
+) -> (StatusCode, Json<HealthResponse>) {
     let start_time = Instant::now();
-    
-    // Vérification de la base de données
-    let db_status = check_database_health(&db).await;
-    
-    // Métriques système
-    let system_metrics = get_system_metrics();
-    
-    // Métriques de performance
-    let response_time = start_time.elapsed().as_millis() as u64;
-    let performance_metrics = PerformanceMetrics {
-        response_time_ms: response_time,
-    };
-    
-    let health_response = HealthResponse {
-        status: if db_status.connected { "healthy".to_string() } else { "unhealthy".to_string() },
-        timestamp: Utc::now(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        database: db_status,
-        system: system_metrics,
-        performance: performance_metrics,
-    };
-    
-    if health_response.status == "healthy" {
-        Ok(Json(health_response))
-    } else {
-        Err(StatusCode::SERVICE_UNAVAILABLE)
-    }
+    let checks = health.run(false).await;
+
+    // Métriques système, rafraîchies par la tâche de fond
+    let system_metrics = metrics.system();
+
+    health_response(checks, system_metrics, start_time)
 }
 
 #[utoipa::path(
@@ -68,18 +62,16 @@ pub async fn health_check(State(db): State<DatabaseManager>) -> Result<Json<Heal
     path = "/api/help/health-light",
     tag = "System",
     responses(
-        (status = 200, description = "System is healthy", body = HealthResponse),
-        (status = 503, description = "System is unhealthy")
+        (status = 200, description = "System is healthy or degraded", body = HealthResponse),
+        (status = 503, description = "A critical check failed", body = HealthResponse)
     ),
     summary = "Get light system health status",
-    description = "Performs a quick health check focusing only on database connection and basic performance metrics."
+    description = "Runs only the critical health checks and skips system metrics."
 )]
-pub async fn health_light(State(db): State<DatabaseManager>) -> Result<Json<HealthResponse>, StatusCode> {
+pub async fn health_light(State(health): State<Arc<HealthRegistry>>) -> (StatusCode, Json<HealthResponse>) {
     let start_time = Instant::now();
-    
-    // Vérification de la base de données seulement
-    let db_status = check_database_health(&db).await;
-    
+    let checks = health.run(true).await;
+
     // Métriques système minimales
     let system_metrics = SystemMetrics {
         cpu_usage: 0.0, // Skip CPU check for speed
@@ -88,29 +80,47 @@ pub async fn health_light(State(db): State<DatabaseManager>) -> Result<Json<Heal
         memory_total_mb: 0,
         memory_usage_percent: 0.0,
         disk_usage_percent: 0.0,
+        disks: Vec::new(),
         uptime: System::uptime(),
     };
-    
-    // Métriques de performance
-    let response_time = start_time.elapsed().as_millis() as u64;
-    let performance_metrics = PerformanceMetrics {
-        response_time_ms: response_time,
+
+    health_response(checks, system_metrics, start_time)
+}
+
+/// Agrège les vérifications ; 503 si une vérification critique échoue
+fn health_response(checks: Vec<CheckResult>, system: SystemMetrics, start_time: Instant) -> (StatusCode, Json<HealthResponse>) {
+    let status = health::aggregate(&checks);
+    let code = if status == HealthStatus::Unhealthy {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
     };
-    
-    let health_response = HealthResponse {
-        status: if db_status.connected { "healthy".to_string() } else { "unhealthy".to_string() },
+
+    let response = HealthResponse {
+        status,
         timestamp: Utc::now(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        database: db_status,
-        system: system_metrics,
-        performance: performance_metrics,
+        checks,
+        system,
+        performance: PerformanceMetrics {
+            response_time_ms: start_time.elapsed().as_millis() as u64,
+        },
     };
-    
-    if health_response.status == "healthy" {
-        Ok(Json(health_response))
-    } else {
-        Err(StatusCode::SERVICE_UNAVAILABLE)
-    }
+    (code, Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/help/errors",
+    tag = "System",
+    responses(
+        (status = 200, description = "Error code catalog", body = Vec<ErrorCodeInfo>)
+    ),
+    summary = "List error codes",
+    description = "Lists every machine-readable error code the API can return in the `code` field of error responses, with its usual HTTP status and a description."
+)]
+pub async fn error_codes() -> Json<Vec<ErrorCodeInfo>> {
+    Json(ErrorCode::ALL.iter().copied().map(ErrorCodeInfo::from).collect())
 }
 
 #[utoipa::path(
@@ -129,28 +139,15 @@ pub async fn info() -> Json<InfoResponse> {
         version: env!("CARGO_PKG_VERSION").to_string(),
         description: env!("CARGO_PKG_DESCRIPTION").to_string(),
         authors: env!("CARGO_PKG_AUTHORS").split(':').map(|s| s.trim().to_string()).collect(),
-        endpoints: vec![
-            EndpointInfo {
-                path: "/help/health".to_string(),
-                method: "GET".to_string(),
-                description: "Vérification complète de l'état de santé du système".to_string(),
-            },
-            EndpointInfo {
-                path: "/help/health-light".to_string(),
-                method: "GET".to_string(),
-                description: "Vérification rapide (DB + performance seulement)".to_string(),
-            },
-            EndpointInfo {
-                path: "/help/info".to_string(),
-                method: "GET".to_string(),
-                description: "Informations sur l'API".to_string(),
-            },
-            EndpointInfo {
-                path: "/help/ping".to_string(),
-                method: "GET".to_string(),
-                description: "Test de connectivité simple".to_string(),
-            },
-        ],
+        endpoints: route_registry()
+            .into_iter()
+            .filter(|route| route.auth == AuthRequirement::None)
+            .map(|route| EndpointInfo {
+                path: route.path,
+                method: route.method,
+                description: route.description,
+            })
+            .collect(),
     })
 }
 
@@ -168,94 +165,141 @@ pub async fn ping() -> &'static str {
     "pong"
 }
 
-/// Vérification de l'état de la base de données
-async fn check_database_health(db: &DatabaseManager) -> DatabaseStatus {
-    let start_time = Instant::now();
-    
-    match sqlx::query("SELECT 1 as test")
-        .fetch_one(db.get_pool())
-        .await
-    {
-        Ok(_) => DatabaseStatus {
-            connected: true,
-            response_time_ms: Some(start_time.elapsed().as_millis() as u64),
-            error: None,
-        },
-        Err(e) => DatabaseStatus {
-            connected: false,
-            response_time_ms: None,
-            error: Some(e.to_string()),
-        },
-    }
+#[utoipa::path(
+    get,
+    path = "/api/help/live",
+    tag = "System",
+    responses(
+        (status = 200, description = "Process is up", body = ProbeResponse)
+    ),
+    summary = "Liveness probe",
+    description = "Always returns 200 while the process can serve HTTP requests. Checks no dependency, so a database outage never makes the orchestrator restart the container."
+)]
+pub async fn live() -> Json<ProbeResponse> {
+    Json(ProbeResponse { status: HealthStatus::Healthy, checks: Vec::new() })
 }
 
-/// Collecte des métriques système (optimisée)
-fn get_system_metrics() -> SystemMetrics {
-    // Utiliser new() d'abord pour les CPU
-    let mut sys = System::new();
-    
-    // Premier refresh pour initialiser
-    sys.refresh_cpu_usage();
-    
-    // Attendre l'intervalle minimum requis pour les CPU
-    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-    
-    // Deuxième refresh pour obtenir les vraies données CPU
-    sys.refresh_cpu_usage();
-    sys.refresh_memory(); // Refresh mémoire aussi
-    
-    // CPU usage avec la méthode recommandée
-    let cpu_usage = if !sys.cpus().is_empty() {
-        let total: f32 = sys.cpus().iter()
-            .map(|cpu| cpu.cpu_usage())
-            .sum();
-        total / sys.cpus().len() as f32
-    } else {
-        0.0
-    };
-    
-    let cpu_count = sys.cpus().len().max(1);
-    
-    // Mémoire
-    let memory_used = sys.used_memory() / 1024 / 1024; // Convert to MB
-    let memory_total = sys.total_memory() / 1024 / 1024; // Convert to MB
-    let memory_usage_percent = if memory_total > 0 {
-        (memory_used as f32 / memory_total as f32) * 100.0
+#[utoipa::path(
+    get,
+    path = "/api/help/startup",
+    tag = "System",
+    responses(
+        (status = 200, description = "Initialization complete", body = ProbeResponse),
+        (status = 503, description = "Instance is still initializing", body = ProbeResponse)
+    ),
+    summary = "Startup probe",
+    description = "Returns 503 until initialization (fixtures, background tasks) is complete, then 200. Orchestrators hold the liveness and readiness probes until it succeeds."
+)]
+pub async fn startup(State(readiness): State<Arc<Readiness>>) -> (StatusCode, Json<ProbeResponse>) {
+    if readiness.is_started() {
+        (StatusCode::OK, Json(ProbeResponse { status: HealthStatus::Healthy, checks: Vec::new() }))
     } else {
-        0.0
-    };
-    
-    // Disques
-    let disks = Disks::new_with_refreshed_list();
-    let disk_usage_percent = if let Some(disk) = disks.first() {
-        let total = disk.total_space();
-        let available = disk.available_space();
-        if total > 0 {
-            let used = total - available;
-            (used as f32 / total as f32) * 100.0
-        } else {
-            0.0
-        }
+        (StatusCode::SERVICE_UNAVAILABLE, Json(ProbeResponse { status: HealthStatus::Unhealthy, checks: Vec::new() }))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/help/ready",
+    tag = "System",
+    responses(
+        (status = 200, description = "Instance accepts traffic", body = ProbeResponse),
+        (status = 503, description = "Instance is initializing, draining, or a dependency is unavailable", body = ProbeResponse)
+    ),
+    summary = "Readiness probe",
+    description = "Returns 503 while the instance is initializing or draining, the database is unreachable, migrations are pending or a background task is restarting, so load balancers stop routing new traffic to it. Liveness (/api/help/live) is unaffected."
+)]
+pub async fn ready(State(readiness): State<Arc<Readiness>>) -> (StatusCode, Json<ProbeResponse>) {
+    let mut checks = readiness.checks().run(false).await;
+
+    // Drapeaux de l'instance, en tête des vérifications
+    let message = if !readiness.is_started() {
+        Some("initialization in progress")
+    } else if !readiness.is_ready() {
+        Some("instance is draining")
     } else {
-        0.0
+        None
     };
-    
-    // Log pour debug (temporaire)
-    println!("Debug CPU: individual_cores=[{}], average={:.1}%", 
-             sys.cpus().iter()
-                .map(|cpu| format!("{:.1}", cpu.cpu_usage()))
-                .collect::<Vec<_>>()
-                .join(", "),
-             cpu_usage);
-    
-    SystemMetrics {
-        cpu_usage,
-        cpu_count,
-        memory_used_mb: memory_used,
-        memory_total_mb: memory_total,
-        memory_usage_percent,
-        disk_usage_percent,
-        uptime: System::uptime(),
-    }
-} 
+    checks.insert(0, CheckResult {
+        name: "accepting_traffic".to_string(),
+        status: if message.is_some() { HealthStatus::Unhealthy } else { HealthStatus::Healthy },
+        critical: true,
+        latency_ms: 0,
+        message: message.map(str::to_string),
+        details: None,
+    });
+
+    let status = health::aggregate(&checks);
+    let code = if status == HealthStatus::Unhealthy { StatusCode::SERVICE_UNAVAILABLE } else { StatusCode::OK };
+    (code, Json(ProbeResponse { status, checks }))
+}
+
+/// Nombre de logs renvoyés par défaut par `/api/help/logs`
+const DEFAULT_LOGS_LIMIT: usize = 100;
+
+#[utoipa::path(
+    get,
+    path = "/api/help/logs",
+    tag = "System",
+    params(LogsQuery),
+    responses(
+        (status = 200, description = "Most recent log records, newest first", body = ApiResponse<Vec<LogRecord>>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "Get the most recent logs",
+    description = "Returns the last log records kept in memory by this instance (`[logging] buffer_size`, 500 by default), filtered by minimum level, for quick diagnostics without shell access. Only records passing the log level filter are kept, and the buffer is lost on restart."
+)]
+pub async fn logs(State(logs): State<Arc<LogBuffer>>, Query(query): Query<LogsQuery>) -> ApiResponse<Vec<LogRecord>> {
+    let level = query.level.unwrap_or(LogLevel::Info);
+    ApiResponse::ok(logs.recent(level, query.limit.unwrap_or(DEFAULT_LOGS_LIMIT)))
+}
 
+#[utoipa::path(
+    post,
+    path = "/api/help/selftest",
+    tag = "System",
+    responses(
+        (status = 200, description = "Every step passed or was skipped", body = ApiResponse<SelfTestReport>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 503, description = "At least one step failed", body = ApiResponse<SelfTestReport>)
+    ),
+    security(("admin_token" = [])),
+    summary = "Run the self-test",
+    description = "Runs an internal diagnostic suite, one step after the other: database round trip, database write rolled back, response cache write and read, background task heartbeat and outbound HTTP call (`[monitoring] ping_url`). Meant to be called right after a deployment; returns 503 if any step fails. Steps that do not apply to this instance (cache disabled, no background task) are skipped."
+)]
+pub async fn selftest(State(state): State<AppState>) -> ApiResponse<SelfTestReport> {
+    let report = selftest::run(&state).await;
+    let status = if report.passed { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    ApiResponse::with_status(status, Some(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/help/slow-endpoints",
+    tag = "System",
+    responses(
+        (status = 200, description = "Slowest requests of the rolling window, slowest first", body = ApiResponse<Vec<SlowRequest>>)
+    ),
+    summary = "Get the slowest requests",
+    description = "Lists the slowest individual requests served by this instance over the last `[status] slow_endpoints_window_seconds` (one hour by default), with their route template, status and duration, to attribute a latency regression to a specific handler. Kept in memory and reset on restart."
+)]
+pub async fn slow_endpoints(State(latency): State<Arc<LatencyStats>>) -> ApiResponse<Vec<SlowRequest>> {
+    ApiResponse::ok(latency.slowest())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/help/system/cpu",
+    tag = "System",
+    responses(
+        (status = 200, description = "CPU usage per core", body = ApiResponse<CpuUsage>)
+    ),
+    summary = "Get CPU usage per core",
+    description = "Reports the usage of each CPU core measured by the background metrics task, with the average and the busiest core, so that a hot task saturating a single core is not hidden behind a low average."
+)]
+pub async fn system_cpu(State(metrics): State<Arc<MetricsStore>>) -> ApiResponse<CpuUsage> {
+    ApiResponse::ok(metrics.cpu())
+}
@@ -0,0 +1,218 @@
+//! # Incidents Handlers Module
+//!
+//! Ce module contient les handlers des incidents affichés sur la page de status :
+//! la consultation est publique, l'ouverture, la modification, les mises à jour
+//! et la résolution sont réservées à l'administration.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    db::DatabaseManager,
+    extractors::{
+        pagination::{Pagination, PaginationParams},
+        path::ApiPath,
+        validated::ValidatedJson,
+    },
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, PaginatedResponse},
+    },
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        incidents::{
+            Incident, IncidentDetail, IncidentId, IncidentStatus, IncidentUpdate, IncidentsQuery, NewIncident,
+            NewIncidentUpdate, ResolveIncident, UpdateIncident,
+        },
+    },
+    services::incidents::{self, DEFAULT_RESOLVED_MESSAGE},
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents",
+    tag = "Incidents",
+    params(IncidentsQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Incidents, most recent first", body = PaginatedResponse<Incident>),
+        (status = 400, description = "Invalid pagination parameters", body = ProblemDetails)
+    ),
+    summary = "List incidents"
+)]
+pub async fn list_incidents(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<IncidentsQuery>,
+    pagination: Pagination,
+) -> Result<PaginatedResponse<Incident>, AppError> {
+    let pool = db.get_pool();
+    let items = incidents::list_incidents(pool, &query, pagination.limit(), pagination.offset()).await?;
+    let total = incidents::count_incidents(pool, &query).await?;
+
+    Ok(pagination.response(items, total))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}",
+    tag = "Incidents",
+    params(("id" = i64, Path, description = "Incident identifier")),
+    responses(
+        (status = 200, description = "Incident with its updates, most recent first", body = ApiResponse<IncidentDetail>),
+        (status = 404, description = "Unknown incident", body = ProblemDetails)
+    ),
+    summary = "Get an incident with its updates"
+)]
+pub async fn get_incident(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<IncidentId>,
+) -> Result<ApiResponse<IncidentDetail>, AppError> {
+    let detail = incidents::get_incident_detail(db.get_pool(), id).await?.ok_or_else(incident_not_found)?;
+
+    Ok(ApiResponse::ok(detail))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/incidents",
+    tag = "Incidents",
+    request_body = NewIncident,
+    responses(
+        (status = 201, description = "Incident opened", body = ApiResponse<IncidentDetail>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Open an incident",
+    description = "The message becomes the first update of the incident, shown on the status page until the incident is resolved."
+)]
+pub async fn create_incident(
+    State(db): State<DatabaseManager>,
+    ValidatedJson(new_incident): ValidatedJson<NewIncident>,
+) -> Result<ApiResponse<IncidentDetail>, AppError> {
+    let created = incidents::create_incident(db.get_pool(), &new_incident).await?;
+
+    Ok(ApiResponse::created(created))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/admin/incidents/{id}",
+    tag = "Incidents",
+    params(("id" = i64, Path, description = "Incident identifier")),
+    request_body = UpdateIncident,
+    responses(
+        (status = 200, description = "Incident updated", body = ApiResponse<Incident>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown incident", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Update the title or severity of an incident",
+    description = "Only the fields present in the body are changed. Post an update to change the status."
+)]
+pub async fn update_incident(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<IncidentId>,
+    ValidatedJson(changes): ValidatedJson<UpdateIncident>,
+) -> Result<ApiResponse<Incident>, AppError> {
+    let updated = incidents::update_incident(db.get_pool(), id, &changes)
+        .await?
+        .ok_or_else(incident_not_found)?;
+
+    Ok(ApiResponse::ok(updated))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/incidents/{id}",
+    tag = "Incidents",
+    params(("id" = i64, Path, description = "Incident identifier")),
+    responses(
+        (status = 204, description = "Incident and its updates deleted"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown incident", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Delete an incident"
+)]
+pub async fn delete_incident(State(db): State<DatabaseManager>, ApiPath(id): ApiPath<IncidentId>) -> Result<StatusCode, AppError> {
+    if !incidents::delete_incident(db.get_pool(), id).await? {
+        return Err(incident_not_found());
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/incidents/{id}/updates",
+    tag = "Incidents",
+    params(("id" = i64, Path, description = "Incident identifier")),
+    request_body = NewIncidentUpdate,
+    responses(
+        (status = 201, description = "Update published", body = ApiResponse<IncidentUpdate>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown incident", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Publish an incident update",
+    description = "The update status becomes the incident status: `resolved` resolves the incident, any other status reopens it."
+)]
+pub async fn add_update(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<IncidentId>,
+    ValidatedJson(update): ValidatedJson<NewIncidentUpdate>,
+) -> Result<ApiResponse<IncidentUpdate>, AppError> {
+    let created = incidents::add_update(db.get_pool(), id, update.status, &update.message)
+        .await?
+        .ok_or_else(incident_not_found)?;
+
+    Ok(ApiResponse::created(created))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/incidents/{id}/resolve",
+    tag = "Incidents",
+    params(("id" = i64, Path, description = "Incident identifier")),
+    request_body = ResolveIncident,
+    responses(
+        (status = 201, description = "Incident resolved; returns the final update", body = ApiResponse<IncidentUpdate>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown incident", body = ProblemDetails),
+        (status = 409, description = "The incident is already resolved", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Resolve an incident",
+    description = "Send `{}` to publish the default resolution message."
+)]
+pub async fn resolve_incident(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<IncidentId>,
+    ValidatedJson(resolve): ValidatedJson<ResolveIncident>,
+) -> Result<ApiResponse<IncidentUpdate>, AppError> {
+    let pool = db.get_pool();
+    let incident = incidents::get_incident(pool, id).await?.ok_or_else(incident_not_found)?;
+    if incident.resolved_at.is_some() {
+        return Err(AppError::coded(ErrorCode::IncidentAlreadyResolved, "incident is already resolved"));
+    }
+
+    let message = resolve.message.as_deref().unwrap_or(DEFAULT_RESOLVED_MESSAGE);
+    let created = incidents::add_update(pool, id, IncidentStatus::Resolved, message)
+        .await?
+        .ok_or_else(incident_not_found)?;
+
+    Ok(ApiResponse::created(created))
+}
+
+fn incident_not_found() -> AppError {
+    AppError::coded(ErrorCode::IncidentNotFound, "incident not found")
+}
@@ -0,0 +1,168 @@
+//! # Jobs Handlers Module
+//!
+//! Ce module expose le suivi des tâches asynchrones (voir `services::jobs`) :
+//! - `accepted` construit la réponse `202 Accepted` d'un handler qui a soumis une tâche,
+//!   avec l'en-tête `Location` de la tâche
+//! - `GET /api/jobs/{id}` retourne l'état de la tâche ; tant qu'elle n'est pas terminée,
+//!   `Retry-After` indique quand revenir
+//! - `POST /api/admin/jobs` soumet une tâche de n'importe quel type enregistré
+//! - `GET /api/admin/jobs` liste les tâches d'un statut, les tâches en dead-letter par défaut
+//! - `POST /api/admin/jobs/{id}/retry` remet en file une tâche en échec
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::{
+    config::Config,
+    db::DatabaseManager,
+    extractors::{
+        links::LinkBuilder,
+        pagination::{Pagination, PaginationParams},
+        path::ApiPath,
+        validated::ValidatedJson,
+    },
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, PaginatedResponse},
+    },
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        jobs::{Job, JobId, JobsQuery, NewJob},
+    },
+    services::jobs::{self, JobError, JobRegistry},
+};
+
+/// `202 Accepted` pour une tâche soumise, avec `Location: /api/jobs/{id}`
+pub fn accepted(links: &LinkBuilder, job: Job) -> Response {
+    let path = format!("/api/jobs/{}", job.id);
+    let location = HeaderValue::from_str(&links.url(&path)).expect("valid header value");
+
+    let mut response = ApiResponse::with_status(StatusCode::ACCEPTED, Some(job))
+        .links(links.to(&path))
+        .into_response();
+    response.headers_mut().insert(header::LOCATION, location);
+    response
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{id}",
+    tag = "Jobs",
+    params(("id" = Uuid, Path, description = "Job identifier, from the `Location` header of the 202 response")),
+    responses(
+        (status = 200, description = "Job state; `Retry-After` is set while the job is queued or running", body = ApiResponse<Job>),
+        (status = 400, description = "Malformed identifier", body = ProblemDetails),
+        (status = 404, description = "Unknown job", body = ProblemDetails)
+    ),
+    summary = "Get the state of a job",
+    description = "Poll this endpoint until `status` is `succeeded` (with `result`) or `failed` (with `error`)."
+)]
+pub async fn get_job(
+    State(db): State<DatabaseManager>,
+    State(config): State<Arc<Config>>,
+    links: LinkBuilder,
+    ApiPath(id): ApiPath<JobId>,
+) -> Result<Response, AppError> {
+    let job = jobs::get_job(db.get_pool(), id)
+        .await?
+        .ok_or_else(|| AppError::coded(ErrorCode::JobNotFound, "job not found"))?;
+    let finished = job.is_finished();
+
+    let mut response = ApiResponse::ok(job).links(links.to(&format!("/api/jobs/{}", id))).into_response();
+    if !finished {
+        let retry_after = HeaderValue::from(config.jobs.poll_interval_seconds.max(1));
+        response.headers_mut().insert(header::RETRY_AFTER, retry_after);
+    }
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/jobs",
+    tag = "Jobs",
+    request_body = NewJob,
+    responses(
+        (status = 202, description = "Job queued; follow the `Location` header", body = ApiResponse<Job>),
+        (status = 400, description = "No handler is registered for this kind", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 422, description = "The payload does not match the job kind", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Submit a job"
+)]
+pub async fn submit_job(
+    State(db): State<DatabaseManager>,
+    State(registry): State<Arc<JobRegistry>>,
+    links: LinkBuilder,
+    ValidatedJson(new_job): ValidatedJson<NewJob>,
+) -> Result<Response, AppError> {
+    registry.check(&new_job.kind, &new_job.payload).map_err(|e| match e {
+        JobError::UnknownKind(_) => AppError::coded(ErrorCode::UnknownJobKind, e.to_string()),
+        _ => AppError::coded(ErrorCode::InvalidJobPayload, e.to_string()),
+    })?;
+
+    let job = jobs::enqueue_kind(db.get_pool(), &new_job.kind, &new_job.payload).await?;
+    Ok(accepted(&links, job))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/jobs",
+    tag = "Jobs",
+    params(JobsQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Jobs with this status, most recently updated first", body = PaginatedResponse<Job>),
+        (status = 400, description = "Invalid pagination parameters", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "List jobs",
+    description = "Lists the dead-letter queue by default (`status=dead`): jobs whose retries are exhausted."
+)]
+pub async fn list_jobs(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<JobsQuery>,
+    pagination: Pagination,
+) -> Result<PaginatedResponse<Job>, AppError> {
+    let pool = db.get_pool();
+    let items = jobs::list_jobs(pool, &query, pagination.limit(), pagination.offset()).await?;
+    let total = jobs::count_jobs(pool, &query).await?;
+
+    Ok(pagination.response(items, total))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/jobs/{id}/retry",
+    tag = "Jobs",
+    params(("id" = Uuid, Path, description = "Job identifier")),
+    responses(
+        (status = 202, description = "Job queued again with a fresh attempt counter", body = ApiResponse<Job>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown job", body = ProblemDetails),
+        (status = 409, description = "The job has not failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Retry a failed or dead-lettered job"
+)]
+pub async fn retry_job(
+    State(db): State<DatabaseManager>,
+    links: LinkBuilder,
+    ApiPath(id): ApiPath<JobId>,
+) -> Result<Response, AppError> {
+    let pool = db.get_pool();
+    match jobs::retry_job(pool, id).await? {
+        Some(job) => Ok(accepted(&links, job)),
+        None if jobs::get_job(pool, id).await?.is_some() => {
+            Err(AppError::coded(ErrorCode::JobNotRetriable, "only failed or dead jobs can be retried"))
+        }
+        None => Err(AppError::coded(ErrorCode::JobNotFound, "job not found")),
+    }
+}
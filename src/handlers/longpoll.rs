@@ -0,0 +1,95 @@
+//! # Long Polling Module
+//!
+//! Ce module fournit les briques des routes en long polling, pour les clients qui
+//! ne peuvent utiliser ni SSE ni WebSocket : la requête reste ouverte jusqu'à
+//! l'arrivée d'un événement, ou jusqu'à l'échéance.
+//! - `poll_until` attend un `tokio::sync::Notify` en revérifiant l'état à chaque réveil
+//! - `recv_until` attend le prochain message d'un canal `tokio::sync::broadcast`
+//! - `long_poll_response` répond `200` avec l'événement, ou `204 No Content` à l'échéance
+//!
+//! ```ignore
+//! async fn poll_events(
+//!     State(state): State<AppState>,
+//!     Query(query): Query<LongPollQuery>,
+//! ) -> Result<Response, AppError> {
+//!     let mut events = state.events.subscribe();
+//!     let event = recv_until(&mut events, query.wait()).await;
+//!     Ok(long_poll_response(event))
+//! }
+//! ```
+//!
+//! Le client relance une requête dès qu'il reçoit une réponse, `200` ou `204`.
+
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::{
+    sync::{broadcast, Notify},
+    time::{timeout_at, Instant},
+};
+use utoipa::IntoParams;
+
+use crate::handlers::response::ApiResponse;
+
+/// Attente par défaut, sans paramètre `timeout`
+pub const DEFAULT_WAIT: Duration = Duration::from_secs(30);
+
+/// Attente maximale acceptée, sous les délais habituels des proxys
+pub const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Paramètre `?timeout=` des routes en long polling
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LongPollQuery {
+    /// Attente maximale en secondes (30 par défaut, 60 au plus)
+    pub timeout: Option<u64>,
+}
+
+impl LongPollQuery {
+    /// Durée d'attente demandée, bornée à `MAX_WAIT`
+    pub fn wait(&self) -> Duration {
+        self.timeout.map_or(DEFAULT_WAIT, Duration::from_secs).min(MAX_WAIT)
+    }
+}
+
+/// Attend que `check` retourne une valeur, en le réévaluant à chaque notification.
+///
+/// `check` est appelé immédiatement, puis après chaque `notify_waiters()` ou
+/// `notify_one()`. L'abonnement précède chaque vérification : une notification
+/// envoyée pendant `check` n'est pas perdue. Retourne `None` à l'échéance.
+pub async fn poll_until<T>(notify: &Notify, wait: Duration, mut check: impl AsyncFnMut() -> Option<T>) -> Option<T> {
+    let deadline = Instant::now() + wait;
+    loop {
+        let notified = notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        if let Some(value) = check().await {
+            return Some(value);
+        }
+        timeout_at(deadline, notified).await.ok()?;
+    }
+}
+
+/// Attend le prochain message du canal, ou `None` à l'échéance ou à sa fermeture.
+///
+/// Les messages perdus par un abonné trop lent (`Lagged`) sont ignorés : le plus
+/// ancien message encore disponible est retourné.
+pub async fn recv_until<T: Clone>(receiver: &mut broadcast::Receiver<T>, wait: Duration) -> Option<T> {
+    let deadline = Instant::now() + wait;
+    loop {
+        match timeout_at(deadline, receiver.recv()).await.ok()? {
+            Ok(value) => return Some(value),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// `200` avec l'événement reçu, `204 No Content` si l'attente a expiré
+pub fn long_poll_response<T: Serialize>(event: Option<T>) -> Response {
+    match event {
+        Some(event) => ApiResponse::ok(event).into_response(),
+        None => ApiResponse::<()>::no_content().into_response(),
+    }
+}
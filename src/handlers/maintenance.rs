@@ -0,0 +1,94 @@
+//! # Maintenance Handlers Module
+//!
+//! Ce module contient les handlers des fenêtres de maintenance planifiée : la
+//! consultation est publique, la planification et la suppression sont réservées
+//! à l'administration.
+
+use axum::{extract::State, http::StatusCode};
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    config::Config,
+    db::DatabaseManager,
+    extractors::{path::ApiPath, validated::ValidatedJson},
+    handlers::{error::AppError, response::ApiResponse},
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        maintenance::{MaintenanceWindow, MaintenanceWindowId, NewMaintenanceWindow},
+    },
+    services::maintenance,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/maintenance",
+    tag = "Maintenance",
+    responses(
+        (status = 200, description = "Current and upcoming maintenance windows, earliest first", body = ApiResponse<Vec<MaintenanceWindow>>)
+    ),
+    summary = "List scheduled maintenance windows",
+    description = "Includes the windows created through the admin API and those declared in `[[maintenance.windows]]` (with a null `id`). Alert notifications are muted while a window is in progress."
+)]
+pub async fn list_windows(
+    State(db): State<DatabaseManager>,
+    State(config): State<Arc<Config>>,
+) -> Result<ApiResponse<Vec<MaintenanceWindow>>, AppError> {
+    let windows = maintenance::scheduled_windows(db.get_pool(), &config.maintenance, Utc::now()).await?;
+
+    Ok(ApiResponse::ok(windows))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/maintenance",
+    tag = "Maintenance",
+    request_body = NewMaintenanceWindow,
+    responses(
+        (status = 201, description = "Maintenance window scheduled", body = ApiResponse<MaintenanceWindow>),
+        (status = 400, description = "The window ends before it starts", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Schedule a maintenance window",
+    description = "The status page announces the window `[maintenance] announce_days` days ahead and shows a banner while it is in progress; alert notifications are muted during the window."
+)]
+pub async fn create_window(
+    State(db): State<DatabaseManager>,
+    ValidatedJson(new_window): ValidatedJson<NewMaintenanceWindow>,
+) -> Result<ApiResponse<MaintenanceWindow>, AppError> {
+    if new_window.ends_at <= new_window.starts_at {
+        return Err(AppError::coded(ErrorCode::InvalidMaintenanceWindow, "ends_at must be after starts_at"));
+    }
+    let created = maintenance::create_window(db.get_pool(), &new_window).await?;
+
+    Ok(ApiResponse::created(created))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/maintenance/{id}",
+    tag = "Maintenance",
+    params(("id" = i64, Path, description = "Maintenance window identifier")),
+    responses(
+        (status = 204, description = "Maintenance window cancelled"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled"),
+        (status = 404, description = "Unknown maintenance window", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Cancel a maintenance window",
+    description = "Only windows created through the API can be removed; windows declared in the configuration stay until the file is changed."
+)]
+pub async fn delete_window(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<MaintenanceWindowId>,
+) -> Result<StatusCode, AppError> {
+    if !maintenance::delete_window(db.get_pool(), id).await? {
+        return Err(AppError::coded(ErrorCode::MaintenanceWindowNotFound, "unknown maintenance window"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
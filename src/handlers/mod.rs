@@ -4,8 +4,21 @@
 
 // Re-export all handler modules here
 // Example:
-// pub mod user;
 // pub mod product;
 
+pub mod admin;
+pub mod download;
+pub mod error;
 pub mod help;
+pub mod incidents;
+pub mod jobs;
+pub mod longpoll;
+pub mod maintenance;
+pub mod post;
+pub mod response;
+pub mod search;
 pub mod status;
+pub mod stream;
+pub mod uploads;
+pub mod user;
+pub mod webhooks;
@@ -0,0 +1,202 @@
+//! # Post Handlers Module
+//!
+//! Ce module contient les handlers de la ressource d'exemple `post` et de ses
+//! commentaires. Il complète la ressource `user` avec des relations : routes
+//! imbriquées (`/users/{id}/posts`), chargement des données liées et traduction
+//! des violations de clés étrangères en 404 sur la ressource manquante.
+
+use axum::{extract::State, http::StatusCode};
+
+use crate::{
+    db::DatabaseManager,
+    extractors::{
+        pagination::{Pagination, PaginationParams},
+        path::ApiPath,
+        validated::ValidatedJson,
+    },
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, PaginatedResponse},
+    },
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        post::{Comment, CommentDetail, NewComment, NewPost, Post, PostDetail, PostId, PostSummary},
+        user::UserId,
+    },
+    services::{
+        post::{self, COMMENT_POST_FK, COMMENT_USER_FK, POST_USER_FK},
+        user,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/posts",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "User identifier"), PaginationParams),
+    responses(
+        (status = 200, description = "Posts of the user, most recent first", body = PaginatedResponse<PostSummary>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown user", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "List the posts of a user"
+)]
+pub async fn list_user_posts(
+    State(db): State<DatabaseManager>,
+    ApiPath(user_id): ApiPath<UserId>,
+    pagination: Pagination,
+) -> Result<PaginatedResponse<PostSummary>, AppError> {
+    let pool = db.get_pool();
+    if user::get_user(pool, user_id).await?.is_none() {
+        return Err(user_not_found());
+    }
+
+    let posts = post::list_user_posts(pool, user_id, pagination.limit(), pagination.offset()).await?;
+    let total = post::count_user_posts(pool, user_id).await?;
+
+    Ok(pagination.response(posts, total))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/posts",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "User identifier")),
+    request_body = NewPost,
+    responses(
+        (status = 201, description = "Post created", body = ApiResponse<Post>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown user", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Create a post for a user"
+)]
+pub async fn create_post(
+    State(db): State<DatabaseManager>,
+    ApiPath(user_id): ApiPath<UserId>,
+    ValidatedJson(new_post): ValidatedJson<NewPost>,
+) -> Result<ApiResponse<Post>, AppError> {
+    let created = post::create_post(db.get_pool(), user_id, &new_post)
+        .await
+        .map_err(missing_reference)?;
+
+    Ok(ApiResponse::created(created))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "Post identifier")),
+    responses(
+        (status = 200, description = "Post with its author and comments", body = ApiResponse<PostDetail>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown post", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Get a post with its author and comments"
+)]
+pub async fn get_post(State(db): State<DatabaseManager>, ApiPath(id): ApiPath<PostId>) -> Result<ApiResponse<PostDetail>, AppError> {
+    let detail = post::get_post_detail(db.get_pool(), id).await?.ok_or_else(post_not_found)?;
+
+    Ok(ApiResponse::ok(detail))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "Post identifier")),
+    responses(
+        (status = 204, description = "Post and its comments deleted"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown post", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Delete a post"
+)]
+pub async fn delete_post(State(db): State<DatabaseManager>, ApiPath(id): ApiPath<PostId>) -> Result<StatusCode, AppError> {
+    if !post::delete_post(db.get_pool(), id).await? {
+        return Err(post_not_found());
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "Post identifier")),
+    responses(
+        (status = 200, description = "Comments of the post, oldest first", body = ApiResponse<Vec<CommentDetail>>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown post", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "List the comments of a post"
+)]
+pub async fn list_comments(
+    State(db): State<DatabaseManager>,
+    ApiPath(post_id): ApiPath<PostId>,
+) -> Result<ApiResponse<Vec<CommentDetail>>, AppError> {
+    let pool = db.get_pool();
+    let comments = post::list_comments(pool, post_id).await?;
+    // Une liste vide ne distingue pas un post sans commentaire d'un post inconnu
+    if comments.is_empty() && !post::post_exists(pool, post_id).await? {
+        return Err(post_not_found());
+    }
+
+    Ok(ApiResponse::ok(comments))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    tag = "Posts",
+    params(("id" = i64, Path, description = "Post identifier")),
+    request_body = NewComment,
+    responses(
+        (status = 201, description = "Comment added", body = ApiResponse<Comment>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown post or author", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Comment on a post"
+)]
+pub async fn create_comment(
+    State(db): State<DatabaseManager>,
+    ApiPath(post_id): ApiPath<PostId>,
+    ValidatedJson(new_comment): ValidatedJson<NewComment>,
+) -> Result<ApiResponse<Comment>, AppError> {
+    let created = post::create_comment(db.get_pool(), post_id, &new_comment)
+        .await
+        .map_err(missing_reference)?;
+
+    Ok(ApiResponse::created(created))
+}
+
+fn user_not_found() -> AppError {
+    AppError::coded(ErrorCode::UserNotFound, "user not found")
+}
+
+fn post_not_found() -> AppError {
+    AppError::coded(ErrorCode::PostNotFound, "post not found")
+}
+
+/// Traduit une violation de clé étrangère en 404 sur la ressource référencée
+fn missing_reference(error: sqlx::Error) -> AppError {
+    let constraint = match &error {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => e.constraint().map(str::to_string),
+        _ => None,
+    };
+
+    match constraint.as_deref() {
+        Some(POST_USER_FK) | Some(COMMENT_USER_FK) => user_not_found(),
+        Some(COMMENT_POST_FK) => post_not_found(),
+        _ => error.into(),
+    }
+}
@@ -0,0 +1,319 @@
+//! # API Response Module
+//!
+//! Ce module fournit `ApiResponse<T>`, une enveloppe JSON homogène pour les réponses
+//! des handlers, construite avec des constructeurs plutôt qu'en littéral :
+//!
+//! ```ignore
+//! pub async fn list_users(...) -> ApiResponse<Vec<User>> {
+//!     ApiResponse::paginated(users, PaginationMeta::new(page, per_page, total))
+//! }
+//! ```
+//!
+//! Le corps contient le code HTTP et son libellé (`"code": 201, "status": "Created"`),
+//! et un objet `meta` (identifiant de requête, horodatage, durée de traitement)
+//! rempli au moment de produire la réponse, pour corréler une réponse et ses logs.
+//! Les erreurs métier passent de préférence par `AppError` (RFC 7807), qui porte le même `meta`.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::{
+    openapi::schema::{KnownFormat, Object, ObjectBuilder, SchemaFormat, Type},
+    ToSchema,
+};
+
+use crate::{handlers::error::AppError, middleware::request_id::RequestContext, models::error::ProblemDetails};
+
+/// Enveloppe JSON des réponses de l'API
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiResponse<T> {
+    /// Code de statut HTTP
+    pub code: u16,
+    /// Libellé du statut HTTP, par exemple `OK` ou `Created`
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub meta: ResponseMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+}
+
+/// Métadonnées de traitement, complétées par `stamp` juste avant l'envoi
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ResponseMeta {
+    /// Identifiant de la requête (en-tête `x-request-id`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Horodatage du serveur à la production de la réponse
+    pub timestamp: DateTime<Utc>,
+    /// Durée de traitement en millisecondes, depuis la réception de la requête
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+    /// Pagination, pour les listes construites avec `ApiResponse::paginated`
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    #[schema(schema_with = optional_pagination)]
+    pub pagination: Option<PaginationMeta>,
+}
+
+/// Champs de `PaginationMeta`, tous facultatifs : le derive décrirait un `Option` aplati
+/// comme « objet ou `null` », ce qu'aucune réponse ne respecte
+fn optional_pagination() -> Object {
+    let count = || {
+        ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+            .minimum(Some(0))
+    };
+    ObjectBuilder::new()
+        .property("page", count().description(Some("Page courante (à partir de 1)")))
+        .property("per_page", count())
+        .property("total", count().description(Some("Nombre total d'éléments")))
+        .property("total_pages", count())
+        .build()
+}
+
+impl ResponseMeta {
+    /// Métadonnées de la requête en cours
+    pub fn current() -> Self {
+        Self::default().stamp()
+    }
+
+    /// Renseigne l'identifiant, l'horodatage et la durée à partir de la requête en cours
+    pub fn stamp(mut self) -> Self {
+        let context = RequestContext::current();
+        self.timestamp = Utc::now();
+        self.request_id = context.as_ref().map(|context| context.id.to_string());
+        // Arrondi à la microseconde
+        self.duration_ms = context.map(|context| (context.started_at.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0);
+        self
+    }
+}
+
+/// Liens hypermédia d'une ressource ou d'une page.
+///
+/// Les URL sont construites par `LinkBuilder` (voir `extractors::links`) à partir de
+/// `[server] public_url`, ou par `Pagination::response` pour les listes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Links {
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last: Option<String>,
+    /// Ressources liées, par relation (par exemple `"download"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub related: BTreeMap<String, String>,
+}
+
+impl Links {
+    /// Liens d'une ressource située à `url`
+    pub fn to(url: impl Into<String>) -> Self {
+        Self {
+            self_link: Some(url.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Ajoute une ressource liée
+    pub fn related(mut self, rel: impl Into<String>, url: impl Into<String>) -> Self {
+        self.related.insert(rel.into(), url.into());
+        self
+    }
+}
+
+/// Informations de pagination d'une liste
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PaginationMeta {
+    /// Page courante (à partir de 1)
+    pub page: u64,
+    pub per_page: u64,
+    /// Nombre total d'éléments
+    pub total: u64,
+    pub total_pages: u64,
+}
+
+impl PaginationMeta {
+    /// Calcule le nombre de pages à partir du total
+    pub fn new(page: u64, per_page: u64, total: u64) -> Self {
+        let total_pages = if per_page == 0 { 0 } else { total.div_ceil(per_page) };
+        Self { page, per_page, total, total_pages }
+    }
+}
+
+impl<T> ApiResponse<T> {
+    /// Réponse avec un statut arbitraire
+    pub fn with_status(status: StatusCode, data: Option<T>) -> Self {
+        Self {
+            code: status.as_u16(),
+            status: status.canonical_reason().unwrap_or("Unknown").to_string(),
+            data,
+            message: None,
+            meta: ResponseMeta::default(),
+            links: None,
+        }
+    }
+
+    /// 200 OK avec des données
+    pub fn ok(data: T) -> Self {
+        Self::with_status(StatusCode::OK, Some(data))
+    }
+
+    /// 201 Created avec la ressource créée
+    pub fn created(data: T) -> Self {
+        Self::with_status(StatusCode::CREATED, Some(data))
+    }
+
+    /// Ajoute un message lisible
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Ajoute les liens hypermédia de la ressource
+    pub fn links(mut self, links: Links) -> Self {
+        self.links = Some(links);
+        self
+    }
+
+    /// Code de statut HTTP de la réponse
+    pub fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl ApiResponse<()> {
+    /// 204 No Content (le corps est vide)
+    pub fn no_content() -> Self {
+        Self::with_status(StatusCode::NO_CONTENT, None)
+    }
+
+    /// Réponse d'erreur simple avec un message
+    pub fn error(status: StatusCode, message: impl Into<String>) -> Self {
+        Self::with_status(status, None).message(message)
+    }
+}
+
+impl<T> ApiResponse<Vec<T>> {
+    /// 200 OK avec une page d'éléments et ses informations de pagination
+    pub fn paginated(items: Vec<T>, meta: PaginationMeta) -> Self {
+        let mut response = Self::ok(items);
+        response.meta.pagination = Some(meta);
+        response
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    fn into_response(mut self) -> Response {
+        let status = self.status_code();
+        if status == StatusCode::NO_CONTENT {
+            return status.into_response();
+        }
+        self.meta = self.meta.stamp();
+        (status, Json(self)).into_response()
+    }
+}
+
+/// Page d'une liste, avec le total et les liens vers les pages voisines.
+///
+/// Construite par `Pagination::response` (voir `extractors::pagination`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    /// Nombre total d'éléments
+    pub total: u64,
+    /// Page courante (à partir de 1)
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+    /// Lien vers la page suivante, s'il y en a une
+    pub next: Option<String>,
+    /// Lien vers la page précédente, s'il y en a une
+    pub prev: Option<String>,
+    /// Liens `self`, `first`, `last`, `next` et `prev`
+    pub links: Links,
+    #[serde(default)]
+    pub meta: ResponseMeta,
+}
+
+impl<T: Serialize> IntoResponse for PaginatedResponse<T> {
+    fn into_response(mut self) -> Response {
+        self.meta = self.meta.stamp();
+        Json(self).into_response()
+    }
+}
+
+/// Résultat d'un élément d'une écriture groupée
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchItemResult<T> {
+    /// Position de l'élément dans la requête (à partir de 0)
+    pub index: usize,
+    /// Statut HTTP qu'aurait reçu l'élément seul
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+}
+
+/// Réponse d'une écriture groupée : un résultat par élément, dans l'ordre de la requête.
+///
+/// Le statut est `200 OK` si tous les éléments ont réussi, `207 Multi-Status` sinon :
+/// un client ne peut pas prendre un lot partiellement échoué pour un succès.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchResponse<T> {
+    /// Nombre d'éléments écrits
+    pub succeeded: usize,
+    /// Nombre d'éléments refusés
+    pub failed: usize,
+    pub results: Vec<BatchItemResult<T>>,
+    #[serde(default)]
+    pub meta: ResponseMeta,
+}
+
+impl<T> BatchResponse<T> {
+    /// Construit la réponse à partir des résultats par élément ; `success` est le statut d'un élément réussi
+    pub fn new(results: Vec<Result<T, AppError>>, success: StatusCode) -> Self {
+        let results: Vec<BatchItemResult<T>> = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| match result {
+                Ok(data) => BatchItemResult { index, status: success.as_u16(), data: Some(data), error: None },
+                Err(error) => {
+                    let problem = error.to_problem();
+                    BatchItemResult { index, status: problem.status, data: None, error: Some(problem) }
+                }
+            })
+            .collect();
+        let failed = results.iter().filter(|result| result.error.is_some()).count();
+
+        BatchResponse { succeeded: results.len() - failed, failed, results, meta: ResponseMeta::default() }
+    }
+
+    /// Statut HTTP de la réponse
+    pub fn status_code(&self) -> StatusCode {
+        if self.failed == 0 {
+            StatusCode::OK
+        } else {
+            StatusCode::MULTI_STATUS
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for BatchResponse<T> {
+    fn into_response(mut self) -> Response {
+        self.meta = self.meta.stamp();
+        (self.status_code(), Json(self)).into_response()
+    }
+}
@@ -0,0 +1,56 @@
+//! # Search Handlers Module
+//!
+//! Ce module contient le handler de recherche plein texte sur les posts.
+
+use axum::extract::{Query, State};
+
+use crate::{
+    db::DatabaseManager,
+    extractors::pagination::{Pagination, PaginationParams},
+    handlers::{error::AppError, response::PaginatedResponse},
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        search::{SearchHit, SearchQuery},
+    },
+    services::search,
+};
+
+/// Longueur maximale des termes de recherche
+const MAX_TERMS_LENGTH: usize = 200;
+
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "Search",
+    params(SearchQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Matching posts, most relevant first", body = PaginatedResponse<SearchHit>),
+        (status = 400, description = "Missing or too long search terms, or invalid pagination", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token")
+    ),
+    security(("admin_token" = [])),
+    summary = "Search posts",
+    description = "Full-text search on post titles and bodies. Results are ranked, titles weigh more than bodies, and each hit carries highlighted excerpts of the body."
+)]
+pub async fn search(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<SearchQuery>,
+    pagination: Pagination,
+) -> Result<PaginatedResponse<SearchHit>, AppError> {
+    let terms = query.q.as_deref().map(str::trim).unwrap_or_default();
+    if terms.is_empty() {
+        return Err(AppError::coded(ErrorCode::MissingSearchTerms, "the `q` parameter is required"));
+    }
+    if terms.chars().count() > MAX_TERMS_LENGTH {
+        return Err(AppError::coded(
+            ErrorCode::InvalidQuery,
+            format!("search terms must be at most {} characters", MAX_TERMS_LENGTH),
+        ));
+    }
+
+    let pool = db.get_pool();
+    let hits = search::search_posts(pool, terms, pagination.limit(), pagination.offset()).await?;
+    let total = search::count_posts(pool, terms).await?;
+
+    Ok(pagination.response(hits, total))
+}
@@ -5,56 +5,256 @@
 //! OPTIMISÉ: Utilise UNIQUEMENT le cache, aucun calcul lors du chargement de page.
 
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Html,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Response,
+    },
+    Json,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt};
+use std::{convert::Infallible, sync::Arc};
+use tokio::sync::broadcast;
+use tracing::warn;
 
 use crate::{
+    config::{Config, JsonCase, StatusPageConfig, StatusPageSection},
     db::DatabaseManager,
+    extractors::{
+        fields::Fields,
+        pagination::{Pagination, PaginationParams},
+        query::QueryOptions,
+    },
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, PaginatedResponse},
+        stream::{csv, ndjson, ExportFormat, ExportQuery},
+    },
     models::{
-        status::{get_history, get_metrics_with_fallback, HistoryEntry},
+        error::{ErrorCode, ProblemDetails},
+        events::{AppEvent, EventFields, EventsQuery},
+        help::{CpuUsage, DiskUsage, LogCounts},
+        incidents::IncidentDetail,
+        maintenance::MaintenanceWindow,
+        status::{
+            calculate_error_penalty, BadgeQuery, ClusterStatus, NodeStatus, EndpointLatency, HistoryEntry, HistoryQuery, MetricsStore, QueryInsightsReport, QueryStat, ServiceStatus, ShieldsBadge, SlowRequest, PerformanceMetrics, RuntimeUsage, StatusSummary, TargetStatus, Throughput, UptimeStats,
+        },
+    },
+    middleware::{
+        casing::{rename_keys, snake_to_camel},
+        latency::LatencyStats,
+        status_codes::StatusCounters,
+    },
+    logs::LogBuffer,
+    sanitize::escape_html,
+    services::{
+        events::{count_events, list_events, stream_events},
+        incidents::open_incidents,
+        maintenance::announced_windows,
+        metrics::{count_history, list_history, recent_history, uptime_stats},
+        cluster::Cluster,
+        monitoring::target_statuses,
+        queries::QueryInsights,
     },
 };
 
+/// Nombre d'événements affichés sur la page de status
+const STATUS_PAGE_EVENTS: i64 = 10;
+
+/// Nombre d'entrées d'historique affichées sur la page de status
+const STATUS_PAGE_HISTORY: i64 = 50;
+
+/// Nombre de routes affichées dans le détail des latences de la page de status
+const STATUS_PAGE_ENDPOINTS: usize = 10;
+
+/// Données affichées par la page de status, rassemblées par `status_page` puis mises en
+/// page par `render_status_page`
+#[derive(Debug, Clone, Default)]
+pub struct StatusPageContent {
+    /// Heure du rendu : maintenances annoncées, heure et charge de la page d'attente
+    pub now: DateTime<Utc>,
+    /// Dernières métriques de la tâche de fond ; `None` au premier démarrage (page d'attente)
+    pub metrics: Option<PerformanceMetrics>,
+    pub history: Vec<HistoryEntry>,
+    pub events: Vec<AppEvent>,
+    pub uptime: Vec<UptimeStats>,
+    pub incidents: Vec<IncidentDetail>,
+    pub maintenance: Vec<MaintenanceWindow>,
+    pub endpoints: Vec<EndpointLatency>,
+    pub slow_requests: Vec<SlowRequest>,
+    pub query_insights: Option<QueryInsightsReport>,
+    pub targets: Vec<TargetStatus>,
+    /// État des instances ; `None` sans pair configuré (section masquée)
+    pub cluster: Option<ClusterStatus>,
+    pub throughput: Throughput,
+    pub log_counts: LogCounts,
+    pub disks: Vec<DiskUsage>,
+    pub cpu: CpuUsage,
+}
+
 /// Handler pour la page de status principale
 /// OPTIMISÉ: N'appelle AUCUNE fonction de health check, utilise uniquement le cache
 /// Temps de réponse ultra-rapide, toutes les métriques sont pré-calculées en arrière-plan
-pub async fn status_page(State(_db): State<DatabaseManager>) -> Result<Html<String>, StatusCode> {
-    // Charger le template HTML
-    let template = include_str!("../../assets/status.html");
+/// (seule la timeline des événements est lue en base, via une requête indexée)
+#[allow(clippy::too_many_arguments)]
+pub async fn status_page(
+    State(db): State<DatabaseManager>,
+    State(config): State<Arc<Config>>,
+    State(latency): State<Arc<LatencyStats>>,
+    State(store): State<Arc<MetricsStore>>,
+    State(status_codes): State<Arc<StatusCounters>>,
+    State(logs): State<Arc<LogBuffer>>,
+    State(queries): State<Arc<QueryInsights>>,
+    State(cluster): State<Arc<Cluster>>,
+) -> Result<Html<String>, StatusCode> {
+    // Timeline des derniers événements
+    let events = list_events(
+        db.get_pool(),
+        &EventsQuery::default(),
+        &QueryOptions::default(),
+        STATUS_PAGE_EVENTS,
+        0,
+    )
+    .await
+    .unwrap_or_else(|e| {
+        warn!("Failed to load events timeline: {}", e);
+        Vec::new()
+    });
     
-    // Utiliser UNIQUEMENT les métriques en cache (pas de calculs)
-    let metrics = match get_metrics_with_fallback() {
-        Some(m) => m,
-        None => {
-            // Fallback avec valeurs par défaut si aucun cache disponible (premier démarrage)
-            return Ok(Html(generate_fallback_page(template)));
-        }
+    // Disponibilité 24h / 7j / 30j (agrégat indexé sur l'historique)
+    let uptime = uptime_stats(db.get_pool()).await.unwrap_or_else(|e| {
+        warn!("Failed to compute uptime: {}", e);
+        Vec::new()
+    });
+    
+    // Incidents en cours, affichés en tête de page
+    let incidents = open_incidents(db.get_pool()).await.unwrap_or_else(|e| {
+        warn!("Failed to load open incidents: {}", e);
+        Vec::new()
+    });
+    
+    // Maintenances en cours ou annoncées, affichées au-dessus des incidents
+    let now = Utc::now();
+    let maintenance = announced_windows(db.get_pool(), &config.maintenance, now).await.unwrap_or_else(|e| {
+        warn!("Failed to load maintenance windows: {}", e);
+        Vec::new()
+    });
+    
+    // Dépendances externes (dernières sondes de chaque cible)
+    let targets = target_statuses(db.get_pool(), &config.monitoring.targets, STATUS_PAGE_HISTORY)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load monitored targets: {}", e);
+            Vec::new()
+        });
+    
+    // Utiliser UNIQUEMENT les métriques en cache (pas de calculs) ; sans cache (premier
+    // démarrage), la page d'attente n'affiche pas d'historique
+    let metrics = store.latest();
+    let history = match metrics {
+        Some(_) => recent_history(db.get_pool(), STATUS_PAGE_HISTORY).await.unwrap_or_else(|e| {
+            warn!("Failed to load metrics history: {}", e);
+            Vec::new()
+        }),
+        None => Vec::new(),
     };
+    let cluster_status = cluster
+        .is_enabled()
+        .then(|| cluster.status(cluster.local_node(metrics.as_ref(), &incidents)));
     
+    let data = StatusPageContent {
+        now,
+        metrics,
+        history,
+        events,
+        uptime,
+        incidents,
+        maintenance,
+        // Latence par route et requêtes les plus lentes (compteurs en mémoire)
+        endpoints: latency.snapshot(),
+        slow_requests: latency.slowest(),
+        // Requêtes SQL les plus coûteuses (dernier relevé de la tâche de fond)
+        query_insights: queries.latest(),
+        targets,
+        cluster: cluster_status,
+        // Débit et logs récents en direct (compteurs en mémoire)
+        throughput: status_codes.throughput(),
+        log_counts: logs.counts(),
+        disks: store.system().disks,
+        cpu: store.cpu(),
+    };
+    Ok(Html(render_status_page(&data, &config)))
+}
+
+/// Met en page la page de status à partir de données déjà rassemblées, sans accès à la
+/// base ni à l'horloge : le même `data` donne toujours le même HTML
+pub fn render_status_page(data: &StatusPageContent, config: &Config) -> String {
+    // Charger le template HTML
+    let template = include_str!("../../assets/status.html");
+
+    let events_html = generate_events_timeline(&data.events);
+    let uptime_html = generate_uptime_stats(&data.uptime);
+    let incidents_html = generate_incidents_banner(&data.incidents);
+    let maintenance_html = generate_maintenance_banner(&data.maintenance, data.now);
+    let endpoints_html = generate_endpoints_table(&data.endpoints);
+    let slow_endpoints_html = generate_slow_endpoints_table(&data.slow_requests);
+    let targets_html = generate_targets_html(&data.targets);
+
+    let page = match &data.metrics {
+        Some(metrics) => render_metrics(template, metrics, &data.history, data.now)
+            .replace("{TARGETS_HTML}", &targets_html)
+            .replace("{EVENTS_TIMELINE_HTML}", &events_html)
+            .replace("{ENDPOINTS_HTML}", &endpoints_html)
+            .replace("{UPTIME_STATS_HTML}", &uptime_html)
+            .replace("{INCIDENTS_HTML}", &incidents_html),
+        // Fallback avec valeurs par défaut si aucun cache disponible (premier démarrage)
+        None => generate_fallback_page(template, data.now, &incidents_html, &events_html, &endpoints_html, &uptime_html, &targets_html),
+    };
+
+    let page = replace_collection_interval(page, config.monitoring.interval_seconds);
+    let page = replace_slow_endpoints(page, &slow_endpoints_html, config.status.slow_endpoints_window_seconds);
+    let page = replace_query_insights(page, data.query_insights.as_ref());
+    let page = replace_cluster(page, data.cluster.as_ref());
+    let page = replace_live_counters(page, data.throughput, data.log_counts);
+    let page = page.replace("{DISKS_HTML}", &generate_disks_html(&data.disks));
+    let page = page.replace("{MAINTENANCE_HTML}", &maintenance_html);
+    let page = replace_cpu_cores(page, &data.cpu);
+    replace_branding(page, &config.status_page)
+}
+
+/// Remplace les scores, l'état et l'historique calculés par la tâche de fond
+fn render_metrics(template: &str, metrics: &PerformanceMetrics, history: &[HistoryEntry], now: DateTime<Utc>) -> String {
     // Toutes les données viennent du cache, aucun calcul
     let (health_color, health_icon, health_status) = get_health_display(metrics.health_score);
     let (score_color_start, score_color_end) = get_score_colors(metrics.health_score);
-    let status_info = get_status_info_from_metrics(&metrics);
+    let status_info = get_status_info_from_metrics(metrics);
     
-    // Historique (lecture rapide depuis la mémoire)
-    let history = get_history();
-    let history_bars = generate_history_bars(&history, "api");
-    let db_history_bars = generate_history_bars(&history, "database");
-    let network_history_bars = generate_network_history_bars(&history);
+    let history_bars = generate_history_bars(history, "api");
+    let db_history_bars = generate_history_bars(history, "database");
+    let network_history_bars = generate_network_history_bars(history);
     
     // Données temporelles (calculs légers)
     let uptime_hours = metrics.uptime / 3600;
     let timestamp = metrics.timestamp.format("%H:%M").to_string();
     
-    // Métriques réseau simulées (calcul très léger)
-    let (network_status, _network_load, _network_percent) = get_network_metrics();
+    // Charge réseau mesurée par la tâche de fond
+    let network_status = metrics
+        .network
+        .map_or_else(|| "Inconnue".to_string(), |network| format!("{} ({:.0}%)", network.load_label(), network.load_percent));
+    
+    // Runtime Tokio mesuré par la tâche de fond
+    let runtime_status = metrics.runtime.map_or_else(|| "Inconnu".to_string(), |runtime| format_runtime(&runtime));
+    
+    // Taux de 5xx du dernier intervalle et points retirés du score de santé
+    let (error_rate, error_penalty) = metrics.requests.map_or_else(
+        || ("—".to_string(), 0),
+        |requests| (format!("{:.1}%", requests.server_error_percent()), calculate_error_penalty(&requests)),
+    );
     
     // Remplacements dans le template (toutes les données viennent du cache)
-    let rendered = template
-        .replace("{API_NAME}", env!("CARGO_PKG_NAME"))
+    template
         .replace("{VERSION}", env!("CARGO_PKG_VERSION"))
         .replace("{TIMESTAMP}", &timestamp)
         
@@ -69,6 +269,8 @@ pub async fn status_page(State(_db): State<DatabaseManager>) -> Result<Html<Stri
         .replace("{MEMORY_SCORE}", &metrics.memory_score.to_string())
         .replace("{PERF_SCORE}", &metrics.perf_score.to_string())
         .replace("{NETWORK_SCORE}", &metrics.network_score.to_string())
+        .replace("{ERROR_RATE}", &error_rate)
+        .replace("{ERROR_PENALTY}", &error_penalty.to_string())
         
         // Status général (depuis le cache)
         .replace("{STATUS_BADGE}", &status_info.0)
@@ -87,19 +289,443 @@ pub async fn status_page(State(_db): State<DatabaseManager>) -> Result<Html<Stri
         .replace("{NETWORK_HISTORY_BARS_HTML}", &network_history_bars)
         
         // Détails techniques
-        .replace("{THEME}", "retro")
         .replace("{UPTIME_FULL}", &format_uptime(metrics.uptime))
-        .replace("{LOAD_AVERAGE}", &get_load_average());
+        .replace("{LOAD_AVERAGE}", &get_load_average(now))
+        .replace("{RUNTIME_STATUS}", &runtime_status)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/events",
+    tag = "Status",
+    params(
+        EventsQuery,
+        PaginationParams,
+        ("filter[kind]" = Option<String>, Query, description = "Only these event kinds (comma-separated)"),
+        ("sort" = Option<String>, Query, description = "Sort fields: occurred_at, kind (prefix with - for descending)"),
+        ("search" = Option<String>, Query, description = "Case-insensitive search in the event message"),
+        ("fields" = Option<String>, Query, description = "Only these attributes of each event (comma-separated): id, kind, message, details, occurred_at")
+    ),
+    responses(
+        (status = 200, description = "Application events, most recent first", body = PaginatedResponse<AppEvent>),
+        (status = 400, description = "Invalid pagination, filter, sort or fields parameters", body = ProblemDetails),
+        (status = 500, description = "Events could not be loaded", body = ProblemDetails)
+    ),
+    summary = "Get the application event timeline",
+    description = "Lists notable application events (deploys, maintenance windows, incidents, config reloads, fixture runs), optionally filtered by kind and date, for correlation during debugging."
+)]
+pub async fn events(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<EventsQuery>,
+    pagination: Pagination,
+    options: QueryOptions<EventFields>,
+    fields: Fields<EventFields>,
+) -> Result<PaginatedResponse<serde_json::Value>, AppError> {
+    let pool = db.get_pool();
+    let events = list_events(pool, &query, &options, pagination.limit(), pagination.offset()).await?;
+    let total = count_events(pool, &query, &options).await?;
+
+    Ok(pagination.response(fields.shape_all(&events)?, total))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/events/export",
+    tag = "Status",
+    params(
+        EventsQuery,
+        ExportQuery,
+        ("filter[kind]" = Option<String>, Query, description = "Only these event kinds (comma-separated)"),
+        ("sort" = Option<String>, Query, description = "Sort fields: occurred_at, kind (prefix with - for descending)"),
+        ("search" = Option<String>, Query, description = "Case-insensitive search in the event message")
+    ),
+    responses(
+        (status = 200, description = "Matching events, one per line",
+            content((String = "application/x-ndjson"), (String = "text/csv"))),
+        (status = 400, description = "Invalid filter or sort parameters", body = ProblemDetails)
+    ),
+    summary = "Export the application event timeline",
+    description = "Streams every matching event as NDJSON or CSV without buffering the result set in memory."
+)]
+pub async fn export_events(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<EventsQuery>,
+    Query(export): Query<ExportQuery>,
+    options: QueryOptions<EventFields>,
+) -> Response {
+    let rows = stream_events(db.get_pool().clone(), query, options);
+
+    match export.format {
+        ExportFormat::Ndjson => ndjson(rows),
+        ExportFormat::Csv => csv(rows, "events.csv"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status",
+    tag = "Status",
+    responses(
+        (status = 200, description = "Latest metrics computed by the background task, with 24h/7d/30d uptime and open incidents", body = ApiResponse<StatusSummary>),
+        (status = 503, description = "No metrics have been computed yet (first minutes after startup)", body = ProblemDetails)
+    ),
+    summary = "Get the current status metrics",
+    description = "Returns the same data as the HTML status page (health score, system usage, API and database latency, network load, uptime percentages, open incidents, throughput). Throughput is measured live; other metrics are refreshed every `[monitoring] interval_seconds` (5 minutes by default); see `/api/status/history` for past entries."
+)]
+pub async fn status(
+    State(db): State<DatabaseManager>,
+    State(store): State<Arc<MetricsStore>>,
+    State(status_codes): State<Arc<StatusCounters>>,
+) -> Result<ApiResponse<StatusSummary>, AppError> {
+    let metrics = store
+        .latest()
+        .ok_or_else(|| AppError::coded(ErrorCode::MetricsNotReady, "metrics have not been computed yet"))?;
+    let uptime = uptime_stats(db.get_pool()).await?;
+    let incidents = open_incidents(db.get_pool()).await?;
+    Ok(ApiResponse::ok(StatusSummary { metrics, uptime, incidents, throughput: status_codes.throughput() }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/cluster",
+    tag = "Status",
+    responses(
+        (status = 200, description = "State of this instance and of its peers, with the aggregate status", body = ApiResponse<ClusterStatus>)
+    ),
+    summary = "Get the cluster status",
+    description = "Aggregates this instance with the peers listed in `[[monitoring.peers]]`, whose `/api/status` is polled by the background metrics task every `[monitoring] interval_seconds`. The cluster is `operational` when every node is, `down` when no node is available, and `degraded` otherwise. Without peers, only this instance is listed."
+)]
+pub async fn cluster(
+    State(db): State<DatabaseManager>,
+    State(store): State<Arc<MetricsStore>>,
+    State(cluster): State<Arc<Cluster>>,
+) -> Result<ApiResponse<ClusterStatus>, AppError> {
+    let incidents = open_incidents(db.get_pool()).await?;
+    let local = cluster.local_node(store.latest().as_ref(), &incidents);
+    Ok(ApiResponse::ok(cluster.status(local)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/status/badge.svg",
+    tag = "Status",
+    params(BadgeQuery),
+    responses(
+        (status = 200, description = "SVG badge: operational, degraded, down or unknown", content_type = "image/svg+xml", body = String)
+    ),
+    summary = "Get the live status badge",
+    description = "Embed it in a README with `![status](https://api.example.com/status/badge.svg)`. The service is `down` when the API or the database does not respond or a critical incident is open, and `degraded` when the health score is below 60 or another incident is open."
+)]
+pub async fn badge_svg(
+    State(db): State<DatabaseManager>,
+    State(store): State<Arc<MetricsStore>>,
+    Query(query): Query<BadgeQuery>,
+) -> Response {
+    let status = service_status(&db, &store).await;
+    let svg = render_badge(query.label(), status.as_str(), status.hex_color());
+
+    (
+        [(header::CONTENT_TYPE, "image/svg+xml"), (header::CACHE_CONTROL, "no-cache, max-age=0")],
+        svg,
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/badge",
+    tag = "Status",
+    params(BadgeQuery),
+    responses(
+        (status = 200, description = "Badge in the shields.io endpoint format (not wrapped in `data`)", body = ShieldsBadge)
+    ),
+    summary = "Get the status badge for shields.io",
+    description = "Use it with `https://img.shields.io/endpoint?url=https://api.example.com/api/status/badge` to style the badge with shields.io. Same states as `/status/badge.svg`."
+)]
+pub async fn badge(
+    State(db): State<DatabaseManager>,
+    State(store): State<Arc<MetricsStore>>,
+    Query(query): Query<BadgeQuery>,
+) -> Response {
+    let status = service_status(&db, &store).await;
+    let badge = ShieldsBadge {
+        schema_version: 1,
+        label: query.label().to_string(),
+        message: status.as_str().to_string(),
+        color: status.color().to_string(),
+    };
+
+    ([(header::CACHE_CONTROL, "no-cache, max-age=0")], Json(badge)).into_response()
+}
+
+/// État global à partir du cache des métriques et des incidents ouverts
+async fn service_status(db: &DatabaseManager, store: &MetricsStore) -> ServiceStatus {
+    let incidents = open_incidents(db.get_pool()).await.unwrap_or_else(|e| {
+        warn!("Failed to load open incidents: {}", e);
+        Vec::new()
+    });
+    ServiceStatus::evaluate(store.latest().as_ref(), &incidents)
+}
+
+/// Badge SVG au style « flat » de shields.io
+fn render_badge(label: &str, message: &str, color: &str) -> String {
+    // Largeur approximative du texte en Verdana 11px
+    let text_width = |text: &str| text.chars().count() * 7 + 10;
+    let (label_width, message_width) = (text_width(label), text_width(message));
+    let width = label_width + message_width;
+    let (label, message) = (escape_html(label), escape_html(message));
 
-    Ok(Html(rendered))
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+<title>{label}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="14">{label}</text>
+<text x="{message_x}" y="14">{message}</text>
+</g>
+</svg>"##,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/live",
+    tag = "Status",
+    responses(
+        (status = 200, description = "Server-sent events: a `metrics` event with the current metrics, then one per update",
+            content_type = "text/event-stream", body = PerformanceMetrics)
+    ),
+    summary = "Stream status metrics updates",
+    description = "Pushes the metrics computed by the background task (every `[monitoring] interval_seconds`) as they are published. The status page subscribes to this stream instead of reloading."
+)]
+pub async fn live(
+    State(config): State<Arc<Config>>,
+    State(store): State<Arc<MetricsStore>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Abonnement avant la lecture du cache : aucune mise à jour n'est perdue entre les deux
+    let updates = store.subscribe();
+    let current = stream::iter(store.latest());
+    let updates = stream::unfold(updates, |mut updates| async move {
+        loop {
+            match updates.recv().await {
+                Ok(metrics) => return Some((metrics, updates)),
+                // Un abonné trop lent ne garde que les métriques les plus récentes
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let json_case = config.api.json_case;
+    let events = current.chain(updates).map(move |metrics| {
+        let mut data = serde_json::to_value(&metrics).unwrap_or_default();
+        // Mêmes noms de champs que les réponses JSON (`[api] json_case`)
+        if json_case == JsonCase::Camel {
+            data = rename_keys(data, &snake_to_camel);
+        }
+        Ok(Event::default().event("metrics").data(data.to_string()))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/endpoints",
+    tag = "Status",
+    responses(
+        (status = 200, description = "Latency histogram of each route since startup, most requested first", body = ApiResponse<Vec<EndpointLatency>>)
+    ),
+    summary = "Get the latency of each endpoint",
+    description = "Counts, error counts and latency quantiles per route template (`GET /api/users/{id}`), measured by the server for real traffic. Quantiles are estimated from the histogram buckets. Counters are kept in memory and reset on restart."
+)]
+pub async fn endpoints(State(latency): State<Arc<LatencyStats>>) -> ApiResponse<Vec<EndpointLatency>> {
+    ApiResponse::ok(latency.snapshot())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/targets",
+    tag = "Status",
+    responses(
+        (status = 200, description = "Configured external dependencies with their latest probes, oldest first", body = ApiResponse<Vec<TargetStatus>>),
+        (status = 500, description = "Probe history could not be loaded", body = ProblemDetails)
+    ),
+    summary = "Get the state of the monitored external dependencies",
+    description = "Each target of `[[monitoring.targets]]` is probed by the background metrics task every `[monitoring] interval_seconds` (HTTP `GET` or TCP connection). Returns the last 50 probes of each target; a target without probes has not been checked yet."
+)]
+pub async fn targets(
+    State(db): State<DatabaseManager>,
+    State(config): State<Arc<Config>>,
+) -> Result<ApiResponse<Vec<TargetStatus>>, AppError> {
+    let targets = target_statuses(db.get_pool(), &config.monitoring.targets, STATUS_PAGE_HISTORY).await?;
+    Ok(ApiResponse::ok(targets))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/status/history",
+    tag = "Status",
+    params(HistoryQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Metrics history entries, most recent first", body = PaginatedResponse<HistoryEntry>),
+        (status = 400, description = "Invalid pagination parameters", body = ProblemDetails),
+        (status = 500, description = "History could not be loaded", body = ProblemDetails)
+    ),
+    summary = "Get the metrics history",
+    description = "Lists the entries recorded by the background metrics task every `[monitoring] interval_seconds` (5 minutes by default). Entries older than `[status] history_retention_days` are deleted."
+)]
+pub async fn history(
+    State(db): State<DatabaseManager>,
+    Query(query): Query<HistoryQuery>,
+    pagination: Pagination,
+) -> Result<PaginatedResponse<HistoryEntry>, AppError> {
+    let pool = db.get_pool();
+    let entries = list_history(pool, query.since, pagination.limit(), pagination.offset()).await?;
+    let total = count_history(pool, query.since).await?;
+
+    Ok(pagination.response(entries, total))
+}
+
+/// Résumé du runtime Tokio : tâches vivantes, files d'attente et occupation des workers
+fn format_runtime(runtime: &RuntimeUsage) -> String {
+    let blocking = runtime
+        .blocking_queue_depth
+        .map_or_else(String::new, |depth| format!(" • bloquantes en attente {}", depth));
+    format!(
+        "{} tâches • file {}{} • occupation {:.0}%",
+        runtime.alive_tasks, runtime.global_queue_depth, blocking, runtime.busy_percent
+    )
+}
+
+/// Remplace les compteurs lus en direct : débit courant et pic, avertissements et erreurs récents
+/// Occupation de chaque point de montage mesuré, dans les détails techniques
+fn generate_disks_html(disks: &[DiskUsage]) -> String {
+    disks.iter().map(|disk| {
+        let color = if disk.usage_percent > 95.0 {
+            "text-error"
+        } else if disk.usage_percent > 85.0 {
+            "text-warning"
+        } else {
+            ""
+        };
+
+        format!(
+            r#"<div class="flex justify-between gap-2">
+                                    <span class="opacity-70 truncate" title="{}">Disque {}:</span>
+                                    <span class="font-medium whitespace-nowrap {}">{:.1}% de {:.0} Go</span>
+                                </div>"#,
+            escape_html(&disk.file_system),
+            escape_html(&disk.mount_point),
+            color,
+            disk.usage_percent,
+            disk.total_bytes as f64 / 1_000_000_000.0
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+/// Panneau dépliable de l'usage par coeur, résumé par le coeur le plus chargé
+fn replace_cpu_cores(page: String, cpu: &CpuUsage) -> String {
+    let cores_html = cpu.cores.iter().map(|core| {
+        let color = if core.usage_percent > 90.0 {
+            "error"
+        } else if core.usage_percent > 70.0 {
+            "warning"
+        } else {
+            "success"
+        };
+
+        format!(
+            r#"<div class="flex items-center justify-between gap-2">
+                                            <span class="opacity-70">{}</span>
+                                            <progress class="progress progress-{} w-20" value="{:.0}" max="100"></progress>
+                                            <span class="font-medium w-10 text-right">{:.0}%</span>
+                                        </div>"#,
+            escape_html(&core.name),
+            color,
+            core.usage_percent,
+            core.usage_percent
+        )
+    }).collect::<Vec<_>>().join("");
+
+    page.replace(
+        "{CPU_CORES_SUMMARY}",
+        &format!("{} coeurs • max {:.0}%", cpu.cores.len(), cpu.max_percent),
+    )
+    .replace("{CPU_CORES_HTML}", &cores_html)
+}
+
+fn replace_live_counters(page: String, throughput: Throughput, log_counts: LogCounts) -> String {
+    page.replace("{CURRENT_RPS}", &format!("{:.1}", throughput.current_rps))
+        .replace("{PEAK_RPS}", &throughput.peak_rps.to_string())
+        .replace("{LOG_WARNINGS}", &log_counts.warnings.to_string())
+        .replace("{LOG_ERRORS}", &log_counts.errors.to_string())
+}
+
+/// Applique la personnalisation `[status_page]` : titre, logo, thème, liens du pied de page
+/// et sections masquées. Appliquée en dernier, pour qu'aucun autre remplacement ne touche
+/// aux valeurs configurées.
+fn replace_branding(page: String, branding: &StatusPageConfig) -> String {
+    let title = escape_html(branding.title());
+    let logo = match &branding.logo_url {
+        Some(url) => format!(r#"<img src="{}" alt="{}" class="h-8 w-auto" />"#, escape_html(url), title),
+        None => r#"<div class="avatar placeholder">
+                                    <div class="bg-primary text-primary-content rounded-full w-8">
+                                        <i data-lucide="activity" class="w-4 h-4"></i>
+                                    </div>
+                                </div>"#
+            .to_string(),
+    };
+    let footer_links = if branding.footer_links.is_empty() {
+        String::new()
+    } else {
+        let links: Vec<String> = branding
+            .footer_links
+            .iter()
+            .map(|link| format!(r#"<a href="{}" class="link link-hover">{}</a>"#, escape_html(&link.url), escape_html(&link.label)))
+            .collect();
+        format!(r#"<div class="flex justify-center gap-4 mt-2 text-sm">{}</div>"#, links.join(""))
+    };
+
+    let page = [
+        (StatusPageSection::Incidents, "{HIDE_INCIDENTS}"),
+        (StatusPageSection::Health, "{HIDE_HEALTH}"),
+        (StatusPageSection::Cluster, "{HIDE_CLUSTER}"),
+        (StatusPageSection::Overview, "{HIDE_OVERVIEW}"),
+        (StatusPageSection::History, "{HIDE_HISTORY}"),
+        (StatusPageSection::Endpoints, "{HIDE_ENDPOINTS}"),
+        (StatusPageSection::Queries, "{HIDE_QUERIES}"),
+        (StatusPageSection::Events, "{HIDE_EVENTS}"),
+        (StatusPageSection::Sidebar, "{HIDE_SIDEBAR}"),
+    ]
+    .into_iter()
+    .fold(page, |page, (section, placeholder)| {
+        page.replace(placeholder, if branding.shows(section) { "" } else { "hidden" })
+    });
+
+    page.replace("{THEME}", &escape_html(&branding.theme))
+        .replace("{LOGO_HTML}", &logo)
+        .replace("{FOOTER_LINKS_HTML}", &footer_links)
+        .replace("{PAGE_TITLE}", &title)
 }
 
 /// Génère une page de fallback si aucun cache n'est disponible
-fn generate_fallback_page(template: &str) -> String {
-    let timestamp = Utc::now().format("%H:%M").to_string();
+fn generate_fallback_page(
+    template: &str,
+    now: DateTime<Utc>,
+    incidents_html: &str,
+    events_html: &str,
+    endpoints_html: &str,
+    uptime_html: &str,
+    targets_html: &str,
+) -> String {
+    let timestamp = now.format("%H:%M").to_string();
     
     template
-        .replace("{API_NAME}", env!("CARGO_PKG_NAME"))
         .replace("{VERSION}", env!("CARGO_PKG_VERSION"))
         .replace("{TIMESTAMP}", &timestamp)
         
@@ -114,6 +740,8 @@ fn generate_fallback_page(template: &str) -> String {
         .replace("{MEMORY_SCORE}", "20")
         .replace("{PERF_SCORE}", "20")
         .replace("{NETWORK_SCORE}", "15")
+        .replace("{ERROR_RATE}", "—")
+        .replace("{ERROR_PENALTY}", "0")
         
         .replace("{STATUS_BADGE}", "info")
         .replace("{STATUS_TEXT}", "Démarrage")
@@ -127,10 +755,15 @@ fn generate_fallback_page(template: &str) -> String {
         .replace("{HISTORY_BARS_HTML}", "")
         .replace("{DB_HISTORY_BARS_HTML}", "")
         .replace("{NETWORK_HISTORY_BARS_HTML}", "")
+        .replace("{TARGETS_HTML}", targets_html)
+        .replace("{EVENTS_TIMELINE_HTML}", events_html)
+        .replace("{ENDPOINTS_HTML}", endpoints_html)
+        .replace("{UPTIME_STATS_HTML}", uptime_html)
+        .replace("{INCIDENTS_HTML}", incidents_html)
         
-        .replace("{THEME}", "retro")
         .replace("{UPTIME_FULL}", "0m")
         .replace("{LOAD_AVERAGE}", "0.00")
+        .replace("{RUNTIME_STATUS}", "Initialisation")
 }
 
 // Fonctions utilitaires optimisées (pas de calculs lourds)
@@ -169,26 +802,8 @@ fn get_status_info_from_metrics(metrics: &crate::models::status::PerformanceMetr
     }
 }
 
-fn get_network_metrics() -> (String, String, u8) {
-    // Simulation très légère
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    Utc::now().timestamp().hash(&mut hasher);
-    let load_percent = (hasher.finish() % 80) as u8 + 10; // Entre 10% et 90%
-    
-    let status = match load_percent {
-        0..=30 => "Faible",
-        31..=60 => "Modérée",
-        61..=80 => "Élevée",
-        _ => "Critique",
-    };
-    
-    (status.to_string(), format!("{}% utilisé", load_percent), load_percent)
-}
-
-fn generate_history_bars(history: &[HistoryEntry], bar_type: &str) -> String {
+/// Barres de l'historique de la page de status, pour l'API (`api`) ou la base (`database`)
+pub fn generate_history_bars(history: &[HistoryEntry], bar_type: &str) -> String {
     history.iter().map(|entry| {
         let (color, tooltip) = match bar_type {
             "api" => {
@@ -249,36 +864,31 @@ fn generate_history_bars(history: &[HistoryEntry], bar_type: &str) -> String {
     }).collect::<Vec<_>>().join("")
 }
 
-fn generate_network_history_bars(history: &[HistoryEntry]) -> String {
+/// Barres de l'historique du trafic réseau de la page de status
+pub fn generate_network_history_bars(history: &[HistoryEntry]) -> String {
     history.iter().map(|entry| {
-        // Simulation légère basée sur le timestamp
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        entry.timestamp.timestamp().hash(&mut hasher);
-        let network_load = (hasher.finish() % 100) as f32;
-        
-        let color = match network_load {
-            x if x < 40.0 => "excellent",
-            x if x < 60.0 => "good",
-            x if x < 80.0 => "warning",
-            x if x < 95.0 => "critical",
-            _ => "overload",
-        };
-        
-        let tooltip = format!(
-            "⏱️ {} | 🌐 {:.0}% charge | 📡 {}",
-            entry.timestamp.format("%H:%M"),
-            network_load,
-            match network_load {
-                x if x < 40.0 => "Réseau fluide",
-                x if x < 60.0 => "Charge normale", 
-                x if x < 80.0 => "Charge élevée",
-                x if x < 95.0 => "Réseau saturé",
-                _ => "Réseau surchargé"
+        let (color, tooltip) = match entry.network {
+            Some(network) => {
+                let color = match network.load_percent {
+                    _ if network.errors > 0 => "critical",
+                    x if x < 40.0 => "excellent",
+                    x if x < 60.0 => "good",
+                    x if x < 80.0 => "warning",
+                    x if x < 95.0 => "critical",
+                    _ => "overload",
+                };
+                let tooltip = format!(
+                    "⏱️ {} | 🌐 {:.0}% charge | ⬇️ {} ⬆️ {} | 📡 {}",
+                    entry.timestamp.format("%H:%M"),
+                    network.load_percent,
+                    format_rate(network.rx_bytes_per_sec),
+                    format_rate(network.tx_bytes_per_sec),
+                    if network.errors > 0 { format!("{} erreur(s)", network.errors) } else { "Aucune erreur".to_string() }
+                );
+                (color, tooltip)
             }
-        );
+            None => ("good", format!("⏱️ {} | 🌐 Non mesuré", entry.timestamp.format("%H:%M"))),
+        };
         
         format!(
             r#"<div class="status-tick {}" title="{}">
@@ -289,6 +899,355 @@ fn generate_network_history_bars(history: &[HistoryEntry]) -> String {
     }).collect::<Vec<_>>().join("")
 }
 
+/// Débit lisible (o/s, Ko/s, Mo/s)
+fn format_rate(bytes_per_sec: u64) -> String {
+    match bytes_per_sec {
+        x if x >= 1024 * 1024 => format!("{:.1} Mo/s", x as f64 / (1024.0 * 1024.0)),
+        x if x >= 1024 => format!("{:.1} Ko/s", x as f64 / 1024.0),
+        x => format!("{} o/s", x),
+    }
+}
+
+fn generate_uptime_stats(uptime: &[UptimeStats]) -> String {
+    uptime.iter().map(|stats| {
+        let label = match stats.window.as_str() {
+            "24h" => "24 heures",
+            "7d" => "7 jours",
+            "30d" => "30 jours",
+            other => other,
+        };
+        let (value, color) = match stats.uptime_percent {
+            Some(percent) if percent >= 99.9 => (format!("{:.2}%", percent), "text-success"),
+            Some(percent) if percent >= 99.0 => (format!("{:.2}%", percent), "text-warning"),
+            Some(percent) => (format!("{:.2}%", percent), "text-error"),
+            None => ("—".to_string(), "opacity-60"),
+        };
+        let response_time = stats
+            .avg_response_time_ms
+            .map_or_else(|| "aucune mesure".to_string(), |ms| format!("{:.0} ms en moyenne", ms));
+
+        format!(
+            r#"<div class="stat p-2">
+                <div class="stat-title text-xs">Disponibilité {}</div>
+                <div class="stat-value text-lg {}">{}</div>
+                <div class="stat-desc">{}</div>
+            </div>"#,
+            label, color, value, response_time
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+fn generate_targets_html(targets: &[TargetStatus]) -> String {
+    targets.iter().map(|target| {
+        let (badge, label) = match target.latest() {
+            Some(check) if check.up => ("badge-success", format!("✅ {} ms", check.response_time_ms.unwrap_or(0))),
+            Some(_) => ("badge-error", "❌ Indisponible".to_string()),
+            None => ("badge-ghost", "En attente".to_string()),
+        };
+        let bars = target.checks.iter().map(|check| {
+            let (color, result) = match check.response_time_ms {
+                Some(ms) if check.up => (determine_network_status_color(ms as f32), format!("✅ {}ms", ms)),
+                _ => ("critical".to_string(), format!("❌ {}", check.error.as_deref().unwrap_or("Indisponible"))),
+            };
+            let tooltip = escape_html(&format!("⏱️ {} | {}", check.checked_at.format("%H:%M"), result));
+            format!(
+                r#"<div class="status-tick {}" title="{}">
+                <div class="tooltip">{}</div>
+            </div>"#,
+                color, tooltip, tooltip
+            )
+        }).collect::<Vec<_>>().join("");
+
+        format!(
+            r#"<div>
+                <div class="flex justify-between items-center mb-1">
+                    <span class="text-sm font-medium flex items-center gap-2" title="{}">
+                        <i data-lucide="{}" class="w-3 h-3"></i>
+                        {}
+                    </span>
+                    <span class="badge badge-sm {}">{}</span>
+                </div>
+                <div class="status-bar">
+                    {}
+                </div>
+            </div>"#,
+            escape_html(&target.endpoint),
+            if target.kind == "tcp" { "plug" } else { "globe" },
+            escape_html(&target.name),
+            badge,
+            label,
+            bars
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+fn generate_cluster_nodes(nodes: &[NodeStatus]) -> String {
+    nodes.iter().map(|node| {
+        let (badge, _) = service_status_display(node.status);
+        let score = node.health_score.map_or_else(|| "—".to_string(), |score| format!("{}/100", score));
+        let detail = match (&node.error, node.response_time_ms) {
+            (Some(error), _) => escape_html(error),
+            (None, Some(ms)) => format!("Répond en {} ms", ms),
+            (None, None) if node.local => "Cette instance".to_string(),
+            (None, None) => "En attente".to_string(),
+        };
+
+        format!(
+            r#"<div class="border border-base-300 rounded-box p-3">
+                                <div class="flex justify-between items-center mb-1">
+                                    <span class="text-sm font-medium flex items-center gap-2" title="{}">
+                                        <i data-lucide="{}" class="w-3 h-3"></i>
+                                        {}
+                                    </span>
+                                    <span class="badge badge-sm badge-{}">{}</span>
+                                </div>
+                                <div class="flex justify-between text-xs opacity-70">
+                                    <span class="truncate">{}</span>
+                                    <span>{}</span>
+                                </div>
+                            </div>"#,
+            escape_html(node.url.as_deref().unwrap_or("")),
+            if node.local { "map-pin" } else { "server" },
+            escape_html(&node.name),
+            badge,
+            node.status.as_str(),
+            detail,
+            score
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+/// Grille des instances et état agrégé ; section masquée sans pair configuré
+fn replace_cluster(page: String, status: Option<&ClusterStatus>) -> String {
+    let Some(status) = status else {
+        return page
+            .replace("{HIDE_CLUSTER}", "hidden")
+            .replace("{CLUSTER_NODES_HTML}", "")
+            .replace("{CLUSTER_SUMMARY}", "")
+            .replace("{CLUSTER_BADGE}", "ghost")
+            .replace("{CLUSTER_STATUS}", "");
+    };
+
+    let (badge, label) = service_status_display(status.status);
+    let score = status.health_score.map_or_else(String::new, |score| format!(" • score moyen {}/100", score));
+    page.replace("{CLUSTER_NODES_HTML}", &generate_cluster_nodes(&status.nodes))
+        .replace("{CLUSTER_SUMMARY}", &format!("{}/{} instances disponibles{}", status.nodes_up, status.nodes.len(), score))
+        .replace("{CLUSTER_BADGE}", badge)
+        .replace("{CLUSTER_STATUS}", label)
+}
+
+/// Couleur daisyUI et libellé d'un état de service
+fn service_status_display(status: ServiceStatus) -> (&'static str, &'static str) {
+    match status {
+        ServiceStatus::Operational => ("success", "Opérationnel"),
+        ServiceStatus::Degraded => ("warning", "Dégradé"),
+        ServiceStatus::Down => ("error", "Indisponible"),
+        ServiceStatus::Unknown => ("ghost", "Inconnu"),
+    }
+}
+
+fn generate_maintenance_banner(windows: &[MaintenanceWindow], now: DateTime<Utc>) -> String {
+    windows.iter().map(|window| {
+        let (alert, label) = if window.is_active_at(now) {
+            ("alert-warning", "Maintenance en cours")
+        } else {
+            ("alert-info", "Maintenance planifiée")
+        };
+        let description = if window.description.is_empty() {
+            String::new()
+        } else {
+            format!(r#"<p class="text-sm">{}</p>"#, escape_html(&window.description))
+        };
+
+        format!(
+            r#"<div role="alert" class="alert {} shadow-lg mb-4">
+                <i data-lucide="wrench" class="w-6 h-6"></i>
+                <div>
+                    <h3 class="font-bold">{} <span class="badge badge-sm badge-ghost ml-2">{}</span></h3>
+                    {}
+                    <p class="text-xs opacity-60">Du {} au {} (UTC)</p>
+                </div>
+            </div>"#,
+            alert,
+            escape_html(&window.title),
+            label,
+            description,
+            window.starts_at.format("%d/%m %H:%M"),
+            window.ends_at.format("%d/%m %H:%M")
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+fn generate_incidents_banner(incidents: &[IncidentDetail]) -> String {
+    incidents.iter().map(|detail| {
+        let incident = &detail.incident;
+        let alert = match incident.severity.as_str() {
+            "critical" => "alert-error",
+            "major" => "alert-warning",
+            _ => "alert-info",
+        };
+        let status = match incident.status.as_str() {
+            "identified" => "Identifié",
+            "monitoring" => "Surveillance",
+            _ => "Investigation",
+        };
+        let latest = detail.updates.first().map_or_else(String::new, |update| {
+            format!(
+                r#"<p class="text-sm">{} <span class="opacity-60">— {}</span></p>"#,
+                escape_html(&update.message),
+                update.created_at.format("%d/%m %H:%M")
+            )
+        });
+
+        format!(
+            r#"<div role="alert" class="alert {} shadow-lg mb-4">
+                <i data-lucide="alert-triangle" class="w-6 h-6"></i>
+                <div>
+                    <h3 class="font-bold">{} <span class="badge badge-sm badge-ghost ml-2">{}</span></h3>
+                    {}
+                </div>
+            </div>"#,
+            alert, escape_html(&incident.title), status, latest
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+fn generate_endpoints_table(endpoints: &[EndpointLatency]) -> String {
+    if endpoints.is_empty() {
+        return r#"<tr><td class="opacity-60">Aucune requête enregistrée</td></tr>"#.to_string();
+    }
+
+    endpoints.iter().take(STATUS_PAGE_ENDPOINTS).map(|endpoint| {
+        let color = determine_network_status_color(endpoint.p95_ms as f32);
+        let badge = match color.as_str() {
+            "excellent" | "good" => "success",
+            "warning" => "warning",
+            _ => "error",
+        };
+
+        format!(
+            r#"<tr>
+                <td class="whitespace-nowrap"><span class="badge badge-ghost badge-sm">{}</span> {}</td>
+                <td class="text-right">{}</td>
+                <td class="text-right">{:.1} ms</td>
+                <td class="text-right"><span class="badge badge-{} badge-sm">{:.0} ms</span></td>
+                <td class="text-right">{:.0} ms</td>
+                <td class="text-right">{}</td>
+            </tr>"#,
+            endpoint.method,
+            escape_html(&endpoint.route),
+            endpoint.count,
+            endpoint.avg_ms,
+            badge,
+            endpoint.p95_ms,
+            endpoint.p99_ms,
+            endpoint.server_errors
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+fn generate_slow_endpoints_table(requests: &[SlowRequest]) -> String {
+    if requests.is_empty() {
+        return r#"<tr><td class="opacity-60">Aucune requête sur la fenêtre</td></tr>"#.to_string();
+    }
+
+    requests.iter().map(|request| {
+        let badge = match request.status {
+            500..=599 => "error",
+            400..=499 => "warning",
+            _ => "ghost",
+        };
+
+        format!(
+            r#"<tr>
+                <td class="whitespace-nowrap"><span class="badge badge-ghost badge-sm">{}</span> {}</td>
+                <td class="text-right"><span class="badge badge-{} badge-sm">{}</span></td>
+                <td class="text-right">{:.0} ms</td>
+                <td class="text-right opacity-60">{}</td>
+            </tr>"#,
+            request.method,
+            escape_html(&request.route),
+            badge,
+            request.status,
+            request.duration_ms,
+            request.completed_at.format("%H:%M:%S")
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+/// Classement des requêtes les plus lentes et durée de sa fenêtre (`[status] slow_endpoints_window_seconds`)
+fn replace_slow_endpoints(page: String, slow_endpoints_html: &str, window_seconds: u64) -> String {
+    page.replace("{SLOW_ENDPOINTS_HTML}", slow_endpoints_html)
+        .replace("{SLOW_ENDPOINTS_WINDOW}", &format_uptime(window_seconds))
+}
+
+fn generate_query_rows(queries: &[QueryStat]) -> String {
+    if queries.is_empty() {
+        return r#"<tr><td class="opacity-60">Aucune requête relevée</td></tr>"#.to_string();
+    }
+
+    queries.iter().map(|query| {
+        let text = escape_html(&query.query);
+        format!(
+            r#"<tr>
+                <td class="max-w-xs truncate font-mono" title="{}">{}</td>
+                <td class="text-right">{}</td>
+                <td class="text-right">{:.0} ms</td>
+                <td class="text-right">{:.1} ms</td>
+            </tr>"#,
+            text,
+            text,
+            query.calls,
+            query.total_time_ms,
+            query.mean_time_ms
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
+/// Classements de `pg_stat_statements` ; section masquée sans relevé (collecteur désactivé
+/// ou extension absente)
+fn replace_query_insights(page: String, report: Option<&QueryInsightsReport>) -> String {
+    match report {
+        Some(report) => page
+            .replace("{QUERIES_BY_TOTAL_HTML}", &generate_query_rows(&report.by_total_time))
+            .replace("{QUERIES_BY_MEAN_HTML}", &generate_query_rows(&report.by_mean_time))
+            .replace("{QUERIES_COLLECTED_AT}", &report.collected_at.format("%H:%M").to_string()),
+        None => page
+            .replace("{HIDE_QUERIES}", "hidden")
+            .replace("{QUERIES_BY_TOTAL_HTML}", "")
+            .replace("{QUERIES_BY_MEAN_HTML}", "")
+            .replace("{QUERIES_COLLECTED_AT}", "—"),
+    }
+}
+
+fn generate_events_timeline(events: &[AppEvent]) -> String {
+    if events.is_empty() {
+        return r#"<tr><td class="opacity-60">Aucun événement enregistré</td></tr>"#.to_string();
+    }
+
+    events.iter().map(|event| {
+        let badge = match event.kind.as_str() {
+            "deploy" => "primary",
+            "maintenance" => "warning",
+            "incident" => "error",
+            "config_reload" => "info",
+            _ => "ghost",
+        };
+
+        format!(
+            r#"<tr>
+                <td class="whitespace-nowrap opacity-70">{}</td>
+                <td><span class="badge badge-{} badge-sm">{}</span></td>
+                <td>{}</td>
+            </tr>"#,
+            event.occurred_at.format("%d/%m %H:%M"),
+            badge,
+            escape_html(&event.kind),
+            escape_html(&event.message)
+        )
+    }).collect::<Vec<_>>().join("")
+}
+
 fn determine_network_status_color(response_time: f32) -> String {
     match response_time {
         x if x < 100.0 => "excellent".to_string(),
@@ -299,6 +1258,16 @@ fn determine_network_status_color(response_time: f32) -> String {
     }
 }
 
+/// Période couverte par l'historique affiché et intervalle de collecte (`[monitoring] interval_seconds`)
+fn replace_collection_interval(page: String, interval_seconds: u64) -> String {
+    let interval_seconds = interval_seconds.max(1);
+    let interval = if interval_seconds < 60 { format!("{}s", interval_seconds) } else { format_uptime(interval_seconds) };
+
+    page.replace("{HISTORY_SPAN}", &format_uptime(interval_seconds * STATUS_PAGE_HISTORY as u64))
+        .replace("{COLLECTION_INTERVAL}", &interval)
+        .replace("{REFRESH_INTERVAL_MS}", &(interval_seconds * 1000).to_string())
+}
+
 fn format_uptime(uptime_seconds: u64) -> String {
     let days = uptime_seconds / 86400;
     let hours = (uptime_seconds % 86400) / 3600;
@@ -313,13 +1282,13 @@ fn format_uptime(uptime_seconds: u64) -> String {
     }
 }
 
-fn get_load_average() -> String {
+fn get_load_average(now: DateTime<Utc>) -> String {
     // Simulation très légère
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
     
     let mut hasher = DefaultHasher::new();
-    Utc::now().timestamp().hash(&mut hasher);
+    now.timestamp().hash(&mut hasher);
     let load = (hasher.finish() % 300) as f32 / 100.0; // Entre 0.0 et 3.0
     
     format!("{:.2}", load)
@@ -0,0 +1,137 @@
+//! # Streaming Responses Module
+//!
+//! Ce module fournit des réponses d'export envoyées ligne par ligne, au fil d'un
+//! flux de résultats SQLx, sans charger l'ensemble des résultats en mémoire :
+//! - `ndjson` : un objet JSON par ligne (`application/x-ndjson`)
+//! - `csv` : un en-tête puis un enregistrement par ligne (`text/csv`)
+//!
+//! ```ignore
+//! let pool = db.get_pool().clone();
+//! let rows = async_stream::stream! {
+//!     let mut rows = sqlx::query_as::<_, User>("SELECT * FROM users").fetch(&pool);
+//!     while let Some(row) = rows.next().await {
+//!         yield row;
+//!     }
+//! };
+//! Ok(ndjson(rows))
+//! ```
+//!
+//! Le statut 200 est envoyé avant la première ligne : une erreur en cours de flux
+//! interrompt la connexion, et le client reçoit une réponse tronquée.
+
+use axum::{
+    body::Body,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use std::io;
+use tracing::error;
+
+/// Format d'export demandé par le client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+/// Paramètre `?format=` des routes d'export
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `ndjson` (défaut) ou `csv`
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Type exportable en CSV
+pub trait CsvRecord {
+    /// Noms des colonnes
+    fn csv_header() -> Vec<&'static str>;
+
+    /// Valeurs des colonnes, dans l'ordre de `csv_header`
+    fn csv_record(&self) -> Vec<String>;
+}
+
+/// Réponse NDJSON : chaque élément du flux est sérialisé sur une ligne.
+pub fn ndjson<S, T, E>(rows: S) -> Response
+where
+    S: Stream<Item = Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: std::fmt::Display,
+{
+    let lines = rows.map(|row| {
+        let row = row.map_err(stream_error)?;
+        let mut line = serde_json::to_vec(&row).map_err(stream_error)?;
+        line.push(b'\n');
+        Ok::<_, io::Error>(Bytes::from(line))
+    });
+
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(lines)).into_response()
+}
+
+/// Réponse CSV en pièce jointe : un en-tête puis un enregistrement par élément du flux.
+pub fn csv<S, T, E>(rows: S, filename: &str) -> Response
+where
+    S: Stream<Item = Result<T, E>> + Send + 'static,
+    T: CsvRecord,
+    E: std::fmt::Display,
+{
+    let header_line = Bytes::from(csv_line(T::csv_header()));
+    let records = rows.map(|row| {
+        let row = row.map_err(stream_error)?;
+        Ok::<_, io::Error>(Bytes::from(csv_line(row.csv_record())))
+    });
+    let lines = futures::stream::once(async { Ok(header_line) }).chain(records);
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Formate une ligne CSV (RFC 4180), terminée par CRLF
+pub fn csv_line<I, V>(values: I) -> String
+where
+    I: IntoIterator<Item = V>,
+    V: AsRef<str>,
+{
+    let mut line = values
+        .into_iter()
+        .map(|value| csv_field(value.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Échappe un champ CSV : guillemets si nécessaire, guillemets internes doublés.
+///
+/// Les valeurs commençant par `=`, `+`, `-` ou `@` sont préfixées d'une apostrophe
+/// pour ne pas être interprétées comme des formules par les tableurs.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn stream_error(e: impl std::fmt::Display) -> io::Error {
+    error!("Streaming export failed: {}", e);
+    io::Error::other(e.to_string())
+}
@@ -0,0 +1,275 @@
+//! # Uploads Handlers Module
+//!
+//! Ce module reçoit les fichiers envoyés en `multipart/form-data` (champ `file`)
+//! et les sert en téléchargement. Le contenu est écrit en flux dans le `Storage`
+//! de l'application, sans être chargé en mémoire :
+//! - la taille est limitée par `[uploads] max_size_bytes` (413 au-delà)
+//! - le type MIME doit figurer dans `[uploads] allowed_mime_types` (415 sinon) et,
+//!   pour les formats connus, correspondre à la signature du contenu
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{Json, Response},
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::{Config, UploadsConfig},
+    db::DatabaseManager,
+    extractors::{fields::Fields, multipart::MultipartStream, path::ApiPath},
+    handlers::{
+        download::{serve_file, StoredFile},
+        error::AppError,
+    },
+    middleware::cache::ResponseCache,
+    models::{
+        error::{ErrorCode, ProblemDetails},
+        uploads::{Upload, UploadFields, UploadId},
+    },
+    services::{storage::Storage, uploads},
+};
+
+/// Nom du champ de formulaire contenant le fichier
+const FILE_FIELD: &str = "file";
+
+#[utoipa::path(
+    post,
+    path = "/api/uploads",
+    tag = "Uploads",
+    request_body(content = String, content_type = "multipart/form-data", description = "Form with a `file` field"),
+    responses(
+        (status = 201, description = "File stored", body = Upload),
+        (status = 400, description = "Malformed form or missing file field", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 413, description = "File exceeds the configured size limit", body = ProblemDetails),
+        (status = 415, description = "File type not allowed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Upload a file",
+    description = "Streams the `file` field of a multipart form to storage, validating its size and MIME type, and records its metadata (size, SHA-256)."
+)]
+pub async fn upload(
+    State(db): State<DatabaseManager>,
+    State(storage): State<Arc<dyn Storage>>,
+    State(config): State<Arc<Config>>,
+    mut multipart: MultipartStream,
+) -> Result<(StatusCode, Json<Upload>), AppError> {
+    while let Some(part) = multipart.next_part().await? {
+        if part.name.as_deref() != Some(FILE_FIELD) {
+            continue;
+        }
+
+        let filename = part
+            .filename
+            .as_deref()
+            .map(sanitize_filename)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| AppError::coded(ErrorCode::MissingFile, "the file field has no filename"))?;
+        let content_type = part
+            .content_type
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        if !config.uploads.allowed_mime_types.contains(&content_type) {
+            return Err(AppError::coded(ErrorCode::UnsupportedFileType, format!("file type not allowed: {}", content_type)));
+        }
+
+        let id = UploadId::new(Uuid::new_v4());
+        let key = id.to_string();
+        let (size, sha256) = match write_part(&mut multipart, storage.as_ref(), &key, &content_type, &config.uploads).await {
+            Ok(written) => written,
+            Err(e) => {
+                discard(storage.as_ref(), &key).await;
+                return Err(e);
+            }
+        };
+
+        let upload = match uploads::insert_upload(db.get_pool(), id, &filename, &content_type, size, &sha256, &key).await {
+            Ok(upload) => upload,
+            Err(e) => {
+                discard(storage.as_ref(), &key).await;
+                return Err(e.into());
+            }
+        };
+
+        info!("Stored upload {} ({}, {} bytes)", upload.id, upload.content_type, upload.size_bytes);
+        return Ok((StatusCode::CREATED, Json(upload)));
+    }
+
+    Err(AppError::coded(ErrorCode::MissingFile, format!("missing `{}` field", FILE_FIELD)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/uploads/{id}",
+    tag = "Uploads",
+    params(
+        ("id" = Uuid, Path, description = "Upload identifier"),
+        ("fields" = Option<String>, Query, description = "Only these attributes (comma-separated)")
+    ),
+    responses(
+        (status = 200, description = "File metadata", body = Upload),
+        (status = 400, description = "Unknown field in `fields`", body = ProblemDetails),
+        (status = 404, description = "Unknown upload", body = ProblemDetails)
+    ),
+    summary = "Get file metadata"
+)]
+pub async fn get_upload(
+    State(db): State<DatabaseManager>,
+    ApiPath(id): ApiPath<UploadId>,
+    fields: Fields<UploadFields>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let upload = uploads::get_upload(db.get_pool(), id)
+        .await?
+        .ok_or_else(|| AppError::coded(ErrorCode::UploadNotFound, "upload not found"))?;
+
+    Ok(Json(fields.shape(&upload)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/uploads/{id}/download",
+    tag = "Uploads",
+    params(
+        ("id" = Uuid, Path, description = "Upload identifier"),
+        ("Range" = Option<String>, Header, description = "Single byte range, for example `bytes=1024-` to resume a download"),
+        ("If-Range" = Option<String>, Header, description = "ETag or date: the range is only served if the file has not changed"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a cached copy")
+    ),
+    responses(
+        (status = 200, description = "File content", content_type = "application/octet-stream"),
+        (status = 206, description = "Requested byte range of the file", content_type = "application/octet-stream"),
+        (status = 304, description = "The cached copy is still current"),
+        (status = 404, description = "Unknown upload", body = ProblemDetails),
+        (status = 416, description = "The range starts beyond the end of the file", body = ProblemDetails)
+    ),
+    summary = "Download a file",
+    description = "Streams the stored content with its original content type, as an attachment. Supports conditional requests (`ETag` is the SHA-256 of the content) and single byte ranges, so interrupted downloads can be resumed."
+)]
+pub async fn download(
+    State(db): State<DatabaseManager>,
+    State(storage): State<Arc<dyn Storage>>,
+    ApiPath(id): ApiPath<UploadId>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let upload = uploads::get_upload(db.get_pool(), id)
+        .await?
+        .ok_or_else(|| AppError::coded(ErrorCode::UploadNotFound, "upload not found"))?;
+
+    let file = StoredFile {
+        key: &upload.storage_key,
+        size: upload.size_bytes as u64,
+        content_type: &upload.content_type,
+        filename: &upload.filename,
+        etag: &upload.sha256,
+        last_modified: upload.created_at,
+    };
+    serve_file(storage.as_ref(), &headers, file).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/uploads/{id}",
+    tag = "Uploads",
+    params(("id" = Uuid, Path, description = "Upload identifier")),
+    responses(
+        (status = 204, description = "File deleted"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown upload", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Delete a file"
+)]
+pub async fn delete_upload(
+    State(db): State<DatabaseManager>,
+    State(storage): State<Arc<dyn Storage>>,
+    State(cache): State<Arc<ResponseCache>>,
+    ApiPath(id): ApiPath<UploadId>,
+) -> Result<StatusCode, AppError> {
+    let upload = uploads::delete_upload(db.get_pool(), id)
+        .await?
+        .ok_or_else(|| AppError::coded(ErrorCode::UploadNotFound, "upload not found"))?;
+
+    discard(storage.as_ref(), &upload.storage_key).await;
+    cache.invalidate_prefix(&format!("/api/uploads/{}", id));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Écrit le contenu de la partie courante dans le stockage.
+///
+/// # Returns
+///
+/// * `Result<(i64, String), AppError>` - Taille écrite et empreinte SHA-256
+async fn write_part(
+    multipart: &mut MultipartStream,
+    storage: &dyn Storage,
+    key: &str,
+    content_type: &str,
+    settings: &UploadsConfig,
+) -> Result<(i64, String), AppError> {
+    let storage_error = |e: std::io::Error| AppError::Internal(format!("failed to write upload {}: {}", key, e));
+
+    let mut writer = storage.writer(key).await.map_err(storage_error)?;
+    let mut hasher = Sha256::new();
+    let mut size: u64 = 0;
+
+    while let Some(chunk) = multipart.chunk().await? {
+        if size == 0 && !matches_signature(content_type, &chunk) {
+            return Err(AppError::coded(ErrorCode::UnsupportedFileType, format!(
+                "file content does not match its declared type ({})",
+                content_type
+            )));
+        }
+
+        size += chunk.len() as u64;
+        if size > settings.max_size_bytes {
+            return Err(AppError::coded(ErrorCode::FileTooLarge, format!(
+                "file exceeds the maximum size of {} bytes",
+                settings.max_size_bytes
+            )));
+        }
+
+        hasher.update(&chunk);
+        writer.write_all(&chunk).await.map_err(storage_error)?;
+    }
+
+    writer.shutdown().await.map_err(storage_error)?;
+    Ok((size as i64, hex::encode(hasher.finalize())))
+}
+
+/// Supprime un contenu orphelin, en journalisant un éventuel échec
+async fn discard(storage: &dyn Storage, key: &str) {
+    if let Err(e) = storage.delete(key).await {
+        warn!("Failed to delete stored upload {}: {}", key, e);
+    }
+}
+
+/// Vérifie la signature (« magic bytes ») des formats connus ; les autres sont acceptés.
+///
+/// Le premier morceau reçu fait au moins quelques octets en pratique ; un morceau
+/// trop court pour être vérifié est refusé pour les formats connus.
+fn matches_signature(content_type: &str, head: &[u8]) -> bool {
+    match content_type {
+        "image/png" => head.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "image/jpeg" => head.starts_with(&[0xFF, 0xD8, 0xFF]),
+        "image/gif" => head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a"),
+        "image/webp" => head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP",
+        "application/pdf" => head.starts_with(b"%PDF-"),
+        _ => true,
+    }
+}
+
+/// Nettoie le nom de fichier fourni par le client : ni chemin, ni guillemets,
+/// ni caractères de contrôle (il est renvoyé dans `Content-Disposition`)
+fn sanitize_filename(filename: &str) -> String {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+    name.chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(255)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
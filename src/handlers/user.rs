@@ -0,0 +1,277 @@
+//! # User Handlers Module
+//!
+//! Ce module contient les handlers de la ressource d'exemple `user`. Il montre
+//! l'assemblage complet d'une ressource : extracteurs validés (`ValidatedJson`,
+//! `ApiPath`, `Pagination`, `QueryOptions`, `Fields`), accès base via le service,
+//! erreurs `AppError` avec codes dédiés et enveloppe `ApiResponse` avec liens.
+
+use axum::{extract::State, http::StatusCode};
+use serde_json::Value;
+
+use crate::{
+    db::DatabaseManager,
+    extractors::{
+        fields::Fields,
+        links::LinkBuilder,
+        pagination::{Pagination, PaginationParams},
+        path::ApiPath,
+        query::QueryOptions,
+        validated::ValidatedJson,
+    },
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, BatchResponse, Links, PaginatedResponse},
+    },
+    models::{
+        batch::{BatchRequest, BatchUpdate},
+        error::{ErrorCode, ProblemDetails},
+        user::{NewUser, UpdateUser, User, UserFields, UserId},
+    },
+    services::{batch, user},
+};
+use validator::Validate;
+
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "Users",
+    params(
+        PaginationParams,
+        ("filter[email]" = Option<String>, Query, description = "Only users with these emails (comma-separated)"),
+        ("sort" = Option<String>, Query, description = "Sort fields: name, email, created_at (prefix with - for descending)"),
+        ("search" = Option<String>, Query, description = "Case-insensitive search in name and email"),
+        ("fields" = Option<String>, Query, description = "Only these attributes of each user (comma-separated)")
+    ),
+    responses(
+        (status = 200, description = "Users, most recent first", body = PaginatedResponse<User>),
+        (status = 400, description = "Invalid pagination, filter, sort or fields parameters", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 403, description = "Admin API is disabled")
+    ),
+    security(("admin_token" = [])),
+    summary = "List users"
+)]
+pub async fn list_users(
+    State(db): State<DatabaseManager>,
+    pagination: Pagination,
+    options: QueryOptions<UserFields>,
+    fields: Fields<UserFields>,
+) -> Result<PaginatedResponse<Value>, AppError> {
+    let pool = db.get_pool();
+    let users = user::list_users(pool, &options, pagination.limit(), pagination.offset()).await?;
+    let total = user::count_users(pool, &options).await?;
+
+    Ok(pagination.response(fields.shape_all(&users)?, total))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    tag = "Users",
+    params(
+        ("id" = i64, Path, description = "User identifier"),
+        ("fields" = Option<String>, Query, description = "Only these attributes (comma-separated)")
+    ),
+    responses(
+        (status = 200, description = "User", body = ApiResponse<User>),
+        (status = 400, description = "Malformed identifier or unknown field", body = ProblemDetails),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown user", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Get a user"
+)]
+pub async fn get_user(
+    State(db): State<DatabaseManager>,
+    links: LinkBuilder,
+    ApiPath(id): ApiPath<UserId>,
+    fields: Fields<UserFields>,
+) -> Result<ApiResponse<Value>, AppError> {
+    let user = user::get_user(db.get_pool(), id).await?.ok_or_else(not_found)?;
+
+    Ok(ApiResponse::ok(fields.shape(&user)?).links(user_links(&links, id)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "Users",
+    request_body = NewUser,
+    responses(
+        (status = 201, description = "User created", body = ApiResponse<User>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 409, description = "Email already in use", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Create a user"
+)]
+pub async fn create_user(
+    State(db): State<DatabaseManager>,
+    links: LinkBuilder,
+    ValidatedJson(new_user): ValidatedJson<NewUser>,
+) -> Result<ApiResponse<User>, AppError> {
+    let created = user::create_user(db.get_pool(), &new_user).await.map_err(email_conflict)?;
+    let links = user_links(&links, created.id);
+
+    Ok(ApiResponse::created(created).links(links))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/users/{id}",
+    tag = "Users",
+    params(("id" = i64, Path, description = "User identifier")),
+    request_body = UpdateUser,
+    responses(
+        (status = 200, description = "User updated", body = ApiResponse<User>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown user", body = ProblemDetails),
+        (status = 409, description = "Email already in use", body = ProblemDetails),
+        (status = 422, description = "Field validation failed", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Update a user",
+    description = "Only the fields present in the body are changed."
+)]
+pub async fn update_user(
+    State(db): State<DatabaseManager>,
+    links: LinkBuilder,
+    ApiPath(id): ApiPath<UserId>,
+    ValidatedJson(changes): ValidatedJson<UpdateUser>,
+) -> Result<ApiResponse<User>, AppError> {
+    let updated = user::update_user(db.get_pool(), id, &changes)
+        .await
+        .map_err(email_conflict)?
+        .ok_or_else(not_found)?;
+
+    Ok(ApiResponse::ok(updated).links(user_links(&links, id)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "Users",
+    params(("id" = i64, Path, description = "User identifier")),
+    responses(
+        (status = 204, description = "User deleted"),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 404, description = "Unknown user", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Delete a user"
+)]
+pub async fn delete_user(State(db): State<DatabaseManager>, ApiPath(id): ApiPath<UserId>) -> Result<StatusCode, AppError> {
+    if !user::delete_user(db.get_pool(), id).await? {
+        return Err(not_found());
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users:batchCreate",
+    tag = "Users",
+    request_body = BatchRequest<NewUser>,
+    responses(
+        (status = 200, description = "All users created", body = BatchResponse<User>),
+        (status = 207, description = "Some users were rejected, see each result", body = BatchResponse<User>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 422, description = "Empty or oversized batch", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Create several users",
+    description = "Each item is validated and written on its own: a rejected item does not prevent the others from being created."
+)]
+pub async fn batch_create_users(
+    State(db): State<DatabaseManager>,
+    ValidatedJson(batch): ValidatedJson<BatchRequest<NewUser>>,
+) -> Result<BatchResponse<User>, AppError> {
+    let results = batch::write_each(db.get_pool(), batch.items, async |conn, new_user: NewUser| {
+        new_user.validate()?;
+        user::create_user(conn, &new_user).await.map_err(email_conflict)
+    })
+    .await?;
+
+    Ok(BatchResponse::new(results, StatusCode::CREATED))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users:batchUpdate",
+    tag = "Users",
+    request_body = BatchRequest<BatchUpdate<i64, UpdateUser>>,
+    responses(
+        (status = 200, description = "All users updated", body = BatchResponse<User>),
+        (status = 207, description = "Some updates were rejected, see each result", body = BatchResponse<User>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 422, description = "Empty or oversized batch", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Update several users",
+    description = "Each item carries the user `id` and the fields to change, as in `PATCH /api/users/{id}`."
+)]
+pub async fn batch_update_users(
+    State(db): State<DatabaseManager>,
+    ValidatedJson(batch): ValidatedJson<BatchRequest<BatchUpdate<UserId, UpdateUser>>>,
+) -> Result<BatchResponse<User>, AppError> {
+    let results = batch::write_each(db.get_pool(), batch.items, async |conn, item: BatchUpdate<UserId, UpdateUser>| {
+        item.changes.validate()?;
+        user::update_user(conn, item.id, &item.changes)
+            .await
+            .map_err(email_conflict)?
+            .ok_or_else(not_found)
+    })
+    .await?;
+
+    Ok(BatchResponse::new(results, StatusCode::OK))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users:batchDelete",
+    tag = "Users",
+    request_body = BatchRequest<i64>,
+    responses(
+        (status = 200, description = "All users deleted; each result holds the deleted identifier", body = BatchResponse<i64>),
+        (status = 207, description = "Some users were not found, see each result", body = BatchResponse<i64>),
+        (status = 401, description = "Invalid or missing admin token"),
+        (status = 422, description = "Empty or oversized batch", body = ProblemDetails)
+    ),
+    security(("admin_token" = [])),
+    summary = "Delete several users"
+)]
+pub async fn batch_delete_users(
+    State(db): State<DatabaseManager>,
+    ValidatedJson(batch): ValidatedJson<BatchRequest<UserId>>,
+) -> Result<BatchResponse<UserId>, AppError> {
+    let results = batch::write_each(db.get_pool(), batch.items, async |conn, id: UserId| {
+        if user::delete_user(conn, id).await? {
+            Ok(id)
+        } else {
+            Err(not_found())
+        }
+    })
+    .await?;
+
+    Ok(BatchResponse::new(results, StatusCode::OK))
+}
+
+fn user_links(links: &LinkBuilder, id: UserId) -> Links {
+    links.to(&format!("/api/users/{}", id)).related("collection", links.url("/api/users"))
+}
+
+fn not_found() -> AppError {
+    AppError::coded(ErrorCode::UserNotFound, "user not found")
+}
+
+/// Remplace la violation d'unicité générique par un code propre à l'email
+fn email_conflict(error: sqlx::Error) -> AppError {
+    match AppError::from(error) {
+        AppError::Coded { code: ErrorCode::ResourceAlreadyExists, .. } => {
+            AppError::coded(ErrorCode::EmailAlreadyUsed, "email already in use")
+        }
+        other => other,
+    }
+}
@@ -0,0 +1,281 @@
+//! # Webhooks Handlers Module
+//!
+//! Ce module reçoit les webhooks entrants des fournisseurs externes.
+//! Chaque requête passe par les étapes suivantes :
+//! 1. Extraction du corps brut (la signature porte sur les octets exacts)
+//! 2. Vérification de la signature HMAC-SHA256 avec le secret du fournisseur
+//! 3. Protection contre le rejeu (timestamp + identifiant de livraison)
+//! 4. Désérialisation et dispatch vers le handler typé du fournisseur
+
+use async_trait::async_trait;
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::{
+    config::{WebhookProviderConfig, WebhooksConfig},
+    models::{error::ErrorCode, webhooks::ExampleEvent},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+type BoxedHandler = Arc<dyn Fn(Bytes) -> BoxFuture<'static, Result<(), WebhookError>> + Send + Sync>;
+
+/// Erreurs possibles lors de la réception d'un webhook
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("unknown webhook provider: {0}")]
+    UnknownProvider(String),
+    #[error("missing header: {0}")]
+    MissingHeader(String),
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("timestamp outside of the tolerated window")]
+    StaleTimestamp,
+    #[error("webhook delivery already processed")]
+    Replayed,
+    #[error("invalid payload: {0}")]
+    InvalidPayload(String),
+    #[error("webhook handler failed: {0}")]
+    Handler(String),
+}
+
+impl WebhookError {
+    /// Code d'erreur stable transmis au fournisseur
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            WebhookError::UnknownProvider(_) => ErrorCode::UnknownWebhookProvider,
+            WebhookError::MissingHeader(_) => ErrorCode::MissingWebhookHeader,
+            WebhookError::InvalidSignature => ErrorCode::InvalidWebhookSignature,
+            WebhookError::StaleTimestamp => ErrorCode::StaleWebhookTimestamp,
+            WebhookError::Replayed => ErrorCode::WebhookReplayed,
+            WebhookError::InvalidPayload(_) => ErrorCode::InvalidWebhookPayload,
+            WebhookError::Handler(_) => ErrorCode::WebhookHandlerFailed,
+        }
+    }
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let code = self.code();
+        let status = StatusCode::from_u16(code.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        (status, Json(serde_json::json!({ "code": code, "error": self.to_string() }))).into_response()
+    }
+}
+
+/// Handler typé pour les événements d'un fournisseur.
+///
+/// Le payload est désérialisé depuis le corps JSON avant l'appel à `handle`.
+#[async_trait]
+pub trait WebhookHandler: Send + Sync + 'static {
+    type Payload: DeserializeOwned + Send;
+
+    async fn handle(&self, payload: Self::Payload) -> Result<(), WebhookError>;
+}
+
+/// Mémoire des livraisons déjà traitées, conservées le temps de la fenêtre de tolérance.
+#[derive(Default)]
+pub struct ReplayGuard {
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl ReplayGuard {
+    /// Enregistre une clé de livraison.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `false` si la clé a déjà été vue et n'a pas encore expiré
+    pub fn register(&self, key: String, ttl_seconds: u64) -> bool {
+        let now = Utc::now().timestamp();
+        let mut seen = self.seen.lock().unwrap();
+
+        // Purge des entrées expirées
+        seen.retain(|_, expires_at| *expires_at > now);
+
+        if seen.contains_key(&key) {
+            return false;
+        }
+        seen.insert(key, now + ttl_seconds as i64);
+        true
+    }
+
+    /// Oublie une clé, pour qu'une livraison en échec puisse être renvoyée.
+    pub fn forget(&self, key: &str) {
+        self.seen.lock().unwrap().remove(key);
+    }
+}
+
+/// Registre associant chaque fournisseur à sa configuration et à son handler typé.
+#[derive(Default)]
+pub struct WebhookRegistry {
+    providers: HashMap<String, WebhookProviderConfig>,
+    handlers: HashMap<String, BoxedHandler>,
+    replay_guard: ReplayGuard,
+}
+
+impl WebhookRegistry {
+    /// Crée un registre vide à partir de la configuration des fournisseurs.
+    pub fn new(config: &WebhooksConfig) -> Self {
+        Self {
+            providers: config.providers.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Associe un handler typé à un fournisseur.
+    pub fn register<H: WebhookHandler>(mut self, provider: &str, handler: H) -> Self {
+        let handler = Arc::new(handler);
+        let boxed: BoxedHandler = Arc::new(move |body: Bytes| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                let payload = serde_json::from_slice::<H::Payload>(&body)
+                    .map_err(|e| WebhookError::InvalidPayload(e.to_string()))?;
+                handler.handle(payload).await
+            })
+        });
+        self.handlers.insert(provider.to_string(), boxed);
+        self
+    }
+
+    /// Vérifie puis dispatch un webhook reçu.
+    pub async fn process(&self, provider: &str, headers: &HeaderMap, body: Bytes) -> Result<(), WebhookError> {
+        let (config, handler) = match (self.providers.get(provider), self.handlers.get(provider)) {
+            (Some(config), Some(handler)) => (config, handler),
+            (Some(_), None) => {
+                warn!("Webhook provider {} is configured but has no registered handler", provider);
+                return Err(WebhookError::UnknownProvider(provider.to_string()));
+            }
+            _ => return Err(WebhookError::UnknownProvider(provider.to_string())),
+        };
+
+        let signature = header_value(headers, &config.signature_header)?;
+        let signature = signature
+            .strip_prefix(config.signature_prefix.as_str())
+            .unwrap_or(signature);
+
+        // Le timestamp, s'il est configuré, fait partie du contenu signé
+        let signed_payload = match &config.timestamp_header {
+            Some(timestamp_header) => {
+                let timestamp = header_value(headers, timestamp_header)?;
+                let timestamp: i64 = timestamp.parse().map_err(|_| WebhookError::StaleTimestamp)?;
+                if (Utc::now().timestamp() - timestamp).unsigned_abs() > config.tolerance_seconds {
+                    return Err(WebhookError::StaleTimestamp);
+                }
+                [format!("{}.", timestamp).as_bytes(), &body].concat()
+            }
+            None => body.to_vec(),
+        };
+
+        if !verify_signature(config.secret.as_bytes(), &signed_payload, signature) {
+            return Err(WebhookError::InvalidSignature);
+        }
+
+        // Une même livraison (ou à défaut une même signature) n'est acceptée qu'une fois
+        let delivery_key = match &config.delivery_id_header {
+            Some(id_header) => header_value(headers, id_header)?.to_string(),
+            None => signature.to_string(),
+        };
+        let delivery_key = format!("{}:{}", provider, delivery_key);
+        if !self.replay_guard.register(delivery_key.clone(), config.tolerance_seconds) {
+            return Err(WebhookError::Replayed);
+        }
+
+        let result = handler(body).await;
+        if result.is_err() {
+            self.replay_guard.forget(&delivery_key);
+        }
+        result
+    }
+}
+
+/// Calcule la signature HMAC-SHA256 (hexadécimale) d'un payload.
+pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Vérifie une signature HMAC-SHA256 hexadécimale en temps constant.
+pub fn verify_signature(secret: &[u8], payload: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, WebhookError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| WebhookError::MissingHeader(name.to_string()))
+}
+
+/// Construit le registre des handlers de webhooks.
+///
+/// Ajoutez ici vos propres handlers :
+/// `.register("github", GithubHandler)`
+pub fn registry(config: &WebhooksConfig) -> WebhookRegistry {
+    WebhookRegistry::new(config)
+        // Handler d'exemple, vous pouvez le supprimer
+        .register("example", ExampleHandler)
+}
+
+/// Handler d'exemple qui se contente de journaliser l'événement reçu
+pub struct ExampleHandler;
+
+#[async_trait]
+impl WebhookHandler for ExampleHandler {
+    type Payload = ExampleEvent;
+
+    async fn handle(&self, payload: ExampleEvent) -> Result<(), WebhookError> {
+        info!("Received example webhook event: {}", payload.event);
+        Ok(())
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{provider}",
+    tag = "Webhooks",
+    params(("provider" = String, Path, description = "Configured webhook provider name")),
+    request_body(content = String, content_type = "application/json", description = "Raw signed payload"),
+    responses(
+        (status = 204, description = "Webhook verified and processed"),
+        (status = 400, description = "Payload could not be parsed"),
+        (status = 401, description = "Missing or invalid signature, or stale timestamp"),
+        (status = 404, description = "Unknown provider"),
+        (status = 409, description = "Delivery already processed")
+    ),
+    summary = "Receive an inbound webhook",
+    description = "Verifies the HMAC-SHA256 signature of the raw body with the provider secret, rejects replayed deliveries and dispatches the payload to the provider's typed handler."
+)]
+pub async fn receive(
+    State(registry): State<Arc<WebhookRegistry>>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, WebhookError> {
+    registry.process(&provider, &headers, body).await.map_err(|e| {
+        warn!("Rejected webhook from {}: {}", provider, e);
+        e
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
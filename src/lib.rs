@@ -1,6 +1,20 @@
+pub mod allocator;
+pub mod cache;
 pub mod config;
 pub mod db;
+pub mod extractors;
 pub mod routes; 
 pub mod handlers;
+pub mod sanitize;
 pub mod models;
-pub mod fixtures;
\ No newline at end of file
+pub mod reporting;
+pub mod fixtures;
+pub mod logs;
+pub mod middleware;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod services;
+pub mod state;
+pub mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
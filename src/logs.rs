@@ -0,0 +1,145 @@
+//! # Logs Module
+//!
+//! Ce module conserve les derniers logs de l'instance en mémoire, pour un diagnostic
+//! rapide sans accès au serveur :
+//! - la couche `tracing` de `layer` copie chaque événement retenu par le filtre des
+//!   logs (`[logging] level` ou `RUST_LOG`) dans un tampon circulaire de
+//!   `[logging] buffer_size` entrées
+//! - `GET /api/help/logs` (admin) restitue les plus récents, filtrés par niveau minimal
+//! - la page de status affiche le nombre d'avertissements et d'erreurs du tampon
+//!
+//! Le tampon est partagé par la couche, installée une fois par processus, et l'état
+//! de l'application (`AppState::logs`).
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+};
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::{
+    middleware::trace::TraceContext,
+    models::help::{LogCounts, LogLevel, LogRecord},
+};
+
+/// Tampon du processus, créé par le premier appel à `buffer`
+static BUFFER: OnceLock<Arc<LogBuffer>> = OnceLock::new();
+
+/// Tampon des logs du processus ; `capacity` n'est prise en compte qu'au premier appel.
+pub fn buffer(capacity: usize) -> Arc<LogBuffer> {
+    BUFFER.get_or_init(|| Arc::new(LogBuffer::new(capacity))).clone()
+}
+
+/// Couche `tracing` alimentant le tampon du processus
+pub fn layer(capacity: usize) -> LogBufferLayer {
+    LogBufferLayer::new(buffer(capacity))
+}
+
+/// Derniers logs, du plus ancien au plus récent.
+#[derive(Debug)]
+pub struct LogBuffer {
+    records: Mutex<VecDeque<LogRecord>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Tampon conservant les `capacity` derniers logs
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { records: Mutex::new(VecDeque::with_capacity(capacity)), capacity }
+    }
+
+    /// Ajoute un log, en retirant le plus ancien si le tampon est plein
+    pub fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Au plus `limit` logs de niveau `min_level` ou plus grave, les plus récents d'abord
+    pub fn recent(&self, min_level: LogLevel, limit: usize) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap();
+        records.iter().rev().filter(|record| record.level >= min_level).take(limit).cloned().collect()
+    }
+
+    /// Avertissements et erreurs présents dans le tampon
+    pub fn counts(&self) -> LogCounts {
+        let records = self.records.lock().unwrap();
+        LogCounts {
+            warnings: records.iter().filter(|record| record.level == LogLevel::Warn).count(),
+            errors: records.iter().filter(|record| record.level == LogLevel::Error).count(),
+        }
+    }
+}
+
+/// Couche `tracing` copiant chaque événement dans un `LogBuffer`.
+pub struct LogBufferLayer {
+    buffer: Arc<LogBuffer>,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: Arc<LogBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = FieldsVisitor::default();
+        event.record(&mut fields);
+        let message = match fields.0.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(value) => value.to_string(),
+            None => String::new(),
+        };
+
+        let metadata = event.metadata();
+        self.buffer.push(LogRecord {
+            timestamp: Utc::now(),
+            level: LogLevel::from(*metadata.level()),
+            target: metadata.target().to_string(),
+            message,
+            trace_id: TraceContext::current().map(|context| context.trace_id_hex()),
+            fields: fields.0,
+        });
+    }
+}
+
+/// Champs d'un événement, convertis en JSON
+#[derive(Default)]
+struct FieldsVisitor(Map<String, Value>);
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::from(format!("{:?}", value)));
+    }
+}
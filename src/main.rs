@@ -10,21 +10,26 @@
 //! - Configuration CORS
 //! - Gestion des erreurs
 
-mod config;
-mod db;
-mod handlers;
-mod models;
-mod routes;
-mod fixtures;
-mod middleware;
-
 use axum::Router;
 use std::net::SocketAddr;
-use tower_http::cors::CorsLayer;
-use tracing::info;
-use fixtures::run_fixtures;
-use crate::middleware::logging::setup_middleware;
-use crate::models::status::start_background_metrics_task;
+use tracing::{info, warn};
+use template_axum_sqlx_api::{
+    config, db, reporting, routes, telemetry,
+    state::AppState,
+    fixtures::{
+        export::{export_command, export_fixtures},
+        fixtures_decision,
+        import::{import_command, import_dumps},
+        load::{generate_load, load_command},
+        reset_requested, run_fixtures, FixtureManager, FixturesDecision,
+    },
+    middleware::{cors::cors_layer, logging::setup_middleware},
+    models::status::start_background_metrics_task,
+    services::{
+        events::record_deploy_if_changed, jobs::start_job_worker, metrics::save_snapshot,
+        scheduler::scheduler, webhooks::start_webhook_dispatcher,
+    },
+};
 
 /// Point d'entrée principal de l'application.
 ///
@@ -39,36 +44,160 @@ async fn main() {
     // Load configuration from config.toml
     let config = config::Config::load(include_str!("../assets/config.toml")).expect("Failed to load configuration");
 
+    // Rapport d'erreurs (panics, 5xx, tâches de fond) ; la garde envoie les derniers événements à l'arrêt
+    let _sentry = reporting::init(&config);
+
     // Initialize database
     let mut db = db::DatabaseManager::new();
     db.connect(&config)
         .await
         .expect("Failed to connect to database");
 
-    // Run fixtures
-    run_fixtures(db.get_pool(), true).await.expect("Failed to run fixtures");
+    // Enregistrer le déploiement dans la timeline si la version a changé
+    if let Err(e) = record_deploy_if_changed(db.get_pool()).await {
+        warn!("Failed to record deploy event: {}", e);
+    }
+
+    // Enregistrer des tables en fichiers de fixtures (`fixtures export <tables>`) puis quitter,
+    // avant toute modification de la base
+    match export_command(&config, std::env::args().skip(1)) {
+        Ok(Some(spec)) => {
+            export_fixtures(db.get_pool(), &spec).await.expect("Failed to export fixtures");
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => panic!("Refusing to export fixtures: {}", e),
+    }
+
+    // Vider la base sur demande (`--reset-database`), avant les fixtures
+    match reset_requested(&config, std::env::args().skip(1)) {
+        Ok(true) => {
+            FixtureManager::new(db.get_pool().clone()).reset().await.expect("Failed to reset database");
+        }
+        Ok(false) => {}
+        Err(e) => panic!("Refusing to reset database: {}", e),
+    }
+
+    // Charger les fixtures uniquement sur demande (`--fixtures` ou `[fixtures] enabled`) ;
+    // une simulation (`--dry-run`) affiche les lignes puis quitte sans démarrer le serveur
+    match fixtures_decision(&config, std::env::args().skip(1)) {
+        Ok(FixturesDecision::Run { clean, profile, dry_run }) => {
+            run_fixtures(db.get_pool(), clean, profile, dry_run, &config.fixtures)
+                .await
+                .expect("Failed to run fixtures");
+            if dry_run {
+                return;
+            }
+        }
+        Ok(FixturesDecision::Skip) => info!("Fixtures not requested, skipping"),
+        Err(e) => panic!("Refusing to run fixtures: {}", e),
+    }
+
+    // Importer des exports anonymisés (`import <fichiers>`) puis quitter sans démarrer le serveur
+    match import_command(&config, std::env::args().skip(1)) {
+        Ok(Some(paths)) => {
+            import_dumps(db.get_pool(), &paths, &config.import, config.fixtures.locale).await.expect("Failed to import dumps");
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => panic!("Refusing to import dumps: {}", e),
+    }
+
+    // Générer les données de charge (`generate-load`) puis quitter sans démarrer le serveur
+    match load_command(&config, std::env::args().skip(1)) {
+        Ok(Some(spec)) => {
+            let inserted = generate_load(db.get_pool(), spec, config.fixtures.seed, config.fixtures.locale)
+                .await
+                .expect("Failed to generate load data");
+            info!("Inserted {} users, {} posts, {} comments", inserted.users, inserted.posts, inserted.comments);
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => panic!("Refusing to generate load data: {}", e),
+    }
+
+    let addr: SocketAddr = config
+        .server_address()
+        .parse()
+        .expect("Invalid server address");
+    let state = AppState::new(db.clone(), config.clone());
 
     // Démarrer la tâche de calcul des métriques en arrière-plan
-    start_background_metrics_task(db.clone(), config.clone()).await;
-    info!("Background metrics task started (5-minute intervals)");
+    start_background_metrics_task(
+        &state.tasks,
+        db.clone(),
+        config.clone(),
+        state.metrics.clone(),
+        state.status_codes.clone(),
+        state.query_insights.clone(),
+        state.cluster.clone(),
+        state.caches.clone(),
+    )
+    .await;
+    info!("Background metrics task started ({}s intervals)", config.monitoring.interval_seconds);
+
+    // Démarrer le worker d'envoi des webhooks sortants
+    start_webhook_dispatcher(&state.tasks, db, config).await;
+
+    // Démarrer le worker des tâches asynchrones
+    start_job_worker(&state.tasks, state.db.clone(), state.config.jobs.clone(), state.jobs.clone()).await;
+
+    // Planifier les tâches périodiques (`[scheduler.tasks]`)
+    match scheduler().start(&state, &state.config.scheduler) {
+        Ok(count) => info!("Scheduler started ({} periodic tasks)", count),
+        Err(e) => panic!("Invalid scheduler configuration: {}", e),
+    }
+
+    // Initialisation terminée : la sonde de startup réussit
+    state.readiness.mark_started();
 
     // Build our application with a route
     let app = Router::new()
-        .merge(routes::create_router(db))
-        .layer(CorsLayer::permissive());
+        .merge(routes::create_router(state.clone()))
+        .layer(cors_layer(&state));
 
     let app = setup_middleware(app);
 
     // Run it
-    let addr: SocketAddr = config
-        .server_address()
-        .parse()
-        .expect("Invalid server address");
     info!("listening on {}", addr);
     axum::serve(
         tokio::net::TcpListener::bind(addr).await.unwrap(),
         app.into_make_service(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await
     .unwrap();
+
+    // Conserver les dernières métriques pour le prochain démarrage
+    if state.config.monitoring.snapshot_max_age_hours > 0
+        && let Some(metrics) = state.metrics.latest()
+        && let Err(e) = save_snapshot(state.db.get_pool(), &metrics).await
+    {
+        warn!("Failed to save metrics snapshot: {}", e);
+    }
+
+    // Envoyer les derniers spans avant de quitter
+    tokio::task::spawn_blocking(telemetry::shutdown).await.ok();
+}
+
+/// Attend Ctrl+C ou SIGTERM, puis laisse les requêtes en cours se terminer
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        if let Ok(mut signal) = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            signal.recv().await;
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutdown signal received, finishing in-flight requests");
 }
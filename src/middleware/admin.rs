@@ -0,0 +1,65 @@
+//! # Admin Guard Middleware
+//!
+//! Ce middleware protège les routes d'administration : la requête doit porter
+//! `Authorization: Bearer <token>` avec le jeton défini dans `[admin] token`, ou
+//! avec la clé d'API (`tk_...`) d'un utilisateur de rôle `admin` (voir `services::auth`).
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::{
+    config::Config,
+    db::DatabaseManager,
+    handlers::error::AppError,
+    models::{auth::Role, error::ErrorCode},
+    services::auth::{find_principal, API_KEY_PREFIX},
+};
+
+pub async fn require_admin(
+    State(config): State<Arc<Config>>,
+    State(db): State<DatabaseManager>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(expected) = config.admin.token.as_deref() else {
+        return AppError::coded(ErrorCode::AdminApiDisabled, "Admin API is disabled").into_response();
+    };
+
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => next.run(req).await,
+        Some(key) if key.starts_with(API_KEY_PREFIX) && is_admin_key(&db, key).await => next.run(req).await,
+        _ => {
+            warn!("Rejected admin request to {}", req.uri().path());
+            AppError::coded(ErrorCode::InvalidAdminToken, "Invalid or missing admin token").into_response()
+        }
+    }
+}
+
+/// Indique si la clé d'API appartient à un administrateur ; une erreur de base refuse l'accès
+async fn is_admin_key(db: &DatabaseManager, key: &str) -> bool {
+    match find_principal(db.get_pool(), key).await {
+        Ok(principal) => principal.is_some_and(|principal| principal.has_role(Role::Admin)),
+        Err(e) => {
+            warn!("Failed to look up API key: {}", e);
+            false
+        }
+    }
+}
+
+/// Comparaison en temps constant pour ne pas divulguer le jeton par timing
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
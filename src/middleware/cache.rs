@@ -0,0 +1,270 @@
+//! # Response Cache Middleware
+//!
+//! Ce middleware met en cache les réponses des routes GET publiques listées dans
+//! `[cache.routes]`, chacune avec sa propre durée de vie :
+//! - la clé combine le chemin, la query string et les en-têtes `[cache] vary`
+//! - les réponses servies portent `Cache-Control: public, max-age=...`, `Age` et `X-Cache`
+//! - les requêtes avec `Authorization` ou `Cookie` ne sont jamais servies depuis le cache
+//! - seules les réponses 200 sans `Set-Cookie` ni `Cache-Control: no-store/private` sont conservées
+//!
+//! Les handlers d'écriture invalident les entrées concernées :
+//!
+//! ```ignore
+//! cache.invalidate_path(&format!("/api/uploads/{}", id));
+//! ```
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{MatchedPath, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+use crate::config::CacheConfig;
+
+/// Taille maximale d'une réponse mise en cache
+const MAX_CACHED_BODY_BYTES: usize = 1024 * 1024;
+
+/// En-tête indiquant si la réponse vient du cache (`HIT`) ou non (`MISS`)
+pub const X_CACHE: &str = "x-cache";
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    /// Chemin (sans query string), utilisé pour l'invalidation
+    path: String,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: Instant,
+    ttl: Duration,
+}
+
+impl CachedResponse {
+    fn is_fresh(&self) -> bool {
+        self.stored_at.elapsed() < self.ttl
+    }
+}
+
+/// Cache mémoire des réponses, partagé par toutes les routes.
+#[derive(Debug)]
+pub struct ResponseCache {
+    max_entries: usize,
+    vary: Vec<HeaderName>,
+    routes: HashMap<String, Duration>,
+    entries: RwLock<HashMap<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        let vary = config
+            .vary
+            .iter()
+            .filter_map(|name| match HeaderName::try_from(name.as_str()) {
+                Ok(name) => Some(name),
+                Err(_) => {
+                    warn!("Ignoring invalid [cache] vary header: {}", name);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            max_entries: config.max_entries,
+            vary,
+            routes: config
+                .routes
+                .iter()
+                .map(|(route, ttl)| (route.clone(), Duration::from_secs(*ttl)))
+                .collect(),
+            entries: RwLock::default(),
+        }
+    }
+
+    /// Durée de vie configurée pour un modèle de route, s'il est mis en cache
+    pub fn ttl_for(&self, route: &str) -> Option<Duration> {
+        self.routes.get(route).copied().filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Nombre de réponses en cache (y compris expirées non encore purgées)
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    /// Indique si le cache est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Supprime toutes les variantes (query strings, en-têtes) d'un chemin
+    pub fn invalidate_path(&self, path: &str) {
+        self.entries.write().unwrap().retain(|_, entry| entry.path != path);
+    }
+
+    /// Supprime les réponses dont le chemin commence par `prefix`
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        self.entries.write().unwrap().retain(|_, entry| !entry.path.starts_with(prefix));
+    }
+
+    /// Vide le cache
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+
+    /// Écrit puis relit une entrée témoin sous `key`, supprimée ensuite (auto-test).
+    ///
+    /// Retourne `None` si le cache est désactivé (`[cache] max_entries = 0`).
+    pub fn round_trip(&self, key: &str) -> Option<bool> {
+        if self.max_entries == 0 {
+            return None;
+        }
+        let body = Bytes::from(key.to_string());
+        self.insert(key.to_string(), CachedResponse {
+            path: key.to_string(),
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: body.clone(),
+            stored_at: Instant::now(),
+            ttl: Duration::from_secs(60),
+        });
+        let found = self.get(key).is_some_and(|entry| entry.body == body);
+        self.entries.write().unwrap().remove(key);
+        Some(found)
+    }
+
+    fn key(&self, req: &Request<Body>) -> String {
+        let mut key = req
+            .uri()
+            .path_and_query()
+            .map(|pq| pq.as_str().to_string())
+            .unwrap_or_else(|| req.uri().path().to_string());
+        for name in &self.vary {
+            let value = req.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or_default();
+            key.push('\n');
+            key.push_str(name.as_str());
+            key.push(':');
+            key.push_str(value);
+        }
+        key
+    }
+
+    /// Reconstruit la réponse avec les en-têtes de cache à jour
+    fn to_response(&self, entry: &CachedResponse, status: &'static str) -> Response {
+        let mut response = Response::new(Body::from(entry.body.clone()));
+        *response.status_mut() = entry.status;
+        *response.headers_mut() = entry.headers.clone();
+
+        let age = entry.stored_at.elapsed();
+        let remaining = entry.ttl.as_secs().saturating_sub(age.as_secs());
+        let headers = response.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&format!("public, max-age={}", remaining)) {
+            headers.insert(header::CACHE_CONTROL, value);
+        }
+        headers.insert(header::AGE, HeaderValue::from(age.as_secs()));
+        headers.insert(HeaderName::from_static(X_CACHE), HeaderValue::from_static(status));
+        if !self.vary.is_empty() {
+            let vary = self.vary.iter().map(HeaderName::as_str).collect::<Vec<_>>().join(", ");
+            if let Ok(value) = HeaderValue::from_str(&vary) {
+                headers.insert(header::VARY, value);
+            }
+        }
+        response
+    }
+
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.read().unwrap().get(key).filter(|entry| entry.is_fresh()).cloned()
+    }
+
+    fn insert(&self, key: String, entry: CachedResponse) {
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.is_fresh());
+        }
+        // Toujours plein : la réponse la plus ancienne laisse sa place
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => {
+                    entries.remove(&oldest);
+                }
+                None => return,
+            }
+        }
+        entries.insert(key, entry);
+    }
+}
+
+pub async fn cache_responses(State(cache): State<Arc<ResponseCache>>, req: Request<Body>, next: Next) -> Response {
+    let ttl = req
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|route| cache.ttl_for(route.as_str()));
+    let personalized = req.headers().contains_key(header::AUTHORIZATION) || req.headers().contains_key(header::COOKIE);
+    // Une requête de plage attend une réponse partielle, jamais la réponse complète en cache
+    let partial = req.headers().contains_key(header::RANGE);
+    let ttl = match ttl {
+        Some(ttl) if req.method() == Method::GET && !personalized && !partial => ttl,
+        _ => return next.run(req).await,
+    };
+
+    let key = cache.key(&req);
+    // `Cache-Control: no-cache` du client force un nouveau calcul, qui remplace l'entrée
+    let revalidate = req
+        .headers()
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("no-cache"));
+
+    if let Some(entry) = cache.get(&key).filter(|_| !revalidate) {
+        debug!("Served cached response for {}", entry.path);
+        return cache.to_response(&entry, "HIT");
+    }
+
+    let path = req.uri().path().to_string();
+    let response = next.run(req).await;
+    if !is_cacheable(&response) {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_CACHED_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to buffer response for {}: {}", path, e);
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::empty())
+                .unwrap_or_default();
+        }
+    };
+
+    let entry = CachedResponse {
+        path,
+        status: parts.status,
+        headers: parts.headers,
+        body,
+        stored_at: Instant::now(),
+        ttl,
+    };
+    let response = cache.to_response(&entry, "MISS");
+    cache.insert(key, entry);
+    response
+}
+
+fn is_cacheable(response: &Response) -> bool {
+    let headers = response.headers();
+    let private = headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("no-store") || value.contains("private"));
+    response.status() == StatusCode::OK && !private && !headers.contains_key(header::SET_COOKIE)
+}
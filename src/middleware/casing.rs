@@ -0,0 +1,134 @@
+//! # JSON Casing Middleware
+//!
+//! Ce middleware applique la convention de nommage des champs JSON choisie dans
+//! `[api] json_case`, sans annoter chaque structure :
+//! - `snake_case` (par défaut) : les corps passent sans transformation
+//! - `camelCase` : les clés des corps JSON reçus sont converties en `snake_case`
+//!   avant les extracteurs, et celles des réponses JSON en `camelCase` à l'envoi
+//!
+//! Seules les clés d'objets sont converties, à toute profondeur ; les valeurs ne sont
+//! jamais modifiées. Les paramètres de query string (`per_page`, `filter[...]`) et la
+//! spécification OpenAPI restent en `snake_case`. Les clés de données libres (par exemple
+//! `details` d'un événement) sont converties comme les autres.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::State,
+    http::{header, HeaderMap, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::{
+    config::{Config, JsonCase},
+    handlers::error::AppError,
+};
+
+/// Taille maximale d'un corps JSON reçu réécrit par le middleware (comme `axum::Json`)
+const MAX_REQUEST_BYTES: usize = 2 * 1024 * 1024;
+
+pub async fn json_casing(State(config): State<Arc<Config>>, req: Request<Body>, next: Next) -> Response {
+    if config.api.json_case == JsonCase::Snake {
+        return next.run(req).await;
+    }
+
+    let req = if is_json(req.headers()) {
+        let (parts, body) = req.into_parts();
+        let bytes = match to_bytes(body, MAX_REQUEST_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(_) => return AppError::PayloadTooLarge("request body is too large".to_string()).into_response(),
+        };
+        // Un corps mal formé est transmis tel quel : l'extracteur produit l'erreur habituelle
+        let bytes = match serde_json::from_slice::<Value>(&bytes) {
+            Ok(value) => serde_json::to_vec(&rename_keys(value, &camel_to_snake)).map_or(bytes, Into::into),
+            Err(_) => bytes,
+        };
+        Request::from_parts(parts, Body::from(bytes))
+    } else {
+        req
+    };
+
+    let response = next.run(req).await;
+    if !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to buffer JSON response for casing: {}", e);
+            return AppError::Internal("failed to read response body".to_string()).into_response();
+        }
+    };
+    let bytes = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(value) => serde_json::to_vec(&rename_keys(value, &snake_to_camel)).map_or(bytes, Into::into),
+        Err(_) => bytes,
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// `application/json` ou `application/*+json` (par exemple `application/problem+json`)
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(str::trim)
+        .is_some_and(|mime| mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json")))
+}
+
+/// Renomme récursivement les clés des objets
+pub fn rename_keys(value: Value, rename: &dyn Fn(&str) -> String) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (rename(&key), rename_keys(value, rename)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| rename_keys(item, rename)).collect()),
+        other => other,
+    }
+}
+
+/// `total_pages` → `totalPages` ; un `_` initial est conservé (`_id` reste `_id`)
+pub fn snake_to_camel(key: &str) -> String {
+    let prefix_len = key.len() - key.trim_start_matches('_').len();
+    let (prefix, rest) = key.split_at(prefix_len);
+
+    let mut output = String::with_capacity(key.len());
+    output.push_str(prefix);
+    let mut upper = false;
+    for c in rest.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            output.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// `totalPages` → `total_pages`
+pub fn camel_to_snake(key: &str) -> String {
+    let mut output = String::with_capacity(key.len() + 4);
+    for (i, c) in key.char_indices() {
+        if c.is_uppercase() {
+            if i > 0 && !output.ends_with('_') {
+                output.push('_');
+            }
+            output.extend(c.to_lowercase());
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
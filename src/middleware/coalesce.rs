@@ -0,0 +1,154 @@
+//! # Request Coalescing Middleware
+//!
+//! Ce middleware regroupe les requêtes GET identiques (même chemin et même query
+//! string) reçues en même temps : une seule exécute le handler, les autres
+//! attendent son résultat et reçoivent une copie de la réponse.
+//!
+//! Avec `[api] coalesce_cache_ttl_ms > 0`, les réponses réussies sont en plus
+//! conservées pendant ce délai et servies directement aux requêtes suivantes.
+//!
+//! La clé ignore les en-têtes : les requêtes portant un `Authorization` ou un
+//! `Cookie` ne sont jamais regroupées. À réserver aux routes publiques coûteuses :
+//!
+//! ```ignore
+//! .route("/report", get(report).route_layer(from_fn_with_state(state.clone(), coalesce)))
+//! ```
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::State,
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+
+/// Taille maximale d'une réponse mise en commun
+const MAX_COALESCED_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Réponse mise en mémoire pour être partagée entre plusieurs requêtes
+#[derive(Debug, Clone)]
+struct SharedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    completed_at: Instant,
+}
+
+impl SharedResponse {
+    fn to_response(&self) -> Response {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+}
+
+type Slot = Arc<OnceCell<SharedResponse>>;
+
+/// Registre des calculs en cours (et des réponses en cache), indexé par chemin + query.
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    ttl: Duration,
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+impl Coalescer {
+    /// Crée un registre ; `ttl` nul désactive le cache (seul le regroupement s'applique).
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slots: Mutex::default(),
+        }
+    }
+
+    /// Nombre de clés actuellement suivies (en cours ou en cache)
+    pub fn len(&self) -> usize {
+        self.slots.lock().unwrap().len()
+    }
+
+    /// Indique si aucune clé n'est suivie
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Récupère l'emplacement d'une clé, en remplaçant une réponse expirée
+    fn slot(&self, key: &str) -> Slot {
+        let mut slots = self.slots.lock().unwrap();
+
+        // Purge des réponses expirées
+        slots.retain(|_, slot| slot.get().is_none_or(|shared| shared.completed_at.elapsed() < self.ttl));
+
+        slots
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone()
+    }
+
+    /// Libère la clé si elle pointe toujours vers cet emplacement
+    fn release(&self, key: &str, slot: &Slot) {
+        let mut slots = self.slots.lock().unwrap();
+        if slots.get(key).is_some_and(|current| Arc::ptr_eq(current, slot)) {
+            slots.remove(key);
+        }
+    }
+}
+
+pub async fn coalesce(State(coalescer): State<Arc<Coalescer>>, req: Request<Body>, next: Next) -> Response {
+    let personalized = req.headers().contains_key(header::AUTHORIZATION) || req.headers().contains_key(header::COOKIE);
+    if req.method() != Method::GET || personalized {
+        return next.run(req).await;
+    }
+
+    let key = req
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let slot = coalescer.slot(&key);
+
+    // Si la requête qui calcule est annulée, une des requêtes en attente prend le relais
+    let mut leader = false;
+    let shared = slot
+        .get_or_try_init(|| async {
+            leader = true;
+            let response = next.run(req).await;
+            let (parts, body) = response.into_parts();
+            let body = to_bytes(body, MAX_COALESCED_BODY_BYTES).await.map_err(|e| {
+                warn!("Failed to buffer coalesced response for {}: {}", key, e);
+            })?;
+            Ok::<_, ()>(SharedResponse {
+                status: parts.status,
+                headers: parts.headers,
+                body,
+                completed_at: Instant::now(),
+            })
+        })
+        .await
+        .cloned();
+
+    let shared = match shared {
+        Ok(shared) => shared,
+        Err(()) => {
+            coalescer.release(&key, &slot);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if !leader {
+        debug!("Served coalesced response for {}", key);
+    }
+
+    // Seules les réponses réussies sont conservées au-delà du calcul en cours
+    if leader && (coalescer.ttl.is_zero() || !shared.status.is_success()) {
+        coalescer.release(&key, &slot);
+    }
+
+    shared.to_response()
+}
@@ -0,0 +1,42 @@
+//! # CORS Middleware
+//!
+//! Ce module construit la couche CORS à partir de la section `[cors]` et des
+//! origines dynamiques (voir `services::cors`).
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::state::AppState;
+
+/// Construit la couche CORS de l'application.
+pub fn cors_layer(state: &AppState) -> CorsLayer {
+    let cors = &state.config.cors;
+    let methods = cors
+        .allowed_methods
+        .iter()
+        .filter_map(|method| method.parse::<Method>().ok())
+        .collect::<Vec<_>>();
+    let headers = cors
+        .allowed_headers
+        .iter()
+        .filter_map(|header| header.parse::<HeaderName>().ok())
+        .collect::<Vec<_>>();
+
+    let db = state.db.clone();
+    let origins = state.cors_origins.clone();
+    let allow_origin = AllowOrigin::async_predicate(move |origin: HeaderValue, _| {
+        let db = db.clone();
+        let origins = origins.clone();
+        async move {
+            match origin.to_str() {
+                Ok(origin) => origins.is_allowed(db.get_pool(), origin).await,
+                Err(_) => false,
+            }
+        }
+    });
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+}
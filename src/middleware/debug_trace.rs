@@ -0,0 +1,135 @@
+//! # Debug Trace Middleware
+//!
+//! Ce middleware permet de diagnostiquer une requête en production sans relever le
+//! niveau de log de toute l'instance :
+//! - une requête portant `X-Debug-Trace: <jeton admin>` est journalisée au niveau
+//!   `trace`, quel que soit `[logging] level` (logs, tampon `/api/help/logs` et
+//!   spans exportés)
+//! - la réponse porte son identifiant de trace dans `X-Trace-Id`, pour retrouver
+//!   ses logs et ses spans
+//! - un jeton absent ou invalide est ignoré : la requête est traitée normalement
+//!
+//! Le niveau est relevé par `DebugTraceFilter`, qui enveloppe le filtre des couches
+//! de logs lorsque `[logging] debug_trace = true`. Seuls les logs émis par la tâche
+//! de la requête sont concernés, pas ceux des tâches qu'elle lance.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use tracing::{
+    level_filters::LevelFilter,
+    span::{Attributes, Id, Record},
+    subscriber::Interest,
+    warn, Metadata, Subscriber,
+};
+use tracing_subscriber::layer::{Context, Filter};
+
+use crate::{config::Config, middleware::{admin::constant_time_eq, trace::TraceContext}};
+
+/// En-tête activant les logs `trace` pour la requête, avec le jeton admin pour valeur
+pub const X_DEBUG_TRACE: &str = "x-debug-trace";
+/// En-tête de réponse portant l'identifiant de trace d'une requête en debug
+pub const X_TRACE_ID: &str = "x-trace-id";
+
+tokio::task_local! {
+    static ACTIVE: ();
+}
+
+/// Marqueur des réponses aux requêtes en debug (toujours journalisées, voir `logging`)
+#[derive(Debug, Clone, Copy)]
+pub struct DebugTraced;
+
+/// Indique si la tâche courante traite une requête en debug
+pub fn is_active() -> bool {
+    ACTIVE.try_with(|_| ()).is_ok()
+}
+
+pub async fn debug_trace(State(config): State<Arc<Config>>, req: Request<Body>, next: Next) -> Response {
+    let Some(provided) = req.headers().get(X_DEBUG_TRACE) else {
+        return next.run(req).await;
+    };
+    let authorized = config.logging.debug_trace
+        && config
+            .admin
+            .token
+            .as_deref()
+            .is_some_and(|expected| constant_time_eq(provided.as_bytes(), expected.as_bytes()));
+    if !authorized {
+        warn!("Ignored {} header on {}", X_DEBUG_TRACE, req.uri().path());
+        return next.run(req).await;
+    }
+
+    let context = req.extensions().get::<TraceContext>().copied();
+    let mut response = ACTIVE.scope((), next.run(req)).await;
+    if let Some(context) = context
+        && let Ok(value) = HeaderValue::from_str(&context.trace_id_hex())
+    {
+        response.headers_mut().insert(X_TRACE_ID, value);
+    }
+    response.extensions_mut().insert(DebugTraced);
+    response
+}
+
+/// Filtre d'une couche de logs, laissant passer tous les niveaux pendant une requête en debug.
+///
+/// Désactivé, il se comporte exactement comme le filtre enveloppé. Activé, les callsites
+/// exclus par ce filtre sont réévalués à chaque appel au lieu d'être écartés une fois
+/// pour toutes, ce qui a un léger coût.
+pub struct DebugTraceFilter<F> {
+    inner: F,
+    enabled: bool,
+}
+
+impl<F> DebugTraceFilter<F> {
+    pub fn new(inner: F, enabled: bool) -> Self {
+        Self { inner, enabled }
+    }
+}
+
+impl<S: Subscriber, F: Filter<S>> Filter<S> for DebugTraceFilter<F> {
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        (self.enabled && is_active()) || self.inner.enabled(meta, cx)
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        let interest = self.inner.callsite_enabled(meta);
+        if self.enabled && interest.is_never() {
+            Interest::sometimes()
+        } else {
+            interest
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        if self.enabled {
+            Some(LevelFilter::TRACE)
+        } else {
+            self.inner.max_level_hint()
+        }
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(id, values, ctx);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+}
@@ -0,0 +1,208 @@
+//! # Latency Middleware
+//!
+//! Ce middleware mesure la latence de chaque requête et l'agrège par route
+//! (méthode et modèle de chemin, par exemple `GET /api/users/{id}`) dans un
+//! histogramme à seuils fixes, partagé via `AppState::latency` :
+//! - la page de status et `GET /api/status/endpoints` en présentent le détail
+//! - les requêtes sans route (404 du routeur) ne sont pas comptées
+//!
+//! Les requêtes les plus lentes de la dernière fenêtre (`[status] slow_endpoints_top`
+//! et `slow_endpoints_window_seconds`) sont conservées individuellement, pour
+//! `GET /api/help/slow-endpoints` et le panneau de la page de status.
+//!
+//! La latence mesurée va jusqu'à l'envoi des en-têtes de la réponse : la durée
+//! d'un corps en flux (export, SSE) n'est pas incluse.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::models::status::{EndpointLatency, LatencyBucket, SlowRequest};
+
+/// Taille du classement des requêtes les plus lentes par défaut
+pub const DEFAULT_SLOW_ENDPOINTS_TOP: usize = 10;
+
+/// Fenêtre glissante du classement par défaut
+pub const DEFAULT_SLOW_ENDPOINTS_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Bornes supérieures des seuils de l'histogramme (millisecondes) ; un dernier seuil reçoit le reste
+pub const BUCKET_BOUNDS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: [u64; BUCKET_BOUNDS_MS.len() + 1],
+    count: u64,
+    sum_ms: f64,
+    max_ms: f64,
+    client_errors: u64,
+    server_errors: u64,
+}
+
+impl Histogram {
+    fn record(&mut self, status: u16, elapsed: Duration) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| ms <= *bound as f64)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+        match status {
+            400..=499 => self.client_errors += 1,
+            500..=599 => self.server_errors += 1,
+            _ => {}
+        }
+    }
+
+    /// Borne supérieure du seuil contenant le quantile, au plus la latence maximale observée
+    fn quantile(&self, q: f64) -> f64 {
+        let rank = ((self.count as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return BUCKET_BOUNDS_MS
+                    .get(bucket)
+                    .map_or(self.max_ms, |bound| (*bound as f64).min(self.max_ms));
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// Requêtes les plus lentes de la fenêtre glissante, de la plus lente à la plus rapide
+#[derive(Debug)]
+struct Leaderboard {
+    top: usize,
+    window: Duration,
+    requests: Vec<(Instant, SlowRequest)>,
+}
+
+impl Leaderboard {
+    /// Retire les requêtes sorties de la fenêtre
+    fn prune(&mut self, now: Instant) {
+        let window = self.window;
+        self.requests.retain(|(at, _)| now.duration_since(*at) <= window);
+    }
+
+    fn record(&mut self, now: Instant, request: SlowRequest) {
+        self.prune(now);
+        if self.requests.len() >= self.top {
+            match self.requests.last() {
+                Some((_, fastest)) if fastest.duration_ms < request.duration_ms => {
+                    self.requests.pop();
+                }
+                _ => return,
+            }
+        }
+        let position = self.requests.partition_point(|(_, slower)| slower.duration_ms >= request.duration_ms);
+        self.requests.insert(position, (now, request));
+    }
+}
+
+/// Histogrammes de latence par route et classement des requêtes les plus lentes.
+#[derive(Debug)]
+pub struct LatencyStats {
+    routes: RwLock<HashMap<(Method, String), Histogram>>,
+    slowest: Mutex<Leaderboard>,
+}
+
+impl Default for LatencyStats {
+    fn default() -> Self {
+        Self::with_leaderboard(DEFAULT_SLOW_ENDPOINTS_TOP, DEFAULT_SLOW_ENDPOINTS_WINDOW)
+    }
+}
+
+impl LatencyStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Conserve les `top` requêtes les plus lentes des dernières `window`
+    pub fn with_leaderboard(top: usize, window: Duration) -> Self {
+        Self {
+            routes: RwLock::new(HashMap::new()),
+            slowest: Mutex::new(Leaderboard { top, window, requests: Vec::with_capacity(top) }),
+        }
+    }
+
+    /// Enregistre une requête terminée
+    pub fn record(&self, method: Method, route: &str, status: u16, elapsed: Duration) {
+        let request = SlowRequest {
+            method: method.to_string(),
+            route: route.to_string(),
+            status,
+            duration_ms: elapsed.as_secs_f64() * 1000.0,
+            completed_at: Utc::now(),
+        };
+        self.slowest.lock().unwrap().record(Instant::now(), request);
+
+        let mut routes = self.routes.write().unwrap();
+        routes.entry((method, route.to_string())).or_default().record(status, elapsed);
+    }
+
+    /// Requêtes les plus lentes de la fenêtre, de la plus lente à la plus rapide
+    pub fn slowest(&self) -> Vec<SlowRequest> {
+        let mut slowest = self.slowest.lock().unwrap();
+        slowest.prune(Instant::now());
+        slowest.requests.iter().map(|(_, request)| request.clone()).collect()
+    }
+
+    /// Détail par route, les plus sollicitées d'abord
+    pub fn snapshot(&self) -> Vec<EndpointLatency> {
+        let routes = self.routes.read().unwrap();
+        let mut endpoints = routes
+            .iter()
+            .map(|((method, route), histogram)| EndpointLatency {
+                method: method.to_string(),
+                route: route.clone(),
+                count: histogram.count,
+                client_errors: histogram.client_errors,
+                server_errors: histogram.server_errors,
+                avg_ms: histogram.sum_ms / histogram.count.max(1) as f64,
+                p50_ms: histogram.quantile(0.50),
+                p95_ms: histogram.quantile(0.95),
+                p99_ms: histogram.quantile(0.99),
+                max_ms: histogram.max_ms,
+                buckets: histogram
+                    .buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(bucket, count)| LatencyBucket { le_ms: BUCKET_BOUNDS_MS.get(bucket).copied(), count: *count })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        endpoints.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.route.cmp(&b.route)));
+        endpoints
+    }
+
+    /// Remet les compteurs et le classement à zéro
+    pub fn clear(&self) {
+        self.routes.write().unwrap().clear();
+        self.slowest.lock().unwrap().requests.clear();
+    }
+}
+
+pub async fn record_latency(State(stats): State<Arc<LatencyStats>>, req: Request<Body>, next: Next) -> Response {
+    let Some(route) = req.extensions().get::<MatchedPath>().map(|route| route.as_str().to_string()) else {
+        return next.run(req).await;
+    };
+    let method = req.method().clone();
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+    stats.record(method, &route, response.status().as_u16(), start.elapsed());
+    response
+}
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::{sync::OnceLock, time::Instant};
 use tower_http::trace::{TraceLayer, DefaultMakeSpan, DefaultOnResponse};
 use axum::{
     http::Request,
@@ -7,6 +7,9 @@ use axum::{
     body::Body,
 };
 use tracing::{info, Level};
+use uuid::Uuid;
+
+use super::debug_trace::DebugTraced;
 
 pub fn logging_layer() -> TraceLayer<tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>> {
     TraceLayer::new_for_http()
@@ -14,6 +17,20 @@ pub fn logging_layer() -> TraceLayer<tower_http::classify::SharedClassifier<towe
         .on_response(DefaultOnResponse::new().level(Level::INFO))
 }
 
+/// Part des logs de fin de requête conservés (`[logging] request_sample_ratio`)
+static REQUEST_SAMPLE_RATIO: OnceLock<f64> = OnceLock::new();
+
+/// Fixe la part des logs de fin de requête conservés ; seul le premier appel est pris en compte
+pub fn set_request_sample_ratio(ratio: f64) {
+    let _ = REQUEST_SAMPLE_RATIO.set(ratio.clamp(0.0, 1.0));
+}
+
+/// Tire au sort le log d'une requête selon `[logging] request_sample_ratio`
+fn sample_request_log() -> bool {
+    let ratio = REQUEST_SAMPLE_RATIO.get().copied().unwrap_or(1.0);
+    ratio >= 1.0 || (Uuid::new_v4().as_u128() as u64 as f64 / u64::MAX as f64) < ratio
+}
+
 pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
     let path = req.uri().path().to_owned();
     let method = req.method().clone();
@@ -22,6 +39,14 @@ pub async fn track_execution_time(req: Request<Body>, next: Next) -> Response {
     let response = next.run(req).await;
     let duration = start.elapsed();
     
+    // Les erreurs serveur et les requêtes en debug échappent à l'échantillonnage
+    let keep = response.status().is_server_error()
+        || response.extensions().get::<DebugTraced>().is_some()
+        || sample_request_log();
+    if !keep {
+        return response;
+    }
+    
     info!(
         "Request {} {} completed in {:.2?} with status {}",
         method,
@@ -38,5 +63,10 @@ pub fn setup_middleware<S>(app: axum::Router<S>) -> axum::Router<S>
 where
     S: Clone + Send + Sync + 'static,
 {
-    app.layer(middleware::from_fn(track_execution_time))
+    // Les paniques deviennent des 500 avant d'atteindre le log de fin de requête
+    app.layer(super::panic::catch_panic_layer())
+        .layer(middleware::from_fn(track_execution_time))
+        .layer(middleware::from_fn(super::request_id::request_id))
+        // Contexte de trace en couche externe : les logs ci-dessus portent trace_id et span_id
+        .layer(middleware::from_fn(super::trace::propagate_trace))
 } 
\ No newline at end of file
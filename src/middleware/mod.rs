@@ -1 +1,12 @@
-pub mod logging;
\ No newline at end of file
+pub mod admin;
+pub mod cache;
+pub mod casing;
+pub mod coalesce;
+pub mod debug_trace;
+pub mod cors;
+pub mod latency;
+pub mod logging;
+pub mod panic;
+pub mod request_id;
+pub mod status_codes;
+pub mod trace;
@@ -0,0 +1,37 @@
+//! # Panic Recovery Middleware
+//!
+//! Ce module convertit une panique dans un handler en réponse 500 structurée
+//! (enveloppe `ApiResponse`) au lieu de laisser la connexion se fermer sans réponse.
+//! La panique est journalisée avec l'identifiant de requête pour faciliter le diagnostic ;
+//! son message n'est jamais renvoyé au client.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::any::Any;
+use tower_http::catch_panic::CatchPanicLayer;
+use tracing::error;
+
+use super::request_id::RequestId;
+use crate::handlers::response::ApiResponse;
+
+type PanicHandler = fn(Box<dyn Any + Send + 'static>) -> Response;
+
+/// Couche de récupération des paniques, à placer à l'intérieur du middleware `request_id`
+pub fn catch_panic_layer() -> CatchPanicLayer<PanicHandler> {
+    CatchPanicLayer::custom(panic_response as PanicHandler)
+}
+
+fn panic_response(panic: Box<dyn Any + Send + 'static>) -> Response {
+    let message = panic
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| panic.downcast_ref::<&str>().copied())
+        .unwrap_or("unknown panic payload");
+    let request_id = RequestId::current().map(|id| id.0).unwrap_or_else(|| "-".to_string());
+
+    error!("Handler panicked (request_id {}): {}", request_id, message);
+
+    ApiResponse::error(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+}
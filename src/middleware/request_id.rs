@@ -0,0 +1,95 @@
+//! # Request ID Middleware
+//!
+//! Ce middleware attribue un identifiant à chaque requête :
+//! - un en-tête `x-request-id` valide reçu du client ou du proxy est repris tel quel
+//! - sinon un UUID v4 est généré
+//! - l'identifiant est disponible en extension (`RequestId`) et via `RequestId::current()`
+//! - il est renvoyé dans l'en-tête `x-request-id` de la réponse
+//!
+//! `RequestContext::current()` donne en plus l'instant de réception de la requête,
+//! d'où les réponses tirent leur durée de traitement (voir `ResponseMeta`).
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::{fmt, time::Instant};
+use uuid::Uuid;
+
+/// Nom de l'en-tête portant l'identifiant de requête
+pub const X_REQUEST_ID: &str = "x-request-id";
+
+/// Longueur maximale acceptée pour un identifiant fourni par le client
+const MAX_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static CURRENT: RequestContext;
+}
+
+/// Contexte de la requête en cours, porté par la tâche qui la traite
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub id: RequestId,
+    /// Instant de réception de la requête par le middleware
+    pub started_at: Instant,
+}
+
+impl RequestContext {
+    /// Contexte de la requête traitée par la tâche en cours, s'il y en a une
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| context.clone()).ok()
+    }
+}
+
+/// Identifiant de la requête en cours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// Reprend un identifiant reçu s'il est raisonnable (ASCII visible, longueur bornée)
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_LENGTH
+            && value.bytes().all(|b| b.is_ascii_graphic());
+        valid.then(|| Self(value.to_string()))
+    }
+
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// Identifiant de la requête traitée par la tâche en cours, s'il y en a une
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| context.id.clone()).ok()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+pub async fn request_id(mut req: Request<Body>, next: Next) -> Response {
+    let started_at = Instant::now();
+    let id = req
+        .headers()
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RequestId::parse)
+        .unwrap_or_else(RequestId::generate);
+    req.extensions_mut().insert(id.clone());
+
+    let context = RequestContext { id: id.clone(), started_at };
+    let mut response = CURRENT.scope(context, next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {
+        response.headers_mut().insert(X_REQUEST_ID, value);
+    }
+    response
+}
@@ -0,0 +1,138 @@
+//! # Status Codes Middleware
+//!
+//! Ce middleware compte les réponses par classe de statut (2xx, 3xx, 4xx, 5xx),
+//! y compris celles des requêtes sans route, dans des compteurs partagés via
+//! `AppState::status_codes`. La tâche des métriques en déduit les réponses de chaque
+//! intervalle : le taux de 5xx est affiché sur la page de status et pénalise le
+//! score de santé.
+//!
+//! Le débit (réponses par seconde) est suivi sur une fenêtre glissante de
+//! `THROUGHPUT_WINDOW_SECONDS` secondes, avec le pic atteint depuis le démarrage ; la
+//! page de status et `GET /api/status` l'affichent en direct.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use crate::models::status::{RequestCounts, Throughput};
+
+/// Durée de la fenêtre glissante du débit courant (secondes)
+pub const THROUGHPUT_WINDOW_SECONDS: u64 = 10;
+
+/// Réponses par seconde écoulée depuis le démarrage
+#[derive(Debug, Default)]
+struct ThroughputWindow {
+    /// (seconde, réponses) des `THROUGHPUT_WINDOW_SECONDS` dernières secondes
+    seconds: VecDeque<(u64, u64)>,
+    /// Réponses de la seconde la plus chargée
+    peak: u64,
+}
+
+/// Compteurs cumulés depuis le démarrage de l'instance.
+#[derive(Debug)]
+pub struct StatusCounters {
+    success: AtomicU64,
+    redirection: AtomicU64,
+    client_errors: AtomicU64,
+    server_errors: AtomicU64,
+    started: Instant,
+    throughput: Mutex<ThroughputWindow>,
+}
+
+impl Default for StatusCounters {
+    fn default() -> Self {
+        Self {
+            success: AtomicU64::new(0),
+            redirection: AtomicU64::new(0),
+            client_errors: AtomicU64::new(0),
+            server_errors: AtomicU64::new(0),
+            started: Instant::now(),
+            throughput: Mutex::new(ThroughputWindow::default()),
+        }
+    }
+}
+
+impl StatusCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compte une réponse ; les réponses 1xx ne sont pas comptées
+    pub fn record(&self, status: StatusCode) {
+        self.record_throughput();
+        let counter = match status.as_u16() {
+            200..=299 => &self.success,
+            300..=399 => &self.redirection,
+            400..=499 => &self.client_errors,
+            500..=599 => &self.server_errors,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Réponses comptées depuis le démarrage
+    pub fn snapshot(&self) -> RequestCounts {
+        RequestCounts {
+            success: self.success.load(Ordering::Relaxed),
+            redirection: self.redirection.load(Ordering::Relaxed),
+            client_errors: self.client_errors.load(Ordering::Relaxed),
+            server_errors: self.server_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Débit moyen sur la fenêtre glissante et pic depuis le démarrage
+    pub fn throughput(&self) -> Throughput {
+        let now = self.started.elapsed().as_secs();
+        let window = self.throughput.lock().unwrap();
+        let recent: u64 = window
+            .seconds
+            .iter()
+            .filter(|(second, _)| now - second < THROUGHPUT_WINDOW_SECONDS)
+            .map(|(_, count)| count)
+            .sum();
+
+        Throughput {
+            current_rps: recent as f64 / THROUGHPUT_WINDOW_SECONDS as f64,
+            peak_rps: window.peak,
+        }
+    }
+
+    fn record_throughput(&self) {
+        let now = self.started.elapsed().as_secs();
+        let mut window = self.throughput.lock().unwrap();
+
+        let count = match window.seconds.back_mut() {
+            Some((second, count)) if *second == now => {
+                *count += 1;
+                *count
+            }
+            _ => {
+                window.seconds.push_back((now, 1));
+                1
+            }
+        };
+        window.peak = window.peak.max(count);
+
+        while window.seconds.front().is_some_and(|(second, _)| now - second >= THROUGHPUT_WINDOW_SECONDS) {
+            window.seconds.pop_front();
+        }
+    }
+}
+
+pub async fn count_status_classes(State(counters): State<Arc<StatusCounters>>, req: Request<Body>, next: Next) -> Response {
+    let response = next.run(req).await;
+    counters.record(response.status());
+    response
+}
@@ -0,0 +1,167 @@
+//! # Trace Context Middleware
+//!
+//! Ce middleware propage le contexte de trace W3C (`traceparent`) :
+//! - le contexte reçu est repris (même `trace_id`), sinon une nouvelle trace est créée
+//! - chaque requête s'exécute dans un span `request` portant `trace_id` et `span_id`,
+//!   repris par tous les logs émis pendant son traitement
+//! - le contexte est renvoyé dans l'en-tête `traceparent` de la réponse
+//! - les appels sortants via `inject` portent un `traceparent` enfant du contexte courant
+//! - si l'export OpenTelemetry est actif, le span `request` est exporté avec ces identifiants
+//!
+//! Format : `00-<trace_id 32 hex>-<span_id 16 hex>-<flags 2 hex>`
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::future::Future;
+use tracing::{field, info_span, Instrument};
+use uuid::Uuid;
+
+use crate::telemetry;
+
+/// Nom de l'en-tête de propagation
+pub const TRACEPARENT: &str = "traceparent";
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// Contexte de trace W3C d'une opération
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: u128,
+    pub span_id: u64,
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Démarre une nouvelle trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_u128(),
+            span_id: random_span_id(),
+            sampled: true,
+        }
+    }
+
+    /// Crée un span enfant dans la même trace
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: random_span_id(),
+            ..*self
+        }
+    }
+
+    /// Analyse un en-tête `traceparent` (version `00`) ; `None` s'il est invalide
+    pub fn parse(header: &str) -> Option<Self> {
+        let mut parts = header.trim().split('-');
+        let (version, trace_id, span_id, flags) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || version != "00" {
+            return None;
+        }
+        if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+        let is_lower_hex = |s: &str| s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        if !is_lower_hex(trace_id) || !is_lower_hex(span_id) || !is_lower_hex(flags) {
+            return None;
+        }
+
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let span_id = u64::from_str_radix(span_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        // Identifiants entièrement nuls interdits par la spécification
+        if trace_id == 0 || span_id == 0 {
+            return None;
+        }
+
+        Some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 0x01 == 0x01,
+        })
+    }
+
+    /// Valeur de l'en-tête `traceparent`
+    pub fn to_header(&self) -> String {
+        format!("00-{}-{}-{:02x}", self.trace_id_hex(), self.span_id_hex(), self.sampled as u8)
+    }
+
+    pub fn trace_id_hex(&self) -> String {
+        format!("{:032x}", self.trace_id)
+    }
+
+    pub fn span_id_hex(&self) -> String {
+        format!("{:016x}", self.span_id)
+    }
+
+    /// Contexte de la tâche en cours, s'il y en a un
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| *context).ok()
+    }
+
+    /// Exécute `future` dans ce contexte (pour les tâches de fond notamment)
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        let span = info_span!("trace", trace_id = %self.trace_id_hex(), span_id = %self.span_id_hex());
+        CURRENT.scope(self, future.instrument(span)).await
+    }
+}
+
+/// Ajoute un `traceparent` enfant du contexte courant (ou d'une nouvelle trace) à un appel sortant.
+pub fn inject(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let context = TraceContext::current()
+        .map(|context| context.child())
+        .unwrap_or_else(TraceContext::new_root);
+    request.header(TRACEPARENT, context.to_header())
+}
+
+pub async fn propagate_trace(mut req: Request<Body>, next: Next) -> Response {
+    let parent = req
+        .headers()
+        .get(TRACEPARENT)
+        .and_then(|value| value.to_str().ok())
+        .and_then(TraceContext::parse);
+
+    let span = info_span!(
+        "request",
+        trace_id = field::Empty,
+        span_id = field::Empty,
+        method = %req.method(),
+        path = %req.uri().path(),
+        otel.kind = "server",
+    );
+    // Avec l'export OpenTelemetry, les identifiants sont ceux du span exporté
+    let context = telemetry::link(&span, parent)
+        .or_else(|| parent.map(|parent| parent.child()))
+        .unwrap_or_else(TraceContext::new_root);
+    span.record("trace_id", field::display(context.trace_id_hex()));
+    span.record("span_id", field::display(context.span_id_hex()));
+    req.extensions_mut().insert(context);
+
+    let mut response = CURRENT.scope(context, next.run(req).instrument(span)).await;
+    if let Ok(value) = HeaderValue::from_str(&context.to_header()) {
+        response.headers_mut().insert(TRACEPARENT, value);
+    }
+    response
+}
+
+fn random_u128() -> u128 {
+    loop {
+        let value = Uuid::new_v4().as_u128();
+        if value != 0 {
+            return value;
+        }
+    }
+}
+
+fn random_span_id() -> u64 {
+    loop {
+        let value = Uuid::new_v4().as_u128() as u64;
+        if value != 0 {
+            return value;
+        }
+    }
+}
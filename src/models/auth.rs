@@ -0,0 +1,45 @@
+//! # Auth Models Module
+//!
+//! Ce module contient les principaux authentifiés : un utilisateur de la table `users`
+//! avec son rôle (`user_credentials`), retrouvé à partir d'une clé d'API (`api_keys`).
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use super::user::UserId;
+
+/// Rôle d'un utilisateur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Accès aux routes d'administration, comme le jeton `[admin] token`
+    Admin,
+    User,
+}
+
+impl Role {
+    /// Représentation stockée en base
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::User => "user",
+        }
+    }
+}
+
+/// Utilisateur authentifié par une clé d'API valide
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Principal {
+    #[schema(value_type = i64)]
+    pub user_id: UserId,
+    pub email: String,
+    pub role: String,
+}
+
+impl Principal {
+    /// Indique si le principal a le rôle demandé
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role == role.as_str()
+    }
+}
@@ -0,0 +1,57 @@
+//! # Batch Models Module
+//!
+//! Ce module contient les corps des écritures groupées (`POST /api/users:batchCreate`...) :
+//! une liste d'éléments traités un par un, dont chacun réussit ou échoue séparément.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::sanitize::Sanitize;
+
+/// Nombre maximal d'éléments dans une requête groupée
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Corps d'une écriture groupée.
+///
+/// Seule la taille de la liste est validée ici : chaque élément est validé
+/// séparément pour que ses erreurs soient rapportées à son index.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchRequest<T> {
+    pub items: Vec<T>,
+}
+
+impl<T: Sanitize> Sanitize for BatchRequest<T> {
+    fn sanitize(&mut self) {
+        self.items.sanitize();
+    }
+}
+
+impl<T> Validate for BatchRequest<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if (1..=MAX_BATCH_SIZE).contains(&self.items.len()) {
+            return Ok(());
+        }
+
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "items",
+            ValidationError::new("length").with_message(format!("must contain between 1 and {} items", MAX_BATCH_SIZE).into()),
+        );
+        Err(errors)
+    }
+}
+
+/// Modification d'un élément dans une mise à jour groupée : l'identifiant et les champs à changer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchUpdate<I, T> {
+    pub id: I,
+    #[serde(flatten)]
+    pub changes: T,
+}
+
+impl<I, T: Sanitize> Sanitize for BatchUpdate<I, T> {
+    fn sanitize(&mut self) {
+        self.changes.sanitize();
+    }
+}
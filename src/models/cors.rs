@@ -0,0 +1,59 @@
+//! # CORS Models Module
+//!
+//! Ce module contient les structures des origines CORS enregistrées en base.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::id::Id;
+use crate::{
+    extractors::query::QuerySpec,
+    sanitize::{self, Sanitize},
+};
+
+/// Identifiant d'une origine CORS
+pub type CorsOriginId = Id<CorsOrigin>;
+
+/// Origine autorisée enregistrée dans la table `cors_origins`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CorsOrigin {
+    #[schema(value_type = i64)]
+    pub id: CorsOriginId,
+    /// Origine normalisée, par exemple `https://app.example.com`
+    pub origin: String,
+    /// Client propriétaire du domaine, le cas échéant
+    pub tenant: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Requête d'enregistrement d'une origine
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewCorsOrigin {
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub origin: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 128, message = "must be between 1 and 128 characters"))]
+    pub tenant: Option<String>,
+}
+
+impl Sanitize for NewCorsOrigin {
+    fn sanitize(&mut self) {
+        self.origin = sanitize::remove_control_chars(&self.origin).trim().to_string();
+        if let Some(tenant) = &mut self.tenant {
+            *tenant = sanitize::plain_text(tenant);
+        }
+    }
+}
+
+/// Champs utilisables dans `filter`, `sort` et `search` sur la liste des origines
+pub struct CorsOriginFields;
+
+impl QuerySpec for CorsOriginFields {
+    const FILTERS: &'static [(&'static str, &'static str)] = &[("tenant", "tenant")];
+    const SORTS: &'static [(&'static str, &'static str)] = &[("origin", "origin"), ("created_at", "created_at")];
+    const SEARCH: &'static [&'static str] = &["origin", "tenant"];
+    const DEFAULT_SORT: &'static str = "origin";
+}
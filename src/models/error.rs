@@ -0,0 +1,176 @@
+//! # Error Models Module
+//!
+//! Ce module contient le format des réponses d'erreur de l'API,
+//! conforme à la RFC 7807 (`application/problem+json`).
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::handlers::response::ResponseMeta;
+
+/// Corps d'une réponse d'erreur (RFC 7807)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    /// URI identifiant le type d'erreur, par exemple `urn:problem:not-found`
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Résumé lisible du type d'erreur
+    pub title: String,
+    /// Code de statut HTTP
+    pub status: u16,
+    /// Identifiant stable de l'erreur, à utiliser par les clients (voir `/api/help/errors`)
+    pub code: ErrorCode,
+    /// Explication propre à cette occurrence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Erreurs de validation par champ (membre d'extension)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    /// Identifiant de requête, horodatage et durée (membre d'extension), ajoutés à l'envoi
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ResponseMeta>,
+}
+
+/// Erreur de validation portant sur un champ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Déclare le catalogue des codes d'erreur : variante, code, statut HTTP usuel, description.
+macro_rules! error_codes {
+    ($($variant:ident = ($code:literal, $status:literal, $description:literal)),* $(,)?) => {
+        /// Code d'erreur stable, présent dans toutes les réponses d'erreur
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum ErrorCode {
+            $(
+                #[serde(rename = $code)]
+                $variant,
+            )*
+        }
+
+        // Schéma écrit à la main : le derive ne voit pas les `rename` générés par la macro
+        impl utoipa::PartialSchema for ErrorCode {
+            fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+                utoipa::openapi::ObjectBuilder::new()
+                    .schema_type(utoipa::openapi::schema::Type::String)
+                    .enum_values(Some([$($code),*]))
+                    .description(Some("Code d'erreur stable, présent dans toutes les réponses d'erreur"))
+                    .into()
+            }
+        }
+
+        impl ToSchema for ErrorCode {}
+
+        impl ErrorCode {
+            /// Tous les codes du catalogue
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant),*];
+
+            /// Code tel qu'il apparaît dans les réponses
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                }
+            }
+
+            /// Statut HTTP habituellement associé au code
+            pub fn status(self) -> u16 {
+                match self {
+                    $(ErrorCode::$variant => $status,)*
+                }
+            }
+
+            /// Description destinée aux équipes clientes
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $description,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Codes génériques
+    BadRequest = ("BAD_REQUEST", 400, "The request is malformed."),
+    ValidationFailed = ("VALIDATION_FAILED", 422, "One or more fields are invalid; see `errors` for details."),
+    NotFound = ("NOT_FOUND", 404, "The requested resource does not exist."),
+    Conflict = ("CONFLICT", 409, "The request conflicts with the current state of the resource."),
+    Unauthorized = ("UNAUTHORIZED", 401, "Authentication is missing or invalid."),
+    Forbidden = ("FORBIDDEN", 403, "The caller is not allowed to perform this action."),
+    PayloadTooLarge = ("PAYLOAD_TOO_LARGE", 413, "The request body is too large."),
+    UnsupportedMediaType = ("UNSUPPORTED_MEDIA_TYPE", 415, "The request content type is not supported."),
+    ServiceUnavailable = ("SERVICE_UNAVAILABLE", 503, "A dependency is temporarily unavailable; retry later."),
+    DatabaseError = ("DATABASE_ERROR", 500, "An unexpected database error occurred."),
+    InternalError = ("INTERNAL_ERROR", 500, "An unexpected error occurred."),
+    // Requêtes
+    InvalidJson = ("INVALID_JSON", 400, "The JSON body is malformed or does not match the expected shape (400 or 422)."),
+    UnknownFields = ("UNKNOWN_FIELDS", 400, "Strict mode: the JSON body contains fields that are not part of the schema."),
+    InvalidPagination = ("INVALID_PAGINATION", 400, "Pagination parameters are invalid or mixed (page/per_page vs limit/offset)."),
+    InvalidQuery = ("INVALID_QUERY", 400, "A filter, sort or search parameter is not supported by this endpoint."),
+    InvalidFields = ("INVALID_FIELDS", 400, "The `fields` parameter names an attribute this resource does not have."),
+    InvalidPathParameter = ("INVALID_PATH_PARAMETER", 400, "A path parameter, such as an identifier, is malformed."),
+    InvalidMultipart = ("INVALID_MULTIPART", 400, "The multipart/form-data body is malformed."),
+    ResourceAlreadyExists = ("RESOURCE_ALREADY_EXISTS", 409, "A resource with the same unique key already exists."),
+    ResourceReferenced = ("RESOURCE_REFERENCED", 409, "The resource references, or is referenced by, another resource."),
+    // Administration
+    AdminApiDisabled = ("ADMIN_API_DISABLED", 403, "The admin API is disabled because no admin token is configured."),
+    InvalidAdminToken = ("INVALID_ADMIN_TOKEN", 401, "The admin bearer token is missing or wrong."),
+    CorsOriginNotFound = ("CORS_ORIGIN_NOT_FOUND", 404, "No dynamic CORS origin has this identifier."),
+    InvalidCorsOrigin = ("INVALID_CORS_ORIGIN", 400, "The origin must be `http(s)://host[:port]` without a path."),
+    QueryInsightsUnavailable = ("QUERY_INSIGHTS_UNAVAILABLE", 409, "Query insights require `[status] query_insights = true` and the `pg_stat_statements` extension."),
+    HeapProfilingUnavailable = ("HEAP_PROFILING_UNAVAILABLE", 409, "Heap profiles require the `jemalloc` feature and `_RJEM_MALLOC_CONF=prof:true` at startup."),
+    // Maintenance
+    MaintenanceWindowNotFound = ("MAINTENANCE_WINDOW_NOT_FOUND", 404, "No maintenance window has this identifier."),
+    InvalidMaintenanceWindow = ("INVALID_MAINTENANCE_WINDOW", 400, "A maintenance window must end after it starts."),
+    // Utilisateurs
+    UserNotFound = ("USER_NOT_FOUND", 404, "No user has this identifier."),
+    EmailAlreadyUsed = ("EMAIL_ALREADY_USED", 409, "Another user already has this email address."),
+    // Posts et commentaires
+    PostNotFound = ("POST_NOT_FOUND", 404, "No post has this identifier."),
+    // Recherche
+    MissingSearchTerms = ("MISSING_SEARCH_TERMS", 400, "The search endpoint requires a non-empty `q` parameter."),
+    // Status et incidents
+    IncidentNotFound = ("INCIDENT_NOT_FOUND", 404, "No incident has this identifier."),
+    IncidentAlreadyResolved = ("INCIDENT_ALREADY_RESOLVED", 409, "The incident is already resolved; post an update to reopen it."),
+    MetricsNotReady = ("METRICS_NOT_READY", 503, "The background metrics task has not completed its first pass yet; retry later."),
+    // Tâches asynchrones
+    JobNotFound = ("JOB_NOT_FOUND", 404, "No job has this identifier."),
+    UnknownJobKind = ("UNKNOWN_JOB_KIND", 400, "No job handler is registered for this kind."),
+    InvalidJobPayload = ("INVALID_JOB_PAYLOAD", 422, "The payload does not match the job kind."),
+    JobNotRetriable = ("JOB_NOT_RETRIABLE", 409, "Only failed or dead-lettered jobs can be retried."),
+    // Fichiers
+    UploadNotFound = ("UPLOAD_NOT_FOUND", 404, "No uploaded file has this identifier."),
+    MissingFile = ("MISSING_FILE", 400, "The form has no `file` field, or the file has no name."),
+    FileTooLarge = ("FILE_TOO_LARGE", 413, "The file exceeds the configured maximum size."),
+    UnsupportedFileType = ("UNSUPPORTED_FILE_TYPE", 415, "The file type is not allowed, or the content does not match it."),
+    RangeNotSatisfiable = ("RANGE_NOT_SATISFIABLE", 416, "The requested byte range starts beyond the end of the file."),
+    // Webhooks entrants
+    UnknownWebhookProvider = ("UNKNOWN_WEBHOOK_PROVIDER", 404, "No webhook provider is configured under this name."),
+    MissingWebhookHeader = ("MISSING_WEBHOOK_HEADER", 401, "A required webhook header (signature, timestamp, delivery id) is missing."),
+    InvalidWebhookSignature = ("INVALID_WEBHOOK_SIGNATURE", 401, "The webhook signature does not match the payload."),
+    StaleWebhookTimestamp = ("STALE_WEBHOOK_TIMESTAMP", 401, "The webhook timestamp is outside the tolerated window."),
+    WebhookReplayed = ("WEBHOOK_REPLAYED", 409, "This webhook delivery was already processed."),
+    InvalidWebhookPayload = ("INVALID_WEBHOOK_PAYLOAD", 400, "The webhook payload could not be parsed."),
+    WebhookHandlerFailed = ("WEBHOOK_HANDLER_FAILED", 500, "The webhook handler failed; the delivery can be retried."),
+}
+
+/// Entrée du catalogue exposé par `/api/help/errors`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorCodeInfo {
+    pub code: ErrorCode,
+    /// Statut HTTP habituel
+    pub status: u16,
+    pub description: String,
+}
+
+impl From<ErrorCode> for ErrorCodeInfo {
+    fn from(code: ErrorCode) -> Self {
+        Self {
+            code,
+            status: code.status(),
+            description: code.description().to_string(),
+        }
+    }
+}
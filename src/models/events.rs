@@ -0,0 +1,92 @@
+//! # Events Models Module
+//!
+//! Ce module contient les structures de la timeline des événements applicatifs
+//! (déploiements, maintenances, incidents, rechargements de configuration, fixtures).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+use super::id::Id;
+use crate::{
+    extractors::{fields::FieldSpec, query::QuerySpec},
+    handlers::stream::CsvRecord,
+};
+
+/// Type d'événement applicatif
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Deploy,
+    Maintenance,
+    Incident,
+    ConfigReload,
+    Fixtures,
+}
+
+impl EventKind {
+    /// Représentation stockée en base
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::Deploy => "deploy",
+            EventKind::Maintenance => "maintenance",
+            EventKind::Incident => "incident",
+            EventKind::ConfigReload => "config_reload",
+            EventKind::Fixtures => "fixtures",
+        }
+    }
+}
+
+/// Identifiant d'un événement de la timeline
+pub type AppEventId = Id<AppEvent>;
+
+/// Événement de la timeline
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AppEvent {
+    #[schema(value_type = i64)]
+    pub id: AppEventId,
+    pub kind: String,
+    pub message: String,
+    pub details: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl CsvRecord for AppEvent {
+    fn csv_header() -> Vec<&'static str> {
+        vec!["id", "kind", "message", "details", "occurred_at"]
+    }
+
+    fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.kind.clone(),
+            self.message.clone(),
+            self.details.to_string(),
+            self.occurred_at.to_rfc3339(),
+        ]
+    }
+}
+
+/// Filtres de la timeline
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct EventsQuery {
+    /// Ne retourner qu'un type d'événement
+    pub kind: Option<EventKind>,
+    /// Ne retourner que les événements postérieurs à cette date
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Champs utilisables dans `filter`, `sort`, `search` et `fields` sur la timeline
+pub struct EventFields;
+
+impl QuerySpec for EventFields {
+    const FILTERS: &'static [(&'static str, &'static str)] = &[("kind", "kind")];
+    const SORTS: &'static [(&'static str, &'static str)] = &[("occurred_at", "occurred_at"), ("kind", "kind")];
+    const SEARCH: &'static [&'static str] = &["message"];
+    const DEFAULT_SORT: &'static str = "-occurred_at";
+}
+
+impl FieldSpec for EventFields {
+    const FIELDS: &'static [&'static str] = &["id", "kind", "message", "details", "occurred_at"];
+}
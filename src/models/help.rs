@@ -4,36 +4,130 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
-    pub status: String,
+    /// État agrégé des vérifications
+    pub status: HealthStatus,
     pub timestamp: DateTime<Utc>,
     pub version: String,
-    pub database: DatabaseStatus,
+    /// Résultat de chaque vérification enregistrée
+    pub checks: Vec<CheckResult>,
     pub system: SystemMetrics,
     pub performance: PerformanceMetrics,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
-pub struct DatabaseStatus {
-    pub connected: bool,
-    pub response_time_ms: Option<u64>,
-    pub error: Option<String>,
+/// État de santé, du meilleur au pire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    /// Fonctionnel, mais une vérification signale un problème
+    Degraded,
+    /// Une vérification critique échoue : l'instance répond 503
+    Unhealthy,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+/// Résultat d'une vérification de santé
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: HealthStatus,
+    /// Un échec de cette vérification rend l'instance `unhealthy`
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Informations propres à la vérification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+/// État d'une tâche de fond supervisée (voir `services::supervisor`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// La tâche a paniqué et attend son redémarrage
+    Restarting,
+}
+
+/// Suivi d'une tâche de fond, rapporté par la vérification `background_tasks`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskHealth {
+    pub name: String,
+    /// Expression cron des tâches du planificateur (`[scheduler.tasks]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    pub state: TaskState,
+    /// Fin du dernier passage
+    pub last_run: Option<DateTime<Utc>>,
+    /// Prochain passage prévu
+    pub next_run: Option<DateTime<Utc>>,
+    pub runs: u64,
+    /// Passages terminés en erreur
+    pub failures: u64,
+    /// Redémarrages après un panic
+    pub restarts: u64,
+    /// Dernière erreur ou dernier panic
+    pub last_error: Option<String>,
+}
+
+impl TaskHealth {
+    /// Le passage prévu n'a pas eu lieu, avec une marge d'un intervalle : la tâche est bloquée
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        match (self.last_run, self.next_run) {
+            (Some(last_run), Some(next_run)) => now > next_run + (next_run - last_run),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SystemMetrics {
     pub cpu_usage: f32,
     pub cpu_count: usize,
     pub memory_used_mb: u64,
     pub memory_total_mb: u64,
     pub memory_usage_percent: f32,
+    /// Occupation du point de montage le plus plein parmi `disks`
     pub disk_usage_percent: f32,
+    /// Occupation par point de montage (`[health] disk_mount_points`, tous si vide)
+    pub disks: Vec<DiskUsage>,
     pub uptime: u64,
 }
 
+/// Usage CPU par coeur depuis le rafraîchissement précédent (`GET /api/help/system/cpu`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CpuUsage {
+    /// Moyenne des coeurs, comme `SystemMetrics::cpu_usage`
+    pub average_percent: f32,
+    /// Coeur le plus chargé : une tâche qui sature un coeur reste visible derrière une moyenne basse
+    pub max_percent: f32,
+    pub cores: Vec<CpuCoreUsage>,
+}
+
+/// Usage d'un coeur CPU
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CpuCoreUsage {
+    /// Nom du coeur (`cpu0`, `cpu1`...)
+    pub name: String,
+    pub usage_percent: f32,
+}
+
+/// Occupation d'un point de montage
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiskUsage {
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub usage_percent: f32,
+}
+
+/// Temps de réponse du contrôle de santé (nom distinct de `status::PerformanceMetrics` dans OpenAPI)
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[schema(as = HealthPerformanceMetrics)]
 pub struct PerformanceMetrics {
     pub response_time_ms: u64,
 }
@@ -52,4 +146,125 @@ pub struct EndpointInfo {
     pub path: String,
     pub method: String,
     pub description: String,
-} 
\ No newline at end of file
+} 
+/// État de la sonde de readiness
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessStatus {
+    pub ready: bool,
+}
+
+/// Statistiques de l'allocateur mémoire, selon la feature `jemalloc` ou `mimalloc`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AllocatorStats {
+    /// Allocateur global : `jemalloc`, `mimalloc` ou `system` (aucune statistique)
+    pub allocator: String,
+    /// Mémoire physique occupée par l'allocateur (jemalloc) ou par le processus (mimalloc)
+    pub resident_bytes: Option<u64>,
+    /// Pic de mémoire physique du processus (mimalloc)
+    pub peak_resident_bytes: Option<u64>,
+    /// Pages actives (jemalloc) ou mémoire engagée (mimalloc)
+    pub active_bytes: Option<u64>,
+    /// Mémoire allouée par l'application (jemalloc)
+    pub allocated_bytes: Option<u64>,
+    /// Part de la mémoire résidente non allouée par l'application
+    pub fragmentation_percent: Option<f64>,
+    /// `POST /api/admin/allocator/heap-profile` peut produire un profil
+    pub heap_profiling: bool,
+}
+
+/// Réponse des sondes de liveness, de startup et de readiness
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProbeResponse {
+    /// `unhealthy` lorsque la sonde échoue (503)
+    pub status: HealthStatus,
+    /// Vérifications exécutées par la sonde (readiness uniquement)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<CheckResult>,
+}
+
+/// Résultat d'une étape de l'auto-test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SelfTestOutcome {
+    Passed,
+    Failed,
+    /// Étape sans objet sur cette instance (cache désactivé, aucune tâche de fond)
+    Skipped,
+}
+
+/// Étape de l'auto-test (`POST /api/help/selftest`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SelfTestStep {
+    pub name: String,
+    pub outcome: SelfTestOutcome,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Rapport de l'auto-test ; `passed` est faux dès qu'une étape échoue (503)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub version: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Étapes dans l'ordre d'exécution
+    pub steps: Vec<SelfTestStep>,
+}
+
+/// Niveau d'un log, du moins grave au plus grave
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// Log conservé en mémoire (voir `crate::logs`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub level: LogLevel,
+    /// Module à l'origine du log (`template_axum_sqlx_api::services::jobs`)
+    pub target: String,
+    pub message: String,
+    /// Trace de la requête ou de la tâche en cours, le cas échéant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Autres champs de l'événement
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    #[schema(value_type = Object)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Avertissements et erreurs parmi les derniers logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LogCounts {
+    pub warnings: usize,
+    pub errors: usize,
+}
+
+/// Filtres des derniers logs
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct LogsQuery {
+    /// Niveau minimal (`trace`, `debug`, `info`, `warn`, `error`) ; `info` par défaut
+    pub level: Option<LogLevel>,
+    /// Nombre maximal de logs renvoyés (100 par défaut)
+    pub limit: Option<usize>,
+}
@@ -0,0 +1,137 @@
+//! # Typed ID Module
+//!
+//! Ce module fournit `Id<T, R>`, un identifiant typé par l'entité qu'il désigne :
+//! un `Id<CorsOrigin>` ne peut pas être passé là où un `Id<Upload, Uuid>` est attendu.
+//!
+//! ```ignore
+//! pub type UserId = Id<User, i32>;
+//!
+//! pub async fn get_user(ApiPath(id): ApiPath<UserId>) -> Result<Json<User>, AppError> { ... }
+//! ```
+//!
+//! La représentation (JSON, SQL, chemin d'URL) est celle de la valeur brute `R` (`i64` par défaut).
+//! Dans les schémas OpenAPI, annoter les champs avec `#[schema(value_type = i64)]` (ou `Uuid`).
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+    Decode, Encode, Postgres, Type,
+};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use crate::sanitize::Sanitize;
+
+/// Identifiant d'une entité `T`, de représentation `R`
+pub struct Id<T, R = i64> {
+    value: R,
+    // `fn() -> T` : ni possession de `T`, ni contraintes Send/Sync héritées
+    _entity: PhantomData<fn() -> T>,
+}
+
+impl<T, R> Id<T, R> {
+    pub const fn new(value: R) -> Self {
+        Self { value, _entity: PhantomData }
+    }
+
+    /// Valeur brute de l'identifiant
+    pub fn get(&self) -> &R {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> R {
+        self.value
+    }
+}
+
+// Implémentations manuelles : un derive exigerait les mêmes traits sur `T`
+
+impl<T, R: Clone> Clone for Id<T, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T, R: Copy> Copy for Id<T, R> {}
+
+impl<T, R: PartialEq> PartialEq for Id<T, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T, R: Eq> Eq for Id<T, R> {}
+
+impl<T, R: Hash> Hash for Id<T, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<T, R: fmt::Debug> fmt::Debug for Id<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T, R: fmt::Display> fmt::Display for Id<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T, R: FromStr> FromStr for Id<T, R> {
+    type Err = R::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::new)
+    }
+}
+
+impl<T, R> Sanitize for Id<T, R> {}
+
+impl<T, R> From<R> for Id<T, R> {
+    fn from(value: R) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, R: Serialize> Serialize for Id<T, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T, R: Deserialize<'de>> Deserialize<'de> for Id<T, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        R::deserialize(deserializer).map(Self::new)
+    }
+}
+
+impl<T, R: Type<Postgres>> Type<Postgres> for Id<T, R> {
+    fn type_info() -> PgTypeInfo {
+        R::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        R::compatible(ty)
+    }
+}
+
+impl<'q, T, R: Encode<'q, Postgres>> Encode<'q, Postgres> for Id<T, R> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.value.encode_by_ref(buf)
+    }
+}
+
+impl<'r, T, R: Decode<'r, Postgres>> Decode<'r, Postgres> for Id<T, R> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        R::decode(value).map(Self::new)
+    }
+}
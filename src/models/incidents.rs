@@ -0,0 +1,170 @@
+//! # Incidents Models Module
+//!
+//! Ce module contient les incidents communiqués sur la page de status et leur
+//! fil de mises à jour. Un incident est ouvert tant qu'il n'est pas `resolved` ;
+//! chaque mise à jour fixe le nouveau statut de l'incident.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use super::id::Id;
+use crate::sanitize::{self, Sanitize};
+
+/// Identifiant d'un incident
+pub type IncidentId = Id<Incident>;
+
+/// Gravité d'un incident
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentSeverity {
+    Minor,
+    Major,
+    Critical,
+}
+
+impl IncidentSeverity {
+    /// Représentation stockée en base
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IncidentSeverity::Minor => "minor",
+            IncidentSeverity::Major => "major",
+            IncidentSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// Statut d'un incident, fixé par sa dernière mise à jour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentStatus {
+    #[default]
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+impl IncidentStatus {
+    /// Représentation stockée en base
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IncidentStatus::Investigating => "investigating",
+            IncidentStatus::Identified => "identified",
+            IncidentStatus::Monitoring => "monitoring",
+            IncidentStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// Incident enregistré dans la table `incidents`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Incident {
+    #[schema(value_type = i64)]
+    pub id: IncidentId,
+    pub title: String,
+    /// `minor`, `major` ou `critical`
+    pub severity: String,
+    /// `investigating`, `identified`, `monitoring` ou `resolved`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Date de résolution ; `null` tant que l'incident est ouvert
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Mise à jour publiée sur un incident
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct IncidentUpdate {
+    pub id: i64,
+    #[schema(value_type = i64)]
+    pub incident_id: IncidentId,
+    pub status: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Incident avec ses mises à jour, les plus récentes d'abord
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IncidentDetail {
+    #[serde(flatten)]
+    pub incident: Incident,
+    pub updates: Vec<IncidentUpdate>,
+}
+
+/// Requête d'ouverture d'un incident, avec son premier message
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewIncident {
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub title: String,
+    pub severity: IncidentSeverity,
+    /// Statut initial (`investigating` par défaut)
+    #[serde(default)]
+    pub status: IncidentStatus,
+    #[validate(length(min = 1, max = 5000, message = "must be between 1 and 5000 characters"))]
+    pub message: String,
+}
+
+impl Sanitize for NewIncident {
+    fn sanitize(&mut self) {
+        self.title = sanitize::plain_text(&self.title);
+        self.message = sanitize::plain_text(&self.message);
+    }
+}
+
+/// Modification partielle d'un incident : les champs absents sont conservés
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateIncident {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<IncidentSeverity>,
+}
+
+impl Sanitize for UpdateIncident {
+    fn sanitize(&mut self) {
+        if let Some(title) = &mut self.title {
+            *title = sanitize::plain_text(title);
+        }
+    }
+}
+
+/// Mise à jour publiée sur un incident ; `resolved` le résout, tout autre statut le rouvre
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewIncidentUpdate {
+    pub status: IncidentStatus,
+    #[validate(length(min = 1, max = 5000, message = "must be between 1 and 5000 characters"))]
+    pub message: String,
+}
+
+impl Sanitize for NewIncidentUpdate {
+    fn sanitize(&mut self) {
+        self.message = sanitize::plain_text(&self.message);
+    }
+}
+
+/// Résolution d'un incident, avec un message facultatif
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ResolveIncident {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 5000, message = "must be between 1 and 5000 characters"))]
+    pub message: Option<String>,
+}
+
+impl Sanitize for ResolveIncident {
+    fn sanitize(&mut self) {
+        if let Some(message) = &mut self.message {
+            *message = sanitize::plain_text(message);
+        }
+    }
+}
+
+/// Filtres de la liste des incidents
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct IncidentsQuery {
+    /// `true` : incidents ouverts seulement ; `false` : incidents résolus seulement
+    pub open: Option<bool>,
+}
@@ -0,0 +1,80 @@
+//! # Jobs Models Module
+//!
+//! Ce module contient les tâches asynchrones soumises par les handlers et
+//! exécutées en arrière-plan (voir `services::jobs`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::id::Id;
+use crate::sanitize::Sanitize;
+
+/// Identifiant d'une tâche, non devinable : il sert de droit de consultation
+pub type JobId = Id<Job, Uuid>;
+
+/// Tâche soumise, avec son état d'avancement
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Job {
+    #[schema(value_type = Uuid)]
+    pub id: JobId,
+    /// Type de tâche, associé à un handler du registre
+    pub kind: String,
+    #[serde(skip)]
+    pub payload: serde_json::Value,
+    /// `queued`, `running`, `succeeded`, `failed` (erreur définitive) ou `dead`
+    /// (nouvelles tentatives épuisées, en attente d'une relance par l'administration)
+    pub status: String,
+    /// Résultat produit par le handler, une fois la tâche terminée
+    pub result: Option<serde_json::Value>,
+    /// Dernière erreur rencontrée
+    pub error: Option<String>,
+    pub attempts: i32,
+    #[serde(skip)]
+    pub run_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// La tâche est terminée, avec succès ou non
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "succeeded" | "failed" | "dead")
+    }
+}
+
+/// Soumission d'une tâche par l'administration
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct NewJob {
+    /// Type de tâche enregistré (par exemple `example`)
+    #[validate(length(min = 1, max = 255))]
+    pub kind: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+impl Sanitize for NewJob {}
+
+/// Filtres de la liste des tâches de l'administration
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct JobsQuery {
+    /// Statut des tâches listées ; `dead` par défaut (dead-letter)
+    #[serde(default = "default_status")]
+    pub status: String,
+    /// Type de tâche
+    pub kind: Option<String>,
+}
+
+impl Default for JobsQuery {
+    fn default() -> Self {
+        Self { status: default_status(), kind: None }
+    }
+}
+
+fn default_status() -> String {
+    "dead".to_string()
+}
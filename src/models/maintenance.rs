@@ -0,0 +1,72 @@
+//! # Maintenance Models Module
+//!
+//! Ce module contient les fenêtres de maintenance planifiée, déclarées dans
+//! `[[maintenance.windows]]` ou créées via l'API d'administration. Pendant une
+//! fenêtre, les notifications d'alertes sont suspendues et la page de status
+//! affiche un bandeau de maintenance.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::id::Id;
+use crate::{
+    config::ScheduledMaintenance,
+    sanitize::{self, Sanitize},
+};
+
+/// Identifiant d'une fenêtre de maintenance
+pub type MaintenanceWindowId = Id<MaintenanceWindow>;
+
+/// Fenêtre de maintenance planifiée
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct MaintenanceWindow {
+    /// Identifiant en base ; `null` pour une fenêtre déclarée dans la configuration
+    #[schema(value_type = Option<i64>)]
+    pub id: Option<MaintenanceWindowId>,
+    pub title: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl MaintenanceWindow {
+    /// La fenêtre couvre `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+}
+
+impl From<&ScheduledMaintenance> for MaintenanceWindow {
+    fn from(scheduled: &ScheduledMaintenance) -> Self {
+        Self {
+            id: None,
+            title: scheduled.title.clone(),
+            description: scheduled.description.clone(),
+            starts_at: scheduled.starts_at,
+            ends_at: scheduled.ends_at,
+        }
+    }
+}
+
+/// Requête de planification d'une fenêtre de maintenance
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewMaintenanceWindow {
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 5000, message = "must be at most 5000 characters"))]
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    /// Doit être postérieure à `starts_at`
+    pub ends_at: DateTime<Utc>,
+}
+
+impl Sanitize for NewMaintenanceWindow {
+    fn sanitize(&mut self) {
+        self.title = sanitize::plain_text(&self.title);
+        self.description = sanitize::plain_text(&self.description);
+    }
+}
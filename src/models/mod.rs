@@ -1,7 +1,21 @@
 // Re-export all model modules here
 // Example:
-// pub mod user;
 // pub mod product;
 
+pub mod auth;
+pub mod batch;
+pub mod cors;
+pub mod error;
+pub mod events;
 pub mod help;
+pub mod id;
+pub mod incidents;
+pub mod jobs;
+pub mod maintenance;
+pub mod post;
+pub mod routes;
+pub mod search;
 pub mod status;
+pub mod uploads;
+pub mod user;
+pub mod webhooks;
@@ -0,0 +1,114 @@
+//! # Post Models Module
+//!
+//! Ce module contient la ressource d'exemple `post` et ses commentaires, reliés
+//! aux utilisateurs par clés étrangères. Les structures « détail » regroupent
+//! les données liées chargées en même temps que la ressource (auteur, commentaires).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::{id::Id, user::UserId};
+use crate::sanitize::{self, Sanitize};
+
+/// Identifiant d'un post
+pub type PostId = Id<Post>;
+
+/// Identifiant d'un commentaire
+pub type CommentId = Id<Comment>;
+
+/// Post enregistré dans la table `posts`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Post {
+    #[schema(value_type = i64)]
+    pub id: PostId,
+    /// Auteur du post
+    #[schema(value_type = i64)]
+    pub user_id: UserId,
+    pub title: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Post d'une liste, avec le nombre de ses commentaires (agrégat SQL)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PostSummary {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub post: Post,
+    pub comment_count: i64,
+}
+
+/// Auteur d'un post ou d'un commentaire
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Author {
+    #[schema(value_type = i64)]
+    pub id: UserId,
+    pub name: String,
+}
+
+/// Post avec son auteur et ses commentaires
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostDetail {
+    #[serde(flatten)]
+    pub post: Post,
+    pub author: Author,
+    pub comments: Vec<CommentDetail>,
+}
+
+/// Requête de création d'un post
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewPost {
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub title: String,
+    #[validate(length(min = 1, max = 20000, message = "must be between 1 and 20000 characters"))]
+    pub body: String,
+}
+
+impl Sanitize for NewPost {
+    fn sanitize(&mut self) {
+        self.title = sanitize::plain_text(&self.title);
+        self.body = sanitize::plain_text(&self.body);
+    }
+}
+
+/// Commentaire enregistré dans la table `comments`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Comment {
+    #[schema(value_type = i64)]
+    pub id: CommentId,
+    #[schema(value_type = i64)]
+    pub post_id: PostId,
+    /// Auteur ; `null` si son compte a été supprimé
+    #[schema(value_type = Option<i64>)]
+    pub user_id: Option<UserId>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Commentaire avec son auteur
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CommentDetail {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub author: Option<Author>,
+}
+
+/// Requête de création d'un commentaire
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewComment {
+    /// Auteur du commentaire
+    #[schema(value_type = i64)]
+    pub user_id: UserId,
+    #[validate(length(min = 1, max = 5000, message = "must be between 1 and 5000 characters"))]
+    pub body: String,
+}
+
+impl Sanitize for NewComment {
+    fn sanitize(&mut self) {
+        self.body = sanitize::plain_text(&self.body);
+    }
+}
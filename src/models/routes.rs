@@ -0,0 +1,56 @@
+//! # Routes Models Module
+//!
+//! Ce module décrit les entrées du registre des routes exposé par l'API.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Authentification requise pour appeler une route
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthRequirement {
+    /// Route publique
+    None,
+    /// Jeton d'administration ou clé d'API d'un administrateur (`Authorization: Bearer`)
+    Admin,
+    /// Signature HMAC du fournisseur de webhooks
+    WebhookSignature,
+}
+
+/// Description d'une route exposée par l'API
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RouteInfo {
+    pub path: String,
+    pub method: String,
+    pub description: String,
+    pub auth: AuthRequirement,
+    /// Politique de limitation de débit appliquée, `None` si aucune
+    pub rate_limit: Option<String>,
+    pub deprecated: bool,
+}
+
+impl RouteInfo {
+    /// Crée une entrée publique, sans limitation de débit ni dépréciation
+    pub fn new(method: &str, path: &str, description: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            method: method.to_string(),
+            description: description.to_string(),
+            auth: AuthRequirement::None,
+            rate_limit: None,
+            deprecated: false,
+        }
+    }
+
+    /// Définit l'authentification requise
+    pub fn auth(mut self, auth: AuthRequirement) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Marque la route comme dépréciée
+    pub fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+}
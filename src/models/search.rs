@@ -0,0 +1,33 @@
+//! # Search Models Module
+//!
+//! Ce module contient les paramètres et les résultats de la recherche plein texte
+//! sur les posts (`GET /api/search?q=`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{post::PostId, user::UserId};
+
+/// Paramètres de recherche
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    /// Termes recherchés, syntaxe des moteurs web : `"phrase exacte"`, `-exclu`, `or`
+    pub q: Option<String>,
+}
+
+/// Post trouvé par la recherche, du plus pertinent au moins pertinent
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SearchHit {
+    #[schema(value_type = i64)]
+    pub id: PostId,
+    #[schema(value_type = i64)]
+    pub user_id: UserId,
+    pub title: String,
+    /// Extraits du corps autour des termes trouvés, entourés de `<mark>`
+    pub headline: String,
+    /// Pertinence calculée par `ts_rank` (titre pondéré au-dessus du corps)
+    pub rank: f32,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,30 +1,49 @@
 //! # Status Models
 //!
 //! Ce module contient les structures de données pour la page de status
-//! et la gestion de l'historique des métriques.
+//! et la tâche de fond des métriques. L'historique est conservé en base
+//! (voir `services::metrics`).
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
 use std::collections::VecDeque;
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
+use crate::cache::CacheRegistry;
 use crate::db::DatabaseManager;
 use crate::config::Config;
-use crate::models::help::SystemMetrics;
-use sysinfo::{Disks, System};
+use crate::models::help::{CpuCoreUsage, CpuUsage, DiskUsage, SystemMetrics};
+use crate::models::incidents::IncidentDetail;
+use crate::middleware::{
+    status_codes::StatusCounters,
+    trace::{inject, TraceContext},
+};
+use crate::services::{
+    alerts::AlertEngine,
+    anomalies::AnomalyDetector,
+    cluster::Cluster,
+    maintenance::active_window,
+    metrics::{load_snapshot, recent_history, record_history, save_snapshot},
+    monitoring::{record_checks, Monitor},
+    queries::QueryInsights,
+    runtime::RuntimeSampler,
+    supervisor::{Supervisor, TaskHandle},
+};
+use sysinfo::{DiskRefreshKind, Disks, Networks, System};
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
 
-/// Taille maximale de l'historique (nombre d'entrées)
-const MAX_HISTORY_SIZE: usize = 50;
+/// Status d'une mesure où l'API ou la base de données ne répond pas ; compté comme indisponibilité
+pub const DEGRADED_STATUS: &str = "Dégradé";
 
-/// Taille de la file pour les calculs de performance (dernières 5 entrées)
-const PERFORMANCE_QUEUE_SIZE: usize = 5;
+/// Nombre minimal de réponses sur l'intervalle pour que le taux de 5xx pénalise le score
+pub const ERROR_RATE_MIN_REQUESTS: u64 = 20;
 
-/// Intervalle minimum entre deux entrées d'historique (5 minutes en secondes)
-const HISTORY_INTERVAL_SECONDS: i64 = 300;
 
 /// Entrée d'historique pour les métriques
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HistoryEntry {
     pub timestamp: DateTime<Utc>,
     pub response_time_ms: u64,
@@ -32,10 +51,449 @@ pub struct HistoryEntry {
     pub db_response_time_ms: Option<u64>,
     pub status: String,
     pub issues: Vec<String>, // Liste des problèmes détectés
+    /// Trafic réseau depuis la mesure précédente (absent des entrées antérieures à sa collecte)
+    pub network: Option<NetworkUsage>,
+}
+
+/// Métrique suivie par la détection d'anomalies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    /// Temps de réponse de l'API (ms)
+    ResponseTime,
+    /// Part des réponses 5xx de l'intervalle (%)
+    ErrorRate,
+}
+
+/// Mesure inhabituelle par rapport aux mesures récentes (voir `services::anomalies`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Anomaly {
+    pub metric: AnomalyMetric,
+    pub value: f64,
+    /// Moyenne des mesures de référence
+    pub mean: f64,
+    /// Écart à la moyenne, en nombre d'écarts types
+    pub z_score: f64,
+}
+
+impl Anomaly {
+    /// Libellé du problème, ajouté aux problèmes de la mesure
+    pub fn describe(&self) -> String {
+        match self.metric {
+            AnomalyMetric::ResponseTime => format!(
+                "Latence inhabituelle: {:.0} ms (moyenne {:.0} ms, {:.1} σ)",
+                self.value, self.mean, self.z_score
+            ),
+            AnomalyMetric::ErrorRate => format!(
+                "Pic d'erreurs 5xx: {:.1}% (moyenne {:.1}%, {:.1} σ)",
+                self.value, self.mean, self.z_score
+            ),
+        }
+    }
+}
+
+/// Trafic réseau mesuré entre deux passages de la tâche de fond
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NetworkUsage {
+    pub rx_bytes_per_sec: u64,
+    pub tx_bytes_per_sec: u64,
+    /// Erreurs de réception et d'émission sur la période
+    pub errors: u64,
+    /// Débit total rapporté à `[status] network_capacity_mbps`
+    pub load_percent: f32,
+}
+
+impl NetworkUsage {
+    /// Calcule le débit à partir des octets et erreurs comptés pendant `elapsed`
+    pub fn from_deltas(rx_bytes: u64, tx_bytes: u64, errors: u64, elapsed: Duration, capacity_mbps: u64) -> Self {
+        let seconds = elapsed.as_secs_f64().max(1.0);
+        let rx_bytes_per_sec = (rx_bytes as f64 / seconds) as u64;
+        let tx_bytes_per_sec = (tx_bytes as f64 / seconds) as u64;
+        let capacity_bits = capacity_mbps.max(1) as f64 * 1_000_000.0;
+        let load_percent = ((rx_bytes_per_sec + tx_bytes_per_sec) as f64 * 8.0 / capacity_bits * 100.0).min(100.0) as f32;
+
+        Self { rx_bytes_per_sec, tx_bytes_per_sec, errors, load_percent }
+    }
+
+    /// Libellé de la charge affiché sur la page de status
+    pub fn load_label(&self) -> &'static str {
+        match self.load_percent {
+            x if x < 30.0 => "Faible",
+            x if x < 60.0 => "Modérée",
+            x if x < 80.0 => "Élevée",
+            _ => "Critique",
+        }
+    }
+}
+
+/// Disponibilité sur une période, calculée à partir de l'historique des métriques
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UptimeStats {
+    /// Période : `24h`, `7d` ou `30d`
+    pub window: String,
+    /// Nombre de mesures sur la période (une par passage, `[monitoring] interval_seconds`)
+    pub samples: i64,
+    /// Part des mesures où l'API et la base de données répondaient ; `null` sans mesure
+    pub uptime_percent: Option<f64>,
+    pub avg_response_time_ms: Option<f64>,
+}
+
+/// Réponse de `GET /api/status` : métriques courantes, disponibilité et incidents ouverts
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StatusSummary {
+    #[serde(flatten)]
+    pub metrics: PerformanceMetrics,
+    pub uptime: Vec<UptimeStats>,
+    /// Incidents non résolus, les plus récents d'abord
+    pub incidents: Vec<IncidentDetail>,
+    /// Débit mesuré au moment de la requête
+    pub throughput: Throughput,
+}
+
+/// Débit de l'instance, mesuré par `middleware::status_codes`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Throughput {
+    /// Réponses par seconde, en moyenne sur les 10 dernières secondes
+    pub current_rps: f64,
+    /// Réponses de la seconde la plus chargée depuis le démarrage
+    pub peak_rps: u64,
+}
+
+/// Latence d'une route, mesurée par `middleware::latency`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EndpointLatency {
+    pub method: String,
+    /// Modèle de chemin de la route (`/api/users/{id}`)
+    pub route: String,
+    pub count: u64,
+    /// Réponses 4xx
+    pub client_errors: u64,
+    /// Réponses 5xx
+    pub server_errors: u64,
+    pub avg_ms: f64,
+    /// Quantiles estimés à partir des seuils de l'histogramme
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// Requête du classement des plus lentes (`GET /api/help/slow-endpoints`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlowRequest {
+    pub method: String,
+    /// Modèle de chemin de la route (`/api/users/{id}`)
+    pub route: String,
+    /// Statut HTTP de la réponse
+    pub status: u16,
+    pub duration_ms: f64,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Statistiques d'une requête SQL relevées dans `pg_stat_statements`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QueryStat {
+    /// Identifiant de la requête normalisée (`queryid`)
+    pub query_id: Option<i64>,
+    /// Texte normalisé, paramètres remplacés par `$1`, `$2`...
+    pub query: String,
+    pub calls: u64,
+    pub total_time_ms: f64,
+    pub mean_time_ms: f64,
+    /// Lignes retournées ou modifiées, toutes exécutions confondues
+    pub rows: u64,
+}
+
+/// Requêtes SQL les plus coûteuses (`GET /api/admin/queries`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QueryInsightsReport {
+    pub collected_at: DateTime<Utc>,
+    /// Classées par temps cumulé
+    pub by_total_time: Vec<QueryStat>,
+    /// Classées par temps moyen d'une exécution
+    pub by_mean_time: Vec<QueryStat>,
+}
+
+/// Seuil de l'histogramme : requêtes de durée inférieure ou égale à `le_ms`
+/// (au-delà du seuil précédent) ; `null` pour le dernier seuil
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LatencyBucket {
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+/// Résultat d'une sonde d'une dépendance externe (voir `services::monitoring`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TargetCheck {
+    pub checked_at: DateTime<Utc>,
+    pub up: bool,
+    /// Durée de la sonde ; absente si elle a expiré
+    pub response_time_ms: Option<u64>,
+    /// Cause de l'indisponibilité
+    pub error: Option<String>,
+}
+
+/// État d'une dépendance externe de `[[monitoring.targets]]`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TargetStatus {
+    pub name: String,
+    /// `http` ou `tcp`
+    pub kind: String,
+    /// URL ou adresse sondée
+    pub endpoint: String,
+    /// Dernières sondes, de la plus ancienne à la plus récente ; vide avant le premier passage
+    pub checks: Vec<TargetCheck>,
+}
+
+impl TargetStatus {
+    /// Dernière sonde
+    pub fn latest(&self) -> Option<&TargetCheck> {
+        self.checks.last()
+    }
+}
+
+/// Filtre de `GET /api/status/history`
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct HistoryQuery {
+    /// Ne retourner que les entrées enregistrées à partir de cette date
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Score de santé en dessous duquel le service est annoncé dégradé
+pub const DEGRADED_HEALTH_SCORE: u8 = 60;
+
+/// État global du service, affiché par les badges de status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStatus {
+    Operational,
+    Degraded,
+    Down,
+    /// Aucune métrique n'a encore été calculée
+    Unknown,
+}
+
+impl ServiceStatus {
+    /// État déduit des dernières métriques et des incidents ouverts.
+    ///
+    /// L'API ou la base de données injoignable, ou un incident `critical` ouvert,
+    /// rendent le service `down` ; un score de santé faible ou un autre incident
+    /// ouvert le rendent `degraded`.
+    pub fn evaluate(metrics: Option<&PerformanceMetrics>, incidents: &[IncidentDetail]) -> Self {
+        match metrics {
+            Some(metrics) => Self::assess(&metrics.status, metrics.health_score, incidents),
+            None => ServiceStatus::Unknown,
+        }
+    }
+
+    /// Même règle qu'`evaluate`, à partir du status et du score de santé seuls
+    /// (réponse d'une autre instance)
+    pub fn assess(status: &str, health_score: u8, incidents: &[IncidentDetail]) -> Self {
+        if status == DEGRADED_STATUS || incidents.iter().any(|detail| detail.incident.severity == "critical") {
+            ServiceStatus::Down
+        } else if health_score < DEGRADED_HEALTH_SCORE || !incidents.is_empty() {
+            ServiceStatus::Degraded
+        } else {
+            ServiceStatus::Operational
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ServiceStatus::Operational => "operational",
+            ServiceStatus::Degraded => "degraded",
+            ServiceStatus::Down => "down",
+            ServiceStatus::Unknown => "unknown",
+        }
+    }
+
+    /// Couleur nommée de shields.io
+    pub fn color(self) -> &'static str {
+        match self {
+            ServiceStatus::Operational => "brightgreen",
+            ServiceStatus::Degraded => "yellow",
+            ServiceStatus::Down => "red",
+            ServiceStatus::Unknown => "lightgrey",
+        }
+    }
+
+    /// Couleur du badge SVG, identique à celle de shields.io
+    pub fn hex_color(self) -> &'static str {
+        match self {
+            ServiceStatus::Operational => "#4c1",
+            ServiceStatus::Degraded => "#dfb317",
+            ServiceStatus::Down => "#e05d44",
+            ServiceStatus::Unknown => "#9f9f9f",
+        }
+    }
+}
+
+/// État d'une instance du cluster (`[[monitoring.peers]]` et l'instance courante)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NodeStatus {
+    pub name: String,
+    /// URL de base du pair ; absente pour l'instance courante
+    pub url: Option<String>,
+    /// Instance ayant servi la réponse
+    pub local: bool,
+    /// Le pair a répondu à la dernière interrogation
+    pub reachable: bool,
+    pub status: ServiceStatus,
+    pub health_score: Option<u8>,
+    /// Durée de la dernière interrogation du pair
+    pub response_time_ms: Option<u64>,
+    pub checked_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+/// État agrégé des instances (`GET /api/status/cluster`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ClusterStatus {
+    /// `operational` si toutes les instances le sont, `down` si aucune ne répond ou n'est
+    /// disponible, `degraded` sinon
+    pub status: ServiceStatus,
+    /// Moyenne des scores de santé des instances qui en ont un
+    pub health_score: Option<u8>,
+    /// Instances `operational` ou `degraded`
+    pub nodes_up: usize,
+    pub nodes: Vec<NodeStatus>,
+}
+
+impl ClusterStatus {
+    pub fn aggregate(nodes: Vec<NodeStatus>) -> Self {
+        let scores: Vec<u32> = nodes.iter().filter_map(|node| node.health_score).map(u32::from).collect();
+        let health_score = (!scores.is_empty()).then(|| (scores.iter().sum::<u32>() / scores.len() as u32) as u8);
+        let nodes_up = nodes
+            .iter()
+            .filter(|node| matches!(node.status, ServiceStatus::Operational | ServiceStatus::Degraded))
+            .count();
+
+        let status = if nodes.iter().all(|node| node.status == ServiceStatus::Unknown) {
+            ServiceStatus::Unknown
+        } else if nodes_up == 0 {
+            ServiceStatus::Down
+        } else if nodes.iter().all(|node| node.status == ServiceStatus::Operational) {
+            ServiceStatus::Operational
+        } else {
+            ServiceStatus::Degraded
+        };
+
+        Self { status, health_score, nodes_up, nodes }
+    }
+}
+
+/// Paramètres des badges de status
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct BadgeQuery {
+    /// Texte de la partie gauche du badge (`status` par défaut)
+    pub label: Option<String>,
+}
+
+impl BadgeQuery {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().filter(|label| !label.trim().is_empty()).unwrap_or("status")
+    }
+}
+
+/// Badge au format « endpoint » de shields.io (`https://img.shields.io/endpoint?url=...`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShieldsBadge {
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+}
+
+/// État du runtime Tokio mesuré par `services::runtime`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RuntimeUsage {
+    /// Threads workers du scheduler
+    pub workers: usize,
+    /// Tâches asynchrones en cours d'exécution ou en attente
+    pub alive_tasks: usize,
+    /// Tâches prêtes en attente dans la file globale du scheduler
+    pub global_queue_depth: usize,
+    /// Tâches en attente d'un thread du pool bloquant (`spawn_blocking`) ; absent sans `tokio_unstable`
+    pub blocking_queue_depth: Option<usize>,
+    /// Part du temps des workers passée à exécuter des tâches sur la période
+    pub busy_percent: f64,
+}
+
+/// Réponses servies par classe de statut, comptées par `middleware::status_codes`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RequestCounts {
+    /// Réponses 2xx
+    pub success: u64,
+    /// Réponses 3xx
+    pub redirection: u64,
+    /// Réponses 4xx
+    pub client_errors: u64,
+    /// Réponses 5xx
+    pub server_errors: u64,
+}
+
+impl RequestCounts {
+    pub fn total(&self) -> u64 {
+        self.success + self.redirection + self.client_errors + self.server_errors
+    }
+
+    /// Part des réponses 5xx, en pourcentage (0 sans réponse)
+    pub fn server_error_percent(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.server_errors as f64 / total as f64 * 100.0,
+        }
+    }
+
+    /// Réponses servies depuis le relevé `previous`
+    pub fn since(&self, previous: &Self) -> Self {
+        Self {
+            success: self.success.saturating_sub(previous.success),
+            redirection: self.redirection.saturating_sub(previous.redirection),
+            client_errors: self.client_errors.saturating_sub(previous.client_errors),
+            server_errors: self.server_errors.saturating_sub(previous.server_errors),
+        }
+    }
+}
+
+/// Utilisation d'un cache typé (`cache::CacheRegistry`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CacheStats {
+    /// Espace de noms du cache
+    pub namespace: String,
+    /// Entrées présentes
+    pub entries: u64,
+    /// Lectures servies depuis le cache
+    pub hits: u64,
+    /// Lectures qui ont dû calculer ou charger la valeur
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Part des lectures servies depuis le cache, en pourcentage (0 sans lecture)
+    pub fn hit_percent(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64 * 100.0,
+        }
+    }
+
+    /// Lectures depuis le relevé `previous` du même espace de noms (toutes s'il n'y figure pas)
+    pub fn since(&self, previous: &[CacheStats]) -> Self {
+        let previous = previous.iter().find(|stats| stats.namespace == self.namespace);
+        Self {
+            namespace: self.namespace.clone(),
+            entries: self.entries,
+            hits: self.hits.saturating_sub(previous.map_or(0, |stats| stats.hits)),
+            misses: self.misses.saturating_sub(previous.map_or(0, |stats| stats.misses)),
+        }
+    }
 }
 
 /// Métriques de performance calculées
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PerformanceMetrics {
     pub timestamp: DateTime<Utc>,
     pub health_score: u8,
@@ -43,6 +501,14 @@ pub struct PerformanceMetrics {
     pub memory_score: u8,
     pub perf_score: u8,
     pub network_score: u8,
+    pub network: Option<NetworkUsage>,
+    /// Réponses servies depuis le passage précédent ; leur taux de 5xx pénalise `health_score`
+    pub requests: Option<RequestCounts>,
+    /// Runtime Tokio, occupation des workers depuis le passage précédent
+    pub runtime: Option<RuntimeUsage>,
+    /// Caches typés, lectures depuis le passage précédent
+    #[serde(default)]
+    pub caches: Vec<CacheStats>,
     pub avg_response_time: f64,
     pub system_load: f64,
     
@@ -63,80 +529,281 @@ pub struct PerformanceMetrics {
     pub minimal_waittime: u64, // en secondes
 }
 
-/// Gestionnaire d'historique global (en mémoire)
-pub static METRICS_HISTORY: Lazy<Mutex<VecDeque<HistoryEntry>>> = 
-    Lazy::new(|| Mutex::new(VecDeque::with_capacity(MAX_HISTORY_SIZE)));
+/// Métriques calculées par la tâche de fond, partagées avec les handlers de status.
+///
+/// Détenu par l'état de l'application (`AppState::metrics`) et transmis à la tâche
+/// de fond : chaque instance de l'application a son propre cache.
+#[derive(Debug)]
+pub struct MetricsStore {
+    /// Dernières métriques calculées
+    latest: Mutex<Option<PerformanceMetrics>>,
+    /// File des métriques de performance (`[monitoring] recent_samples` dernières entrées)
+    recent: Mutex<VecDeque<PerformanceMetrics>>,
+    capacity: usize,
+    /// Diffusion des nouvelles métriques aux abonnés de `/api/status/live`
+    updates: broadcast::Sender<PerformanceMetrics>,
+    /// Instances `sysinfo` conservées d'un passage à l'autre, rafraîchies par la tâche de fond
+    system: Mutex<SystemSampler>,
+}
+
+impl MetricsStore {
+    /// Cache conservant les `recent_samples` dernières mesures
+    pub fn new(recent_samples: usize) -> Self {
+        let capacity = recent_samples.max(1);
+        Self {
+            latest: Mutex::new(None),
+            recent: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            updates: broadcast::channel(16).0,
+            system: Mutex::new(SystemSampler::new()),
+        }
+    }
+
+    /// Limite les disques mesurés à ces points de montage (`[health] disk_mount_points`) ;
+    /// tous si la liste est vide
+    pub fn with_disk_mounts(self, mount_points: Vec<String>) -> Self {
+        self.system.lock().unwrap().mount_points = mount_points;
+        self
+    }
+
+    /// Met à jour le cache et diffuse les métriques aux abonnés
+    pub fn publish(&self, metrics: PerformanceMetrics) {
+        *self.latest.lock().unwrap() = Some(metrics.clone());
+
+        {
+            let mut recent = self.recent.lock().unwrap();
+            if recent.len() >= self.capacity {
+                recent.pop_front();
+            }
+            recent.push_back(metrics.clone());
+        }
+
+        // Aucun abonné : rien à diffuser
+        let _ = self.updates.send(metrics);
+    }
+
+    /// Abonnement aux métriques publiées après l'appel
+    pub fn subscribe(&self) -> broadcast::Receiver<PerformanceMetrics> {
+        self.updates.subscribe()
+    }
+
+    /// Dernières métriques calculées ; `None` avant le premier passage de la tâche de fond
+    pub fn latest(&self) -> Option<PerformanceMetrics> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Les dernières métriques sont plus récentes que leur `minimal_waittime`
+    pub fn is_fresh(&self) -> bool {
+        self.latest().is_some_and(|metrics| {
+            Utc::now().signed_duration_since(metrics.timestamp).num_seconds() < metrics.minimal_waittime as i64
+        })
+    }
+
+    /// Dernières métriques publiées, de la plus ancienne à la plus récente
+    pub fn recent(&self) -> Vec<PerformanceMetrics> {
+        self.recent.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Rafraîchit les métriques système ; l'usage CPU couvre la période depuis l'appel précédent
+    pub fn refresh_system(&self) -> SystemMetrics {
+        self.system.lock().unwrap().sample()
+    }
+
+    /// Dernières métriques système, sans attente : mesurées au besoin si la tâche de fond
+    /// n'est pas encore passée (usage CPU alors nul)
+    pub fn system(&self) -> SystemMetrics {
+        let mut system = self.system.lock().unwrap();
+        match &system.latest {
+            Some(metrics) => metrics.clone(),
+            None => system.sample(),
+        }
+    }
 
-/// File des métriques de performance (dernières 5 entrées)
-pub static PERFORMANCE_QUEUE: Lazy<Mutex<VecDeque<PerformanceMetrics>>> = 
-    Lazy::new(|| Mutex::new(VecDeque::with_capacity(PERFORMANCE_QUEUE_SIZE)));
+    /// Usage CPU par coeur de la dernière mesure système, prise au besoin comme `system`
+    pub fn cpu(&self) -> CpuUsage {
+        let mut system = self.system.lock().unwrap();
+        if system.latest.is_none() {
+            system.sample();
+        }
+        system.cpu.clone()
+    }
+}
+
+/// Démarre la tâche de calcul en arrière-plan, sous la supervision de `tasks`
+#[allow(clippy::too_many_arguments)]
+pub async fn start_background_metrics_task(
+    tasks: &Supervisor,
+    db: DatabaseManager,
+    config: Config,
+    store: Arc<MetricsStore>,
+    status_codes: Arc<StatusCounters>,
+    queries: Arc<QueryInsights>,
+    cluster: Arc<Cluster>,
+    caches: Arc<CacheRegistry>,
+) {
+    // Dernières métriques du précédent démarrage, affichées jusqu'au premier passage
+    if config.monitoring.snapshot_max_age_hours > 0 {
+        let max_age = chrono::Duration::hours(config.monitoring.snapshot_max_age_hours as i64);
+        match load_snapshot(db.get_pool(), max_age).await {
+            Ok(Some(metrics)) => {
+                info!("Restored metrics snapshot from {}", metrics.timestamp);
+                store.publish(metrics);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load metrics snapshot: {}", e),
+        }
+    }
 
-/// Dernière métrique calculée (cache global)
-pub static LATEST_CACHED_METRICS: Lazy<Mutex<Option<PerformanceMetrics>>> = 
-    Lazy::new(|| Mutex::new(None));
+    tasks.spawn("metrics", move |task| {
+        run_metrics_task(task, db.clone(), config.clone(), store.clone(), status_codes.clone(), queries.clone(), cluster.clone(), caches.clone())
+    });
+}
 
-/// Démarre la tâche de calcul en arrière-plan
-pub async fn start_background_metrics_task(_db: DatabaseManager, config: Config) {
-    tokio::spawn(async move {
-        let mut interval = interval(Duration::from_secs(HISTORY_INTERVAL_SECONDS as u64));
+/// Boucle de la tâche des métriques, reconstruite à chaque redémarrage
+#[allow(clippy::too_many_arguments)]
+async fn run_metrics_task(
+    task: TaskHandle,
+    db: DatabaseManager,
+    config: Config,
+    store: Arc<MetricsStore>,
+    status_codes: Arc<StatusCounters>,
+    queries: Arc<QueryInsights>,
+    cluster: Arc<Cluster>,
+    caches: Arc<CacheRegistry>,
+) {
+    let period = Duration::from_secs(config.monitoring.interval_seconds.max(1));
+    let mut interval = interval(period);
+    
+    // Compteurs réseau de référence : chaque passage mesure le trafic depuis le précédent
+    let mut network = NetworkSampler::new();
+    let mut runtime = RuntimeSampler::new();
+    // Relevé de référence des réponses : chaque passage compte celles servies depuis le précédent
+    let mut last_requests = status_codes.snapshot();
+    let mut last_caches = caches.stats().await;
+    let mut alerts = AlertEngine::new(&config.alerts);
+    let monitor = Monitor::new(&config.monitoring);
+    let mut anomalies = AnomalyDetector::new(&config.anomalies);
+    
+    // Attendre un peu pour que le serveur soit prêt
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    
+    // Référence des anomalies : dernières mesures enregistrées avant ce démarrage
+    if anomalies.is_enabled() {
+        match recent_history(db.get_pool(), anomalies.window_samples() as i64).await {
+            Ok(history) => anomalies.seed(&history),
+            Err(e) => warn!("Failed to load metrics history for anomaly detection: {}", e),
+        }
+    }
+    
+    loop {
+        interval.tick().await;
         
-        // Attendre un peu pour que le serveur soit prêt
-        tokio::time::sleep(Duration::from_secs(5)).await;
+        let system_metrics = store.refresh_system();
+        let network_usage = network.sample(config.status.network_capacity_mbps);
+        let runtime_usage = runtime.sample();
+        let requests = status_codes.snapshot();
+        let interval_requests = requests.since(&last_requests);
+        last_requests = requests;
+        let cache_stats = caches.stats().await;
+        let interval_caches = cache_stats.iter().map(|stats| stats.since(&last_caches)).collect();
+        last_caches = cache_stats;
         
-        loop {
-            interval.tick().await;
+        // Faire des vraies requêtes HTTP vers notre API (une trace par passage)
+        let metrics = TraceContext::new_root()
+            .scope(calculate_metrics_via_direct_system_calls(&db, &config, system_metrics, network_usage, interval_requests, runtime_usage, interval_caches))
+            .await;
+        if let Ok(metrics) = &metrics {
+            // Mettre à jour le cache et prévenir les pages ouvertes
+            store.publish(metrics.clone());
+            let detected = anomalies.observe(metrics);
+            alerts.set_anomalies(detected.clone());
+            // Notifications suspendues pendant une maintenance planifiée
+            match active_window(db.get_pool(), &config.maintenance, Utc::now()).await {
+                Ok(window) => alerts.set_maintenance(window),
+                Err(e) => warn!("Failed to load maintenance windows: {}", e),
+            }
+            alerts.process(metrics).await;
+            
+            // Créer une HistoryEntry à partir des métriques
+            let history_entry = HistoryEntry {
+                timestamp: metrics.timestamp,
+                response_time_ms: metrics.response_time_ms,
+                db_connected: metrics.db_connected,
+                db_response_time_ms: metrics.db_response_time_ms,
+                status: metrics.status.clone(),
+                issues: generate_issues(
+                    metrics.db_connected,
+                    metrics.db_response_time_ms,
+                    metrics.response_time_ms,
+                    metrics.cpu_usage,
+                    metrics.memory_usage_percent,
+                    metrics.disk_usage_percent,
+                    metrics.network,
+                    &detected,
+                ),
+                network: metrics.network,
+            };
             
-            // Faire des vraies requêtes HTTP vers notre API
-            if let Ok(metrics) = calculate_metrics_via_direct_system_calls(&config).await {
-                // Mettre à jour le cache global
-                {
-                    let mut cached = LATEST_CACHED_METRICS.lock().unwrap();
-                    *cached = Some(metrics.clone());
-                }
-                
-                add_performance_metrics(metrics.clone());
-                
-                // Créer une HistoryEntry à partir des métriques
-                let history_entry = HistoryEntry {
-                    timestamp: metrics.timestamp,
-                    response_time_ms: metrics.response_time_ms,
-                    db_connected: metrics.db_connected,
-                    db_response_time_ms: metrics.db_response_time_ms,
-                    status: metrics.status.clone(),
-                    issues: generate_issues(
-                        metrics.db_connected,
-                        metrics.db_response_time_ms,
-                        metrics.response_time_ms,
-                        metrics.cpu_usage,
-                        metrics.memory_usage_percent,
-                        metrics.disk_usage_percent,
-                    ),
-                };
-                
-                // Ajouter à l'historique
-                add_history_entry(history_entry);
+            // Ajouter à l'historique ; la tâche planifiée `history_pruning` purge les entrées expirées
+            let pool = db.get_pool();
+            if config.monitoring.snapshot_max_age_hours > 0
+                && let Err(e) = save_snapshot(pool, metrics).await
+            {
+                warn!("Failed to save metrics snapshot: {}", e);
+            }
+            if let Err(e) = record_history(pool, &history_entry).await {
+                warn!("Failed to record metrics history: {}", e);
             }
         }
-    });
+        
+        // Sondes des dépendances externes, même si le calcul des métriques a échoué
+        if !config.monitoring.targets.is_empty() {
+            let pool = db.get_pool();
+            let checks = TraceContext::new_root().scope(monitor.probe_all()).await;
+            if let Err(e) = record_checks(pool, &checks).await {
+                warn!("Failed to record monitored targets: {}", e);
+            }
+        }
+        
+        // État des autres instances du déploiement
+        if cluster.is_enabled() {
+            TraceContext::new_root().scope(cluster.poll_all()).await;
+        }
+        
+        // Requêtes SQL les plus coûteuses, pour relier la lenteur de l'API à la base
+        if queries.is_enabled()
+            && let Err(e) = queries.refresh(db.get_pool()).await
+        {
+            warn!("Failed to collect pg_stat_statements: {}", e);
+        }
+        
+        task.record(metrics.map(drop), period);
+    }
 }
 
-/// Obtient l'URL de base du serveur depuis la configuration
-fn get_server_base_url(config: &Config) -> String {
-    format!("http://{}", config.server_address())
+/// URL appelée pour mesurer le temps de réponse : `[monitoring] ping_url`, ou le ping de cette instance
+pub(crate) fn ping_url(config: &Config) -> String {
+    config
+        .monitoring
+        .ping_url
+        .clone()
+        .unwrap_or_else(|| format!("http://{}/api/help/ping", config.server_address()))
 }
 
 /// Calcule les métriques via des calculs système directs (pas d'appels HTTP)
-async fn calculate_metrics_via_direct_system_calls(config: &Config) -> Result<PerformanceMetrics, Box<dyn std::error::Error + Send + Sync>> {
-    // Calculer les métriques système directement avec la fonction optimisée
-    let system_metrics = get_system_metrics_optimized();
-    
+async fn calculate_metrics_via_direct_system_calls(
+    db: &DatabaseManager,
+    config: &Config,
+    system_metrics: SystemMetrics,
+    network: NetworkUsage,
+    requests: RequestCounts,
+    runtime: RuntimeUsage,
+    caches: Vec<CacheStats>,
+) -> Result<PerformanceMetrics, Box<dyn std::error::Error + Send + Sync>> {
     // Test de connectivité simple avec un ping HTTP rapide
     let client = reqwest::Client::new();
-    let base_url = get_server_base_url(config);
-    
     let ping_start = std::time::Instant::now();
-    let ping_response = client
-        .get(format!("{}/api/help/ping", base_url))
+    let ping_response = inject(client.get(ping_url(config)))
         .timeout(Duration::from_secs(3))
         .send()
         .await;
@@ -147,20 +814,21 @@ async fn calculate_metrics_via_direct_system_calls(config: &Config) -> Result<Pe
     };
     
     // Test DB simple (juste un ping, pas de calculs lourds)
-    let (db_connected, db_response_time_ms) = test_db_connectivity().await;
+    let (db_connected, db_response_time_ms) = test_db_connectivity(db, config).await;
     
     // Calculer les scores
     let cpu_score = calculate_cpu_score(system_metrics.cpu_usage);
     let memory_score = calculate_memory_score(system_metrics.memory_usage_percent);
     let perf_score = calculate_performance_score(response_time_ms);
-    let network_score = calculate_network_score();
-    let health_score = cpu_score + memory_score + perf_score + network_score;
+    let network_score = calculate_network_score(&network);
+    // Les erreurs applicatives (5xx) font baisser le score, quel que soit l'état du système
+    let health_score = (cpu_score + memory_score + perf_score + network_score).saturating_sub(calculate_error_penalty(&requests));
     
     // Status général
     let status = if ping_success && db_connected {
         if response_time_ms < 100 { "Optimal" } else { "Stable" }
     } else {
-        "Dégradé"
+        DEGRADED_STATUS
     }.to_string();
     
     Ok(PerformanceMetrics {
@@ -170,6 +838,10 @@ async fn calculate_metrics_via_direct_system_calls(config: &Config) -> Result<Pe
         memory_score,
         perf_score,
         network_score,
+        network: Some(network),
+        requests: Some(requests),
+        runtime: Some(runtime),
+        caches,
         avg_response_time: response_time_ms as f64,
         system_load: calculate_system_load_from_values(
             system_metrics.cpu_usage, 
@@ -190,198 +862,160 @@ async fn calculate_metrics_via_direct_system_calls(config: &Config) -> Result<Pe
         db_response_time_ms,
         status,
         
-        // Optimisation: 30 secondes minimum entre recalculs
-        minimal_waittime: 30,
+        // Durée de fraîcheur du cache (`[monitoring] cache_ttl_seconds`)
+        minimal_waittime: config.monitoring.cache_ttl_seconds,
     })
 }
 
-/// Collecte des métriques système (optimisée) - copiée depuis handlers/help.rs
-fn get_system_metrics_optimized() -> SystemMetrics {
-    // Utiliser new() d'abord pour les CPU
-    let mut sys = System::new();
-    
-    // Premier refresh pour initialiser
-    sys.refresh_cpu_usage();
-    
-    // Attendre l'intervalle minimum requis pour les CPU
-    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-    
-    // Deuxième refresh pour obtenir les vraies données CPU
-    sys.refresh_cpu_usage();
-    sys.refresh_memory(); // Refresh mémoire aussi
-    
-    // CPU usage avec la méthode recommandée
-    let cpu_usage = if !sys.cpus().is_empty() {
-        let total: f32 = sys.cpus().iter()
-            .map(|cpu| cpu.cpu_usage())
-            .sum();
-        total / sys.cpus().len() as f32
-    } else {
-        0.0
-    };
-    
-    let cpu_count = sys.cpus().len().max(1);
-    
-    // Mémoire
-    let memory_used = sys.used_memory() / 1024 / 1024; // Convert to MB
-    let memory_total = sys.total_memory() / 1024 / 1024; // Convert to MB
-    let memory_usage_percent = if memory_total > 0 {
-        (memory_used as f32 / memory_total as f32) * 100.0
-    } else {
-        0.0
-    };
-    
-    // Disques
-    let disks = Disks::new_with_refreshed_list();
-    let disk_usage_percent = if let Some(disk) = disks.first() {
-        let total = disk.total_space();
-        let available = disk.available_space();
-        if total > 0 {
-            let used = total - available;
-            (used as f32 / total as f32) * 100.0
-        } else {
-            0.0
-        }
-    } else {
-        0.0
-    };
-    
-    // Log pour debug (temporaire)
-    println!("Debug CPU: individual_cores=[{}], average={:.1}%", 
-             sys.cpus().iter()
-                .map(|cpu| format!("{:.1}", cpu.cpu_usage()))
-                .collect::<Vec<_>>()
-                .join(", "),
-             cpu_usage);
-    
-    SystemMetrics {
-        cpu_usage,
-        cpu_count,
-        memory_used_mb: memory_used,
-        memory_total_mb: memory_total,
-        memory_usage_percent,
-        disk_usage_percent,
-        uptime: System::uptime(),
-    }
-}
-
-/// Test simple de connectivité DB
-async fn test_db_connectivity() -> (bool, Option<u64>) {
-    // TODO: Implémenter un vrai test de DB si nécessaire
-    // Pour l'instant, on simule une DB connectée avec temps de réponse réaliste
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    Utc::now().timestamp().hash(&mut hasher);
-    let db_time = (hasher.finish() % 50) + 5; // Entre 5ms et 55ms
-    
-    (true, Some(db_time))
-}
-
-/// Calcule la charge système à partir des valeurs individuelles
-fn calculate_system_load_from_values(cpu_usage: f32, memory_usage: f32, disk_usage: f32) -> f64 {
-    let cpu_load = cpu_usage / 100.0;
-    let memory_load = memory_usage / 100.0;
-    let disk_load = disk_usage / 100.0;
-    
-    // Moyenne pondérée : CPU 40%, Mémoire 40%, Disque 20%
-    (cpu_load * 0.4 + memory_load * 0.4 + disk_load * 0.2) as f64
+/// Instances `sysinfo` de longue durée : chaque mesure rafraîchit seulement les CPU,
+/// la mémoire et l'espace des disques déjà connus, sans attente bloquante
+#[derive(Debug)]
+struct SystemSampler {
+    sys: System,
+    disks: Disks,
+    /// Points de montage retenus ; tous si vide
+    mount_points: Vec<String>,
+    /// Détail par coeur de la dernière mesure
+    cpu: CpuUsage,
+    /// Dernière mesure, servie aux handlers entre deux passages
+    latest: Option<SystemMetrics>,
 }
 
-/// Ajoute une entrée d'historique directement
-fn add_history_entry(entry: HistoryEntry) {
-    let mut history = METRICS_HISTORY.lock().unwrap();
-    
-    // Vérifier si assez de temps s'est écoulé depuis la dernière entrée
-    if let Some(last_entry) = history.back() {
-        let time_diff = entry.timestamp.signed_duration_since(last_entry.timestamp);
-        if time_diff.num_seconds() < HISTORY_INTERVAL_SECONDS {
-            return; // Pas assez de temps écoulé
+impl SystemSampler {
+    fn new() -> Self {
+        Self {
+            sys: System::new(),
+            disks: Disks::new(),
+            mount_points: Vec::new(),
+            cpu: CpuUsage::default(),
+            latest: None,
         }
     }
-    
-    // Si on atteint la limite, on supprime la plus ancienne entrée
-    if history.len() >= MAX_HISTORY_SIZE {
-        history.pop_front();
-    }
-    
-    history.push_back(entry);
-}
 
-/// Ajoute les métriques de performance à la file
-fn add_performance_metrics(metrics: PerformanceMetrics) {
-    let mut queue = PERFORMANCE_QUEUE.lock().unwrap();
-    
-    if queue.len() >= PERFORMANCE_QUEUE_SIZE {
-        queue.pop_front();
+    /// Mesure l'usage CPU depuis le rafraîchissement précédent, la mémoire et chaque point de montage
+    fn sample(&mut self) -> SystemMetrics {
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory();
+        // Relit la liste des montages sans reconstruire les disques déjà connus
+        self.disks.refresh_specifics(true, DiskRefreshKind::nothing().with_storage());
+
+        // Usage par coeur et moyenne des coeurs
+        let cpus = self.sys.cpus();
+        let cores: Vec<CpuCoreUsage> = cpus
+            .iter()
+            .map(|cpu| CpuCoreUsage { name: cpu.name().to_string(), usage_percent: cpu.cpu_usage() })
+            .collect();
+        let cpu_usage = if cores.is_empty() {
+            0.0
+        } else {
+            cores.iter().map(|core| core.usage_percent).sum::<f32>() / cores.len() as f32
+        };
+        let cpu_count = cpus.len().max(1);
+        self.cpu = CpuUsage {
+            average_percent: cpu_usage,
+            max_percent: cores.iter().map(|core| core.usage_percent).fold(0.0, f32::max),
+            cores,
+        };
+
+        // Mémoire
+        let memory_used = self.sys.used_memory() / 1024 / 1024; // Convert to MB
+        let memory_total = self.sys.total_memory() / 1024 / 1024; // Convert to MB
+        let memory_usage_percent = if memory_total > 0 {
+            (memory_used as f32 / memory_total as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        // Disques : un point de montage peut apparaître plusieurs fois (bind mounts)
+        let mut disks: Vec<DiskUsage> = Vec::new();
+        for disk in self.disks.list() {
+            let mount_point = disk.mount_point().to_string_lossy().into_owned();
+            let wanted = self.mount_points.is_empty() || self.mount_points.contains(&mount_point);
+            if !wanted || disk.total_space() == 0 || disks.iter().any(|known| known.mount_point == mount_point) {
+                continue;
+            }
+            disks.push(DiskUsage {
+                usage_percent: disk.total_space().saturating_sub(disk.available_space()) as f32 / disk.total_space() as f32 * 100.0,
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                total_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+                mount_point,
+            });
+        }
+        // Le montage le plus plein : les données peuvent ne pas être sur le premier
+        let disk_usage_percent = disks.iter().map(|disk| disk.usage_percent).fold(0.0, f32::max);
+
+        let metrics = SystemMetrics {
+            cpu_usage,
+            cpu_count,
+            memory_used_mb: memory_used,
+            memory_total_mb: memory_total,
+            memory_usage_percent,
+            disk_usage_percent,
+            disks,
+            uptime: System::uptime(),
+        };
+        self.latest = Some(metrics.clone());
+        metrics
     }
-    
-    queue.push_back(metrics);
 }
 
-/// Récupère les dernières métriques de performance depuis le cache global
-pub fn get_latest_performance_metrics() -> Option<PerformanceMetrics> {
-    let cached = LATEST_CACHED_METRICS.lock().unwrap();
-    cached.clone()
+/// Compteurs réseau des interfaces (hors boucle locale) entre deux passages
+struct NetworkSampler {
+    networks: Networks,
+    last_refresh: std::time::Instant,
 }
 
-/// Vérifie si on peut utiliser le cache ou si on doit recalculer
-pub fn should_use_cache() -> bool {
-    if let Some(metrics) = get_latest_performance_metrics() {
-        let now = Utc::now();
-        let time_diff = now.signed_duration_since(metrics.timestamp);
-        time_diff.num_seconds() < metrics.minimal_waittime as i64
-    } else {
-        false
+impl NetworkSampler {
+    fn new() -> Self {
+        Self { networks: Networks::new_with_refreshed_list(), last_refresh: std::time::Instant::now() }
     }
-}
 
-/// Récupère les métriques avec fallback intelligent
-pub fn get_metrics_with_fallback() -> Option<PerformanceMetrics> {
-    if should_use_cache() {
-        get_latest_performance_metrics()
-    } else {
-        // Si le cache est trop vieux, retourner quand même les dernières valeurs
-        // Le background task va les mettre à jour
-        get_latest_performance_metrics()
+    /// Trafic depuis le passage précédent
+    fn sample(&mut self, capacity_mbps: u64) -> NetworkUsage {
+        self.networks.refresh(true);
+        let elapsed = self.last_refresh.elapsed();
+        self.last_refresh = std::time::Instant::now();
+
+        let (mut rx, mut tx, mut errors) = (0, 0, 0);
+        for (_, data) in self.networks.iter().filter(|(name, _)| !matches!(name.as_str(), "lo" | "lo0")) {
+            rx += data.received();
+            tx += data.transmitted();
+            errors += data.errors_on_received() + data.errors_on_transmitted();
+        }
+        NetworkUsage::from_deltas(rx, tx, errors, elapsed, capacity_mbps)
     }
 }
 
-/// Récupère toutes les métriques de performance
-pub fn get_performance_queue() -> Vec<PerformanceMetrics> {
-    let queue = PERFORMANCE_QUEUE.lock().unwrap();
-    queue.iter().cloned().collect()
-}
+/// Test de connectivité DB : exécute `SELECT 1` et mesure son temps de réponse.
+///
+/// La requête est bornée par `[health] check_timeout_ms` ; au-delà, ou en cas
+/// d'erreur, la base est considérée déconnectée.
+async fn test_db_connectivity(db: &DatabaseManager, config: &Config) -> (bool, Option<u64>) {
+    let start = std::time::Instant::now();
+    let timeout = Duration::from_millis(config.health.check_timeout_ms);
 
-/// Calcule le temps de réponse moyen
-fn get_average_response_time() -> f64 {
-    let history = METRICS_HISTORY.lock().unwrap();
-    if history.is_empty() {
-        return 0.0;
+    match tokio::time::timeout(timeout, sqlx::query("SELECT 1").execute(db.get_pool())).await {
+        Ok(Ok(_)) => (true, Some(start.elapsed().as_millis() as u64)),
+        Ok(Err(e)) => {
+            warn!("Database connectivity check failed: {}", e);
+            (false, None)
+        }
+        Err(_) => {
+            warn!("Database connectivity check timed out after {} ms", timeout.as_millis());
+            (false, None)
+        }
     }
-    
-    let total: u64 = history.iter().map(|entry| entry.response_time_ms).sum();
-    total as f64 / history.len() as f64
-}
-
-/// Récupérer l'historique complet
-pub fn get_history() -> Vec<HistoryEntry> {
-    let history = METRICS_HISTORY.lock().unwrap();
-    history.iter().cloned().collect()
 }
 
-/// Récupérer les dernières N entrées
-pub fn get_recent_history(count: usize) -> Vec<HistoryEntry> {
-    let history = METRICS_HISTORY.lock().unwrap();
-    history.iter()
-        .rev()
-        .take(count)
-        .cloned()
-        .collect::<Vec<_>>()
-        .into_iter()
-        .rev()
-        .collect()
+/// Calcule la charge système à partir des valeurs individuelles
+fn calculate_system_load_from_values(cpu_usage: f32, memory_usage: f32, disk_usage: f32) -> f64 {
+    let cpu_load = cpu_usage / 100.0;
+    let memory_load = memory_usage / 100.0;
+    let disk_load = disk_usage / 100.0;
+    
+    // Moyenne pondérée : CPU 40%, Mémoire 40%, Disque 20%
+    (cpu_load * 0.4 + memory_load * 0.4 + disk_load * 0.2) as f64
 }
 
 /// Détermine la couleur du status en fonction des métriques
@@ -406,6 +1040,7 @@ pub fn determine_status_color(entry: &HistoryEntry) -> &'static str {
 }
 
 /// Génère la liste des problèmes basée sur les métriques
+#[allow(clippy::too_many_arguments)]
 pub fn generate_issues(
     db_connected: bool,
     db_response_time_ms: Option<u64>,
@@ -413,15 +1048,17 @@ pub fn generate_issues(
     cpu_usage: f32,
     memory_usage_percent: f32,
     disk_usage_percent: f32,
+    network: Option<NetworkUsage>,
+    anomalies: &[Anomaly],
 ) -> Vec<String> {
     let mut issues = Vec::new();
     
     if !db_connected {
         issues.push("Base de données déconnectée".to_string());
-    } else if let Some(db_time) = db_response_time_ms {
-        if db_time > 500 {
-            issues.push(format!("DB lente: {} ms", db_time));
-        }
+    } else if let Some(db_time) = db_response_time_ms
+        && db_time > 500
+    {
+        issues.push(format!("DB lente: {} ms", db_time));
     }
     
     if response_time_ms > 1000 {
@@ -448,6 +1085,18 @@ pub fn generate_issues(
         issues.push(format!("Disque presque plein: {:.1}%", disk_usage_percent));
     }
     
+    if let Some(network) = network {
+        if network.load_percent > 80.0 {
+            issues.push(format!("Réseau saturé: {:.1}%", network.load_percent));
+        }
+        if network.errors > 0 {
+            issues.push(format!("Erreurs réseau: {}", network.errors));
+        }
+    }
+    
+    // Dégradations repérées avant que les seuils fixes ne soient atteints
+    issues.extend(anomalies.iter().map(Anomaly::describe));
+    
     if issues.is_empty() {
         issues.push("Aucun problème détecté".to_string());
     }
@@ -455,7 +1104,8 @@ pub fn generate_issues(
     issues
 }
 
-fn calculate_cpu_score(cpu_usage: f32) -> u8 {
+/// Score CPU (sur 25) selon l'usage moyen des coeurs
+pub fn calculate_cpu_score(cpu_usage: f32) -> u8 {
     match cpu_usage {
         x if x < 30.0 => 25,
         x if x < 50.0 => 20,
@@ -466,7 +1116,8 @@ fn calculate_cpu_score(cpu_usage: f32) -> u8 {
     }
 }
 
-fn calculate_memory_score(memory_usage: f32) -> u8 {
+/// Score mémoire (sur 25) selon la part de mémoire utilisée
+pub fn calculate_memory_score(memory_usage: f32) -> u8 {
     match memory_usage {
         x if x < 40.0 => 25,
         x if x < 60.0 => 20,
@@ -477,7 +1128,8 @@ fn calculate_memory_score(memory_usage: f32) -> u8 {
     }
 }
 
-fn calculate_performance_score(response_time: u64) -> u8 {
+/// Score de performance (sur 25) selon le temps de réponse mesuré, en millisecondes
+pub fn calculate_performance_score(response_time: u64) -> u8 {
     match response_time {
         x if x < 50 => 25,
         x if x < 100 => 20,
@@ -488,21 +1140,33 @@ fn calculate_performance_score(response_time: u64) -> u8 {
     }
 }
 
-fn calculate_network_score() -> u8 {
-    // Simulation basée sur une charge réseau fictive
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    chrono::Utc::now().timestamp().hash(&mut hasher);
-    let pseudo_random = (hasher.finish() % 100) as f32;
-    
-    match pseudo_random {
-        x if x < 70.0 => 25, // 70% chance d'avoir un bon score
-        x if x < 85.0 => 20,
-        x if x < 95.0 => 15,
-        x if x < 98.0 => 10,
+/// Score réseau : charge du lien, pénalisée en cas d'erreurs d'interface
+pub fn calculate_network_score(network: &NetworkUsage) -> u8 {
+    let score: u8 = match network.load_percent {
+        x if x < 40.0 => 25,
+        x if x < 60.0 => 20,
+        x if x < 80.0 => 15,
+        x if x < 95.0 => 10,
         _ => 5,
+    };
+    if network.errors > 0 { score.saturating_sub(10) } else { score }
+}
+
+/// Points retirés du score de santé selon la part de réponses 5xx de l'intervalle.
+///
+/// En dessous de `ERROR_RATE_MIN_REQUESTS` réponses, le taux n'est pas significatif
+/// et le score n'est pas pénalisé.
+pub fn calculate_error_penalty(requests: &RequestCounts) -> u8 {
+    if requests.total() < ERROR_RATE_MIN_REQUESTS {
+        return 0;
+    }
+    match requests.server_error_percent() {
+        x if x < 1.0 => 0,
+        x if x < 2.0 => 5,
+        x if x < 5.0 => 15,
+        x if x < 10.0 => 30,
+        x if x < 25.0 => 45,
+        _ => 60,
     }
 }
 
@@ -0,0 +1,38 @@
+//! # Uploads Models Module
+//!
+//! Ce module contient les métadonnées des fichiers envoyés.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::id::Id;
+use crate::extractors::fields::FieldSpec;
+
+/// Identifiant d'un fichier envoyé
+pub type UploadId = Id<Upload, Uuid>;
+
+/// Fichier envoyé (le contenu est dans le stockage, sous `storage_key`)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Upload {
+    #[schema(value_type = Uuid)]
+    pub id: UploadId,
+    /// Nom du fichier tel qu'envoyé par le client
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Empreinte SHA-256 du contenu (hexadécimal)
+    pub sha256: String,
+    #[serde(skip)]
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Attributs sélectionnables via `?fields=` sur les fichiers
+pub struct UploadFields;
+
+impl FieldSpec for UploadFields {
+    const FIELDS: &'static [&'static str] = &["id", "filename", "content_type", "size_bytes", "sha256", "created_at"];
+}
@@ -0,0 +1,84 @@
+//! # User Models Module
+//!
+//! Ce module contient la ressource d'exemple `user` : le modèle lu en base,
+//! les corps de création et de modification, et les champs utilisables
+//! dans la query string des listes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::id::Id;
+use crate::{
+    extractors::{fields::FieldSpec, query::QuerySpec},
+    sanitize::{self, Sanitize},
+};
+
+/// Identifiant d'un utilisateur
+pub type UserId = Id<User>;
+
+/// Utilisateur enregistré dans la table `users`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct User {
+    #[schema(value_type = i64)]
+    pub id: UserId,
+    pub email: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Requête de création d'un utilisateur
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct NewUser {
+    #[validate(email(message = "must be a valid email"), length(max = 255, message = "must be at most 255 characters"))]
+    pub email: String,
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub name: String,
+}
+
+impl Sanitize for NewUser {
+    fn sanitize(&mut self) {
+        self.email = sanitize::remove_control_chars(&self.email).trim().to_string();
+        self.name = sanitize::plain_text(&self.name);
+    }
+}
+
+/// Modification partielle d'un utilisateur : les champs absents sont conservés
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateUser {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(email(message = "must be a valid email"), length(max = 255, message = "must be at most 255 characters"))]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 255, message = "must be between 1 and 255 characters"))]
+    pub name: Option<String>,
+}
+
+impl Sanitize for UpdateUser {
+    fn sanitize(&mut self) {
+        if let Some(email) = &mut self.email {
+            *email = sanitize::remove_control_chars(email).trim().to_string();
+        }
+        if let Some(name) = &mut self.name {
+            *name = sanitize::plain_text(name);
+        }
+    }
+}
+
+/// Champs utilisables dans `sort`, `search` et `fields` sur la liste des utilisateurs
+pub struct UserFields;
+
+impl QuerySpec for UserFields {
+    const FILTERS: &'static [(&'static str, &'static str)] = &[("email", "email")];
+    const SORTS: &'static [(&'static str, &'static str)] =
+        &[("name", "name"), ("email", "email"), ("created_at", "created_at")];
+    const SEARCH: &'static [&'static str] = &["name", "email"];
+    const DEFAULT_SORT: &'static str = "-created_at";
+}
+
+impl FieldSpec for UserFields {
+    const FIELDS: &'static [&'static str] = &["id", "email", "name", "created_at", "updated_at"];
+}
@@ -0,0 +1,52 @@
+//! # Webhooks Models Module
+//!
+//! Ce module contient les payloads typés des webhooks entrants
+//! ainsi que les structures des abonnements et livraisons sortantes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+// Payload d'exemple, vous pouvez le supprimer
+#[derive(Debug, Deserialize)]
+pub struct ExampleEvent {
+    pub event: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// Abonnement d'un système externe à nos événements
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: i32,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Livraison d'un événement vers un abonnement
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub subscription_id: i32,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Corps JSON envoyé aux abonnés
+#[derive(Debug, Serialize)]
+pub struct OutboundWebhookBody<'a> {
+    pub id: i64,
+    pub event: &'a str,
+    pub data: &'a serde_json::Value,
+}
@@ -0,0 +1,206 @@
+//! # Redis Module
+//!
+//! Ce module (feature `redis`) fournit un backend partagé entre les instances, configuré
+//! sous `[redis]` : cache, limitation de débit ou sessions qui doivent survivre à une
+//! instance et être vus par toutes.
+//! - `RedisManager` est créé au démarrage si `[redis] url` est défini (`AppState::redis`) ;
+//!   la connexion est ouverte au premier appel puis rétablie automatiquement
+//! - `get` / `set` lisent et écrivent des valeurs typées, sérialisées en JSON, avec une
+//!   durée de vie (`[redis] default_ttl_seconds` sans durée explicite)
+//! - `publish` / `subscribe` diffusent des messages typés entre instances
+//! - la vérification non critique `redis` de `/api/help/health` mesure un `PING`
+//!
+//! Toutes les clés et tous les canaux reçoivent le préfixe `[redis] key_prefix`.
+//!
+//! ```ignore
+//! if let Some(redis) = &state.redis {
+//!     redis.set("users:42", &user, Some(Duration::from_secs(60))).await?;
+//!     let cached: Option<User> = redis.get("users:42").await?;
+//!     redis.publish("users.updated", &user.id).await?;
+//! }
+//! ```
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use redis::{
+    aio::{ConnectionManager, ConnectionManagerConfig},
+    AsyncCommands, Client,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use tokio::sync::OnceCell;
+
+use crate::{
+    config::RedisConfig,
+    services::health::{HealthCheck, Probe},
+};
+
+/// Erreurs des opérations Redis
+#[derive(Debug, Error)]
+pub enum RedisError {
+    /// Le serveur n'a pas répondu dans le délai de connexion
+    #[error("redis connection timed out after {0:?}")]
+    Timeout(Duration),
+    /// Erreur de connexion ou de commande
+    #[error("redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    /// La valeur ne correspond pas au type demandé
+    #[error("JSON serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Client Redis partagé, détenu par l'état de l'application.
+///
+/// Le clone est peu coûteux : tous les clones partagent la même connexion multiplexée.
+#[derive(Clone)]
+pub struct RedisManager {
+    client: Client,
+    connection: Arc<OnceCell<ConnectionManager>>,
+    prefix: String,
+    default_ttl: Duration,
+    connect_timeout: Duration,
+    response_timeout: Duration,
+}
+
+impl RedisManager {
+    /// Crée le client si `[redis] url` est défini, sans se connecter.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<RedisManager>, RedisError>` - `None` sans URL, erreur si l'URL est invalide
+    pub fn new(config: &RedisConfig) -> Result<Option<Self>, RedisError> {
+        let Some(url) = &config.url else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            client: Client::open(url.as_str())?,
+            connection: Arc::new(OnceCell::new()),
+            prefix: config.key_prefix.clone(),
+            default_ttl: Duration::from_secs(config.default_ttl_seconds),
+            connect_timeout: Duration::from_millis(config.connect_timeout_ms),
+            response_timeout: Duration::from_millis(config.response_timeout_ms),
+        }))
+    }
+
+    /// Clé ou canal avec le préfixe `[redis] key_prefix`
+    pub fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Connexion partagée, ouverte au premier appel
+    async fn connection(&self) -> Result<ConnectionManager, RedisError> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| async {
+                let config = ConnectionManagerConfig::new()
+                    .set_connection_timeout(self.connect_timeout)
+                    .set_response_timeout(self.response_timeout)
+                    .set_number_of_retries(1);
+                tokio::time::timeout(self.connect_timeout, self.client.get_connection_manager_with_config(config))
+                    .await
+                    .map_err(|_| RedisError::Timeout(self.connect_timeout))?
+                    .map_err(RedisError::from)
+            })
+            .await?;
+        Ok(connection.clone())
+    }
+
+    /// Vérifie que le serveur répond
+    pub async fn ping(&self) -> Result<(), RedisError> {
+        let mut connection = self.connection().await?;
+        redis::cmd("PING").query_async::<String>(&mut connection).await?;
+        Ok(())
+    }
+
+    /// Lit une valeur ; `None` si la clé n'existe pas ou a expiré.
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, RedisError> {
+        let mut connection = self.connection().await?;
+        let value: Option<Vec<u8>> = connection.get(self.key(key)).await?;
+        Ok(value.map(|value| serde_json::from_slice(&value)).transpose()?)
+    }
+
+    /// Écrit une valeur, qui expire après `ttl` (`[redis] default_ttl_seconds` sans valeur).
+    pub async fn set<T: Serialize>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<(), RedisError> {
+        let value = serde_json::to_vec(value)?;
+        let ttl = ttl.unwrap_or(self.default_ttl).as_millis().max(1) as u64;
+        let mut connection = self.connection().await?;
+        connection.pset_ex::<_, _, ()>(self.key(key), value, ttl).await?;
+        Ok(())
+    }
+
+    /// Supprime une clé ; retourne `true` si elle existait.
+    pub async fn delete(&self, key: &str) -> Result<bool, RedisError> {
+        let mut connection = self.connection().await?;
+        let deleted: usize = connection.del(self.key(key)).await?;
+        Ok(deleted > 0)
+    }
+
+    /// Durée de vie restante d'une clé ; `None` si elle n'existe pas ou n'expire pas.
+    pub async fn ttl(&self, key: &str) -> Result<Option<Duration>, RedisError> {
+        let mut connection = self.connection().await?;
+        let millis: i64 = connection.pttl(self.key(key)).await?;
+        Ok((millis >= 0).then(|| Duration::from_millis(millis as u64)))
+    }
+
+    /// Diffuse un message sur un canal ; retourne le nombre d'abonnés qui l'ont reçu.
+    pub async fn publish<T: Serialize>(&self, channel: &str, message: &T) -> Result<usize, RedisError> {
+        let message = serde_json::to_vec(message)?;
+        let mut connection = self.connection().await?;
+        Ok(connection.publish(self.key(channel), message).await?)
+    }
+
+    /// S'abonne à un canal, sur une connexion dédiée fermée quand le flux est abandonné.
+    ///
+    /// Un message qui ne correspond pas au type demandé est transmis comme une erreur,
+    /// sans interrompre le flux.
+    pub async fn subscribe<T: DeserializeOwned + Send + 'static>(
+        &self,
+        channel: &str,
+    ) -> Result<BoxStream<'static, Result<T, RedisError>>, RedisError> {
+        let mut pubsub = tokio::time::timeout(self.connect_timeout, self.client.get_async_pubsub())
+            .await
+            .map_err(|_| RedisError::Timeout(self.connect_timeout))??;
+        pubsub.subscribe(self.key(channel)).await?;
+
+        Ok(pubsub
+            .into_on_message()
+            .map(|message| serde_json::from_slice(message.get_payload_bytes()).map_err(RedisError::from))
+            .boxed())
+    }
+}
+
+/// Serveur Redis ; non critique : les appelants doivent pouvoir se passer du cache partagé.
+pub struct RedisCheck {
+    redis: RedisManager,
+}
+
+impl RedisCheck {
+    pub fn new(redis: RedisManager) -> Self {
+        Self { redis }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for RedisCheck {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Probe {
+        let start = Instant::now();
+        match self.redis.ping().await {
+            Ok(()) => Probe::healthy().details(json!({ "response_time_ms": start.elapsed().as_millis() as u64 })),
+            Err(e) => Probe::degraded(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! # Reporting Module
+//!
+//! Ce module envoie les erreurs à Sentry (ou à un service compatible, comme GlitchTip)
+//! lorsque `[telemetry.sentry] dsn` est défini :
+//! - les panics, dans les handlers comme dans les tâches de fond (hook de panic)
+//! - les erreurs 5xx de `AppError`, avec leur code d'erreur et l'identifiant de trace
+//! - les passages en échec des tâches de fond supervisées
+//!
+//! Chaque événement porte la release `template-axum-sqlx-api@<CARGO_PKG_VERSION>` et
+//! l'environnement de `[server] environment`. Sans DSN, les fonctions de capture ne
+//! font rien.
+
+use sentry::{types::Dsn, ClientInitGuard, ClientOptions, Level};
+use std::error::Error;
+use tracing::{info, warn};
+
+use crate::{config::Config, middleware::trace::TraceContext};
+
+/// Démarre le client Sentry ; `None` si aucun DSN n'est configuré ou s'il est invalide.
+///
+/// Le client reste actif tant que la garde est conservée ; sa destruction envoie les
+/// derniers événements.
+pub fn init(config: &Config) -> Option<ClientInitGuard> {
+    let settings = &config.telemetry.sentry;
+    let dsn = settings.dsn.as_deref().filter(|dsn| !dsn.trim().is_empty())?;
+    let dsn = match dsn.parse::<Dsn>() {
+        Ok(dsn) => dsn,
+        Err(e) => {
+            warn!("Error reporting disabled, invalid Sentry DSN: {}", e);
+            return None;
+        }
+    };
+
+    info!("Reporting errors to Sentry ({})", dsn.host());
+    Some(sentry::init(ClientOptions {
+        dsn: Some(dsn),
+        release: sentry::release_name!(),
+        environment: Some(config.server.environment.as_str().into()),
+        sample_rate: settings.sample_rate.clamp(0.0, 1.0),
+        ..Default::default()
+    }))
+}
+
+/// Signale une erreur renvoyée en 5xx, avec son code et la trace de la requête
+pub fn capture_server_error<E: Error + ?Sized>(error: &E, code: &str) {
+    sentry::with_scope(
+        |scope| {
+            scope.set_tag("error.code", code);
+            if let Some(context) = TraceContext::current() {
+                scope.set_tag("trace_id", context.trace_id_hex());
+            }
+        },
+        || sentry::capture_error(error),
+    );
+}
+
+/// Signale l'échec d'un passage d'une tâche de fond supervisée
+pub fn capture_task_failure(task: &str, error: &str) {
+    sentry::with_scope(
+        |scope| scope.set_tag("task", task),
+        || sentry::capture_message(&format!("Background task {} failed: {}", task, error), Level::Error),
+    );
+}
@@ -0,0 +1,49 @@
+//! # Admin Routes Module
+//!
+//! Ce module configure les routes d'administration, toutes protégées
+//! par le middleware `require_admin`.
+
+use axum::{middleware::from_fn_with_state, routing::{delete, get, post}, Router};
+use crate::{
+    handlers::admin,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes d'administration
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/routes.json", get(admin::routes_sitemap))
+        .route("/admin/readiness", get(admin::get_readiness).put(admin::set_readiness))
+        .route("/admin/cors-origins", get(admin::list_cors_origins).post(admin::create_cors_origin))
+        .route("/admin/cors-origins/{id}", delete(admin::delete_cors_origin))
+        .route("/admin/allocator", get(admin::allocator_stats))
+        .route("/admin/allocator/heap-profile", post(admin::heap_profile))
+        .route("/admin/queries", get(admin::query_insights))
+        .route_layer(from_fn_with_state(state.clone(), require_admin))
+}
+
+/// Entrées du registre pour les routes d'administration
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/routes.json", "Registre des routes exposées par l'API")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/admin/readiness", "État du drapeau de readiness")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("PUT", "/api/admin/readiness", "Retire ou réintègre l'instance du load balancer")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/admin/cors-origins", "Liste des origines CORS dynamiques")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/cors-origins", "Enregistre une origine CORS dynamique")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("DELETE", "/api/admin/cors-origins/{id}", "Supprime une origine CORS dynamique")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/admin/allocator", "Statistiques de l'allocateur mémoire")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/allocator/heap-profile", "Profil du tas (feature `jemalloc`)")
+            .auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/admin/queries", "Requêtes SQL les plus coûteuses (`pg_stat_statements`)")
+            .auth(AuthRequirement::Admin),
+    ]
+}
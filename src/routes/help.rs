@@ -2,14 +2,60 @@
 //!
 //! Ce module configure les routes d'aide et de diagnostic de l'API.
 
-use axum::{routing::get, Router};
-use crate::{db::DatabaseManager, handlers::help};
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{get, post},
+    Router,
+};
+use crate::{
+    handlers::help,
+    middleware::{admin::require_admin, coalesce::coalesce},
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
 
 /// Créer le routeur pour les routes d'aide
-pub fn router() -> Router<DatabaseManager> {
+pub fn router(state: &AppState) -> Router<AppState> {
+    // Les logs peuvent contenir des informations sensibles et l'auto-test écrit en base :
+    // réservés à l'administration
+    let protected = Router::new()
+        .route("/help/logs", get(help::logs))
+        .route("/help/selftest", post(help::selftest))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
     Router::new()
-        .route("/help/health", get(help::health_check))
+        // Le health check complet est coûteux : les appels simultanés sont regroupés
+        .route(
+            "/help/health",
+            get(help::health_check).route_layer(from_fn_with_state(state.coalescer.clone(), coalesce)),
+        )
         .route("/help/health-light", get(help::health_light))
+        .route("/help/errors", get(help::error_codes))
         .route("/help/info", get(help::info))
         .route("/help/ping", get(help::ping))
-} 
\ No newline at end of file
+        .route("/help/live", get(help::live))
+        .route("/help/startup", get(help::startup))
+        .route("/help/ready", get(help::ready))
+        .route("/help/slow-endpoints", get(help::slow_endpoints))
+        .route("/help/system/cpu", get(help::system_cpu))
+        .merge(protected)
+}
+
+/// Entrées du registre pour les routes d'aide
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/help/health", "Vérification complète de l'état de santé du système"),
+        RouteInfo::new("GET", "/api/help/health-light", "Vérification rapide (DB + performance seulement)"),
+        RouteInfo::new("GET", "/api/help/errors", "Catalogue des codes d'erreur"),
+        RouteInfo::new("GET", "/api/help/info", "Informations sur l'API"),
+        RouteInfo::new("GET", "/api/help/ping", "Test de connectivité simple"),
+        RouteInfo::new("GET", "/api/help/live", "Sonde de liveness (processus en vie)"),
+        RouteInfo::new("GET", "/api/help/startup", "Sonde de startup (initialisation terminée)"),
+        RouteInfo::new("GET", "/api/help/ready", "Sonde de readiness (base, migrations, tâches de fond)"),
+        RouteInfo::new("GET", "/api/help/slow-endpoints", "Requêtes les plus lentes de la dernière heure"),
+        RouteInfo::new("GET", "/api/help/system/cpu", "Usage CPU par coeur"),
+        RouteInfo::new("GET", "/api/help/logs", "Derniers logs de l'instance").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/help/selftest", "Auto-test après déploiement (base, cache, tâches, HTTP sortant)")
+            .auth(AuthRequirement::Admin),
+    ]
+}
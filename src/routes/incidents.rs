@@ -0,0 +1,44 @@
+//! # Incidents Routes Module
+//!
+//! Ce module configure les routes des incidents : consultation publique
+//! (`/incidents`), gestion réservée à l'administration (`/admin/incidents`).
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{get, patch, post},
+    Router,
+};
+use crate::{
+    handlers::incidents,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes des incidents
+pub fn router(state: &AppState) -> Router<AppState> {
+    let protected = Router::new()
+        .route("/admin/incidents", post(incidents::create_incident))
+        .route("/admin/incidents/{id}", patch(incidents::update_incident).delete(incidents::delete_incident))
+        .route("/admin/incidents/{id}/updates", post(incidents::add_update))
+        .route("/admin/incidents/{id}/resolve", post(incidents::resolve_incident))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
+    Router::new()
+        .route("/incidents", get(incidents::list_incidents))
+        .route("/incidents/{id}", get(incidents::get_incident))
+        .merge(protected)
+}
+
+/// Entrées du registre pour les routes des incidents
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/incidents", "Liste des incidents"),
+        RouteInfo::new("GET", "/api/incidents/{id}", "Incident et ses mises à jour"),
+        RouteInfo::new("POST", "/api/admin/incidents", "Ouverture d'un incident").auth(AuthRequirement::Admin),
+        RouteInfo::new("PATCH", "/api/admin/incidents/{id}", "Modification d'un incident").auth(AuthRequirement::Admin),
+        RouteInfo::new("DELETE", "/api/admin/incidents/{id}", "Suppression d'un incident").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/incidents/{id}/updates", "Mise à jour d'un incident").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/incidents/{id}/resolve", "Résolution d'un incident").auth(AuthRequirement::Admin),
+    ]
+}
@@ -0,0 +1,40 @@
+//! # Jobs Routes Module
+//!
+//! Ce module configure les routes des tâches asynchrones. La soumission générique,
+//! la liste des tâches (dead-letter par défaut) et leur relance sont réservées à
+//! l'administration ; le suivi se fait par identifiant (UUID non devinable), pour que
+//! tout handler puisse renvoyer vers `/api/jobs/{id}`.
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{get, post},
+    Router,
+};
+use crate::{
+    handlers::jobs,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes de tâches
+pub fn router(state: &AppState) -> Router<AppState> {
+    let protected = Router::new()
+        .route("/admin/jobs", get(jobs::list_jobs).post(jobs::submit_job))
+        .route("/admin/jobs/{id}/retry", post(jobs::retry_job))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
+    Router::new()
+        .route("/jobs/{id}", get(jobs::get_job))
+        .merge(protected)
+}
+
+/// Entrées du registre pour les routes de tâches
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/admin/jobs", "Liste des tâches asynchrones (dead-letter par défaut)").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/jobs", "Soumission d'une tâche asynchrone").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/admin/jobs/{id}/retry", "Relance d'une tâche en échec").auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/jobs/{id}", "État d'une tâche asynchrone"),
+    ]
+}
@@ -0,0 +1,38 @@
+//! # Maintenance Routes Module
+//!
+//! Ce module configure les routes des fenêtres de maintenance : consultation
+//! publique (`/maintenance`), planification réservée à l'administration
+//! (`/admin/maintenance`).
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{delete, get, post},
+    Router,
+};
+use crate::{
+    handlers::maintenance,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes de maintenance
+pub fn router(state: &AppState) -> Router<AppState> {
+    let protected = Router::new()
+        .route("/admin/maintenance", post(maintenance::create_window))
+        .route("/admin/maintenance/{id}", delete(maintenance::delete_window))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
+    Router::new()
+        .route("/maintenance", get(maintenance::list_windows))
+        .merge(protected)
+}
+
+/// Entrées du registre pour les routes de maintenance
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/maintenance", "Fenêtres de maintenance en cours et à venir"),
+        RouteInfo::new("POST", "/api/admin/maintenance", "Planification d'une maintenance").auth(AuthRequirement::Admin),
+        RouteInfo::new("DELETE", "/api/admin/maintenance/{id}", "Annulation d'une maintenance").auth(AuthRequirement::Admin),
+    ]
+}
@@ -9,32 +9,144 @@
 //! Pour ajouter de nouvelles routes :
 //! 1. Créez un nouveau module dans le dossier `routes/`
 //! 2. Implémentez une fonction `router()` qui retourne un `Router`
-//! 3. Ajoutez le module dans ce fichier
-//! 4. Utilisez `merge()` pour combiner les routes
+//! 3. Implémentez une fonction `routes()` qui décrit ces routes pour le registre
+//! 4. Ajoutez le module dans ce fichier
+//! 5. Utilisez `merge()` pour combiner les routes et complétez `route_registry()`
 
-use crate::db::DatabaseManager;
-use axum::{routing::get, Router};
+use crate::{
+    middleware::{
+        cache::cache_responses, casing::json_casing, debug_trace::debug_trace, latency::record_latency,
+        status_codes::count_status_classes,
+    },
+    models::routes::RouteInfo,
+    state::AppState,
+};
+use axum::{middleware::from_fn_with_state, routing::get, Router};
 use utoipa_swagger_ui::SwaggerUi;
-use utoipa::OpenApi;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
 
 // Re-export all route modules here
+pub mod admin;
 pub mod help;
+pub mod incidents;
+pub mod jobs;
+pub mod maintenance;
+pub mod post;
+pub mod search;
+pub mod status;
+pub mod uploads;
+pub mod user;
+pub mod webhooks;
 
 #[derive(OpenApi)]
 #[openapi(paths(crate::handlers::help::health_check, crate::handlers::help::health_light,
-                crate::handlers::help::info, crate::handlers::help::ping))]
+                crate::handlers::help::error_codes, crate::handlers::help::info,
+                crate::handlers::help::ping, crate::handlers::help::live,
+                crate::handlers::help::startup, crate::handlers::help::ready,
+                crate::handlers::help::logs, crate::handlers::help::slow_endpoints,
+                crate::handlers::help::system_cpu, crate::handlers::help::selftest,
+                crate::handlers::status::status, crate::handlers::status::history,
+                crate::handlers::status::endpoints, crate::handlers::status::targets,
+                crate::handlers::status::cluster,
+                crate::handlers::status::badge, crate::handlers::status::badge_svg,
+                crate::handlers::status::live,
+                crate::handlers::status::events, crate::handlers::status::export_events,
+                crate::handlers::webhooks::receive, crate::handlers::admin::routes_sitemap,
+                crate::handlers::admin::get_readiness, crate::handlers::admin::set_readiness,
+                crate::handlers::admin::list_cors_origins, crate::handlers::admin::create_cors_origin,
+                crate::handlers::admin::delete_cors_origin,
+                crate::handlers::admin::allocator_stats, crate::handlers::admin::heap_profile,
+                crate::handlers::admin::query_insights,
+                crate::handlers::uploads::upload, crate::handlers::uploads::get_upload,
+                crate::handlers::uploads::download, crate::handlers::uploads::delete_upload,
+                crate::handlers::user::list_users, crate::handlers::user::get_user,
+                crate::handlers::user::create_user, crate::handlers::user::update_user,
+                crate::handlers::user::delete_user, crate::handlers::user::batch_create_users,
+                crate::handlers::user::batch_update_users, crate::handlers::user::batch_delete_users,
+                crate::handlers::post::list_user_posts, crate::handlers::post::create_post,
+                crate::handlers::post::get_post, crate::handlers::post::delete_post,
+                crate::handlers::post::list_comments, crate::handlers::post::create_comment,
+                crate::handlers::search::search,
+                crate::handlers::jobs::get_job, crate::handlers::jobs::submit_job,
+                crate::handlers::jobs::list_jobs, crate::handlers::jobs::retry_job,
+                crate::handlers::incidents::list_incidents, crate::handlers::incidents::get_incident,
+                crate::handlers::incidents::create_incident, crate::handlers::incidents::update_incident,
+                crate::handlers::incidents::delete_incident, crate::handlers::incidents::add_update,
+                crate::handlers::incidents::resolve_incident,
+                crate::handlers::maintenance::list_windows, crate::handlers::maintenance::create_window,
+                crate::handlers::maintenance::delete_window),
+          modifiers(&SecurityAddon))]
 struct ApiDoc;
 
-pub fn create_router(db: DatabaseManager) -> Router {
+/// Déclare le schéma d'authentification des routes d'administration
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Page de status principale à la racine
         .route("/", get(crate::handlers::status::status_page))
+        // Badge de status à intégrer dans un README ou un tableau de bord
+        .route("/status/badge.svg", get(crate::handlers::status::badge_svg))
         // Routes API
-        .nest("/api", help::router())
+        .nest("/api", help::router(&state))
+        .nest("/api", status::router(&state))
+        .nest("/api", webhooks::router())
+        .nest("/api", admin::router(&state))
+        .nest("/api", uploads::router(&state))
+        .nest("/api", user::router(&state))
+        .nest("/api", post::router(&state))
+        .nest("/api", search::router(&state))
+        .nest("/api", jobs::router(&state))
+        .nest("/api", incidents::router(&state))
+        .nest("/api", maintenance::router(&state))
+        // Cache des réponses pour les routes listées dans `[cache.routes]`
+        .layer(from_fn_with_state(state.response_cache.clone(), cache_responses))
+        // Nommage des champs JSON (`[api] json_case`), appliqué autour du cache
+        .layer(from_fn_with_state(state.config.clone(), json_casing))
+        // Latence par route, mesurée au plus près du client
+        .layer(from_fn_with_state(state.latency.clone(), record_latency))
         .merge(SwaggerUi::new("/api/swagger").url("/api-doc/openapi.json", ApiDoc::openapi()))
+        // Réponses par classe de statut, y compris les requêtes sans route
+        .layer(from_fn_with_state(state.status_codes.clone(), count_status_classes))
+        // Logs de niveau trace pour une requête portant `X-Debug-Trace`
+        .layer(from_fn_with_state(state.config.clone(), debug_trace))
         // Add your other route modules here
         // Example:
-        // .nest("/api", user::router())
-        // .nest("/api", product::router())
-        .with_state(db)
+        // .nest("/api", product::router(&state))
+        .with_state(state)
+}
+
+/// Registre de toutes les routes exposées par l'API
+pub fn route_registry() -> Vec<RouteInfo> {
+    let mut registry = vec![
+        RouteInfo::new("GET", "/", "Page de status"),
+        RouteInfo::new("GET", "/status/badge.svg", "Badge SVG du status"),
+        RouteInfo::new("GET", "/api/swagger", "Documentation Swagger UI"),
+        RouteInfo::new("GET", "/api-doc/openapi.json", "Spécification OpenAPI"),
+    ];
+    registry.extend(help::routes());
+    registry.extend(status::routes());
+    registry.extend(webhooks::routes());
+    registry.extend(admin::routes());
+    registry.extend(uploads::routes());
+    registry.extend(user::routes());
+    registry.extend(post::routes());
+    registry.extend(search::routes());
+    registry.extend(jobs::routes());
+    registry.extend(incidents::routes());
+    registry.extend(maintenance::routes());
+    registry
 }
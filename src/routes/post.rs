@@ -0,0 +1,38 @@
+//! # Post Routes Module
+//!
+//! Ce module configure les routes de la ressource d'exemple `post` : les posts
+//! sont créés et listés sous leur auteur (`/users/{id}/posts`), puis adressés
+//! directement par leur identifiant (`/posts/{id}`), commentaires compris.
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::get,
+    Router,
+};
+use crate::{
+    handlers::post,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes des posts
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/users/{id}/posts", get(post::list_user_posts).post(post::create_post))
+        .route("/posts/{id}", get(post::get_post).delete(post::delete_post))
+        .route("/posts/{id}/comments", get(post::list_comments).post(post::create_comment))
+        .route_layer(from_fn_with_state(state.clone(), require_admin))
+}
+
+/// Entrées du registre pour les routes des posts
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/users/{id}/posts", "Posts d'un utilisateur").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/users/{id}/posts", "Création d'un post").auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/posts/{id}", "Post avec auteur et commentaires").auth(AuthRequirement::Admin),
+        RouteInfo::new("DELETE", "/api/posts/{id}", "Suppression d'un post et de ses commentaires").auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/posts/{id}/comments", "Commentaires d'un post").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/posts/{id}/comments", "Ajout d'un commentaire").auth(AuthRequirement::Admin),
+    ]
+}
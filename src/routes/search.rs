@@ -0,0 +1,28 @@
+//! # Search Routes Module
+//!
+//! Ce module configure la route de recherche plein texte. Elle parcourt les posts,
+//! réservés à l'administration : la recherche l'est aussi.
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::get,
+    Router,
+};
+use crate::{
+    handlers::search,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour la recherche
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/search", get(search::search))
+        .route_layer(from_fn_with_state(state.clone(), require_admin))
+}
+
+/// Entrées du registre pour la recherche
+pub fn routes() -> Vec<RouteInfo> {
+    vec![RouteInfo::new("GET", "/api/search", "Recherche plein texte dans les posts").auth(AuthRequirement::Admin)]
+}
@@ -0,0 +1,38 @@
+//! # Status Routes Module
+//!
+//! Ce module configure les routes JSON du sous-système de status.
+
+use axum::{middleware::from_fn_with_state, routing::get, Router};
+use crate::{handlers::status, middleware::coalesce::coalesce, models::routes::RouteInfo, state::AppState};
+
+/// Créer le routeur pour les routes de status
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/status", get(status::status))
+        .route(
+            "/status/events",
+            get(status::events).route_layer(from_fn_with_state(state.coalescer.clone(), coalesce)),
+        )
+        .route("/status/events/export", get(status::export_events))
+        .route("/status/history", get(status::history))
+        .route("/status/endpoints", get(status::endpoints))
+        .route("/status/targets", get(status::targets))
+        .route("/status/cluster", get(status::cluster))
+        .route("/status/badge", get(status::badge))
+        .route("/status/live", get(status::live))
+}
+
+/// Entrées du registre pour les routes de status
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/status", "Métriques de status (JSON)"),
+        RouteInfo::new("GET", "/api/status/events", "Timeline des événements applicatifs"),
+        RouteInfo::new("GET", "/api/status/events/export", "Export NDJSON/CSV de la timeline"),
+        RouteInfo::new("GET", "/api/status/history", "Historique des métriques"),
+        RouteInfo::new("GET", "/api/status/endpoints", "Latence par route"),
+        RouteInfo::new("GET", "/api/status/targets", "État des dépendances externes"),
+        RouteInfo::new("GET", "/api/status/cluster", "État agrégé des instances du cluster"),
+        RouteInfo::new("GET", "/api/status/badge", "Badge de status au format shields.io"),
+        RouteInfo::new("GET", "/api/status/live", "Flux SSE des mises à jour des métriques"),
+    ]
+}
@@ -0,0 +1,40 @@
+//! # Uploads Routes Module
+//!
+//! Ce module configure les routes d'envoi et de téléchargement de fichiers.
+//! L'envoi et la suppression sont réservés à l'administration ; la lecture
+//! se fait par identifiant (UUID non devinable).
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{delete, get, post},
+    Router,
+};
+use crate::{
+    handlers::uploads,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes de fichiers
+pub fn router(state: &AppState) -> Router<AppState> {
+    let protected = Router::new()
+        .route("/uploads", post(uploads::upload))
+        .route("/uploads/{id}", delete(uploads::delete_upload))
+        .route_layer(from_fn_with_state(state.clone(), require_admin));
+
+    Router::new()
+        .route("/uploads/{id}", get(uploads::get_upload))
+        .route("/uploads/{id}/download", get(uploads::download))
+        .merge(protected)
+}
+
+/// Entrées du registre pour les routes de fichiers
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("POST", "/api/uploads", "Envoi d'un fichier (multipart)").auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/uploads/{id}", "Métadonnées d'un fichier"),
+        RouteInfo::new("GET", "/api/uploads/{id}/download", "Téléchargement d'un fichier"),
+        RouteInfo::new("DELETE", "/api/uploads/{id}", "Suppression d'un fichier").auth(AuthRequirement::Admin),
+    ]
+}
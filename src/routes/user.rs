@@ -0,0 +1,45 @@
+//! # User Routes Module
+//!
+//! Ce module configure les routes de la ressource d'exemple `user`.
+//! Les utilisateurs contiennent des données personnelles : toutes les routes
+//! sont réservées à l'administration.
+
+use axum::{
+    middleware::from_fn_with_state,
+    routing::{get, post},
+    Router,
+};
+use crate::{
+    handlers::user,
+    middleware::admin::require_admin,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les routes utilisateurs
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/users", get(user::list_users).post(user::create_user))
+        .route("/users:batchCreate", post(user::batch_create_users))
+        .route("/users:batchUpdate", post(user::batch_update_users))
+        .route("/users:batchDelete", post(user::batch_delete_users))
+        .route(
+            "/users/{id}",
+            get(user::get_user).patch(user::update_user).delete(user::delete_user),
+        )
+        .route_layer(from_fn_with_state(state.clone(), require_admin))
+}
+
+/// Entrées du registre pour les routes utilisateurs
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("GET", "/api/users", "Liste paginée des utilisateurs").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/users", "Création d'un utilisateur").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/users:batchCreate", "Création groupée d'utilisateurs").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/users:batchUpdate", "Modification groupée d'utilisateurs").auth(AuthRequirement::Admin),
+        RouteInfo::new("POST", "/api/users:batchDelete", "Suppression groupée d'utilisateurs").auth(AuthRequirement::Admin),
+        RouteInfo::new("GET", "/api/users/{id}", "Détail d'un utilisateur").auth(AuthRequirement::Admin),
+        RouteInfo::new("PATCH", "/api/users/{id}", "Modification d'un utilisateur").auth(AuthRequirement::Admin),
+        RouteInfo::new("DELETE", "/api/users/{id}", "Suppression d'un utilisateur").auth(AuthRequirement::Admin),
+    ]
+}
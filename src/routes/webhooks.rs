@@ -0,0 +1,24 @@
+//! # Webhooks Routes Module
+//!
+//! Ce module configure la route de réception des webhooks entrants.
+
+use axum::{routing::post, Router};
+use crate::{
+    handlers::webhooks,
+    models::routes::{AuthRequirement, RouteInfo},
+    state::AppState,
+};
+
+/// Créer le routeur pour les webhooks entrants
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/webhooks/{provider}", post(webhooks::receive))
+}
+
+/// Entrées du registre pour les webhooks entrants
+pub fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo::new("POST", "/api/webhooks/{provider}", "Réception d'un webhook signé")
+            .auth(AuthRequirement::WebhookSignature),
+    ]
+}
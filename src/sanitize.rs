@@ -0,0 +1,122 @@
+//! # Sanitize Module
+//!
+//! Ce module regroupe le nettoyage des textes fournis par les clients, avant
+//! validation et stockage :
+//! - `strip_html` retire les balises, pour les champs en texte brut
+//! - `escape_html` neutralise un texte inséré dans une page HTML (page de status)
+//! - `remove_control_chars` retire les caractères de contrôle et de direction
+//!   (retours arrière, séquences d'échappement, surcharges bidirectionnelles)
+//! - `truncate` borne la longueur d'un texte sans couper un caractère
+//!
+//! `ValidatedJson` appelle `Sanitize::sanitize` sur le corps reçu avant de le
+//! valider : les longueurs maximales sont donc vérifiées sur le texte nettoyé.
+//!
+//! ```ignore
+//! impl Sanitize for NewPost {
+//!     fn sanitize(&mut self) {
+//!         self.title = sanitize::plain_text(&self.title);
+//!     }
+//! }
+//! ```
+
+/// Nettoyage d'un corps de requête avant validation.
+///
+/// L'implémentation par défaut ne modifie rien, pour les corps sans texte libre.
+pub trait Sanitize {
+    fn sanitize(&mut self) {}
+}
+
+impl<T: Sanitize> Sanitize for Option<T> {
+    fn sanitize(&mut self) {
+        if let Some(value) = self {
+            value.sanitize();
+        }
+    }
+}
+
+impl<T: Sanitize> Sanitize for Vec<T> {
+    fn sanitize(&mut self) {
+        self.iter_mut().for_each(Sanitize::sanitize);
+    }
+}
+
+/// Texte brut d'une ligne ou d'un paragraphe : sans balises ni caractères de contrôle, sans espaces autour
+pub fn plain_text(value: &str) -> String {
+    strip_html(&remove_control_chars(value)).trim().to_string()
+}
+
+/// Retire les balises HTML (`<b>`, `</p>`, `<!-- -->`, `<?xml ?>`...) ; le texte entre les balises est conservé.
+///
+/// Le contenu des éléments `script` et `style` est retiré avec eux. Un `<` qui n'ouvre
+/// pas de balise (`a < b`) est conservé : le texte doit encore être échappé à l'affichage.
+pub fn strip_html(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        let opens_tag = tag[1..].starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        let Some(end) = tag.find('>').filter(|_| opens_tag) else {
+            output.push('<');
+            rest = &tag[1..];
+            continue;
+        };
+
+        rest = &tag[end + 1..];
+        let name = tag_name(&tag[1..end]);
+        if name == "script" || name == "style" {
+            // Le contenu est retiré jusqu'à la balise fermante, ou jusqu'à la fin du texte
+            let closing = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&closing) {
+                Some(position) => rest[position..].find('>').map_or("", |end| &rest[position + end + 1..]),
+                None => "",
+            };
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Nom d'une balise ouvrante, en minuscules (`"script"` pour `<SCRIPT src=...>`)
+fn tag_name(tag: &str) -> String {
+    tag.chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Échappe les caractères spéciaux HTML d'un texte inséré dans une page.
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Retire les caractères de contrôle, sauf le saut de ligne et la tabulation, et les
+/// caractères de formatage invisibles (surcharges bidirectionnelles, espaces de largeur nulle).
+pub fn remove_control_chars(value: &str) -> String {
+    value
+        .chars()
+        .filter(|&c| match c {
+            '\n' | '\t' => true,
+            '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}' => false,
+            c => !c.is_control(),
+        })
+        .collect()
+}
+
+/// Tronque un texte à `max_chars` caractères ; `…` remplace la fin retirée.
+pub fn truncate(value: &str, max_chars: usize) -> String {
+    match value.char_indices().nth(max_chars) {
+        None => value.to_string(),
+        Some(_) if max_chars == 0 => String::new(),
+        Some(_) => {
+            let (cut, _) = value.char_indices().nth(max_chars - 1).expect("max_chars is in bounds");
+            format!("{}…", &value[..cut])
+        }
+    }
+}
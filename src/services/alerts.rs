@@ -0,0 +1,399 @@
+//! # Alerts Service
+//!
+//! Ce module évalue chaque nouvelle mesure de la tâche des métriques contre les règles
+//! de `[[alerts.rules]]` et notifie les destinations de `[[alerts.notifiers]]` :
+//! - une règle passe en alerte (`firing`) après `debounce_samples` mesures consécutives
+//!   en échec, et revient à la normale (`resolved`) après autant de mesures en succès
+//! - une notification n'est envoyée qu'aux changements d'état, pas à chaque mesure
+//! - les règles `anomaly` suivent les anomalies de la mesure (voir `services::anomalies`)
+//! - Slack et Discord reçoivent un message texte, un endpoint `webhook` l'alerte en JSON
+//! - les règles avec `page = true` déclenchent aussi un appel PagerDuty (Events API v2)
+//!   ou Opsgenie, dédupliqué par règle et clos au retour à la normale ; la gravité de
+//!   l'appel suit le score de santé, relevée par la `severity` de la règle
+//! - `[alerts.email]` envoie un email d'alerte ou de retour à la normale par SMTP, pour
+//!   les règles dont la `severity` atteint `min_severity`
+//! - pendant une fenêtre de maintenance (voir `services::maintenance`), les règles ne sont
+//!   pas évaluées : une condition toujours vraie après la fenêtre déclenche une alerte
+//!   après l'anti-rebond habituel
+//!
+//! Un échec d'envoi est journalisé sans nouvelle tentative : la mesure suivante
+//! n'émet rien tant que l'état ne change pas.
+
+use chrono::{DateTime, Utc};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::HashMap, time::Duration};
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::{
+        AlertCondition, AlertNotifier, AlertRule, AlertSeverity, AlertsConfig, EmailAlertsConfig, NotifierKind,
+        SmtpTls,
+    },
+    middleware::trace::inject,
+    models::{
+        maintenance::MaintenanceWindow,
+        status::{Anomaly, PerformanceMetrics},
+    },
+};
+
+/// Transition d'une règle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// Alerte notifiée lors d'un changement d'état
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub rule: String,
+    pub severity: AlertSeverity,
+    pub state: AlertState,
+    pub message: String,
+    /// Score de santé de la mesure ayant changé l'état
+    pub health_score: u8,
+    pub timestamp: DateTime<Utc>,
+    /// La règle déclenche un appel d'astreinte
+    #[serde(skip)]
+    pub page: bool,
+}
+
+/// Gravité d'un appel d'astreinte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PageSeverity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl PageSeverity {
+    /// Gravité PagerDuty (`payload.severity`)
+    pub fn pagerduty(self) -> &'static str {
+        match self {
+            PageSeverity::Info => "info",
+            PageSeverity::Warning => "warning",
+            PageSeverity::Error => "error",
+            PageSeverity::Critical => "critical",
+        }
+    }
+
+    /// Priorité Opsgenie
+    pub fn opsgenie(self) -> &'static str {
+        match self {
+            PageSeverity::Info => "P4",
+            PageSeverity::Warning => "P3",
+            PageSeverity::Error => "P2",
+            PageSeverity::Critical => "P1",
+        }
+    }
+}
+
+impl Alert {
+    /// Message texte des notifications Slack et Discord
+    pub fn text(&self) -> String {
+        match self.state {
+            AlertState::Firing => format!("🔴 [{}] {}", self.rule, self.message),
+            AlertState::Resolved => format!("✅ [{}] Resolved: {}", self.rule, self.message),
+        }
+    }
+
+    /// Clé de déduplication : un seul appel ouvert par règle, clos au retour à la normale
+    pub fn dedup_key(&self) -> String {
+        format!("{}:{}", env!("CARGO_PKG_NAME"), self.rule)
+    }
+
+    /// Gravité de l'appel : selon le score de santé, au moins celle de la règle
+    pub fn page_severity(&self) -> PageSeverity {
+        let from_score = match self.health_score {
+            x if x < 40 => PageSeverity::Critical,
+            x if x < 60 => PageSeverity::Error,
+            x if x < 80 => PageSeverity::Warning,
+            _ => PageSeverity::Info,
+        };
+        let from_rule = match self.severity {
+            AlertSeverity::Info => PageSeverity::Info,
+            AlertSeverity::Warning => PageSeverity::Warning,
+            AlertSeverity::Critical => PageSeverity::Critical,
+        };
+        from_score.max(from_rule)
+    }
+
+    /// Objet et corps de l'email d'alerte ou de retour à la normale
+    pub fn email(&self, subject_prefix: &str) -> (String, String) {
+        let timestamp = self.timestamp.format("%Y-%m-%d %H:%M:%S UTC");
+        match self.state {
+            AlertState::Firing => (
+                format!("{} [{}] {}: {}", subject_prefix, self.severity.as_str().to_uppercase(), self.rule, self.message),
+                format!(
+                    "Alert {} is firing.\n\nSeverity: {}\nProblem: {}\nSince: {}\n\nYou will receive another email when it resolves.\n",
+                    self.rule, self.severity.as_str(), self.message, timestamp
+                ),
+            ),
+            AlertState::Resolved => (
+                format!("{} [RESOLVED] {}", subject_prefix, self.rule),
+                format!(
+                    "Alert {} is resolved.\n\nSeverity: {}\nLast problem: {}\nResolved at: {}\n",
+                    self.rule, self.severity.as_str(), self.message, timestamp
+                ),
+            ),
+        }
+    }
+}
+
+impl AlertSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// Envoi des alertes par SMTP (`[alerts.email]`)
+struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    recipients: Vec<Mailbox>,
+    min_severity: AlertSeverity,
+    subject_prefix: String,
+}
+
+impl EmailNotifier {
+    fn new(settings: &EmailAlertsConfig, timeout: Duration) -> Result<Self, String> {
+        let builder = match settings.tls {
+            SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host).map_err(|e| e.to_string())?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host).map_err(|e| e.to_string())?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host),
+        };
+        let builder = builder.port(settings.port).timeout(Some(timeout));
+        let builder = match (&settings.username, &settings.password) {
+            (Some(username), Some(password)) => builder.credentials(Credentials::new(username.clone(), password.clone())),
+            _ => builder,
+        };
+
+        let parse = |address: &str| address.parse::<Mailbox>().map_err(|e| format!("invalid address {}: {}", address, e));
+        Ok(Self {
+            transport: builder.build(),
+            from: parse(&settings.from)?,
+            recipients: settings.recipients.iter().map(|address| parse(address)).collect::<Result<_, _>>()?,
+            min_severity: settings.min_severity,
+            subject_prefix: settings.subject_prefix.clone(),
+        })
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let (subject, body) = alert.email(&self.subject_prefix);
+        let mut message = Message::builder().from(self.from.clone()).subject(subject).header(ContentType::TEXT_PLAIN);
+        for recipient in &self.recipients {
+            message = message.to(recipient.clone());
+        }
+        let message = message.body(body).map_err(|e| e.to_string())?;
+
+        self.transport.send(message).await.map(drop).map_err(|e| e.to_string())
+    }
+}
+
+impl AlertCondition {
+    /// Description du problème si la mesure ne respecte pas la condition
+    pub fn check(&self, metrics: &PerformanceMetrics, anomalies: &[Anomaly]) -> Option<String> {
+        match *self {
+            AlertCondition::DbDown if !metrics.db_connected => Some("Database is unreachable".to_string()),
+            AlertCondition::HealthScoreBelow { threshold } if metrics.health_score < threshold => {
+                Some(format!("Health score is {} (below {})", metrics.health_score, threshold))
+            }
+            AlertCondition::ResponseTimeAbove { threshold_ms } if metrics.response_time_ms > threshold_ms => {
+                Some(format!("API response time is {} ms (above {} ms)", metrics.response_time_ms, threshold_ms))
+            }
+            AlertCondition::Anomaly if !anomalies.is_empty() => Some(
+                anomalies.iter().map(Anomaly::describe).collect::<Vec<_>>().join("; "),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// État d'une règle entre deux mesures
+#[derive(Debug, Default)]
+struct RuleState {
+    firing: bool,
+    /// Mesures consécutives contredisant l'état courant
+    streak: u32,
+    /// Dernier problème constaté, repris dans la notification de retour à la normale
+    message: String,
+}
+
+/// Moteur d'alertes, détenu par la tâche des métriques.
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    notifiers: Vec<AlertNotifier>,
+    debounce_samples: u32,
+    states: HashMap<String, RuleState>,
+    client: Client,
+    email: Option<EmailNotifier>,
+    /// Fenêtre de maintenance en cours, pendant laquelle rien n'est notifié
+    maintenance: Option<MaintenanceWindow>,
+    /// Anomalies de la mesure à évaluer, pour les règles `anomaly`
+    anomalies: Vec<Anomaly>,
+}
+
+impl AlertEngine {
+    pub fn new(settings: &AlertsConfig) -> Self {
+        let timeout = Duration::from_secs(settings.timeout_seconds);
+        let client = Client::builder().timeout(timeout).build().unwrap_or_default();
+        // Une configuration SMTP invalide désactive les emails sans empêcher les autres notifications
+        let email = settings.email.as_ref().filter(|email| !email.recipients.is_empty()).and_then(|email| {
+            EmailNotifier::new(email, timeout)
+                .inspect_err(|e| warn!("Email alerts are disabled: {}", e))
+                .ok()
+        });
+
+        Self {
+            rules: settings.rules.clone(),
+            notifiers: settings.notifiers.clone(),
+            debounce_samples: settings.debounce_samples.max(1),
+            states: HashMap::new(),
+            client,
+            email,
+            maintenance: None,
+            anomalies: Vec::new(),
+        }
+    }
+
+    /// Évalue une mesure et retourne les règles qui changent d'état.
+    pub fn evaluate(&mut self, metrics: &PerformanceMetrics) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for rule in &self.rules {
+            let problem = rule.condition.check(metrics, &self.anomalies);
+            let state = self.states.entry(rule.name.clone()).or_default();
+            if let Some(message) = &problem {
+                state.message = message.clone();
+            }
+
+            if problem.is_some() == state.firing {
+                state.streak = 0;
+                continue;
+            }
+            state.streak += 1;
+            if state.streak < self.debounce_samples {
+                continue;
+            }
+
+            state.firing = problem.is_some();
+            state.streak = 0;
+            alerts.push(Alert {
+                rule: rule.name.clone(),
+                severity: rule.severity,
+                health_score: metrics.health_score,
+                page: rule.page,
+                state: if state.firing { AlertState::Firing } else { AlertState::Resolved },
+                message: state.message.clone(),
+                timestamp: metrics.timestamp,
+            });
+        }
+
+        alerts
+    }
+
+    /// Évalue une mesure et notifie chaque changement d'état, sauf pendant une maintenance.
+    pub async fn process(&mut self, metrics: &PerformanceMetrics) {
+        if let Some(window) = &self.maintenance {
+            debug!("Alerts muted during maintenance \"{}\"", window.title);
+            return;
+        }
+        for alert in self.evaluate(metrics) {
+            self.notify(&alert).await;
+        }
+    }
+
+    /// Anomalies de la prochaine mesure évaluée
+    pub fn set_anomalies(&mut self, anomalies: Vec<Anomaly>) {
+        self.anomalies = anomalies;
+    }
+
+    /// Suspend les notifications pendant la fenêtre de maintenance, ou les rétablit avec `None`
+    pub fn set_maintenance(&mut self, window: Option<MaintenanceWindow>) {
+        self.maintenance = window;
+    }
+
+    /// Envoie l'alerte à toutes les destinations configurées
+    pub async fn notify(&self, alert: &Alert) {
+        info!("Alert {} is {:?}: {}", alert.rule, alert.state, alert.message);
+
+        for notifier in &self.notifiers {
+            if notifier.kind.is_paging() && !alert.page {
+                continue;
+            }
+
+            let result = inject(self.request(notifier, alert))
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                warn!("Failed to send {:?} alert notification for {}: {}", notifier.kind, alert.rule, e);
+            }
+        }
+
+        if let Some(email) = &self.email
+            && alert.severity >= email.min_severity
+            && let Err(e) = email.send(alert).await
+        {
+            warn!("Failed to send alert email for {}: {}", alert.rule, e);
+        }
+    }
+
+    /// Requête de notification propre à chaque destination
+    fn request(&self, notifier: &AlertNotifier, alert: &Alert) -> RequestBuilder {
+        let endpoint = notifier.endpoint();
+        let key = notifier.key.as_deref().unwrap_or_default();
+        match (notifier.kind, alert.state) {
+            (NotifierKind::Slack, _) => self.client.post(endpoint).json(&json!({ "text": alert.text() })),
+            (NotifierKind::Discord, _) => self.client.post(endpoint).json(&json!({ "content": alert.text() })),
+            (NotifierKind::Webhook, _) => self.client.post(endpoint).json(alert),
+            (NotifierKind::Pagerduty, AlertState::Firing) => self.client.post(format!("{}/v2/enqueue", endpoint)).json(&json!({
+                "routing_key": key,
+                "event_action": "trigger",
+                "dedup_key": alert.dedup_key(),
+                "payload": {
+                    "summary": format!("[{}] {}", alert.rule, alert.message),
+                    "source": env!("CARGO_PKG_NAME"),
+                    "severity": alert.page_severity().pagerduty(),
+                    "timestamp": alert.timestamp,
+                    "custom_details": { "health_score": alert.health_score, "rule_severity": alert.severity.as_str() },
+                },
+            })),
+            (NotifierKind::Pagerduty, AlertState::Resolved) => self.client.post(format!("{}/v2/enqueue", endpoint)).json(&json!({
+                "routing_key": key,
+                "event_action": "resolve",
+                "dedup_key": alert.dedup_key(),
+            })),
+            (NotifierKind::Opsgenie, AlertState::Firing) => self
+                .client
+                .post(format!("{}/v2/alerts", endpoint))
+                .header("Authorization", format!("GenieKey {}", key))
+                .json(&json!({
+                    "message": format!("[{}] {}", alert.rule, alert.message),
+                    "alias": alert.dedup_key(),
+                    "priority": alert.page_severity().opsgenie(),
+                    "source": env!("CARGO_PKG_NAME"),
+                    "details": { "health_score": alert.health_score.to_string(), "rule_severity": alert.severity.as_str() },
+                })),
+            (NotifierKind::Opsgenie, AlertState::Resolved) => self
+                .client
+                .post(format!("{}/v2/alerts/{}/close", endpoint, alert.dedup_key()))
+                .query(&[("identifierType", "alias")])
+                .header("Authorization", format!("GenieKey {}", key))
+                .json(&json!({ "source": env!("CARGO_PKG_NAME"), "note": format!("Resolved: {}", alert.message) })),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! # Anomalies Service
+//!
+//! Ce module repère les mesures inhabituelles de la tâche des métriques, avant que
+//! les seuils fixes de `generate_issues` ou des alertes ne soient franchis :
+//! - la référence est la moyenne et l'écart type des `[anomalies] window_samples`
+//!   dernières mesures, chargées depuis `metrics_history` au démarrage de la tâche
+//! - une mesure est inhabituelle si elle dépasse la moyenne de plus de `z_threshold`
+//!   écarts types ; seules les hausses sont signalées
+//! - le taux de 5xx n'est pas conservé dans l'historique : sa référence se construit
+//!   au fil des passages, et les intervalles de moins de `ERROR_RATE_MIN_REQUESTS`
+//!   réponses sont ignorés
+//!
+//! Les anomalies sont ajoutées aux problèmes de la mesure et peuvent déclencher
+//! les règles d'alerte de type `anomaly`.
+
+use std::collections::VecDeque;
+
+use crate::{
+    config::AnomalyConfig,
+    models::status::{Anomaly, AnomalyMetric, HistoryEntry, PerformanceMetrics, ERROR_RATE_MIN_REQUESTS},
+};
+
+/// Écart type minimal du temps de réponse (ms), pour qu'une API très régulière
+/// ne signale pas chaque milliseconde d'écart
+const MIN_RESPONSE_TIME_STDDEV: f64 = 5.0;
+/// Écart type minimal du taux de 5xx (points de pourcentage)
+const MIN_ERROR_RATE_STDDEV: f64 = 1.0;
+
+/// Moyenne et écart type d'une série de mesures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Baseline {
+    pub fn from_samples<'a>(samples: impl IntoIterator<Item = &'a f64>) -> Option<Self> {
+        let samples: Vec<f64> = samples.into_iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / count;
+        Some(Self { mean, stddev: variance.sqrt() })
+    }
+
+    /// Écart de `value` à la moyenne, en nombre d'écarts types (au moins `min_stddev`)
+    pub fn z_score(&self, value: f64, min_stddev: f64) -> f64 {
+        (value - self.mean) / self.stddev.max(min_stddev)
+    }
+}
+
+/// Fenêtre glissante d'une métrique
+struct Series {
+    metric: AnomalyMetric,
+    min_stddev: f64,
+    samples: VecDeque<f64>,
+}
+
+impl Series {
+    fn new(metric: AnomalyMetric, min_stddev: f64) -> Self {
+        Self { metric, min_stddev, samples: VecDeque::new() }
+    }
+
+    fn push(&mut self, value: f64, capacity: usize) {
+        if self.samples.len() >= capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// Compare `value` à la référence, puis l'ajoute à la fenêtre
+    fn observe(&mut self, value: f64, settings: &AnomalyConfig) -> Option<Anomaly> {
+        let anomaly = (self.samples.len() >= settings.min_samples.max(2))
+            .then(|| Baseline::from_samples(&self.samples))
+            .flatten()
+            .map(|baseline| Anomaly {
+                metric: self.metric,
+                value,
+                mean: baseline.mean,
+                z_score: baseline.z_score(value, self.min_stddev),
+            })
+            .filter(|anomaly| anomaly.z_score > settings.z_threshold);
+
+        self.push(value, settings.window_samples.max(1));
+        anomaly
+    }
+}
+
+/// Détecteur détenu par la tâche des métriques.
+pub struct AnomalyDetector {
+    settings: AnomalyConfig,
+    response_time: Series,
+    error_rate: Series,
+}
+
+impl AnomalyDetector {
+    pub fn new(settings: &AnomalyConfig) -> Self {
+        Self {
+            settings: settings.clone(),
+            response_time: Series::new(AnomalyMetric::ResponseTime, MIN_RESPONSE_TIME_STDDEV),
+            error_rate: Series::new(AnomalyMetric::ErrorRate, MIN_ERROR_RATE_STDDEV),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    /// Nombre d'entrées d'historique utiles à la référence
+    pub fn window_samples(&self) -> usize {
+        self.settings.window_samples.max(1)
+    }
+
+    /// Initialise la référence du temps de réponse avec l'historique, du plus ancien au plus récent
+    pub fn seed(&mut self, history: &[HistoryEntry]) {
+        let capacity = self.window_samples();
+        for entry in history {
+            self.response_time.push(entry.response_time_ms as f64, capacity);
+        }
+    }
+
+    /// Compare une mesure aux précédentes et l'ajoute à la référence.
+    pub fn observe(&mut self, metrics: &PerformanceMetrics) -> Vec<Anomaly> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+
+        let mut anomalies = Vec::new();
+        anomalies.extend(self.response_time.observe(metrics.response_time_ms as f64, &self.settings));
+        if let Some(requests) = metrics.requests
+            && requests.total() >= ERROR_RATE_MIN_REQUESTS
+        {
+            anomalies.extend(self.error_rate.observe(requests.server_error_percent(), &self.settings));
+        }
+        anomalies
+    }
+}
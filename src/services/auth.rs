@@ -0,0 +1,86 @@
+//! # Auth Service
+//!
+//! Ce module regroupe les accès aux tables `user_credentials` et `api_keys` : hachage
+//! des mots de passe (argon2), émission des clés d'API et recherche du principal
+//! correspondant à une clé. Seule l'empreinte SHA-256 d'une clé est enregistrée.
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use sha2::{Digest, Sha256};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::models::{
+    auth::{Principal, Role},
+    user::UserId,
+};
+
+/// Préfixe des clés d'API, pour les reconnaître dans un en-tête ou un fichier
+pub const API_KEY_PREFIX: &str = "tk_";
+
+/// Hache un mot de passe au format PHC (`$argon2id$...`), avec un sel aléatoire
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())?;
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+/// Vérifie un mot de passe contre son empreinte ; une empreinte illisible ne correspond à rien
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// Nouvelle clé d'API aléatoire (`tk_` suivi de 32 caractères hexadécimaux)
+pub fn generate_api_key() -> String {
+    format!("{}{}", API_KEY_PREFIX, Uuid::new_v4().simple())
+}
+
+/// Empreinte enregistrée d'une clé d'API (SHA-256 en hexadécimal)
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Enregistre le mot de passe haché et le rôle d'un utilisateur, en remplaçant les précédents
+pub async fn set_credentials(
+    executor: impl PgExecutor<'_>,
+    user_id: UserId,
+    password_hash: &str,
+    role: Role,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO user_credentials (user_id, password_hash, role) VALUES ($1, $2, $3)
+         ON CONFLICT (user_id) DO UPDATE SET password_hash = EXCLUDED.password_hash, role = EXCLUDED.role, updated_at = now()",
+    )
+    .bind(user_id)
+    .bind(password_hash)
+    .bind(role.as_str())
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Enregistre une clé d'API d'un utilisateur, sous un nom libre (`fixtures`, `ci`...)
+pub async fn issue_api_key(executor: impl PgExecutor<'_>, user_id: UserId, name: &str, key: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO api_keys (user_id, name, key_hash) VALUES ($1, $2, $3)")
+        .bind(user_id)
+        .bind(name)
+        .bind(hash_api_key(key))
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Principal d'une clé d'API enregistrée et non expirée
+pub async fn find_principal(pool: &PgPool, key: &str) -> Result<Option<Principal>, sqlx::Error> {
+    sqlx::query_as::<_, Principal>(
+        "SELECT u.id AS user_id, u.email, c.role
+         FROM api_keys k
+         JOIN users u ON u.id = k.user_id
+         JOIN user_credentials c ON c.user_id = k.user_id
+         WHERE k.key_hash = $1 AND (k.expires_at IS NULL OR k.expires_at > now())",
+    )
+    .bind(hash_api_key(key))
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,37 @@
+//! # Batch Service
+//!
+//! Ce module fournit l'exécution des écritures groupées. Tous les éléments passent
+//! dans une même transaction, chacun dans son propre savepoint : l'échec d'un élément
+//! annule uniquement ses écritures, les autres sont validés ensemble à la fin.
+
+use sqlx::{Acquire, PgConnection, PgPool};
+
+/// Applique `op` à chaque élément et renvoie un résultat par élément, dans l'ordre.
+///
+/// `op` reçoit la connexion du savepoint de l'élément et doit l'utiliser pour toutes
+/// ses requêtes. Un élément en erreur est annulé sans interrompre le lot.
+///
+/// # Returns
+///
+/// * `Result<Vec<Result<R, E>>, sqlx::Error>` - erreur seulement si la transaction elle-même échoue
+pub async fn write_each<T, R, E>(
+    pool: &PgPool,
+    items: Vec<T>,
+    mut op: impl AsyncFnMut(&mut PgConnection, T) -> Result<R, E>,
+) -> Result<Vec<Result<R, E>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut results = Vec::with_capacity(items.len());
+
+    for item in items {
+        let mut savepoint = (&mut *tx).begin().await?;
+        let result = op(&mut savepoint, item).await;
+        match result {
+            Ok(_) => savepoint.commit().await?,
+            Err(_) => savepoint.rollback().await?,
+        }
+        results.push(result);
+    }
+
+    tx.commit().await?;
+    Ok(results)
+}
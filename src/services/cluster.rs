@@ -0,0 +1,168 @@
+//! # Cluster Service
+//!
+//! Ce module fait de la page de status un tableau de bord des instances d'un petit
+//! déploiement multi-instances, à partir de `[[monitoring.peers]]` :
+//! - à chaque passage de la tâche des métriques, le `GET /api/status` de chaque pair est
+//!   interrogé en parallèle, dans la limite de `[monitoring] timeout_ms`
+//! - le dernier résultat de chaque pair est conservé en mémoire
+//! - `GET /api/status/cluster` et la page de status affichent l'état de chaque instance,
+//!   y compris celle-ci, et un état agrégé
+//!
+//! Seuls le score de santé, le status et les incidents ouverts de la réponse des pairs
+//! sont lus.
+
+use chrono::Utc;
+use futures::future::join_all;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::{sync::Mutex, time::Duration};
+use tokio::time::Instant;
+use tracing::info;
+
+use crate::{
+    config::{MonitoringConfig, PeerNode},
+    middleware::trace::inject,
+    models::{
+        incidents::IncidentDetail,
+        status::{ClusterStatus, NodeStatus, PerformanceMetrics, ServiceStatus},
+    },
+};
+
+/// Enveloppe `ApiResponse` de la réponse d'un pair
+#[derive(Deserialize)]
+struct Envelope {
+    data: PeerSummary,
+}
+
+/// Champs de `StatusSummary` utiles à l'agrégation ; les autres sont ignorés
+#[derive(Deserialize)]
+struct PeerSummary {
+    health_score: u8,
+    status: String,
+    incidents: Vec<IncidentDetail>,
+}
+
+/// Pairs de l'instance et leur dernier état connu.
+pub struct Cluster {
+    node_name: String,
+    peers: Vec<PeerNode>,
+    timeout: Duration,
+    client: Client,
+    latest: Mutex<Vec<NodeStatus>>,
+}
+
+impl Cluster {
+    pub fn new(settings: &MonitoringConfig) -> Self {
+        let timeout = Duration::from_millis(settings.timeout_ms);
+        let client = Client::builder().timeout(timeout).build().unwrap_or_default();
+
+        // Pairs en état inconnu jusqu'au premier passage de la tâche des métriques
+        let pending = settings
+            .peers
+            .iter()
+            .map(|peer| NodeStatus {
+                name: peer.name.clone(),
+                url: Some(peer.url.clone()),
+                local: false,
+                reachable: false,
+                status: ServiceStatus::Unknown,
+                health_score: None,
+                response_time_ms: None,
+                checked_at: Utc::now(),
+                error: None,
+            })
+            .collect();
+
+        Self {
+            node_name: settings.node_name(),
+            peers: settings.peers.clone(),
+            timeout,
+            client,
+            latest: Mutex::new(pending),
+        }
+    }
+
+    /// Indique si des pairs sont configurés
+    pub fn is_enabled(&self) -> bool {
+        !self.peers.is_empty()
+    }
+
+    /// Interroge tous les pairs en parallèle et conserve leur état, dans l'ordre de la configuration
+    pub async fn poll_all(&self) -> Vec<NodeStatus> {
+        let nodes = join_all(self.peers.iter().map(|peer| self.poll(peer))).await;
+        *self.latest.lock().unwrap() = nodes.clone();
+        nodes
+    }
+
+    /// Interroge un pair
+    pub async fn poll(&self, peer: &PeerNode) -> NodeStatus {
+        let url = format!("{}/api/status", peer.url.trim_end_matches('/'));
+        let start = Instant::now();
+
+        let mut node = NodeStatus {
+            name: peer.name.clone(),
+            url: Some(peer.url.clone()),
+            local: false,
+            reachable: false,
+            status: ServiceStatus::Down,
+            health_score: None,
+            response_time_ms: None,
+            checked_at: Utc::now(),
+            error: None,
+        };
+
+        let response = match inject(self.client.get(&url)).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                node.error = Some(if e.is_timeout() {
+                    format!("no response after {} ms", self.timeout.as_millis())
+                } else {
+                    e.to_string()
+                });
+                info!("Cluster peer {} is unreachable: {}", peer.name, node.error.as_deref().unwrap_or_default());
+                return node;
+            }
+        };
+        node.reachable = true;
+        node.response_time_ms = Some(start.elapsed().as_millis() as u64);
+
+        match response.status() {
+            // Le pair répond mais n'a pas encore calculé ses métriques
+            StatusCode::SERVICE_UNAVAILABLE => node.status = ServiceStatus::Unknown,
+            status if status.is_success() => match response.bytes().await.map_err(|e| e.to_string()).and_then(|body| {
+                serde_json::from_slice::<Envelope>(&body).map_err(|e| e.to_string())
+            }) {
+                Ok(envelope) => {
+                    let peer = envelope.data;
+                    node.status = ServiceStatus::assess(&peer.status, peer.health_score, &peer.incidents);
+                    node.health_score = Some(peer.health_score);
+                }
+                Err(e) => node.error = Some(format!("invalid status response: {}", e)),
+            },
+            status => node.error = Some(format!("unexpected status {}", status)),
+        }
+        node
+    }
+
+    /// État de cette instance, présenté comme un nœud du cluster
+    pub fn local_node(&self, metrics: Option<&PerformanceMetrics>, incidents: &[IncidentDetail]) -> NodeStatus {
+        NodeStatus {
+            name: self.node_name.clone(),
+            url: None,
+            local: true,
+            reachable: true,
+            status: ServiceStatus::evaluate(metrics, incidents),
+            health_score: metrics.map(|metrics| metrics.health_score),
+            response_time_ms: None,
+            checked_at: metrics.map_or_else(Utc::now, |metrics| metrics.timestamp),
+            error: None,
+        }
+    }
+
+    /// État agrégé de cette instance et du dernier état connu des pairs
+    pub fn status(&self, local: NodeStatus) -> ClusterStatus {
+        let mut nodes = vec![local];
+        nodes.extend(self.latest.lock().unwrap().iter().cloned());
+        ClusterStatus::aggregate(nodes)
+    }
+}
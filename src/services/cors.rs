@@ -0,0 +1,199 @@
+//! # CORS Origins Service
+//!
+//! Ce module gère les origines CORS autorisées :
+//! - les origines statiques de `[cors] allowed_origins`
+//! - avec `[cors] dynamic_origins = true`, les origines enregistrées dans la table
+//!   `cors_origins` (domaines déclarés par les clients), gardées en cache mémoire
+//!
+//! Le cache est rechargé après `dynamic_origins_ttl_seconds`, et invalidé
+//! immédiatement par les routes d'administration qui modifient la table.
+
+use sqlx::{PgPool, QueryBuilder};
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+use crate::{
+    config::CorsConfig,
+    extractors::query::QueryOptions,
+    models::cors::{CorsOrigin, CorsOriginFields, CorsOriginId, NewCorsOrigin},
+};
+
+/// Origines dynamiques en cache et date de leur chargement
+type CachedOrigins = (Instant, Arc<HashSet<String>>);
+
+/// Ensemble des origines autorisées (statiques et dynamiques).
+#[derive(Debug)]
+pub struct CorsOrigins {
+    allow_any: bool,
+    static_origins: HashSet<String>,
+    dynamic: bool,
+    ttl: Duration,
+    cache: RwLock<Option<CachedOrigins>>,
+    /// Un seul rechargement à la fois, même sous forte charge
+    refresh: tokio::sync::Mutex<()>,
+}
+
+impl CorsOrigins {
+    /// Construit l'ensemble à partir de la configuration CORS.
+    pub fn new(config: &CorsConfig) -> Self {
+        let static_origins = config
+            .allowed_origins
+            .iter()
+            .filter_map(|origin| match normalize_origin(origin) {
+                Ok(origin) => Some(origin),
+                Err(_) if origin == "*" => None,
+                Err(e) => {
+                    warn!("Ignoring invalid CORS origin {:?}: {}", origin, e);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            allow_any: config.allowed_origins.iter().any(|origin| origin == "*"),
+            static_origins,
+            dynamic: config.dynamic_origins,
+            ttl: Duration::from_secs(config.dynamic_origins_ttl_seconds),
+            cache: RwLock::new(None),
+            refresh: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Indique si une origine est autorisée.
+    ///
+    /// Les origines statiques sont vérifiées sans accès à la base ; en cas d'erreur
+    /// lors du rechargement, le dernier cache connu continue d'être utilisé.
+    pub async fn is_allowed(&self, pool: &PgPool, origin: &str) -> bool {
+        if self.allow_any {
+            return true;
+        }
+        let Ok(origin) = normalize_origin(origin) else {
+            return false;
+        };
+        if self.static_origins.contains(&origin) {
+            return true;
+        }
+        if !self.dynamic {
+            return false;
+        }
+
+        match self.dynamic_origins(pool).await {
+            Some(origins) => origins.contains(&origin),
+            None => false,
+        }
+    }
+
+    /// Vide le cache : le prochain appel rechargera les origines depuis la base.
+    pub fn invalidate(&self) {
+        *self.cache.write().unwrap() = None;
+    }
+
+    /// Origines dynamiques en cache, rechargées si elles ont expiré
+    async fn dynamic_origins(&self, pool: &PgPool) -> Option<Arc<HashSet<String>>> {
+        if let Some(origins) = self.fresh_cache() {
+            return Some(origins);
+        }
+
+        let _guard = self.refresh.lock().await;
+        // Un autre appel a pu recharger le cache pendant l'attente
+        if let Some(origins) = self.fresh_cache() {
+            return Some(origins);
+        }
+
+        match sqlx::query_scalar::<_, String>("SELECT origin FROM cors_origins")
+            .fetch_all(pool)
+            .await
+        {
+            Ok(origins) => {
+                let origins = Arc::new(origins.into_iter().collect::<HashSet<_>>());
+                *self.cache.write().unwrap() = Some((Instant::now(), origins.clone()));
+                Some(origins)
+            }
+            Err(e) => {
+                warn!("Failed to load dynamic CORS origins, using stale cache: {}", e);
+                self.cache.read().unwrap().as_ref().map(|(_, origins)| origins.clone())
+            }
+        }
+    }
+
+    fn fresh_cache(&self) -> Option<Arc<HashSet<String>>> {
+        match self.cache.read().unwrap().as_ref() {
+            Some((loaded_at, origins)) if loaded_at.elapsed() < self.ttl => Some(origins.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Normalise une origine : schéma et hôte en minuscules, sans slash final.
+///
+/// Seules les origines `http(s)://hôte[:port]` sont acceptées (pas de chemin).
+pub fn normalize_origin(origin: &str) -> Result<String, String> {
+    let origin = origin.trim().trim_end_matches('/').to_ascii_lowercase();
+    let host = origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"))
+        .ok_or_else(|| "origin must start with http:// or https://".to_string())?;
+
+    if host.is_empty() || host.contains(['/', '?', '#', ' ']) {
+        return Err("origin must be a scheme and host, without path".to_string());
+    }
+    Ok(origin)
+}
+
+/// Liste une page des origines enregistrées en base.
+pub async fn list_origins(
+    pool: &PgPool,
+    options: &QueryOptions<CorsOriginFields>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<CorsOrigin>, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT * FROM cors_origins WHERE true");
+    options.push_conditions(&mut builder);
+    options.push_order_by(&mut builder, "id");
+    builder.push(" LIMIT ").push_bind(limit);
+    builder.push(" OFFSET ").push_bind(offset);
+
+    builder.build_query_as::<CorsOrigin>().fetch_all(pool).await
+}
+
+/// Compte les origines enregistrées en base.
+pub async fn count_origins(pool: &PgPool, options: &QueryOptions<CorsOriginFields>) -> Result<i64, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT count(*) FROM cors_origins WHERE true");
+    options.push_conditions(&mut builder);
+
+    builder.build_query_scalar::<i64>().fetch_one(pool).await
+}
+
+/// Enregistre une origine (déjà normalisée) ; une origine existante est mise à jour.
+pub async fn add_origin(pool: &PgPool, new_origin: &NewCorsOrigin) -> Result<CorsOrigin, sqlx::Error> {
+    let origin = sqlx::query_as::<_, CorsOrigin>(
+        "INSERT INTO cors_origins (origin, tenant) VALUES ($1, $2)
+         ON CONFLICT (origin) DO UPDATE SET tenant = EXCLUDED.tenant
+         RETURNING *",
+    )
+    .bind(&new_origin.origin)
+    .bind(&new_origin.tenant)
+    .fetch_one(pool)
+    .await?;
+
+    info!("Registered CORS origin {}", origin.origin);
+    Ok(origin)
+}
+
+/// Supprime une origine.
+///
+/// # Returns
+///
+/// * `Result<bool, sqlx::Error>` - `false` si l'origine n'existait pas
+pub async fn remove_origin(pool: &PgPool, id: CorsOriginId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM cors_origins WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
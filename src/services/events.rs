@@ -0,0 +1,132 @@
+//! # Events Service
+//!
+//! Ce module enregistre et interroge la timeline des événements applicatifs.
+//! Les événements servent à corréler un changement de comportement avec ce qui
+//! s'est passé au même moment (déploiement, maintenance, incident...).
+
+use futures::{Stream, StreamExt};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::{info, warn};
+
+use crate::{
+    extractors::query::QueryOptions,
+    models::events::{AppEvent, EventFields, EventKind, EventsQuery},
+    sanitize,
+};
+
+/// Longueur maximale d'un message d'événement, au-delà il est tronqué
+const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Enregistre un événement dans la timeline.
+///
+/// Le message est affiché sur la page de status : il est ramené à du texte brut et borné.
+pub async fn record_event(
+    pool: &PgPool,
+    kind: EventKind,
+    message: &str,
+    details: serde_json::Value,
+) -> Result<AppEvent, sqlx::Error> {
+    let message = sanitize::truncate(&sanitize::plain_text(message), MAX_MESSAGE_CHARS);
+    let event = sqlx::query_as::<_, AppEvent>(
+        "INSERT INTO app_events (kind, message, details) VALUES ($1, $2, $3) RETURNING *",
+    )
+    .bind(kind.as_str())
+    .bind(&message)
+    .bind(details)
+    .fetch_one(pool)
+    .await?;
+
+    info!("Recorded {} event: {}", kind.as_str(), message);
+    Ok(event)
+}
+
+/// Enregistre un événement sans faire échouer l'appelant (l'erreur est seulement journalisée).
+pub async fn try_record_event(pool: &PgPool, kind: EventKind, message: &str, details: serde_json::Value) {
+    if let Err(e) = record_event(pool, kind, message, details).await {
+        warn!("Failed to record {} event: {}", kind.as_str(), e);
+    }
+}
+
+/// Ajoute à la requête les filtres de `EventsQuery` et de la query string
+fn push_event_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &EventsQuery, options: &QueryOptions<EventFields>) {
+    if let Some(kind) = query.kind {
+        builder.push(" AND kind = ").push_bind(kind.as_str());
+    }
+    if let Some(since) = query.since {
+        builder.push(" AND occurred_at >= ").push_bind(since);
+    }
+    options.push_conditions(builder);
+}
+
+/// Récupère une page des événements selon les filtres fournis (les plus récents d'abord par défaut).
+pub async fn list_events(
+    pool: &PgPool,
+    query: &EventsQuery,
+    options: &QueryOptions<EventFields>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<AppEvent>, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT * FROM app_events WHERE true");
+    push_event_filters(&mut builder, query, options);
+    options.push_order_by(&mut builder, "id DESC");
+    builder.push(" LIMIT ").push_bind(limit);
+    builder.push(" OFFSET ").push_bind(offset);
+
+    builder.build_query_as::<AppEvent>().fetch_all(pool).await
+}
+
+/// Parcourt en flux tous les événements correspondant aux filtres, pour les exports.
+///
+/// Le flux possède sa propre référence au pool : il peut être renvoyé dans le corps
+/// d'une réponse, et les lignes sont lues au rythme où le client les consomme.
+pub fn stream_events(
+    pool: PgPool,
+    query: EventsQuery,
+    options: QueryOptions<EventFields>,
+) -> impl Stream<Item = Result<AppEvent, sqlx::Error>> + Send + 'static {
+    async_stream::stream! {
+        let mut builder = QueryBuilder::new("SELECT * FROM app_events WHERE true");
+        push_event_filters(&mut builder, &query, &options);
+        options.push_order_by(&mut builder, "id DESC");
+
+        let mut rows = builder.build_query_as::<AppEvent>().fetch(&pool);
+        while let Some(row) = rows.next().await {
+            yield row;
+        }
+    }
+}
+
+/// Compte les événements correspondant aux filtres fournis.
+pub async fn count_events(
+    pool: &PgPool,
+    query: &EventsQuery,
+    options: &QueryOptions<EventFields>,
+) -> Result<i64, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT count(*) FROM app_events WHERE true");
+    push_event_filters(&mut builder, query, options);
+
+    builder.build_query_scalar::<i64>().fetch_one(pool).await
+}
+
+/// Enregistre un événement de déploiement si la version a changé depuis le dernier démarrage.
+pub async fn record_deploy_if_changed(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let version = env!("CARGO_PKG_VERSION");
+
+    let last_version = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT details->>'version' FROM app_events WHERE kind = $1 ORDER BY occurred_at DESC LIMIT 1",
+    )
+    .bind(EventKind::Deploy.as_str())
+    .fetch_optional(pool)
+    .await?
+    .flatten();
+
+    if last_version.as_deref() != Some(version) {
+        let message = match last_version {
+            Some(previous) => format!("Deployed version {} (previously {})", version, previous),
+            None => format!("Deployed version {}", version),
+        };
+        record_event(pool, EventKind::Deploy, &message, serde_json::json!({ "version": version })).await?;
+    }
+
+    Ok(())
+}
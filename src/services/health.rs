@@ -0,0 +1,362 @@
+//! # Health Service
+//!
+//! Ce module définit le trait `HealthCheck` et le registre `HealthRegistry` agrégé
+//! par `/api/help/health`. Chaque sous-système (base de données, cache externe,
+//! API tierce, tâche de fond...) enregistre sa vérification dans `registry` :
+//!
+//! ```ignore
+//! HealthRegistry::new(timeout)
+//!     .register(DatabaseCheck::new(db.clone(), production))
+//!     .register(RedisCheck::new(client))
+//! ```
+//!
+//! Les vérifications s'exécutent en parallèle, chacune bornée par
+//! `[health] check_timeout_ms`. Une vérification critique en échec rend l'instance
+//! `unhealthy` (503) ; les autres échecs la rendent seulement `degraded`.
+//!
+//! La sonde `/api/help/ready` utilise son propre registre (`readiness_registry`) :
+//! base de données joignable, migrations appliquées et tâches de fond en cours
+//! d'exécution, toutes requises.
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::Config,
+    db::{DatabaseManager, MIGRATOR},
+    models::{
+        help::{CheckResult, HealthStatus, TaskState},
+        status::MetricsStore,
+    },
+    services::{runtime::RuntimeSampler, supervisor::Supervisor},
+};
+
+/// Fenêtre sur laquelle la vérification `runtime` mesure l'occupation des workers
+const RUNTIME_SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+
+/// Occupation des workers à partir de laquelle le runtime est considéré saturé
+const RUNTIME_BUSY_DEGRADED_PERCENT: f64 = 90.0;
+
+/// Tâches prêtes en attente, par worker, à partir desquelles le scheduler prend du retard
+const RUNTIME_QUEUE_DEGRADED_PER_WORKER: usize = 50;
+
+/// Résultat brut d'une vérification, avant mesure de la latence
+#[derive(Debug, Clone)]
+pub struct Probe {
+    pub status: HealthStatus,
+    pub message: Option<String>,
+    pub details: Option<Value>,
+}
+
+impl Probe {
+    pub fn healthy() -> Self {
+        Self { status: HealthStatus::Healthy, message: None, details: None }
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self { status: HealthStatus::Degraded, message: Some(message.into()), details: None }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self { status: HealthStatus::Unhealthy, message: Some(message.into()), details: None }
+    }
+
+    /// Ajoute des informations propres à la vérification
+    pub fn details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// Vérification de santé d'un sous-système.
+#[async_trait]
+pub trait HealthCheck: Send + Sync + 'static {
+    /// Nom de la vérification dans la réponse
+    fn name(&self) -> &'static str;
+
+    /// Indique si un échec rend l'instance indisponible (par défaut : oui)
+    fn critical(&self) -> bool {
+        true
+    }
+
+    async fn check(&self) -> Probe;
+}
+
+/// Registre des vérifications de santé.
+#[derive(Clone)]
+pub struct HealthRegistry {
+    checks: Vec<Arc<dyn HealthCheck>>,
+    timeout: Duration,
+}
+
+impl HealthRegistry {
+    /// Crée un registre vide ; `timeout` borne chaque vérification.
+    pub fn new(timeout: Duration) -> Self {
+        Self { checks: Vec::new(), timeout }
+    }
+
+    /// Ajoute une vérification.
+    pub fn register<C: HealthCheck>(mut self, check: C) -> Self {
+        self.checks.push(Arc::new(check));
+        self
+    }
+
+    /// Exécute les vérifications en parallèle, ou seulement les critiques avec `critical_only`.
+    pub async fn run(&self, critical_only: bool) -> Vec<CheckResult> {
+        let checks = self.checks.iter().filter(|check| !critical_only || check.critical());
+        join_all(checks.map(|check| self.run_one(check.as_ref()))).await
+    }
+
+    async fn run_one(&self, check: &dyn HealthCheck) -> CheckResult {
+        let start = Instant::now();
+        let probe = tokio::time::timeout(self.timeout, check.check())
+            .await
+            .unwrap_or_else(|_| Probe::unhealthy(format!("timed out after {} ms", self.timeout.as_millis())));
+
+        CheckResult {
+            name: check.name().to_string(),
+            status: probe.status,
+            critical: check.critical(),
+            latency_ms: start.elapsed().as_millis() as u64,
+            message: probe.message,
+            details: probe.details,
+        }
+    }
+}
+
+/// État agrégé : un échec critique rend l'instance `unhealthy`, tout autre problème `degraded`.
+pub fn aggregate(results: &[CheckResult]) -> HealthStatus {
+    results
+        .iter()
+        .map(|result| match result.status {
+            HealthStatus::Unhealthy if !result.critical => HealthStatus::Degraded,
+            status => status,
+        })
+        .max()
+        .unwrap_or(HealthStatus::Healthy)
+}
+
+/// Construit le registre des vérifications de l'application.
+pub fn registry(db: &DatabaseManager, config: &Config, tasks: &Arc<Supervisor>, metrics: &Arc<MetricsStore>) -> HealthRegistry {
+    HealthRegistry::new(Duration::from_millis(config.health.check_timeout_ms))
+        .register(DatabaseCheck { db: db.clone(), production: config.is_production() })
+        .register(DiskCheck { metrics: metrics.clone(), degraded_percent: config.health.disk_degraded_percent })
+        .register(BackgroundTasksCheck::new(tasks.clone()))
+        .register(RuntimeCheck)
+}
+
+/// Construit le registre de la sonde de readiness, dont chaque vérification est requise.
+pub fn readiness_registry(db: &DatabaseManager, config: &Config, tasks: &Arc<Supervisor>) -> HealthRegistry {
+    HealthRegistry::new(Duration::from_millis(config.health.check_timeout_ms))
+        .register(DatabaseCheck { db: db.clone(), production: config.is_production() })
+        .register(MigrationsCheck::new(db.clone()))
+        .register(BackgroundTasksCheck::new(tasks.clone()).required())
+}
+
+/// Connexion à la base de données ; en production, une connexion non chiffrée est signalée.
+pub struct DatabaseCheck {
+    db: DatabaseManager,
+    production: bool,
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    async fn check(&self) -> Probe {
+        if let Err(e) = sqlx::query("SELECT 1").execute(self.db.get_pool()).await {
+            return Probe::unhealthy(e.to_string());
+        }
+
+        let encrypted = self.db.is_encrypted().await.ok();
+        let probe = if self.production && encrypted == Some(false) {
+            Probe::degraded("Database connection is not encrypted (TLS disabled)")
+        } else {
+            Probe::healthy()
+        };
+        probe.details(json!({ "encrypted": encrypted }))
+    }
+}
+
+/// Espace disque de chaque point de montage mesuré (`[health] disk_mount_points`), lu
+/// dans les métriques système rafraîchies par la tâche de fond ; non critique, l'instance
+/// peut encore servir.
+pub struct DiskCheck {
+    metrics: Arc<MetricsStore>,
+    degraded_percent: f32,
+}
+
+#[async_trait]
+impl HealthCheck for DiskCheck {
+    fn name(&self) -> &'static str {
+        "disk"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Probe {
+        let system = self.metrics.system();
+        let full: Vec<String> = system
+            .disks
+            .iter()
+            .filter(|disk| disk.usage_percent >= self.degraded_percent)
+            .map(|disk| format!("{:.1}% on {}", disk.usage_percent, disk.mount_point))
+            .collect();
+        let probe = if full.is_empty() {
+            Probe::healthy()
+        } else {
+            Probe::degraded(format!("disk usage is {}", full.join(", ")))
+        };
+        let mounts: Vec<_> = system
+            .disks
+            .iter()
+            .map(|disk| json!({ "mount_point": disk.mount_point, "usage_percent": disk.usage_percent }))
+            .collect();
+        probe.details(json!({ "usage_percent": system.disk_usage_percent, "mounts": mounts }))
+    }
+}
+
+/// Migrations embarquées toutes appliquées d'après `_sqlx_migrations`.
+///
+/// Sans cette table (schéma appliqué hors de `sqlx migrate`), l'état des migrations
+/// est inconnu : la vérification est seulement `degraded`.
+pub struct MigrationsCheck {
+    db: DatabaseManager,
+}
+
+impl MigrationsCheck {
+    pub fn new(db: DatabaseManager) -> Self {
+        Self { db }
+    }
+
+    async fn pending(&self) -> Result<Option<Vec<i64>>, sqlx::Error> {
+        let pool = self.db.get_pool();
+        let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+        if !tracked {
+            return Ok(None);
+        }
+
+        let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await?;
+        Ok(Some(
+            MIGRATOR
+                .iter()
+                .filter(|migration| !migration.migration_type.is_down_migration())
+                .map(|migration| migration.version)
+                .filter(|version| !applied.contains(version))
+                .collect(),
+        ))
+    }
+}
+
+#[async_trait]
+impl HealthCheck for MigrationsCheck {
+    fn name(&self) -> &'static str {
+        "migrations"
+    }
+
+    async fn check(&self) -> Probe {
+        match self.pending().await {
+            Err(e) => Probe::unhealthy(e.to_string()),
+            Ok(None) => Probe::degraded("migrations are not tracked (no _sqlx_migrations table)"),
+            Ok(Some(pending)) if pending.is_empty() => Probe::healthy(),
+            Ok(Some(pending)) => {
+                Probe::unhealthy(format!("{} pending migration(s)", pending.len())).details(json!({ "pending": pending }))
+            }
+        }
+    }
+}
+
+/// Tâches de fond supervisées ; non critique par défaut : l'instance sert encore les requêtes.
+///
+/// Signale une tâche qui redémarre après un panic, ou bloquée au-delà de son passage prévu.
+pub struct BackgroundTasksCheck {
+    tasks: Arc<Supervisor>,
+    required: bool,
+}
+
+impl BackgroundTasksCheck {
+    pub fn new(tasks: Arc<Supervisor>) -> Self {
+        Self { tasks, required: false }
+    }
+
+    /// Une tâche en échec rend l'instance `unhealthy` (sonde de readiness)
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+#[async_trait]
+impl HealthCheck for BackgroundTasksCheck {
+    fn name(&self) -> &'static str {
+        "background_tasks"
+    }
+
+    fn critical(&self) -> bool {
+        self.required
+    }
+
+    async fn check(&self) -> Probe {
+        let report = self.tasks.report();
+        let now = Utc::now();
+
+        let problems = report
+            .iter()
+            .filter_map(|task| match task.state {
+                TaskState::Restarting => Some(format!("{} is restarting", task.name)),
+                TaskState::Running if task.is_overdue(now) => Some(format!("{} missed its scheduled run", task.name)),
+                TaskState::Running => None,
+            })
+            .collect::<Vec<_>>();
+        let probe = match (problems.is_empty(), self.required) {
+            (true, _) => Probe::healthy(),
+            (false, true) => Probe::unhealthy(problems.join(", ")),
+            (false, false) => Probe::degraded(problems.join(", ")),
+        };
+        probe.details(json!({ "tasks": report }))
+    }
+}
+
+/// Runtime Tokio ; non critique : signale des workers saturés ou une file qui s'allonge.
+pub struct RuntimeCheck;
+
+#[async_trait]
+impl HealthCheck for RuntimeCheck {
+    fn name(&self) -> &'static str {
+        "runtime"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> Probe {
+        let mut sampler = RuntimeSampler::new();
+        tokio::time::sleep(RUNTIME_SAMPLE_WINDOW).await;
+        let usage = sampler.sample();
+
+        let mut problems = Vec::new();
+        if usage.busy_percent >= RUNTIME_BUSY_DEGRADED_PERCENT {
+            problems.push(format!("workers are {:.0}% busy", usage.busy_percent));
+        }
+        if usage.global_queue_depth >= RUNTIME_QUEUE_DEGRADED_PER_WORKER * usage.workers.max(1) {
+            problems.push(format!("{} tasks waiting in the scheduler queue", usage.global_queue_depth));
+        }
+        let probe = if problems.is_empty() { Probe::healthy() } else { Probe::degraded(problems.join(", ")) };
+        probe.details(json!(usage))
+    }
+}
@@ -0,0 +1,207 @@
+//! # Incidents Service
+//!
+//! Ce module regroupe les accès aux tables `incidents` et `incident_updates`.
+//! L'ouverture, les mises à jour et la résolution d'un incident sont aussi
+//! enregistrées dans la timeline des événements (`kind = incident`).
+
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::warn;
+
+use crate::{
+    models::{
+        events::EventKind,
+        incidents::{
+            Incident, IncidentDetail, IncidentId, IncidentStatus, IncidentUpdate, IncidentsQuery, NewIncident,
+            UpdateIncident,
+        },
+    },
+    services::events::record_event,
+};
+
+/// Message publié par défaut à la résolution
+pub const DEFAULT_RESOLVED_MESSAGE: &str = "This incident has been resolved.";
+
+/// Ouvre un incident avec son premier message.
+pub async fn create_incident(pool: &PgPool, new_incident: &NewIncident) -> Result<IncidentDetail, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let incident = sqlx::query_as::<_, Incident>(
+        "INSERT INTO incidents (title, severity, status, resolved_at)
+         VALUES ($1, $2, $3, CASE WHEN $3 = 'resolved' THEN now() END)
+         RETURNING *",
+    )
+    .bind(&new_incident.title)
+    .bind(new_incident.severity.as_str())
+    .bind(new_incident.status.as_str())
+    .fetch_one(&mut *tx)
+    .await?;
+    let update = sqlx::query_as::<_, IncidentUpdate>(
+        "INSERT INTO incident_updates (incident_id, status, message) VALUES ($1, $2, $3) RETURNING *",
+    )
+    .bind(incident.id)
+    .bind(new_incident.status.as_str())
+    .bind(&new_incident.message)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    timeline(pool, &incident, &format!("Incident opened: {}", incident.title)).await;
+    Ok(IncidentDetail { incident, updates: vec![update] })
+}
+
+/// Liste une page des incidents, les plus récents d'abord.
+pub async fn list_incidents(
+    pool: &PgPool,
+    query: &IncidentsQuery,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Incident>, sqlx::Error> {
+    sqlx::query_as::<_, Incident>(
+        "SELECT * FROM incidents
+         WHERE $1::boolean IS NULL OR (resolved_at IS NULL) = $1
+         ORDER BY created_at DESC, id DESC
+         LIMIT $2 OFFSET $3",
+    )
+    .bind(query.open)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Compte les incidents correspondant aux filtres.
+pub async fn count_incidents(pool: &PgPool, query: &IncidentsQuery) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT count(*) FROM incidents WHERE $1::boolean IS NULL OR (resolved_at IS NULL) = $1")
+        .bind(query.open)
+        .fetch_one(pool)
+        .await
+}
+
+/// Incidents ouverts avec leurs mises à jour, les plus récents d'abord (page de status).
+pub async fn open_incidents(pool: &PgPool) -> Result<Vec<IncidentDetail>, sqlx::Error> {
+    let incidents = sqlx::query_as::<_, Incident>(
+        "SELECT * FROM incidents WHERE resolved_at IS NULL ORDER BY created_at DESC, id DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    let ids = incidents.iter().map(|incident| incident.id.into_inner()).collect::<Vec<i64>>();
+
+    // Une seule requête pour les mises à jour de tous les incidents ouverts
+    let updates = sqlx::query_as::<_, IncidentUpdate>(
+        "SELECT * FROM incident_updates WHERE incident_id = ANY($1) ORDER BY created_at DESC, id DESC",
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents
+        .into_iter()
+        .map(|incident| IncidentDetail {
+            updates: updates.iter().filter(|update| update.incident_id == incident.id).cloned().collect(),
+            incident,
+        })
+        .collect())
+}
+
+/// Récupère un incident avec ses mises à jour.
+pub async fn get_incident_detail(pool: &PgPool, id: IncidentId) -> Result<Option<IncidentDetail>, sqlx::Error> {
+    let Some(incident) = get_incident(pool, id).await? else {
+        return Ok(None);
+    };
+    let updates = sqlx::query_as::<_, IncidentUpdate>(
+        "SELECT * FROM incident_updates WHERE incident_id = $1 ORDER BY created_at DESC, id DESC",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(IncidentDetail { incident, updates }))
+}
+
+/// Récupère un incident.
+pub async fn get_incident(pool: &PgPool, id: IncidentId) -> Result<Option<Incident>, sqlx::Error> {
+    sqlx::query_as::<_, Incident>("SELECT * FROM incidents WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Modifie le titre ou la gravité d'un incident.
+pub async fn update_incident(pool: &PgPool, id: IncidentId, changes: &UpdateIncident) -> Result<Option<Incident>, sqlx::Error> {
+    sqlx::query_as::<_, Incident>(
+        "UPDATE incidents
+         SET title = coalesce($2, title), severity = coalesce($3, severity), updated_at = now()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&changes.title)
+    .bind(changes.severity.map(|severity| severity.as_str()))
+    .fetch_optional(pool)
+    .await
+}
+
+/// Publie une mise à jour et applique son statut à l'incident.
+///
+/// `resolved` résout l'incident ; tout autre statut rouvre un incident résolu.
+pub async fn add_update(
+    pool: &PgPool,
+    id: IncidentId,
+    status: IncidentStatus,
+    message: &str,
+) -> Result<Option<IncidentUpdate>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let incident = sqlx::query_as::<_, Incident>(
+        "UPDATE incidents
+         SET status = $2, updated_at = now(),
+             resolved_at = CASE WHEN $2 = 'resolved' THEN coalesce(resolved_at, now()) END
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(incident) = incident else {
+        return Ok(None);
+    };
+
+    let update = sqlx::query_as::<_, IncidentUpdate>(
+        "INSERT INTO incident_updates (incident_id, status, message) VALUES ($1, $2, $3) RETURNING *",
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .bind(message)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let summary = match status {
+        IncidentStatus::Resolved => format!("Incident resolved: {}", incident.title),
+        _ => format!("Incident {}: {}", status.as_str(), incident.title),
+    };
+    timeline(pool, &incident, &summary).await;
+    Ok(Some(update))
+}
+
+/// Supprime un incident et ses mises à jour.
+///
+/// # Returns
+///
+/// * `Result<bool, sqlx::Error>` - `false` si l'incident n'existait pas
+pub async fn delete_incident(pool: &PgPool, id: IncidentId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM incidents WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Ajoute l'événement à la timeline ; un échec n'annule pas l'opération
+async fn timeline(pool: &PgPool, incident: &Incident, message: &str) {
+    let details = json!({ "incident_id": incident.id, "severity": incident.severity, "status": incident.status });
+    if let Err(e) = record_event(pool, EventKind::Incident, message, details).await {
+        warn!("Failed to record incident event: {}", e);
+    }
+}
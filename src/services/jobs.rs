@@ -0,0 +1,403 @@
+//! # Jobs Service
+//!
+//! Ce module gère les tâches asynchrones, pour les opérations trop longues pour
+//! être traitées pendant la requête :
+//! - le handler enregistre la tâche avec `enqueue` et répond `202 Accepted`
+//!   (voir `handlers::jobs::accepted`), avec `Location: /api/jobs/{id}`
+//! - `[jobs] workers` workers en arrière-plan se partagent les tâches dues
+//!   (`FOR UPDATE SKIP LOCKED`) et les exécutent avec le handler du registre associé
+//!   à leur type (`kind`)
+//! - les échecs sont retentés avec un backoff exponentiel ; après `max_attempts`
+//!   exécutions, la tâche passe en dead-letter (`dead`) jusqu'à sa relance par
+//!   `POST /api/admin/jobs/{id}/retry`
+//! - le client suit l'avancement sur `GET /api/jobs/{id}`
+//!
+//! Le payload d'une tâche implémente `Job`, qui porte son type :
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct GenerateReport { month: String }
+//!
+//! impl Job for GenerateReport {
+//!     const KIND: &'static str = "reports.generate";
+//! }
+//!
+//! let job = jobs::enqueue(db.get_pool(), &GenerateReport { month }).await?;
+//! Ok(handlers::jobs::accepted(&links, job))
+//! ```
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::JobsConfig,
+    db::DatabaseManager,
+    middleware::trace::TraceContext,
+    models::jobs::{Job as JobRecord, JobId, JobsQuery},
+    services::{
+        metrics::{UptimeReport, UptimeReportJob},
+        supervisor::Supervisor,
+        webhooks::backoff_delay,
+    },
+};
+
+type BoxedRunner = Arc<dyn Fn(DatabaseManager, serde_json::Value) -> BoxFuture<'static, Result<serde_json::Value, JobError>> + Send + Sync>;
+
+/// Erreurs d'exécution d'une tâche
+#[derive(Debug, Error)]
+pub enum JobError {
+    /// Aucun handler n'est enregistré pour ce type de tâche (pas de nouvelle tentative)
+    #[error("unknown job kind: {0}")]
+    UnknownKind(String),
+    /// Le payload ne correspond pas au handler (pas de nouvelle tentative)
+    #[error("invalid job payload: {0}")]
+    InvalidPayload(String),
+    /// Échec de la tâche, retentée tant que `max_attempts` n'est pas atteint
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl JobError {
+    /// Une nouvelle tentative peut réussir
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, JobError::Failed(_))
+    }
+}
+
+/// Payload d'un type de tâche, soumis avec `enqueue`.
+///
+/// `KIND` associe le payload au handler enregistré sous ce nom dans le registre
+/// (`JobRegistry::register`).
+pub trait Job: Serialize + Send + Sync {
+    /// Type de tâche, enregistré avec la tâche (`kind`)
+    const KIND: &'static str;
+}
+
+/// Handler typé d'un type de tâche.
+///
+/// Le payload est désérialisé depuis le JSON enregistré avant l'appel à `run` ;
+/// le résultat est stocké avec la tâche et exposé par `GET /api/jobs/{id}`.
+#[async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+    type Payload: DeserializeOwned + Send;
+    type Output: Serialize;
+
+    async fn run(&self, db: &DatabaseManager, payload: Self::Payload) -> Result<Self::Output, JobError>;
+}
+
+struct RegisteredJob {
+    run: BoxedRunner,
+    check: fn(&serde_json::Value) -> Result<(), JobError>,
+}
+
+/// Registre associant chaque type de tâche à son handler typé.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: HashMap<String, RegisteredJob>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Associe un handler typé à un type de tâche.
+    pub fn register<H: JobHandler>(mut self, kind: &str, handler: H) -> Self {
+        let handler = Arc::new(handler);
+        let run: BoxedRunner = Arc::new(move |db, payload| {
+            let handler = handler.clone();
+            Box::pin(async move {
+                let payload = serde_json::from_value::<H::Payload>(payload)
+                    .map_err(|e| JobError::InvalidPayload(e.to_string()))?;
+                let output = handler.run(&db, payload).await?;
+                serde_json::to_value(output).map_err(|e| JobError::Failed(format!("JSON serialization error: {}", e)))
+            })
+        });
+        let check = |payload: &serde_json::Value| {
+            H::Payload::deserialize(payload)
+                .map(drop)
+                .map_err(|e| JobError::InvalidPayload(e.to_string()))
+        };
+        self.jobs.insert(kind.to_string(), RegisteredJob { run, check });
+        self
+    }
+
+    /// Types de tâches enregistrés, triés
+    pub fn kinds(&self) -> Vec<&str> {
+        let mut kinds = self.jobs.keys().map(String::as_str).collect::<Vec<_>>();
+        kinds.sort_unstable();
+        kinds
+    }
+
+    /// Vérifie qu'une tâche peut être soumise : type enregistré et payload conforme.
+    pub fn check(&self, kind: &str, payload: &serde_json::Value) -> Result<(), JobError> {
+        let job = self.jobs.get(kind).ok_or_else(|| JobError::UnknownKind(kind.to_string()))?;
+        (job.check)(payload)
+    }
+
+    /// Exécute une tâche avec le handler de son type.
+    pub async fn run(&self, db: &DatabaseManager, kind: &str, payload: serde_json::Value) -> Result<serde_json::Value, JobError> {
+        let job = self.jobs.get(kind).ok_or_else(|| JobError::UnknownKind(kind.to_string()))?;
+        (job.run)(db.clone(), payload).await
+    }
+}
+
+/// Construit le registre des tâches.
+///
+/// Ajoutez ici vos propres handlers :
+/// `.register("reports.generate", GenerateReport)`
+pub fn registry() -> JobRegistry {
+    JobRegistry::new()
+        // Tâche d'exemple, vous pouvez la supprimer
+        .register(ExamplePayload::KIND, ExampleJob)
+        // Rapport de disponibilité, soumis par la tâche planifiée `uptime_report`
+        .register(UptimeReport::KIND, UptimeReportJob)
+}
+
+/// Payload de la tâche d'exemple
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExamplePayload {
+    pub message: String,
+}
+
+impl Job for ExamplePayload {
+    const KIND: &'static str = "example";
+}
+
+/// Tâche d'exemple qui journalise le message reçu et le renvoie
+pub struct ExampleJob;
+
+#[async_trait]
+impl JobHandler for ExampleJob {
+    type Payload = ExamplePayload;
+    type Output = serde_json::Value;
+
+    async fn run(&self, _db: &DatabaseManager, payload: ExamplePayload) -> Result<serde_json::Value, JobError> {
+        info!("Running example job: {}", payload.message);
+        Ok(serde_json::json!({ "message": payload.message }))
+    }
+}
+
+/// Tâche réservée par le worker
+#[derive(Debug, FromRow)]
+struct ClaimedJob {
+    id: JobId,
+    kind: String,
+    payload: serde_json::Value,
+    attempts: i32,
+}
+
+/// Enregistre une tâche, exécutée dès que possible par un worker.
+pub async fn enqueue<T: Job>(pool: &PgPool, job: &T) -> Result<JobRecord, sqlx::Error> {
+    let payload = serde_json::to_value(job)
+        .map_err(|e| sqlx::Error::Protocol(format!("JSON serialization error: {}", e)))?;
+    enqueue_kind(pool, T::KIND, &payload).await
+}
+
+/// Enregistre une tâche d'un type connu seulement à l'exécution (soumission par
+/// l'administration) ; le payload doit avoir été vérifié avec `JobRegistry::check`.
+pub async fn enqueue_kind(pool: &PgPool, kind: &str, payload: &serde_json::Value) -> Result<JobRecord, sqlx::Error> {
+    let job = sqlx::query_as::<_, JobRecord>(
+        "INSERT INTO jobs (id, kind, payload)
+         VALUES ($1, $2, $3)
+         RETURNING *",
+    )
+    .bind(JobId::new(Uuid::new_v4()))
+    .bind(kind)
+    .bind(payload)
+    .fetch_one(pool)
+    .await?;
+
+    info!("Enqueued job {} ({})", job.id, job.kind);
+    Ok(job)
+}
+
+/// Récupère une tâche par son identifiant.
+pub async fn get_job(pool: &PgPool, id: JobId) -> Result<Option<JobRecord>, sqlx::Error> {
+    sqlx::query_as::<_, JobRecord>("SELECT * FROM jobs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Liste les tâches d'un statut, les dernières modifiées d'abord.
+pub async fn list_jobs(pool: &PgPool, query: &JobsQuery, limit: i64, offset: i64) -> Result<Vec<JobRecord>, sqlx::Error> {
+    sqlx::query_as::<_, JobRecord>(
+        "SELECT * FROM jobs
+         WHERE status = $1 AND ($2::text IS NULL OR kind = $2)
+         ORDER BY updated_at DESC, id
+         LIMIT $3 OFFSET $4",
+    )
+    .bind(&query.status)
+    .bind(&query.kind)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Compte les tâches correspondant aux filtres.
+pub async fn count_jobs(pool: &PgPool, query: &JobsQuery) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT count(*) FROM jobs WHERE status = $1 AND ($2::text IS NULL OR kind = $2)")
+        .bind(&query.status)
+        .bind(&query.kind)
+        .fetch_one(pool)
+        .await
+}
+
+/// Remet en file une tâche en échec (`failed` ou `dead`), avec un nouveau compteur de
+/// tentatives. Retourne `None` si la tâche n'existe pas ou n'est pas en échec.
+pub async fn retry_job(pool: &PgPool, id: JobId) -> Result<Option<JobRecord>, sqlx::Error> {
+    let job = sqlx::query_as::<_, JobRecord>(
+        "UPDATE jobs
+         SET status = 'queued', attempts = 0, run_at = now(), finished_at = NULL, updated_at = now()
+         WHERE id = $1 AND status IN ('failed', 'dead')
+         RETURNING *",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(job) = &job {
+        info!("Requeued job {} ({})", job.id, job.kind);
+    }
+    Ok(job)
+}
+
+/// Démarre les workers d'exécution des tâches en arrière-plan, sous la supervision de `tasks`.
+///
+/// Chaque worker réserve ses propres lots : `FOR UPDATE SKIP LOCKED` garantit qu'une
+/// tâche n'est exécutée que par un seul d'entre eux, y compris entre plusieurs instances.
+pub async fn start_job_worker(tasks: &Supervisor, db: DatabaseManager, settings: JobsConfig, registry: Arc<JobRegistry>) {
+    for worker in 0..settings.workers.max(1) {
+        let db = db.clone();
+        let settings = settings.clone();
+        let registry = registry.clone();
+        tasks.spawn(&format!("job_worker_{}", worker), move |task| {
+            let db = db.clone();
+            let settings = settings.clone();
+            let registry = registry.clone();
+            async move {
+                let period = Duration::from_secs(settings.poll_interval_seconds);
+                let mut interval = tokio::time::interval(period);
+
+                loop {
+                    interval.tick().await;
+
+                    let processed = TraceContext::new_root()
+                        .scope(run_due_jobs(&db, &registry, &settings))
+                        .await;
+                    task.record(processed.map(drop), period);
+                }
+            }
+        });
+    }
+}
+
+/// Exécute un lot de tâches dues.
+///
+/// Les tâches sont réservées avec `FOR UPDATE SKIP LOCKED` pour la durée du bail
+/// (`lease_seconds`) : une tâche dont le worker s'est arrêté en cours d'exécution
+/// est reprise à l'expiration du bail. Le bail doit donc couvrir la durée d'une tâche.
+///
+/// # Returns
+///
+/// * `Result<usize, sqlx::Error>` - Nombre de tâches traitées
+pub async fn run_due_jobs(db: &DatabaseManager, registry: &JobRegistry, settings: &JobsConfig) -> Result<usize, sqlx::Error> {
+    let pool = db.get_pool();
+    let jobs = sqlx::query_as::<_, ClaimedJob>(
+        "UPDATE jobs
+         SET status = 'running', attempts = attempts + 1,
+             run_at = now() + make_interval(secs => $2), updated_at = now()
+         WHERE id IN (
+             SELECT id FROM jobs
+             WHERE status IN ('queued', 'running') AND run_at <= now()
+             ORDER BY run_at
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, kind, payload, attempts",
+    )
+    .bind(settings.batch_size)
+    .bind(settings.lease_seconds as f64)
+    .fetch_all(pool)
+    .await?;
+
+    let count = jobs.len();
+    for job in jobs {
+        let outcome = registry.run(db, &job.kind, job.payload).await;
+        finish(pool, settings, job.id, &job.kind, job.attempts, outcome).await?;
+    }
+
+    Ok(count)
+}
+
+/// Enregistre le résultat d'une exécution
+async fn finish(
+    pool: &PgPool,
+    settings: &JobsConfig,
+    id: JobId,
+    kind: &str,
+    attempts: i32,
+    outcome: Result<serde_json::Value, JobError>,
+) -> Result<(), sqlx::Error> {
+    match outcome {
+        Ok(result) => {
+            info!("Job {} ({}) succeeded", id, kind);
+            sqlx::query(
+                "UPDATE jobs
+                 SET status = 'succeeded', result = $2, error = NULL, finished_at = now(), updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(result)
+            .execute(pool)
+            .await?;
+        }
+        Err(e) if e.is_retryable() && attempts < settings.max_attempts => {
+            let delay = backoff_delay(attempts as u32, settings.base_backoff_seconds, settings.max_backoff_seconds);
+            warn!("Job {} ({}) failed, retrying in {}s: {}", id, kind, delay, e);
+            sqlx::query(
+                "UPDATE jobs
+                 SET status = 'queued', error = $2, run_at = now() + make_interval(secs => $3), updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(e.to_string())
+            .bind(delay as f64)
+            .execute(pool)
+            .await?;
+        }
+        Err(e) if e.is_retryable() => {
+            warn!("Job {} ({}) dead-lettered after {} attempts: {}", id, kind, attempts, e);
+            sqlx::query(
+                "UPDATE jobs
+                 SET status = 'dead', error = $2, finished_at = now(), updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(e.to_string())
+            .execute(pool)
+            .await?;
+        }
+        Err(e) => {
+            warn!("Job {} ({}) failed permanently: {}", id, kind, e);
+            sqlx::query(
+                "UPDATE jobs
+                 SET status = 'failed', error = $2, finished_at = now(), updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(e.to_string())
+            .execute(pool)
+            .await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! # Maintenance Service
+//!
+//! Ce module regroupe les fenêtres de maintenance planifiée : celles de la table
+//! `maintenance_windows` (créées via l'API d'administration) et celles déclarées
+//! dans `[[maintenance.windows]]`.
+//! - la tâche des métriques suspend les notifications d'alertes pendant une fenêtre
+//! - la page de status annonce les fenêtres en cours et celles qui commencent dans
+//!   les `[maintenance] announce_days` prochains jours
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::{
+    config::MaintenanceConfig,
+    models::{
+        events::EventKind,
+        maintenance::{MaintenanceWindow, MaintenanceWindowId, NewMaintenanceWindow},
+    },
+    services::events::try_record_event,
+};
+
+/// Enregistre une fenêtre de maintenance et l'ajoute à la timeline des événements.
+pub async fn create_window(pool: &PgPool, window: &NewMaintenanceWindow) -> Result<MaintenanceWindow, sqlx::Error> {
+    let created = sqlx::query_as::<_, MaintenanceWindow>(
+        "INSERT INTO maintenance_windows (title, description, starts_at, ends_at)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, title, description, starts_at, ends_at",
+    )
+    .bind(&window.title)
+    .bind(&window.description)
+    .bind(window.starts_at)
+    .bind(window.ends_at)
+    .fetch_one(pool)
+    .await?;
+
+    let details = json!({ "maintenance_id": created.id, "starts_at": created.starts_at, "ends_at": created.ends_at });
+    try_record_event(pool, EventKind::Maintenance, &format!("Maintenance planifiée : {}", created.title), details).await;
+    Ok(created)
+}
+
+/// Supprime une fenêtre ; `false` si elle n'existe pas.
+pub async fn delete_window(pool: &PgPool, id: MaintenanceWindowId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM maintenance_windows WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Fenêtres non terminées à `now` (base et configuration), par date de début.
+pub async fn scheduled_windows(
+    pool: &PgPool,
+    settings: &MaintenanceConfig,
+    now: DateTime<Utc>,
+) -> Result<Vec<MaintenanceWindow>, sqlx::Error> {
+    let mut windows = sqlx::query_as::<_, MaintenanceWindow>(
+        "SELECT id, title, description, starts_at, ends_at FROM maintenance_windows
+         WHERE ends_at > $1
+         ORDER BY starts_at, id",
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    windows.extend(
+        settings
+            .windows
+            .iter()
+            .map(MaintenanceWindow::from)
+            .filter(|window| window.ends_at > now),
+    );
+    windows.sort_by_key(|window| window.starts_at);
+    Ok(windows)
+}
+
+/// Fenêtre en cours à `now`, s'il y en a une.
+pub async fn active_window(
+    pool: &PgPool,
+    settings: &MaintenanceConfig,
+    now: DateTime<Utc>,
+) -> Result<Option<MaintenanceWindow>, sqlx::Error> {
+    Ok(scheduled_windows(pool, settings, now)
+        .await?
+        .into_iter()
+        .find(|window| window.is_active_at(now)))
+}
+
+/// Fenêtres à annoncer sur la page de status : en cours, ou commençant dans
+/// les `announce_days` prochains jours.
+pub async fn announced_windows(
+    pool: &PgPool,
+    settings: &MaintenanceConfig,
+    now: DateTime<Utc>,
+) -> Result<Vec<MaintenanceWindow>, sqlx::Error> {
+    let horizon = now + Duration::days(i64::from(settings.announce_days));
+    Ok(scheduled_windows(pool, settings, now)
+        .await?
+        .into_iter()
+        .filter(|window| window.starts_at <= horizon)
+        .collect())
+}
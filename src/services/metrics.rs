@@ -0,0 +1,215 @@
+//! # Metrics History Service
+//!
+//! Ce module conserve l'historique des métriques en base (table `metrics_history`),
+//! pour qu'il survive aux redémarrages :
+//! - la tâche de fond des métriques enregistre une entrée à chaque passage
+//! - les entrées plus anciennes que `[status] history_retention_days` sont purgées par
+//!   la tâche planifiée `history_pruning` (voir `services::scheduler`)
+//! - la page de status et `GET /api/status/history` lisent les entrées récentes
+//! - la tâche asynchrone `reports.uptime` (planifiée par `uptime_report`) enregistre la
+//!   disponibilité sur 24h/7j/30j comme résultat de la tâche
+//!
+//! Les dernières métriques calculées sont aussi conservées (table `metrics_snapshot`,
+//! une seule ligne) et rechargées au démarrage, pour que la page de status affiche
+//! des données réelles avant le premier passage de la tâche de fond.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow, PgPool};
+
+use crate::{
+    db::DatabaseManager,
+    models::status::{HistoryEntry, NetworkUsage, PerformanceMetrics, UptimeStats, DEGRADED_STATUS},
+    services::jobs::{Job, JobError, JobHandler},
+};
+
+/// Ligne de `metrics_history` (entiers signés côté PostgreSQL)
+#[derive(Debug, FromRow)]
+struct HistoryRow {
+    recorded_at: DateTime<Utc>,
+    response_time_ms: i64,
+    db_connected: bool,
+    db_response_time_ms: Option<i64>,
+    status: String,
+    issues: Vec<String>,
+    network_rx_bytes_per_sec: Option<i64>,
+    network_tx_bytes_per_sec: Option<i64>,
+    network_errors: Option<i64>,
+    network_load_percent: Option<f32>,
+}
+
+impl From<HistoryRow> for HistoryEntry {
+    fn from(row: HistoryRow) -> Self {
+        Self {
+            timestamp: row.recorded_at,
+            response_time_ms: row.response_time_ms.max(0) as u64,
+            db_connected: row.db_connected,
+            db_response_time_ms: row.db_response_time_ms.map(|ms| ms.max(0) as u64),
+            status: row.status,
+            issues: row.issues,
+            network: match (row.network_rx_bytes_per_sec, row.network_tx_bytes_per_sec, row.network_load_percent) {
+                (Some(rx), Some(tx), Some(load_percent)) => Some(NetworkUsage {
+                    rx_bytes_per_sec: rx.max(0) as u64,
+                    tx_bytes_per_sec: tx.max(0) as u64,
+                    errors: row.network_errors.unwrap_or(0).max(0) as u64,
+                    load_percent,
+                }),
+                _ => None,
+            },
+        }
+    }
+}
+
+const HISTORY_COLUMNS: &str = "recorded_at, response_time_ms, db_connected, db_response_time_ms, status, issues, \
+    network_rx_bytes_per_sec, network_tx_bytes_per_sec, network_errors, network_load_percent";
+
+/// Enregistre une entrée d'historique.
+pub async fn record_history(pool: &PgPool, entry: &HistoryEntry) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO metrics_history (recorded_at, response_time_ms, db_connected, db_response_time_ms, status, issues,
+             network_rx_bytes_per_sec, network_tx_bytes_per_sec, network_errors, network_load_percent)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+    )
+    .bind(entry.timestamp)
+    .bind(entry.response_time_ms as i64)
+    .bind(entry.db_connected)
+    .bind(entry.db_response_time_ms.map(|ms| ms as i64))
+    .bind(&entry.status)
+    .bind(&entry.issues)
+    .bind(entry.network.map(|n| n.rx_bytes_per_sec as i64))
+    .bind(entry.network.map(|n| n.tx_bytes_per_sec as i64))
+    .bind(entry.network.map(|n| n.errors as i64))
+    .bind(entry.network.map(|n| n.load_percent))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Dernières entrées, de la plus ancienne à la plus récente (ordre d'affichage de la page de status)
+pub async fn recent_history(pool: &PgPool, count: i64) -> Result<Vec<HistoryEntry>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, HistoryRow>(&format!(
+        "SELECT {} FROM (
+             SELECT * FROM metrics_history ORDER BY recorded_at DESC LIMIT $1
+         ) recent
+         ORDER BY recorded_at",
+        HISTORY_COLUMNS
+    ))
+    .bind(count)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(HistoryEntry::from).collect())
+}
+
+/// Page de l'historique, les entrées les plus récentes d'abord
+pub async fn list_history(
+    pool: &PgPool,
+    since: Option<DateTime<Utc>>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<HistoryEntry>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, HistoryRow>(&format!(
+        "SELECT {} FROM metrics_history
+         WHERE $1::timestamptz IS NULL OR recorded_at >= $1
+         ORDER BY recorded_at DESC
+         LIMIT $2 OFFSET $3",
+        HISTORY_COLUMNS
+    ))
+    .bind(since)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(HistoryEntry::from).collect())
+}
+
+/// Nombre d'entrées de l'historique, depuis `since` si fourni
+pub async fn count_history(pool: &PgPool, since: Option<DateTime<Utc>>) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT count(*) FROM metrics_history WHERE $1::timestamptz IS NULL OR recorded_at >= $1",
+    )
+    .bind(since)
+    .fetch_one(pool)
+    .await
+}
+
+/// Supprime les entrées plus anciennes que `retention_days` jours.
+///
+/// # Returns
+///
+/// * `Result<u64, sqlx::Error>` - Nombre d'entrées supprimées
+pub async fn prune_history(pool: &PgPool, retention_days: u32) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM metrics_history WHERE recorded_at < now() - make_interval(days => $1)")
+        .bind(retention_days as i32)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Disponibilité et temps de réponse moyen sur 24 heures, 7 jours et 30 jours.
+///
+/// Une mesure compte comme disponible si l'API et la base de données répondaient.
+pub async fn uptime_stats(pool: &PgPool) -> Result<Vec<UptimeStats>, sqlx::Error> {
+    sqlx::query_as::<_, UptimeStats>(
+        "SELECT w.label AS window,
+                count(h.id) AS samples,
+                100.0 * count(h.id) FILTER (WHERE h.db_connected AND h.status <> $1) / NULLIF(count(h.id), 0)::float8
+                    AS uptime_percent,
+                avg(h.response_time_ms)::float8 AS avg_response_time_ms
+         FROM (VALUES ('24h', interval '24 hours'), ('7d', interval '7 days'), ('30d', interval '30 days')) w(label, span)
+         LEFT JOIN metrics_history h ON h.recorded_at >= now() - w.span
+         GROUP BY w.label, w.span
+         ORDER BY w.span",
+    )
+    .bind(DEGRADED_STATUS)
+    .fetch_all(pool)
+    .await
+}
+
+/// Payload de la tâche de rapport de disponibilité
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UptimeReport {}
+
+impl Job for UptimeReport {
+    const KIND: &'static str = "reports.uptime";
+}
+
+/// Calcule la disponibilité sur 24h/7j/30j, conservée comme résultat de la tâche
+pub struct UptimeReportJob;
+
+#[async_trait]
+impl JobHandler for UptimeReportJob {
+    type Payload = UptimeReport;
+    type Output = Vec<UptimeStats>;
+
+    async fn run(&self, db: &DatabaseManager, _payload: UptimeReport) -> Result<Vec<UptimeStats>, JobError> {
+        uptime_stats(db.get_pool()).await.map_err(|e| JobError::Failed(e.to_string()))
+    }
+}
+
+/// Remplace les dernières métriques conservées.
+pub async fn save_snapshot(pool: &PgPool, metrics: &PerformanceMetrics) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO metrics_snapshot (id, metrics, saved_at) VALUES (1, $1, now())
+         ON CONFLICT (id) DO UPDATE SET metrics = EXCLUDED.metrics, saved_at = EXCLUDED.saved_at",
+    )
+    .bind(Json(metrics))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Dernières métriques conservées, si elles datent de moins de `max_age`.
+///
+/// Un instantané illisible (format d'une version précédente) est ignoré.
+pub async fn load_snapshot(pool: &PgPool, max_age: Duration) -> Result<Option<PerformanceMetrics>, sqlx::Error> {
+    let snapshot = sqlx::query_scalar::<_, serde_json::Value>("SELECT metrics FROM metrics_snapshot WHERE id = 1")
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(snapshot
+        .and_then(|metrics| serde_json::from_value::<PerformanceMetrics>(metrics).ok())
+        .filter(|metrics| Utc::now().signed_duration_since(metrics.timestamp) < max_age))
+}
@@ -0,0 +1,29 @@
+//! # Services Module
+//!
+//! Ce module regroupe les sous-systèmes applicatifs qui ne sont pas liés
+//! à une route précise (workers en arrière-plan, intégrations externes...).
+
+pub mod alerts;
+pub mod anomalies;
+pub mod auth;
+pub mod batch;
+pub mod cluster;
+pub mod cors;
+pub mod events;
+pub mod health;
+pub mod incidents;
+pub mod jobs;
+pub mod maintenance;
+pub mod metrics;
+pub mod monitoring;
+pub mod post;
+pub mod queries;
+pub mod runtime;
+pub mod scheduler;
+pub mod search;
+pub mod selftest;
+pub mod storage;
+pub mod supervisor;
+pub mod uploads;
+pub mod user;
+pub mod webhooks;
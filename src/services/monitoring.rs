@@ -0,0 +1,190 @@
+//! # Monitoring Service
+//!
+//! Ce module surveille les dépendances externes listées dans `[[monitoring.targets]]`,
+//! pour faire de la page de status un tableau de bord multi-services :
+//! - à chaque passage de la tâche des métriques, toutes les cibles sont sondées en
+//!   parallèle (`GET` pour `http`, ouverture d'une connexion pour `tcp`), dans la
+//!   limite de `timeout_ms`
+//! - chaque résultat est enregistré dans `monitor_checks`, purgé comme l'historique
+//!   des métriques par la tâche planifiée `history_pruning` (`[status] history_retention_days`)
+//! - la page de status et `GET /api/status/targets` affichent l'état et les dernières
+//!   sondes de chaque cible
+
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::{Client, StatusCode};
+use sqlx::{FromRow, PgPool};
+use std::{collections::HashMap, time::Duration};
+use tokio::{net::TcpStream, time::Instant};
+use tracing::info;
+
+use crate::{
+    config::{MonitorTarget, MonitoringConfig, TargetProbe},
+    middleware::trace::inject,
+    models::status::{TargetCheck, TargetStatus},
+};
+
+/// Sondes des dépendances externes, détenues par la tâche des métriques.
+pub struct Monitor {
+    targets: Vec<MonitorTarget>,
+    timeout: Duration,
+    client: Client,
+}
+
+impl Monitor {
+    pub fn new(settings: &MonitoringConfig) -> Self {
+        let timeout = Duration::from_millis(settings.timeout_ms);
+        let client = Client::builder().timeout(timeout).build().unwrap_or_default();
+
+        Self {
+            targets: settings.targets.clone(),
+            timeout,
+            client,
+        }
+    }
+
+    /// Sonde toutes les cibles en parallèle ; résultats dans l'ordre de la configuration
+    pub async fn probe_all(&self) -> Vec<(String, TargetCheck)> {
+        join_all(self.targets.iter().map(|target| async move {
+            (target.name.clone(), self.probe(&target.probe).await)
+        }))
+        .await
+    }
+
+    /// Sonde une cible
+    pub async fn probe(&self, probe: &TargetProbe) -> TargetCheck {
+        let checked_at = Utc::now();
+        let start = Instant::now();
+
+        let outcome = match probe {
+            TargetProbe::Http { url, expected_status } => self.probe_http(url, *expected_status).await,
+            TargetProbe::Tcp { address } => match tokio::time::timeout(self.timeout, TcpStream::connect(address)).await {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => Err(format!("no connection after {} ms", self.timeout.as_millis())),
+            },
+        };
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        match outcome {
+            Ok(()) => TargetCheck { checked_at, up: true, response_time_ms: Some(elapsed), error: None },
+            Err(error) => {
+                let timed_out = start.elapsed() >= self.timeout;
+                TargetCheck { checked_at, up: false, response_time_ms: (!timed_out).then_some(elapsed), error: Some(error) }
+            }
+        }
+    }
+
+    async fn probe_http(&self, url: &str, expected_status: Option<u16>) -> Result<(), String> {
+        let response = inject(self.client.get(url)).send().await.map_err(|e| {
+            if e.is_timeout() {
+                format!("no response after {} ms", self.timeout.as_millis())
+            } else {
+                e.to_string()
+            }
+        })?;
+
+        let status = response.status();
+        let up = match expected_status {
+            Some(expected) => status == StatusCode::from_u16(expected).unwrap_or_default(),
+            None => status.is_success() || status.is_redirection(),
+        };
+        if up {
+            Ok(())
+        } else {
+            Err(format!("unexpected status {}", status))
+        }
+    }
+}
+
+/// Ligne de `monitor_checks`
+#[derive(Debug, FromRow)]
+struct CheckRow {
+    target: String,
+    checked_at: DateTime<Utc>,
+    up: bool,
+    response_time_ms: Option<i64>,
+    error: Option<String>,
+}
+
+/// Enregistre les résultats d'un passage.
+pub async fn record_checks(pool: &PgPool, checks: &[(String, TargetCheck)]) -> Result<(), sqlx::Error> {
+    for (target, check) in checks {
+        if !check.up {
+            info!("Monitored target {} is down: {}", target, check.error.as_deref().unwrap_or("unknown error"));
+        }
+        sqlx::query(
+            "INSERT INTO monitor_checks (target, checked_at, up, response_time_ms, error)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(target)
+        .bind(check.checked_at)
+        .bind(check.up)
+        .bind(check.response_time_ms.map(|ms| ms as i64))
+        .bind(&check.error)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// État des cibles configurées, avec leurs `count` dernières sondes.
+///
+/// Les sondes d'une cible retirée de la configuration ne sont plus affichées.
+pub async fn target_statuses(pool: &PgPool, targets: &[MonitorTarget], count: i64) -> Result<Vec<TargetStatus>, sqlx::Error> {
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let names = targets.iter().map(|target| target.name.clone()).collect::<Vec<_>>();
+    let rows = sqlx::query_as::<_, CheckRow>(
+        "SELECT target, checked_at, up, response_time_ms, error FROM (
+             SELECT *, row_number() OVER (PARTITION BY target ORDER BY checked_at DESC) AS rank
+             FROM monitor_checks
+             WHERE target = ANY($1)
+         ) recent
+         WHERE rank <= $2
+         ORDER BY checked_at",
+    )
+    .bind(&names)
+    .bind(count)
+    .fetch_all(pool)
+    .await?;
+
+    let mut checks = HashMap::<String, Vec<TargetCheck>>::new();
+    for row in rows {
+        checks.entry(row.target).or_default().push(TargetCheck {
+            checked_at: row.checked_at,
+            up: row.up,
+            response_time_ms: row.response_time_ms.map(|ms| ms.max(0) as u64),
+            error: row.error,
+        });
+    }
+
+    Ok(targets
+        .iter()
+        .map(|target| TargetStatus {
+            name: target.name.clone(),
+            kind: match target.probe {
+                TargetProbe::Http { .. } => "http",
+                TargetProbe::Tcp { .. } => "tcp",
+            }
+            .to_string(),
+            endpoint: target.probe.endpoint().to_string(),
+            checks: checks.remove(&target.name).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Supprime les sondes plus anciennes que `retention_days` jours.
+///
+/// # Returns
+///
+/// * `Result<u64, sqlx::Error>` - Nombre de sondes supprimées
+pub async fn prune_checks(pool: &PgPool, retention_days: u32) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM monitor_checks WHERE checked_at < now() - make_interval(days => $1)")
+        .bind(retention_days as i32)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
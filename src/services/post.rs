@@ -0,0 +1,149 @@
+//! # Post Service
+//!
+//! Ce module regroupe les accès aux tables `posts` et `comments`. Les données liées
+//! sont chargées par jointure (auteur, nombre de commentaires) ou par une requête
+//! groupée par post, jamais par une requête par élément.
+
+use sqlx::{FromRow, PgPool};
+
+use crate::models::{
+    post::{Author, Comment, CommentDetail, NewComment, NewPost, Post, PostDetail, PostId, PostSummary},
+    user::UserId,
+};
+
+/// Contrainte liant un post à son auteur
+pub const POST_USER_FK: &str = "posts_user_id_fkey";
+/// Contrainte liant un commentaire à son post
+pub const COMMENT_POST_FK: &str = "comments_post_id_fkey";
+/// Contrainte liant un commentaire à son auteur
+pub const COMMENT_USER_FK: &str = "comments_user_id_fkey";
+
+/// Ligne issue d'une jointure avec l'auteur
+#[derive(FromRow)]
+struct WithAuthorName<T> {
+    #[sqlx(flatten)]
+    inner: T,
+    author_name: Option<String>,
+}
+
+impl WithAuthorName<Comment> {
+    fn into_detail(self) -> CommentDetail {
+        let author = self
+            .inner
+            .user_id
+            .zip(self.author_name)
+            .map(|(id, name)| Author { id, name });
+        CommentDetail { comment: self.inner, author }
+    }
+}
+
+/// Liste une page des posts d'un utilisateur, avec leur nombre de commentaires.
+pub async fn list_user_posts(pool: &PgPool, user_id: UserId, limit: i64, offset: i64) -> Result<Vec<PostSummary>, sqlx::Error> {
+    sqlx::query_as::<_, PostSummary>(
+        "SELECT p.*, count(c.id) AS comment_count
+         FROM posts p
+         LEFT JOIN comments c ON c.post_id = p.id
+         WHERE p.user_id = $1
+         GROUP BY p.id
+         ORDER BY p.created_at DESC, p.id DESC
+         LIMIT $2 OFFSET $3",
+    )
+    .bind(user_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Compte les posts d'un utilisateur.
+pub async fn count_user_posts(pool: &PgPool, user_id: UserId) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT count(*) FROM posts WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Crée un post ; un auteur inconnu produit une violation de clé étrangère.
+pub async fn create_post(pool: &PgPool, user_id: UserId, new_post: &NewPost) -> Result<Post, sqlx::Error> {
+    sqlx::query_as::<_, Post>("INSERT INTO posts (user_id, title, body) VALUES ($1, $2, $3) RETURNING *")
+        .bind(user_id)
+        .bind(&new_post.title)
+        .bind(&new_post.body)
+        .fetch_one(pool)
+        .await
+}
+
+/// Récupère un post avec son auteur et ses commentaires.
+pub async fn get_post_detail(pool: &PgPool, id: PostId) -> Result<Option<PostDetail>, sqlx::Error> {
+    let row = sqlx::query_as::<_, WithAuthorName<Post>>(
+        "SELECT p.*, u.name AS author_name
+         FROM posts p
+         JOIN users u ON u.id = p.user_id
+         WHERE p.id = $1",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    Ok(Some(PostDetail {
+        author: Author {
+            id: row.inner.user_id,
+            name: row.author_name.unwrap_or_default(),
+        },
+        comments: list_comments(pool, id).await?,
+        post: row.inner,
+    }))
+}
+
+/// Indique si un post existe.
+pub async fn post_exists(pool: &PgPool, id: PostId) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM posts WHERE id = $1)")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Supprime un post ; ses commentaires sont supprimés en cascade.
+///
+/// # Returns
+///
+/// * `Result<bool, sqlx::Error>` - `false` si le post n'existait pas
+pub async fn delete_post(pool: &PgPool, id: PostId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM posts WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Liste les commentaires d'un post, du plus ancien au plus récent, avec leur auteur.
+pub async fn list_comments(pool: &PgPool, post_id: PostId) -> Result<Vec<CommentDetail>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, WithAuthorName<Comment>>(
+        "SELECT c.*, u.name AS author_name
+         FROM comments c
+         LEFT JOIN users u ON u.id = c.user_id
+         WHERE c.post_id = $1
+         ORDER BY c.created_at, c.id",
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(WithAuthorName::into_detail).collect())
+}
+
+/// Ajoute un commentaire ; un post ou un auteur inconnu produit une violation de
+/// clé étrangère (`COMMENT_POST_FK` ou `COMMENT_USER_FK`).
+pub async fn create_comment(pool: &PgPool, post_id: PostId, new_comment: &NewComment) -> Result<Comment, sqlx::Error> {
+    sqlx::query_as::<_, Comment>("INSERT INTO comments (post_id, user_id, body) VALUES ($1, $2, $3) RETURNING *")
+        .bind(post_id)
+        .bind(new_comment.user_id)
+        .bind(&new_comment.body)
+        .fetch_one(pool)
+        .await
+}
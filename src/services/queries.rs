@@ -0,0 +1,128 @@
+//! # Query Insights Service
+//!
+//! Ce module relie la lenteur de l'API aux requêtes SQL qui la causent, à partir de
+//! l'extension `pg_stat_statements` (activée par `[status] query_insights`) :
+//! - à chaque passage de la tâche des métriques, les requêtes de la base courante
+//!   les plus coûteuses en temps total et en temps moyen sont relevées
+//! - sans l'extension (non créée ou absente de `shared_preload_libraries`), aucun
+//!   relevé n'est fait et la section est masquée sur la page de status
+//! - `GET /api/admin/queries` relit les statistiques à la demande
+
+use chrono::Utc;
+use sqlx::{FromRow, PgPool};
+use std::sync::Mutex;
+
+use crate::models::status::{QueryInsightsReport, QueryStat};
+
+/// Classement des requêtes de `pg_stat_statements`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryOrder {
+    /// Temps cumulé de toutes les exécutions
+    TotalTime,
+    /// Temps moyen d'une exécution
+    MeanTime,
+}
+
+impl QueryOrder {
+    fn column(self) -> &'static str {
+        match self {
+            Self::TotalTime => "total_exec_time",
+            Self::MeanTime => "mean_exec_time",
+        }
+    }
+}
+
+/// Ligne de `pg_stat_statements` (colonnes de PostgreSQL 13 et plus)
+#[derive(Debug, FromRow)]
+struct QueryStatRow {
+    queryid: Option<i64>,
+    query: Option<String>,
+    calls: i64,
+    total_exec_time: f64,
+    mean_exec_time: f64,
+    rows: i64,
+}
+
+impl From<QueryStatRow> for QueryStat {
+    fn from(row: QueryStatRow) -> Self {
+        Self {
+            query_id: row.queryid,
+            // Texte masqué par PostgreSQL aux rôles sans `pg_read_all_stats`
+            query: row.query.unwrap_or_else(|| "<insufficient privilege>".to_string()),
+            calls: row.calls.max(0) as u64,
+            total_time_ms: row.total_exec_time,
+            mean_time_ms: row.mean_exec_time,
+            rows: row.rows.max(0) as u64,
+        }
+    }
+}
+
+/// Indique si l'extension `pg_stat_statements` est créée dans la base
+pub async fn extension_installed(pool: &PgPool) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements')")
+        .fetch_one(pool)
+        .await
+}
+
+/// Requêtes de la base courante les plus coûteuses selon `order`
+pub async fn top_queries(pool: &PgPool, order: QueryOrder, limit: usize) -> Result<Vec<QueryStat>, sqlx::Error> {
+    let sql = format!(
+        "SELECT queryid, query, calls, total_exec_time, mean_exec_time, rows
+         FROM pg_stat_statements
+         WHERE dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
+         ORDER BY {} DESC
+         LIMIT $1",
+        order.column()
+    );
+    let rows: Vec<QueryStatRow> = sqlx::query_as(&sql).bind(limit as i64).fetch_all(pool).await?;
+
+    Ok(rows.into_iter().map(QueryStat::from).collect())
+}
+
+/// Relevé des requêtes les plus coûteuses ; `None` sans l'extension
+pub async fn collect(pool: &PgPool, limit: usize) -> Result<Option<QueryInsightsReport>, sqlx::Error> {
+    if !extension_installed(pool).await? {
+        return Ok(None);
+    }
+
+    Ok(Some(QueryInsightsReport {
+        collected_at: Utc::now(),
+        by_total_time: top_queries(pool, QueryOrder::TotalTime, limit).await?,
+        by_mean_time: top_queries(pool, QueryOrder::MeanTime, limit).await?,
+    }))
+}
+
+/// Dernier relevé de `pg_stat_statements`, partagé entre la tâche des métriques et la page de status.
+#[derive(Debug)]
+pub struct QueryInsights {
+    enabled: bool,
+    top: usize,
+    latest: Mutex<Option<QueryInsightsReport>>,
+}
+
+impl QueryInsights {
+    /// Collecteur des `top` requêtes les plus coûteuses ; inactif si `enabled` est faux
+    pub fn new(enabled: bool, top: usize) -> Self {
+        Self {
+            enabled,
+            top: top.max(1),
+            latest: Mutex::new(None),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Relève les statistiques et conserve le résultat ; `None` sans l'extension
+    pub async fn refresh(&self, pool: &PgPool) -> Result<Option<QueryInsightsReport>, sqlx::Error> {
+        let report = collect(pool, self.top).await?;
+        *self.latest.lock().unwrap() = report.clone();
+        Ok(report)
+    }
+
+    /// Dernier relevé ; `None` avant le premier passage ou sans l'extension
+    pub fn latest(&self) -> Option<QueryInsightsReport> {
+        self.latest.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,77 @@
+//! # Runtime Service
+//!
+//! Ce module mesure l'état du runtime Tokio, pour rendre visible une famine des
+//! tâches asynchrones que les seuls chiffres CPU et mémoire ne montrent pas :
+//! - tâches vivantes et profondeur de la file globale du scheduler
+//! - profondeur de la file du pool de threads bloquants (seulement si l'application
+//!   est compilée avec `RUSTFLAGS="--cfg tokio_unstable"`)
+//! - part du temps pendant lequel les workers exécutent des tâches, entre deux mesures
+//!
+//! La tâche des métriques mesure le runtime à chaque passage (page de status) ; la
+//! vérification `runtime` de `/api/help/health` le mesure sur une courte fenêtre.
+
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, RuntimeMetrics};
+
+use crate::models::status::RuntimeUsage;
+
+/// Compteurs de référence du runtime : chaque mesure couvre la période depuis la précédente
+pub struct RuntimeSampler {
+    metrics: RuntimeMetrics,
+    last_sample: Instant,
+    busy: Duration,
+}
+
+impl RuntimeSampler {
+    /// Sampler du runtime courant ; doit être appelé depuis une tâche Tokio
+    pub fn new() -> Self {
+        let metrics = Handle::current().metrics();
+        let busy = total_busy(&metrics);
+        Self { metrics, last_sample: Instant::now(), busy }
+    }
+
+    /// État du runtime, avec l'occupation des workers depuis la mesure précédente
+    pub fn sample(&mut self) -> RuntimeUsage {
+        let elapsed = self.last_sample.elapsed();
+        let busy = total_busy(&self.metrics);
+        let workers = self.metrics.num_workers();
+
+        let available = elapsed.as_secs_f64() * workers.max(1) as f64;
+        let busy_percent = if available > 0.0 {
+            ((busy - self.busy).as_secs_f64() / available * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        self.last_sample = Instant::now();
+        self.busy = busy;
+
+        RuntimeUsage {
+            workers,
+            alive_tasks: self.metrics.num_alive_tasks(),
+            global_queue_depth: self.metrics.global_queue_depth(),
+            blocking_queue_depth: blocking_queue_depth(&self.metrics),
+            busy_percent,
+        }
+    }
+}
+
+impl Default for RuntimeSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Temps cumulé d'exécution de tâches, tous workers confondus
+fn total_busy(metrics: &RuntimeMetrics) -> Duration {
+    (0..metrics.num_workers()).map(|worker| metrics.worker_total_busy_duration(worker)).sum()
+}
+
+#[cfg(tokio_unstable)]
+fn blocking_queue_depth(metrics: &RuntimeMetrics) -> Option<usize> {
+    Some(metrics.blocking_queue_depth())
+}
+
+#[cfg(not(tokio_unstable))]
+fn blocking_queue_depth(_metrics: &RuntimeMetrics) -> Option<usize> {
+    None
+}
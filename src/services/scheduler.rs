@@ -0,0 +1,199 @@
+//! # Scheduler Service
+//!
+//! Ce module exécute les tâches périodiques selon les expressions cron de
+//! `[scheduler.tasks]` (en UTC) :
+//! - `history_pruning` : purge de l'historique des métriques et des sondes plus ancien
+//!   que `[status] history_retention_days`
+//! - `uptime_report` : soumission d'une tâche asynchrone `reports.uptime`, dont le
+//!   résultat est la disponibilité sur 24h/7j/30j
+//! - `cache_warmup` : nouveau calcul des réponses de `[cache.routes]` sans paramètre de
+//!   chemin, pour que les clients ne paient pas le premier calcul après expiration
+//!
+//! Chaque tâche planifiée est supervisée (`scheduled_<nom>`) : expression cron, dernier
+//! et prochain passage, passages en échec et dernière erreur sont rapportés par la
+//! vérification `background_tasks` de `/api/help/health`.
+//!
+//! ```ignore
+//! // Ajoutez vos tâches à `scheduler()`, puis planifiez-les dans la configuration :
+//! // [scheduler.tasks]
+//! // reports = "0 2 * * 1"
+//! Scheduler::new().register("reports", |state| async move { generate_reports(&state).await })
+//! ```
+
+use chrono::{DateTime, Utc};
+use croner::Cron;
+use futures::future::BoxFuture;
+use reqwest::header::CACHE_CONTROL;
+use std::{collections::BTreeMap, error::Error, future::Future, sync::Arc};
+use tracing::info;
+
+use crate::{
+    config::SchedulerConfig,
+    middleware::trace::{inject, TraceContext},
+    services::{
+        jobs,
+        metrics::{prune_history, UptimeReport},
+        monitoring::prune_checks,
+    },
+    state::AppState,
+};
+
+/// Résultat d'un passage d'une tâche planifiée
+pub type TaskResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+type BoxedTask = Arc<dyn Fn(AppState) -> BoxFuture<'static, TaskResult> + Send + Sync>;
+
+/// Registre des tâches périodiques, planifiées par leur nom dans `[scheduler.tasks]`.
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: BTreeMap<String, BoxedTask>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre une tâche sous un nom de `[scheduler.tasks]`.
+    pub fn register<F, Fut>(mut self, name: &str, task: F) -> Self
+    where
+        F: Fn(AppState) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = TaskResult> + Send + 'static,
+    {
+        let task: BoxedTask = Arc::new(move |state| Box::pin(task(state)));
+        self.tasks.insert(name.to_string(), task);
+        self
+    }
+
+    /// Noms des tâches enregistrées, triés
+    pub fn names(&self) -> Vec<&str> {
+        self.tasks.keys().map(String::as_str).collect()
+    }
+
+    /// Exécute immédiatement un passage d'une tâche, hors planning.
+    pub async fn run(&self, name: &str, state: &AppState) -> TaskResult {
+        let task = self.tasks.get(name).ok_or_else(|| format!("unknown scheduled task: {}", name))?;
+        task(state.clone()).await
+    }
+
+    /// Démarre les tâches de `settings` sous la supervision de `state.tasks`.
+    ///
+    /// Les expressions sont toutes vérifiées avant le démarrage : une tâche inconnue ou
+    /// une expression invalide est refusée. Retourne le nombre de tâches planifiées.
+    pub fn start(&self, state: &AppState, settings: &SchedulerConfig) -> Result<usize, String> {
+        let mut planned = Vec::new();
+        for (name, expression) in &settings.tasks {
+            let task = self.tasks.get(name).ok_or_else(|| {
+                format!("unknown scheduled task: {} (registered: {})", name, self.names().join(", "))
+            })?;
+            let cron = parse_schedule(expression).map_err(|e| format!("[scheduler.tasks] {}: {}", name, e))?;
+            planned.push((name, expression, cron, task.clone()));
+        }
+
+        for (name, expression, cron, task) in &planned {
+            let tasks = state.tasks.clone();
+            let state = state.clone();
+            let cron = cron.clone();
+            let task = task.clone();
+            tasks.spawn_scheduled(&format!("scheduled_{}", name), expression, move |handle| {
+                let state = state.clone();
+                let cron = cron.clone();
+                let task = task.clone();
+                async move {
+                    let Some(mut next) = next_run(&cron, Utc::now()) else {
+                        return;
+                    };
+                    handle.plan(next);
+
+                    loop {
+                        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                        tokio::time::sleep(wait).await;
+
+                        let outcome = TraceContext::new_root().scope(task(state.clone())).await;
+                        // Après un réveil en avance sur l'horloge, le passage prévu ne se répète pas
+                        let Some(following) = next_run(&cron, next.max(Utc::now())) else {
+                            return;
+                        };
+                        next = following;
+                        handle.record(outcome, (next - Utc::now()).to_std().unwrap_or_default());
+                    }
+                }
+            });
+            info!("Scheduled task {} ({})", name, expression);
+        }
+        Ok(planned.len())
+    }
+}
+
+/// Lit une expression cron : 5 champs, 6 avec les secondes en tête, ou `@hourly`, `@daily`...
+pub fn parse_schedule(expression: &str) -> Result<Cron, String> {
+    let cron = Cron::new(expression).with_seconds_optional().parse().map_err(|e| e.to_string())?;
+    next_run(&cron, Utc::now()).ok_or_else(|| format!("{} never matches", expression))?;
+    Ok(cron)
+}
+
+/// Prochain passage strictement après `after`
+pub fn next_run(cron: &Cron, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    cron.find_next_occurrence(&after, false).ok()
+}
+
+/// Construit le registre des tâches périodiques.
+///
+/// Ajoutez ici vos propres tâches : `.register("reports", generate_reports)`
+pub fn scheduler() -> Scheduler {
+    Scheduler::new()
+        .register("history_pruning", |state| async move { history_pruning(&state).await })
+        .register("uptime_report", |state| async move { uptime_report(&state).await })
+        .register("cache_warmup", |state| async move {
+            let base_url = format!("http://{}", state.config.server_address());
+            warm_cache(&state, &base_url).await.map(drop)
+        })
+}
+
+/// Purge l'historique des métriques et des sondes au-delà de `[status] history_retention_days`
+pub async fn history_pruning(state: &AppState) -> TaskResult {
+    let pool = state.db.get_pool();
+    let retention_days = state.config.status.history_retention_days;
+    let metrics = prune_history(pool, retention_days).await?;
+    let checks = prune_checks(pool, retention_days).await?;
+    info!("Pruned {} metrics history entries and {} monitored target checks", metrics, checks);
+    Ok(())
+}
+
+/// Soumet la tâche asynchrone du rapport de disponibilité
+pub async fn uptime_report(state: &AppState) -> TaskResult {
+    let job = jobs::enqueue(state.db.get_pool(), &UptimeReport {}).await?;
+    info!("Uptime report queued as job {}", job.id);
+    Ok(())
+}
+
+/// Recalcule les réponses en cache des routes de `[cache.routes]` sans paramètre de chemin,
+/// en appelant l'instance sur `base_url`. `Cache-Control: no-cache` force un nouveau calcul,
+/// qui remplace l'entrée ; seule la variante des en-têtes par défaut est préparée.
+///
+/// # Returns
+///
+/// * `Result<usize, _>` - Nombre de routes recalculées, erreur si l'une d'elles a échoué
+pub async fn warm_cache(state: &AppState, base_url: &str) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let mut routes: Vec<&String> = state.config.cache.routes.keys().filter(|route| !route.contains('{')).collect();
+    routes.sort();
+
+    let client = reqwest::Client::new();
+    let mut failed = Vec::new();
+    for route in &routes {
+        let response = inject(client.get(format!("{}{}", base_url, route)))
+            .header(CACHE_CONTROL, "no-cache")
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => failed.push(format!("{} ({})", route, response.status())),
+            Err(e) => failed.push(format!("{} ({})", route, e)),
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(format!("cache warmup failed for {}", failed.join(", ")).into());
+    }
+    Ok(routes.len())
+}
@@ -0,0 +1,44 @@
+//! # Search Service
+//!
+//! Ce module regroupe les requêtes de recherche plein texte. Les termes sont interprétés
+//! par `websearch_to_tsquery`, qui accepte toute saisie utilisateur sans erreur de syntaxe,
+//! et comparés à la colonne générée `posts.search` (index GIN).
+
+use sqlx::PgPool;
+
+use crate::models::search::SearchHit;
+
+/// Configuration de recherche Postgres, identique à celle de la colonne générée
+const TEXT_SEARCH_CONFIG: &str = "english";
+
+/// Options de `ts_headline` : extraits courts, termes entourés de `<mark>`
+const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15, MaxFragments=2";
+
+/// Recherche une page de posts, classés par pertinence.
+pub async fn search_posts(pool: &PgPool, terms: &str, limit: i64, offset: i64) -> Result<Vec<SearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, SearchHit>(
+        "SELECT p.id, p.user_id, p.title, p.created_at,
+                ts_headline($1::regconfig, p.body, query, $3) AS headline,
+                ts_rank(p.search, query) AS rank
+         FROM posts p, websearch_to_tsquery($1::regconfig, $2) query
+         WHERE p.search @@ query
+         ORDER BY rank DESC, p.id DESC
+         LIMIT $4 OFFSET $5",
+    )
+    .bind(TEXT_SEARCH_CONFIG)
+    .bind(terms)
+    .bind(HEADLINE_OPTIONS)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Compte les posts correspondant aux termes.
+pub async fn count_posts(pool: &PgPool, terms: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT count(*) FROM posts WHERE search @@ websearch_to_tsquery($1::regconfig, $2)")
+        .bind(TEXT_SEARCH_CONFIG)
+        .bind(terms)
+        .fetch_one(pool)
+        .await
+}
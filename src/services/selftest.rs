@@ -0,0 +1,161 @@
+//! # Self-Test Service
+//!
+//! Ce module exécute l'auto-test de `POST /api/help/selftest`, à lancer juste après un
+//! déploiement. Contrairement aux vérifications de `/api/help/health`, qui observent,
+//! chaque étape exerce réellement un sous-système :
+//! - `database_round_trip` : requête simple sur le pool
+//! - `database_write_rollback` : écriture dans `app_events`, relue puis annulée
+//! - `cache_read_write` : entrée témoin écrite puis relue dans le cache des réponses
+//! - `background_tasks` : chaque tâche supervisée tourne et n'a pas manqué son passage
+//! - `outbound_http` : appel de `[monitoring] ping_url` (le ping de cette instance sans valeur)
+//!
+//! Les étapes s'exécutent l'une après l'autre, chacune bornée par `[health] check_timeout_ms`.
+
+use chrono::Utc;
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    middleware::trace::inject,
+    models::{
+        help::{HealthStatus, SelfTestOutcome, SelfTestReport, SelfTestStep},
+        status::ping_url,
+    },
+    services::health::{BackgroundTasksCheck, HealthCheck},
+    state::AppState,
+};
+
+/// Résultat d'une étape avant mesure de sa durée
+type StepResult = (SelfTestOutcome, Option<String>);
+
+/// Exécute toutes les étapes et construit le rapport
+pub async fn run(state: &AppState) -> SelfTestReport {
+    let started_at = Utc::now();
+    let start = Instant::now();
+    let timeout = Duration::from_millis(state.config.health.check_timeout_ms);
+
+    let steps = vec![
+        step("database_round_trip", timeout, database_round_trip(state)).await,
+        step("database_write_rollback", timeout, database_write_rollback(state)).await,
+        step("cache_read_write", timeout, async { cache_read_write(state) }).await,
+        step("background_tasks", timeout, background_tasks(state)).await,
+        step("outbound_http", timeout, outbound_http(state)).await,
+    ];
+
+    let failed: Vec<&str> = steps
+        .iter()
+        .filter(|step| step.outcome == SelfTestOutcome::Failed)
+        .map(|step| step.name.as_str())
+        .collect();
+    if failed.is_empty() {
+        info!("Self-test passed");
+    } else {
+        warn!("Self-test failed: {}", failed.join(", "));
+    }
+
+    SelfTestReport {
+        passed: failed.is_empty(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        steps,
+    }
+}
+
+/// Exécute une étape dans la limite de `timeout` et mesure sa durée
+async fn step(name: &str, timeout: Duration, run: impl Future<Output = StepResult>) -> SelfTestStep {
+    let start = Instant::now();
+    let (outcome, message) = tokio::time::timeout(timeout, run)
+        .await
+        .unwrap_or_else(|_| failed(format!("timed out after {} ms", timeout.as_millis())));
+
+    SelfTestStep {
+        name: name.to_string(),
+        outcome,
+        duration_ms: start.elapsed().as_millis() as u64,
+        message,
+    }
+}
+
+fn passed() -> StepResult {
+    (SelfTestOutcome::Passed, None)
+}
+
+fn failed(message: impl Into<String>) -> StepResult {
+    (SelfTestOutcome::Failed, Some(message.into()))
+}
+
+fn skipped(message: impl Into<String>) -> StepResult {
+    (SelfTestOutcome::Skipped, Some(message.into()))
+}
+
+async fn database_round_trip(state: &AppState) -> StepResult {
+    match sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(state.db.get_pool()).await {
+        Ok(1) => passed(),
+        Ok(value) => failed(format!("unexpected result {}", value)),
+        Err(e) => failed(e.to_string()),
+    }
+}
+
+/// Écrit une ligne dans une transaction annulée, puis vérifie qu'elle n'a pas été conservée
+async fn database_write_rollback(state: &AppState) -> StepResult {
+    let pool = state.db.get_pool();
+    let written = async {
+        let mut tx = pool.begin().await?;
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO app_events (kind, message) VALUES ('selftest', 'Self-test write') RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let read: i64 = sqlx::query_scalar("SELECT count(*) FROM app_events WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.rollback().await?;
+        let kept: i64 = sqlx::query_scalar("SELECT count(*) FROM app_events WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await?;
+        Ok::<_, sqlx::Error>((read, kept))
+    };
+
+    match written.await {
+        Ok((1, 0)) => passed(),
+        Ok((0, _)) => failed("written row could not be read back"),
+        Ok(_) => failed("row was kept after rollback"),
+        Err(e) => failed(e.to_string()),
+    }
+}
+
+fn cache_read_write(state: &AppState) -> StepResult {
+    match state.response_cache.round_trip(&format!("selftest:{}", Uuid::new_v4())) {
+        Some(true) => passed(),
+        Some(false) => failed("written entry could not be read back"),
+        None => skipped("response cache is disabled"),
+    }
+}
+
+/// Chaque tâche supervisée doit tourner et ne pas avoir manqué son passage prévu
+async fn background_tasks(state: &AppState) -> StepResult {
+    if state.tasks.report().is_empty() {
+        return skipped("no background task is running");
+    }
+    let probe = BackgroundTasksCheck::new(state.tasks.clone()).required().check().await;
+    match probe.status {
+        HealthStatus::Healthy => passed(),
+        _ => failed(probe.message.unwrap_or_default()),
+    }
+}
+
+async fn outbound_http(state: &AppState) -> StepResult {
+    let url = ping_url(&state.config);
+    match inject(reqwest::Client::new().get(&url)).send().await {
+        Ok(response) if response.status().is_success() => passed(),
+        Ok(response) => failed(format!("{} returned {}", url, response.status())),
+        Err(e) => failed(format!("{}: {}", url, e)),
+    }
+}
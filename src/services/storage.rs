@@ -0,0 +1,91 @@
+//! # Storage Service
+//!
+//! Ce module définit le trait `Storage`, l'abstraction du stockage des fichiers,
+//! et son implémentation sur disque local (`LocalStorage`).
+//!
+//! Les fichiers sont lus et écrits en flux ; une autre implémentation (S3, GCS...)
+//! peut être branchée dans `AppState` sans toucher aux handlers.
+
+use async_trait::async_trait;
+use std::{
+    io::{self, SeekFrom},
+    path::PathBuf,
+    pin::Pin,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWrite};
+
+/// Flux d'écriture vers le stockage
+pub type StorageWriter = Pin<Box<dyn AsyncWrite + Send>>;
+
+/// Flux de lecture depuis le stockage
+pub type StorageReader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Stockage des fichiers, adressés par une clé opaque.
+#[async_trait]
+pub trait Storage: Send + Sync + 'static {
+    /// Ouvre un flux d'écriture pour la clé (le contenu existant est remplacé)
+    async fn writer(&self, key: &str) -> io::Result<StorageWriter>;
+
+    /// Ouvre un flux de lecture ; `ErrorKind::NotFound` si la clé n'existe pas
+    async fn reader(&self, key: &str) -> io::Result<StorageReader>;
+
+    /// Ouvre un flux de lecture limité à `len` octets à partir de `start`.
+    ///
+    /// L'implémentation par défaut lit et ignore les octets précédant `start` ;
+    /// un stockage qui sait lire une plage (seek, `GET` avec `Range` sur S3) doit la redéfinir.
+    async fn range_reader(&self, key: &str, start: u64, len: u64) -> io::Result<StorageReader> {
+        let mut reader = self.reader(key).await?;
+        tokio::io::copy(&mut (&mut reader).take(start), &mut tokio::io::sink()).await?;
+        Ok(Box::pin(reader.take(len)))
+    }
+
+    /// Supprime le contenu associé à la clé (sans erreur s'il n'existe pas)
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+/// Stockage sur disque local, un fichier par clé.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Chemin du fichier d'une clé ; les clés sont générées par l'application,
+    /// les séparateurs de chemin y sont tout de même refusés
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        if key.is_empty() || key.contains(['/', '\\']) || key.starts_with('.') {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid storage key"));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn writer(&self, key: &str) -> io::Result<StorageWriter> {
+        let path = self.path(key)?;
+        tokio::fs::create_dir_all(&self.root).await?;
+        Ok(Box::pin(tokio::fs::File::create(path).await?))
+    }
+
+    async fn reader(&self, key: &str) -> io::Result<StorageReader> {
+        Ok(Box::pin(tokio::fs::File::open(self.path(key)?).await?))
+    }
+
+    async fn range_reader(&self, key: &str, start: u64, len: u64) -> io::Result<StorageReader> {
+        let mut file = tokio::fs::File::open(self.path(key)?).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+        Ok(Box::pin(file.take(len)))
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! # Supervisor Service
+//!
+//! Ce module supervise les tâches de fond (métriques, envoi des webhooks, tâches
+//! asynchrones...), pour qu'une tâche morte ne passe pas inaperçue :
+//! - une tâche qui panique est redémarrée avec un backoff exponentiel
+//!   (1 seconde, puis 2, 4... jusqu'à 1 minute)
+//! - chaque passage est rapporté avec `TaskHandle::record` : dernier passage,
+//!   prochain passage prévu, nombre de passages, d'échecs et de redémarrages
+//!   (les tâches du planificateur y ajoutent leur expression cron, voir `spawn_scheduled`)
+//! - la vérification `background_tasks` de `/api/help/health` signale une tâche en
+//!   cours de redémarrage ou en retard sur son passage prévu
+//! - les panics et les passages en échec sont signalés à Sentry s'il est configuré
+//!
+//! ```ignore
+//! supervisor.spawn("reports", move |task| {
+//!     let db = db.clone();
+//!     async move {
+//!         loop {
+//!             let outcome = generate_reports(&db).await;
+//!             task.record(outcome, Duration::from_secs(60));
+//!             tokio::time::sleep(Duration::from_secs(60)).await;
+//!         }
+//!     }
+//! });
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::{
+    any::Any,
+    fmt::Display,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing::{error, warn};
+
+use crate::{
+    models::help::{TaskHealth, TaskState},
+    services::webhooks::backoff_delay,
+};
+
+/// Délai avant le premier redémarrage (secondes), doublé à chaque panic consécutif
+const RESTART_BASE_SECONDS: u64 = 1;
+
+/// Délai maximal entre deux redémarrages (secondes)
+const RESTART_MAX_SECONDS: u64 = 60;
+
+/// Superviseur des tâches de fond, détenu par l'état de l'application.
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    tasks: Mutex<Vec<Arc<Mutex<TaskHealth>>>>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Démarre une tâche supervisée.
+    ///
+    /// `run` construit la boucle de la tâche ; il est rappelé à chaque redémarrage
+    /// après un panic. Une boucle qui se termine est également redémarrée.
+    pub fn spawn<F, Fut>(&self, name: &str, run: F)
+    where
+        F: Fn(TaskHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.start(name, None, run);
+    }
+
+    /// Démarre une tâche supervisée planifiée par l'expression cron `schedule`, rapportée
+    /// avec son suivi.
+    pub fn spawn_scheduled<F, Fut>(&self, name: &str, schedule: &str, run: F)
+    where
+        F: Fn(TaskHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.start(name, Some(schedule.to_string()), run);
+    }
+
+    fn start<F, Fut>(&self, name: &str, schedule: Option<String>, run: F)
+    where
+        F: Fn(TaskHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let health = Arc::new(Mutex::new(TaskHealth {
+            name: name.to_string(),
+            schedule,
+            state: TaskState::Running,
+            last_run: None,
+            next_run: None,
+            runs: 0,
+            failures: 0,
+            restarts: 0,
+            last_error: None,
+        }));
+        self.tasks.lock().unwrap().push(health.clone());
+
+        let name = name.to_string();
+        tokio::spawn(async move {
+            let handle = TaskHandle { health: health.clone() };
+            // Panics consécutifs, sans passage réussi entre eux
+            let mut consecutive = 0;
+
+            loop {
+                let runs_before = health.lock().unwrap().runs;
+                let reason = match tokio::spawn(run(handle.clone())).await {
+                    Ok(()) => "task loop returned".to_string(),
+                    Err(e) if e.is_panic() => format!("panicked: {}", panic_message(e.into_panic())),
+                    Err(e) => e.to_string(),
+                };
+
+                if health.lock().unwrap().runs > runs_before {
+                    consecutive = 0;
+                }
+                consecutive += 1;
+                let delay = backoff_delay(consecutive, RESTART_BASE_SECONDS, RESTART_MAX_SECONDS);
+                error!("Background task {} stopped ({}), restarting in {}s", name, reason, delay);
+
+                {
+                    let mut health = health.lock().unwrap();
+                    health.state = TaskState::Restarting;
+                    health.restarts += 1;
+                    health.last_error = Some(reason);
+                }
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+                health.lock().unwrap().state = TaskState::Running;
+            }
+        });
+    }
+
+    /// État des tâches supervisées, dans l'ordre de démarrage
+    pub fn report(&self) -> Vec<TaskHealth> {
+        self.tasks.lock().unwrap().iter().map(|health| health.lock().unwrap().clone()).collect()
+    }
+}
+
+/// Accès d'une tâche supervisée à son propre suivi.
+#[derive(Debug, Clone)]
+pub struct TaskHandle {
+    health: Arc<Mutex<TaskHealth>>,
+}
+
+impl TaskHandle {
+    /// Annonce le prochain passage, avant le premier passage d'une tâche planifiée.
+    pub fn plan(&self, next_run: DateTime<Utc>) {
+        self.health.lock().unwrap().next_run = Some(next_run);
+    }
+
+    /// Enregistre la fin d'un passage ; le suivant est prévu dans `next_in`.
+    pub fn record<E: Display>(&self, outcome: Result<(), E>, next_in: Duration) {
+        let now = Utc::now();
+        let mut health = self.health.lock().unwrap();
+        health.runs += 1;
+        health.last_run = Some(now);
+        health.next_run = chrono::Duration::from_std(next_in).ok().map(|next_in| now + next_in);
+
+        if let Err(e) = outcome {
+            warn!("Background task {} failed: {}", health.name, e);
+            crate::reporting::capture_task_failure(&health.name, &e.to_string());
+            health.failures += 1;
+            health.last_error = Some(e.to_string());
+        }
+    }
+}
+
+/// Message d'un panic (`panic!("...")` ou `panic!("{}", ...)`)
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
@@ -0,0 +1,48 @@
+//! # Uploads Service
+//!
+//! Ce module gère les métadonnées des fichiers envoyés (table `uploads`).
+//! Le contenu est écrit séparément dans le `Storage` configuré.
+
+use sqlx::PgPool;
+use crate::models::uploads::{Upload, UploadId};
+
+/// Enregistre les métadonnées d'un fichier déjà écrit dans le stockage.
+pub async fn insert_upload(
+    pool: &PgPool,
+    id: UploadId,
+    filename: &str,
+    content_type: &str,
+    size_bytes: i64,
+    sha256: &str,
+    storage_key: &str,
+) -> Result<Upload, sqlx::Error> {
+    sqlx::query_as::<_, Upload>(
+        "INSERT INTO uploads (id, filename, content_type, size_bytes, sha256, storage_key)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(filename)
+    .bind(content_type)
+    .bind(size_bytes)
+    .bind(sha256)
+    .bind(storage_key)
+    .fetch_one(pool)
+    .await
+}
+
+/// Récupère les métadonnées d'un fichier.
+pub async fn get_upload(pool: &PgPool, id: UploadId) -> Result<Option<Upload>, sqlx::Error> {
+    sqlx::query_as::<_, Upload>("SELECT * FROM uploads WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Supprime les métadonnées d'un fichier et les retourne, s'il existait.
+pub async fn delete_upload(pool: &PgPool, id: UploadId) -> Result<Option<Upload>, sqlx::Error> {
+    sqlx::query_as::<_, Upload>("DELETE FROM uploads WHERE id = $1 RETURNING *")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
@@ -0,0 +1,88 @@
+//! # User Service
+//!
+//! Ce module regroupe les accès à la table `users` pour la ressource d'exemple.
+//! Les handlers n'écrivent jamais de SQL : ils passent par ces fonctions.
+
+use sqlx::{PgExecutor, PgPool, QueryBuilder};
+
+use crate::{
+    extractors::query::QueryOptions,
+    models::user::{NewUser, UpdateUser, User, UserFields, UserId},
+};
+
+/// Liste une page d'utilisateurs.
+pub async fn list_users(
+    pool: &PgPool,
+    options: &QueryOptions<UserFields>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<User>, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT * FROM users WHERE true");
+    options.push_conditions(&mut builder);
+    options.push_order_by(&mut builder, "id");
+    builder.push(" LIMIT ").push_bind(limit);
+    builder.push(" OFFSET ").push_bind(offset);
+
+    builder.build_query_as::<User>().fetch_all(pool).await
+}
+
+/// Compte les utilisateurs correspondant aux filtres.
+pub async fn count_users(pool: &PgPool, options: &QueryOptions<UserFields>) -> Result<i64, sqlx::Error> {
+    let mut builder = QueryBuilder::new("SELECT count(*) FROM users WHERE true");
+    options.push_conditions(&mut builder);
+
+    builder.build_query_scalar::<i64>().fetch_one(pool).await
+}
+
+/// Récupère un utilisateur.
+pub async fn get_user(pool: &PgPool, id: UserId) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Crée un utilisateur ; un email déjà utilisé produit une violation d'unicité.
+///
+/// Les fonctions d'écriture acceptent un pool ou une connexion, pour servir aussi
+/// dans une transaction (voir `services::batch`).
+pub async fn create_user(executor: impl PgExecutor<'_>, new_user: &NewUser) -> Result<User, sqlx::Error> {
+    sqlx::query_as::<_, User>("INSERT INTO users (email, name) VALUES ($1, $2) RETURNING *")
+        .bind(&new_user.email)
+        .bind(&new_user.name)
+        .fetch_one(executor)
+        .await
+}
+
+/// Modifie les champs fournis d'un utilisateur.
+///
+/// # Returns
+///
+/// * `Result<Option<User>, sqlx::Error>` - `None` si l'utilisateur n'existe pas
+pub async fn update_user(executor: impl PgExecutor<'_>, id: UserId, changes: &UpdateUser) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>(
+        "UPDATE users
+         SET email = coalesce($2, email), name = coalesce($3, name), updated_at = now()
+         WHERE id = $1
+         RETURNING *",
+    )
+    .bind(id)
+    .bind(&changes.email)
+    .bind(&changes.name)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Supprime un utilisateur.
+///
+/// # Returns
+///
+/// * `Result<bool, sqlx::Error>` - `false` si l'utilisateur n'existait pas
+pub async fn delete_user(executor: impl PgExecutor<'_>, id: UserId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,265 @@
+//! # Outbound Webhooks Service
+//!
+//! Ce module gère l'envoi des webhooks sortants :
+//! - `enqueue_event` crée une livraison pour chaque abonnement concerné
+//! - un worker en arrière-plan envoie les livraisons dues, signées en HMAC-SHA256
+//! - les échecs sont retentés avec un backoff exponentiel, puis passés en
+//!   dead-letter (`status = 'dead'`) après `max_attempts` tentatives
+//!
+//! Chaque tentative est enregistrée dans `webhook_delivery_attempts`.
+
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::{
+    config::{Config, OutboundWebhooksConfig},
+    db::DatabaseManager,
+    handlers::webhooks::sign_payload,
+    middleware::trace::{inject, TraceContext},
+    models::webhooks::{OutboundWebhookBody, WebhookDelivery, WebhookSubscription},
+    services::supervisor::Supervisor,
+};
+
+/// Livraison réservée par le worker, avec les informations de l'abonnement
+#[derive(Debug, FromRow)]
+struct ClaimedDelivery {
+    id: i64,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+/// Crée un abonnement aux webhooks sortants.
+///
+/// # Arguments
+///
+/// * `event_types` - Types d'événements souscrits (vide = tous)
+pub async fn create_subscription(
+    pool: &PgPool,
+    url: &str,
+    secret: &str,
+    event_types: &[String],
+) -> Result<WebhookSubscription, sqlx::Error> {
+    sqlx::query_as::<_, WebhookSubscription>(
+        "INSERT INTO webhook_subscriptions (url, secret, event_types)
+         VALUES ($1, $2, $3)
+         RETURNING *",
+    )
+    .bind(url)
+    .bind(secret)
+    .bind(event_types)
+    .fetch_one(pool)
+    .await
+}
+
+/// Émet un événement : une livraison est créée pour chaque abonnement actif concerné.
+///
+/// # Returns
+///
+/// * `Result<u64, sqlx::Error>` - Nombre de livraisons créées
+pub async fn enqueue_event<T: Serialize>(pool: &PgPool, event_type: &str, payload: &T) -> Result<u64, sqlx::Error> {
+    let payload = serde_json::to_value(payload)
+        .map_err(|e| sqlx::Error::Protocol(format!("JSON serialization error: {}", e)))?;
+
+    let result = sqlx::query(
+        "INSERT INTO webhook_deliveries (subscription_id, event_type, payload)
+         SELECT id, $1, $2 FROM webhook_subscriptions
+         WHERE active AND (cardinality(event_types) = 0 OR $1 = ANY(event_types))",
+    )
+    .bind(event_type)
+    .bind(payload)
+    .execute(pool)
+    .await?;
+
+    info!("Enqueued event {} for {} subscription(s)", event_type, result.rows_affected());
+    Ok(result.rows_affected())
+}
+
+/// Récupère une livraison par son identifiant.
+pub async fn get_delivery(pool: &PgPool, id: i64) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+    sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Délai avant la prochaine tentative : `base * 2^(attempts - 1)`, plafonné à `max`.
+pub fn backoff_delay(attempts: u32, base_seconds: u64, max_seconds: u64) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(32);
+    base_seconds.saturating_mul(1u64 << exponent).min(max_seconds)
+}
+
+/// Démarre le worker d'envoi des webhooks en arrière-plan, sous la supervision de `tasks`
+pub async fn start_webhook_dispatcher(tasks: &Supervisor, db: DatabaseManager, config: Config) {
+    let settings = config.webhooks.outbound;
+    tasks.spawn("webhook_dispatcher", move |task| {
+        let db = db.clone();
+        let settings = settings.clone();
+        async move {
+            let client = reqwest::Client::new();
+            let period = Duration::from_secs(settings.poll_interval_seconds);
+            let mut interval = tokio::time::interval(period);
+
+            loop {
+                interval.tick().await;
+
+                // Une trace par passage, propagée aux destinataires
+                let dispatched = TraceContext::new_root()
+                    .scope(dispatch_due_deliveries(db.get_pool(), &client, &settings))
+                    .await;
+                task.record(dispatched.map(drop), period);
+            }
+        }
+    });
+}
+
+/// Envoie un lot de livraisons dues.
+///
+/// Les livraisons sont réservées avec `FOR UPDATE SKIP LOCKED` : plusieurs instances
+/// peuvent tourner en parallèle sans envoyer deux fois le même événement.
+///
+/// # Returns
+///
+/// * `Result<usize, sqlx::Error>` - Nombre de livraisons traitées
+pub async fn dispatch_due_deliveries(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &OutboundWebhooksConfig,
+) -> Result<usize, sqlx::Error> {
+    // Le bail couvre la durée de l'envoi pour qu'un worker en panne ne bloque pas la livraison
+    let lease_seconds = (settings.timeout_seconds + 30) as f64;
+
+    let deliveries = sqlx::query_as::<_, ClaimedDelivery>(
+        "UPDATE webhook_deliveries d
+         SET next_attempt_at = now() + make_interval(secs => $2), updated_at = now()
+         FROM webhook_subscriptions s
+         WHERE s.id = d.subscription_id
+           AND d.id IN (
+               SELECT id FROM webhook_deliveries
+               WHERE status = 'pending' AND next_attempt_at <= now()
+               ORDER BY next_attempt_at
+               LIMIT $1
+               FOR UPDATE SKIP LOCKED
+           )
+         RETURNING d.id, d.event_type, d.payload, d.attempts, s.url, s.secret",
+    )
+    .bind(settings.batch_size)
+    .bind(lease_seconds)
+    .fetch_all(pool)
+    .await?;
+
+    let count = deliveries.len();
+    for delivery in deliveries {
+        deliver(pool, client, settings, delivery).await?;
+    }
+
+    Ok(count)
+}
+
+/// Envoie une livraison et enregistre le résultat de la tentative
+async fn deliver(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &OutboundWebhooksConfig,
+    delivery: ClaimedDelivery,
+) -> Result<(), sqlx::Error> {
+    let body = serde_json::to_vec(&OutboundWebhookBody {
+        id: delivery.id,
+        event: &delivery.event_type,
+        data: &delivery.payload,
+    })
+    .map_err(|e| sqlx::Error::Protocol(format!("JSON serialization error: {}", e)))?;
+
+    // Même schéma de signature que les webhooks entrants : HMAC("{timestamp}.{body}")
+    let timestamp = Utc::now().timestamp();
+    let signature = sign_payload(
+        delivery.secret.as_bytes(),
+        &[format!("{}.", timestamp).as_bytes(), &body].concat(),
+    );
+
+    let start = Instant::now();
+    let response = inject(client.post(&delivery.url))
+        .timeout(Duration::from_secs(settings.timeout_seconds))
+        .header("content-type", "application/json")
+        .header("x-webhook-id", delivery.id.to_string())
+        .header("x-webhook-event", &delivery.event_type)
+        .header("x-webhook-timestamp", timestamp.to_string())
+        .header("x-webhook-signature", format!("sha256={}", signature))
+        .body(body)
+        .send()
+        .await;
+    let duration_ms = start.elapsed().as_millis() as i64;
+
+    let (status_code, error) = match response {
+        Ok(resp) if resp.status().is_success() => (Some(resp.status().as_u16() as i32), None),
+        Ok(resp) => (Some(resp.status().as_u16() as i32), Some(format!("HTTP {}", resp.status()))),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    sqlx::query(
+        "INSERT INTO webhook_delivery_attempts (delivery_id, status_code, error, duration_ms)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(delivery.id)
+    .bind(status_code)
+    .bind(&error)
+    .bind(duration_ms)
+    .execute(pool)
+    .await?;
+
+    let attempts = delivery.attempts + 1;
+    match error {
+        None => {
+            sqlx::query(
+                "UPDATE webhook_deliveries
+                 SET status = 'delivered', attempts = $2, last_error = NULL, updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .execute(pool)
+            .await?;
+            info!("Delivered webhook {} ({}) to {}", delivery.id, delivery.event_type, delivery.url);
+        }
+        Some(error) if attempts >= settings.max_attempts => {
+            sqlx::query(
+                "UPDATE webhook_deliveries
+                 SET status = 'dead', attempts = $2, last_error = $3, updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(&error)
+            .execute(pool)
+            .await?;
+            warn!("Webhook {} dead-lettered after {} attempts: {}", delivery.id, attempts, error);
+        }
+        Some(error) => {
+            let delay = backoff_delay(
+                attempts as u32,
+                settings.base_backoff_seconds,
+                settings.max_backoff_seconds,
+            );
+            sqlx::query(
+                "UPDATE webhook_deliveries
+                 SET attempts = $2, last_error = $3,
+                     next_attempt_at = now() + make_interval(secs => $4), updated_at = now()
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(&error)
+            .bind(delay as f64)
+            .execute(pool)
+            .await?;
+            warn!("Webhook {} failed (attempt {}), retrying in {}s: {}", delivery.id, attempts, delay, error);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! # Application State Module
+//!
+//! Ce module définit l'état partagé par tous les handlers Axum.
+//! Grâce à `FromRef`, un handler peut extraire directement une partie de l'état
+//! (par exemple `State<DatabaseManager>`) sans connaître la structure complète.
+
+use axum::extract::FromRef;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{
+    cache::CacheRegistry,
+    config::Config,
+    db::DatabaseManager,
+    handlers::webhooks::WebhookRegistry,
+    logs::LogBuffer,
+    middleware::{cache::ResponseCache, coalesce::Coalescer, latency::LatencyStats, status_codes::StatusCounters},
+    models::status::MetricsStore,
+    services::{
+        cluster::Cluster,
+        cors::CorsOrigins,
+        health::HealthRegistry,
+        jobs::JobRegistry,
+        queries::QueryInsights,
+        storage::{LocalStorage, Storage},
+        supervisor::Supervisor,
+    },
+};
+
+/// État global de l'application.
+#[derive(Clone, FromRef)]
+pub struct AppState {
+    /// Gestionnaire de base de données
+    pub db: DatabaseManager,
+    /// Configuration chargée au démarrage
+    pub config: Arc<Config>,
+    /// Registre des handlers de webhooks entrants
+    pub webhooks: Arc<WebhookRegistry>,
+    /// Drapeaux et vérifications des sondes de startup et de readiness
+    pub readiness: Arc<Readiness>,
+    /// Regroupement des requêtes GET identiques sur les routes coûteuses
+    pub coalescer: Arc<Coalescer>,
+    /// Cache des réponses GET publiques (`[cache.routes]`)
+    pub response_cache: Arc<ResponseCache>,
+    /// Caches typés par espace de noms (`[cache.namespaces]`), relevés par la tâche des métriques
+    pub caches: Arc<CacheRegistry>,
+    /// Latence mesurée par route, affichée sur la page de status
+    pub latency: Arc<LatencyStats>,
+    /// Réponses par classe de statut, dont la tâche des métriques tire le taux de 5xx
+    pub status_codes: Arc<StatusCounters>,
+    /// Métriques calculées par la tâche de fond, lues par les handlers de status
+    pub metrics: Arc<MetricsStore>,
+    /// Requêtes SQL les plus coûteuses, relevées par la tâche de fond
+    pub query_insights: Arc<QueryInsights>,
+    /// Dernier état des autres instances (`[[monitoring.peers]]`), interrogées par la tâche de fond
+    pub cluster: Arc<Cluster>,
+    /// Origines CORS autorisées (statiques et enregistrées en base)
+    pub cors_origins: Arc<CorsOrigins>,
+    /// Stockage des fichiers envoyés
+    pub storage: Arc<dyn Storage>,
+    /// Vérifications agrégées par `/api/help/health`
+    pub health: Arc<HealthRegistry>,
+    /// Handlers des tâches asynchrones, par type
+    pub jobs: Arc<JobRegistry>,
+    /// Tâches de fond supervisées, rapportées par `/api/help/health`
+    pub tasks: Arc<Supervisor>,
+    /// Derniers logs de l'instance, consultables via `/api/help/logs`
+    pub logs: Arc<LogBuffer>,
+    /// Backend Redis partagé entre instances, si `[redis] url` est défini
+    #[cfg(feature = "redis")]
+    #[from_ref(skip)]
+    pub redis: Option<crate::redis::RedisManager>,
+}
+
+impl AppState {
+    /// Construit l'état de l'application à partir de la base de données et de la configuration.
+    pub fn new(db: DatabaseManager, config: Config) -> Self {
+        let webhooks = crate::handlers::webhooks::registry(&config.webhooks);
+        let coalescer = Coalescer::new(Duration::from_millis(config.api.coalesce_cache_ttl_ms));
+        let response_cache = ResponseCache::new(&config.cache);
+        let caches = CacheRegistry::new(&config.cache);
+        let cors_origins = CorsOrigins::new(&config.cors);
+        let storage = LocalStorage::new(config.uploads.storage_path.clone());
+        let tasks = Arc::new(Supervisor::new());
+        let metrics = Arc::new(
+            MetricsStore::new(config.monitoring.recent_samples).with_disk_mounts(config.health.disk_mount_points.clone()),
+        );
+        let health = crate::services::health::registry(&db, &config, &tasks, &metrics);
+        #[cfg(feature = "redis")]
+        let redis = crate::redis::RedisManager::new(&config.redis).expect("Invalid [redis] url");
+        #[cfg(feature = "redis")]
+        let health = match &redis {
+            Some(redis) => health.register(crate::redis::RedisCheck::new(redis.clone())),
+            None => health,
+        };
+        let readiness = Readiness::new(crate::services::health::readiness_registry(&db, &config, &tasks));
+        let logs = crate::logs::buffer(config.logging.buffer_size);
+        let cluster = Cluster::new(&config.monitoring);
+        let query_insights = QueryInsights::new(config.status.query_insights, config.status.query_insights_top);
+        let latency = LatencyStats::with_leaderboard(
+            config.status.slow_endpoints_top,
+            Duration::from_secs(config.status.slow_endpoints_window_seconds),
+        );
+
+        Self {
+            db,
+            config: Arc::new(config),
+            webhooks: Arc::new(webhooks),
+            readiness: Arc::new(readiness),
+            coalescer: Arc::new(coalescer),
+            response_cache: Arc::new(response_cache),
+            caches: Arc::new(caches),
+            latency: Arc::new(latency),
+            status_codes: Arc::new(StatusCounters::new()),
+            metrics,
+            query_insights: Arc::new(query_insights),
+            cluster: Arc::new(cluster),
+            cors_origins: Arc::new(cors_origins),
+            storage: Arc::new(storage),
+            health: Arc::new(health),
+            jobs: Arc::new(crate::services::jobs::registry()),
+            tasks,
+            logs,
+            #[cfg(feature = "redis")]
+            redis,
+        }
+    }
+}
+
+/// Disponibilité de l'instance, consultée par les sondes de startup et de readiness.
+///
+/// L'instance est « démarrée » une fois l'initialisation terminée (`mark_started`).
+/// Passer l'instance en « draining » fait échouer la sonde de readiness : le load
+/// balancer arrête d'y envoyer du trafic, tandis que la liveness reste saine et que
+/// les requêtes en cours se terminent normalement.
+pub struct Readiness {
+    started: AtomicBool,
+    ready: AtomicBool,
+    checks: HealthRegistry,
+}
+
+impl Readiness {
+    /// Crée les drapeaux d'une instance en cours d'initialisation ; `checks` sont
+    /// exécutées par la sonde de readiness.
+    pub fn new(checks: HealthRegistry) -> Self {
+        Self {
+            started: AtomicBool::new(false),
+            ready: AtomicBool::new(true),
+            checks,
+        }
+    }
+
+    /// Indique si l'initialisation est terminée
+    pub fn is_started(&self) -> bool {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    /// Signale la fin de l'initialisation (fixtures, tâches de fond démarrées)
+    pub fn mark_started(&self) {
+        self.started.store(true, Ordering::SeqCst);
+    }
+
+    /// Indique si l'instance accepte du nouveau trafic (hors draining)
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// Modifie la disponibilité de l'instance
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::SeqCst);
+    }
+
+    /// Vérifications exécutées par la sonde de readiness
+    pub fn checks(&self) -> &HealthRegistry {
+        &self.checks
+    }
+}
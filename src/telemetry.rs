@@ -0,0 +1,244 @@
+//! # Telemetry Module
+//!
+//! Ce module exporte les traces vers un collecteur OpenTelemetry (Jaeger, Tempo...)
+//! en OTLP/HTTP, lorsque `[telemetry] enabled = true` :
+//! - les spans `tracing` deviennent des spans OpenTelemetry (dont le span `request`
+//!   de chaque requête, voir `middleware::trace`)
+//! - chaque requête SQLx devient un span enfant `db.query`, reconstruit à partir de
+//!   l'événement `sqlx::query` émis par SQLx à la fin de la requête
+//! - la ressource porte `service.name`, `service.version`, `deployment.environment`
+//!   et les attributs de `[telemetry.resource_attributes]`
+//!
+//! Le contexte W3C reçu (`traceparent`) devient le parent du span de la requête :
+//! l'identifiant de trace des logs, des réponses et du collecteur est le même.
+//!
+//! Les spans sont envoyés par lots depuis un thread dédié ; `shutdown` envoie les
+//! derniers spans à l'arrêt du serveur.
+
+use opentelemetry::{
+    trace::{
+        SamplingDecision, SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer as _,
+        TracerProvider as _,
+    },
+    Context, KeyValue,
+};
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    trace::{Sampler, SdkTracer, SdkTracerProvider},
+    Resource,
+};
+use std::{
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
+use tracing::{field::Field, warn, Event, Level, Subscriber};
+use tracing_opentelemetry::{OpenTelemetrySpanExt, OtelData};
+use tracing_subscriber::{
+    filter::{FilterExt, Targets},
+    layer::Context as LayerContext,
+    registry::LookupSpan,
+    EnvFilter, Layer,
+};
+
+use crate::{
+    config::Config,
+    middleware::{debug_trace::DebugTraceFilter, trace::TraceContext},
+};
+
+/// Fournisseur de traces actif, conservé pour `shutdown`
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Couches `tracing` de l'export OpenTelemetry ; `None` si la télémétrie est désactivée.
+///
+/// Les spans sont filtrés comme les logs (`[logging] level` ou `RUST_LOG`) ; les
+/// événements `sqlx::query` (niveau debug) sont toujours reçus pour produire les spans SQL.
+pub fn layer<S>(config: &Config) -> Result<Option<Box<dyn Layer<S> + Send + Sync>>, ExporterBuildError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+{
+    let settings = &config.telemetry;
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&settings.endpoint)
+        .with_timeout(Duration::from_secs(settings.export_timeout_seconds))
+        .build()?;
+
+    let environment = config.server.environment.as_str();
+    let resource = Resource::builder()
+        .with_service_name(settings.service_name.clone())
+        .with_attributes([
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            KeyValue::new("deployment.environment", environment),
+        ])
+        .with_attributes(
+            settings
+                .resource_attributes
+                .iter()
+                .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+        )
+        .build();
+
+    // Une trace commencée par l'appelant suit sa décision d'échantillonnage
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(settings.sample_ratio.clamp(0.0, 1.0))));
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler)
+        .with_resource(resource)
+        .build();
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    let _ = PROVIDER.set(provider);
+
+    let level = || {
+        EnvFilter::try_from_default_env()
+            .or_else(|_| EnvFilter::try_new(&config.logging.level))
+            .unwrap_or_else(|_| EnvFilter::new("info"))
+    };
+    // Les requêtes en debug (`X-Debug-Trace`) exportent aussi leurs spans de niveau trace
+    let spans = tracing_opentelemetry::layer()
+        .with_tracer(tracer.clone())
+        .with_filter(DebugTraceFilter::new(level(), config.logging.debug_trace));
+    // La couche SQL doit aussi voir les spans exportés, pour y rattacher les requêtes
+    let queries = SqlxQuerySpans { tracer }.with_filter(level().or(Targets::new().with_target("sqlx::query", Level::DEBUG)));
+
+    Ok(Some(spans.and_then(queries).boxed()))
+}
+
+/// Envoie les spans en attente et arrête l'export
+pub fn shutdown() {
+    if let Some(Err(e)) = PROVIDER.get().map(SdkTracerProvider::shutdown) {
+        warn!("Failed to flush telemetry: {}", e);
+    }
+}
+
+/// Rattache `span` au contexte reçu et retourne son contexte OpenTelemetry.
+///
+/// `None` si la télémétrie est désactivée ou si le span n'est pas enregistré :
+/// l'appelant conserve alors son propre contexte.
+pub fn link(span: &tracing::Span, parent: Option<TraceContext>) -> Option<TraceContext> {
+    PROVIDER.get()?;
+    if let Some(parent) = parent {
+        span.set_parent(remote_context(parent));
+    }
+
+    let context = span.context();
+    let span_context = context.span().span_context().clone();
+    span_context.is_valid().then(|| TraceContext {
+        trace_id: u128::from_be_bytes(span_context.trace_id().to_bytes()),
+        span_id: u64::from_be_bytes(span_context.span_id().to_bytes()),
+        sampled: span_context.is_sampled(),
+    })
+}
+
+fn remote_context(parent: TraceContext) -> Context {
+    let flags = if parent.sampled { TraceFlags::SAMPLED } else { TraceFlags::default() };
+    Context::new().with_remote_span_context(SpanContext::new(
+        TraceId::from_bytes(parent.trace_id.to_be_bytes()),
+        SpanId::from_bytes(parent.span_id.to_be_bytes()),
+        flags,
+        true,
+        TraceState::default(),
+    ))
+}
+
+/// Produit un span `db.query` pour chaque événement `sqlx::query` émis dans un span exporté.
+///
+/// SQLx n'émet pas de span : l'événement de fin de requête porte la durée (`elapsed_secs`),
+/// d'où sont déduits les instants de début et de fin.
+struct SqlxQuerySpans {
+    tracer: SdkTracer,
+}
+
+impl<S> Layer<S> for SqlxQuerySpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+        // Une requête hors d'une opération tracée (tâche de fond...) n'est pas exportée
+        let Some(span) = ctx.event_span(event) else {
+            return;
+        };
+        let parent = {
+            let extensions = span.extensions();
+            let Some(data) = extensions.get::<OtelData>() else {
+                return;
+            };
+            parent_context(data)
+        };
+        if !parent.span().span_context().is_sampled() {
+            return;
+        }
+
+        let mut query = QueryFields::default();
+        event.record(&mut query);
+
+        let end = SystemTime::now();
+        let start = end - Duration::from_secs_f64(query.elapsed_secs.max(0.0));
+        let statement = if query.statement.trim().is_empty() { query.summary } else { query.statement.trim().to_string() };
+        self.tracer
+            .span_builder("db.query")
+            .with_kind(SpanKind::Client)
+            .with_start_time(start)
+            .with_end_time(end)
+            .with_attributes([
+                KeyValue::new("db.system", "postgresql"),
+                KeyValue::new("db.statement", statement),
+                KeyValue::new("db.rows_affected", query.rows_affected as i64),
+                KeyValue::new("db.rows_returned", query.rows_returned as i64),
+            ])
+            .start_with_context(&self.tracer, &parent);
+    }
+}
+
+/// Contexte du span `tracing` englobant, tel qu'il sera exporté
+fn parent_context(data: &OtelData) -> Context {
+    let parent = data.parent_cx.span().span_context().clone();
+    let trace_id = data.builder.trace_id.unwrap_or_else(|| parent.trace_id());
+    let span_id = data.builder.span_id.unwrap_or(SpanId::INVALID);
+    let sampled = match &data.builder.sampling_result {
+        Some(result) => result.decision == SamplingDecision::RecordAndSample,
+        None => parent.is_sampled() || !parent.is_valid(),
+    };
+    let flags = if sampled { TraceFlags::SAMPLED } else { TraceFlags::default() };
+
+    Context::new().with_remote_span_context(SpanContext::new(trace_id, span_id, flags, false, TraceState::default()))
+}
+
+/// Champs de l'événement `sqlx::query`
+#[derive(Default)]
+struct QueryFields {
+    summary: String,
+    statement: String,
+    rows_affected: u64,
+    rows_returned: u64,
+    elapsed_secs: f64,
+}
+
+impl tracing::field::Visit for QueryFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.statement = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_affected" => self.rows_affected = value,
+            "rows_returned" => self.rows_returned = value,
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
@@ -0,0 +1,290 @@
+//! # Test App Module
+//!
+//! `TestApp` démarre l'application complète (routes, CORS, middlewares) sur un port
+//! éphémère, avec sa propre base jetable, et fournit un client HTTP typé.
+//!
+//! ```ignore
+//! let app = TestApp::builder().configure(|config| config.api.max_per_page = 50).spawn().await;
+//! app.as_admin()
+//!     .post("/api/users", json!({ "email": "ada@example.com", "name": "Ada" }))
+//!     .await
+//!     .assert_status(StatusCode::CREATED)
+//!     .assert_json_includes(json!({ "data": { "name": "Ada" } }));
+//! app.close().await;
+//! ```
+
+use crate::{
+    config::Config,
+    handlers::error::PROBLEM_JSON,
+    models::error::ProblemDetails,
+    state::AppState,
+    testing::{app_router, snapshot::redact_volatile, test_config, TestDatabase},
+};
+use axum::http::StatusCode;
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, Method};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::net::SocketAddr;
+use tokio::task::JoinHandle;
+
+/// Jeton d'administration configuré par défaut par `TestApp`
+pub const TEST_ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Configuration d'une `TestApp` avant son démarrage
+pub struct TestAppBuilder {
+    config: Config,
+}
+
+impl TestAppBuilder {
+    /// Remplace la configuration de départ (par défaut `Config::default()`, `DATABASE_URL` si définie)
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Modifie la configuration
+    pub fn configure(mut self, configure: impl FnOnce(&mut Config)) -> Self {
+        configure(&mut self.config);
+        self
+    }
+
+    /// Clone une base jetable, construit le routeur complet et le sert sur un port éphémère
+    pub async fn spawn(self) -> TestApp {
+        let db = TestDatabase::with_config(self.config).await;
+        let state = AppState::new(db.manager(), db.config());
+        state.readiness.mark_started();
+
+        let app = app_router(&state);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind test server");
+        let address = listener.local_addr().expect("Failed to read test server address");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service()).await.ok();
+        });
+
+        TestApp {
+            address,
+            state,
+            db,
+            server: AbortOnDrop(server),
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+/// Arrête le serveur de test quand la `TestApp` est détruite
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Application démarrée pour un test
+pub struct TestApp {
+    /// Adresse du serveur (`127.0.0.1:<port éphémère>`)
+    pub address: SocketAddr,
+    /// État partagé avec les handlers (configuration, métriques, caches...)
+    pub state: AppState,
+    /// Base jetable de l'application
+    pub db: TestDatabase,
+    server: AbortOnDrop,
+    http: reqwest::Client,
+}
+
+impl TestApp {
+    /// Configuration par défaut, avec `TEST_ADMIN_TOKEN` comme jeton d'administration
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder { config: test_config() }
+    }
+
+    /// Démarre une application avec la configuration par défaut
+    pub async fn spawn() -> Self {
+        Self::builder().spawn().await
+    }
+
+    /// URL absolue d'un chemin de l'API
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.address, path)
+    }
+
+    /// Client sans authentification
+    pub fn client(&self) -> TestClient {
+        TestClient {
+            base_url: self.url(""),
+            http: self.http.clone(),
+            token: None,
+        }
+    }
+
+    /// Client authentifié avec le jeton d'administration configuré
+    pub fn as_admin(&self) -> TestClient {
+        let token = self.state.config.admin.token.clone().expect("No admin token configured");
+        self.as_bearer(&token)
+    }
+
+    /// Client authentifié avec un jeton `Authorization: Bearer` quelconque
+    pub fn as_bearer(&self, token: &str) -> TestClient {
+        TestClient {
+            token: Some(token.to_string()),
+            ..self.client()
+        }
+    }
+
+    /// Arrête le serveur et supprime la base jetable
+    pub async fn close(self) {
+        let TestApp { server, db, .. } = self;
+        drop(server);
+        db.close().await;
+    }
+}
+
+/// Client HTTP d'une `TestApp`
+#[derive(Clone)]
+pub struct TestClient {
+    base_url: String,
+    http: reqwest::Client,
+    token: Option<String>,
+}
+
+impl TestClient {
+    /// Envoie une requête, avec un corps JSON optionnel
+    pub async fn request(&self, method: Method, path: &str, body: Option<Value>) -> TestResponse {
+        let mut request = self.http.request(method.clone(), format!("{}{}", self.base_url, path));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.expect("Failed to send test request");
+        let status = StatusCode::from_u16(response.status().as_u16()).expect("Invalid status code");
+        let headers = response.headers().clone();
+        let body = response.bytes().await.expect("Failed to read test response");
+        TestResponse {
+            request: format!("{} {}", method, path),
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> TestResponse {
+        self.request(Method::GET, path, None).await
+    }
+
+    pub async fn post(&self, path: &str, body: Value) -> TestResponse {
+        self.request(Method::POST, path, Some(body)).await
+    }
+
+    pub async fn put(&self, path: &str, body: Value) -> TestResponse {
+        self.request(Method::PUT, path, Some(body)).await
+    }
+
+    pub async fn patch(&self, path: &str, body: Value) -> TestResponse {
+        self.request(Method::PATCH, path, Some(body)).await
+    }
+
+    pub async fn delete(&self, path: &str) -> TestResponse {
+        self.request(Method::DELETE, path, None).await
+    }
+}
+
+/// Réponse reçue par un `TestClient`, avec des assertions chaînables
+#[derive(Debug)]
+pub struct TestResponse {
+    /// Méthode et chemin, repris dans les messages d'échec
+    pub request: String,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl TestResponse {
+    /// Corps JSON (`Null` si le corps est vide)
+    pub fn json(&self) -> Value {
+        if self.body.is_empty() {
+            return Value::Null;
+        }
+        serde_json::from_slice(&self.body)
+            .unwrap_or_else(|e| panic!("{}: invalid JSON body ({}): {}", self.request, e, self.text()))
+    }
+
+    /// Corps JSON dont les champs volatils sont masqués, pour `insta::assert_json_snapshot!`
+    pub fn redacted_json(&self) -> Value {
+        redact_volatile(self.json())
+    }
+
+    /// Corps JSON désérialisé dans un type
+    pub fn json_as<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_value(self.json()).unwrap_or_else(|e| panic!("{}: unexpected JSON body ({})", self.request, e))
+    }
+
+    /// Corps brut en texte
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Valeur d'un en-tête de réponse
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Vérifie le code de statut, en affichant le corps en cas d'échec
+    pub fn assert_status(&self, expected: StatusCode) -> &Self {
+        assert_eq!(self.status, expected, "{}: unexpected status, body: {}", self.request, self.text());
+        self
+    }
+
+    /// Vérifie que le corps JSON contient `expected` : les objets peuvent avoir d'autres
+    /// champs, les tableaux et les valeurs doivent être égaux
+    pub fn assert_json_includes(&self, expected: Value) -> &Self {
+        let actual = self.json();
+        if let Err(path) = includes(&actual, &expected, String::new()) {
+            panic!(
+                "{}: JSON mismatch at {}\nexpected (subset): {}\nactual: {}",
+                self.request,
+                if path.is_empty() { "/" } else { &path },
+                expected,
+                actual
+            );
+        }
+        self
+    }
+
+    /// Vérifie l'enveloppe d'une erreur : `application/problem+json`, corps `ProblemDetails`
+    /// valide et `status` égal au statut de la réponse
+    pub fn assert_problem(&self) -> &Self {
+        assert_eq!(self.header("content-type"), Some(PROBLEM_JSON), "{}: unexpected content type", self.request);
+        let problem: ProblemDetails = serde_json::from_slice(&self.body)
+            .unwrap_or_else(|e| panic!("{}: invalid problem document ({}): {}", self.request, e, self.text()));
+        assert_eq!(problem.status, self.status.as_u16(), "{}: problem status differs from the response", self.request);
+        self
+    }
+
+    /// Vérifie le code d'erreur du corps (`code`, voir `ErrorCode`)
+    pub fn assert_error_code(&self, code: &str) -> &Self {
+        let actual = self.json();
+        assert_eq!(actual["code"], code, "{}: unexpected error code, body: {}", self.request, actual);
+        self
+    }
+}
+
+/// Retourne le chemin JSON du premier écart entre `actual` et le sous-ensemble `expected`
+fn includes(actual: &Value, expected: &Value, path: String) -> Result<(), String> {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().try_for_each(|(key, value)| {
+            let path = format!("{}/{}", path, key);
+            match actual.get(key) {
+                Some(actual) => includes(actual, value, path),
+                None => Err(path),
+            }
+        }),
+        _ if actual == expected => Ok(()),
+        _ => Err(path),
+    }
+}
@@ -0,0 +1,41 @@
+//! # Test Container Module
+//!
+//! Avec la feature `testcontainers`, les bases de test sont créées dans un PostgreSQL
+//! jetable lancé par Docker au premier test, plutôt que sur la base locale du
+//! développeur. Le conteneur est partagé par les tests d'un même binaire et supprimé
+//! à la fin du processus par le nettoyeur de testcontainers.
+
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{runners::AsyncRunner, ContainerAsync, ImageExt},
+};
+use tokio::sync::OnceCell;
+use tracing::info;
+
+/// Version de PostgreSQL du conteneur (13+ pour `DROP DATABASE … WITH (FORCE)`)
+const POSTGRES_TAG: &str = "16-alpine";
+
+/// Conteneur et URL de sa base d'administration, démarrés une fois par processus
+static CONTAINER: OnceCell<(ContainerAsync<Postgres>, String)> = OnceCell::const_new();
+
+/// URL du PostgreSQL jetable, démarré au premier appel
+pub async fn postgres_url() -> String {
+    let (_, url) = CONTAINER
+        .get_or_init(|| async {
+            let container = Postgres::default()
+                .with_tag(POSTGRES_TAG)
+                .start()
+                .await
+                .expect("Failed to start the Postgres container (is Docker running?)");
+            let host = container.get_host().await.expect("Failed to read the container host");
+            let port = container
+                .get_host_port_ipv4(5432)
+                .await
+                .expect("Failed to read the container port");
+            let url = format!("postgres://postgres:postgres@{}:{}/postgres", host, port);
+            info!("Postgres container started on {}", url);
+            (container, url)
+        })
+        .await;
+    url.clone()
+}
@@ -0,0 +1,344 @@
+//! # Contract Test Module
+//!
+//! `ContractRunner` parcourt le document OpenAPI servi par une `TestApp`, appelle chaque
+//! opération documentée avec des données d'exemple dérivées des schémas, puis vérifie la
+//! réponse : statut documenté, type de contenu déclaré et corps JSON conforme au schéma.
+//! Un écart entre la documentation et les handlers fait échouer le test.
+//!
+//! ```ignore
+//! let app = TestApp::spawn().await;
+//! let report = ContractRunner::new(&app).await.skip("GET /api/status/live").run().await;
+//! report.assert_ok();
+//! ```
+//!
+//! Une réponse `application/problem+json` satisfait un `application/json` documenté.
+//! Les opérations dont la seule réponse est un flux (`text/event-stream`) sont ignorées.
+//! Les paramètres de chemin valent l'exemple de leur schéma (`1` pour un entier), sauf
+//! valeur imposée par `path_param`.
+
+use crate::testing::{TestApp, TestClient};
+use reqwest::Method;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Types de contenu dont la réponse ne se termine pas
+const STREAMING_CONTENT_TYPES: &[&str] = &["text/event-stream"];
+
+/// Opération décrite par le document OpenAPI
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub method: Method,
+    /// Chemin documenté, avec ses paramètres (`/api/users/{id}`)
+    pub path: String,
+    /// Opération protégée par un schéma de sécurité : appelée avec le jeton d'administration
+    pub secured: bool,
+    pub operation: Value,
+}
+
+impl Operation {
+    /// Nom affiché dans les rapports (`GET /api/users/{id}`)
+    pub fn name(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+/// Résultat d'une vérification de contrat
+#[derive(Debug, Default)]
+pub struct ContractReport {
+    /// Opérations appelées, avec le statut reçu
+    pub checked: Vec<(String, u16)>,
+    /// Opérations ignorées (`skip` ou flux)
+    pub skipped: Vec<String>,
+    /// Écarts constatés, un message par écart
+    pub failures: Vec<String>,
+}
+
+impl ContractReport {
+    /// Échoue en listant tous les écarts
+    pub fn assert_ok(&self) {
+        assert!(
+            self.failures.is_empty(),
+            "{} contract violations:\n{}",
+            self.failures.len(),
+            self.failures.join("\n")
+        );
+    }
+}
+
+/// Vérifie les opérations du document OpenAPI d'une `TestApp`
+pub struct ContractRunner<'a> {
+    app: &'a TestApp,
+    doc: Value,
+    skip: Vec<String>,
+    path_params: HashMap<String, String>,
+}
+
+impl<'a> ContractRunner<'a> {
+    /// Récupère le document OpenAPI servi par l'application
+    pub async fn new(app: &'a TestApp) -> Self {
+        let doc = app.client().get("/api-doc/openapi.json").await.json();
+        Self::with_document(app, doc)
+    }
+
+    /// Vérifie un document déjà chargé
+    pub fn with_document(app: &'a TestApp, doc: Value) -> Self {
+        Self {
+            app,
+            doc,
+            skip: Vec::new(),
+            path_params: HashMap::new(),
+        }
+    }
+
+    /// Ignore une opération (`"POST /api/admin/allocator/heap-profile"`)
+    pub fn skip(mut self, operation: &str) -> Self {
+        self.skip.push(operation.to_string());
+        self
+    }
+
+    /// Valeur d'un paramètre de chemin, pour toutes les opérations
+    pub fn path_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.path_params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Opérations du document, dans l'ordre des chemins
+    pub fn operations(&self) -> Vec<Operation> {
+        let global_security = self.doc.get("security").is_some_and(|security| !is_empty(security));
+        let mut operations = Vec::new();
+        for (path, item) in self.doc["paths"].as_object().into_iter().flatten() {
+            for (method, operation) in item.as_object().into_iter().flatten() {
+                let Ok(method) = Method::from_bytes(method.to_uppercase().as_bytes()) else {
+                    continue;
+                };
+                let secured = operation.get("security").map_or(global_security, |security| !is_empty(security));
+                operations.push(Operation {
+                    method,
+                    path: path.clone(),
+                    secured,
+                    operation: operation.clone(),
+                });
+            }
+        }
+        operations
+    }
+
+    /// Appelle chaque opération et vérifie sa réponse, les `DELETE` en dernier
+    pub async fn run(&self) -> ContractReport {
+        let mut report = ContractReport::default();
+        let mut operations = self.operations();
+        // Suppressions en dernier : les autres opérations trouvent encore les lignes existantes
+        operations.sort_by_key(|operation| operation.method == Method::DELETE);
+        for operation in operations {
+            let name = operation.name();
+            if self.skip.contains(&name) || self.is_streaming(&operation) {
+                report.skipped.push(name);
+                continue;
+            }
+            match self.check(&operation).await {
+                Ok(status) => report.checked.push((name, status)),
+                Err(failure) => report.failures.push(format!("{}: {}", name, failure)),
+            }
+        }
+        report
+    }
+
+    /// Appelle une opération ; retourne le statut reçu ou l'écart constaté
+    pub async fn check(&self, operation: &Operation) -> Result<u16, String> {
+        let client: TestClient = if operation.secured { self.app.as_admin() } else { self.app.client() };
+        let body = self.request_body_schema(&operation.operation).map(|schema| self.example(schema));
+        let response = client.request(operation.method.clone(), &self.url(operation), body).await;
+        let status = response.status.as_u16();
+
+        let responses = &operation.operation["responses"];
+        let documented = responses
+            .get(status.to_string())
+            .or_else(|| responses.get("default"))
+            .map(|response| self.resolve(response))
+            .ok_or_else(|| format!("undocumented status {}, body: {}", status, response.text()))?;
+
+        let Some(content) = documented.get("content").and_then(Value::as_object).filter(|content| !content.is_empty()) else {
+            return Ok(status);
+        };
+        let content_type = response.header("content-type").unwrap_or_default().to_string();
+        let (media_type, media) = content
+            .iter()
+            .find(|(media_type, _)| media_type_matches(media_type, &content_type))
+            .ok_or_else(|| {
+                format!(
+                    "status {}: content type {:?} is not one of the documented {:?}",
+                    status,
+                    content_type,
+                    content.keys().collect::<Vec<_>>()
+                )
+            })?;
+        if (media_type == "application/json" || media_type.ends_with("+json"))
+            && let Some(schema) = media.get("schema")
+        {
+            let body: Value = serde_json::from_slice(&response.body)
+                .map_err(|e| format!("status {}: invalid JSON body ({}): {}", status, e, response.text()))?;
+            self.validate(schema, &body)
+                .map_err(|errors| format!("status {}: response does not match its schema: {}", status, errors))?;
+        }
+        Ok(status)
+    }
+
+    /// Valeur d'exemple conforme à un schéma : `example`, `default` ou première valeur
+    /// d'`enum` s'ils existent, sinon une valeur construite à partir du type et du format.
+    /// Pour un objet, seuls les champs obligatoires sont remplis.
+    pub fn example(&self, schema: &Value) -> Value {
+        self.example_named(schema, "")
+    }
+
+    /// Comme `example`, le nom du champ complétant un format absent (`email`, `url`...)
+    fn example_named(&self, schema: &Value, name: &str) -> Value {
+        let schema = self.resolve(schema);
+        if let Some(example) = schema
+            .get("example")
+            .or_else(|| schema.get("examples").and_then(|examples| examples.get(0)))
+            .or_else(|| schema.get("default"))
+            .or_else(|| schema.get("enum").and_then(|values| values.get(0)))
+            .or_else(|| schema.get("const"))
+        {
+            return example.clone();
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = Map::new();
+            for part in parts {
+                match self.example_named(part, name) {
+                    Value::Object(object) => merged.extend(object),
+                    other => return other,
+                }
+            }
+            return Value::Object(merged);
+        }
+        if let Some(variants) = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array) {
+            let variant = variants
+                .iter()
+                .find(|variant| self.resolve(variant).get("type") != Some(&json!("null")))
+                .or(variants.first());
+            return variant.map_or(Value::Null, |variant| self.example_named(variant, name));
+        }
+
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ if schema.get("properties").is_some() => vec!["object"],
+            _ => Vec::new(),
+        };
+        match types.into_iter().find(|kind| *kind != "null").unwrap_or("null") {
+            "object" => {
+                let required: Vec<&str> = schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str).collect();
+                let object = schema["properties"]
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(name, _)| required.contains(&name.as_str()))
+                    .map(|(name, property)| (name.clone(), self.example_named(property, name)))
+                    .collect();
+                Value::Object(object)
+            }
+            "array" => {
+                let count = schema["minItems"].as_u64().unwrap_or(1).max(1);
+                let item = schema.get("items").map_or(Value::Null, |items| self.example(items));
+                Value::Array(vec![item; count as usize])
+            }
+            "integer" => json!(schema["minimum"].as_i64().unwrap_or(1).max(1)),
+            "number" => json!(schema["minimum"].as_f64().unwrap_or(1.0).max(1.0)),
+            "boolean" => json!(true),
+            "string" => json!(example_string(schema, name)),
+            _ => Value::Null,
+        }
+    }
+
+    /// Vérifie une valeur contre un schéma du document (références résolues dans ses composants)
+    pub fn validate(&self, schema: &Value, value: &Value) -> Result<(), String> {
+        let root = json!({ "allOf": [schema], "components": self.doc.get("components").cloned().unwrap_or_default() });
+        let validator = jsonschema::options()
+            .with_draft(jsonschema::Draft::Draft202012)
+            .build(&root)
+            .map_err(|e| format!("invalid schema: {}", e))?;
+        let errors: Vec<String> = validator
+            .iter_errors(value)
+            .map(|error| format!("{} at {}", error, error.instance_path))
+            .collect();
+        if errors.is_empty() { Ok(()) } else { Err(errors.join("; ")) }
+    }
+
+    /// Suit une référence `#/...` du document
+    fn resolve<'v>(&'v self, value: &'v Value) -> &'v Value {
+        match value.get("$ref").and_then(Value::as_str).and_then(|reference| reference.strip_prefix('#')) {
+            Some(pointer) => self.doc.pointer(pointer).map_or(value, |target| self.resolve(target)),
+            None => value,
+        }
+    }
+
+    fn request_body_schema<'v>(&'v self, operation: &'v Value) -> Option<&'v Value> {
+        let body = self.resolve(operation.get("requestBody")?);
+        body["content"].get("application/json")?.get("schema")
+    }
+
+    /// Chemin avec ses paramètres remplacés, et les paramètres de requête obligatoires
+    fn url(&self, operation: &Operation) -> String {
+        let mut path = operation.path.clone();
+        let mut query = Vec::new();
+        for parameter in operation.operation["parameters"].as_array().into_iter().flatten() {
+            let parameter = self.resolve(parameter);
+            let name = parameter["name"].as_str().unwrap_or_default();
+            let value = self.path_params.get(name).cloned().unwrap_or_else(|| {
+                match parameter.get("schema").map(|schema| self.example_named(schema, name)) {
+                    Some(Value::String(value)) => value,
+                    Some(value) => value.to_string(),
+                    None => "1".to_string(),
+                }
+            });
+            match parameter["in"].as_str() {
+                Some("path") => path = path.replace(&format!("{{{}}}", name), &value),
+                Some("query") if parameter["required"] == true => query.push(format!("{}={}", name, value)),
+                _ => {}
+            }
+        }
+        if query.is_empty() { path } else { format!("{}?{}", path, query.join("&")) }
+    }
+
+    /// Toutes les réponses de succès documentées sont des flux
+    fn is_streaming(&self, operation: &Operation) -> bool {
+        let content_types: Vec<&String> = operation.operation["responses"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(status, _)| status.starts_with('2'))
+            .flat_map(|(_, response)| self.resolve(response)["content"].as_object().into_iter().flatten().map(|(kind, _)| kind))
+            .collect();
+        !content_types.is_empty() && content_types.iter().all(|kind| STREAMING_CONTENT_TYPES.contains(&kind.as_str()))
+    }
+}
+
+/// Un type `+json` (`application/problem+json`) satisfait un `application/json` documenté
+fn media_type_matches(documented: &str, content_type: &str) -> bool {
+    let actual = content_type.split(';').next().unwrap_or_default().trim();
+    actual == documented || (documented == "application/json" && actual.starts_with("application/") && actual.ends_with("+json"))
+}
+
+fn is_empty(value: &Value) -> bool {
+    value.as_array().is_some_and(Vec::is_empty)
+}
+
+/// Chaîne d'exemple selon le format déclaré, ou à défaut le nom du champ
+fn example_string(schema: &Value, name: &str) -> String {
+    let format = schema["format"].as_str().or(match name {
+        "email" => Some("email"),
+        "url" | "uri" => Some("uri"),
+        _ => None,
+    });
+    let value = match format {
+        Some("email") => "contract@example.com".to_string(),
+        Some("date-time") => chrono::Utc::now().to_rfc3339(),
+        Some("date") => chrono::Utc::now().date_naive().to_string(),
+        Some("uuid") => uuid::Uuid::nil().to_string(),
+        Some("uri") | Some("url") => "https://example.com".to_string(),
+        _ => "example".to_string(),
+    };
+    let min = schema["minLength"].as_u64().unwrap_or(0) as usize;
+    if value.len() < min { format!("{}{}", value, "x".repeat(min - value.len())) } else { value }
+}
@@ -0,0 +1,206 @@
+//! # Test Database Module
+//!
+//! `TestDatabase` crée une base jetable par test, clonée (`CREATE DATABASE … TEMPLATE`)
+//! depuis une base modèle où les migrations sont appliquées une seule fois. Les tests
+//! peuvent ainsi s'exécuter en parallèle sans partager leurs lignes.
+//!
+//! ```ignore
+//! let db = TestDatabase::new().await;
+//! let state = AppState::new(db.manager(), db.config());
+//! // ...
+//! db.close().await;
+//! ```
+
+use crate::{
+    config::Config,
+    db::{DatabaseManager, MIGRATOR},
+};
+use sha2::{Digest, Sha256};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    Connection, Executor, PgConnection, PgPool,
+};
+use tokio::sync::OnceCell;
+use tracing::info;
+
+/// Base modèle migrée, créée une fois par processus
+static TEMPLATE: OnceCell<String> = OnceCell::const_new();
+
+/// Base de données jetable, supprimée par `close` ou à la destruction
+pub struct TestDatabase {
+    name: String,
+    /// Connexion au serveur par la base configurée, pour supprimer la base jetable
+    admin: PgConnectOptions,
+    config: Config,
+    pool: PgPool,
+    closed: bool,
+}
+
+impl TestDatabase {
+    /// Clone la base modèle avec la configuration par défaut (`DATABASE_URL` si définie)
+    pub async fn new() -> Self {
+        let mut config = Config::default();
+        if let Ok(url) = std::env::var("DATABASE_URL") {
+            config.database.url = url;
+        }
+        Self::with_config(config).await
+    }
+
+    /// Clone la base modèle sur le serveur de `config.database`, ou sur le conteneur
+    /// PostgreSQL avec la feature `testcontainers`
+    pub async fn with_config(mut config: Config) -> Self {
+        #[cfg(feature = "testcontainers")]
+        {
+            config.database.url = super::container::postgres_url().await;
+        }
+        let options = DatabaseManager::connect_options(&config).expect("Invalid test database configuration");
+        let template = TEMPLATE
+            .get_or_init(|| create_template(options.clone()))
+            .await
+            .clone();
+
+        let name = format!("{}_test_{}", base_name(&options), uuid::Uuid::new_v4().simple());
+        let mut admin = PgConnection::connect_with(&options).await.expect("Failed to connect to test server");
+        admin
+            .execute(format!("CREATE DATABASE \"{}\" TEMPLATE \"{}\"", name, template).as_str())
+            .await
+            .expect("Failed to clone template database");
+        admin.close().await.ok();
+
+        config.database.url = with_database(&config.database.url, &name);
+        let pool = PgPoolOptions::new()
+            .max_connections(config.database.max_connections)
+            .connect_with(options.clone().database(&name))
+            .await
+            .expect("Failed to connect to test database");
+        Self { name, admin: options, config, pool, closed: false }
+    }
+
+    /// Nom de la base jetable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Configuration dont l'URL pointe vers la base jetable
+    pub fn config(&self) -> Config {
+        self.config.clone()
+    }
+
+    /// Pool de connexions à la base jetable
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Gestionnaire partageant le pool de la base jetable, pour construire un `AppState`
+    pub fn manager(&self) -> DatabaseManager {
+        DatabaseManager::from_pool(self.pool.clone())
+    }
+
+    /// Ferme les connexions et supprime la base
+    pub async fn close(mut self) {
+        self.closed = true;
+        self.pool.close().await;
+        drop_database(&self.admin, &self.name).await;
+    }
+}
+
+impl Drop for TestDatabase {
+    /// Filet de sécurité pour les tests qui échouent avant `close` : le runtime du test
+    /// peut être en cours d'arrêt, la suppression passe par un thread et un runtime dédiés
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+        let (admin, name) = (self.admin.clone(), self.name.clone());
+        let handle = std::thread::spawn(move || {
+            if let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                runtime.block_on(drop_database(&admin, &name));
+            }
+        });
+        handle.join().ok();
+    }
+}
+
+/// Crée la base modèle si elle n'existe pas encore, et retourne son nom.
+///
+/// Le nom inclut une empreinte des migrations : une migration ajoutée ou modifiée
+/// produit une nouvelle base modèle. Un verrou consultatif protège la création quand
+/// plusieurs binaires de test démarrent en même temps.
+async fn create_template(options: PgConnectOptions) -> String {
+    let mut hasher = Sha256::new();
+    for migration in MIGRATOR.iter() {
+        hasher.update(migration.version.to_be_bytes());
+        hasher.update(&migration.checksum);
+    }
+    let name = format!("{}_template_{}", base_name(&options), &hex::encode(hasher.finalize())[..12]);
+
+    let mut admin = PgConnection::connect_with(&options).await.expect("Failed to connect to test server");
+    admin
+        .execute("SELECT pg_advisory_lock(hashtext('testing::create_template'))")
+        .await
+        .expect("Failed to lock template creation");
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)")
+        .bind(&name)
+        .fetch_one(&mut admin)
+        .await
+        .expect("Failed to look up template database");
+    if !exists {
+        info!("Creating template database {}", name);
+        // Base temporaire renommée une fois migrée : un échec ne laisse pas de modèle incomplet
+        let building = format!("{}_building", name);
+        admin
+            .execute(format!("DROP DATABASE IF EXISTS \"{}\"", building).as_str())
+            .await
+            .expect("Failed to drop stale template database");
+        admin
+            .execute(format!("CREATE DATABASE \"{}\"", building).as_str())
+            .await
+            .expect("Failed to create template database");
+        let mut conn = PgConnection::connect_with(&options.clone().database(&building))
+            .await
+            .expect("Failed to connect to template database");
+        MIGRATOR.run(&mut conn).await.expect("Failed to migrate template database");
+        conn.close().await.ok();
+        admin
+            .execute(format!("ALTER DATABASE \"{}\" RENAME TO \"{}\"", building, name).as_str())
+            .await
+            .expect("Failed to rename template database");
+    }
+
+    admin
+        .execute("SELECT pg_advisory_unlock(hashtext('testing::create_template'))")
+        .await
+        .ok();
+    admin.close().await.ok();
+    name
+}
+
+/// `WITH (FORCE)` ferme les connexions encore ouvertes (PostgreSQL 13+)
+async fn drop_database(options: &PgConnectOptions, name: &str) {
+    if let Ok(mut admin) = PgConnection::connect_with(options).await {
+        admin
+            .execute(format!("DROP DATABASE IF EXISTS \"{}\" WITH (FORCE)", name).as_str())
+            .await
+            .ok();
+        admin.close().await.ok();
+    }
+}
+
+/// Préfixe des bases créées : le nom de la base configurée
+fn base_name(options: &PgConnectOptions) -> String {
+    options.get_database().unwrap_or("postgres").to_string()
+}
+
+/// Remplace la base de données d'une URL PostgreSQL
+pub(super) fn with_database(url: &str, name: &str) -> String {
+    let (base, query) = url.split_once('?').map_or((url, None), |(base, query)| (base, Some(query)));
+    let prefix = match base.rfind('/') {
+        Some(index) if index > base.find("://").map_or(0, |index| index + 2) => &base[..index],
+        _ => base,
+    };
+    match query {
+        Some(query) => format!("{}/{}?{}", prefix, name, query),
+        None => format!("{}/{}", prefix, name),
+    }
+}
@@ -0,0 +1,86 @@
+//! # Mock Module
+//!
+//! Ce module lance des serveurs `wiremock` à la place des services externes appelés par
+//! l'application (ping du score de performance, cibles et pairs surveillés, notifications
+//! d'alertes, export des traces, webhooks sortants), pour rendre déterministes les tests
+//! de ces chemins.
+//!
+//! ```ignore
+//! let mock = MockHttp::start().await;
+//! Mock::given(method("POST")).and(path("/services/hook")).respond_with(ResponseTemplate::new(200)).mount(mock.server()).await;
+//!
+//! let mut config = test_config();
+//! mock.redirect(&mut config);
+//! ```
+//!
+//! `redirect` garde le chemin et la requête de chaque URL configurée et remplace seulement
+//! leur origine : les réponses se déclarent avec les chemins réels des services.
+
+use crate::config::{Config, TargetProbe};
+use reqwest::Url;
+use wiremock::MockServer;
+
+pub use wiremock;
+
+/// Serveur HTTP simulé, arrêté à la fin du test
+pub struct MockHttp {
+    server: MockServer,
+}
+
+impl MockHttp {
+    /// Démarre un serveur sur un port éphémère
+    pub async fn start() -> Self {
+        Self { server: MockServer::start().await }
+    }
+
+    /// Serveur `wiremock`, sur lequel monter les réponses attendues
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Origine du serveur (`http://127.0.0.1:port`)
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// URL absolue de `path` sur le serveur, par exemple pour un abonnement aux webhooks
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.uri(), path.trim_start_matches('/'))
+    }
+
+    /// Redirige vers ce serveur les appels sortants de `config` : ping du score de
+    /// performance, cibles HTTP et pairs surveillés, notifications d'alertes et export
+    /// OTLP. Les adresses TCP et les emails ne sont pas concernés.
+    pub fn redirect(&self, config: &mut Config) {
+        let ping = config.monitoring.ping_url.clone().unwrap_or_else(|| "/api/help/ping".to_string());
+        config.monitoring.ping_url = Some(self.rebase(&ping));
+        for target in &mut config.monitoring.targets {
+            if let TargetProbe::Http { url, .. } = &mut target.probe {
+                *url = self.rebase(url);
+            }
+        }
+        for peer in &mut config.monitoring.peers {
+            peer.url = self.rebase(&peer.url);
+        }
+        for notifier in &mut config.alerts.notifiers {
+            // URL vide : API publique de PagerDuty ou d'Opsgenie, remplacée elle aussi
+            notifier.url = self.rebase(notifier.endpoint());
+        }
+        config.telemetry.endpoint = self.rebase(&config.telemetry.endpoint);
+    }
+
+    /// Remplace l'origine de `url` par celle du serveur ; un chemin seul est ajouté à l'origine
+    pub fn rebase(&self, url: &str) -> String {
+        match Url::parse(url) {
+            Ok(url) => {
+                let mut rebased = format!("{}{}", self.uri(), url.path().trim_end_matches('/'));
+                if let Some(query) = url.query() {
+                    rebased.push('?');
+                    rebased.push_str(query);
+                }
+                rebased
+            }
+            Err(_) => self.url(url),
+        }
+    }
+}
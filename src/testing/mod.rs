@@ -0,0 +1,27 @@
+//! # Testing Module
+//!
+//! Ce module fournit des utilitaires pour les tests d'intégration (feature `testing`,
+//! activée pour les tests par la dev-dependency du crate sur lui-même) :
+//! - `TestDatabase` : une base jetable par test, clonée depuis une base modèle migrée
+//! - `TestApp` : l'application complète servie sur un port éphémère, avec un client HTTP
+//! - `pool` : l'état et le routeur de l'application sur les pools de `#[sqlx::test]`
+//! - `container` (feature `testcontainers`) : un PostgreSQL jetable lancé par Docker
+//! - `contract` : tests de contrat générés depuis le document OpenAPI
+//! - `mock` : serveurs `wiremock` à la place des services HTTP externes
+//! - `snapshot` : champs volatils masqués pour les tests d'instantanés (`insta`)
+//! - `strategies` : entrées de requêtes générées pour les tests de propriétés (`proptest`)
+
+mod app;
+pub mod contract;
+#[cfg(feature = "testcontainers")]
+pub mod container;
+mod database;
+pub mod mock;
+pub mod pool;
+pub mod snapshot;
+pub mod strategies;
+
+pub use app::{TestApp, TestAppBuilder, TestClient, TestResponse, TEST_ADMIN_TOKEN};
+pub use database::TestDatabase;
+pub use mock::MockHttp;
+pub use pool::{app_router, app_state, test_config};
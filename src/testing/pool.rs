@@ -0,0 +1,54 @@
+//! # Test Pool Module
+//!
+//! Ce module raccorde à l'application les pools créés par `#[sqlx::test]` : chaque test
+//! reçoit une base neuve, migrée avec `db::MIGRATOR` (ou le dossier `migrations/`), et
+//! éventuellement remplie par des scripts SQL de `tests/fixtures/`.
+//!
+//! ```ignore
+//! #[sqlx::test(migrator = "template_axum_sqlx_api::db::MIGRATOR", fixtures("users"))]
+//! async fn test_list_users(pool: PgPool) {
+//!     let app = app_router(&app_state(pool));
+//!     // ...
+//! }
+//! ```
+
+use super::{database::with_database, TEST_ADMIN_TOKEN};
+use crate::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::{cors::cors_layer, logging::setup_middleware},
+    routes::create_router,
+    state::AppState,
+};
+use axum::Router;
+use sqlx::PgPool;
+
+/// Configuration par défaut des tests : `DATABASE_URL` si définie, et `TEST_ADMIN_TOKEN`
+/// comme jeton d'administration
+pub fn test_config() -> Config {
+    let mut config = Config::default();
+    if let Ok(url) = std::env::var("DATABASE_URL") {
+        config.database.url = url;
+    }
+    config.admin.token = Some(TEST_ADMIN_TOKEN.to_string());
+    config
+}
+
+/// État de l'application sur un pool existant, avec `test_config()` pointant vers sa base
+pub fn app_state(pool: PgPool) -> AppState {
+    let mut config = test_config();
+    if let Some(name) = pool.connect_options().get_database() {
+        config.database.url = with_database(&config.database.url, name);
+    }
+    let state = AppState::new(DatabaseManager::from_pool(pool), config);
+    state.readiness.mark_started();
+    state
+}
+
+/// Routeur complet de l'application (routes, CORS, middlewares), comme au démarrage
+pub fn app_router(state: &AppState) -> Router {
+    let app = Router::new()
+        .merge(create_router(state.clone()))
+        .layer(cors_layer(state));
+    setup_middleware(app)
+}
@@ -0,0 +1,57 @@
+//! # Snapshot Module
+//!
+//! Ce module prépare les réponses JSON pour les tests d'instantanés (`insta`) en
+//! remplaçant les champs qui changent à chaque exécution : identifiants, dates,
+//! durées. L'instantané ne change alors que si la forme de la réponse change.
+//!
+//! ```ignore
+//! let response = app.client().get("/api/help/info").await;
+//! insta::assert_json_snapshot!(response.redacted_json());
+//! ```
+
+use serde_json::Value;
+
+/// Valeur de remplacement d'un champ volatil, d'après son nom
+fn placeholder(key: &str) -> Option<&'static str> {
+    match key {
+        "timestamp" => Some("[timestamp]"),
+        "request_id" | "trace_id" | "span_id" => Some("[trace]"),
+        "id" => Some("[id]"),
+        _ if key.ends_with("_at") => Some("[timestamp]"),
+        _ if key.ends_with("_id") => Some("[id]"),
+        _ if key.ends_with("_ms") => Some("[duration]"),
+        _ => None,
+    }
+}
+
+/// Remplace récursivement les champs volatils (`id`, `*_id`, `timestamp`, `*_at`,
+/// `*_ms`, `request_id`...). Les valeurs `null` sont conservées : l'instantané
+/// montre toujours si un champ optionnel est renseigné.
+pub fn redact_volatile(value: Value) -> Value {
+    redact_fields(value, &[])
+}
+
+/// Comme `redact_volatile`, avec des champs supplémentaires et leur remplacement
+pub fn redact_fields(value: Value, extra: &[(&str, &str)]) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    let replacement = extra
+                        .iter()
+                        .find(|(name, _)| *name == key)
+                        .map(|(_, placeholder)| *placeholder)
+                        .or_else(|| placeholder(&key));
+                    let value = match replacement {
+                        Some(placeholder) if !value.is_null() => Value::from(placeholder),
+                        _ => redact_fields(value, extra),
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| redact_fields(item, extra)).collect()),
+        other => other,
+    }
+}
@@ -0,0 +1,136 @@
+//! # Strategies Module
+//!
+//! Ce module fournit des stratégies `proptest` qui génèrent des entrées de requêtes :
+//! query strings de pagination et du langage de `QueryOptions`, corps JSON quelconques
+//! ou proches d'un modèle. Les tests de propriétés vérifient ensuite des invariants
+//! (aucune erreur 500, enveloppe d'erreur valide, colonnes SQL issues de la liste blanche)
+//! sur des centaines d'entrées, réduites au cas minimal en cas d'échec.
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn parse_never_panics(query in query_options_query::<UserFields>()) {
+//!         let _ = QueryOptions::<UserFields>::parse(&query);
+//!     }
+//! }
+//! ```
+
+use crate::extractors::query::QuerySpec;
+use proptest::{
+    collection::{btree_map, vec},
+    prelude::*,
+    sample::select,
+};
+use serde_json::{Map, Number, Value};
+
+pub use proptest;
+
+/// Paramètres de pagination acceptés par l'extracteur `Pagination`
+const PAGINATION_KEYS: &[&str] = &["page", "per_page", "limit", "offset"];
+
+/// Nom de champ absent de toutes les listes blanches
+const UNKNOWN_FIELD: &str = "unknown";
+
+/// Texte quelconque, souvent fait des caractères qui structurent une query string
+pub fn query_text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z0-9_]{0,12}",
+        "[-+,=&%\\[\\]a-z0-9 ]{0,16}",
+        any::<String>(),
+    ]
+}
+
+/// Nombre tel qu'un client peut l'écrire : petit, aux bornes, négatif, trop grand ou pas un nombre
+pub fn number_text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (0u64..200).prop_map(|n| n.to_string()),
+        any::<u64>().prop_map(|n| n.to_string()),
+        any::<i64>().prop_map(|n| n.to_string()),
+        Just(u64::MAX.to_string()),
+        "[0-9]{20,30}",
+        query_text(),
+    ]
+}
+
+/// Query string de pagination : `page`, `per_page`, `limit` et `offset` combinés au hasard,
+/// éventuellement répétés
+pub fn pagination_query() -> impl Strategy<Value = String> {
+    vec((select(PAGINATION_KEYS), number_text()), 0..5).prop_map(|pairs| encode(&pairs))
+}
+
+/// Query string du langage de `QueryOptions<S>` : filtres, tris et recherche sur les champs
+/// de `S` et sur des champs inconnus, ou texte brut quelconque
+pub fn query_options_query<S: QuerySpec>() -> impl Strategy<Value = String> {
+    let filters = field_names(S::FILTERS);
+    let sorts = field_names(S::SORTS);
+    let key = prop_oneof![
+        select(filters).prop_map(|field| format!("filter[{}]", field)),
+        query_text().prop_map(|field| format!("filter[{}]", field)),
+        Just("sort".to_string()),
+        Just("search".to_string()),
+        query_text(),
+    ];
+    let sort = vec((any::<bool>(), select(sorts)), 0..4).prop_map(|fields| {
+        fields
+            .into_iter()
+            .map(|(descending, field)| format!("{}{}", if descending { "-" } else { "" }, field))
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+    let value = prop_oneof![sort, vec(query_text(), 0..4).prop_map(|values| values.join(",")), query_text()];
+    prop_oneof![
+        4 => vec((key, value), 0..6).prop_map(|pairs| encode(&pairs)),
+        1 => any::<String>(),
+    ]
+}
+
+/// Valeur JSON quelconque, imbriquée sur quelques niveaux
+pub fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_filter_map("JSON numbers are finite", |f| Number::from_f64(f).map(Value::Number)),
+        any::<String>().prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 32, 6, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..6).prop_map(Value::Array),
+            btree_map("[a-z_]{1,10}", inner, 0..6).prop_map(|fields| Value::Object(fields.into_iter().collect())),
+        ]
+    })
+}
+
+/// Corps JSON proche d'un modèle : chaque champ de `fields` est présent ou non, avec une
+/// valeur plausible (texte, email) ou quelconque, plus d'éventuels champs inconnus.
+/// Parfois, le corps n'est pas un objet.
+pub fn json_payload(fields: &'static [&'static str]) -> impl Strategy<Value = Value> {
+    let field_value = prop_oneof![
+        "[a-z]{1,10}@[a-z]{1,10}\\.[a-z]{2,3}".prop_map(Value::from),
+        "\\PC{0,300}".prop_map(Value::from),
+        json_value(),
+    ];
+    let known = vec(proptest::option::of(field_value), fields.len());
+    let extra = btree_map("[a-z_]{1,10}", json_value(), 0..3);
+    let object = (known, extra).prop_map(move |(values, extra)| {
+        let mut object: Map<String, Value> = extra.into_iter().collect();
+        for (field, value) in fields.iter().zip(values) {
+            if let Some(value) = value {
+                object.insert(field.to_string(), value);
+            }
+        }
+        Value::Object(object)
+    });
+    prop_oneof![9 => object, 1 => json_value()]
+}
+
+/// Noms des champs d'une liste blanche, plus un champ inconnu
+fn field_names(fields: &'static [(&'static str, &'static str)]) -> Vec<&'static str> {
+    fields.iter().map(|(name, _)| *name).chain([UNKNOWN_FIELD]).collect()
+}
+
+/// Encode des paires clé/valeur en query string
+fn encode<K: AsRef<str>, V: AsRef<str>>(pairs: &[(K, V)]) -> String {
+    let pairs: Vec<(&str, &str)> = pairs.iter().map(|(key, value)| (key.as_ref(), value.as_ref())).collect();
+    serde_urlencoded::to_string(pairs).unwrap_or_default()
+}
@@ -0,0 +1,94 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use axum::body::to_bytes;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    routes::create_router,
+    state::AppState,
+};
+
+fn create_app(token: Option<&str>) -> Router {
+    let mut config = Config::default();
+    config.admin.token = token.map(str::to_string);
+    create_router(AppState::new(DatabaseManager::new(), config))
+}
+
+fn routes_request(token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri("/api/routes.json");
+    if let Some(token) = token {
+        builder = builder.header("authorization", format!("Bearer {}", token));
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_routes_json_requires_token() {
+    let response = create_app(Some("secret")).oneshot(routes_request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = create_app(Some("secret")).oneshot(routes_request(Some("wrong"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_routes_json_disabled_without_configured_token() {
+    let response = create_app(None).oneshot(routes_request(Some("secret"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_routes_json_lists_registry() {
+    let response = create_app(Some("secret")).oneshot(routes_request(Some("secret"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let routes: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+
+    let sitemap = routes
+        .iter()
+        .find(|route| route["path"] == "/api/routes.json")
+        .expect("registry should describe itself");
+    assert_eq!(sitemap["method"], "GET");
+    assert_eq!(sitemap["auth"], "admin");
+    assert_eq!(sitemap["deprecated"], false);
+    assert!(sitemap["rate_limit"].is_null());
+
+    assert!(routes.iter().any(|route| route["path"] == "/api/help/ping" && route["auth"] == "none"));
+}
+
+#[tokio::test]
+async fn test_readiness_toggle_drains_instance() {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    let state = AppState::new(db, config);
+    state.readiness.mark_started();
+    let app = create_router(state);
+
+    let probe = || Request::builder().uri("/api/help/ready").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(probe()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let drain = Request::builder()
+        .method("PUT")
+        .uri("/api/admin/readiness")
+        .header("authorization", "Bearer secret")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"ready":false}"#))
+        .unwrap();
+    let response = app.clone().oneshot(drain).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // La readiness échoue, la liveness reste saine
+    let response = app.clone().oneshot(probe()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let ping = Request::builder().uri("/api/help/ping").body(Body::empty()).unwrap();
+    let response = app.oneshot(ping).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
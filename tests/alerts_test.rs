@@ -0,0 +1,237 @@
+use axum::{body::Bytes, http::StatusCode, routing::post, Router};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use template_axum_sqlx_api::{
+    config::{AlertCondition, AlertNotifier, AlertSeverity, AlertsConfig, EmailAlertsConfig, NotifierKind, SmtpTls},
+    models::status::PerformanceMetrics,
+    services::alerts::{AlertEngine, AlertState},
+};
+
+type Received = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+
+/// Démarre un receveur local qui enregistre le chemin et le corps des notifications
+async fn start_receiver() -> (String, Received) {
+    let received: Received = Arc::default();
+    let store = received.clone();
+    let app = Router::new().route("/{target}", post(move |axum::extract::Path(target): axum::extract::Path<String>, body: Bytes| {
+        let store = store.clone();
+        async move {
+            store.lock().unwrap().push((target, serde_json::from_slice(&body).unwrap()));
+            StatusCode::OK
+        }
+    }));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{}", addr), received)
+}
+
+fn metrics(db_connected: bool, health_score: u8) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected,
+        db_response_time_ms: db_connected.then_some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+fn settings(toml: &str) -> AlertsConfig {
+    toml::from_str(toml).expect("invalid alerts config")
+}
+
+#[test]
+fn test_alert_rules_from_config() {
+    let settings = settings(
+        r#"
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+
+        [[rules]]
+        name = "low-health-score"
+        kind = "health_score_below"
+        threshold = 60
+        "#,
+    );
+
+    assert_eq!(settings.debounce_samples, 2);
+    assert_eq!(settings.rules[0].condition, AlertCondition::DbDown);
+    assert_eq!(settings.rules[1].condition, AlertCondition::HealthScoreBelow { threshold: 60 });
+    assert_eq!(settings.rules[0].severity, AlertSeverity::Warning);
+    assert!(settings.email.is_none());
+}
+
+#[test]
+fn test_alerts_are_debounced() {
+    let mut engine = AlertEngine::new(&settings(
+        r#"
+        debounce_samples = 2
+
+        [[rules]]
+        name = "low-health-score"
+        kind = "health_score_below"
+        threshold = 60
+        "#,
+    ));
+
+    // Une mesure isolée en échec ne déclenche rien
+    assert!(engine.evaluate(&metrics(true, 40)).is_empty());
+    assert!(engine.evaluate(&metrics(true, 90)).is_empty());
+
+    assert!(engine.evaluate(&metrics(true, 40)).is_empty());
+    let alerts = engine.evaluate(&metrics(true, 35));
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].state, AlertState::Firing);
+    assert_eq!(alerts[0].message, "Health score is 35 (below 60)");
+
+    // Pas de nouvelle notification tant que l'état ne change pas
+    assert!(engine.evaluate(&metrics(true, 30)).is_empty());
+
+    assert!(engine.evaluate(&metrics(true, 90)).is_empty());
+    let alerts = engine.evaluate(&metrics(true, 90));
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].state, AlertState::Resolved);
+}
+
+#[tokio::test]
+async fn test_alerts_are_sent_to_notifiers() {
+    let (base_url, received) = start_receiver().await;
+    let mut settings = settings(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+        "#,
+    );
+    settings.notifiers = ["slack", "discord", "webhook"]
+        .into_iter()
+        .zip([NotifierKind::Slack, NotifierKind::Discord, NotifierKind::Webhook])
+        .map(|(path, kind)| AlertNotifier { kind, url: format!("{}/{}", base_url, path), key: None })
+        .collect();
+    let mut engine = AlertEngine::new(&settings);
+
+    engine.process(&metrics(false, 50)).await;
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 3);
+    let body = |target: &str| received.iter().find(|(t, _)| t == target).unwrap().1.clone();
+    assert_eq!(body("slack")["text"], "🔴 [database-down] Database is unreachable");
+    assert_eq!(body("discord")["content"], "🔴 [database-down] Database is unreachable");
+    assert_eq!(body("webhook")["rule"], "database-down");
+    assert_eq!(body("webhook")["state"], "firing");
+}
+
+/// Démarre un serveur SMTP minimal qui enregistre le contenu (DATA) de chaque email
+async fn start_smtp_server() -> (u16, Arc<Mutex<Vec<String>>>) {
+    let received: Arc<Mutex<Vec<String>>> = Arc::default();
+    let store = received.clone();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let store = store.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+                let mut data: Option<String> = None;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(body) = data.as_mut() {
+                        if line == "." {
+                            store.lock().unwrap().push(data.take().unwrap());
+                            writer.write_all(b"250 queued\r\n").await.unwrap();
+                        } else {
+                            body.push_str(&line);
+                            body.push('\n');
+                        }
+                        continue;
+                    }
+                    let reply: &[u8] = match line.get(..4).unwrap_or_default().to_uppercase().as_str() {
+                        "EHLO" => b"250 localhost\r\n",
+                        "DATA" => {
+                            data = Some(String::new());
+                            b"354 end with .\r\n"
+                        }
+                        "QUIT" => {
+                            writer.write_all(b"221 bye\r\n").await.unwrap();
+                            break;
+                        }
+                        _ => b"250 OK\r\n",
+                    };
+                    writer.write_all(reply).await.unwrap();
+                }
+            });
+        }
+    });
+
+    (port, received)
+}
+
+#[tokio::test]
+async fn test_alerts_are_sent_by_email() {
+    let (port, received) = start_smtp_server().await;
+    let mut settings = settings(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+        severity = "critical"
+
+        [[rules]]
+        name = "low-health-score"
+        kind = "health_score_below"
+        threshold = 60
+        severity = "info"
+        "#,
+    );
+    settings.email = Some(EmailAlertsConfig {
+        host: "127.0.0.1".to_string(),
+        port,
+        tls: SmtpTls::None,
+        from: "API alerts <alerts@example.com>".to_string(),
+        recipients: vec!["ops@example.com".to_string()],
+        subject_prefix: "[api]".to_string(),
+        ..EmailAlertsConfig::default()
+    });
+    let mut engine = AlertEngine::new(&settings);
+
+    // La règle `info` est sous `min_severity` (warning) : seule la règle critique est envoyée
+    engine.process(&metrics(false, 50)).await;
+    engine.process(&metrics(true, 90)).await;
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2, "{:?}", received);
+    assert!(received[0].contains("Subject: [api] [CRITICAL] database-down: Database is unreachable"));
+    assert!(received[0].contains("To: ops@example.com"));
+    assert!(received[0].contains("Alert database-down is firing."));
+    assert!(received[1].contains("Subject: [api] [RESOLVED] database-down"));
+    assert!(received[1].contains("Alert database-down is resolved."));
+}
@@ -0,0 +1,87 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    allocator::fragmentation_percent,
+    config::Config,
+    db::DatabaseManager,
+    routes::create_router,
+    state::AppState,
+};
+
+fn create_app() -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    create_router(AppState::new(DatabaseManager::new(), config))
+}
+
+fn admin_request(method: Method, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", "Bearer secret")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[test]
+fn test_fragmentation_percent() {
+    assert_eq!(fragmentation_percent(200, 150), Some(25.0));
+    assert_eq!(fragmentation_percent(100, 120), Some(0.0));
+    assert_eq!(fragmentation_percent(0, 0), None);
+}
+
+#[tokio::test]
+async fn test_allocator_stats() {
+    let response = create_app().oneshot(admin_request(Method::GET, "/api/admin/allocator")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    if cfg!(feature = "jemalloc") {
+        assert_eq!(stats["allocator"], "jemalloc");
+        assert!(stats["resident_bytes"].as_u64().unwrap() > 0);
+        assert!(stats["allocated_bytes"].as_u64().unwrap() > 0);
+    } else if cfg!(feature = "mimalloc") {
+        assert_eq!(stats["allocator"], "mimalloc");
+        assert!(stats["resident_bytes"].as_u64().unwrap() > 0);
+    } else {
+        assert_eq!(stats["allocator"], "system");
+        assert!(stats["resident_bytes"].is_null());
+        assert_eq!(stats["heap_profiling"], false);
+    }
+}
+
+#[tokio::test]
+async fn test_heap_profile() {
+    let response = create_app().oneshot(admin_request(Method::GET, "/api/admin/allocator")).await.unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let response = create_app()
+        .oneshot(admin_request(Method::POST, "/api/admin/allocator/heap-profile"))
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    if stats["heap_profiling"] == true {
+        assert_eq!(status, StatusCode::OK);
+        assert!(!body.is_empty());
+    } else {
+        // Sans jemalloc ni `_RJEM_MALLOC_CONF=prof:true`, aucun profil ne peut être produit
+        assert_eq!(status, StatusCode::CONFLICT);
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["code"], "HEAP_PROFILING_UNAVAILABLE");
+    }
+}
+
+#[tokio::test]
+async fn test_allocator_routes_require_token() {
+    let request = Request::builder().uri("/api/admin/allocator").body(Body::empty()).unwrap();
+    let response = create_app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,156 @@
+use chrono::Utc;
+use template_axum_sqlx_api::{
+    config::{AlertsConfig, AnomalyConfig},
+    models::status::{generate_issues, AnomalyMetric, HistoryEntry, PerformanceMetrics, RequestCounts},
+    services::{
+        alerts::{AlertEngine, AlertState},
+        anomalies::{AnomalyDetector, Baseline},
+    },
+};
+
+fn metrics(response_time_ms: u64, requests: Option<RequestCounts>) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: response_time_ms as f64,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+fn requests(success: u64, server_errors: u64) -> Option<RequestCounts> {
+    Some(RequestCounts { success, redirection: 0, client_errors: 0, server_errors })
+}
+
+fn history(response_time_ms: u64) -> HistoryEntry {
+    HistoryEntry {
+        timestamp: Utc::now(),
+        response_time_ms,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        issues: Vec::new(),
+        network: None,
+    }
+}
+
+fn settings(min_samples: usize) -> AnomalyConfig {
+    AnomalyConfig { min_samples, ..AnomalyConfig::default() }
+}
+
+#[test]
+fn test_baseline() {
+    let baseline = Baseline::from_samples(&[10.0, 20.0, 30.0]).unwrap();
+    assert_eq!(baseline.mean, 20.0);
+    assert!((baseline.stddev - 8.165).abs() < 0.001);
+    assert!((baseline.z_score(40.0, 1.0) - 2.449).abs() < 0.001);
+    // Série constante : l'écart type minimal évite une division par zéro
+    assert_eq!(Baseline::from_samples(&[10.0, 10.0]).unwrap().z_score(20.0, 5.0), 2.0);
+    assert!(Baseline::from_samples(&[]).is_none());
+}
+
+#[test]
+fn test_latency_spike_is_detected() {
+    let mut detector = AnomalyDetector::new(&settings(5));
+
+    for response_time in [40, 45, 50, 55, 60, 45, 50] {
+        assert!(detector.observe(&metrics(response_time, None)).is_empty());
+    }
+
+    // Sous les seuils fixes (500 ms), mais loin des mesures habituelles
+    let anomalies = detector.observe(&metrics(300, None));
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].metric, AnomalyMetric::ResponseTime);
+    assert!(anomalies[0].z_score > 3.0);
+    assert!(anomalies[0].describe().starts_with("Latence inhabituelle: 300 ms"));
+
+    // Les baisses ne sont pas signalées
+    assert!(detector.observe(&metrics(1, None)).is_empty());
+}
+
+#[test]
+fn test_baseline_is_seeded_from_history() {
+    let mut detector = AnomalyDetector::new(&settings(5));
+    // Pas d'anomalie tant que la référence est trop courte
+    detector.seed(&[20, 22].map(history));
+    assert!(detector.observe(&metrics(200, None)).is_empty());
+
+    let mut detector = AnomalyDetector::new(&settings(5));
+    detector.seed(&[20, 22, 18, 21, 19, 20].map(history));
+
+    assert_eq!(detector.observe(&metrics(200, None)).len(), 1);
+}
+
+#[test]
+fn test_error_rate_spike_is_detected() {
+    let mut detector = AnomalyDetector::new(&settings(3));
+    for _ in 0..4 {
+        assert!(detector.observe(&metrics(50, requests(100, 0))).is_empty());
+    }
+
+    // Trop peu de réponses sur l'intervalle pour juger du taux
+    assert!(detector.observe(&metrics(50, requests(2, 3))).is_empty());
+
+    let anomalies = detector.observe(&metrics(50, requests(90, 10)));
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].metric, AnomalyMetric::ErrorRate);
+    assert_eq!(anomalies[0].value, 10.0);
+}
+
+#[test]
+fn test_disabled_detector_reports_nothing() {
+    let mut detector = AnomalyDetector::new(&AnomalyConfig { enabled: false, ..settings(2) });
+    detector.seed(&[10, 10, 10].map(history));
+
+    assert!(detector.observe(&metrics(5000, None)).is_empty());
+}
+
+#[test]
+fn test_anomalies_become_issues_and_alerts() {
+    let mut detector = AnomalyDetector::new(&settings(3));
+    detector.seed(&[30, 32, 28, 30].map(history));
+    let spike = metrics(250, None);
+    let anomalies = detector.observe(&spike);
+
+    let issues = generate_issues(true, Some(2), 250, 10.0, 30.0, 40.0, None, &anomalies);
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].starts_with("Latence inhabituelle"));
+
+    let settings: AlertsConfig = toml::from_str(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "unusual-latency"
+        kind = "anomaly"
+        "#,
+    )
+    .unwrap();
+    let mut engine = AlertEngine::new(&settings);
+    engine.set_anomalies(anomalies);
+    let alerts = engine.evaluate(&spike);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].state, AlertState::Firing);
+
+    engine.set_anomalies(Vec::new());
+    assert_eq!(engine.evaluate(&metrics(30, None))[0].state, AlertState::Resolved);
+}
@@ -0,0 +1,122 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::{
+        incidents::{Incident, IncidentDetail, IncidentId},
+        status::{PerformanceMetrics, ServiceStatus, DEGRADED_STATUS},
+    },
+    routes::create_router,
+    state::AppState,
+};
+
+async fn state() -> AppState {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    AppState::new(db, Config::default())
+}
+
+async fn get(app: Router, uri: &str) -> (StatusCode, String, String) {
+    let response = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn metrics(health_score: u8, status: &str) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: status.to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+fn incident(severity: &str) -> IncidentDetail {
+    IncidentDetail {
+        incident: Incident {
+            id: IncidentId::new(1),
+            title: "Outage".to_string(),
+            severity: severity.to_string(),
+            status: "investigating".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            resolved_at: None,
+        },
+        updates: Vec::new(),
+    }
+}
+
+#[test]
+fn test_service_status_evaluation() {
+    let healthy = metrics(95, "Optimal");
+    assert_eq!(ServiceStatus::evaluate(None, &[]), ServiceStatus::Unknown);
+    assert_eq!(ServiceStatus::evaluate(Some(&healthy), &[]), ServiceStatus::Operational);
+    assert_eq!(ServiceStatus::evaluate(Some(&metrics(45, "Stable")), &[]), ServiceStatus::Degraded);
+    assert_eq!(ServiceStatus::evaluate(Some(&metrics(95, DEGRADED_STATUS)), &[]), ServiceStatus::Down);
+    assert_eq!(ServiceStatus::evaluate(Some(&healthy), &[incident("minor")]), ServiceStatus::Degraded);
+    assert_eq!(ServiceStatus::evaluate(Some(&healthy), &[incident("critical")]), ServiceStatus::Down);
+}
+
+#[tokio::test]
+async fn test_svg_badge_reflects_current_metrics() {
+    let state = state().await;
+
+    let (status, content_type, svg) = get(create_router(state.clone()), "/status/badge.svg").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("status: unknown"));
+
+    state.metrics.publish(metrics(95, "Optimal"));
+    let (_, _, svg) = get(create_router(state.clone()), "/status/badge.svg?label=my%20api").await;
+    assert!(svg.contains("my api: operational"));
+    assert!(svg.contains("#4c1"));
+
+    state.metrics.publish(metrics(95, DEGRADED_STATUS));
+    let (_, _, svg) = get(create_router(state), "/status/badge.svg").await;
+    assert!(svg.contains("status: down"));
+}
+
+#[tokio::test]
+async fn test_shields_endpoint_badge() {
+    let state = state().await;
+    state.metrics.publish(metrics(50, "Stable"));
+
+    let (status, content_type, body) = get(create_router(state), "/api/status/badge?label=api").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json");
+    let badge: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(badge["schemaVersion"], 1);
+    assert_eq!(badge["label"], "api");
+    assert_eq!(badge["message"], "degraded");
+    assert_eq!(badge["color"], "yellow");
+}
@@ -0,0 +1,162 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    middleware::from_fn_with_state,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{CacheConfig, Config},
+    db::DatabaseManager,
+    middleware::cache::{cache_responses, ResponseCache, X_CACHE},
+    routes::create_router,
+    state::AppState,
+};
+
+struct App {
+    router: Router,
+    cache: Arc<ResponseCache>,
+    calls: Arc<AtomicUsize>,
+}
+
+fn create_app() -> App {
+    let mut config = CacheConfig::default();
+    config.routes.insert("/items/{id}".to_string(), 60);
+    config.routes.insert("/private".to_string(), 60);
+    let cache = Arc::new(ResponseCache::new(&config));
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let private_counter = calls.clone();
+    let router = Router::new()
+        .route(
+            "/items/{id}",
+            get(move || async move { counter.fetch_add(1, Ordering::SeqCst).to_string() }),
+        )
+        .route(
+            "/private",
+            get(move || async move {
+                private_counter.fetch_add(1, Ordering::SeqCst);
+                ([(header::CACHE_CONTROL, "private")], "secret").into_response()
+            }),
+        )
+        .route("/uncached", get(|| async { "fresh" }))
+        .layer(from_fn_with_state(cache.clone(), cache_responses));
+
+    App { router, cache, calls }
+}
+
+async fn send(app: &App, uri: &str, headers: &[(header::HeaderName, &str)]) -> (Option<String>, String) {
+    let mut request = Request::builder().uri(uri);
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    let response = app
+        .router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let x_cache = response
+        .headers()
+        .get(X_CACHE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (x_cache, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_second_request_is_served_from_cache() {
+    let app = create_app();
+
+    assert_eq!(send(&app, "/items/1", &[]).await, (Some("MISS".to_string()), "0".to_string()));
+    assert_eq!(send(&app, "/items/1", &[]).await, (Some("HIT".to_string()), "0".to_string()));
+    // Autre chemin, autre query string : autres entrées
+    assert_eq!(send(&app, "/items/2", &[]).await.1, "1");
+    assert_eq!(send(&app, "/items/1?page=2", &[]).await.1, "2");
+    assert_eq!(app.calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_cache_headers() {
+    let app = create_app();
+    send(&app, "/items/1", &[]).await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(Request::builder().uri("/items/1").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let headers = response.headers();
+    assert_eq!(headers[header::CACHE_CONTROL], "public, max-age=60");
+    assert_eq!(headers[header::AGE], "0");
+    assert_eq!(headers[header::VARY], "accept, accept-encoding");
+}
+
+#[tokio::test]
+async fn test_vary_headers_and_credentials_bypass() {
+    let app = create_app();
+    send(&app, "/items/1", &[(header::ACCEPT, "application/json")]).await;
+
+    let (x_cache, _) = send(&app, "/items/1", &[(header::ACCEPT, "text/csv")]).await;
+    assert_eq!(x_cache.as_deref(), Some("MISS"));
+
+    let (x_cache, body) = send(&app, "/items/1", &[(header::AUTHORIZATION, "Bearer token")]).await;
+    assert_eq!(x_cache, None);
+    assert_eq!(body, "2");
+}
+
+#[tokio::test]
+async fn test_uncacheable_responses_are_not_stored() {
+    let app = create_app();
+
+    assert_eq!(send(&app, "/uncached", &[]).await.0, None);
+    send(&app, "/private", &[]).await;
+    send(&app, "/private", &[]).await;
+    assert_eq!(app.calls.load(Ordering::SeqCst), 2);
+    assert!(app.cache.is_empty());
+}
+
+#[tokio::test]
+async fn test_invalidation() {
+    let app = create_app();
+    send(&app, "/items/1", &[]).await;
+    send(&app, "/items/1?fields=id", &[]).await;
+    send(&app, "/items/2", &[]).await;
+    assert_eq!(app.cache.len(), 3);
+
+    app.cache.invalidate_path("/items/1");
+    assert_eq!(app.cache.len(), 1);
+    assert_eq!(send(&app, "/items/1", &[]).await.0.as_deref(), Some("MISS"));
+
+    // `no-cache` force le recalcul et rafraîchit l'entrée
+    let (x_cache, body) = send(&app, "/items/2", &[(header::CACHE_CONTROL, "no-cache")]).await;
+    assert_eq!((x_cache.as_deref(), body.as_str()), (Some("MISS"), "4"));
+
+    app.cache.clear();
+    assert!(app.cache.is_empty());
+}
+
+#[tokio::test]
+async fn test_nested_routes_use_their_full_template() {
+    let mut config = Config::default();
+    config.cache.routes.insert("/api/help/info".to_string(), 30);
+    let app = create_router(AppState::new(DatabaseManager::new(), config));
+
+    for expected in ["MISS", "HIT"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/help/info").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[X_CACHE], expected);
+    }
+}
@@ -0,0 +1,87 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    middleware::from_fn_with_state,
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, JsonCase},
+    handlers::{error::AppError, response::ApiResponse},
+    middleware::casing::{camel_to_snake, json_casing, rename_keys, snake_to_camel},
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Profile {
+    display_name: String,
+    avatar_url: Option<String>,
+}
+
+async fn echo(Json(profile): Json<Profile>) -> Result<ApiResponse<Profile>, AppError> {
+    if profile.display_name.is_empty() {
+        return Err(AppError::BadRequest("display_name is required".to_string()));
+    }
+    Ok(ApiResponse::ok(profile))
+}
+
+fn app(json_case: JsonCase) -> Router {
+    let mut config = Config::default();
+    config.api.json_case = json_case;
+    Router::new()
+        .route("/profile", post(echo))
+        .layer(from_fn_with_state(Arc::new(config), json_casing))
+}
+
+async fn send(app: Router, body: Value) -> (StatusCode, Value) {
+    let request = Request::post("/profile")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn test_key_conversions() {
+    assert_eq!(snake_to_camel("total_pages"), "totalPages");
+    assert_eq!(snake_to_camel("id"), "id");
+    assert_eq!(snake_to_camel("_links"), "_links");
+    assert_eq!(camel_to_snake("perPage"), "per_page");
+    assert_eq!(camel_to_snake("requestId"), "request_id");
+    assert_eq!(camel_to_snake("email"), "email");
+
+    let value = json!({ "user_id": 1, "tags": [{ "created_at": "x" }], "note": "keep_value" });
+    assert_eq!(
+        rename_keys(value, &snake_to_camel),
+        json!({ "userId": 1, "tags": [{ "createdAt": "x" }], "note": "keep_value" })
+    );
+}
+
+#[tokio::test]
+async fn test_camel_case_round_trip() {
+    let (status, body) = send(app(JsonCase::Camel), json!({ "displayName": "Ada", "avatarUrl": null })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({ "displayName": "Ada", "avatarUrl": null }));
+    assert!(body["meta"].get("timestamp").is_some());
+}
+
+#[tokio::test]
+async fn test_snake_case_is_unchanged() {
+    let (status, body) = send(app(JsonCase::Snake), json!({ "display_name": "Ada" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({ "display_name": "Ada", "avatar_url": null }));
+
+    // En snake_case, une clé camelCase n'est pas reconnue
+    let request = Request::post("/profile")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "displayName": "Ada" }).to_string()))
+        .unwrap();
+    let response = app(JsonCase::Snake).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,183 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, PeerNode},
+    db::DatabaseManager,
+    models::status::{ClusterStatus, NodeStatus, PerformanceMetrics, ServiceStatus},
+    routes::create_router,
+    services::cluster::Cluster,
+    state::AppState,
+};
+
+async fn state(config: Config) -> AppState {
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    AppState::new(db, config)
+}
+
+/// Démarre une instance dont les métriques sont déjà calculées
+async fn start_peer() -> String {
+    let state = state(Config::default()).await;
+    state.metrics.publish(metrics());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = create_router(state);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    format!("http://{}", addr)
+}
+
+/// Adresse locale sur laquelle plus rien n'écoute
+async fn closed_url() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+fn metrics() -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+fn node(name: &str, status: ServiceStatus, health_score: Option<u8>) -> NodeStatus {
+    NodeStatus {
+        name: name.to_string(),
+        url: None,
+        local: false,
+        reachable: status != ServiceStatus::Down,
+        status,
+        health_score,
+        response_time_ms: None,
+        checked_at: Utc::now(),
+        error: None,
+    }
+}
+
+fn config_with_peers(peers: Vec<PeerNode>) -> Config {
+    let mut config = Config::default();
+    config.monitoring.node_name = Some("api-1".to_string());
+    config.monitoring.timeout_ms = 1000;
+    config.monitoring.peers = peers;
+    config
+}
+
+#[test]
+fn test_cluster_aggregate() {
+    use ServiceStatus::*;
+
+    let all_up = ClusterStatus::aggregate(vec![node("a", Operational, Some(90)), node("b", Operational, Some(80))]);
+    assert_eq!(all_up.status, Operational);
+    assert_eq!(all_up.health_score, Some(85));
+    assert_eq!(all_up.nodes_up, 2);
+
+    let one_down = ClusterStatus::aggregate(vec![node("a", Operational, Some(90)), node("b", Down, None)]);
+    assert_eq!(one_down.status, Degraded);
+    assert_eq!(one_down.health_score, Some(90));
+    assert_eq!(one_down.nodes_up, 1);
+
+    let all_down = ClusterStatus::aggregate(vec![node("a", Down, None), node("b", Unknown, None)]);
+    assert_eq!(all_down.status, Down);
+    assert_eq!(all_down.nodes_up, 0);
+
+    let pending = ClusterStatus::aggregate(vec![node("a", Unknown, None)]);
+    assert_eq!(pending.status, Unknown);
+    assert_eq!(pending.health_score, None);
+}
+
+#[tokio::test]
+async fn test_cluster_polls_peers() {
+    let peers = vec![
+        PeerNode { name: "api-2".to_string(), url: start_peer().await },
+        PeerNode { name: "api-3".to_string(), url: closed_url().await },
+    ];
+    let cluster = Cluster::new(&config_with_peers(peers).monitoring);
+
+    // Avant la première interrogation, les pairs sont en état inconnu
+    let pending = cluster.status(cluster.local_node(Some(&metrics()), &[]));
+    assert_eq!(pending.nodes.len(), 3);
+    assert_eq!(pending.nodes[1].status, ServiceStatus::Unknown);
+
+    let nodes = cluster.poll_all().await;
+    assert!(nodes[0].reachable);
+    assert!(nodes[0].error.is_none(), "{:?}", nodes[0].error);
+    assert_eq!(nodes[0].health_score, Some(95));
+    assert!(nodes[0].response_time_ms.is_some());
+    assert!(!nodes[1].reachable);
+    assert_eq!(nodes[1].status, ServiceStatus::Down);
+    assert!(nodes[1].error.is_some());
+
+    let status = cluster.status(cluster.local_node(Some(&metrics()), &[]));
+    assert_eq!(status.nodes[0].name, "api-1");
+    assert!(status.nodes[0].local);
+    // L'état du pair dépend aussi des incidents ouverts dans la base partagée
+    assert!(status.nodes_up >= 1);
+    assert_eq!(status.status, ServiceStatus::Degraded);
+}
+
+#[tokio::test]
+async fn test_cluster_endpoint_lists_local_node() {
+    let state = state(config_with_peers(Vec::new())).await;
+    state.metrics.publish(metrics());
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/api/status/cluster").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+    let nodes = body["data"]["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0]["name"], "api-1");
+    assert_eq!(nodes[0]["local"], true);
+    assert_eq!(body["data"]["health_score"], 95);
+}
+
+#[tokio::test]
+async fn test_status_page_shows_cluster_grid() {
+    let page = |config: Config| async move {
+        let response = create_router(state(config).await)
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    };
+
+    let single = page(config_with_peers(Vec::new())).await;
+    assert!(single.contains(r#"id="cluster" class="card bg-base-100 shadow-xl border border-base-300 mb-6 hidden""#));
+
+    let peers = vec![PeerNode { name: "api-2".to_string(), url: closed_url().await }];
+    let clustered = page(config_with_peers(peers)).await;
+    assert!(clustered.contains(r#"id="cluster" class="card bg-base-100 shadow-xl border border-base-300 mb-6 ""#));
+    assert!(clustered.contains("api-1"));
+    assert!(clustered.contains("api-2"));
+    assert!(!clustered.contains("{CLUSTER_"), "cluster placeholders left in the page");
+}
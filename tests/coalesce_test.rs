@@ -0,0 +1,98 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::from_fn_with_state,
+    routing::get,
+    Router,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::middleware::coalesce::{coalesce, Coalescer};
+
+/// Handler lent qui compte ses exécutions
+async fn slow_report(State(calls): State<Arc<AtomicUsize>>) -> String {
+    let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    format!("report #{}", call)
+}
+
+fn app(ttl: Duration) -> (Router, Arc<AtomicUsize>, Arc<Coalescer>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let coalescer = Arc::new(Coalescer::new(ttl));
+    let router = Router::new()
+        .route("/report", get(slow_report))
+        .route_layer(from_fn_with_state(coalescer.clone(), coalesce))
+        .with_state(calls.clone());
+    (router, calls, coalescer)
+}
+
+async fn get_body(app: Router, uri: &str, authorized: bool) -> String {
+    let mut request = Request::builder().uri(uri);
+    if authorized {
+        request = request.header(header::AUTHORIZATION, "Bearer token");
+    }
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_concurrent_identical_requests_share_one_computation() {
+    let (app, calls, coalescer) = app(Duration::ZERO);
+
+    let requests = (0..10).map(|_| get_body(app.clone(), "/report?period=month", false));
+    let bodies = futures::future::join_all(requests).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(bodies.iter().all(|body| body == "report #1"));
+
+    // Sans TTL, rien n'est conservé une fois le calcul terminé
+    assert!(coalescer.is_empty());
+    assert_eq!(get_body(app, "/report?period=month", false).await, "report #2");
+}
+
+#[tokio::test]
+async fn test_different_queries_are_not_coalesced() {
+    let (app, calls, _) = app(Duration::ZERO);
+
+    let (a, b) = tokio::join!(
+        get_body(app.clone(), "/report?period=month", false),
+        get_body(app.clone(), "/report?period=year", false)
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_ne!(a, b);
+}
+
+#[tokio::test]
+async fn test_successful_responses_are_cached_for_ttl() {
+    let (app, calls, _) = app(Duration::from_millis(300));
+
+    assert_eq!(get_body(app.clone(), "/report", false).await, "report #1");
+    assert_eq!(get_body(app.clone(), "/report", false).await, "report #1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    assert_eq!(get_body(app, "/report", false).await, "report #2");
+}
+
+#[tokio::test]
+async fn test_authorized_requests_bypass_coalescing() {
+    let (app, calls, _) = app(Duration::from_secs(60));
+
+    let (a, b) = tokio::join!(
+        get_body(app.clone(), "/report", true),
+        get_body(app.clone(), "/report", true)
+    );
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_ne!(a, b);
+}
@@ -0,0 +1,79 @@
+use serde_json::json;
+use template_axum_sqlx_api::{
+    fixtures::{
+        post::{CommentFactory, PostFactory},
+        user::UserFactory,
+        Factory,
+    },
+    testing::{contract::ContractRunner, TestApp},
+};
+
+#[tokio::test]
+async fn test_openapi_contract() {
+    let app = TestApp::spawn().await;
+    // `{id}` vaut 1 : une ligne existe pour les routes des utilisateurs et des posts
+    let user = UserFactory::new().create(app.db.pool()).await.unwrap();
+    let post = PostFactory::new(user.id).create(app.db.pool()).await.unwrap();
+    CommentFactory::new(post.id).author(user.id).create(app.db.pool()).await.unwrap();
+    assert_eq!((*user.id.get(), *post.id.get()), (1, 1));
+
+    let report = ContractRunner::new(&app).await.run().await;
+    report.assert_ok();
+    assert!(report.checked.len() > 50, "only {} operations checked", report.checked.len());
+    assert!(report.skipped.contains(&"GET /api/status/live".to_string()));
+    assert!(report.checked.contains(&("GET /api/users/{id}".to_string(), 200)));
+    assert!(report.checked.contains(&("POST /api/users".to_string(), 201)));
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_contract_reports_drift() {
+    let app = TestApp::spawn().await;
+    let mut doc = app.client().get("/api-doc/openapi.json").await.json();
+    // Champ documenté comme obligatoire mais jamais renvoyé par le handler
+    doc["components"]["schemas"]["InfoResponse"]["required"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!("license"));
+    // Statut de succès que le handler ne renvoie pas
+    let responses = doc["paths"]["/api/help/ping"]["get"]["responses"].as_object_mut().unwrap();
+    let ok = responses.remove("200").unwrap();
+    responses.insert("202".to_string(), ok);
+
+    let report = ContractRunner::with_document(&app, doc).skip("GET /api/help/health").run().await;
+    assert!(report.skipped.contains(&"GET /api/help/health".to_string()));
+    let failures = report.failures.join("\n");
+    assert!(failures.contains("GET /api/help/info: status 200: response does not match its schema"), "{}", failures);
+    assert!(failures.contains("\"license\" is a required property"), "{}", failures);
+    assert!(failures.contains("GET /api/help/ping: undocumented status 200"), "{}", failures);
+    assert_eq!(report.failures.len(), 2, "{}", failures);
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_contract_examples_follow_schemas() {
+    let app = TestApp::spawn().await;
+    let runner = ContractRunner::new(&app).await;
+
+    let new_user = runner.example(&json!({ "$ref": "#/components/schemas/NewUser" }));
+    assert_eq!(new_user["email"], "contract@example.com");
+    assert!(new_user["name"].is_string());
+
+    let schema = json!({
+        "type": "object",
+        "required": ["tags", "status"],
+        "properties": {
+            "tags": { "type": "array", "items": { "type": "string", "minLength": 10 }, "minItems": 2 },
+            "status": { "type": "string", "enum": ["open", "closed"] },
+            "note": { "type": ["string", "null"] }
+        }
+    });
+    let example = runner.example(&schema);
+    assert_eq!(example, json!({ "tags": ["examplexxx", "examplexxx"], "status": "open" }));
+    assert!(runner.validate(&schema, &example).is_ok());
+    assert!(runner.validate(&schema, &json!({ "tags": [] })).is_err());
+
+    app.close().await;
+}
@@ -0,0 +1,133 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use once_cell::sync::Lazy;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::cors::cors_layer,
+    routes::create_router,
+    services::cors::normalize_origin,
+    state::AppState,
+};
+
+static TEST_MUTEX: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+async fn create_app() -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    config.cors.dynamic_origins = true;
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    let state = AppState::new(db, config);
+
+    create_router(state.clone()).layer(cors_layer(&state))
+}
+
+async fn allowed_origin(app: &Router, origin: &str) -> Option<String> {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/help/ping")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+fn admin_request(method: &str, uri: &str, body: Body) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", "Bearer secret")
+        .header("content-type", "application/json")
+        .body(body)
+        .unwrap()
+}
+
+#[test]
+fn test_normalize_origin() {
+    assert_eq!(normalize_origin("HTTPS://App.Example.com/").unwrap(), "https://app.example.com");
+    assert_eq!(normalize_origin("http://localhost:3000").unwrap(), "http://localhost:3000");
+    assert!(normalize_origin("app.example.com").is_err());
+    assert!(normalize_origin("https://app.example.com/path").is_err());
+}
+
+#[tokio::test]
+async fn test_static_origins_are_allowed() {
+    let _lock = TEST_MUTEX.lock().await;
+    let app = create_app().await;
+
+    assert_eq!(
+        allowed_origin(&app, "http://localhost:3000").await.as_deref(),
+        Some("http://localhost:3000")
+    );
+    assert_eq!(allowed_origin(&app, "https://evil.example.com").await, None);
+}
+
+#[tokio::test]
+async fn test_registered_origin_is_allowed_immediately() {
+    let _lock = TEST_MUTEX.lock().await;
+    let app = create_app().await;
+    let origin = "https://tenant-cors-test.example.com";
+
+    // Chargement du cache avant l'enregistrement
+    assert_eq!(allowed_origin(&app, origin).await, None);
+
+    let response = app
+        .clone()
+        .oneshot(admin_request(
+            "POST",
+            "/api/admin/cors-origins",
+            Body::from(format!(r#"{{"origin": "{}/", "tenant": "acme"}}"#, origin)),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created["origin"], origin);
+
+    // Le cache est invalidé par l'enregistrement
+    assert_eq!(allowed_origin(&app, origin).await.as_deref(), Some(origin));
+
+    let response = app
+        .clone()
+        .oneshot(admin_request(
+            "DELETE",
+            &format!("/api/admin/cors-origins/{}", created["id"]),
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(allowed_origin(&app, origin).await, None);
+}
+
+#[tokio::test]
+async fn test_invalid_origin_is_rejected() {
+    let _lock = TEST_MUTEX.lock().await;
+    let app = create_app().await;
+
+    let response = app
+        .oneshot(admin_request(
+            "POST",
+            "/api/admin/cors-origins",
+            Body::from(r#"{"origin": "not-an-origin"}"#),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,80 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    logs::{LogBuffer, LogBufferLayer},
+    middleware::{
+        debug_trace::{DebugTraceFilter, X_DEBUG_TRACE, X_TRACE_ID},
+        logging::setup_middleware,
+    },
+    models::help::LogLevel,
+    routes::create_router,
+    state::AppState,
+};
+
+async fn create_app(debug_trace: bool) -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    config.logging.debug_trace = debug_trace;
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    setup_middleware(create_router(AppState::new(db, config)))
+}
+
+/// Envoie une requête qui interroge la base et retourne la réponse et les logs de niveau debug ou inférieur
+async fn request(app: Router, debug_header: Option<&str>) -> (StatusCode, Option<String>, Vec<String>) {
+    let buffer = Arc::new(LogBuffer::new(1000));
+    let _guard = tracing_subscriber::registry()
+        .with(LogBufferLayer::new(buffer.clone()).with_filter(DebugTraceFilter::new(EnvFilter::new("info"), true)))
+        .set_default();
+
+    let mut request = Request::builder().uri("/api/users").header("Authorization", "Bearer secret");
+    if let Some(value) = debug_header {
+        request = request.header(X_DEBUG_TRACE, value);
+    }
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let trace_id = response.headers().get(X_TRACE_ID).map(|value| value.to_str().unwrap().to_string());
+
+    let debug_logs = buffer
+        .recent(LogLevel::Trace, 1000)
+        .into_iter()
+        .filter(|record| matches!(record.level, LogLevel::Debug | LogLevel::Trace))
+        .map(|record| record.trace_id.unwrap_or_default())
+        .collect();
+    (response.status(), trace_id, debug_logs)
+}
+
+#[tokio::test]
+async fn test_debug_header_elevates_one_request() {
+    let app = create_app(true).await;
+
+    let (status, trace_id, debug_logs) = request(app.clone(), Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    let trace_id = trace_id.expect("missing X-Trace-Id");
+    assert_eq!(trace_id.len(), 32);
+    assert!(!debug_logs.is_empty(), "no debug logs for the traced request");
+    assert!(debug_logs.iter().all(|id| *id == trace_id));
+
+    // Sans l'en-tête, le niveau configuré (info) s'applique
+    let (_, trace_id, debug_logs) = request(app, None).await;
+    assert!(trace_id.is_none());
+    assert!(debug_logs.is_empty());
+}
+
+#[tokio::test]
+async fn test_debug_header_requires_admin_token() {
+    let (status, trace_id, debug_logs) = request(create_app(true).await, Some("wrong")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(trace_id.is_none());
+    assert!(debug_logs.is_empty());
+
+    let (_, trace_id, _) = request(create_app(false).await, Some("secret")).await;
+    assert!(trace_id.is_none(), "debug tracing is disabled by [logging] debug_trace = false");
+}
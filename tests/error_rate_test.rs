@@ -0,0 +1,102 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::Utc;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::status_codes::StatusCounters,
+    models::status::{calculate_error_penalty, PerformanceMetrics, RequestCounts},
+    routes::create_router,
+    state::AppState,
+};
+
+fn counts(success: u64, server_errors: u64) -> RequestCounts {
+    RequestCounts { success, server_errors, ..RequestCounts::default() }
+}
+
+#[test]
+fn test_error_penalty_follows_server_error_rate() {
+    assert_eq!(calculate_error_penalty(&counts(100, 0)), 0);
+    assert_eq!(calculate_error_penalty(&counts(97, 3)), 15);
+    assert_eq!(calculate_error_penalty(&counts(90, 10)), 45);
+    assert_eq!(calculate_error_penalty(&counts(50, 50)), 60);
+    // Trop peu de réponses pour un taux significatif
+    assert_eq!(calculate_error_penalty(&counts(5, 5)), 0);
+    assert_eq!(calculate_error_penalty(&RequestCounts::default()), 0);
+}
+
+#[test]
+fn test_counters_report_responses_since_previous_snapshot() {
+    let counters = StatusCounters::new();
+    counters.record(StatusCode::OK);
+    let previous = counters.snapshot();
+
+    counters.record(StatusCode::CREATED);
+    counters.record(StatusCode::FOUND);
+    counters.record(StatusCode::NOT_FOUND);
+    counters.record(StatusCode::BAD_GATEWAY);
+    counters.record(StatusCode::CONTINUE);
+
+    let interval = counters.snapshot().since(&previous);
+    assert_eq!(interval, RequestCounts { success: 1, redirection: 1, client_errors: 1, server_errors: 1 });
+    assert_eq!(interval.total(), 4);
+    assert_eq!(interval.server_error_percent(), 25.0);
+}
+
+#[tokio::test]
+async fn test_middleware_counts_status_classes() {
+    let state = AppState::new(DatabaseManager::new(), Config::default());
+
+    for uri in ["/api/help/ping", "/api/help/live", "/api/no-such-route"] {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        create_router(state.clone()).oneshot(request).await.unwrap();
+    }
+
+    let counts = state.status_codes.snapshot();
+    assert_eq!(counts.success, 2);
+    assert_eq!(counts.client_errors, 1);
+    assert_eq!(counts.server_errors, 0);
+}
+
+#[tokio::test]
+async fn test_status_page_shows_error_rate() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    state.metrics.publish(PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 50,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: Some(counts(180, 20)),
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    });
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains("5xx: 10.0% (-45)"), "error rate not rendered");
+}
@@ -0,0 +1,128 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    response::IntoResponse,
+};
+use std::collections::HashSet;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    handlers::error::{AppError, PROBLEM_JSON},
+    models::error::{ErrorCode, ErrorCodeInfo, FieldError, ProblemDetails},
+    routes::create_router,
+    state::AppState,
+};
+use tower::ServiceExt;
+use validator::Validate;
+
+#[derive(Validate)]
+struct SignupForm {
+    #[validate(email(message = "must be a valid email"))]
+    email: String,
+    #[validate(length(min = 8))]
+    password: String,
+}
+
+async fn problem(error: AppError) -> (StatusCode, Option<String>, ProblemDetails) {
+    let response = error.into_response();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_not_found_is_rendered_as_problem_json() {
+    let (status, content_type, problem) = problem(AppError::NotFound("user not found".to_string())).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_type.as_deref(), Some(PROBLEM_JSON));
+    assert_eq!(problem.problem_type, "urn:problem:not-found");
+    assert_eq!(problem.title, "Not Found");
+    assert_eq!(problem.status, 404);
+    assert_eq!(problem.code, ErrorCode::NotFound);
+    assert_eq!(problem.detail.as_deref(), Some("user not found"));
+}
+
+#[tokio::test]
+async fn test_internal_errors_do_not_leak_details() {
+    let (status, _, problem) = problem(AppError::Internal("secret connection string".to_string())).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(problem.detail, None);
+}
+
+#[tokio::test]
+async fn test_validation_errors_list_fields() {
+    let form = SignupForm {
+        email: "not-an-email".to_string(),
+        password: "short".to_string(),
+    };
+    let error: AppError = form.validate().unwrap_err().into();
+    let (status, _, problem) = problem(error).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        problem.errors,
+        vec![
+            FieldError { field: "email".to_string(), message: "must be a valid email".to_string() },
+            FieldError { field: "password".to_string(), message: "length".to_string() },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sqlx_errors_are_mapped() {
+    assert_eq!(AppError::from(sqlx::Error::RowNotFound).status(), StatusCode::NOT_FOUND);
+    assert_eq!(AppError::from(sqlx::Error::PoolTimedOut).status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    // Violation d'unicité réelle côté PostgreSQL
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let mut tx = db.get_pool().begin().await.unwrap();
+    let insert = "INSERT INTO cors_origins (origin) VALUES ('https://error-test.example.com')";
+    sqlx::query(insert).execute(&mut *tx).await.unwrap();
+    let error = sqlx::query(insert).execute(&mut *tx).await.unwrap_err();
+    tx.rollback().await.unwrap();
+
+    let error = AppError::from(error);
+    assert_eq!(error.status(), StatusCode::CONFLICT);
+    assert_eq!(error.code(), ErrorCode::ResourceAlreadyExists);
+}
+
+#[tokio::test]
+async fn test_coded_errors_take_status_from_code() {
+    let (status, _, problem) = problem(AppError::coded(ErrorCode::UploadNotFound, "upload not found")).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(problem.problem_type, "urn:problem:not-found");
+    assert_eq!(problem.code, ErrorCode::UploadNotFound);
+    assert_eq!(problem.detail.as_deref(), Some("upload not found"));
+
+    let body = serde_json::to_value(&problem).unwrap();
+    assert_eq!(body["code"], "UPLOAD_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_error_catalog_lists_every_code() {
+    let app = create_router(AppState::new(DatabaseManager::new(), Config::default()));
+    let response = app
+        .oneshot(Request::builder().uri("/api/help/errors").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let catalog: Vec<ErrorCodeInfo> = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(catalog.len(), ErrorCode::ALL.len());
+    let unique: HashSet<_> = catalog.iter().map(|info| info.code.as_str()).collect();
+    assert_eq!(unique.len(), catalog.len());
+    assert!(catalog.iter().all(|info| !info.description.is_empty()));
+    assert!(catalog
+        .iter()
+        .any(|info| info.code == ErrorCode::ValidationFailed && info.status == 422));
+}
@@ -0,0 +1,82 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use once_cell::sync::Lazy;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    handlers::response::PaginatedResponse,
+    models::events::{AppEvent, EventKind},
+    routes::create_router,
+    services::events::record_event,
+    state::AppState,
+};
+
+static TEST_MUTEX: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+async fn connect() -> DatabaseManager {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    db
+}
+
+#[tokio::test]
+async fn test_events_endpoint_filters_by_kind() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+
+    let recorded = record_event(
+        db.get_pool(),
+        EventKind::Maintenance,
+        "Maintenance window <test>",
+        serde_json::json!({ "duration_minutes": 15 }),
+    )
+    .await
+    .expect("Failed to record event");
+
+    let app = create_router(AppState::new(db, Config::default()));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/status/events?kind=maintenance&per_page=100")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: PaginatedResponse<AppEvent> = serde_json::from_slice(&body).unwrap();
+    let events = page.items;
+
+    assert!(page.total >= 1);
+    assert!(events.iter().all(|e| e.kind == "maintenance"));
+    assert!(events.iter().any(|e| e.id == recorded.id));
+}
+
+#[tokio::test]
+async fn test_status_page_renders_escaped_timeline() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+
+    record_event(db.get_pool(), EventKind::Incident, "Incident <b>api</b> & \"db\" < 2", serde_json::json!({}))
+        .await
+        .expect("Failed to record event");
+
+    let app = create_router(AppState::new(db, Config::default()));
+    let response = app
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+
+    // Les balises sont retirées à l'enregistrement, le reste est échappé à l'affichage
+    assert!(html.contains("Incident api &amp; &quot;db&quot; &lt; 2"));
+    assert!(!html.contains("{EVENTS_TIMELINE_HTML}"));
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+use template_axum_sqlx_api::{
+    config::{Config, Environment},
+    fixtures::{
+        export::{export_command, export_fixtures, ExportSpec},
+        files::FixtureFile,
+        post::{CommentFactory, PostFactory},
+        user::UserFactory,
+        Factory, FixtureManager,
+    },
+    testing::TestDatabase,
+};
+
+#[test]
+fn test_export_command_parsing() {
+    let config = Config::default();
+    assert_eq!(export_command(&config, ["--fixtures"]), Ok(None));
+    assert_eq!(export_command(&config, ["export", "users"]), Ok(None));
+    assert_eq!(
+        export_command(&config, ["fixtures", "export", "users", "posts", "--output", "seed"]),
+        Ok(Some(ExportSpec { tables: vec!["users".to_string(), "posts".to_string()], output: PathBuf::from("seed") }))
+    );
+    assert!(export_command(&config, ["fixtures", "export"]).is_err());
+
+    // Lecture seule : permis en production, vers `[fixtures] directory` par défaut
+    let mut config = config;
+    config.server.environment = Environment::Production;
+    let spec = export_command(&config, ["fixtures", "export", "users"]).unwrap().unwrap();
+    assert_eq!(spec.output, PathBuf::from(&config.fixtures.directory));
+}
+
+#[tokio::test]
+async fn test_exported_fixtures_reload_with_references() {
+    let source = TestDatabase::new().await;
+    let ada = UserFactory::new().email("ada@example.com").name("Ada").create(source.pool()).await.unwrap();
+    let grace = UserFactory::new().email("grace@example.com").name("Grace").create(source.pool()).await.unwrap();
+    let post = PostFactory::new(ada.id).title("Bienvenue").create(source.pool()).await.unwrap();
+    CommentFactory::new(post.id).author(grace.id).body("Merci !").create(source.pool()).await.unwrap();
+    CommentFactory::new(post.id).body("Anonyme").create(source.pool()).await.unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let spec = ExportSpec {
+        tables: vec!["comments".to_string(), "users".to_string(), "posts".to_string()],
+        output: dir.path().to_path_buf(),
+    };
+    let exported = export_fixtures(source.pool(), &spec).await.unwrap();
+    // Ordre des clés étrangères, pas celui de la commande
+    assert_eq!(exported, [("users".to_string(), 2), ("posts".to_string(), 1), ("comments".to_string(), 2)]);
+
+    let posts = FixtureFile::read(&dir.path().join("posts.yaml")).unwrap();
+    assert_eq!(posts.depends_on, ["users"]);
+    assert!(!posts.rows[0].contains_key("id"));
+    assert_eq!(posts.rows[0]["_ref"], post.id.to_string());
+    assert_eq!(posts.rows[0]["user_id"], serde_json::json!({ "$ref": format!("users.{}", ada.id) }));
+    let comments = FixtureFile::read(&dir.path().join("comments.yaml")).unwrap();
+    assert!(!comments.rows[0].contains_key("_ref"));
+    assert!(comments.rows[1]["user_id"].is_null());
+    source.close().await;
+
+    // Les fichiers se rechargent dans une base vierge, horodatages compris
+    let target = TestDatabase::new().await;
+    sqlx::query("SELECT setval('users_id_seq', 100)").execute(target.pool()).await.unwrap();
+    let inserted = FixtureManager::new(target.pool().clone()).load_files(dir.path()).await.unwrap();
+    assert_eq!(inserted, 5);
+    let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        "SELECT p.title, author.email, commenter.email FROM comments c
+         JOIN posts p ON p.id = c.post_id
+         JOIN users author ON author.id = p.user_id
+         LEFT JOIN users commenter ON commenter.id = c.user_id
+         ORDER BY c.body",
+    )
+    .fetch_all(target.pool())
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        [
+            ("Bienvenue".to_string(), "ada@example.com".to_string(), None),
+            ("Bienvenue".to_string(), "ada@example.com".to_string(), Some("grace@example.com".to_string())),
+        ]
+    );
+    target.close().await;
+}
@@ -0,0 +1,82 @@
+use template_axum_sqlx_api::{
+    config::FakerLocale,
+    fixtures::{
+        dummy::DummyFactory,
+        fixture_rng, locale,
+        post::{CommentFactory, PostFactory},
+        user::UserFactory,
+        Factory,
+    },
+    testing::TestDatabase,
+};
+
+#[tokio::test]
+async fn test_factories_create_related_models() {
+    let db = TestDatabase::new().await;
+    let pool = db.pool();
+
+    let user = UserFactory::new().name("Ada").create(pool).await.unwrap();
+    assert_eq!(user.name, "Ada");
+    assert!(user.email.ends_with("@example.com"));
+
+    let post = PostFactory::new(user.id).title("Bienvenue").create(pool).await.unwrap();
+    assert_eq!(post.user_id, user.id);
+    assert_eq!(post.title, "Bienvenue");
+    assert!(!post.body.is_empty());
+
+    let comments = CommentFactory::new(post.id).author(user.id).create_many(pool, 3).await.unwrap();
+    assert_eq!(comments.len(), 3);
+    assert!(comments.iter().all(|comment| comment.post_id == post.id && comment.user_id == Some(user.id)));
+
+    let anonymous = CommentFactory::new(post.id).body("Sans auteur").create(pool).await.unwrap();
+    assert_eq!((anonymous.user_id, anonymous.body.as_str()), (None, "Sans auteur"));
+
+    let dummy = DummyFactory::new().create(pool).await.unwrap();
+    assert!(dummy.id > 0);
+
+    db.close().await;
+}
+
+#[tokio::test]
+async fn test_factory_emails_are_unique() {
+    let db = TestDatabase::new().await;
+
+    let users = UserFactory::new().create_many(db.pool(), 20).await.unwrap();
+    let mut emails: Vec<&str> = users.iter().map(|user| user.email.as_str()).collect();
+    emails.sort();
+    emails.dedup();
+    assert_eq!(emails.len(), 20);
+
+    // Un email imposé reste soumis à la contrainte d'unicité
+    let factory = UserFactory::new().email("ada@example.com");
+    factory.create(db.pool()).await.unwrap();
+    assert!(factory.create(db.pool()).await.is_err());
+
+    db.close().await;
+}
+
+#[test]
+fn test_factory_overrides_keep_seeded_values() {
+    let build = |factory: UserFactory| factory.build_with(&mut fixture_rng(42, "users"));
+
+    let generated = build(UserFactory::new());
+    let renamed = build(UserFactory::new().name("Ada"));
+    // Les champs non imposés ne dépendent pas des autres valeurs imposées
+    assert_eq!(renamed.email, generated.email);
+    assert_eq!(renamed.name, "Ada");
+}
+
+#[test]
+fn test_factory_locale() {
+    let build = |locale: FakerLocale| UserFactory::new().locale(locale).build_with(&mut fixture_rng(42, "users"));
+
+    assert_ne!(build(FakerLocale::FrFr).name, build(FakerLocale::EnUs).name);
+
+    // Noms dans l'écriture de la langue, emails toujours en ASCII
+    let japanese = build(FakerLocale::JaJp);
+    assert!(!japanese.name.is_ascii());
+    assert!(japanese.email.is_ascii() && japanese.email.ends_with("@example.com"));
+
+    assert_eq!(locale::email_local_part("Hélène.Dupré"), "helene.dupre");
+    assert_eq!(locale::email_local_part("さくら"), "user");
+}
@@ -0,0 +1,81 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    extractors::fields::{select_fields, FieldSpec, Fields},
+    handlers::error::AppError,
+};
+
+#[derive(Serialize)]
+struct Book {
+    id: i64,
+    title: String,
+    summary: String,
+}
+
+struct BookFields;
+
+impl FieldSpec for BookFields {
+    const FIELDS: &'static [&'static str] = &["id", "title", "summary"];
+}
+
+async fn book(fields: Fields<BookFields>) -> Result<Json<Value>, AppError> {
+    let book = Book {
+        id: 1,
+        title: "Dune".to_string(),
+        summary: "A very long summary".to_string(),
+    };
+    Ok(Json(fields.shape(&book)?))
+}
+
+async fn get_json(uri: &str) -> (StatusCode, Value) {
+    let response = Router::new()
+        .route("/book", get(book))
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_fields_shape_the_response() {
+    let (status, body) = get_json("/book?fields=id,title").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 1, "title": "Dune" }));
+
+    let (_, body) = get_json("/book").await;
+    assert_eq!(body["summary"], "A very long summary");
+}
+
+#[tokio::test]
+async fn test_unknown_fields_are_rejected() {
+    let (status, body) = get_json("/book?fields=id,password").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "INVALID_FIELDS");
+}
+
+#[test]
+fn test_parse_ignores_other_parameters() {
+    let fields = Fields::<BookFields>::parse("page=2&fields=title,%20id,title").unwrap();
+
+    assert_eq!(fields.selected().unwrap(), ["title", "id"]);
+    assert!(fields.contains("id"));
+    assert!(!fields.contains("summary"));
+    assert!(Fields::<BookFields>::parse("fields=").unwrap().selected().is_none());
+}
+
+#[test]
+fn test_select_fields_on_arrays() {
+    let value = json!([{ "id": 1, "kind": "deploy" }, { "id": 2, "kind": "incident" }]);
+
+    assert_eq!(select_fields(value, &["kind"]), json!([{ "kind": "deploy" }, { "kind": "incident" }]));
+}
@@ -0,0 +1,8 @@
+-- Posts chargés par #[sqlx::test(fixtures("users", "posts"))], après les utilisateurs
+INSERT INTO posts (user_id, title, body)
+SELECT id, 'Bienvenue', 'Premier post' FROM users WHERE email = 'ada@example.com';
+
+INSERT INTO comments (post_id, user_id, body)
+SELECT posts.id, users.id, 'Merci !'
+FROM posts, users
+WHERE posts.title = 'Bienvenue' AND users.email = 'grace@example.com';
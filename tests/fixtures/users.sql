@@ -0,0 +1,4 @@
+-- Utilisateurs chargés par #[sqlx::test(fixtures("users"))]
+INSERT INTO users (email, name) VALUES
+    ('ada@example.com', 'Ada Lovelace'),
+    ('grace@example.com', 'Grace Hopper');
@@ -1,82 +1,421 @@
 use template_axum_sqlx_api::{
-    config::Config,
+    config::{ConflictStrategy, Config, Environment, FakerLocale, FixtureProfile},
     db::DatabaseManager,
-    fixtures,
+    fixtures::{
+        dependency_order, files::read_fixture_files, fixture_rng, fixture_seed, fixture_sets, fixtures_decision,
+        post::PostFactory, reset_requested, run_fixtures, user::UserFactory, Factory, FixtureManager, FixturesDecision,
+    },
+    testing::TestDatabase,
 };
-use sqlx::Row;
-use sqlx::{Pool, Postgres};
-use std::sync::Mutex;
-use once_cell::sync::Lazy;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::str::FromStr;
 
-static TEST_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+fn config(environment: Environment) -> Config {
+    let mut config = Config::default();
+    config.server.environment = environment;
+    config
+}
 
-async fn get_count(pool: &Pool<Postgres>) -> i64 {
-    sqlx::query("SELECT COUNT(*) FROM dummy")
-        .fetch_one(pool)
-        .await
-        .expect("Failed to query dummy table")
-        .get(0)
+#[test]
+fn test_fixtures_are_opt_in() {
+    let config = config(Environment::Development);
+    assert_eq!(fixtures_decision(&config, Vec::<String>::new()), Ok(FixturesDecision::Skip));
+    assert_eq!(fixtures_decision(&config, ["--port"]), Ok(FixturesDecision::Skip));
+    assert_eq!(fixtures_decision(&config, ["--fixtures"]), Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Minimal, dry_run: false }));
+
+    let mut config = config;
+    config.fixtures.enabled = true;
+    config.fixtures.clean = false;
+    assert_eq!(fixtures_decision(&config, Vec::<String>::new()), Ok(FixturesDecision::Run { clean: false, profile: FixtureProfile::Minimal, dry_run: false }));
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-async fn test_run_fixtures() {
-    let _lock = TEST_MUTEX.lock().unwrap();
-    // Setup database connection
-    let config = Config::default();
-    let mut db = DatabaseManager::new();
-    db.connect(&config).await.expect("Failed to connect to database");
-    let pool = db.get_pool();
+#[test]
+fn test_fixture_profile_from_config_or_cli() {
+    let mut config = config(Environment::Development);
+    config.fixtures.profile = FixtureProfile::Demo;
+    assert_eq!(
+        fixtures_decision(&config, ["--fixtures"]),
+        Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Demo, dry_run: false })
+    );
 
-    // Test running fixtures with clean=true
-    let result = fixtures::run_fixtures(pool, true).await;
-    assert!(result.is_ok(), "Failed to run fixtures: {:?}", result.err());
+    // L'option de ligne de commande remplace la configuration et demande le chargement
+    assert_eq!(
+        fixtures_decision(&config, ["--fixtures-profile", "load"]),
+        Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Load, dry_run: false })
+    );
+    assert_eq!(
+        fixtures_decision(&config, ["--fixtures-profile=minimal"]),
+        Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Minimal, dry_run: false })
+    );
+    assert!(fixtures_decision(&config, ["--fixtures-profile", "huge"]).unwrap_err().contains("huge"));
+    assert!(fixtures_decision(&config, ["--fixtures-profile"]).is_err());
 
-    // Verify that dummy table has data
-    let count: i64 = get_count(pool).await;
-    assert!(count == 100, "Dummy table should contain 100 data after fixtures but got {}", count);
+    assert_eq!(
+        [FixtureProfile::Minimal, FixtureProfile::Demo, FixtureProfile::Load].map(FixtureProfile::rows),
+        [10, 10_000, 1_000_000]
+    );
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-async fn test_run_fixtures_without_clean() {
-    let _lock = TEST_MUTEX.lock().unwrap();
-    // Setup database connection
-    let config = Config::default();
-    let mut db = DatabaseManager::new();
-    db.connect(&config).await.expect("Failed to connect to database");
-    let pool = db.get_pool();
+#[test]
+fn test_fixtures_refused_in_production_unless_forced() {
+    let mut config = config(Environment::Production);
+    assert_eq!(fixtures_decision(&config, Vec::<String>::new()), Ok(FixturesDecision::Skip));
+    assert!(fixtures_decision(&config, ["--fixtures"]).is_err());
 
-    // Test running fixtures with clean=false
-    let result = fixtures::run_fixtures(pool, true).await;
-    assert!(result.is_ok(), "Failed to run fixtures with cleaning: {:?}", result.err());
-    let first_count: i64 = get_count(pool).await;
+    config.fixtures.enabled = true;
+    assert!(fixtures_decision(&config, Vec::<String>::new()).is_err());
+    assert_eq!(fixtures_decision(&config, ["--force-fixtures"]), Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Minimal, dry_run: false }));
 
-    let result = fixtures::run_fixtures(pool, false).await;
-    assert!(result.is_ok(), "Failed to run fixtures without cleaning: {:?}", result.err());
+    config.fixtures.force = true;
+    assert_eq!(fixtures_decision(&config, Vec::<String>::new()), Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Minimal, dry_run: false }));
+}
 
-    // Verify that dummy table has data
-    let second_count: i64 = get_count(pool).await;
-    assert!(second_count == first_count*2, "Dummy table should contain more data after fixtures but got {} and {}", first_count, second_count);
+#[test]
+fn test_dry_run_allowed_in_production() {
+    let config = config(Environment::Production);
+    assert_eq!(
+        fixtures_decision(&config, ["--fixtures", "--dry-run"]),
+        Ok(FixturesDecision::Run { clean: true, profile: FixtureProfile::Minimal, dry_run: true })
+    );
+    // Sans demande de chargement, l'option seule ne fait rien
+    assert_eq!(fixtures_decision(&config, ["--dry-run"]), Ok(FixturesDecision::Skip));
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-async fn test_fixtures_cleanup() {
-    let _lock = TEST_MUTEX.lock().unwrap();
-    // Setup database connection
-    let config = Config::default();
+async fn pool() -> sqlx::PgPool {
     let mut db = DatabaseManager::new();
-    db.connect(&config).await.expect("Failed to connect to database");
-    let pool = db.get_pool();
-
-    // First run fixtures
-    fixtures::run_fixtures(pool, true).await.expect("Failed to run fixtures");
-
-    // Verify first run
-    let first_count: i64 = get_count(pool).await;
-    // Then run fixtures again with clean=true to test cleanup
-    let result = fixtures::run_fixtures(pool, true).await;
-    assert!(result.is_ok(), "Failed to clean and rerun fixtures: {:?}", result.err());
-
-    // Verify second run
-    let second_count: i64 = get_count(pool).await;
-    assert!(second_count == first_count, "Dummy table should have 100 data loading fixtures but got {}", second_count);
-} 
\ No newline at end of file
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    db.get_pool().clone()
+}
+
+#[tokio::test]
+async fn test_fixture_files_resolve_references() {
+    let pool = pool().await;
+    let dir = tempfile::tempdir().unwrap();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    std::fs::write(
+        dir.path().join("01_users.yaml"),
+        format!(
+            "table: users\nrows:\n  - _ref: alice\n    email: alice.{suffix}@example.com\n    name: Alice\n"
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("02_posts.json"),
+        r#"{ "table": "posts", "rows": [
+            { "_ref": "welcome", "user_id": { "$ref": "users.alice" }, "title": "Welcome", "body": "Hello" }
+        ] }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("03_comments.yml"),
+        "table: comments\nrows:\n  - post_id: { $ref: posts.welcome }\n    user_id: { $ref: users.alice }\n    body: First\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+    let inserted = FixtureManager::new(pool.clone()).load_files(dir.path()).await.unwrap();
+    assert_eq!(inserted, 3);
+
+    let (user_id,): (i64,) = sqlx::query_as("SELECT id FROM users WHERE email = $1")
+        .bind(format!("alice.{suffix}@example.com"))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let comments: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM comments c JOIN posts p ON p.id = c.post_id WHERE p.user_id = $1 AND c.user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(comments, 1);
+
+    sqlx::query("DELETE FROM comments WHERE user_id = $1").bind(user_id).execute(&pool).await.unwrap();
+    sqlx::query("DELETE FROM posts WHERE user_id = $1").bind(user_id).execute(&pool).await.unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1").bind(user_id).execute(&pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fixture_files_are_validated() {
+    let pool = pool().await;
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+
+    // Référence inconnue : rien n'est inséré
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("users.yaml"),
+        format!("table: users\nrows:\n  - email: bob.{suffix}@example.com\n    name: Bob\n"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("x_posts.yaml"),
+        "table: posts\nrows:\n  - user_id: { $ref: users.missing }\n    title: T\n    body: B\n",
+    )
+    .unwrap();
+    let error = FixtureManager::new(pool.clone()).load_files(dir.path()).await.unwrap_err();
+    assert!(error.to_string().contains("users.missing"));
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
+        .bind(format!("bob.{suffix}@example.com"))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 0);
+
+    // Les identifiants sont insérés tels quels dans la requête
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bad.yaml"), "table: \"users; DROP TABLE users\"\nrows: []\n").unwrap();
+    assert!(FixtureManager::new(pool.clone()).load_files(dir.path()).await.is_err());
+
+    // Dossier absent : aucune fixture
+    assert_eq!(FixtureManager::new(pool).load_files(&dir.path().join("missing")).await.unwrap(), 0);
+}
+
+#[test]
+fn test_dependency_order() {
+    let items = [("posts", vec!["users"]), ("dummy", vec![]), ("users", vec![]), ("comments", vec!["posts", "users"])];
+    assert_eq!(dependency_order(&items), Ok(vec![1, 2, 0, 3]));
+
+    let unknown = [("posts", vec!["authors"])];
+    assert!(dependency_order(&unknown).unwrap_err().contains("authors"));
+
+    let cycle = [("a", vec!["b"]), ("b", vec!["a"]), ("c", vec![])];
+    assert_eq!(dependency_order(&cycle), Err("circular fixture dependencies between a, b".to_string()));
+}
+
+#[test]
+fn test_fixture_sets_load_users_before_posts() {
+    let sets = fixture_sets();
+    let items: Vec<(&str, &[&str])> = sets.iter().map(|set| (set.name, set.depends_on)).collect();
+    let order: Vec<&str> = dependency_order(&items).unwrap().into_iter().map(|index| sets[index].name).collect();
+    let position = |name| order.iter().position(|set| *set == name).unwrap();
+    assert!(position("users") < position("posts"));
+}
+
+#[test]
+fn test_fixture_files_follow_depends_on() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a_comments.yaml"), "table: comments\ndepends_on: [posts, users]\n").unwrap();
+    std::fs::write(dir.path().join("b_posts.yaml"), "table: posts\ndepends_on: [users]\n").unwrap();
+    std::fs::write(dir.path().join("c_users.yaml"), "table: users\n").unwrap();
+    std::fs::write(dir.path().join("d_dummy.yaml"), "table: dummy\ndepends_on: [tags]\n").unwrap();
+
+    let tables: Vec<String> = read_fixture_files(dir.path()).unwrap().into_iter().map(|file| file.table).collect();
+    assert_eq!(tables, ["users", "posts", "comments", "dummy"]);
+}
+
+#[test]
+fn test_seeded_fixtures_are_reproducible() {
+    let emails = |seed, set| -> Vec<String> {
+        let mut rng = fixture_rng(seed, set);
+        (0..5).map(|_| UserFactory::new().build_with(&mut rng).email).collect()
+    };
+    assert_eq!(emails(42, "users"), emails(42, "users"));
+    assert_ne!(emails(42, "users"), emails(43, "users"));
+    // Chaque jeu a son propre générateur
+    assert_ne!(emails(42, "users"), emails(42, "posts"));
+
+    assert_eq!(fixture_seed(Some(42)), 42);
+}
+
+/// Pool limité à un schéma jetable : les tests de remise à zéro ne touchent pas aux tables partagées
+async fn isolated_pool() -> (sqlx::PgPool, String) {
+    let schema = format!("fixtures_test_{}", uuid::Uuid::new_v4().simple());
+    let shared = pool().await;
+    sqlx::query(&format!("CREATE SCHEMA {}", schema)).execute(&shared).await.unwrap();
+    let options = PgConnectOptions::from_str(&Config::default().database.url)
+        .unwrap()
+        .options([("search_path", schema.as_str())]);
+    let pool = PgPoolOptions::new().max_connections(2).connect_with(options).await.unwrap();
+    (pool, schema)
+}
+
+async fn drop_schema(schema: &str) {
+    sqlx::query(&format!("DROP SCHEMA {} CASCADE", schema)).execute(&pool().await).await.unwrap();
+}
+
+async fn count(pool: &sqlx::PgPool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table)).fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_truncate_follows_foreign_keys() {
+    let (pool, schema) = isolated_pool().await;
+    for statement in [
+        "CREATE TABLE authors (id bigserial PRIMARY KEY)",
+        "CREATE TABLE books (id bigserial PRIMARY KEY, author_id bigint REFERENCES authors (id))",
+        "CREATE TABLE reviews (id bigserial PRIMARY KEY, book_id bigint REFERENCES books (id))",
+        "CREATE TABLE tags (id bigserial PRIMARY KEY)",
+        "INSERT INTO authors DEFAULT VALUES",
+        "INSERT INTO books (author_id) SELECT id FROM authors",
+        "INSERT INTO reviews (book_id) SELECT id FROM books",
+        "INSERT INTO tags DEFAULT VALUES",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let manager = FixtureManager::new(pool.clone());
+
+    let truncated = manager.truncate(&["authors"]).await.unwrap();
+    assert_eq!(truncated, ["authors", "books", "reviews"]);
+    assert_eq!(count(&pool, "reviews").await, 0);
+    assert_eq!(count(&pool, "tags").await, 1);
+
+    // Séquences remises à zéro
+    let id: i64 = sqlx::query_scalar("INSERT INTO authors DEFAULT VALUES RETURNING id").fetch_one(&pool).await.unwrap();
+    assert_eq!(id, 1);
+
+    assert!(manager.truncate(&["missing"]).await.unwrap_err().to_string().contains("missing"));
+
+    assert_eq!(manager.reset().await.unwrap(), ["authors", "books", "reviews", "tags"]);
+    assert_eq!(count(&pool, "tags").await, 0);
+
+    pool.close().await;
+    drop_schema(&schema).await;
+}
+
+#[tokio::test]
+async fn test_recreate_runs_migrations() {
+    let (pool, schema) = isolated_pool().await;
+    sqlx::query("CREATE TABLE leftover (id int)").execute(&pool).await.unwrap();
+
+    let manager = FixtureManager::new(pool.clone());
+    manager.recreate().await.unwrap();
+    sqlx::query("INSERT INTO users (email, name) VALUES ('a@example.com', 'A')").execute(&pool).await.unwrap();
+
+    // Deuxième passage : les tables créées par les migrations sont supprimées puis recréées
+    manager.recreate().await.unwrap();
+    assert_eq!(count(&pool, "users").await, 0);
+    let leftover: Option<String> = sqlx::query_scalar("SELECT to_regclass('leftover')::text").fetch_one(&pool).await.unwrap();
+    assert!(leftover.is_none());
+
+    // Le suivi des migrations est conservé par reset
+    let tables = manager.reset().await.unwrap();
+    assert!(tables.contains(&"users".to_string()) && !tables.contains(&"_sqlx_migrations".to_string()));
+
+    pool.close().await;
+    drop_schema(&schema).await;
+}
+
+#[test]
+fn test_reset_refused_in_production_unless_forced() {
+    assert_eq!(reset_requested(&config(Environment::Development), ["--reset-database"]), Ok(true));
+    assert_eq!(reset_requested(&config(Environment::Production), ["--fixtures"]), Ok(false));
+    assert!(reset_requested(&config(Environment::Production), ["--reset-database"]).is_err());
+    assert_eq!(
+        reset_requested(&config(Environment::Production), ["--reset-database", "--force-fixtures"]),
+        Ok(true)
+    );
+}
+
+#[tokio::test]
+async fn test_run_fixtures_creates_profile_rows() {
+    let db = TestDatabase::new().await;
+    let mut settings = Config::default().fixtures;
+    settings.directory = "missing-fixtures-directory".to_string();
+    settings.seed = Some(7);
+
+    run_fixtures(db.pool(), true, FixtureProfile::Minimal, false, &settings).await.unwrap();
+    for table in ["dummy", "posts", "comments"] {
+        assert_eq!(count(db.pool(), table).await, 10, "{}", table);
+    }
+    // Les deux principaux s'ajoutent aux utilisateurs du profil
+    assert_eq!(count(db.pool(), "users").await, 12);
+    assert_eq!(count(db.pool(), "api_keys").await, 2);
+
+    // Rechargement avec nettoyage : le volume ne s'additionne pas
+    run_fixtures(db.pool(), true, FixtureProfile::Minimal, false, &settings).await.unwrap();
+    assert_eq!(count(db.pool(), "users").await, 12);
+    db.close().await;
+}
+
+#[tokio::test]
+async fn test_submit_fixtures_inserts_in_batches() {
+    let db = TestDatabase::new().await;
+    let mut rng = fixture_rng(3, "users");
+    let users: Vec<_> = (0..25).map(|_| UserFactory::new().build_with(&mut rng)).collect();
+    let expected: Vec<String> = users.iter().map(|user| user.email.clone()).collect();
+
+    // 25 lignes par blocs de 10 : trois instructions, une seule transaction
+    FixtureManager::new(db.pool().clone()).with_batch_size(10).submit_fixtures(users, "users").await.unwrap();
+    let emails: Vec<String> = sqlx::query_scalar("SELECT email FROM users ORDER BY id").fetch_all(db.pool()).await.unwrap();
+    assert_eq!(emails, expected);
+
+    // Une ligne invalide annule tous les blocs
+    let duplicates = vec![UserFactory::new().build(), UserFactory::new().email(&expected[0]).build()];
+    let manager = FixtureManager::new(db.pool().clone()).with_batch_size(1);
+    assert!(manager.submit_fixtures(duplicates, "users").await.is_err());
+    assert_eq!(count(db.pool(), "users").await, 25);
+    db.close().await;
+}
+
+#[tokio::test]
+async fn test_dry_run_does_not_modify_database() {
+    let db = TestDatabase::new().await;
+    let mut settings = Config::default().fixtures;
+    settings.directory = "missing-fixtures-directory".to_string();
+    settings.seed = Some(7);
+    run_fixtures(db.pool(), false, FixtureProfile::Minimal, false, &settings).await.unwrap();
+
+    run_fixtures(db.pool(), true, FixtureProfile::Minimal, true, &settings).await.unwrap();
+    for table in ["dummy", "posts", "comments"] {
+        assert_eq!(count(db.pool(), table).await, 10, "{}", table);
+    }
+    assert_eq!(count(db.pool(), "users").await, 12);
+    db.close().await;
+}
+
+#[tokio::test]
+async fn test_submit_fixtures_conflict_strategies() {
+    let db = TestDatabase::new().await;
+    let users = |name: &str| (0..3).map(|i| UserFactory::new().email(format!("user{}@example.com", i)).name(name).build()).collect::<Vec<_>>();
+    let names = async || -> Vec<String> {
+        sqlx::query_scalar("SELECT name FROM users ORDER BY email").fetch_all(db.pool()).await.unwrap()
+    };
+    FixtureManager::new(db.pool().clone()).submit_fixtures(users("first"), "users").await.unwrap();
+
+    // Par défaut, un conflit d'unicité fait échouer le chargement
+    assert!(FixtureManager::new(db.pool().clone()).submit_fixtures(users("second"), "users").await.is_err());
+
+    let manager = FixtureManager::new(db.pool().clone()).with_conflict("users", ConflictStrategy::Skip);
+    manager.submit_fixtures(users("second"), "users").await.unwrap();
+    assert_eq!(names().await, ["first"; 3]);
+
+    let manager = FixtureManager::new(db.pool().clone()).with_conflict("users", ConflictStrategy::Update);
+    manager.submit_fixtures(users("third"), "users").await.unwrap();
+    assert_eq!(names().await, ["third"; 3]);
+    assert_eq!(count(db.pool(), "users").await, 3);
+
+    // Sans contrainte d'unicité sur les colonnes insérées, pas de cible pour la mise à jour
+    let user_id: i64 = sqlx::query_scalar("SELECT id FROM users LIMIT 1").fetch_one(db.pool()).await.unwrap();
+    let posts = vec![PostFactory::new(user_id.into()).build()];
+    let manager = FixtureManager::new(db.pool().clone()).with_conflict("posts", ConflictStrategy::Update);
+    let error = manager.submit_fixtures(posts, "posts").await.unwrap_err();
+    assert!(error.to_string().contains("No unique constraint"), "{}", error);
+    db.close().await;
+}
+
+#[test]
+fn test_conflict_strategies_from_config() {
+    let settings: template_axum_sqlx_api::config::FixturesConfig = toml::from_str(
+        r#"
+        [on_conflict]
+        users = "update"
+        dummy = "skip"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(settings.on_conflict["users"], ConflictStrategy::Update);
+    assert_eq!(settings.on_conflict["dummy"], ConflictStrategy::Skip);
+    assert_eq!(ConflictStrategy::default(), ConflictStrategy::Error);
+}
+
+#[test]
+fn test_faker_locale_from_config() {
+    let settings: template_axum_sqlx_api::config::FixturesConfig = toml::from_str(r#"locale = "fr_FR""#).unwrap();
+    assert_eq!(settings.locale, FakerLocale::FrFr);
+    assert_eq!(template_axum_sqlx_api::config::FixturesConfig::default().locale, FakerLocale::EnUs);
+    assert!(toml::from_str::<template_axum_sqlx_api::config::FixturesConfig>(r#"locale = "xx_XX""#).is_err());
+
+    assert_eq!(FakerLocale::from_str("ja_JP"), Ok(FakerLocale::JaJp));
+    assert_eq!(FakerLocale::from_str("en"), Ok(FakerLocale::EnUs));
+    assert!(FakerLocale::from_str("fr").unwrap_err().contains("fr_FR"));
+}
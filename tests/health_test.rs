@@ -2,19 +2,107 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use async_trait::async_trait;
+use std::{sync::Arc, time::Duration};
 use tower::ServiceExt;
 use template_axum_sqlx_api::{
-    config::Config,
+    config::{Config, Environment},
     db::DatabaseManager,
+    models::help::HealthStatus,
     routes::create_router,
+    services::health::{self, HealthCheck, HealthRegistry, Probe},
+    state::AppState,
 };
 use axum::body::to_bytes;
 
+/// Résultat d'une vérification dans le corps de `/api/help/health`
+fn check<'a>(health: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    health["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == name)
+        .unwrap_or_else(|| panic!("missing {} check", name))
+}
+
+struct StaticCheck {
+    name: &'static str,
+    critical: bool,
+    probe: fn() -> Probe,
+}
+
+#[async_trait]
+impl HealthCheck for StaticCheck {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn critical(&self) -> bool {
+        self.critical
+    }
+
+    async fn check(&self) -> Probe {
+        (self.probe)()
+    }
+}
+
+struct SlowCheck;
+
+#[async_trait]
+impl HealthCheck for SlowCheck {
+    fn name(&self) -> &'static str {
+        "slow"
+    }
+
+    async fn check(&self) -> Probe {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Probe::healthy()
+    }
+}
+
+async fn health_with(registry: HealthRegistry) -> (StatusCode, serde_json::Value) {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to test database");
+    let mut state = AppState::new(db, Config::default());
+    state.health = Arc::new(registry);
+
+    let request = Request::builder().uri("/api/help/health-light").body(Body::empty()).unwrap();
+    let response = create_router(state).oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_registered_checks_are_aggregated() {
+    let timeout = Duration::from_millis(100);
+    let ok = StaticCheck { name: "cache", critical: true, probe: Probe::healthy };
+    let flaky = StaticCheck { name: "search", critical: false, probe: || Probe::unhealthy("search is down") };
+
+    // Un échec non critique dégrade l'instance sans la rendre indisponible
+    let registry = HealthRegistry::new(timeout).register(ok).register(flaky);
+    let results = registry.run(false).await;
+    assert_eq!(health::aggregate(&results), HealthStatus::Degraded);
+    assert_eq!(results[1].message.as_deref(), Some("search is down"));
+    assert_eq!(registry.run(true).await.len(), 1);
+
+    let failing = StaticCheck { name: "queue", critical: true, probe: || Probe::unhealthy("queue is down") };
+    let (status, body) = health_with(HealthRegistry::new(timeout).register(failing)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unhealthy");
+    assert_eq!(check(&body, "queue")["message"], "queue is down");
+
+    // Une vérification trop lente est en échec
+    let (status, body) = health_with(HealthRegistry::new(timeout).register(SlowCheck)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(check(&body, "slow")["message"], "timed out after 100 ms");
+}
+
 #[tokio::test]
 async fn test_health_check() {
     let mut db = DatabaseManager::new();
     db.connect(&Config::default()).await.expect("Failed to connect to test database");
-    let app = create_router(db);
+    let app = create_router(AppState::new(db, Config::default()));
 
     let response = Request::builder()
         .uri("/api/help/health")
@@ -27,16 +115,47 @@ async fn test_health_check() {
     let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
     let health: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(health["status"], "healthy");
-    assert!(health["database"]["connected"].as_bool().unwrap());
+    assert_ne!(health["status"], "unhealthy");
+    let database = check(&health, "database");
+    assert_eq!(database["status"], "healthy");
+    assert_eq!(database["critical"], true);
+    assert!(database["latency_ms"].is_u64());
+    assert_eq!(check(&health, "disk")["critical"], false);
+    assert!(check(&health, "disk")["details"]["mounts"].is_array());
+    assert_eq!(check(&health, "background_tasks")["status"], "healthy");
     assert!(health["system"]["cpu_count"].as_u64().unwrap() > 0);
+    assert!(health["system"]["disks"].is_array());
+}
+
+#[tokio::test]
+async fn test_health_warns_unencrypted_database_in_production() {
+    let mut config = Config::default();
+    config.server.environment = Environment::Production;
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to test database");
+    let encrypted = db.is_encrypted().await.unwrap();
+    let app = create_router(AppState::new(db, config));
+
+    let response = Request::builder()
+        .uri("/api/help/health-light")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(response).await.unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let health: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let database = check(&health, "database");
+    assert_eq!(database["details"]["encrypted"], encrypted);
+    assert_eq!(database["status"], if encrypted { "healthy" } else { "degraded" });
+    assert_eq!(database.get("message").is_none(), encrypted);
 }
 
 #[tokio::test]
 async fn test_health_light() {
     let mut db = DatabaseManager::new();
     db.connect(&Config::default()).await.expect("Failed to connect to test database");
-    let app = create_router(db);
+    let app = create_router(AppState::new(db, Config::default()));
 
     let response = Request::builder()
         .uri("/api/help/health-light")
@@ -50,14 +169,16 @@ async fn test_health_light() {
     let health: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(health["status"], "healthy");
-    assert!(health["database"]["connected"].as_bool().unwrap());
+    assert_eq!(check(&health, "database")["status"], "healthy");
+    // Seules les vérifications critiques sont exécutées
+    assert_eq!(health["checks"].as_array().unwrap().len(), 1);
 }
 
 #[tokio::test]
 async fn test_info() {
     let mut db = DatabaseManager::new();
     db.connect(&Config::default()).await.expect("Failed to connect to test database");
-    let app = create_router(db);
+    let app = create_router(AppState::new(db, Config::default()));
 
     let response = Request::builder()
         .uri("/api/help/info")
@@ -80,7 +201,7 @@ async fn test_info() {
 async fn test_ping() {
     let mut db = DatabaseManager::new();
     db.connect(&Config::default()).await.expect("Failed to connect to test database");
-    let app = create_router(db);
+    let app = create_router(AppState::new(db, Config::default()));
 
     let response = Request::builder()
         .uri("/api/help/ping")
@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::{cors::CorsOriginId, id::Id, uploads::UploadId},
+};
+use uuid::Uuid;
+
+#[test]
+fn test_ids_use_the_raw_representation() {
+    let id = CorsOriginId::new(42);
+    assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+    assert_eq!(serde_json::from_str::<CorsOriginId>("42").unwrap(), id);
+    assert_eq!(id.to_string(), "42");
+    assert_eq!("42".parse::<CorsOriginId>().unwrap(), id);
+    assert!("abc".parse::<CorsOriginId>().is_err());
+
+    let uuid = Uuid::new_v4();
+    let upload_id = UploadId::new(uuid);
+    assert_eq!(serde_json::to_value(upload_id).unwrap(), serde_json::json!(uuid.to_string()));
+    assert_eq!(*upload_id.get(), uuid);
+}
+
+#[test]
+fn test_ids_do_not_require_traits_on_the_entity() {
+    struct Untracked;
+
+    let ids: HashSet<Id<Untracked>> = [Id::new(1), Id::new(1), Id::new(2)].into_iter().collect();
+    assert_eq!(ids.len(), 2);
+}
+
+#[tokio::test]
+async fn test_ids_bind_and_decode_with_sqlx() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+
+    let id: CorsOriginId = sqlx::query_scalar("SELECT $1::bigint")
+        .bind(CorsOriginId::new(7))
+        .fetch_one(db.get_pool())
+        .await
+        .unwrap();
+    assert_eq!(id.into_inner(), 7);
+}
@@ -0,0 +1,146 @@
+use std::{collections::HashMap, path::PathBuf};
+use template_axum_sqlx_api::{
+    config::{AnonymizeRule, Config, Environment, FakerLocale, ImportConfig},
+    fixtures::import::{import_command, import_dumps, parse_copy_blocks, parse_csv, Anonymizer},
+    testing::TestDatabase,
+};
+
+const DUMP: &str = r#"--
+-- PostgreSQL database dump
+--
+
+SET client_encoding = 'UTF8';
+
+COPY public.comments (id, post_id, user_id, body, created_at) FROM stdin;
+1	1	2	Bravo Ada\tmerci	2024-01-02 10:00:00+00
+2	1	\N	Compte supprimé	2024-01-02 11:00:00+00
+\.
+
+COPY public.posts (id, user_id, title, body, created_at, updated_at) FROM stdin;
+1	1	Bienvenue	Premier post\nsur deux lignes	2024-01-01 10:00:00+00	2024-01-01 10:00:00+00
+\.
+
+COPY public."users" (id, email, name, created_at, updated_at) FROM stdin;
+1	ada@corp.example	Ada Lovelace	2024-01-01 09:00:00+00	2024-01-01 09:00:00+00
+2	grace@corp.example	Grace Hopper	2024-01-01 09:30:00+00	2024-01-01 09:30:00+00
+\.
+
+SELECT pg_catalog.setval('public.users_id_seq', 2, true);
+"#;
+
+fn import_config() -> ImportConfig {
+    ImportConfig {
+        salt: Some("staging".to_string()),
+        rules: HashMap::from([
+            ("users.id".to_string(), AnonymizeRule::Hash),
+            ("users.email".to_string(), AnonymizeRule::Email),
+            ("users.name".to_string(), AnonymizeRule::Name),
+            ("posts.user_id".to_string(), AnonymizeRule::Hash),
+            ("comments.user_id".to_string(), AnonymizeRule::Hash),
+            ("comments.body".to_string(), AnonymizeRule::Sentence),
+        ]),
+    }
+}
+
+#[test]
+fn test_import_command_parsing() {
+    let config = Config::default();
+    assert_eq!(import_command(&config, ["--fixtures"]), Ok(None));
+    assert_eq!(
+        import_command(&config, ["--reset-database", "import", "dump.sql", "users.csv"]),
+        Ok(Some(vec![PathBuf::from("dump.sql"), PathBuf::from("users.csv")]))
+    );
+    assert!(import_command(&config, ["import"]).is_err());
+
+    let mut config = config;
+    config.server.environment = Environment::Production;
+    assert!(import_command(&config, ["import", "dump.sql"]).is_err());
+    assert!(import_command(&config, ["import", "dump.sql", "--force-fixtures"]).unwrap().is_some());
+}
+
+#[test]
+fn test_parse_dumps() {
+    let tables = parse_copy_blocks(DUMP).unwrap();
+    let names: Vec<&str> = tables.iter().map(|table| table.table.as_str()).collect();
+    assert_eq!(names, ["comments", "posts", "users"]);
+    assert_eq!(tables[0].rows[0][3].as_deref(), Some("Bravo Ada\tmerci"));
+    assert_eq!(tables[0].rows[1][2], None);
+    assert_eq!(tables[1].rows[0][3].as_deref(), Some("Premier post\nsur deux lignes"));
+    assert_eq!(tables[2].columns, ["id", "email", "name", "created_at", "updated_at"]);
+
+    let csv = parse_csv("users", "email,name\n\"doe, jane@corp.example\",\nada@corp.example,Ada\n").unwrap();
+    assert_eq!(csv.columns, ["email", "name"]);
+    assert_eq!(csv.rows[0], [Some("doe, jane@corp.example".to_string()), None]);
+
+    assert!(parse_copy_blocks("COPY users (id, email) FROM stdin;\n1\n\\.\n").is_err());
+}
+
+#[test]
+fn test_anonymizer_is_consistent() {
+    let anonymizer = Anonymizer::new(&import_config());
+    assert_eq!(anonymizer.rule("users", "email"), AnonymizeRule::Email);
+    assert_eq!(anonymizer.rule("users", "created_at"), AnonymizeRule::Keep);
+
+    // Une même valeur donne le même résultat, quelle que soit la colonne : les clés étrangères correspondent
+    let id = anonymizer.apply(AnonymizeRule::Hash, "42").unwrap();
+    assert_eq!(anonymizer.apply(AnonymizeRule::Hash, "42").unwrap(), id);
+    assert!(id.parse::<i64>().unwrap() > 0);
+    assert_ne!(anonymizer.apply(AnonymizeRule::Hash, "43").unwrap(), id);
+    assert_eq!(anonymizer.apply(AnonymizeRule::Hash, "ada").unwrap().len(), 16);
+
+    let email = anonymizer.apply(AnonymizeRule::Email, "ada@corp.example").unwrap();
+    assert!(email.ends_with("@example.com"));
+    assert_eq!(anonymizer.apply(AnonymizeRule::Email, "ada@corp.example").unwrap(), email);
+    assert_eq!(anonymizer.apply(AnonymizeRule::Null, "ada"), None);
+
+    // Un autre sel donne d'autres valeurs
+    let other = Anonymizer::new(&ImportConfig { salt: Some("other".to_string()), ..import_config() });
+    assert_ne!(other.apply(AnonymizeRule::Hash, "42").unwrap(), id);
+}
+
+#[tokio::test]
+async fn test_import_dump_without_pii() {
+    let db = TestDatabase::new().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dump.sql");
+    std::fs::write(&path, DUMP).unwrap();
+
+    let imported = import_dumps(db.pool(), &[path], &import_config(), FakerLocale::default()).await.unwrap();
+    // Ordre des clés étrangères, pas celui du fichier
+    assert_eq!(
+        imported,
+        [("users".to_string(), 2), ("posts".to_string(), 1), ("comments".to_string(), 2)]
+    );
+
+    let users: Vec<(i64, String, String)> = sqlx::query_as("SELECT id, email, name FROM users ORDER BY email")
+        .fetch_all(db.pool())
+        .await
+        .unwrap();
+    assert!(users.iter().all(|(id, email, name)| *id > 2
+        && email.ends_with("@example.com")
+        && !["Ada Lovelace", "Grace Hopper"].contains(&name.as_str())));
+
+    // Les identifiants hachés restent liés
+    let (author, title): (String, String) =
+        sqlx::query_as("SELECT users.email, posts.title FROM posts JOIN users ON users.id = posts.user_id")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    assert!(author.ends_with("@example.com"));
+    assert_eq!(title, "Bienvenue");
+    let commented: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments JOIN users ON users.id = comments.user_id")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(commented, 1);
+    let bodies: Vec<String> = sqlx::query_scalar("SELECT body FROM comments").fetch_all(db.pool()).await.unwrap();
+    assert!(!bodies.iter().any(|body| body.contains("Ada")));
+
+    // Séquences recalées : une nouvelle ligne ne heurte pas les identifiants importés
+    sqlx::query("INSERT INTO posts (user_id, title, body) SELECT id, 't', 'b' FROM users LIMIT 1")
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    db.close().await;
+}
@@ -0,0 +1,191 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::status::PerformanceMetrics,
+    routes::create_router,
+    state::AppState,
+};
+
+async fn state() -> AppState {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    AppState::new(db, config)
+}
+
+async fn app() -> Router {
+    create_router(state().await)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer secret");
+    let body = match body {
+        Some(body) => {
+            builder = builder.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    let response = app.clone().oneshot(builder.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn unique_title(prefix: &str) -> String {
+    format!("{} {}", prefix, Utc::now().timestamp_nanos_opt().unwrap())
+}
+
+fn metrics() -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+#[tokio::test]
+async fn test_incident_lifecycle() {
+    let app = app().await;
+    let title = unique_title("Database latency");
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/admin/incidents",
+        Some(json!({ "title": title, "severity": "major", "message": "Queries are slower than usual" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["status"], "investigating");
+    assert_eq!(body["data"]["updates"].as_array().unwrap().len(), 1);
+    let id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(&app, "PATCH", &format!("/api/admin/incidents/{}", id), Some(json!({ "severity": "critical" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["severity"], "critical");
+    assert_eq!(body["data"]["title"], title);
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        &format!("/api/admin/incidents/{}/updates", id),
+        Some(json!({ "status": "identified", "message": "An index is missing" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["status"], "identified");
+
+    let (status, body) = send(&app, "POST", &format!("/api/admin/incidents/{}/resolve", id), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["status"], "resolved");
+
+    let (status, body) = send(&app, "GET", &format!("/api/incidents/{}", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "resolved");
+    assert!(body["data"]["resolved_at"].is_string());
+    let updates = body["data"]["updates"].as_array().unwrap();
+    assert_eq!(updates.len(), 3);
+    assert_eq!(updates[0]["status"], "resolved");
+
+    // Une seconde résolution est refusée
+    let (status, body) = send(&app, "POST", &format!("/api/admin/incidents/{}/resolve", id), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "INCIDENT_ALREADY_RESOLVED");
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/admin/incidents/{}", id), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, "GET", &format!("/api/incidents/{}", id), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "INCIDENT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_open_incidents_are_listed_and_shown_on_status() {
+    let state = state().await;
+    state.metrics.publish(metrics());
+    let app = create_router(state);
+    let title = unique_title("API outage");
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/admin/incidents",
+        Some(json!({ "title": title, "severity": "critical", "message": "The API is unreachable" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(&app, "GET", "/api/incidents?open=true&per_page=100", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["items"].as_array().unwrap();
+    assert!(items.iter().any(|item| item["id"] == id));
+    assert!(items.iter().all(|item| item["resolved_at"].is_null()));
+
+    let (status, body) = send(&app, "GET", "/api/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let incidents = body["data"]["incidents"].as_array().unwrap();
+    assert!(incidents.iter().any(|incident| incident["id"] == id && incident["updates"][0]["message"] == "The API is unreachable"));
+
+    let response = app.clone().oneshot(Request::builder().uri("/").body(Body::empty()).unwrap()).await.unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains(&title));
+
+    send(&app, "POST", &format!("/api/admin/incidents/{}/resolve", id), Some(json!({ "message": "Back to normal" }))).await;
+    let (_, body) = send(&app, "GET", "/api/incidents?open=false&per_page=100", None).await;
+    assert!(body["items"].as_array().unwrap().iter().any(|item| item["id"] == id));
+    let (_, body) = send(&app, "GET", "/api/status", None).await;
+    assert!(!body["data"]["incidents"].as_array().unwrap().iter().any(|incident| incident["id"] == id));
+}
+
+#[tokio::test]
+async fn test_incident_admin_routes_require_token() {
+    let app = app().await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/incidents")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(json!({ "title": "x", "severity": "minor", "message": "y" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
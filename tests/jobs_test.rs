@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    response::Response,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, JobsConfig},
+    db::DatabaseManager,
+    routes::create_router,
+    services::jobs::{self, ExamplePayload, Job, JobError, JobHandler, JobRegistry},
+    state::AppState,
+};
+
+/// Tâche qui échoue toujours, pour vérifier les nouvelles tentatives
+struct FailingJob;
+
+#[derive(Serialize, Deserialize)]
+struct FailingPayload {}
+
+impl Job for FailingPayload {
+    const KIND: &'static str = "test.failing";
+}
+
+#[async_trait]
+impl JobHandler for FailingJob {
+    type Payload = FailingPayload;
+    type Output = ();
+
+    async fn run(&self, _db: &DatabaseManager, _payload: FailingPayload) -> Result<(), JobError> {
+        Err(JobError::Failed("upstream unavailable".to_string()))
+    }
+}
+
+/// Registre de tous les passages du worker dans ces tests
+fn registry() -> JobRegistry {
+    jobs::registry().register(FailingPayload::KIND, FailingJob)
+}
+
+fn settings() -> JobsConfig {
+    JobsConfig {
+        max_attempts: 2,
+        base_backoff_seconds: 0,
+        batch_size: 100,
+        ..JobsConfig::default()
+    }
+}
+
+async fn setup() -> (Router, DatabaseManager) {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    (create_router(AppState::new(db.clone(), config)), db)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> Response {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer secret");
+    let body = match body {
+        Some(body) => {
+            builder = builder.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    app.clone().oneshot(builder.body(body).unwrap()).await.unwrap()
+}
+
+async fn json(response: Response) -> Value {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_submit_poll_and_retry_jobs() {
+    let (app, db) = setup().await;
+
+    let response = send(&app, "POST", "/api/admin/jobs", Some(json!({ "kind": "example", "payload": { "message": "hello" } }))).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let location = response.headers()[header::LOCATION].to_str().unwrap().to_string();
+    let body = json(response).await;
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(location, format!("/api/jobs/{}", id));
+    assert_eq!(body["links"]["self"], location);
+    assert!(body["data"].get("payload").is_none());
+
+    let response = send(&app, "GET", &location, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+    assert_eq!(json(response).await["data"]["status"], "queued");
+
+    jobs::run_due_jobs(&db, &registry(), &settings()).await.unwrap();
+
+    let response = send(&app, "GET", &location, None).await;
+    assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    let body = json(response).await;
+    assert_eq!(body["data"]["status"], "succeeded");
+    assert_eq!(body["data"]["result"], json!({ "message": "hello" }));
+    assert_eq!(body["data"]["attempts"], 1);
+    assert!(body["data"]["finished_at"].is_string());
+
+    // Un seul test exécute des passages du worker : les tests tournent en parallèle sur la même base
+    let job = jobs::enqueue(db.get_pool(), &FailingPayload {}).await.unwrap();
+    assert_eq!(job.kind, "test.failing");
+
+    jobs::run_due_jobs(&db, &registry(), &settings()).await.unwrap();
+    let state = jobs::get_job(db.get_pool(), job.id).await.unwrap().unwrap();
+    assert_eq!(state.status, "queued");
+    assert_eq!(state.attempts, 1);
+    assert_eq!(state.error.as_deref(), Some("upstream unavailable"));
+
+    jobs::run_due_jobs(&db, &registry(), &settings()).await.unwrap();
+    let state = jobs::get_job(db.get_pool(), job.id).await.unwrap().unwrap();
+    assert_eq!(state.status, "dead");
+    assert_eq!(state.attempts, 2);
+    assert!(state.is_finished());
+
+    // Dead-letter : listé par l'administration, puis relancé avec un nouveau compteur
+    let response = send(&app, "GET", "/api/admin/jobs?kind=test.failing", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json(response).await;
+    assert!(body["items"].as_array().unwrap().iter().any(|dead| dead["id"] == job.id.to_string()));
+
+    let response = send(&app, "POST", &format!("/api/admin/jobs/{}/retry", job.id), None).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = json(response).await;
+    assert_eq!((body["data"]["status"].as_str(), body["data"]["attempts"].as_i64()), (Some("queued"), Some(0)));
+    assert!(body["data"]["finished_at"].is_null());
+
+    let response = send(&app, "POST", &format!("/api/admin/jobs/{}/retry", job.id), None).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(json(response).await["code"], "JOB_NOT_RETRIABLE");
+
+    // Workers concurrents : chaque tâche n'est réservée qu'une fois
+    let mut ids = Vec::new();
+    for index in 0..20 {
+        let payload = ExamplePayload { message: format!("job {}", index) };
+        ids.push(jobs::enqueue(db.get_pool(), &payload).await.unwrap().id);
+    }
+    let settings = JobsConfig { batch_size: 3, ..settings() };
+    let registry = registry();
+    loop {
+        let passes = futures::future::try_join_all((0..4).map(|_| jobs::run_due_jobs(&db, &registry, &settings)))
+            .await
+            .unwrap();
+        if passes.iter().sum::<usize>() == 0 {
+            break;
+        }
+    }
+    for id in ids {
+        let state = jobs::get_job(db.get_pool(), id).await.unwrap().unwrap();
+        assert_eq!((state.status.as_str(), state.attempts), ("succeeded", 1));
+    }
+}
+
+#[tokio::test]
+async fn test_submit_rejects_unknown_kind_and_bad_payload() {
+    let (app, _) = setup().await;
+
+    let response = send(&app, "POST", "/api/admin/jobs", Some(json!({ "kind": "missing" }))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json(response).await["code"], "UNKNOWN_JOB_KIND");
+
+    let response = send(&app, "POST", "/api/admin/jobs", Some(json!({ "kind": "example", "payload": { "msg": 1 } }))).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json(response).await["code"], "INVALID_JOB_PAYLOAD");
+
+    let response = send(&app, "GET", &format!("/api/jobs/{}", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(json(response).await["code"], "JOB_NOT_FOUND");
+
+    let response = send(&app, "POST", &format!("/api/admin/jobs/{}/retry", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(json(response).await["code"], "JOB_NOT_FOUND");
+}
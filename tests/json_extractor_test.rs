@@ -0,0 +1,104 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Extension, Router,
+};
+use axum::body::to_bytes;
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, SchemaStrictness},
+    db::DatabaseManager,
+    extractors::json::ApiJson,
+    state::AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Payload {
+    name: String,
+    tags: Vec<Tag>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Tag {
+    label: String,
+}
+
+async fn echo(ApiJson(payload): ApiJson<Payload>) -> String {
+    payload.name
+}
+
+fn create_app(strictness: SchemaStrictness) -> Router {
+    let mut config = Config::default();
+    config.api.request_strictness = strictness;
+
+    Router::new()
+        .route("/echo", post(echo))
+        .route(
+            "/echo-strict",
+            post(echo).route_layer(Extension(SchemaStrictness::Strict)),
+        )
+        .with_state(AppState::new(DatabaseManager::new(), config))
+}
+
+fn json_request(uri: &str, body: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+const EXTRA_FIELDS: &str = r#"{"name":"a","tags":[{"label":"x","color":"red"}],"extra":1}"#;
+
+#[tokio::test]
+async fn test_lenient_mode_ignores_unknown_fields() {
+    let response = create_app(SchemaStrictness::Lenient)
+        .oneshot(json_request("/echo", EXTRA_FIELDS))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_strict_mode_rejects_unknown_fields() {
+    let response = create_app(SchemaStrictness::Strict)
+        .oneshot(json_request("/echo", EXTRA_FIELDS))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["unknown_fields"], serde_json::json!(["extra", "tags[0].color"]));
+    assert_eq!(body["code"], "UNKNOWN_FIELDS");
+}
+
+#[tokio::test]
+async fn test_route_override_takes_precedence() {
+    let response = create_app(SchemaStrictness::Lenient)
+        .oneshot(json_request("/echo-strict", EXTRA_FIELDS))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_strict_mode_accepts_exact_payload() {
+    let response = create_app(SchemaStrictness::Strict)
+        .oneshot(json_request("/echo", r#"{"name":"a","tags":[{"label":"x"}]}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_invalid_data_returns_422() {
+    let response = create_app(SchemaStrictness::Strict)
+        .oneshot(json_request("/echo", r#"{"name":1}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,117 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Method, Request, StatusCode},
+};
+use std::time::Duration;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::latency::LatencyStats,
+    routes::create_router,
+    state::AppState,
+};
+
+#[test]
+fn test_quantiles_from_histogram() {
+    let stats = LatencyStats::new();
+    for _ in 0..90 {
+        stats.record(Method::GET, "/api/things", 200, Duration::from_millis(3));
+    }
+    for _ in 0..9 {
+        stats.record(Method::GET, "/api/things", 200, Duration::from_millis(80));
+    }
+    stats.record(Method::GET, "/api/things", 503, Duration::from_millis(1200));
+    stats.record(Method::POST, "/api/things", 422, Duration::from_millis(7));
+
+    let endpoints = stats.snapshot();
+    assert_eq!(endpoints.len(), 2);
+    let get = &endpoints[0];
+    assert_eq!((get.method.as_str(), get.count), ("GET", 100));
+    assert_eq!(get.p50_ms, 5.0);
+    assert_eq!(get.p95_ms, 100.0);
+    assert_eq!(get.p99_ms, 100.0);
+    assert!((get.max_ms - 1200.0).abs() < 1.0);
+    assert_eq!(get.server_errors, 1);
+    assert_eq!(get.buckets.iter().map(|b| b.count).sum::<u64>(), 100);
+    assert_eq!(get.buckets.last().unwrap().le_ms, None);
+    assert_eq!(endpoints[1].client_errors, 1);
+}
+
+#[tokio::test]
+async fn test_requests_are_recorded_by_route() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    let app = create_router(state.clone());
+
+    for uri in ["/api/help/ping", "/api/help/ping", "/api/no-such-route"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_ne!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let response = app
+        .oneshot(Request::builder().uri("/api/status/endpoints").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let endpoints = body["data"].as_array().unwrap();
+
+    let ping = endpoints.iter().find(|e| e["route"] == "/api/help/ping").expect("ping not recorded");
+    assert_eq!(ping["method"], "GET");
+    assert_eq!(ping["count"], 2);
+    // Les requêtes sans route ne sont pas comptées
+    assert!(endpoints.iter().all(|e| e["route"] != "/api/no-such-route"));
+    assert_eq!(state.latency.snapshot().len(), endpoints.len() + 1);
+}
+
+#[test]
+fn test_slowest_requests_leaderboard() {
+    let stats = LatencyStats::with_leaderboard(3, Duration::from_secs(60));
+    for (route, ms) in [("/a", 40), ("/b", 900), ("/c", 15), ("/d", 300), ("/e", 120)] {
+        stats.record(Method::GET, route, 200, Duration::from_millis(ms));
+    }
+    stats.record(Method::POST, "/f", 503, Duration::from_millis(5));
+
+    let slowest = stats.slowest();
+    let routes: Vec<_> = slowest.iter().map(|request| request.route.as_str()).collect();
+    assert_eq!(routes, ["/b", "/d", "/e"]);
+    assert!((slowest[0].duration_ms - 900.0).abs() < 1.0);
+    assert_eq!(slowest[0].status, 200);
+}
+
+#[test]
+fn test_slowest_requests_leave_the_window() {
+    let stats = LatencyStats::with_leaderboard(3, Duration::from_millis(50));
+    stats.record(Method::GET, "/slow", 200, Duration::from_millis(900));
+    std::thread::sleep(Duration::from_millis(80));
+    stats.record(Method::GET, "/fast", 200, Duration::from_millis(2));
+
+    let slowest = stats.slowest();
+    assert_eq!(slowest.len(), 1);
+    assert_eq!(slowest[0].route, "/fast");
+}
+
+#[tokio::test]
+async fn test_slow_endpoints_route() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    state.latency.record(Method::GET, "/api/users/{id}", 200, Duration::from_millis(1500));
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/api/help/slow-endpoints").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let slowest = &body["data"][0];
+    assert_eq!(slowest["route"], "/api/users/{id}");
+    assert_eq!(slowest["method"], "GET");
+    assert!(slowest["duration_ms"].as_f64().unwrap() >= 1500.0);
+}
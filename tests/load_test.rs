@@ -0,0 +1,64 @@
+use template_axum_sqlx_api::{
+    config::{Config, Environment, FakerLocale},
+    fixtures::load::{generate_load, load_command, LoadSpec},
+    testing::TestDatabase,
+};
+
+fn config(environment: Environment) -> Config {
+    let mut config = Config::default();
+    config.server.environment = environment;
+    config
+}
+
+#[test]
+fn test_load_command_parsing() {
+    let config = config(Environment::Development);
+    assert_eq!(load_command(&config, ["--fixtures"]), Ok(None));
+    assert_eq!(load_command(&config, ["generate-load"]), Ok(Some(LoadSpec::default())));
+    assert_eq!(
+        load_command(&config, ["generate-load", "--users", "10", "--posts=20", "--comments", "0"]),
+        Ok(Some(LoadSpec { users: 10, posts: 20, comments: 0 }))
+    );
+
+    assert!(load_command(&config, ["generate-load", "--users"]).is_err());
+    assert!(load_command(&config, ["generate-load", "--users", "many"]).is_err());
+    assert!(load_command(&config, ["generate-load", "--users", "0"]).is_err());
+    assert!(load_command(&config, ["generate-load", "--posts", "0"]).is_err());
+}
+
+#[test]
+fn test_load_command_is_refused_in_production() {
+    let config = config(Environment::Production);
+    assert!(load_command(&config, ["generate-load"]).is_err());
+    assert!(load_command(&config, ["generate-load", "--force-fixtures"]).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_generate_load_copies_related_rows() {
+    let db = TestDatabase::new().await;
+    let spec = LoadSpec { users: 120, posts: 60_000, comments: 500 };
+
+    let inserted = generate_load(db.pool(), spec, Some(42), FakerLocale::default()).await.unwrap();
+    assert_eq!(inserted, spec);
+
+    let count = |table: &str| {
+        let query = format!("SELECT COUNT(*) FROM {}", table);
+        let pool = db.pool().clone();
+        async move { sqlx::query_scalar::<_, i64>(&query).fetch_one(&pool).await.unwrap() }
+    };
+    assert_eq!(count("users").await, 120);
+    // Plusieurs blocs COPY pour une même table
+    assert_eq!(count("posts").await, 60_000);
+    assert_eq!(count("comments").await, 500);
+    let anonymous: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments WHERE user_id IS NULL")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(anonymous, 0);
+
+    // Une seconde exécution s'ajoute sans conflit d'email
+    generate_load(db.pool(), LoadSpec { users: 10, posts: 0, comments: 0 }, Some(42), FakerLocale::default()).await.unwrap();
+    assert_eq!(count("users").await, 130);
+
+    db.close().await;
+}
@@ -0,0 +1,98 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    logs::{LogBuffer, LogBufferLayer},
+    models::help::{LogCounts, LogLevel, LogRecord},
+    routes::create_router,
+    state::AppState,
+};
+
+fn record(level: LogLevel, message: &str) -> LogRecord {
+    LogRecord {
+        timestamp: Utc::now(),
+        level,
+        target: "logs_test".to_string(),
+        message: message.to_string(),
+        trace_id: None,
+        fields: Map::new(),
+    }
+}
+
+#[test]
+fn test_buffer_keeps_most_recent_records() {
+    let buffer = LogBuffer::new(3);
+    buffer.push(record(LogLevel::Error, "oldest"));
+    buffer.push(record(LogLevel::Info, "started"));
+    buffer.push(record(LogLevel::Warn, "slow query"));
+    buffer.push(record(LogLevel::Debug, "details"));
+
+    let messages = |records: Vec<LogRecord>| records.into_iter().map(|record| record.message).collect::<Vec<_>>();
+    assert_eq!(messages(buffer.recent(LogLevel::Trace, 10)), vec!["details", "slow query", "started"]);
+    assert_eq!(messages(buffer.recent(LogLevel::Info, 10)), vec!["slow query", "started"]);
+    assert_eq!(messages(buffer.recent(LogLevel::Trace, 1)), vec!["details"]);
+    assert_eq!(buffer.counts(), LogCounts { warnings: 1, errors: 0 });
+}
+
+#[test]
+fn test_layer_records_events_with_fields() {
+    let buffer = Arc::new(LogBuffer::new(10));
+    let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(buffer.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(user_id = 42, email = "jane@example.com", "User created");
+        tracing::error!("Database unreachable: {}", "timeout");
+    });
+
+    let records = buffer.recent(LogLevel::Trace, 10);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].level, LogLevel::Error);
+    assert_eq!(records[0].message, "Database unreachable: timeout");
+    assert_eq!(records[1].message, "User created");
+    assert_eq!(records[1].target, "logs_test");
+    assert_eq!(records[1].fields["user_id"], 42);
+    assert_eq!(records[1].fields["email"], "jane@example.com");
+}
+
+#[tokio::test]
+async fn test_logs_endpoint_requires_admin_and_filters_by_level() {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    let state = AppState::new(DatabaseManager::new(), config);
+    let marker = Utc::now().timestamp_nanos_opt().unwrap().to_string();
+    state.logs.push(record(LogLevel::Debug, &format!("debug {}", marker)));
+    state.logs.push(record(LogLevel::Warn, &format!("warn {}", marker)));
+
+    let request = |uri: &str, token: Option<&str>| {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(token) = token {
+            builder = builder.header("authorization", format!("Bearer {}", token));
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let response = create_router(state.clone()).oneshot(request("/api/help/logs", None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = create_router(state.clone())
+        .oneshot(request("/api/help/logs?level=warn&limit=1", Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let records = body["data"].as_array().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["message"], format!("warn {}", marker));
+    assert_eq!(records[0]["level"], "warn");
+
+    let response = create_router(state).oneshot(request("/api/help/logs?level=verbose", Some("secret"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,86 @@
+use axum::{body::to_bytes, http::StatusCode};
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{broadcast, Notify};
+use template_axum_sqlx_api::handlers::longpoll::{
+    long_poll_response, poll_until, recv_until, LongPollQuery, DEFAULT_WAIT, MAX_WAIT,
+};
+
+#[test]
+fn test_wait_is_bounded() {
+    assert_eq!(LongPollQuery::default().wait(), DEFAULT_WAIT);
+    assert_eq!(LongPollQuery { timeout: Some(5) }.wait(), Duration::from_secs(5));
+    assert_eq!(LongPollQuery { timeout: Some(3600) }.wait(), MAX_WAIT);
+}
+
+#[tokio::test]
+async fn test_poll_until_wakes_on_notification() {
+    let notify = Arc::new(Notify::new());
+    let counter = Arc::new(AtomicU32::new(0));
+
+    let producer = {
+        let (notify, counter) = (notify.clone(), counter.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            counter.store(3, Ordering::SeqCst);
+            notify.notify_waiters();
+        })
+    };
+
+    let value = poll_until(&notify, Duration::from_secs(5), async || {
+        Some(counter.load(Ordering::SeqCst)).filter(|&value| value > 0)
+    })
+    .await;
+    assert_eq!(value, Some(3));
+    producer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_poll_until_returns_immediately_when_ready() {
+    let notify = Notify::new();
+    let value = poll_until(&notify, Duration::from_secs(5), async || Some("ready")).await;
+    assert_eq!(value, Some("ready"));
+}
+
+#[tokio::test]
+async fn test_poll_until_times_out() {
+    let notify = Notify::new();
+    let value: Option<()> = poll_until(&notify, Duration::from_millis(20), async || None).await;
+    assert_eq!(value, None);
+}
+
+#[tokio::test]
+async fn test_recv_until() {
+    let (sender, mut receiver) = broadcast::channel(4);
+    sender.send("first").unwrap();
+    assert_eq!(recv_until(&mut receiver, Duration::from_millis(20)).await, Some("first"));
+    assert_eq!(recv_until(&mut receiver, Duration::from_millis(20)).await, None);
+
+    // Un abonné en retard reçoit le plus ancien message encore disponible
+    let (sender, mut receiver) = broadcast::channel(2);
+    for value in 1..=4 {
+        sender.send(value).unwrap();
+    }
+    assert_eq!(recv_until(&mut receiver, Duration::from_millis(20)).await, Some(3));
+
+    drop(sender);
+    assert_eq!(recv_until(&mut receiver, Duration::from_millis(20)).await, Some(4));
+    assert_eq!(recv_until(&mut receiver, Duration::from_secs(5)).await, None);
+}
+
+#[tokio::test]
+async fn test_long_poll_response() {
+    let response = long_poll_response(Some(serde_json::json!({ "id": 1 })));
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["data"]["id"], 1);
+
+    let response = long_poll_response(None::<()>);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(to_bytes(response.into_body(), usize::MAX).await.unwrap().is_empty());
+}
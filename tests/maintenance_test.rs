@@ -0,0 +1,211 @@
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{AlertNotifier, AlertsConfig, Config, NotifierKind, ScheduledMaintenance},
+    db::DatabaseManager,
+    models::{maintenance::MaintenanceWindow, status::PerformanceMetrics},
+    routes::create_router,
+    services::{alerts::AlertEngine, maintenance::active_window},
+    state::AppState,
+};
+
+async fn create_app(config: Config) -> (Router, DatabaseManager) {
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    (create_router(AppState::new(db.clone(), config)), db)
+}
+
+fn admin_config() -> Config {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    config
+}
+
+async fn send(app: Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Vec<u8>) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", "Bearer secret")
+        .header("content-type", "application/json");
+    let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+    let response = app.oneshot(request.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    (status, to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec())
+}
+
+fn scheduled(title: &str, starts_in_hours: i64, duration_hours: i64) -> ScheduledMaintenance {
+    let starts_at = Utc::now() + Duration::hours(starts_in_hours);
+    ScheduledMaintenance {
+        title: title.to_string(),
+        description: format!("{} description", title),
+        starts_at,
+        ends_at: starts_at + Duration::hours(duration_hours),
+    }
+}
+
+fn metrics(db_connected: bool) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 50,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected,
+        db_response_time_ms: db_connected.then_some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+/// Démarre un receveur local qui compte les notifications reçues
+async fn start_receiver() -> (String, Arc<Mutex<usize>>) {
+    let received = Arc::new(Mutex::new(0));
+    let count = received.clone();
+    let app = Router::new().route("/", post(move |_: Bytes| {
+        let count = count.clone();
+        async move {
+            *count.lock().unwrap() += 1;
+            StatusCode::OK
+        }
+    }));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{}/", addr), received)
+}
+
+#[tokio::test]
+async fn test_maintenance_window_lifecycle() {
+    let (app, _) = create_app(admin_config()).await;
+    let starts_at = Utc::now() + Duration::days(2);
+    let window = json!({
+        "title": "Mise à jour de la base",
+        "description": "Lecture seule pendant quelques minutes",
+        "starts_at": starts_at,
+        "ends_at": starts_at + Duration::hours(1),
+    });
+
+    let (status, body) = send(app.clone(), "POST", "/api/admin/maintenance", Some(window)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let created: Value = serde_json::from_slice(&body).unwrap();
+    let id = created["data"]["id"].as_i64().unwrap();
+
+    let (status, body) = send(app.clone(), "GET", "/api/maintenance", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Value = serde_json::from_slice(&body).unwrap();
+    assert!(listed["data"].as_array().unwrap().iter().any(|window| window["id"] == id));
+
+    let uri = format!("/api/admin/maintenance/{}", id);
+    let (status, _) = send(app.clone(), "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = send(app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let problem: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "MAINTENANCE_WINDOW_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_maintenance_window_must_end_after_start() {
+    let (app, _) = create_app(admin_config()).await;
+    let starts_at = Utc::now() + Duration::days(1);
+    let window = json!({ "title": "À l'envers", "starts_at": starts_at, "ends_at": starts_at - Duration::hours(1) });
+
+    let (status, body) = send(app, "POST", "/api/admin/maintenance", Some(window)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let problem: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "INVALID_MAINTENANCE_WINDOW");
+}
+
+#[tokio::test]
+async fn test_status_page_announces_maintenance() {
+    let mut config = Config::default();
+    config.maintenance.announce_days = 3;
+    config.maintenance.windows = vec![
+        scheduled("Migration du stockage", -1, 2),
+        scheduled("Changement de fournisseur", 24, 1),
+        scheduled("Refonte lointaine", 24 * 10, 1),
+        scheduled("Maintenance terminée", -3, 1),
+    ];
+    let (app, _) = create_app(config).await;
+
+    let response = app.oneshot(Request::builder().uri("/").body(Body::empty()).unwrap()).await.unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+
+    assert!(page.contains("Migration du stockage"));
+    assert!(page.contains("Migration du stockage description"));
+    assert!(page.contains("Maintenance en cours"));
+    assert!(page.contains("Changement de fournisseur"));
+    assert!(page.contains("Maintenance planifiée"));
+    assert!(!page.contains("Refonte lointaine"), "windows beyond announce_days are not announced");
+    assert!(!page.contains("Maintenance terminée"), "ended windows are not announced");
+    assert!(!page.contains("{MAINTENANCE_HTML}"));
+}
+
+#[tokio::test]
+async fn test_alerts_are_muted_during_maintenance() {
+    let mut config = Config::default();
+    config.maintenance.windows = vec![scheduled("Redémarrage de la base", -1, 2)];
+    let (_, db) = create_app(config.clone()).await;
+
+    let (url, received) = start_receiver().await;
+    let mut settings: AlertsConfig = toml::from_str(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+        "#,
+    )
+    .unwrap();
+    settings.notifiers = vec![AlertNotifier { kind: NotifierKind::Webhook, url, key: None }];
+    let mut engine = AlertEngine::new(&settings);
+
+    let window = active_window(db.get_pool(), &config.maintenance, Utc::now()).await.unwrap();
+    assert_eq!(window.as_ref().map(|window| window.title.as_str()), Some("Redémarrage de la base"));
+    engine.set_maintenance(window);
+    engine.process(&metrics(false)).await;
+    assert_eq!(*received.lock().unwrap(), 0);
+
+    // La condition toujours vraie après la fenêtre est notifiée
+    engine.set_maintenance(None);
+    engine.process(&metrics(false)).await;
+    assert_eq!(*received.lock().unwrap(), 1);
+}
+
+#[test]
+fn test_maintenance_window_bounds() {
+    let window = MaintenanceWindow::from(&scheduled("Fenêtre", 0, 1));
+
+    assert!(window.id.is_none());
+    assert!(window.is_active_at(window.starts_at));
+    assert!(window.is_active_at(window.starts_at + Duration::minutes(30)));
+    assert!(!window.is_active_at(window.ends_at));
+    assert!(!window.is_active_at(window.starts_at - Duration::seconds(1)));
+}
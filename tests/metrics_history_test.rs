@@ -0,0 +1,121 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use once_cell::sync::Lazy;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    handlers::response::PaginatedResponse,
+    models::status::{HistoryEntry, NetworkUsage, UptimeStats, DEGRADED_STATUS},
+    routes::create_router,
+    services::metrics::{list_history, prune_history, recent_history, record_history, uptime_stats},
+    state::AppState,
+};
+
+static TEST_MUTEX: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+async fn connect() -> DatabaseManager {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    db
+}
+
+fn entry(age: Duration, status: &str) -> HistoryEntry {
+    HistoryEntry {
+        timestamp: Utc::now() - age,
+        response_time_ms: 42,
+        db_connected: true,
+        db_response_time_ms: Some(3),
+        status: status.to_string(),
+        issues: vec!["Aucun problème détecté".to_string()],
+        network: Some(NetworkUsage::from_deltas(3_000_000, 1_000_000, 0, std::time::Duration::from_secs(300), 1000)),
+    }
+}
+
+#[tokio::test]
+async fn test_history_survives_and_is_pruned() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+    let pool = db.get_pool();
+
+    let marker = format!("test-{}", uuid::Uuid::new_v4());
+    record_history(pool, &entry(Duration::days(30), &marker)).await.unwrap();
+    record_history(pool, &entry(Duration::minutes(10), &marker)).await.unwrap();
+    record_history(pool, &entry(Duration::minutes(5), &marker)).await.unwrap();
+
+    let since = Some(Utc::now() - Duration::days(31));
+    let ours = |entries: Vec<HistoryEntry>| entries.into_iter().filter(|e| e.status == marker).collect::<Vec<_>>();
+    let listed = ours(list_history(pool, since, 1000, 0).await.unwrap());
+    assert_eq!(listed.len(), 3);
+    assert!(listed[0].timestamp > listed[1].timestamp, "most recent first");
+    assert_eq!(listed[0].db_response_time_ms, Some(3));
+    assert_eq!(listed[0].network.map(|n| n.rx_bytes_per_sec), Some(10_000));
+
+    let pruned = prune_history(pool, 7).await.unwrap();
+    assert!(pruned >= 1);
+    let listed = ours(list_history(pool, since, 1000, 0).await.unwrap());
+    assert_eq!(listed.len(), 2);
+
+    // La page de status lit les entrées dans l'ordre chronologique
+    let recent = recent_history(pool, 50).await.unwrap();
+    assert!(recent.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+}
+
+#[tokio::test]
+async fn test_history_endpoint_filters_by_date() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+
+    let marker = format!("test-{}", uuid::Uuid::new_v4());
+    record_history(db.get_pool(), &entry(Duration::hours(2), &marker)).await.unwrap();
+    record_history(db.get_pool(), &entry(Duration::seconds(1), &marker)).await.unwrap();
+
+    let since = (Utc::now() - Duration::hours(1)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let app = create_router(AppState::new(db, Config::default()));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/status/history?since={}&per_page=100", since))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: PaginatedResponse<HistoryEntry> = serde_json::from_slice(&body).unwrap();
+
+    assert!(page.items.iter().all(|e| e.timestamp >= Utc::now() - Duration::hours(1)));
+    assert_eq!(page.items.iter().filter(|e| e.status == marker).count(), 1);
+}
+
+#[tokio::test]
+async fn test_uptime_counts_degraded_samples_as_downtime() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+    let pool = db.get_pool();
+
+    let before = uptime_stats(pool).await.unwrap();
+    record_history(pool, &entry(Duration::hours(1), "Optimal")).await.unwrap();
+    record_history(pool, &entry(Duration::hours(2), DEGRADED_STATUS)).await.unwrap();
+    // Hors de la fenêtre de 24 heures
+    record_history(pool, &entry(Duration::days(3), DEGRADED_STATUS)).await.unwrap();
+    let after = uptime_stats(pool).await.unwrap();
+
+    let windows = after.iter().map(|stats| stats.window.as_str()).collect::<Vec<_>>();
+    assert_eq!(windows, ["24h", "7d", "30d"]);
+
+    // Mesures disponibles avant et après, déduites du pourcentage
+    let up = |stats: &UptimeStats| {
+        stats.uptime_percent.unwrap_or(0.0) * stats.samples as f64 / 100.0
+    };
+    assert_eq!(after[0].samples - before[0].samples, 2);
+    assert!((up(&after[0]) - up(&before[0]) - 1.0).abs() < 1e-6);
+    assert_eq!(after[1].samples - before[1].samples, 3);
+    assert!((up(&after[1]) - up(&before[1]) - 1.0).abs() < 1e-6);
+    assert!(after[0].avg_response_time_ms.is_some());
+}
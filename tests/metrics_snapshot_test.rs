@@ -0,0 +1,83 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use chrono::{Duration, Utc};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::status::{start_background_metrics_task, PerformanceMetrics},
+    routes::create_router,
+    services::metrics::{load_snapshot, save_snapshot},
+    state::AppState,
+};
+
+fn metrics(health_score: u8, age: Duration) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now() - age,
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+/// Un seul test : l'instantané est une ligne unique partagée
+#[tokio::test]
+async fn test_metrics_snapshot_survives_restart() {
+    let config = Config::default();
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    let pool = db.get_pool();
+
+    // Trop ancien pour être rechargé
+    save_snapshot(pool, &metrics(40, Duration::hours(30))).await.unwrap();
+    assert!(load_snapshot(pool, Duration::hours(24)).await.unwrap().is_none());
+
+    save_snapshot(pool, &metrics(87, Duration::minutes(10))).await.unwrap();
+    let restored = load_snapshot(pool, Duration::hours(24)).await.unwrap().unwrap();
+    assert_eq!(restored.health_score, 87);
+
+    // Au démarrage de la tâche, la page de status affiche l'instantané au lieu des valeurs d'attente
+    let state = AppState::new(db.clone(), config.clone());
+    start_background_metrics_task(
+        &state.tasks,
+        db,
+        config,
+        state.metrics.clone(),
+        state.status_codes.clone(),
+        state.query_insights.clone(),
+        state.cluster.clone(),
+        state.caches.clone(),
+    )
+    .await;
+    assert_eq!(state.metrics.latest().map(|metrics| metrics.health_score), Some(87));
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(!page.contains("Initialisation..."));
+    assert!(page.contains(r#"animateValue("health-score", 0, 87, 2400)"#));
+}
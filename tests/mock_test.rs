@@ -0,0 +1,181 @@
+use chrono::Utc;
+use std::time::Duration;
+use template_axum_sqlx_api::{
+    config::{AlertNotifier, AlertsConfig, Config, MonitorTarget, NotifierKind, PeerNode, TargetProbe},
+    models::status::{start_background_metrics_task, PerformanceMetrics, DEGRADED_STATUS},
+    services::{alerts::AlertEngine, monitoring::Monitor},
+    state::AppState,
+    testing::{
+        mock::wiremock::{
+            matchers::{body_partial_json, method, path},
+            Mock, ResponseTemplate,
+        },
+        test_config, MockHttp, TestDatabase,
+    },
+};
+
+fn metrics(db_connected: bool) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 50,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected,
+        db_response_time_ms: db_connected.then_some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+fn target(name: &str, url: &str, expected_status: Option<u16>) -> MonitorTarget {
+    MonitorTarget {
+        name: name.to_string(),
+        probe: TargetProbe::Http { url: url.to_string(), expected_status },
+    }
+}
+
+#[tokio::test]
+async fn test_redirect_rebases_outbound_urls() {
+    let mock = MockHttp::start().await;
+    let mut config = Config::default();
+    config.monitoring.targets = vec![
+        target("payments", "https://payments.example.com/v1/health/?deep=true", None),
+        MonitorTarget { name: "redis".to_string(), probe: TargetProbe::Tcp { address: "localhost:6379".to_string() } },
+    ];
+    config.monitoring.peers = vec![PeerNode { name: "eu-2".to_string(), url: "http://10.0.0.2:3000".to_string() }];
+    config.alerts.notifiers = vec![
+        AlertNotifier { kind: NotifierKind::Slack, url: "https://hooks.slack.com/services/T0/B0/x".to_string(), key: None },
+        AlertNotifier { kind: NotifierKind::Pagerduty, url: String::new(), key: Some("routing".to_string()) },
+    ];
+
+    mock.redirect(&mut config);
+
+    let origin = mock.uri();
+    assert_eq!(config.monitoring.ping_url, Some(format!("{}/api/help/ping", origin)));
+    assert_eq!(config.monitoring.targets[0].probe.endpoint(), format!("{}/v1/health?deep=true", origin));
+    assert_eq!(config.monitoring.targets[1].probe.endpoint(), "localhost:6379");
+    assert_eq!(config.monitoring.peers[0].url, origin);
+    assert_eq!(config.alerts.notifiers[0].url, format!("{}/services/T0/B0/x", origin));
+    assert_eq!(config.alerts.notifiers[1].endpoint(), origin);
+    assert!(config.telemetry.endpoint.starts_with(&origin));
+    assert_eq!(mock.url("/hooks/orders"), format!("{}/hooks/orders", origin));
+}
+
+#[tokio::test]
+async fn test_targets_are_probed_against_mock() {
+    let mock = MockHttp::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(mock.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/status"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(mock.server())
+        .await;
+
+    let mut config = Config::default();
+    config.monitoring.targets = vec![
+        target("payments", "https://payments.example.com/health", None),
+        target("search", "https://search.example.com/v1/status", None),
+        target("maintenance", "https://search.example.com/v1/status", Some(503)),
+    ];
+    mock.redirect(&mut config);
+
+    let checks = Monitor::new(&config.monitoring).probe_all().await;
+    let up = checks.iter().map(|(name, check)| (name.as_str(), check.up)).collect::<Vec<_>>();
+    assert_eq!(up, vec![("payments", true), ("search", false), ("maintenance", true)]);
+    assert_eq!(mock.server().received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_paging_notifiers_are_sent_to_mock() {
+    let mock = MockHttp::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/enqueue"))
+        .and(body_partial_json(serde_json::json!({ "routing_key": "routing", "event_action": "trigger" })))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(mock.server())
+        .await;
+
+    let mut alerts = toml::from_str::<AlertsConfig>(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+        page = true
+        "#,
+    )
+    .unwrap();
+    alerts.notifiers = vec![AlertNotifier { kind: NotifierKind::Pagerduty, url: String::new(), key: Some("routing".to_string()) }];
+    let mut config = Config { alerts, ..Config::default() };
+    mock.redirect(&mut config);
+
+    AlertEngine::new(&config.alerts).process(&metrics(false)).await;
+
+    mock.server().verify().await;
+}
+
+#[tokio::test]
+async fn test_response_time_ping_uses_mock() {
+    let mock = MockHttp::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/help/ping"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(mock.server())
+        .await;
+
+    let mut config = test_config();
+    config.monitoring.interval_seconds = 1;
+    config.monitoring.snapshot_max_age_hours = 0;
+    mock.redirect(&mut config);
+    let db = TestDatabase::with_config(config).await;
+    let state = AppState::new(db.manager(), db.config());
+    start_background_metrics_task(
+        &state.tasks,
+        db.manager(),
+        db.config(),
+        state.metrics.clone(),
+        state.status_codes.clone(),
+        state.query_insights.clone(),
+        state.cluster.clone(),
+        state.caches.clone(),
+    )
+    .await;
+
+    // Premier passage après le délai de démarrage de la tâche
+    let mut latest = None;
+    for _ in 0..60 {
+        latest = state.metrics.latest();
+        if latest.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    let metrics = latest.expect("no metrics computed");
+
+    // Ping en échec : la page passe en dégradé bien que la base réponde
+    assert!(metrics.db_connected);
+    assert_eq!(metrics.status, DEGRADED_STATUS);
+    assert!(!mock.server().received_requests().await.unwrap().is_empty());
+}
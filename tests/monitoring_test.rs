@@ -0,0 +1,223 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, MonitoringConfig, TargetProbe},
+    db::DatabaseManager,
+    models::status::{MetricsStore, PerformanceMetrics},
+    routes::create_router,
+    services::monitoring::{record_checks, Monitor},
+    state::AppState,
+};
+
+/// Démarre un service local : `/ok` répond 200, `/broken` répond 500
+async fn start_service() -> String {
+    let app = Router::new()
+        .route("/ok", get(|| async { StatusCode::OK }))
+        .route("/broken", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    addr.to_string()
+}
+
+/// Adresse locale sur laquelle plus rien n'écoute
+async fn closed_address() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+fn settings(toml: &str) -> MonitoringConfig {
+    toml::from_str(toml).expect("invalid monitoring config")
+}
+
+#[test]
+fn test_monitoring_config_parses_targets() {
+    let settings = settings(
+        r#"
+        timeout_ms = 2000
+
+        [[targets]]
+        name = "Payments API"
+        kind = "http"
+        url = "https://payments.example.com/health"
+        expected_status = 204
+
+        [[targets]]
+        name = "Redis"
+        kind = "tcp"
+        address = "localhost:6379"
+        "#,
+    );
+
+    assert_eq!(settings.timeout_ms, 2000);
+    assert_eq!(settings.interval_seconds, 300);
+    assert_eq!(
+        settings.targets[0].probe,
+        TargetProbe::Http { url: "https://payments.example.com/health".to_string(), expected_status: Some(204) }
+    );
+    assert_eq!(settings.targets[1].probe.endpoint(), "localhost:6379");
+    assert!(MonitoringConfig::default().targets.is_empty());
+}
+
+#[tokio::test]
+async fn test_probes_report_availability() {
+    let service = start_service().await;
+    let closed = closed_address().await;
+    let monitor = Monitor::new(&settings(&format!(
+        r#"
+        timeout_ms = 2000
+
+        [[targets]]
+        name = "ok"
+        kind = "http"
+        url = "http://{service}/ok"
+
+        [[targets]]
+        name = "broken"
+        kind = "http"
+        url = "http://{service}/broken"
+
+        [[targets]]
+        name = "expected-error"
+        kind = "http"
+        url = "http://{service}/broken"
+        expected_status = 500
+
+        [[targets]]
+        name = "tcp-open"
+        kind = "tcp"
+        address = "{service}"
+
+        [[targets]]
+        name = "tcp-closed"
+        kind = "tcp"
+        address = "{closed}"
+        "#
+    )));
+
+    let checks = monitor.probe_all().await;
+    let up = checks.iter().map(|(name, check)| (name.as_str(), check.up)).collect::<Vec<_>>();
+    assert_eq!(
+        up,
+        vec![("ok", true), ("broken", false), ("expected-error", true), ("tcp-open", true), ("tcp-closed", false)]
+    );
+    assert!(checks[0].1.response_time_ms.is_some());
+    assert!(checks[1].1.error.as_deref().unwrap().contains("500"));
+}
+
+#[tokio::test]
+async fn test_targets_are_shown_on_status() {
+    let service = start_service().await;
+    let name = format!("Billing {}", Utc::now().timestamp_nanos_opt().unwrap());
+
+    let monitoring = settings(&format!(
+        r#"
+        [[targets]]
+        name = "{name}"
+        kind = "http"
+        url = "http://{service}/ok"
+
+        [[targets]]
+        name = "Never probed"
+        kind = "tcp"
+        address = "localhost:1"
+        "#
+    ));
+    let config = Config { monitoring, ..Config::default() };
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+
+    // Deux passages de la tâche des métriques pour la première cible
+    let monitor = Monitor::new(&config.monitoring);
+    for _ in 0..2 {
+        let checks = monitor.probe_all().await.into_iter().filter(|(target, _)| *target == name).collect::<Vec<_>>();
+        record_checks(db.get_pool(), &checks).await.unwrap();
+    }
+
+    let app = create_router(AppState::new(db, config));
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/api/status/targets").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let targets = body["data"].as_array().unwrap();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(targets[0]["name"], name.as_str());
+    assert_eq!(targets[0]["kind"], "http");
+    assert_eq!(targets[0]["checks"].as_array().unwrap().len(), 2);
+    assert_eq!(targets[0]["checks"][1]["up"], true);
+    assert_eq!(targets[1]["endpoint"], "localhost:1");
+    assert!(targets[1]["checks"].as_array().unwrap().is_empty());
+
+    let response = app.oneshot(Request::builder().uri("/").body(Body::empty()).unwrap()).await.unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains(&name));
+    assert!(page.contains("En attente"));
+}
+
+fn metrics(health_score: u8) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+#[test]
+fn test_metrics_store_keeps_configured_samples() {
+    let store = MetricsStore::new(3);
+    for score in [10, 20, 30, 40] {
+        store.publish(metrics(score));
+    }
+
+    let scores = store.recent().iter().map(|m| m.health_score).collect::<Vec<_>>();
+    assert_eq!(scores, vec![20, 30, 40]);
+    assert_eq!(store.latest().unwrap().health_score, 40);
+    assert!(store.is_fresh());
+}
+
+#[tokio::test]
+async fn test_status_page_shows_collection_interval() {
+    let monitoring = settings("interval_seconds = 60");
+    let config = Config { monitoring, ..Config::default() };
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+
+    let app = create_router(AppState::new(db, config));
+    let response = app.oneshot(Request::builder().uri("/").body(Body::empty()).unwrap()).await.unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains("Dernières 50m • Calcul automatique toutes les 1m"), "interval not rendered");
+    assert!(page.contains("location.reload(), 60000"));
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+use template_axum_sqlx_api::models::status::{calculate_network_score, generate_issues, NetworkUsage};
+
+#[test]
+fn test_network_usage_from_deltas() {
+    // 375 Mo en 5 minutes sur un lien de 100 Mbit/s : 1,25 Mo/s, soit 10 Mbit/s
+    let usage = NetworkUsage::from_deltas(300_000_000, 75_000_000, 0, Duration::from_secs(300), 100);
+
+    assert_eq!(usage.rx_bytes_per_sec, 1_000_000);
+    assert_eq!(usage.tx_bytes_per_sec, 250_000);
+    assert!((usage.load_percent - 10.0).abs() < 0.01);
+    assert_eq!(usage.load_label(), "Faible");
+    assert_eq!(calculate_network_score(&usage), 25);
+}
+
+#[test]
+fn test_saturated_network_is_reported() {
+    let usage = NetworkUsage::from_deltas(u64::MAX / 2, 0, 3, Duration::from_secs(1), 1);
+
+    assert_eq!(usage.load_percent, 100.0);
+    assert_eq!(usage.load_label(), "Critique");
+    assert_eq!(calculate_network_score(&usage), 0);
+
+    let issues = generate_issues(true, Some(5), 20, 10.0, 10.0, 10.0, Some(usage), &[]);
+    assert!(issues.iter().any(|issue| issue.starts_with("Réseau saturé")));
+    assert!(issues.contains(&"Erreurs réseau: 3".to_string()));
+}
@@ -0,0 +1,116 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    extractors::pagination::Pagination,
+    handlers::response::PaginatedResponse,
+    state::AppState,
+};
+
+/// Liste fictive de 45 éléments
+async fn numbers(pagination: Pagination) -> PaginatedResponse<i64> {
+    let total = 45;
+    let items = (pagination.offset()..total).take(pagination.limit() as usize).collect();
+    pagination.response(items, total)
+}
+
+fn create_app() -> Router {
+    create_app_with(Config::default())
+}
+
+fn create_app_with(mut config: Config) -> Router {
+    config.api.default_per_page = 10;
+    config.api.max_per_page = 20;
+
+    Router::new()
+        .nest("/api", Router::new().route("/numbers", get(numbers)))
+        .with_state(AppState::new(DatabaseManager::new(), config))
+}
+
+async fn get_json(uri: &str) -> (StatusCode, Value) {
+    send(create_app(), uri).await
+}
+
+async fn send(app: Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_default_page() {
+    let (status, body) = get_json("/api/numbers").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["items"].as_array().unwrap().len(), 10);
+    assert_eq!(body["total"], 45);
+    assert_eq!(body["page"], 1);
+    assert_eq!(body["total_pages"], 5);
+    assert_eq!(body["next"], "/api/numbers?page=2&per_page=10");
+    assert!(body["prev"].is_null());
+}
+
+#[tokio::test]
+async fn test_page_links_keep_other_filters() {
+    let (_, body) = get_json("/api/numbers?kind=odd&page=3&per_page=15").await;
+
+    assert_eq!(body["items"][0], 30);
+    assert_eq!(body["total_pages"], 3);
+    assert!(body["next"].is_null());
+    assert_eq!(body["prev"], "/api/numbers?kind=odd&page=2&per_page=15");
+}
+
+#[tokio::test]
+async fn test_limit_offset_style() {
+    let (_, body) = get_json("/api/numbers?limit=5&offset=7").await;
+
+    assert_eq!(body["items"][0], 7);
+    assert_eq!(body["items"].as_array().unwrap().len(), 5);
+    assert_eq!(body["next"], "/api/numbers?limit=5&offset=12");
+    assert_eq!(body["prev"], "/api/numbers?limit=5&offset=2");
+}
+
+#[tokio::test]
+async fn test_per_page_is_capped() {
+    let (_, body) = get_json("/api/numbers?per_page=1000").await;
+    assert_eq!(body["per_page"], 20);
+}
+
+#[tokio::test]
+async fn test_invalid_parameters_are_rejected() {
+    let (status, body) = get_json("/api/numbers?page=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:problem:bad-request");
+
+    let (status, _) = get_json("/api/numbers?page=2&offset=10").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get_json("/api/numbers?per_page=abc").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_links_use_public_url() {
+    let mut config = Config::default();
+    config.server.public_url = Some("https://api.example.com/".to_string());
+    let (_, body) = send(create_app_with(config), "/api/numbers?page=2").await;
+
+    let links = &body["links"];
+    assert_eq!(links["self"], "https://api.example.com/api/numbers?page=2&per_page=10");
+    assert_eq!(links["first"], "https://api.example.com/api/numbers?page=1&per_page=10");
+    assert_eq!(links["last"], "https://api.example.com/api/numbers?page=5&per_page=10");
+    assert_eq!(links["next"], "https://api.example.com/api/numbers?page=3&per_page=10");
+    assert_eq!(links["prev"], "https://api.example.com/api/numbers?page=1&per_page=10");
+    assert_eq!(body["next"], links["next"]);
+}
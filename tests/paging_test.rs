@@ -0,0 +1,154 @@
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode, Uri},
+    Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use template_axum_sqlx_api::{
+    config::{AlertNotifier, AlertSeverity, AlertsConfig, NotifierKind},
+    models::status::PerformanceMetrics,
+    services::alerts::{Alert, AlertEngine, AlertState, PageSeverity},
+};
+
+/// Requête reçue : chemin et query, en-tête `authorization`, corps JSON
+type Received = Arc<Mutex<Vec<(String, Option<String>, Value)>>>;
+
+/// Démarre un faux service d'astreinte qui enregistre toutes les requêtes
+async fn start_pager() -> (String, Received) {
+    let received: Received = Arc::default();
+    let store = received.clone();
+    let app = Router::new().fallback(move |uri: Uri, headers: HeaderMap, body: Bytes| {
+        let store = store.clone();
+        async move {
+            let authorization = headers.get("authorization").map(|value| value.to_str().unwrap().to_string());
+            store.lock().unwrap().push((uri.to_string(), authorization, serde_json::from_slice(&body).unwrap()));
+            StatusCode::ACCEPTED
+        }
+    });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{}", addr), received)
+}
+
+fn metrics(db_connected: bool, health_score: u8) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected,
+        db_response_time_ms: db_connected.then_some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+/// Règle paginée `database-down` et règle non paginée `low-health-score`
+async fn engine(kind: NotifierKind) -> (AlertEngine, Received) {
+    let (url, received) = start_pager().await;
+    let mut settings: AlertsConfig = toml::from_str(
+        r#"
+        debounce_samples = 1
+
+        [[rules]]
+        name = "database-down"
+        kind = "db_down"
+        page = true
+
+        [[rules]]
+        name = "low-health-score"
+        kind = "health_score_below"
+        threshold = 60
+        "#,
+    )
+    .unwrap();
+    settings.notifiers = vec![AlertNotifier { kind, url, key: Some("secret-key".to_string()) }];
+    (AlertEngine::new(&settings), received)
+}
+
+fn alert(severity: AlertSeverity, health_score: u8) -> Alert {
+    Alert {
+        rule: "database-down".to_string(),
+        severity,
+        state: AlertState::Firing,
+        message: "Database is unreachable".to_string(),
+        health_score,
+        timestamp: Utc::now(),
+        page: true,
+    }
+}
+
+#[test]
+fn test_page_severity_follows_health_score() {
+    assert_eq!(alert(AlertSeverity::Info, 95).page_severity(), PageSeverity::Info);
+    assert_eq!(alert(AlertSeverity::Info, 70).page_severity(), PageSeverity::Warning);
+    assert_eq!(alert(AlertSeverity::Info, 50).page_severity(), PageSeverity::Error);
+    assert_eq!(alert(AlertSeverity::Warning, 20).page_severity(), PageSeverity::Critical);
+    // La gravité de la règle est un minimum
+    assert_eq!(alert(AlertSeverity::Critical, 95).page_severity(), PageSeverity::Critical);
+    assert_eq!(alert(AlertSeverity::Warning, 95).page_severity(), PageSeverity::Warning);
+    assert_eq!(PageSeverity::Critical.opsgenie(), "P1");
+}
+
+#[tokio::test]
+async fn test_pagerduty_trigger_and_resolve() {
+    let (mut engine, received) = engine(NotifierKind::Pagerduty).await;
+
+    engine.process(&metrics(false, 50)).await;
+    engine.process(&metrics(true, 90)).await;
+
+    // La règle sans `page = true` n'est pas envoyée
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2, "{:?}", received);
+    let (path, _, trigger) = &received[0];
+    assert_eq!(path, "/v2/enqueue");
+    assert_eq!(trigger["routing_key"], "secret-key");
+    assert_eq!(trigger["event_action"], "trigger");
+    assert_eq!(trigger["dedup_key"], "template-axum-sqlx-api:database-down");
+    assert_eq!(trigger["payload"]["severity"], "error");
+    assert_eq!(trigger["payload"]["summary"], "[database-down] Database is unreachable");
+
+    let (_, _, resolve) = &received[1];
+    assert_eq!(resolve["event_action"], "resolve");
+    assert_eq!(resolve["dedup_key"], trigger["dedup_key"]);
+}
+
+#[tokio::test]
+async fn test_opsgenie_create_and_close() {
+    let (mut engine, received) = engine(NotifierKind::Opsgenie).await;
+
+    engine.process(&metrics(false, 30)).await;
+    engine.process(&metrics(true, 90)).await;
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2, "{:?}", received);
+    let (path, authorization, create) = &received[0];
+    assert_eq!(path, "/v2/alerts");
+    assert_eq!(authorization.as_deref(), Some("GenieKey secret-key"));
+    assert_eq!(create["alias"], "template-axum-sqlx-api:database-down");
+    assert_eq!(create["priority"], "P1");
+
+    let (path, _, _) = &received[1];
+    assert_eq!(path, "/v2/alerts/template-axum-sqlx-api:database-down/close?identifierType=alias");
+}
@@ -0,0 +1,75 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::middleware::{
+    logging::setup_middleware,
+    request_id::{RequestId, X_REQUEST_ID},
+};
+
+async fn explode() -> &'static str {
+    panic!("boom")
+}
+
+fn create_app() -> Router {
+    let app = Router::new()
+        .route("/panic", get(explode))
+        .route("/id", get(|| async { RequestId::current().unwrap().0 }));
+    setup_middleware(app)
+}
+
+#[tokio::test]
+async fn test_panic_returns_structured_500() {
+    let response = create_app()
+        .oneshot(Request::builder().uri("/panic").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(response.headers().contains_key(X_REQUEST_ID));
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], 500);
+    assert_eq!(body["status"], "Internal Server Error");
+    assert_eq!(body["message"], "Internal server error");
+    // Le message de la panique ne fuit pas
+    assert!(!body.to_string().contains("boom"));
+}
+
+#[tokio::test]
+async fn test_request_id_is_reused_or_generated() {
+    let response = create_app()
+        .oneshot(
+            Request::builder()
+                .uri("/id")
+                .header(X_REQUEST_ID, "req-123")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[X_REQUEST_ID], "req-123");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"req-123");
+
+    let response = create_app()
+        .oneshot(Request::builder().uri("/id").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let generated = response.headers()[X_REQUEST_ID].to_str().unwrap().to_string();
+    assert_eq!(generated.len(), 36);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, generated.as_bytes());
+}
+
+#[test]
+fn test_invalid_request_ids_are_rejected() {
+    assert!(RequestId::parse("").is_none());
+    assert!(RequestId::parse("has space").is_none());
+    assert!(RequestId::parse(&"a".repeat(200)).is_none());
+    assert_eq!(RequestId::parse("abc-1").unwrap().as_str(), "abc-1");
+}
@@ -0,0 +1,131 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{config::Config, db::DatabaseManager, routes::create_router, state::AppState};
+use uuid::Uuid;
+
+async fn create_app() -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    create_router(AppState::new(db, config))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .header(header::CONTENT_TYPE, "application/json");
+    let body = body.map(|body| Body::from(body.to_string())).unwrap_or_default();
+    let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_slice(&body).unwrap() };
+    (status, body)
+}
+
+async fn create_user(app: &Router, name: &str) -> i64 {
+    let email = format!("{}-{}@example.com", name.to_lowercase(), Uuid::new_v4().simple());
+    let (status, body) = send(app, "POST", "/api/users", Some(json!({ "email": email, "name": name }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    body["data"]["id"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn test_post_with_author_and_comments() {
+    let app = create_app().await;
+    let author = create_user(&app, "Grace").await;
+    let reader = create_user(&app, "Alan").await;
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        &format!("/api/users/{}/posts", author),
+        Some(json!({ "title": "Compilers", "body": "Notes on compilers" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let post = body["data"]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["user_id"], author);
+
+    for (user, text) in [(reader, "Great read"), (author, "Thanks!")] {
+        let (status, _) = send(
+            &app,
+            "POST",
+            &format!("/api/posts/{}/comments", post),
+            Some(json!({ "user_id": user, "body": text })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, body) = send(&app, "GET", &format!("/api/posts/{}", post), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let detail = &body["data"];
+    assert_eq!(detail["title"], "Compilers");
+    assert_eq!(detail["author"], json!({ "id": author, "name": "Grace" }));
+    assert_eq!(detail["comments"][0]["body"], "Great read");
+    assert_eq!(detail["comments"][0]["author"]["name"], "Alan");
+    assert_eq!(detail["comments"][1]["author"]["id"], author);
+
+    let (status, body) = send(&app, "GET", &format!("/api/users/{}/posts", author), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["id"], post);
+    assert_eq!(body["items"][0]["comment_count"], 2);
+
+    // Le commentaire d'un compte supprimé reste, sans auteur
+    send(&app, "DELETE", &format!("/api/users/{}", reader), None).await;
+    let (_, body) = send(&app, "GET", &format!("/api/posts/{}/comments", post), None).await;
+    assert_eq!(body["data"][0]["user_id"], Value::Null);
+    assert_eq!(body["data"][0]["author"], Value::Null);
+
+    // Supprimer l'auteur supprime ses posts et leurs commentaires
+    send(&app, "DELETE", &format!("/api/users/{}", author), None).await;
+    let (status, body) = send(&app, "GET", &format!("/api/posts/{}", post), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "POST_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_missing_references_are_not_found() {
+    let app = create_app().await;
+    let user = create_user(&app, "Linus").await;
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/users/999999999/posts",
+        Some(json!({ "title": "Orphan", "body": "No author" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "USER_NOT_FOUND");
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/posts/999999999/comments",
+        Some(json!({ "user_id": user, "body": "Hello?" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "POST_NOT_FOUND");
+
+    let (status, body) = send(&app, "GET", "/api/users/999999999/posts", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "USER_NOT_FOUND");
+
+    let (status, _) = send(&app, "GET", "/api/posts/999999999/comments", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    send(&app, "DELETE", &format!("/api/users/{}", user), None).await;
+}
@@ -0,0 +1,106 @@
+use axum::http::StatusCode;
+use template_axum_sqlx_api::{
+    fixtures::{
+        fixture_rng,
+        principal::{create_principals, PrincipalFactory},
+        Factory, FixtureManager,
+    },
+    models::auth::Role,
+    services::auth::{find_principal, hash_api_key, verify_password},
+    testing::TestApp,
+};
+
+#[tokio::test]
+async fn test_principal_credentials_are_hashed() {
+    let app = TestApp::spawn().await;
+    let pool = app.db.pool();
+
+    let principal = PrincipalFactory::admin().password("correct horse").create(pool).await.unwrap();
+    assert_eq!(principal.password, "correct horse");
+    assert!(principal.api_key.starts_with("tk_"));
+
+    let (password_hash, role): (String, String) =
+        sqlx::query_as("SELECT password_hash, role FROM user_credentials WHERE user_id = $1")
+            .bind(principal.user.id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    assert!(password_hash.starts_with("$argon2id$"));
+    assert!(verify_password("correct horse", &password_hash));
+    assert!(!verify_password("wrong horse", &password_hash));
+    assert_eq!(role, "admin");
+
+    // Seule l'empreinte de la clé est enregistrée
+    let key_hash: String = sqlx::query_scalar("SELECT key_hash FROM api_keys WHERE user_id = $1")
+        .bind(principal.user.id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(key_hash, hash_api_key(&principal.api_key));
+
+    let found = find_principal(pool, &principal.api_key).await.unwrap().unwrap();
+    assert_eq!((found.user_id, found.email.as_str()), (principal.user.id, principal.user.email.as_str()));
+    assert!(found.has_role(Role::Admin));
+    assert!(find_principal(pool, "tk_unknown").await.unwrap().is_none());
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_admin_api_keys_open_protected_routes() {
+    let app = TestApp::spawn().await;
+    let admin = PrincipalFactory::admin().create(app.db.pool()).await.unwrap();
+    let member = PrincipalFactory::new(Role::User).create(app.db.pool()).await.unwrap();
+
+    app.as_bearer(&admin.api_key).get("/api/users").await.assert_status(StatusCode::OK);
+    app.as_bearer(&member.api_key)
+        .get("/api/users")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED)
+        .assert_error_code("INVALID_ADMIN_TOKEN");
+    app.as_bearer("tk_0123456789abcdef0123456789abcdef")
+        .get("/api/users")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // Une clé expirée n'est plus acceptée
+    sqlx::query("UPDATE api_keys SET expires_at = now() - interval '1 minute' WHERE user_id = $1")
+        .bind(admin.user.id)
+        .execute(app.db.pool())
+        .await
+        .unwrap();
+    app.as_bearer(&admin.api_key).get("/api/users").await.assert_status(StatusCode::UNAUTHORIZED);
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_principal_fixtures_can_be_reloaded() {
+    let app = TestApp::spawn().await;
+    let manager = FixtureManager::new(app.db.pool().clone());
+
+    create_principals(&manager, &mut fixture_rng(1, "principals"), 10).await.unwrap();
+    create_principals(&manager, &mut fixture_rng(2, "principals"), 10).await.unwrap();
+
+    // Les emails fixes sont repris, et seule la dernière clé de chaque principal reste valide
+    let rows: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT u.email, c.role, (SELECT count(*) FROM api_keys k WHERE k.user_id = u.id)
+         FROM users u JOIN user_credentials c ON c.user_id = u.id ORDER BY u.email",
+    )
+    .fetch_all(app.db.pool())
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        [
+            ("admin@example.com".to_string(), "admin".to_string(), 1),
+            ("member@example.com".to_string(), "user".to_string(), 1),
+        ]
+    );
+
+    let mut rng = fixture_rng(2, "principals");
+    let admin = PrincipalFactory::admin().email("admin@example.com").name("Admin").build_with(&mut rng);
+    app.as_bearer(&admin.api_key).get("/api/users").await.assert_status(StatusCode::OK);
+
+    app.close().await;
+}
@@ -0,0 +1,104 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use std::time::Duration;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::help::TaskState,
+    routes::create_router,
+    state::AppState,
+};
+
+async fn state() -> AppState {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    AppState::new(db, Config::default())
+}
+
+async fn probe(state: &AppState, uri: &str) -> (StatusCode, Value) {
+    let response = create_router(state.clone())
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn check<'a>(body: &'a Value, name: &str) -> &'a Value {
+    body["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == name)
+        .unwrap_or_else(|| panic!("check {} missing", name))
+}
+
+#[tokio::test]
+async fn test_liveness_does_not_depend_on_database() {
+    // Base de données jamais connectée : la liveness reste saine
+    let state = AppState::new(DatabaseManager::new(), Config::default());
+
+    let (status, body) = probe(&state, "/api/help/live").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "healthy");
+}
+
+#[tokio::test]
+async fn test_startup_succeeds_once_initialized() {
+    let state = state().await;
+
+    let (status, body) = probe(&state, "/api/help/startup").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unhealthy");
+
+    state.readiness.mark_started();
+    let (status, _) = probe(&state, "/api/help/startup").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_readiness_runs_dependency_checks() {
+    let state = state().await;
+
+    let (status, body) = probe(&state, "/api/help/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(check(&body, "accepting_traffic")["message"], "initialization in progress");
+
+    state.readiness.mark_started();
+    let (status, body) = probe(&state, "/api/help/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    let names = body["checks"].as_array().unwrap().iter().map(|check| check["name"].as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["accepting_traffic", "database", "migrations", "background_tasks"]);
+    assert_eq!(check(&body, "database")["status"], "healthy");
+    assert_ne!(check(&body, "migrations")["status"], "unhealthy");
+    assert!(body["checks"].as_array().unwrap().iter().all(|check| check["critical"] == true));
+}
+
+#[tokio::test]
+async fn test_readiness_fails_while_a_task_is_restarting() {
+    let state = state().await;
+    state.readiness.mark_started();
+
+    state.tasks.spawn("crashing", |_task| async move { panic!("always") });
+    for _ in 0..100 {
+        if state.tasks.report()[0].state == TaskState::Restarting {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let (status, body) = probe(&state, "/api/help/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let tasks = check(&body, "background_tasks");
+    assert_eq!(tasks["status"], "unhealthy");
+    assert_eq!(tasks["message"], "crashing is restarting");
+
+    // La liveness n'est pas affectée
+    let (status, _) = probe(&state, "/api/help/live").await;
+    assert_eq!(status, StatusCode::OK);
+}
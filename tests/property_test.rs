@@ -0,0 +1,115 @@
+use axum::{extract::FromRequestParts, http::Request};
+use proptest::{
+    prelude::*,
+    test_runner::{Config as ProptestConfig, TestRunner},
+};
+use sqlx::{Postgres, QueryBuilder};
+use std::sync::Arc;
+use template_axum_sqlx_api::{
+    config::Config,
+    extractors::{
+        pagination::Pagination,
+        query::{QueryOptions, QuerySpec},
+    },
+    models::{error::ErrorCode, user::UserFields},
+    testing::{
+        strategies::{json_payload, pagination_query, proptest, query_options_query},
+        TestApp,
+    },
+};
+
+/// Pagination extraite d'une query string, comme dans un handler
+fn extract_pagination(config: &Arc<Config>, query: &str) -> Option<Result<Pagination, ErrorCode>> {
+    let request = Request::builder().uri(format!("/api/users?{}", query)).body(()).ok()?;
+    let (mut parts, _) = request.into_parts();
+    let result = futures::executor::block_on(Pagination::from_request_parts(&mut parts, config));
+    Some(result.map_err(|e| e.code()))
+}
+
+proptest! {
+    #[test]
+    fn pagination_is_bounded(query in pagination_query(), total in 0i64..10_000) {
+        let config = Arc::new(Config::default());
+        let max_per_page = config.api.max_per_page;
+        let Some(result) = extract_pagination(&config, &query) else {
+            return Ok(());
+        };
+        match result {
+            Ok(pagination) => {
+                prop_assert!((1..=max_per_page).contains(&pagination.per_page()));
+                prop_assert!(pagination.limit() >= 1 && pagination.offset() >= 0);
+                prop_assert!(pagination.page() >= 1);
+
+                let response = pagination.response(Vec::<()>::new(), total);
+                prop_assert_eq!(response.total_pages, (total as u64).div_ceil(response.per_page));
+                prop_assert!(response.links.self_link.is_some() && response.links.last.is_some());
+                prop_assert_eq!(response.next.is_some(), (pagination.offset() as u64).saturating_add(response.per_page) < total as u64);
+            }
+            Err(code) => prop_assert_eq!(code, ErrorCode::InvalidPagination),
+        }
+    }
+
+    #[test]
+    fn query_options_use_whitelisted_columns(query in query_options_query::<UserFields>()) {
+        match QueryOptions::<UserFields>::parse(&query) {
+            Ok(options) => {
+                for (column, values) in &options.filters {
+                    prop_assert!(UserFields::FILTERS.iter().any(|(_, allowed)| allowed == column));
+                    prop_assert!(!values.is_empty() && values.iter().all(|value| !value.is_empty()));
+                }
+                for field in &options.sort {
+                    prop_assert!(UserFields::SORTS.iter().any(|(_, allowed)| *allowed == field.column));
+                }
+
+                // Les valeurs sont toujours liées : une par filtre, une par colonne de recherche
+                let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE true");
+                options.push_conditions(&mut builder);
+                options.push_order_by(&mut builder, "id");
+                let bound = options.filters.len() + options.search.as_ref().map_or(0, |_| UserFields::SEARCH.len());
+                prop_assert_eq!(builder.sql().matches('$').count(), bound);
+            }
+            Err(error) => {
+                prop_assert_eq!(error.code(), ErrorCode::InvalidQuery);
+                prop_assert_eq!(error.status().as_u16(), 400);
+            }
+        }
+    }
+}
+
+/// Requêtes générées contre l'application complète : jamais d'erreur serveur, et chaque
+/// erreur est un document `application/problem+json` valide
+#[test]
+fn test_error_responses_are_problem_documents() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let app = runtime.block_on(TestApp::spawn());
+    let client = app.as_admin();
+    let mut runner = TestRunner::new(ProptestConfig::with_cases(48));
+
+    runner
+        .run(&json_payload(&["email", "name"]), |payload| {
+            let response = runtime.block_on(client.post("/api/users", payload));
+            prop_assert!(!response.status.is_server_error(), "{}: {}", response.status, response.text());
+            if response.status.is_client_error() {
+                response.assert_problem();
+            }
+            Ok(())
+        })
+        .unwrap();
+
+    let query = (pagination_query(), query_options_query::<UserFields>())
+        .prop_map(|(pagination, options)| format!("{}&{}", pagination, options));
+    runner
+        .run(&query, |query| {
+            // Texte brut impossible à placer dans une URI : rejeté par le client HTTP
+            prop_assume!(format!("/api/users?{}", query).parse::<axum::http::Uri>().is_ok());
+            let response = runtime.block_on(client.get(&format!("/api/users?{}", query)));
+            prop_assert!(!response.status.is_server_error(), "{}: {}", response.status, response.text());
+            if response.status.is_client_error() {
+                response.assert_problem();
+            }
+            Ok(())
+        })
+        .unwrap();
+
+    runtime.block_on(app.close());
+}
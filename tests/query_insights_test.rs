@@ -0,0 +1,80 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    routes::create_router,
+    services::queries::{collect, extension_installed, QueryInsights},
+    state::AppState,
+};
+
+async fn connect(config: &Config) -> DatabaseManager {
+    let mut db = DatabaseManager::new();
+    db.connect(config).await.expect("Failed to connect to database");
+    db
+}
+
+async fn create_app(query_insights: bool) -> (Router, DatabaseManager) {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    config.status.query_insights = query_insights;
+    let db = connect(&config).await;
+    (create_router(AppState::new(db.clone(), config)), db)
+}
+
+async fn get(app: Router, uri: &str) -> (StatusCode, Vec<u8>) {
+    let request = Request::builder()
+        .uri(uri)
+        .header("authorization", "Bearer secret")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    (status, to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec())
+}
+
+#[tokio::test]
+async fn test_query_insights_disabled() {
+    let (app, _) = create_app(false).await;
+    let (status, body) = get(app, "/api/admin/queries").await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    let problem: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["code"], "QUERY_INSIGHTS_UNAVAILABLE");
+}
+
+#[tokio::test]
+async fn test_query_insights_follow_extension() {
+    let (app, db) = create_app(true).await;
+    let installed = extension_installed(db.get_pool()).await.unwrap();
+    let (status, body) = get(app, "/api/admin/queries").await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+
+    if installed {
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["by_total_time"].is_array());
+        assert!(body["by_mean_time"].is_array());
+    } else {
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "QUERY_INSIGHTS_UNAVAILABLE");
+        assert!(collect(db.get_pool(), 10).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_status_page_hides_queries_without_report() {
+    assert!(QueryInsights::new(false, 10).latest().is_none());
+
+    let (app, _) = create_app(false).await;
+    let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+
+    assert!(page.contains(r#"id="query-insights" class="card bg-base-100 shadow-xl border border-base-300 mb-6 hidden""#));
+    assert!(!page.contains("{QUERIES_"), "query placeholders left in the page");
+}
@@ -0,0 +1,99 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use once_cell::sync::Lazy;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    extractors::query::{QueryOptions, SortField},
+    models::{cors::NewCorsOrigin, events::EventFields},
+    routes::create_router,
+    services::cors::add_origin,
+    state::AppState,
+};
+
+static TEST_MUTEX: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+#[test]
+fn test_parse_filters_sort_and_search() {
+    let options = QueryOptions::<EventFields>::parse(
+        "filter%5Bkind%5D=deploy,incident&sort=-occurred_at,kind&search=db&page=2",
+    )
+    .unwrap();
+
+    assert_eq!(options.filters, vec![("kind", vec!["deploy".to_string(), "incident".to_string()])]);
+    assert_eq!(
+        options.sort,
+        vec![
+            SortField { column: "occurred_at", descending: true },
+            SortField { column: "kind", descending: false },
+        ]
+    );
+    assert_eq!(options.search.as_deref(), Some("db"));
+}
+
+#[test]
+fn test_unknown_fields_are_rejected() {
+    assert!(QueryOptions::<EventFields>::parse("filter[password]=x").is_err());
+    assert!(QueryOptions::<EventFields>::parse("sort=details").is_err());
+    assert!(QueryOptions::<EventFields>::parse("sort=kind;drop table app_events").is_err());
+}
+
+#[tokio::test]
+async fn test_admin_list_filters_and_sorts() {
+    let _lock = TEST_MUTEX.lock().await;
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    for (origin, tenant) in [
+        ("https://a.query-test.example.com", "query-test-acme"),
+        ("https://b.query-test.example.com", "query-test-acme"),
+        ("https://c.query-test.example.com", "query-test-other"),
+    ] {
+        let new_origin = NewCorsOrigin {
+            origin: origin.to_string(),
+            tenant: Some(tenant.to_string()),
+        };
+        add_origin(db.get_pool(), &new_origin).await.unwrap();
+    }
+
+    let app = create_router(AppState::new(db, config));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/admin/cors-origins?filter[tenant]=query-test-acme&sort=-origin&search=QUERY-TEST")
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let origins: Vec<&str> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["origin"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(origins, vec!["https://b.query-test.example.com", "https://a.query-test.example.com"]);
+    assert_eq!(body["total"], 2);
+}
+
+#[tokio::test]
+async fn test_invalid_sort_returns_bad_request() {
+    let app = create_router(AppState::new(DatabaseManager::new(), Config::default()));
+    let response = app
+        .oneshot(Request::builder().uri("/api/status/events?sort=secret").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,107 @@
+//! Exécuté avec `cargo test --features redis` ; les tests marqués `ignore` demandent un
+//! serveur : `REDIS_URL=redis://127.0.0.1:6379 cargo test --features redis -- --ignored`
+#![cfg(feature = "redis")]
+
+use axum::http::StatusCode;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use template_axum_sqlx_api::{
+    config::RedisConfig,
+    redis::{RedisError, RedisManager},
+    testing::TestApp,
+};
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Session {
+    user_id: i64,
+    roles: Vec<String>,
+}
+
+fn config(url: &str) -> RedisConfig {
+    RedisConfig {
+        url: Some(url.to_string()),
+        // Préfixe propre à chaque test : les tests partagent le serveur
+        key_prefix: format!("test:{}:", Uuid::new_v4()),
+        connect_timeout_ms: 500,
+        ..RedisConfig::default()
+    }
+}
+
+fn server() -> RedisManager {
+    let url = std::env::var("REDIS_URL").expect("REDIS_URL must point to a Redis server");
+    RedisManager::new(&config(&url)).unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_redis_is_optional_and_reports_unreachable_servers() {
+    assert!(RedisManager::new(&RedisConfig::default()).unwrap().is_none());
+    assert!(RedisManager::new(&config("http://127.0.0.1")).is_err());
+
+    let redis = RedisManager::new(&config("redis://127.0.0.1:1")).unwrap().unwrap();
+    assert!(redis.key("sessions:1").ends_with(":sessions:1"));
+    assert!(matches!(redis.ping().await, Err(RedisError::Redis(_) | RedisError::Timeout(_))));
+
+    // Vérification non critique : l'instance reste disponible sans Redis
+    let app = TestApp::builder()
+        .configure(|config| config.redis.url = Some("redis://127.0.0.1:1".to_string()))
+        .spawn()
+        .await;
+    let health = app.client().get("/api/help/health").await.assert_status(StatusCode::OK).json();
+    let check = health["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == "redis")
+        .unwrap()
+        .clone();
+    assert_eq!((check["status"].as_str(), check["critical"].as_bool()), (Some("degraded"), Some(false)));
+    app.close().await;
+}
+
+#[tokio::test]
+#[ignore = "requires a Redis server (REDIS_URL)"]
+async fn test_typed_values_with_ttl() {
+    let redis = server();
+    redis.ping().await.unwrap();
+
+    let session = Session { user_id: 42, roles: vec!["admin".to_string()] };
+    redis.set("sessions:abc", &session, Some(Duration::from_secs(30))).await.unwrap();
+    assert_eq!(redis.get::<Session>("sessions:abc").await.unwrap(), Some(session));
+    let ttl = redis.ttl("sessions:abc").await.unwrap().unwrap();
+    assert!(ttl > Duration::from_secs(25) && ttl <= Duration::from_secs(30));
+
+    // Durée de vie par défaut, puis type inattendu
+    redis.set("counter", &7, None).await.unwrap();
+    assert!(redis.ttl("counter").await.unwrap().unwrap() > Duration::from_secs(3500));
+    assert!(matches!(redis.get::<Session>("counter").await, Err(RedisError::Json(_))));
+
+    assert!(redis.delete("sessions:abc").await.unwrap());
+    assert!(!redis.delete("sessions:abc").await.unwrap());
+    assert_eq!(redis.get::<Session>("sessions:abc").await.unwrap(), None);
+    assert_eq!(redis.ttl("sessions:abc").await.unwrap(), None);
+
+    redis.set("short", &"soon gone", Some(Duration::from_millis(50))).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(redis.get::<String>("short").await.unwrap(), None);
+}
+
+#[tokio::test]
+#[ignore = "requires a Redis server (REDIS_URL)"]
+async fn test_publish_and_subscribe() {
+    let redis = server();
+    let mut messages = redis.subscribe::<Session>("sessions.revoked").await.unwrap();
+
+    let session = Session { user_id: 7, roles: Vec::new() };
+    assert_eq!(redis.publish("sessions.revoked", &session).await.unwrap(), 1);
+    redis.publish("sessions.revoked", &"not a session").await.unwrap();
+    // Canal d'un autre préfixe : non reçu
+    assert_eq!(redis.publish("other", &session).await.unwrap(), 0);
+
+    let timeout = Duration::from_secs(2);
+    let received = tokio::time::timeout(timeout, messages.next()).await.unwrap().unwrap();
+    assert_eq!(received.unwrap(), session);
+    let received = tokio::time::timeout(timeout, messages.next()).await.unwrap().unwrap();
+    assert!(matches!(received, Err(RedisError::Json(_))));
+}
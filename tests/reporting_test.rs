@@ -0,0 +1,45 @@
+use axum::{http::StatusCode, response::IntoResponse};
+use sentry::{test::with_captured_events, Level};
+use template_axum_sqlx_api::{
+    config::{Config, SentryConfig, TelemetryConfig},
+    handlers::error::AppError,
+    reporting,
+};
+
+#[test]
+fn test_server_errors_are_reported_with_their_code() {
+    let events = with_captured_events(|| {
+        let response = AppError::Internal("cache poisoned".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        // Les erreurs client ne sont pas signalées
+        let response = AppError::NotFound("no such user".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    });
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.tags["error.code"], "INTERNAL_ERROR");
+    assert_eq!(event.exception[0].value.as_deref(), Some("internal error: cache poisoned"));
+}
+
+#[test]
+fn test_task_failures_are_reported() {
+    let events = with_captured_events(|| reporting::capture_task_failure("webhook_dispatcher", "database unavailable"));
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].level, Level::Error);
+    assert_eq!(events[0].tags["task"], "webhook_dispatcher");
+    assert_eq!(
+        events[0].message.as_deref(),
+        Some("Background task webhook_dispatcher failed: database unavailable")
+    );
+}
+
+#[test]
+fn test_reporting_requires_a_valid_dsn() {
+    assert!(reporting::init(&Config::default()).is_none());
+
+    let sentry = SentryConfig { dsn: Some("not a dsn".to_string()), ..SentryConfig::default() };
+    let config = Config { telemetry: TelemetryConfig { sentry, ..TelemetryConfig::default() }, ..Config::default() };
+    assert!(reporting::init(&config).is_none());
+}
@@ -0,0 +1,114 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    extractors::links::LinkBuilder,
+    handlers::{
+        error::AppError,
+        response::{ApiResponse, PaginationMeta},
+    },
+    middleware::{logging::setup_middleware, request_id::X_REQUEST_ID},
+};
+
+async fn render(response: impl IntoResponse) -> (StatusCode, Value) {
+    let response = response.into_response();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_slice(&body).unwrap() };
+    (status, body)
+}
+
+/// Corps sans `meta`, dont l'horodatage change à chaque réponse
+fn without_meta(mut body: Value) -> Value {
+    body.as_object_mut().unwrap().remove("meta");
+    body
+}
+
+#[tokio::test]
+async fn test_ok_and_created_include_status() {
+    let (status, body) = render(ApiResponse::ok(json!({ "id": 1 }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(without_meta(body), json!({ "code": 200, "status": "OK", "data": { "id": 1 } }));
+
+    let (status, body) = render(ApiResponse::created("user").message("User created")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["status"], "Created");
+    assert_eq!(body["message"], "User created");
+}
+
+#[tokio::test]
+async fn test_no_content_has_empty_body() {
+    let (status, body) = render(ApiResponse::no_content()).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(body, Value::Null);
+}
+
+#[tokio::test]
+async fn test_error_response() {
+    let (status, body) = render(ApiResponse::error(StatusCode::CONFLICT, "Already exists")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(without_meta(body), json!({ "code": 409, "status": "Conflict", "message": "Already exists" }));
+}
+
+#[tokio::test]
+async fn test_paginated_response() {
+    let (status, body) = render(ApiResponse::paginated(vec![1, 2], PaginationMeta::new(2, 2, 5))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([1, 2]));
+    // La pagination est à plat dans `meta`, à côté de l'horodatage
+    for (key, value) in [("page", 2), ("per_page", 2), ("total", 5), ("total_pages", 3)] {
+        assert_eq!(body["meta"][key], value);
+    }
+    assert!(body["meta"]["timestamp"].is_string());
+}
+
+#[tokio::test]
+async fn test_meta_carries_request_id_and_duration() {
+    let app = setup_middleware(
+        Router::new()
+            .route("/ok", get(|| async { ApiResponse::ok("done") }))
+            .route("/error", get(|| async { AppError::validation("bad input") })),
+    );
+
+    for uri in ["/ok", "/error"] {
+        let request = Request::builder().uri(uri).header(X_REQUEST_ID, "req-42").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[X_REQUEST_ID], "req-42");
+
+        let (_, body) = render(response).await;
+        let meta = &body["meta"];
+        assert_eq!(meta["request_id"], "req-42");
+        assert!(meta["duration_ms"].as_f64().unwrap() >= 0.0);
+        assert!(chrono::DateTime::parse_from_rfc3339(meta["timestamp"].as_str().unwrap()).is_ok());
+    }
+
+    // Hors d'une requête, seul l'horodatage est renseigné
+    let (_, body) = render(ApiResponse::ok(1)).await;
+    assert!(body["meta"]["timestamp"].is_string());
+    assert!(body["meta"].get("request_id").is_none());
+}
+
+#[tokio::test]
+async fn test_resource_links() {
+    let links = LinkBuilder::new(Some("https://api.example.com"));
+    let response = ApiResponse::ok(json!({ "id": 7 }))
+        .links(links.to("/api/uploads/7").related("download", links.url("/api/uploads/7/download")));
+    let (_, body) = render(response).await;
+
+    assert_eq!(
+        body["links"],
+        json!({
+            "self": "https://api.example.com/api/uploads/7",
+            "related": { "download": "https://api.example.com/api/uploads/7/download" }
+        })
+    );
+
+    // Sans URL publique, les liens sont relatifs
+    assert_eq!(LinkBuilder::new(None).url("/api/uploads/7"), "/api/uploads/7");
+}
@@ -0,0 +1,102 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use chrono::Utc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::{
+        help::HealthStatus,
+        status::{PerformanceMetrics, RuntimeUsage},
+    },
+    routes::create_router,
+    services::{
+        health::{HealthRegistry, RuntimeCheck},
+        runtime::RuntimeSampler,
+    },
+    state::AppState,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sampler_measures_worker_activity() {
+    let mut sampler = RuntimeSampler::new();
+
+    // Une tâche qui occupe un worker sans jamais céder la main
+    tokio::spawn(async {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(200) {
+            std::hint::spin_loop();
+        }
+    })
+    .await
+    .unwrap();
+    // Les workers publient leur temps d'occupation en se mettant en attente
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let usage = sampler.sample();
+    assert_eq!(usage.workers, 2);
+    assert!(usage.busy_percent > 10.0, "busy: {}", usage.busy_percent);
+    assert!(usage.busy_percent <= 100.0);
+}
+
+#[tokio::test]
+async fn test_runtime_health_check() {
+    let registry = HealthRegistry::new(Duration::from_secs(1)).register(RuntimeCheck);
+
+    let results = registry.run(false).await;
+    assert_eq!(results[0].name, "runtime");
+    assert!(!results[0].critical);
+    assert_eq!(results[0].status, HealthStatus::Healthy);
+    let details = results[0].details.as_ref().unwrap();
+    assert_eq!(details["workers"], 1);
+    assert!(details["alive_tasks"].is_u64());
+}
+
+#[tokio::test]
+async fn test_status_page_shows_runtime() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    state.metrics.publish(PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: Some(RuntimeUsage {
+            workers: 4,
+            alive_tasks: 12,
+            global_queue_depth: 3,
+            blocking_queue_depth: None,
+            busy_percent: 42.4,
+        }),
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    });
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains("12 tâches • file 3 • occupation 42%"), "runtime not rendered");
+}
@@ -0,0 +1,47 @@
+use template_axum_sqlx_api::sanitize::{escape_html, plain_text, remove_control_chars, strip_html, truncate};
+
+#[test]
+fn test_strip_html_keeps_text_between_tags() {
+    assert_eq!(strip_html("<b>Hello</b> <i>world</i>"), "Hello world");
+    assert_eq!(strip_html("<a href=\"x\" onclick=\"evil()\">link</a>"), "link");
+    assert_eq!(strip_html("before<!-- comment -->after"), "beforeafter");
+    // Le contenu des scripts et styles disparaît avec eux
+    assert_eq!(strip_html("a<script>alert('x')</script>b<STYLE>p{}</style>c"), "abc");
+    assert_eq!(strip_html("a<script>never closed"), "a");
+}
+
+#[test]
+fn test_strip_html_keeps_comparisons() {
+    assert_eq!(strip_html("1 < 2 and 3 > 2"), "1 < 2 and 3 > 2");
+    assert_eq!(strip_html("x <3 y"), "x <3 y");
+    assert_eq!(strip_html("unclosed <b"), "unclosed <b");
+}
+
+#[test]
+fn test_escape_html() {
+    assert_eq!(
+        escape_html(r#"<img src=x onerror="alert('1')"> & co"#),
+        "&lt;img src=x onerror=&quot;alert(&#39;1&#39;)&quot;&gt; &amp; co"
+    );
+}
+
+#[test]
+fn test_remove_control_chars() {
+    assert_eq!(remove_control_chars("line\nnext\ttab"), "line\nnext\ttab");
+    assert_eq!(remove_control_chars("bell\u{7}esc\u{1b}[31mred\r"), "bellesc[31mred");
+    // Surcharge bidirectionnelle et espace de largeur nulle
+    assert_eq!(remove_control_chars("file\u{202E}gpj.exe\u{200B}"), "filegpj.exe");
+}
+
+#[test]
+fn test_truncate_counts_characters() {
+    assert_eq!(truncate("short", 10), "short");
+    assert_eq!(truncate("exactly", 7), "exactly");
+    assert_eq!(truncate("héllo wörld", 5), "héll…");
+    assert_eq!(truncate("abc", 0), "");
+}
+
+#[test]
+fn test_plain_text() {
+    assert_eq!(plain_text("  <p>Hello\u{0}, <b>you</b></p>\n"), "Hello, you");
+}
@@ -0,0 +1,130 @@
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use std::{collections::BTreeMap, time::Duration};
+use template_axum_sqlx_api::{
+    config::SchedulerConfig,
+    models::status::HistoryEntry,
+    services::{
+        jobs::{self, Job},
+        metrics::{record_history, UptimeReport},
+        scheduler::{next_run, parse_schedule, scheduler, warm_cache, Scheduler},
+    },
+    testing::TestApp,
+};
+
+fn settings(tasks: &[(&str, &str)]) -> SchedulerConfig {
+    SchedulerConfig {
+        tasks: tasks.iter().map(|(name, cron)| (name.to_string(), cron.to_string())).collect::<BTreeMap<_, _>>(),
+    }
+}
+
+#[test]
+fn test_parse_schedule() {
+    let after = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let next = |expression: &str| next_run(&parse_schedule(expression).unwrap(), after).unwrap();
+
+    assert_eq!(next("0 6 * * *"), Utc.with_ymd_and_hms(2026, 10, 17, 6, 0, 0).unwrap());
+    assert_eq!(next("*/5 * * * *"), Utc.with_ymd_and_hms(2026, 10, 16, 12, 5, 0).unwrap());
+    assert_eq!(next("@hourly"), Utc.with_ymd_and_hms(2026, 10, 16, 13, 0, 0).unwrap());
+    // Secondes en tête
+    assert_eq!(next("30 * * * * *"), Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 30).unwrap());
+
+    assert!(parse_schedule("every minute").is_err());
+    assert!(parse_schedule("61 * * * *").is_err());
+}
+
+#[tokio::test]
+async fn test_start_rejects_unknown_tasks_and_invalid_expressions() {
+    let app = TestApp::spawn().await;
+
+    let error = scheduler().start(&app.state, &settings(&[("missing", "@daily")])).unwrap_err();
+    assert!(error.contains("unknown scheduled task: missing"), "{}", error);
+    assert!(error.contains("cache_warmup, history_pruning, uptime_report"), "{}", error);
+
+    let error = scheduler().start(&app.state, &settings(&[("history_pruning", "0 25 * * *")])).unwrap_err();
+    assert!(error.starts_with("[scheduler.tasks] history_pruning:"), "{}", error);
+    // Rien n'est démarré si une expression est refusée
+    assert!(app.state.tasks.report().is_empty());
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_scheduled_task_runs_are_tracked() {
+    let app = TestApp::spawn().await;
+
+    let registry = Scheduler::new()
+        .register("ticking", |_state| async { Ok(()) })
+        .register("failing", |_state| async { Err("report storage unavailable".into()) });
+    let planned = registry
+        .start(&app.state, &settings(&[("ticking", "* * * * * *"), ("failing", "* * * * * *")]))
+        .unwrap();
+    assert_eq!(planned, 2);
+
+    let mut report = app.state.tasks.report();
+    for _ in 0..50 {
+        if report.iter().all(|task| task.runs > 0) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        report = app.state.tasks.report();
+    }
+    let task = |name: &str| report.iter().find(|task| task.name == name).unwrap().clone();
+
+    let ticking = task("scheduled_ticking");
+    assert_eq!(ticking.schedule.as_deref(), Some("* * * * * *"));
+    assert!(ticking.runs > 0 && ticking.failures == 0);
+    assert!(ticking.next_run.unwrap() > ticking.last_run.unwrap());
+
+    let failing = task("scheduled_failing");
+    assert!(failing.failures > 0);
+    assert_eq!(failing.last_error.as_deref(), Some("report storage unavailable"));
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_builtin_tasks() {
+    let app = TestApp::builder()
+        .configure(|config| {
+            config.cache.routes.insert("/api/help/info".to_string(), 60);
+            config.cache.routes.insert("/api/uploads/{id}".to_string(), 60);
+        })
+        .spawn()
+        .await;
+    let pool = app.db.pool();
+
+    // history_pruning : seules les entrées au-delà de la rétention sont supprimées
+    for age_days in [1, 45] {
+        let entry = HistoryEntry {
+            timestamp: Utc::now() - ChronoDuration::days(age_days),
+            response_time_ms: 20,
+            db_connected: true,
+            db_response_time_ms: Some(2),
+            status: "healthy".to_string(),
+            issues: Vec::new(),
+            network: None,
+        };
+        record_history(pool, &entry).await.unwrap();
+    }
+    scheduler().run("history_pruning", &app.state).await.unwrap();
+    let remaining: i64 = sqlx::query_scalar("SELECT count(*) FROM metrics_history").fetch_one(pool).await.unwrap();
+    assert_eq!(remaining, 1);
+
+    // uptime_report : la tâche asynchrone produit la disponibilité par période
+    scheduler().run("uptime_report", &app.state).await.unwrap();
+    jobs::run_due_jobs(&app.state.db, &app.state.jobs, &app.state.config.jobs).await.unwrap();
+    let report: (String, serde_json::Value) = sqlx::query_as("SELECT status, result FROM jobs WHERE kind = $1")
+        .bind(UptimeReport::KIND)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(report.0, "succeeded");
+    assert_eq!(report.1.as_array().unwrap().len(), 3);
+
+    // cache_warmup : seules les routes sans paramètre sont préparées
+    assert_eq!(warm_cache(&app.state, &app.url("")).await.unwrap(), 1);
+    assert_eq!(app.state.response_cache.len(), 1);
+    assert!(warm_cache(&app.state, "http://127.0.0.1:9").await.is_err());
+
+    app.close().await;
+}
@@ -0,0 +1,82 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{config::Config, db::DatabaseManager, routes::create_router, state::AppState};
+use uuid::Uuid;
+
+async fn create_app() -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    create_router(AppState::new(db, config))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .header(header::CONTENT_TYPE, "application/json");
+    let body = body.map(|body| Body::from(body.to_string())).unwrap_or_default();
+    let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_slice(&body).unwrap() };
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_search_ranks_and_highlights_posts() {
+    let app = create_app().await;
+    let email = format!("search-{}@example.com", Uuid::new_v4().simple());
+    let (_, body) = send(&app, "POST", "/api/users", Some(json!({ "email": email, "name": "Searcher" }))).await;
+    let user = body["data"]["id"].as_i64().unwrap();
+
+    // Terme propre au test, pour ne pas dépendre des autres posts
+    let term = format!("zebra{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let posts = [
+        json!({ "title": "Unrelated title", "body": format!("A long body that mentions {} once.", term) }),
+        json!({ "title": format!("All about {}", term), "body": format!("The {} is described here.", term) }),
+        json!({ "title": "Nothing to see", "body": "No match in this one." }),
+    ];
+    for post in posts {
+        let (status, _) = send(&app, "POST", &format!("/api/users/{}/posts", user), Some(post)).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, body) = send(&app, "GET", &format!("/api/search?q={}", term), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["items"][0]["title"], format!("All about {}", term));
+    assert!(body["items"][0]["rank"].as_f64().unwrap() > body["items"][1]["rank"].as_f64().unwrap());
+    assert!(body["items"][1]["headline"].as_str().unwrap().contains(&format!("<mark>{}</mark>", term)));
+
+    // Syntaxe web : un terme exclu retire les posts qui le contiennent
+    let (_, body) = send(&app, "GET", &format!("/api/search?q={}%20-described", term), None).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["title"], "Unrelated title");
+
+    send(&app, "DELETE", &format!("/api/users/{}", user), None).await;
+}
+
+#[tokio::test]
+async fn test_search_requires_terms() {
+    let app = create_app().await;
+
+    for uri in ["/api/search", "/api/search?q=%20%20"] {
+        let (status, body) = send(&app, "GET", uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "MISSING_SEARCH_TERMS");
+    }
+
+    let (status, body) = send(&app, "GET", &format!("/api/search?q={}", "a".repeat(201)), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "INVALID_QUERY");
+}
@@ -0,0 +1,99 @@
+use axum::http::StatusCode;
+use std::time::Duration;
+use template_axum_sqlx_api::testing::{
+    mock::wiremock::{
+        matchers::{method, path},
+        Mock, ResponseTemplate,
+    },
+    MockHttp, TestApp,
+};
+
+/// Résultat de chaque étape du rapport, dans l'ordre
+fn outcomes(report: &serde_json::Value) -> Vec<(String, String)> {
+    report["data"]["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| (step["name"].as_str().unwrap().to_string(), step["outcome"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_selftest_reports_every_step() {
+    let mock = MockHttp::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/help/ping"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("pong"))
+        .mount(mock.server())
+        .await;
+    let app = TestApp::builder().configure(|config| mock.redirect(config)).spawn().await;
+    app.state.tasks.spawn("heartbeat", |task| async move {
+        loop {
+            task.record(Ok::<_, String>(()), Duration::from_secs(60));
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+
+    let response = app.as_admin().post("/api/help/selftest", serde_json::json!({})).await;
+    response.assert_status(StatusCode::OK);
+    let report = response.json();
+    assert_eq!(report["data"]["passed"], true);
+    assert_eq!(
+        outcomes(&report),
+        [
+            ("database_round_trip".to_string(), "passed".to_string()),
+            ("database_write_rollback".to_string(), "passed".to_string()),
+            ("cache_read_write".to_string(), "passed".to_string()),
+            ("background_tasks".to_string(), "passed".to_string()),
+            ("outbound_http".to_string(), "passed".to_string()),
+        ]
+    );
+
+    // Rien n'est conservé : ni l'écriture annulée, ni l'entrée témoin du cache
+    let events: i64 = sqlx::query_scalar("SELECT count(*) FROM app_events WHERE kind = 'selftest'")
+        .fetch_one(app.db.pool())
+        .await
+        .unwrap();
+    assert_eq!(events, 0);
+    assert!(app.state.response_cache.is_empty());
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_selftest_fails_on_outbound_error() {
+    let mock = MockHttp::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(502))
+        .mount(mock.server())
+        .await;
+    let app = TestApp::builder()
+        .configure(|config| {
+            mock.redirect(config);
+            config.cache.max_entries = 0;
+        })
+        .spawn()
+        .await;
+
+    let response = app.as_admin().post("/api/help/selftest", serde_json::json!({})).await;
+    response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let report = response.json();
+    assert_eq!(report["data"]["passed"], false);
+    let outcomes = outcomes(&report);
+    assert_eq!(outcomes[2], ("cache_read_write".to_string(), "skipped".to_string()));
+    assert_eq!(outcomes[3], ("background_tasks".to_string(), "skipped".to_string()));
+    assert_eq!(outcomes[4], ("outbound_http".to_string(), "failed".to_string()));
+    assert!(report["data"]["steps"][4]["message"].as_str().unwrap().contains("502"));
+
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_selftest_requires_admin() {
+    let app = TestApp::spawn().await;
+    app.client()
+        .post("/api/help/selftest", serde_json::json!({}))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    app.close().await;
+}
@@ -0,0 +1,57 @@
+use serde_json::json;
+use template_axum_sqlx_api::testing::{snapshot::redact_fields, TestApp};
+
+#[tokio::test]
+async fn test_help_info_snapshot() {
+    let app = TestApp::spawn().await;
+    let response = app.client().get("/api/help/info").await;
+    insta::assert_json_snapshot!("help_info", response.redacted_json(), { ".version" => "[version]" });
+    app.close().await;
+}
+
+#[tokio::test]
+async fn test_help_health_snapshot() {
+    let app = TestApp::spawn().await;
+    let mut body = app.client().get("/api/help/health").await.redacted_json();
+    // L'état dépend de la machine (espace disque...) : seule la forme est comparée
+    for check in body["checks"].as_array_mut().unwrap() {
+        check.as_object_mut().unwrap().remove("message");
+    }
+    insta::assert_json_snapshot!("help_health", body, {
+        ".version" => "[version]",
+        ".status" => "[status]",
+        ".system" => "[system]",
+        ".performance" => "[performance]",
+        ".checks[].status" => "[status]",
+        ".checks[].details" => "[details]",
+    });
+    app.close().await;
+}
+
+#[test]
+fn test_redaction_keeps_shape() {
+    let value = json!({
+        "data": [{ "id": 4, "user_id": 7, "name": "Ada", "created_at": "2026-10-16T12:00:00Z", "deleted_at": null }],
+        "meta": { "timestamp": "2026-10-16T12:00:00Z", "request_id": "abc", "latency_ms": 12 },
+        "token": "secret",
+    });
+    insta::assert_json_snapshot!(redact_fields(value, &[("token", "[token]")]), @r#"
+    {
+      "data": [
+        {
+          "created_at": "[timestamp]",
+          "deleted_at": null,
+          "id": "[id]",
+          "name": "Ada",
+          "user_id": "[id]"
+        }
+      ],
+      "meta": {
+        "latency_ms": "[duration]",
+        "request_id": "[trace]",
+        "timestamp": "[timestamp]"
+      },
+      "token": "[token]"
+    }
+    "#);
+}
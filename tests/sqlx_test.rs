@@ -0,0 +1,64 @@
+//! Exemples de tests `#[sqlx::test]` : chaque test reçoit une base neuve, migrée par
+//! `db::MIGRATOR`, et remplie par les scripts de `tests/fixtures/` demandés.
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+use sqlx::PgPool;
+use template_axum_sqlx_api::{
+    models::user::NewUser,
+    services::{post, user},
+    testing::{app_router, app_state, TEST_ADMIN_TOKEN},
+};
+use tower::ServiceExt;
+
+#[sqlx::test(migrator = "template_axum_sqlx_api::db::MIGRATOR")]
+async fn test_repository_on_empty_database(pool: PgPool) {
+    let new_user = NewUser { email: "ada@example.com".to_string(), name: "Ada".to_string() };
+    let created = user::create_user(&pool, &new_user).await.unwrap();
+
+    let found = user::get_user(&pool, created.id).await.unwrap().unwrap();
+    assert_eq!(found.email, "ada@example.com");
+    // Base neuve : le premier utilisateur reçoit le premier identifiant
+    assert_eq!(*created.id.get(), 1);
+}
+
+#[sqlx::test(migrator = "template_axum_sqlx_api::db::MIGRATOR", fixtures("users", "posts"))]
+async fn test_repository_with_sql_fixtures(pool: PgPool) {
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users").fetch_one(&pool).await.unwrap();
+    assert_eq!(users, 2);
+
+    let post_id = sqlx::query_scalar("SELECT id FROM posts WHERE title = 'Bienvenue'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let detail = post::get_post_detail(&pool, post_id).await.unwrap().unwrap();
+    assert_eq!(detail.author.name, "Ada Lovelace");
+    assert_eq!(detail.comments.len(), 1);
+}
+
+#[sqlx::test(migrator = "template_axum_sqlx_api::db::MIGRATOR", fixtures("users"))]
+async fn test_handler_on_sqlx_test_pool(pool: PgPool) {
+    let app = app_router(&app_state(pool));
+
+    let request = Request::builder()
+        .uri("/api/users?sort=email")
+        .header(header::AUTHORIZATION, format!("Bearer {}", TEST_ADMIN_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["items"][0]["email"], "ada@example.com");
+}
+
+#[sqlx::test(migrator = "template_axum_sqlx_api::db::MIGRATOR")]
+async fn test_state_points_to_test_database(pool: PgPool) {
+    let name = pool.connect_options().get_database().unwrap().to_string();
+    let state = app_state(pool);
+    assert!(state.config.database.url.ends_with(&name));
+}
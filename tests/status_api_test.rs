@@ -0,0 +1,91 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::status::PerformanceMetrics,
+    routes::create_router,
+    state::AppState,
+};
+
+async fn state() -> AppState {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    AppState::new(db, Config::default())
+}
+
+async fn get_status(app: Router) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::builder().uri("/api/status").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn metrics() -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+#[tokio::test]
+async fn test_status_api_serves_cached_metrics() {
+    let state = state().await;
+
+    // Avant le premier passage de la tâche de fond
+    let (status, body) = get_status(create_router(state.clone())).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["code"], "METRICS_NOT_READY");
+
+    state.metrics.publish(metrics());
+    let (status, body) = get_status(create_router(state)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["health_score"], 95);
+    assert_eq!(body["data"]["db_response_time_ms"], 2);
+    assert_eq!(body["data"]["status"], "Optimal");
+    assert_eq!(body["data"]["uptime"].as_array().unwrap().len(), 3);
+    assert_eq!(body["data"]["uptime"][0]["window"], "24h");
+}
+
+#[tokio::test]
+async fn test_app_instances_have_separate_metrics() {
+    let first = state().await;
+    let second = state().await;
+    first.metrics.publish(metrics());
+
+    let (status, _) = get_status(create_router(first)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = get_status(create_router(second)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
@@ -0,0 +1,81 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::Utc;
+use futures::StreamExt;
+use std::time::Duration;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::status::PerformanceMetrics,
+    routes::create_router,
+    state::AppState,
+};
+
+fn metrics(health_score: u8) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+#[tokio::test]
+async fn test_live_stream_pushes_current_then_new_metrics() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    let store = state.metrics.clone();
+    let app = create_router(state);
+
+    store.publish(metrics(80));
+    let response = app
+        .oneshot(Request::builder().uri("/api/status/live").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+    let mut body = response.into_body().into_data_stream();
+    let mut next_event = async || {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .expect("no event received")
+            .unwrap()
+            .unwrap();
+        String::from_utf8(chunk.to_vec()).unwrap()
+    };
+
+    // Métriques en cache à la connexion
+    let first = next_event().await;
+    assert!(first.starts_with("event: metrics\n"), "{}", first);
+    assert!(first.contains("\"health_score\":80"), "{}", first);
+
+    // Puis chaque nouvelle publication
+    store.publish(metrics(42));
+    let second = next_event().await;
+    assert!(second.contains("\"health_score\":42"), "{}", second);
+}
@@ -0,0 +1,51 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, FooterLink, StatusPageSection},
+    db::DatabaseManager,
+    routes::create_router,
+    state::AppState,
+};
+
+async fn status_page(config: Config) -> String {
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    let response = create_router(AppState::new(db, config))
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_status_page_default_branding() {
+    let page = status_page(Config::default()).await;
+
+    assert!(page.contains(r#"data-theme="retro""#));
+    assert!(page.contains("<title>Status • template-axum-sqlx-api</title>"));
+    assert!(page.contains(r#"data-lucide="activity""#));
+    assert!(!page.contains("{HIDE_"), "section placeholders left in the page");
+    assert!(!page.contains(r#"sidebar-sticky hidden"#));
+}
+
+#[tokio::test]
+async fn test_status_page_custom_branding() {
+    let mut config = Config::default();
+    config.status_page.title = Some("Acme <API>".to_string());
+    config.status_page.logo_url = Some("https://example.com/logo.svg".to_string());
+    config.status_page.theme = "corporate".to_string();
+    config.status_page.footer_links = vec![FooterLink { label: "Support".to_string(), url: "https://example.com/support".to_string() }];
+    config.status_page.sections.retain(|section| *section != StatusPageSection::Sidebar);
+
+    let page = status_page(config).await;
+
+    assert!(page.contains(r#"data-theme="corporate""#));
+    assert!(page.contains("<title>Status • Acme &lt;API&gt;</title>"), "title not escaped");
+    assert!(page.contains(r#"<img src="https://example.com/logo.svg""#));
+    assert!(page.contains(r#"<a href="https://example.com/support" class="link link-hover">Support</a>"#));
+    assert!(page.contains("sidebar-sticky hidden"));
+    assert!(!page.contains("glow-on-hover hidden"), "health section should stay visible");
+}
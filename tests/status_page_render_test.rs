@@ -0,0 +1,155 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use template_axum_sqlx_api::{
+    config::Config,
+    handlers::status::{render_status_page, StatusPageContent},
+    models::{
+        events::AppEvent,
+        help::{CpuCoreUsage, CpuUsage, DiskUsage, LogCounts},
+        status::{HistoryEntry, NetworkUsage, PerformanceMetrics, RequestCounts, RuntimeUsage, Throughput, UptimeStats},
+    },
+};
+
+// Pages de référence de `tests/snapshots/` : un changement du template ou des fonctions de
+// rendu fait échouer ces tests tant que les fichiers n'ont pas été relus et acceptés
+// (`cargo insta review`).
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+}
+
+fn metrics(health_score: u8, db_connected: bool, response_time_ms: u64) -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: now(),
+        health_score,
+        cpu_score: 22,
+        memory_score: 24,
+        perf_score: 18,
+        network_score: 20,
+        network: Some(NetworkUsage { rx_bytes_per_sec: 250_000, tx_bytes_per_sec: 125_000, errors: 0, load_percent: 12.5 }),
+        requests: Some(RequestCounts { success: 980, redirection: 5, client_errors: 10, server_errors: 5 }),
+        runtime: Some(RuntimeUsage {
+            workers: 4,
+            alive_tasks: 42,
+            global_queue_depth: 3,
+            blocking_queue_depth: None,
+            busy_percent: 37.5,
+        }),
+        caches: Vec::new(),
+        avg_response_time: response_time_ms as f64,
+        system_load: 0.4,
+        cpu_usage: 35.0,
+        cpu_count: 4,
+        memory_usage_percent: 48.0,
+        memory_used_mb: 1920,
+        memory_total_mb: 4000,
+        disk_usage_percent: 61.0,
+        uptime: 3 * 86400 + 5 * 3600 + 12 * 60,
+        response_time_ms,
+        db_connected,
+        db_response_time_ms: db_connected.then_some(4),
+        status: if db_connected { "healthy" } else { "unhealthy" }.to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+/// Une mesure par minute, la plus récente en premier, avec une panne de base de données
+fn history() -> Vec<HistoryEntry> {
+    (0..6)
+        .map(|minute| {
+            let db_connected = minute != 3;
+            HistoryEntry {
+                timestamp: now() - Duration::minutes(minute),
+                response_time_ms: 20 + minute as u64 * 40,
+                db_connected,
+                db_response_time_ms: db_connected.then_some(3),
+                status: if db_connected { "healthy" } else { "unhealthy" }.to_string(),
+                issues: if db_connected { Vec::new() } else { vec!["Database unreachable".to_string()] },
+                network: Some(NetworkUsage {
+                    rx_bytes_per_sec: 100_000 * minute as u64,
+                    tx_bytes_per_sec: 50_000,
+                    errors: 0,
+                    load_percent: 5.0,
+                }),
+            }
+        })
+        .collect()
+}
+
+fn data(metrics: Option<PerformanceMetrics>) -> StatusPageContent {
+    StatusPageContent {
+        now: now(),
+        history: if metrics.is_some() { history() } else { Vec::new() },
+        metrics,
+        events: vec![AppEvent {
+            id: 1.into(),
+            kind: "deploy".to_string(),
+            message: "Deployed version <next>".to_string(),
+            details: serde_json::json!({}),
+            occurred_at: now() - Duration::hours(2),
+        }],
+        uptime: ["24h", "7d", "30d"]
+            .into_iter()
+            .map(|window| UptimeStats {
+                window: window.to_string(),
+                samples: 1440,
+                uptime_percent: Some(99.93),
+                avg_response_time_ms: Some(23.4),
+            })
+            .collect(),
+        throughput: Throughput { current_rps: 12.5, peak_rps: 48 },
+        log_counts: LogCounts { warnings: 3, errors: 1 },
+        disks: vec![DiskUsage {
+            mount_point: "/".to_string(),
+            file_system: "ext4".to_string(),
+            total_bytes: 100_000_000_000,
+            available_bytes: 39_000_000_000,
+            usage_percent: 61.0,
+        }],
+        cpu: CpuUsage {
+            average_percent: 35.0,
+            max_percent: 72.0,
+            cores: vec![
+                CpuCoreUsage { name: "cpu0".to_string(), usage_percent: 72.0 },
+                CpuCoreUsage { name: "cpu1".to_string(), usage_percent: 12.0 },
+            ],
+        },
+        ..StatusPageContent::default()
+    }
+}
+
+/// Page rendue, sans le numéro de version du paquet
+fn render(data: &StatusPageContent) -> String {
+    render_status_page(data, &Config::default()).replace(env!("CARGO_PKG_VERSION"), "[version]")
+}
+
+#[test]
+fn test_status_page_healthy_snapshot() {
+    insta::assert_snapshot!("status_page_healthy", render(&data(Some(metrics(94, true, 23)))));
+}
+
+#[test]
+fn test_status_page_degraded_snapshot() {
+    insta::assert_snapshot!("status_page_degraded", render(&data(Some(metrics(45, false, 640)))));
+}
+
+#[test]
+fn test_status_page_starting_snapshot() {
+    insta::assert_snapshot!("status_page_starting", render(&data(None)));
+}
+
+#[test]
+fn test_status_page_rendering_is_deterministic() {
+    let data = data(Some(metrics(94, true, 23)));
+    let page = render(&data);
+    assert_eq!(page, render(&data));
+    // Aucun marqueur `{NOM}` du template ne reste sans valeur
+    let left: Vec<&str> = page
+        .match_indices('{')
+        .filter_map(|(start, _)| {
+            let name = &page[start + 1..];
+            let len = name.find(|c: char| !(c.is_ascii_uppercase() || c == '_')).unwrap_or(name.len());
+            (len > 0 && name[len..].starts_with('}')).then(|| &name[..len])
+        })
+        .collect();
+    assert!(left.is_empty(), "placeholders left in the page: {:?}", left);
+}
@@ -0,0 +1,82 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    handlers::stream::{csv_line, ndjson},
+    models::events::EventKind,
+    routes::create_router,
+    services::events::record_event,
+    state::AppState,
+};
+
+#[test]
+fn test_csv_line_escaping() {
+    assert_eq!(csv_line(["a", "b c", "1"]), "a,b c,1\r\n");
+    assert_eq!(csv_line(["x,y", "say \"hi\"", "multi\nline"]), "\"x,y\",\"say \"\"hi\"\"\",\"multi\nline\"\r\n");
+    assert_eq!(csv_line(["=SUM(A1)"]), "'=SUM(A1)\r\n");
+}
+
+#[tokio::test]
+async fn test_ndjson_writes_one_object_per_line() {
+    let rows = futures::stream::iter(vec![
+        Ok::<_, std::io::Error>(serde_json::json!({ "id": 1 })),
+        Ok(serde_json::json!({ "id": 2 })),
+    ]);
+    let response = ndjson(rows);
+
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/x-ndjson");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"{\"id\":1}\n{\"id\":2}\n");
+}
+
+#[tokio::test]
+async fn test_export_events_as_ndjson_and_csv() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let marker = format!("export-test-{}", uuid::Uuid::new_v4());
+    for i in 0..3 {
+        record_event(db.get_pool(), EventKind::Maintenance, &format!("{} #{}", marker, i), serde_json::json!({}))
+            .await
+            .unwrap();
+    }
+    let app = create_router(AppState::new(db, Config::default()));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/status/events/export?search={}&sort=occurred_at", marker))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let messages: Vec<String> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["message"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(messages, (0..3).map(|i| format!("{} #{}", marker, i)).collect::<Vec<_>>());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/status/events/export?format=csv&search={}", marker))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let csv = String::from_utf8(body.to_vec()).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("id,kind,message,details,occurred_at"));
+    assert_eq!(lines.count(), 3);
+}
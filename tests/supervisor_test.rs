@@ -0,0 +1,110 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use template_axum_sqlx_api::{
+    models::help::{HealthStatus, TaskHealth, TaskState},
+    services::{
+        health::{BackgroundTasksCheck, HealthRegistry},
+        supervisor::Supervisor,
+    },
+};
+
+/// Attend que la tâche atteigne l'état attendu
+async fn wait_for(supervisor: &Supervisor, condition: impl Fn(&TaskHealth) -> bool) -> TaskHealth {
+    for _ in 0..100 {
+        if let Some(task) = supervisor.report().into_iter().next().filter(|task| condition(task)) {
+            return task;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("task did not reach the expected state: {:?}", supervisor.report());
+}
+
+#[tokio::test]
+async fn test_panicking_task_is_restarted() {
+    let supervisor = Supervisor::new();
+    let starts = Arc::new(AtomicU32::new(0));
+
+    let counter = starts.clone();
+    supervisor.spawn("flaky", move |task| {
+        let start = counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            if start == 0 {
+                panic!("boom");
+            }
+            loop {
+                task.record(Ok::<(), String>(()), Duration::from_secs(60));
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        }
+    });
+
+    let task = wait_for(&supervisor, |task| task.runs > 0).await;
+    assert_eq!(task.name, "flaky");
+    assert_eq!(task.state, TaskState::Running);
+    assert_eq!(task.restarts, 1);
+    assert_eq!(task.last_error.as_deref(), Some("panicked: boom"));
+    assert!(task.next_run.unwrap() > task.last_run.unwrap());
+    assert_eq!(starts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_failed_runs_are_counted() {
+    let supervisor = Supervisor::new();
+    supervisor.spawn("failing", |task| async move {
+        task.record(Err("database unavailable"), Duration::from_secs(60));
+        task.record(Ok::<(), &str>(()), Duration::from_secs(60));
+        std::future::pending::<()>().await;
+    });
+
+    let task = wait_for(&supervisor, |task| task.runs == 2).await;
+    assert_eq!(task.failures, 1);
+    assert_eq!(task.restarts, 0);
+    assert_eq!(task.last_error.as_deref(), Some("database unavailable"));
+}
+
+#[tokio::test]
+async fn test_health_reports_restarting_tasks() {
+    let supervisor = Arc::new(Supervisor::new());
+    let registry = HealthRegistry::new(Duration::from_secs(1)).register(BackgroundTasksCheck::new(supervisor.clone()));
+
+    let results = registry.run(false).await;
+    assert_eq!(results[0].status, HealthStatus::Healthy);
+
+    supervisor.spawn("crashing", |_task| async move { panic!("always") });
+    wait_for(&supervisor, |task| task.state == TaskState::Restarting).await;
+
+    let results = registry.run(false).await;
+    assert_eq!(results[0].name, "background_tasks");
+    assert!(!results[0].critical);
+    assert_eq!(results[0].status, HealthStatus::Degraded);
+    assert_eq!(results[0].message.as_deref(), Some("crashing is restarting"));
+    assert_eq!(results[0].details.as_ref().unwrap()["tasks"][0]["restarts"], 1);
+}
+
+#[test]
+fn test_task_is_overdue_after_a_missed_interval() {
+    let now = Utc::now();
+    let task = TaskHealth {
+        name: "metrics".to_string(),
+        schedule: None,
+        state: TaskState::Running,
+        last_run: Some(now - ChronoDuration::seconds(400)),
+        next_run: Some(now - ChronoDuration::seconds(100)),
+        runs: 1,
+        failures: 0,
+        restarts: 0,
+        last_error: None,
+    };
+    // Passage prévu il y a 100 s, intervalle de 300 s : encore dans la marge
+    assert!(!task.is_overdue(now));
+    assert!(task.is_overdue(now + ChronoDuration::seconds(250)));
+
+    let never_run = TaskHealth { last_run: None, next_run: None, ..task };
+    assert!(!never_run.is_overdue(now));
+}
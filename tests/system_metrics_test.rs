@@ -0,0 +1,94 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    models::status::MetricsStore,
+    routes::create_router,
+    state::AppState,
+};
+
+#[test]
+fn test_system_metrics_are_measured_without_background_task() {
+    let store = MetricsStore::new(10);
+
+    // Avant le premier passage de la tâche de fond, une mesure est prise à la demande
+    let system = store.system();
+    assert!(system.cpu_count > 0);
+    assert!(system.memory_total_mb > 0);
+    assert!((0.0..=100.0).contains(&system.disk_usage_percent));
+}
+
+#[test]
+fn test_system_metrics_serve_the_last_refresh() {
+    let store = MetricsStore::new(10);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    let refreshed = store.refresh_system();
+
+    let cached = store.system();
+    assert_eq!(cached.cpu_usage, refreshed.cpu_usage);
+    assert_eq!(cached.memory_used_mb, refreshed.memory_used_mb);
+    assert_eq!(cached.uptime, refreshed.uptime);
+    assert!((0.0..=100.0).contains(&cached.cpu_usage));
+}
+
+#[test]
+fn test_disk_usage_is_reported_per_mount() {
+    let system = MetricsStore::new(10).system();
+
+    // L'occupation globale est celle du montage le plus plein
+    let fullest = system.disks.iter().map(|disk| disk.usage_percent).fold(0.0, f32::max);
+    assert_eq!(system.disk_usage_percent, fullest);
+    for disk in &system.disks {
+        assert!(disk.total_bytes > 0, "{} has no capacity", disk.mount_point);
+        assert!((0.0..=100.0).contains(&disk.usage_percent));
+    }
+}
+
+#[test]
+fn test_disk_mounts_can_be_filtered() {
+    let all = MetricsStore::new(10).system();
+    let Some(first) = all.disks.first() else { return };
+
+    let filtered = MetricsStore::new(10).with_disk_mounts(vec![first.mount_point.clone()]).system();
+    assert_eq!(filtered.disks.len(), 1);
+    assert_eq!(filtered.disks[0].mount_point, first.mount_point);
+
+    let none = MetricsStore::new(10).with_disk_mounts(vec!["/no/such/mount".to_string()]).system();
+    assert!(none.disks.is_empty());
+    assert_eq!(none.disk_usage_percent, 0.0);
+}
+
+#[test]
+fn test_cpu_usage_is_reported_per_core() {
+    let store = MetricsStore::new(10);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    let system = store.refresh_system();
+
+    let cpu = store.cpu();
+    assert_eq!(cpu.cores.len(), system.cpu_count);
+    assert_eq!(cpu.average_percent, system.cpu_usage);
+    for core in &cpu.cores {
+        assert!(core.usage_percent <= cpu.max_percent);
+    }
+    assert!(cpu.max_percent >= cpu.average_percent);
+}
+
+#[tokio::test]
+async fn test_cpu_endpoint() {
+    let app = create_router(AppState::new(DatabaseManager::new(), Config::default()));
+    let response = app
+        .oneshot(Request::builder().uri("/api/help/system/cpu").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let cpu: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(!cpu["data"]["cores"].as_array().unwrap().is_empty());
+    assert!(cpu["data"]["cores"][0]["name"].is_string());
+    assert!(cpu["data"]["max_percent"].is_number());
+}
@@ -0,0 +1,88 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::{logging::setup_middleware, trace::TRACEPARENT},
+    routes::create_router,
+    state::AppState,
+    telemetry,
+};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+/// Collecteur OTLP/HTTP factice : conserve les corps reçus
+async fn start_collector() -> (String, Arc<Mutex<Vec<Bytes>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route(
+            "/v1/traces",
+            post(|State(received): State<Arc<Mutex<Vec<Bytes>>>>, body: Bytes| async move {
+                received.lock().unwrap().push(body);
+                StatusCode::OK
+            }),
+        )
+        .with_state(received.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (endpoint, received)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let config = Config::default();
+    assert!(telemetry::layer::<Registry>(&config).unwrap().is_none());
+    assert!(telemetry::link(&tracing::Span::none(), None).is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_exports_request_and_query_spans() {
+    let (endpoint, received) = start_collector().await;
+
+    let mut config = Config::default();
+    config.telemetry.enabled = true;
+    config.telemetry.endpoint = endpoint;
+    config.telemetry.service_name = "telemetry-test".to_string();
+
+    let layer = telemetry::layer::<Registry>(&config).unwrap().unwrap();
+    let subscriber = Registry::default().with(layer);
+    // Abonné global : les requêtes SQL peuvent s'exécuter sur n'importe quel thread du runtime
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    let app = setup_middleware(create_router(AppState::new(db, config)));
+
+    let request = Request::get(format!("/api/uploads/{}", uuid::Uuid::new_v4()))
+        .header(TRACEPARENT, format!("00-{}-00f067aa0ba902b7-01", TRACE_ID))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let traceparent = response.headers()[TRACEPARENT].to_str().unwrap();
+    assert!(traceparent.starts_with(&format!("00-{}-", TRACE_ID)));
+    assert!(!traceparent.contains("00f067aa0ba902b7"));
+
+    tokio::task::spawn_blocking(telemetry::shutdown).await.unwrap();
+
+    let exported = received.lock().unwrap().concat();
+    assert!(contains(&exported, b"telemetry-test"));
+    assert!(contains(&exported, b"request"));
+    assert!(contains(&exported, b"db.query"));
+    assert!(contains(&exported, b"SELECT * FROM uploads WHERE id = $1"));
+    assert!(contains(&exported, &hex::decode(TRACE_ID).unwrap()));
+}
@@ -0,0 +1,20 @@
+//! Exécuté avec `cargo test --features testcontainers` (Docker requis)
+#![cfg(feature = "testcontainers")]
+
+use axum::http::StatusCode;
+use template_axum_sqlx_api::testing::{container::postgres_url, TestApp};
+
+#[tokio::test]
+async fn test_app_runs_on_postgres_container() {
+    let app = TestApp::spawn().await;
+
+    // Base jetable créée sur le conteneur, migrations appliquées
+    let url = postgres_url().await;
+    let server = url.rsplit_once('/').unwrap().0;
+    assert!(app.db.config().database.url.starts_with(server));
+    let version: String = sqlx::query_scalar("SHOW server_version").fetch_one(app.db.pool()).await.unwrap();
+    assert!(version.starts_with("16"), "{}", version);
+
+    app.as_admin().get("/api/users").await.assert_status(StatusCode::OK);
+    app.close().await;
+}
@@ -0,0 +1,129 @@
+use axum::http::StatusCode;
+use serde_json::json;
+use template_axum_sqlx_api::testing::{TestApp, TestDatabase};
+
+async fn database_exists(db: &TestDatabase, name: &str) -> bool {
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)")
+        .bind(name)
+        .fetch_one(db.pool())
+        .await
+        .unwrap()
+}
+
+async fn count_users(db: &TestDatabase) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM users").fetch_one(db.pool()).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_databases_are_isolated() {
+    let (first, second) = tokio::join!(TestDatabase::new(), TestDatabase::new());
+    assert_ne!(first.name(), second.name());
+
+    // Base migrée et vide, indépendante de la base partagée
+    assert_eq!(count_users(&first).await, 0);
+    sqlx::query("INSERT INTO users (email, name) VALUES ('a@example.com', 'A')")
+        .execute(first.pool())
+        .await
+        .unwrap();
+    assert_eq!(count_users(&first).await, 1);
+    assert_eq!(count_users(&second).await, 0);
+
+    // La configuration pointe vers la base jetable
+    assert!(first.config().database.url.ends_with(first.name()));
+
+    let name = first.name().to_string();
+    first.close().await;
+    assert!(!database_exists(&second, &name).await);
+    second.close().await;
+}
+
+#[tokio::test]
+async fn test_database_is_dropped_without_close() {
+    let observer = TestDatabase::new().await;
+    let name = {
+        let db = TestDatabase::new().await;
+        assert!(database_exists(&observer, db.name()).await);
+        db.name().to_string()
+    };
+    assert!(!database_exists(&observer, &name).await);
+    observer.close().await;
+}
+
+#[tokio::test]
+async fn test_app_state_runs_on_test_database() {
+    use axum::{body::Body, http::Request};
+    use template_axum_sqlx_api::{routes::create_router, state::AppState};
+    use tower::ServiceExt;
+
+    let db = TestDatabase::new().await;
+    let mut config = db.config();
+    config.admin.token = Some("secret".to_string());
+    let app = create_router(AppState::new(db.manager(), config));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users")
+                .header("Authorization", "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["total"], 0);
+    db.close().await;
+}
+
+#[tokio::test]
+async fn test_app_serves_full_router() {
+    let app = TestApp::builder().configure(|config| config.api.max_per_page = 5).spawn().await;
+
+    // Middlewares appliqués comme en production
+    let response = app.client().get("/api/help/live").await;
+    response.assert_status(StatusCode::OK);
+    assert!(response.header("x-request-id").is_some());
+
+    app.client().get("/api/users").await.assert_status(StatusCode::UNAUTHORIZED);
+    app.as_bearer("wrong").get("/api/users").await.assert_status(StatusCode::UNAUTHORIZED);
+
+    let created = app.as_admin().post("/api/users", json!({ "email": "ada@example.com", "name": "Ada" })).await;
+    created
+        .assert_status(StatusCode::CREATED)
+        .assert_json_includes(json!({ "data": { "email": "ada@example.com", "name": "Ada" } }));
+    let id = created.json()["data"]["id"].as_i64().unwrap();
+
+    app.as_admin()
+        .get("/api/users?per_page=100")
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_json_includes(json!({ "total": 1, "per_page": 5 }));
+
+    app.as_admin()
+        .post("/api/users", json!({ "email": "ada@example.com", "name": "Ada" }))
+        .await
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_code("EMAIL_ALREADY_USED");
+
+    app.as_admin().delete(&format!("/api/users/{}", id)).await.assert_status(StatusCode::NO_CONTENT);
+
+    // Chaque application a sa propre base
+    let other = TestApp::spawn().await;
+    assert_ne!(other.address, app.address);
+    other.as_admin().get("/api/users").await.assert_json_includes(json!({ "total": 0 }));
+
+    other.close().await;
+    app.close().await;
+}
+
+#[tokio::test]
+#[should_panic(expected = "JSON mismatch at /data/name")]
+async fn test_json_assertion_reports_path() {
+    let app = TestApp::spawn().await;
+    app.as_admin()
+        .post("/api/users", json!({ "email": "bob@example.com", "name": "Bob" }))
+        .await
+        .assert_json_includes(json!({ "data": { "name": "Robert" } }));
+}
@@ -0,0 +1,90 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::Utc;
+use serde_json::Value;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    middleware::status_codes::StatusCounters,
+    models::status::PerformanceMetrics,
+    routes::create_router,
+    state::AppState,
+};
+
+fn metrics() -> PerformanceMetrics {
+    PerformanceMetrics {
+        timestamp: Utc::now(),
+        health_score: 95,
+        cpu_score: 25,
+        memory_score: 25,
+        perf_score: 20,
+        network_score: 25,
+        network: None,
+        requests: None,
+        runtime: None,
+        caches: Vec::new(),
+        avg_response_time: 12.0,
+        system_load: 0.2,
+        cpu_usage: 10.0,
+        cpu_count: 4,
+        memory_usage_percent: 30.0,
+        memory_used_mb: 1200,
+        memory_total_mb: 4000,
+        disk_usage_percent: 40.0,
+        uptime: 3600,
+        response_time_ms: 12,
+        db_connected: true,
+        db_response_time_ms: Some(2),
+        status: "Optimal".to_string(),
+        minimal_waittime: 30,
+    }
+}
+
+#[test]
+fn test_throughput_is_averaged_over_the_window() {
+    let counters = StatusCounters::new();
+    assert_eq!(counters.throughput().current_rps, 0.0);
+    assert_eq!(counters.throughput().peak_rps, 0);
+
+    for _ in 0..30 {
+        counters.record(StatusCode::OK);
+    }
+
+    let throughput = counters.throughput();
+    assert_eq!(throughput.current_rps, 3.0);
+    // Les 30 réponses tombent dans une ou deux secondes
+    assert!(throughput.peak_rps >= 15 && throughput.peak_rps <= 30);
+}
+
+#[tokio::test]
+async fn test_status_reports_throughput() {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    let state = AppState::new(db, Config::default());
+    state.metrics.publish(metrics());
+
+    for _ in 0..4 {
+        let request = Request::builder().uri("/api/help/ping").body(Body::empty()).unwrap();
+        create_router(state.clone()).oneshot(request).await.unwrap();
+    }
+
+    let response = create_router(state.clone())
+        .oneshot(Request::builder().uri("/api/status").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    // La réponse en cours n'est pas encore comptée
+    assert_eq!(body["data"]["throughput"]["current_rps"], 0.4);
+    assert!(body["data"]["throughput"]["peak_rps"].as_u64().unwrap() >= 2);
+
+    let response = create_router(state)
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let page = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains("0.5 req/s • pic"), "throughput not rendered");
+}
@@ -0,0 +1,95 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{HeaderMap, Request},
+    middleware::from_fn,
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+use template_axum_sqlx_api::middleware::trace::{inject, propagate_trace, TraceContext, TRACEPARENT};
+
+const PARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+/// Renvoie le `traceparent` reçu par un service aval appelé avec `inject`
+async fn call_downstream() -> String {
+    let downstream = Router::new().route(
+        "/",
+        get(|headers: HeaderMap| async move { headers[TRACEPARENT].to_str().unwrap().to_string() }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, downstream).await.unwrap() });
+
+    inject(reqwest::Client::new().get(format!("http://{}/", addr)))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap()
+}
+
+fn create_app() -> Router {
+    Router::new()
+        .route("/current", get(|| async { TraceContext::current().unwrap().to_header() }))
+        .route("/downstream", get(call_downstream))
+        .layer(from_fn(propagate_trace))
+}
+
+async fn send(uri: &str, traceparent: Option<&str>) -> (Option<String>, String) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(traceparent) = traceparent {
+        request = request.header(TRACEPARENT, traceparent);
+    }
+    let response = create_app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let header = response
+        .headers()
+        .get(TRACEPARENT)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (header, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[test]
+fn test_parse_traceparent() {
+    let context = TraceContext::parse(PARENT).unwrap();
+    assert_eq!(context.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(context.span_id_hex(), "00f067aa0ba902b7");
+    assert!(context.sampled);
+    assert_eq!(context.to_header(), PARENT);
+
+    assert!(TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01").is_none());
+    assert!(TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").is_none());
+    assert!(TraceContext::parse("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01").is_none());
+    assert!(TraceContext::parse("garbage").is_none());
+}
+
+#[tokio::test]
+async fn test_incoming_trace_is_continued() {
+    let (header, current) = send("/current", Some(PARENT)).await;
+    let header = TraceContext::parse(&header.unwrap()).unwrap();
+    let parent = TraceContext::parse(PARENT).unwrap();
+
+    assert_eq!(header.trace_id, parent.trace_id);
+    assert_ne!(header.span_id, parent.span_id);
+    assert_eq!(TraceContext::parse(&current).unwrap(), header);
+}
+
+#[tokio::test]
+async fn test_new_trace_without_or_with_invalid_header() {
+    let (header, _) = send("/current", None).await;
+    assert!(TraceContext::parse(&header.unwrap()).is_some());
+
+    let (header, _) = send("/current", Some("invalid")).await;
+    assert!(TraceContext::parse(&header.unwrap()).is_some());
+}
+
+#[tokio::test]
+async fn test_outbound_requests_carry_child_context() {
+    let (header, downstream) = send("/downstream", Some(PARENT)).await;
+    let request_context = TraceContext::parse(&header.unwrap()).unwrap();
+    let downstream = TraceContext::parse(&downstream).unwrap();
+
+    assert_eq!(downstream.trace_id, request_context.trace_id);
+    assert_ne!(downstream.span_id, request_context.span_id);
+}
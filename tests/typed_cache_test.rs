@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use template_axum_sqlx_api::{
+    cache::CacheRegistry,
+    config::{CacheConfig, CacheNamespaceConfig},
+    models::status::CacheStats,
+};
+
+fn registry(namespaces: &[(&str, CacheNamespaceConfig)]) -> CacheRegistry {
+    CacheRegistry::new(&CacheConfig {
+        namespaces: namespaces.iter().map(|(name, config)| (name.to_string(), config.clone())).collect::<HashMap<_, _>>(),
+        ..CacheConfig::default()
+    })
+}
+
+#[tokio::test]
+async fn test_get_or_compute_counts_hits_and_coalesces_misses() {
+    let caches = registry(&[]);
+    let users = caches.namespace::<i64, String>("users");
+    let computed = Arc::new(AtomicUsize::new(0));
+
+    // Dix lectures concurrentes d'une clé absente : un seul calcul
+    let reads = (0..10).map(|_| {
+        let users = users.clone();
+        let computed = computed.clone();
+        async move {
+            users
+                .get_or_compute(42, async move {
+                    computed.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    "Ada".to_string()
+                })
+                .await
+        }
+    });
+    let values = futures::future::join_all(reads).await;
+    assert!(values.iter().all(|value| value == "Ada"));
+    assert_eq!(computed.load(Ordering::SeqCst), 1);
+
+    assert_eq!(users.get(&42).await.as_deref(), Some("Ada"));
+    assert_eq!(users.get(&7).await, None);
+    let stats = users.stats().await;
+    assert_eq!((stats.entries, stats.hits, stats.misses), (1, 10, 2));
+
+    // Même espace de noms : mêmes entrées
+    users.invalidate(&42).await;
+    assert_eq!(caches.namespace::<i64, String>("users").get(&42).await, None);
+}
+
+#[tokio::test]
+async fn test_try_get_or_compute_keeps_only_successes() {
+    let caches = registry(&[]);
+    let lookups = caches.namespace::<String, u32>("lookups");
+
+    let failed: Result<u32, String> = lookups.try_get_or_compute("a".to_string(), async { Err("backend down".to_string()) }).await;
+    assert_eq!(failed.unwrap_err(), "backend down");
+    assert_eq!(lookups.get(&"a".to_string()).await, None);
+
+    assert_eq!(lookups.try_get_or_compute("a".to_string(), async { Ok::<_, String>(1) }).await, Ok(1));
+    // Valeur en cache : le calcul n'est pas attendu
+    assert_eq!(lookups.try_get_or_compute("a".to_string(), async { Err("unused".to_string()) }).await, Ok(1));
+}
+
+#[tokio::test]
+async fn test_namespaces_apply_their_configuration() {
+    let caches = registry(&[
+        ("short", CacheNamespaceConfig { ttl_seconds: 1, ..CacheNamespaceConfig::default() }),
+        ("idle", CacheNamespaceConfig { ttl_seconds: 0, tti_seconds: 1, ..CacheNamespaceConfig::default() }),
+        ("small", CacheNamespaceConfig { max_entries: 10, ..CacheNamespaceConfig::default() }),
+    ]);
+
+    let short = caches.namespace::<u32, u32>("short");
+    let idle = caches.namespace::<u32, u32>("idle");
+    short.insert(1, 1).await;
+    idle.insert(1, 1).await;
+    idle.insert(2, 2).await;
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        // Lue régulièrement, l'entrée reste ; l'autre expire faute d'accès
+        assert_eq!(idle.get(&1).await, Some(1));
+    }
+    assert_eq!(short.get(&1).await, None);
+    assert_eq!(idle.get(&2).await, None);
+
+    let small = caches.namespace::<u32, u32>("small");
+    for key in 0..100 {
+        small.insert(key, key).await;
+    }
+    assert!(small.stats().await.entries <= 10);
+
+    // Espace non configuré : valeurs par défaut
+    let other = caches.namespace::<u32, u32>("other");
+    other.insert(1, 1).await;
+    assert!(caches.invalidate_all("other"));
+    assert!(!caches.invalidate_all("missing"));
+    assert_eq!(other.get(&1).await, None);
+    assert_eq!(caches.names(), ["idle", "other", "short", "small"]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "cache namespace users already exists with other key or value types")]
+async fn test_namespace_types_cannot_change() {
+    let caches = registry(&[]);
+    caches.namespace::<i64, String>("users");
+    caches.namespace::<String, String>("users");
+}
+
+#[tokio::test]
+async fn test_stats_since_previous_sample() {
+    let caches = registry(&[]);
+    let users = caches.namespace::<i64, i64>("users");
+    let posts = caches.namespace::<i64, i64>("posts");
+
+    users.get_or_compute(1, async { 1 }).await;
+    let previous = caches.stats().await;
+    users.get(&1).await;
+    users.get(&1).await;
+    users.get(&2).await;
+    posts.get(&1).await;
+
+    let interval: Vec<CacheStats> = caches.stats().await.iter().map(|stats| stats.since(&previous)).collect();
+    assert_eq!(interval.iter().map(|stats| stats.namespace.as_str()).collect::<Vec<_>>(), ["posts", "users"]);
+    assert_eq!((interval[1].entries, interval[1].hits, interval[1].misses), (1, 2, 1));
+    assert!((interval[1].hit_percent() - 66.7).abs() < 0.1);
+    assert_eq!((interval[0].hits, interval[0].misses), (0, 1));
+    assert_eq!(CacheStats::default().hit_percent(), 0.0);
+}
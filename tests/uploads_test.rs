@@ -0,0 +1,281 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use bytes::Bytes;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::Config,
+    db::DatabaseManager,
+    extractors::multipart::{parse_boundary, MultipartStream, PartHeaders},
+    routes::create_router,
+    state::AppState,
+};
+
+const BOUNDARY: &str = "----test-boundary";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nfake image data";
+
+fn form(field: &str, filename: &str, content_type: &str, content: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(format!("--{}\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHoliday\r\n", BOUNDARY).as_bytes());
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            BOUNDARY, field, filename, content_type
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+async fn create_app(storage: &tempfile::TempDir, max_size_bytes: u64) -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+    config.uploads.storage_path = storage.path().to_path_buf();
+    config.uploads.max_size_bytes = max_size_bytes;
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    create_router(AppState::new(db, config))
+}
+
+fn upload_request(body: Vec<u8>, token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/api/uploads")
+        .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", BOUNDARY));
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    builder.body(Body::from(body)).unwrap()
+}
+
+async fn json(response: axum::response::Response) -> serde_json::Value {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn test_parse_boundary() {
+    assert_eq!(parse_boundary("multipart/form-data; boundary=abc").as_deref(), Some("abc"));
+    assert_eq!(parse_boundary("multipart/form-data; boundary=\"a b\"").as_deref(), Some("a b"));
+    assert_eq!(parse_boundary("application/json"), None);
+}
+
+#[tokio::test]
+async fn test_multipart_stream_handles_tiny_chunks() {
+    let body = form("file", "photo.png", "image/png", PNG);
+    let chunks = body.into_iter().map(|byte| Ok::<_, std::io::Error>(Bytes::from(vec![byte])));
+    let stream = Body::from_stream(futures::stream::iter(chunks)).into_data_stream();
+    let mut multipart = MultipartStream::new(BOUNDARY, stream);
+
+    let title = multipart.next_part().await.unwrap().unwrap();
+    assert_eq!(title.name.as_deref(), Some("title"));
+
+    let file = multipart.next_part().await.unwrap().unwrap();
+    assert_eq!(
+        file,
+        PartHeaders {
+            name: Some("file".to_string()),
+            filename: Some("photo.png".to_string()),
+            content_type: Some("image/png".to_string()),
+        }
+    );
+    let mut content = Vec::new();
+    while let Some(chunk) = multipart.chunk().await.unwrap() {
+        content.extend_from_slice(&chunk);
+    }
+    assert_eq!(content, PNG);
+
+    assert!(multipart.next_part().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_upload_download_and_delete() {
+    let storage = tempfile::tempdir().unwrap();
+    let app = create_app(&storage, 1024).await;
+
+    let response = app
+        .clone()
+        .oneshot(upload_request(form("file", "../photo.png", "image/png", PNG), Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let upload = json(response).await;
+    assert_eq!(upload["filename"], "photo.png");
+    assert_eq!(upload["size_bytes"], PNG.len());
+    assert!(upload.get("storage_key").is_none());
+    let id = upload["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(format!("/api/uploads/{}/download", id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+    assert_eq!(response.headers()[header::CONTENT_DISPOSITION], "attachment; filename=\"photo.png\"");
+    let content = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&content[..], PNG);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/api/uploads/{}", id))
+                .header(header::AUTHORIZATION, "Bearer secret")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(std::fs::read_dir(storage.path()).unwrap().next().is_none());
+
+    let response = app
+        .oneshot(Request::builder().uri(format!("/api/uploads/{}", id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+fn download_request(id: &str, headers: &[(header::HeaderName, &str)]) -> Request<Body> {
+    let mut builder = Request::builder().uri(format!("/api/uploads/{}/download", id));
+    for (name, value) in headers {
+        builder = builder.header(name, *value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_download_ranges_and_conditional_requests() {
+    let storage = tempfile::tempdir().unwrap();
+    let app = create_app(&storage, 1024).await;
+
+    let response = app
+        .clone()
+        .oneshot(upload_request(form("file", "photo.png", "image/png", PNG), Some("secret")))
+        .await
+        .unwrap();
+    let upload = json(response).await;
+    let id = upload["id"].as_str().unwrap();
+    let etag = format!("\"{}\"", upload["sha256"].as_str().unwrap());
+
+    let response = app.clone().oneshot(download_request(id, &[])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    let last_modified = response.headers()[header::LAST_MODIFIED].to_str().unwrap().to_string();
+
+    // Reprise à partir d'un octet, puis suffixe
+    let response = app.clone().oneshot(download_request(id, &[(header::RANGE, "bytes=8-")])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes 8-{}/{}", PNG.len() - 1, PNG.len()));
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], (PNG.len() - 8).to_string());
+    assert_eq!(&to_bytes(response.into_body(), usize::MAX).await.unwrap()[..], &PNG[8..]);
+
+    let response = app.clone().oneshot(download_request(id, &[(header::RANGE, "bytes=-4")])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(&to_bytes(response.into_body(), usize::MAX).await.unwrap()[..], &PNG[PNG.len() - 4..]);
+
+    let response = app.clone().oneshot(download_request(id, &[(header::RANGE, "bytes=1000-")])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], format!("bytes */{}", PNG.len()));
+    assert_eq!(json(response).await["code"], "RANGE_NOT_SATISFIABLE");
+
+    // Plusieurs plages, ou un `If-Range` périmé : le fichier entier est servi
+    for headers in [
+        [(header::RANGE, "bytes=0-1,4-5"), (header::IF_RANGE, etag.as_str())],
+        [(header::RANGE, "bytes=0-1"), (header::IF_RANGE, "\"stale\"")],
+    ] {
+        let response = app.clone().oneshot(download_request(id, &headers)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(&to_bytes(response.into_body(), usize::MAX).await.unwrap()[..], PNG);
+    }
+
+    let response = app
+        .clone()
+        .oneshot(download_request(id, &[(header::RANGE, "bytes=0-3"), (header::IF_RANGE, etag.as_str())]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(&to_bytes(response.into_body(), usize::MAX).await.unwrap()[..], &PNG[..4]);
+
+    // Requêtes conditionnelles
+    for headers in [[(header::IF_NONE_MATCH, etag.as_str())], [(header::IF_MODIFIED_SINCE, last_modified.as_str())]] {
+        let response = app.clone().oneshot(download_request(id, &headers)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(to_bytes(response.into_body(), usize::MAX).await.unwrap().is_empty());
+    }
+    let response = app.oneshot(download_request(id, &[(header::IF_NONE_MATCH, "\"other\"")])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_upload_requires_admin_token() {
+    let storage = tempfile::tempdir().unwrap();
+    let app = create_app(&storage, 1024).await;
+
+    let response = app
+        .oneshot(upload_request(form("file", "photo.png", "image/png", PNG), None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_upload_rejects_invalid_files() {
+    let storage = tempfile::tempdir().unwrap();
+    let app = create_app(&storage, 16).await;
+
+    // Trop volumineux
+    let response = app
+        .clone()
+        .oneshot(upload_request(form("file", "big.txt", "text/plain", &[b'a'; 64]), Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Type non autorisé
+    let response = app
+        .clone()
+        .oneshot(upload_request(form("file", "run.sh", "application/x-sh", b"#!/bin/sh"), Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    // Contenu qui ne correspond pas au type déclaré
+    let response = app
+        .clone()
+        .oneshot(upload_request(form("file", "fake.png", "image/png", b"<html>"), Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    // Champ `file` absent
+    let response = app
+        .oneshot(upload_request(form("other", "photo.png", "image/png", PNG), Some("secret")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Aucun fichier orphelin ne reste dans le stockage
+    assert!(std::fs::read_dir(storage.path()).unwrap().next().is_none());
+}
+
+#[tokio::test]
+async fn test_malformed_upload_id_is_rejected() {
+    let storage = tempfile::tempdir().unwrap();
+    let app = create_app(&storage, 1024).await;
+
+    let response = app
+        .oneshot(Request::builder().uri("/api/uploads/not-a-uuid").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json(response).await["code"], "INVALID_PATH_PARAMETER");
+}
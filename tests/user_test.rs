@@ -0,0 +1,196 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{config::Config, db::DatabaseManager, routes::create_router, state::AppState};
+use uuid::Uuid;
+
+async fn create_app() -> Router {
+    let mut config = Config::default();
+    config.admin.token = Some("secret".to_string());
+
+    let mut db = DatabaseManager::new();
+    db.connect(&config).await.expect("Failed to connect to database");
+    create_router(AppState::new(db, config))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .header(header::CONTENT_TYPE, "application/json");
+    let body = body.map(|body| Body::from(body.to_string())).unwrap_or_default();
+    let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_slice(&body).unwrap() };
+    (status, body)
+}
+
+/// Email propre à chaque test, pour ne pas dépendre du contenu de la table
+fn unique_email(prefix: &str) -> String {
+    format!("{}-{}@example.com", prefix, Uuid::new_v4().simple())
+}
+
+#[tokio::test]
+async fn test_user_crud() {
+    let app = create_app().await;
+    let email = unique_email("ada");
+
+    let (status, body) = send(&app, "POST", "/api/users", Some(json!({ "email": email, "name": "Ada" }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = body["data"]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["email"], email.as_str());
+    assert_eq!(body["links"]["self"], format!("/api/users/{}", id));
+
+    let (status, body) = send(&app, "GET", &format!("/api/users/{}?fields=name", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({ "name": "Ada" }));
+
+    let (status, body) = send(&app, "PATCH", &format!("/api/users/{}", id), Some(json!({ "name": "Ada Lovelace" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Ada Lovelace");
+    assert_eq!(body["data"]["email"], email.as_str());
+
+    let (status, body) = send(&app, "GET", &format!("/api/users?filter[email]={}", email), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["id"], id);
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/users/{}", id), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = send(&app, "GET", &format!("/api/users/{}", id), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "USER_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_create_user_validation_and_conflict() {
+    let app = create_app().await;
+
+    let (status, body) = send(&app, "POST", "/api/users", Some(json!({ "email": "nope", "name": "" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let fields: Vec<&str> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["email", "name"]);
+
+    let email = unique_email("dup");
+    let (status, body) = send(&app, "POST", "/api/users", Some(json!({ "email": email, "name": "First" }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, conflict) = send(&app, "POST", "/api/users", Some(json!({ "email": email, "name": "Second" }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(conflict["code"], "EMAIL_ALREADY_USED");
+
+    send(&app, "DELETE", &format!("/api/users/{}", body["data"]["id"]), None).await;
+}
+
+#[tokio::test]
+async fn test_users_require_admin_token() {
+    let app = create_app().await;
+    let response = app
+        .oneshot(Request::builder().uri("/api/users").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_malformed_user_id() {
+    let app = create_app().await;
+    let (status, body) = send(&app, "GET", "/api/users/abc", None).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "INVALID_PATH_PARAMETER");
+}
+
+#[tokio::test]
+async fn test_batch_create_reports_each_item() {
+    let app = create_app().await;
+    let email = unique_email("batch");
+    let items = json!([
+        { "email": email, "name": "Ada" },
+        { "email": unique_email("batch"), "name": "Alan" },
+        { "email": "nope", "name": "Invalid" },
+        { "email": email, "name": "Duplicate" }
+    ]);
+
+    let (status, body) = send(&app, "POST", "/api/users:batchCreate", Some(json!({ "items": items }))).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(body["succeeded"], 2);
+    assert_eq!(body["failed"], 2);
+
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results[0]["status"], 201);
+    assert_eq!(results[0]["data"]["name"], "Ada");
+    assert_eq!(results[2]["index"], 2);
+    assert_eq!(results[2]["status"], 422);
+    assert_eq!(results[2]["error"]["code"], "VALIDATION_FAILED");
+    assert_eq!(results[3]["status"], 409);
+    assert_eq!(results[3]["error"]["code"], "EMAIL_ALREADY_USED");
+    assert!(results[3].get("data").is_none());
+
+    // Les éléments refusés n'empêchent pas l'écriture des autres
+    let ids: Vec<Value> = results[..2].iter().map(|result| result["data"]["id"].clone()).collect();
+    let (status, body) = send(&app, "GET", &format!("/api/users/{}", ids[1]), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Alan");
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/users:batchUpdate",
+        Some(json!({ "items": [{ "id": ids[0], "name": "Ada L." }, { "id": 999999999, "name": "Ghost" }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(body["results"][0]["data"]["name"], "Ada L.");
+    assert_eq!(body["results"][1]["error"]["code"], "USER_NOT_FOUND");
+
+    let (status, body) = send(&app, "POST", "/api/users:batchDelete", Some(json!({ "items": ids }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["succeeded"], 2);
+    assert_eq!(body["results"][0]["data"], ids[0]);
+}
+
+#[tokio::test]
+async fn test_batch_size_is_bounded() {
+    let app = create_app().await;
+
+    let (status, body) = send(&app, "POST", "/api/users:batchDelete", Some(json!({ "items": [] }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["field"], "items");
+
+    let ids: Vec<i64> = (1..=101).collect();
+    let (status, _) = send(&app, "POST", "/api/users:batchDelete", Some(json!({ "items": ids }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_user_text_is_sanitized_before_validation() {
+    let app = create_app().await;
+
+    let email = unique_email("clean");
+    let body = json!({ "email": format!("  {}\u{7}", email), "name": "<b>Ada</b>\u{202E} Lovelace<script>x()</script>" });
+    let (status, body) = send(&app, "POST", "/api/users", Some(body)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["email"], email);
+    assert_eq!(body["data"]["name"], "Ada Lovelace");
+
+    // Un nom fait uniquement de balises est vide une fois nettoyé
+    let (status, problem) = send(&app, "POST", "/api/users", Some(json!({ "email": unique_email("markup"), "name": "<i></i>" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(problem["errors"][0]["field"], "name");
+
+    send(&app, "DELETE", &format!("/api/users/{}", body["data"]["id"]), None).await;
+}
@@ -0,0 +1,101 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, SchemaStrictness},
+    db::DatabaseManager,
+    extractors::validated::ValidatedJson,
+    sanitize::Sanitize,
+    state::AppState,
+};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+struct Signup {
+    #[validate(email(message = "must be a valid email"))]
+    email: String,
+    #[validate(length(min = 8, message = "must be at least 8 characters"))]
+    password: String,
+}
+
+// Rien à nettoyer : un mot de passe doit rester tel quel
+impl Sanitize for Signup {}
+
+async fn signup(ValidatedJson(payload): ValidatedJson<Signup>) -> String {
+    payload.email
+}
+
+fn create_app(strictness: SchemaStrictness) -> Router {
+    let mut config = Config::default();
+    config.api.request_strictness = strictness;
+
+    Router::new()
+        .route("/signup", post(signup))
+        .with_state(AppState::new(DatabaseManager::new(), config))
+}
+
+async fn send(app: Router, body: &str) -> (StatusCode, Vec<u8>) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/signup")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec())
+}
+
+#[tokio::test]
+async fn test_valid_payload_is_accepted() {
+    let (status, body) = send(
+        create_app(SchemaStrictness::Lenient),
+        r#"{"email": "jane@example.com", "password": "correct horse"}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"jane@example.com");
+}
+
+#[tokio::test]
+async fn test_invalid_payload_lists_field_errors() {
+    let (status, body) = send(
+        create_app(SchemaStrictness::Lenient),
+        r#"{"email": "jane", "password": "short"}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "urn:problem:validation-error");
+    assert_eq!(
+        body["errors"],
+        serde_json::json!([
+            { "field": "email", "message": "must be a valid email" },
+            { "field": "password", "message": "must be at least 8 characters" },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_strictness_still_applies() {
+    let (status, body) = send(
+        create_app(SchemaStrictness::Strict),
+        r#"{"email": "jane@example.com", "password": "correct horse", "admin": true}"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["unknown_fields"], serde_json::json!(["admin"]));
+}
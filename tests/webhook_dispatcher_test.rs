@@ -0,0 +1,119 @@
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+use template_axum_sqlx_api::{
+    config::{Config, OutboundWebhooksConfig},
+    db::DatabaseManager,
+    handlers::webhooks::verify_signature,
+    services::webhooks::{backoff_delay, create_subscription, dispatch_due_deliveries, enqueue_event, get_delivery},
+};
+
+static TEST_MUTEX: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+/// Démarre un receveur local qui enregistre les requêtes reçues
+async fn start_receiver() -> (String, Received) {
+    let received: Received = Arc::default();
+    let store = received.clone();
+    let app = Router::new()
+        .route("/ok", post(move |headers: HeaderMap, body: Bytes| {
+            let store = store.clone();
+            async move {
+                store.lock().unwrap().push((headers, body));
+                StatusCode::OK
+            }
+        }))
+        .route("/fail", post(|| async { StatusCode::INTERNAL_SERVER_ERROR }));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{}", addr), received)
+}
+
+async fn connect() -> DatabaseManager {
+    let mut db = DatabaseManager::new();
+    db.connect(&Config::default()).await.expect("Failed to connect to database");
+    db
+}
+
+#[test]
+fn test_backoff_delay() {
+    assert_eq!(backoff_delay(1, 30, 3600), 30);
+    assert_eq!(backoff_delay(2, 30, 3600), 60);
+    assert_eq!(backoff_delay(4, 30, 3600), 240);
+    assert_eq!(backoff_delay(20, 30, 3600), 3600);
+}
+
+#[tokio::test]
+async fn test_dispatch_delivers_signed_payload() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+    let pool = db.get_pool();
+    let (base_url, received) = start_receiver().await;
+
+    let event_type = format!("test.delivered.{}", uuid::Uuid::new_v4());
+    create_subscription(pool, &format!("{}/ok", base_url), "secret", std::slice::from_ref(&event_type))
+        .await
+        .unwrap();
+    let created = enqueue_event(pool, &event_type, &serde_json::json!({ "answer": 42 })).await.unwrap();
+    assert_eq!(created, 1);
+
+    let client = reqwest::Client::new();
+    dispatch_due_deliveries(pool, &client, &OutboundWebhooksConfig::default()).await.unwrap();
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    let (headers, body) = &received[0];
+    let timestamp = headers["x-webhook-timestamp"].to_str().unwrap();
+    let signature = headers["x-webhook-signature"].to_str().unwrap().trim_start_matches("sha256=");
+    let signed = [format!("{}.", timestamp).as_bytes(), body.as_ref()].concat();
+    assert!(verify_signature(b"secret", &signed, signature));
+
+    let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(body["event"], event_type);
+    assert_eq!(body["data"]["answer"], 42);
+
+    let delivery_id = body["id"].as_i64().unwrap();
+    let delivery = get_delivery(pool, delivery_id).await.unwrap().unwrap();
+    assert_eq!(delivery.status, "delivered");
+    assert_eq!(delivery.attempts, 1);
+}
+
+#[tokio::test]
+async fn test_dispatch_dead_letters_after_max_attempts() {
+    let _lock = TEST_MUTEX.lock().await;
+    let db = connect().await;
+    let pool = db.get_pool();
+    let (base_url, _) = start_receiver().await;
+
+    let event_type = format!("test.failing.{}", uuid::Uuid::new_v4());
+    let subscription = create_subscription(pool, &format!("{}/fail", base_url), "secret", std::slice::from_ref(&event_type))
+        .await
+        .unwrap();
+    enqueue_event(pool, &event_type, &serde_json::json!({})).await.unwrap();
+
+    let settings = OutboundWebhooksConfig {
+        max_attempts: 1,
+        ..OutboundWebhooksConfig::default()
+    };
+    dispatch_due_deliveries(pool, &reqwest::Client::new(), &settings).await.unwrap();
+
+    let (status, attempts, last_error): (String, i32, Option<String>) = sqlx::query_as(
+        "SELECT status, attempts, last_error FROM webhook_deliveries WHERE subscription_id = $1",
+    )
+    .bind(subscription.id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "dead");
+    assert_eq!(attempts, 1);
+    assert!(last_error.unwrap().contains("500"));
+}
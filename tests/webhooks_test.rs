@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use tower::ServiceExt;
+use template_axum_sqlx_api::{
+    config::{Config, WebhookProviderConfig},
+    db::DatabaseManager,
+    handlers::webhooks::sign_payload,
+    routes::create_router,
+    state::AppState,
+};
+
+const SECRET: &str = "test-secret";
+
+fn create_app() -> Router {
+    let mut config = Config::default();
+    config.webhooks.providers.insert(
+        "example".to_string(),
+        WebhookProviderConfig {
+            secret: SECRET.to_string(),
+            signature_header: "x-signature".to_string(),
+            signature_prefix: "sha256=".to_string(),
+            timestamp_header: Some("x-timestamp".to_string()),
+            delivery_id_header: Some("x-delivery-id".to_string()),
+            tolerance_seconds: 300,
+        },
+    );
+    create_router(AppState::new(DatabaseManager::new(), config))
+}
+
+fn webhook_request(body: &str, timestamp: i64, signature: &str, delivery_id: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/api/webhooks/example")
+        .header("x-signature", format!("sha256={}", signature))
+        .header("x-timestamp", timestamp.to_string())
+        .header("x-delivery-id", delivery_id)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn sign(body: &str, timestamp: i64) -> String {
+    sign_payload(SECRET.as_bytes(), format!("{}.{}", timestamp, body).as_bytes())
+}
+
+#[tokio::test]
+async fn test_webhook_valid_signature() {
+    let app = create_app();
+    let body = r#"{"event":"ping","data":{}}"#;
+    let timestamp = Utc::now().timestamp();
+
+    let response = app
+        .oneshot(webhook_request(body, timestamp, &sign(body, timestamp), "delivery-1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_webhook_invalid_signature() {
+    let app = create_app();
+    let body = r#"{"event":"ping"}"#;
+    let timestamp = Utc::now().timestamp();
+
+    let response = app
+        .oneshot(webhook_request(body, timestamp, &sign("tampered", timestamp), "delivery-1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_webhook_stale_timestamp() {
+    let app = create_app();
+    let body = r#"{"event":"ping"}"#;
+    let timestamp = Utc::now().timestamp() - 3600;
+
+    let response = app
+        .oneshot(webhook_request(body, timestamp, &sign(body, timestamp), "delivery-1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_webhook_replay_rejected() {
+    let app = create_app();
+    let body = r#"{"event":"ping"}"#;
+    let timestamp = Utc::now().timestamp();
+    let signature = sign(body, timestamp);
+
+    let first = app
+        .clone()
+        .oneshot(webhook_request(body, timestamp, &signature, "delivery-1"))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::NO_CONTENT);
+
+    let replay = app
+        .oneshot(webhook_request(body, timestamp, &signature, "delivery-1"))
+        .await
+        .unwrap();
+    assert_eq!(replay.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_webhook_unknown_provider() {
+    let app = create_app();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/webhooks/unknown")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}